@@ -6,11 +6,12 @@
 package org.rust.clion.profiler
 
 import com.intellij.execution.configurations.RunProfile
+import com.intellij.execution.runners.ExecutionEnvironment
 import com.intellij.openapi.util.SystemInfo
 import com.intellij.profiler.clion.ProfilerExecutor
+import org.rust.cargo.runconfig.CargoRunStateBase
 import org.rust.cargo.runconfig.RsExecutableRunner
-
-private const val ERROR_MESSAGE_TITLE: String = "Unable to run profiler"
+import org.rust.clion.profiler.RsProfilerRunnerUtils.ERROR_MESSAGE_TITLE
 
 class RsProfilerRunner : RsExecutableRunner(ProfilerExecutor.EXECUTOR_ID, ERROR_MESSAGE_TITLE) {
     override fun getRunnerId(): String = RUNNER_ID
@@ -18,6 +19,11 @@ class RsProfilerRunner : RsExecutableRunner(ProfilerExecutor.EXECUTOR_ID, ERROR_
     override fun canRun(executorId: String, profile: RunProfile): Boolean =
         (SystemInfo.isMac || SystemInfo.isLinux) && super.canRun(executorId, profile)
 
+    override fun execute(environment: ExecutionEnvironment) {
+        RsProfilerRunnerUtils.enableDebugInfo(environment, environment.state as CargoRunStateBase)
+        super.execute(environment)
+    }
+
     companion object {
         const val RUNNER_ID: String = "RsProfilerRunner"
     }
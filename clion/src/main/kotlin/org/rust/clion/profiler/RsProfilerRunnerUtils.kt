@@ -0,0 +1,83 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.clion.profiler
+
+import com.google.common.annotations.VisibleForTesting
+import com.intellij.execution.configuration.EnvironmentVariablesData
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.notification.NotificationType
+import com.intellij.util.text.SemVer
+import org.rust.cargo.runconfig.CargoRunStateBase
+import org.rust.cargo.runconfig.buildtool.CargoPatch
+import org.rust.cargo.runconfig.buildtool.cargoPatches
+import org.rust.cargo.toolchain.CargoCommandLine
+import org.rust.cargo.util.CargoArgsParser.Companion.parseArgs
+import org.rust.ide.notifications.showBalloon
+
+object RsProfilerRunnerUtils {
+    const val ERROR_MESSAGE_TITLE: String = "Unable to run profiler"
+
+    /** Profile overrides via `CARGO_PROFILE_<name>_<key>` variables are available since 1.43 */
+    private val MIN_PROFILE_ENV_VERSION: SemVer = SemVer("1.43.0", 1, 43, 0)
+    /** `strip` profile option is stable since 1.59 */
+    private val MIN_STRIP_VERSION: SemVer = SemVer("1.59.0", 1, 59, 0)
+
+    private val BUILTIN_PROFILES: List<String> = listOf("dev", "release", "test", "bench")
+
+    /**
+     * Without debug info, the profiler can show neither call stacks nor source positions,
+     * and `release` and `bench` profiles don't have debug info by default.
+     * So we force it for the build of profiled binary (including test and bench harnesses)
+     */
+    fun enableDebugInfo(environment: ExecutionEnvironment, state: CargoRunStateBase) {
+        val rustcVersion = state.cargoProject?.rustcInfo?.version?.semver
+        if (rustcVersion == null || rustcVersion < MIN_PROFILE_ENV_VERSION) {
+            if (state.commandLine.isOptimized) {
+                environment.project.showBalloon(
+                    "Can't enable debug info for the profiled binary, call stacks may be incomplete. " +
+                        "Add <code>debug = true</code> to the profile in <code>Cargo.toml</code> manually",
+                    NotificationType.WARNING
+                )
+            }
+            return
+        }
+
+        val patch = createDebugInfoPatch(disableStrip = rustcVersion >= MIN_STRIP_VERSION)
+        state.addCommandLinePatch(patch)
+        environment.cargoPatches.add(patch)
+    }
+
+    /** Variables that are already set by the user (e.g. `CARGO_PROFILE_RELEASE_DEBUG=1`) are kept as is */
+    @VisibleForTesting
+    fun createDebugInfoPatch(disableStrip: Boolean): CargoPatch = { commandLine ->
+        val profiles = BUILTIN_PROFILES + listOfNotNull(commandLine.customProfile)
+        val debugInfoVariables = profiles.flatMap { profile ->
+            val prefix = "CARGO_PROFILE_${profile.toUpperCase().replace('-', '_')}"
+            listOfNotNull(
+                "${prefix}_DEBUG" to "true",
+                if (disableStrip) "${prefix}_STRIP" to "false" else null
+            )
+        }
+        val oldVariables = commandLine.environmentVariables
+        val environmentVariables = EnvironmentVariablesData.create(
+            debugInfoVariables.toMap() + oldVariables.envs,
+            oldVariables.isPassParentEnvs
+        )
+        commandLine.copy(environmentVariables = environmentVariables)
+    }
+
+    private val CargoCommandLine.isOptimized: Boolean
+        get() = command == "bench" || "--release" in parseArgs(command, additionalArguments).commandArguments
+
+    /** `--profile foo` or `--profile=foo` */
+    private val CargoCommandLine.customProfile: String?
+        get() {
+            val commandArguments = parseArgs(command, additionalArguments).commandArguments
+            val index = commandArguments.indexOf("--profile")
+            if (index != -1) return commandArguments.getOrNull(index + 1)
+            return commandArguments.find { it.startsWith("--profile=") }?.removePrefix("--profile=")
+        }
+}
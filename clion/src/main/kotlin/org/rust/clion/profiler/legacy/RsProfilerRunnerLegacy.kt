@@ -6,12 +6,17 @@
 package org.rust.clion.profiler.legacy
 
 import com.intellij.execution.configurations.RunProfile
+import com.intellij.execution.configurations.RunProfileState
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.execution.ui.RunContentDescriptor
 import com.intellij.openapi.util.SystemInfo
 import com.intellij.profiler.clion.ProfilerExecutor
+import org.jetbrains.concurrency.Promise
+import org.rust.cargo.runconfig.CargoRunStateBase
 import org.rust.cargo.runconfig.buildtool.CargoBuildManager.isBuildToolWindowEnabled
 import org.rust.cargo.runconfig.legacy.RsAsyncRunner
-
-private const val ERROR_MESSAGE_TITLE: String = "Unable to run profiler"
+import org.rust.clion.profiler.RsProfilerRunnerUtils
+import org.rust.clion.profiler.RsProfilerRunnerUtils.ERROR_MESSAGE_TITLE
 
 /**
  * This runner is used if [isBuildToolWindowEnabled] is false.
@@ -22,6 +27,11 @@ class RsProfilerRunnerLegacy : RsAsyncRunner(ProfilerExecutor.EXECUTOR_ID, ERROR
     override fun canRun(executorId: String, profile: RunProfile): Boolean =
         (SystemInfo.isMac || SystemInfo.isLinux) && super.canRun(executorId, profile)
 
+    override fun execute(environment: ExecutionEnvironment, state: RunProfileState): Promise<RunContentDescriptor?> {
+        RsProfilerRunnerUtils.enableDebugInfo(environment, state as CargoRunStateBase)
+        return super.execute(environment, state)
+    }
+
     companion object {
         const val RUNNER_ID: String = "RsProfilerRunnerLegacy"
     }
@@ -5,6 +5,7 @@
 
 package org.rust.clion.profiler.perf
 
+import com.google.common.annotations.VisibleForTesting
 import com.intellij.execution.ExecutionException
 import com.intellij.execution.configurations.GeneralCommandLine
 import com.intellij.execution.configurations.RunnerSettings
@@ -106,8 +107,29 @@ class RsPerfConfigurationExtension : CargoCommandConfigurationExtension() {
             defaultArgs: List<String>,
             outputPath: String
         ): GeneralCommandLine = this.apply {
-            parametersList.prependAll("record", "--freq=$samplingFrequency", *defaultArgs.toTypedArray(), "-o", outputPath, exePath)
+            parametersList.prependAll(*createPerfRecordArgs(samplingFrequency, defaultArgs, outputPath, exePath))
             exePath = perfPath
         }
+
+        @VisibleForTesting
+        fun createPerfRecordArgs(
+            samplingFrequency: Int,
+            defaultArgs: List<String>,
+            outputPath: String,
+            exePath: String
+        ): Array<String> {
+            // Rust binaries are usually built without frame pointers, so DWARF is the only way to unwind their stacks
+            val hasCallGraphArg = defaultArgs.any { it == "-g" || it.startsWith("--call-graph") }
+            val callGraphArgs = if (hasCallGraphArg) emptyArray<String>() else arrayOf("--call-graph", "dwarf")
+            return arrayOf(
+                "record",
+                "--freq=$samplingFrequency",
+                *callGraphArgs,
+                *defaultArgs.toTypedArray(),
+                "-o",
+                outputPath,
+                exePath
+            )
+        }
     }
 }
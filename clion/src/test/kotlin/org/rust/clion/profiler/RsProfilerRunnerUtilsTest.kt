@@ -0,0 +1,63 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.clion.profiler
+
+import com.intellij.execution.configuration.EnvironmentVariablesData
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertTrue
+import org.junit.Test
+import org.rust.cargo.toolchain.CargoCommandLine
+import java.nio.file.Paths
+
+class RsProfilerRunnerUtilsTest {
+    @Test
+    fun `test debug info variables`() {
+        val envs = patchEnvs(emptyMap())
+        assertEquals("true", envs["CARGO_PROFILE_RELEASE_DEBUG"])
+        assertEquals("true", envs["CARGO_PROFILE_BENCH_DEBUG"])
+        assertEquals("false", envs["CARGO_PROFILE_RELEASE_STRIP"])
+    }
+
+    @Test
+    fun `test no strip variables for old rustc`() {
+        val envs = patchEnvs(emptyMap(), disableStrip = false)
+        assertEquals("true", envs["CARGO_PROFILE_RELEASE_DEBUG"])
+        assertTrue(envs.keys.none { it.endsWith("_STRIP") })
+    }
+
+    @Test
+    fun `test custom profile`() {
+        val envs = patchEnvs(emptyMap(), "--profile", "release-lto")
+        assertEquals("true", envs["CARGO_PROFILE_RELEASE_LTO_DEBUG"])
+    }
+
+    @Test
+    fun `test user variables are kept`() {
+        val envs = patchEnvs(mapOf(
+            "RUSTFLAGS" to "-C target-cpu=native",
+            "CARGO_PROFILE_RELEASE_DEBUG" to "1"
+        ))
+        assertEquals("-C target-cpu=native", envs["RUSTFLAGS"])
+        assertEquals("1", envs["CARGO_PROFILE_RELEASE_DEBUG"])
+        assertEquals("true", envs["CARGO_PROFILE_BENCH_DEBUG"])
+    }
+
+    private fun patchEnvs(
+        envs: Map<String, String>,
+        vararg args: String,
+        disableStrip: Boolean = true
+    ): Map<String, String> {
+        val commandLine = CargoCommandLine(
+            "build",
+            Paths.get("/my-crate"),
+            args.toList(),
+            environmentVariables = EnvironmentVariablesData.create(envs, true)
+        )
+        val patched = RsProfilerRunnerUtils.createDebugInfoPatch(disableStrip)(commandLine)
+        assertTrue(patched.environmentVariables.isPassParentEnvs)
+        return patched.environmentVariables.envs
+    }
+}
@@ -0,0 +1,52 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.clion.profiler.perf
+
+import com.intellij.util.execution.ParametersListUtil
+import org.junit.Assert.assertEquals
+import org.junit.Test
+
+class RsPerfConfigurationExtensionTest {
+    @Test
+    fun `test dwarf call graph`() = doTest(
+        "",
+        "record --freq=1000 --call-graph dwarf -o out.perf app"
+    )
+
+    @Test
+    fun `test user call graph`() = doTest(
+        "--call-graph fp",
+        "record --freq=1000 --call-graph fp -o out.perf app"
+    )
+
+    @Test
+    fun `test user call graph with equals sign`() = doTest(
+        "--call-graph=lbr",
+        "record --freq=1000 --call-graph=lbr -o out.perf app"
+    )
+
+    @Test
+    fun `test user call graph short option`() = doTest(
+        "-g --no-inherit",
+        "record --freq=1000 -g --no-inherit -o out.perf app"
+    )
+
+    @Test
+    fun `test other user args`() = doTest(
+        "--no-inherit",
+        "record --freq=1000 --call-graph dwarf --no-inherit -o out.perf app"
+    )
+
+    private fun doTest(defaultArgs: String, expected: String) {
+        val args = RsPerfConfigurationExtension.createPerfRecordArgs(
+            1000,
+            ParametersListUtil.parse(defaultArgs),
+            "out.perf",
+            "app"
+        )
+        assertEquals(ParametersListUtil.parse(expected), args.toList())
+    }
+}
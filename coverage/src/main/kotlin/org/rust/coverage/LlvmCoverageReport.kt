@@ -0,0 +1,111 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.coverage
+
+import com.google.gson.JsonArray
+import com.google.gson.JsonObject
+import com.google.gson.JsonParser
+import java.io.File
+
+/**
+ * Line and branch coverage read from the output of `llvm-cov export -format=text`.
+ * See https://github.com/llvm/llvm-project/blob/main/llvm/tools/llvm-cov/CoverageExporterJson.cpp
+ */
+class LlvmCoverageReport {
+    private val info: MutableMap<String, FileCoverage> = hashMapOf()
+    val records: Set<Map.Entry<String, FileCoverage>> get() = info.entries
+
+    class FileCoverage {
+        /** Execution counts of instrumented lines by line numbers */
+        val lineHits: MutableMap<Int, Long> = sortedMapOf()
+        /** Branches by line numbers of their conditions */
+        val branches: MutableMap<Int, MutableList<BranchHits>> = sortedMapOf()
+    }
+
+    data class BranchHits(val trueHits: Long, val falseHits: Long)
+
+    /**
+     * A segment is a point where the execution count changes:
+     * `[line, column, count, hasCount, isRegionEntry, isGapRegion]`
+     */
+    private class Segment(
+        val line: Int,
+        val count: Long,
+        val hasCount: Boolean,
+        val isRegionEntry: Boolean,
+        val isGap: Boolean
+    ) {
+        val isStartOfRegion: Boolean get() = !isGap && hasCount && isRegionEntry
+    }
+
+    private fun mergeFileReport(filePath: String, segments: List<Segment>, branches: Map<Int, List<BranchHits>>) {
+        val fileCoverage = info.getOrPut(filePath) { FileCoverage() }
+        for ((line, hits) in computeLineHits(segments)) {
+            fileCoverage.lineHits.merge(line, hits) { old, new -> old + new }
+        }
+        for ((line, lineBranches) in branches) {
+            fileCoverage.branches.getOrPut(line) { mutableListOf() }.addAll(lineBranches)
+        }
+    }
+
+    companion object {
+        fun read(exportFile: File, toLocalPathConverter: (String) -> String = { it }): LlvmCoverageReport {
+            val report = LlvmCoverageReport()
+            val root = exportFile.bufferedReader().use { JsonParser.parseReader(it) } as? JsonObject ?: return report
+            val exports = root.getAsJsonArray("data") ?: return report
+            for (export in exports) {
+                val files = (export as? JsonObject)?.getAsJsonArray("files") ?: continue
+                for (file in files) {
+                    if (file !is JsonObject) continue
+                    val filePath = toLocalPathConverter(file.getAsJsonPrimitive("filename").asString)
+                    val segments = file.getAsJsonArray("segments")
+                        ?.mapNotNull { (it as? JsonArray)?.toSegment() }
+                        .orEmpty()
+                    val branches = file.getAsJsonArray("branches")
+                        ?.mapNotNull { it as? JsonArray }
+                        .orEmpty()
+                        .groupBy({ it[0].asInt }, { BranchHits(it[4].asLong, it[5].asLong) })
+                    report.mergeFileReport(filePath, segments, branches)
+                }
+            }
+            return report
+        }
+
+        private fun JsonArray.toSegment(): Segment? {
+            if (size() < 5) return null
+            val isGap = size() > 5 && get(5).asBoolean
+            return Segment(get(0).asInt, get(2).asLong, get(3).asBoolean, get(4).asBoolean, isGap)
+        }
+
+        /** The same algorithm as `LineCoverageStats` in LLVM uses */
+        private fun computeLineHits(segments: List<Segment>): Map<Int, Long> {
+            if (segments.isEmpty()) return emptyMap()
+            val segmentsByLine = segments.groupBy { it.line }
+            val lineHits = mutableMapOf<Int, Long>()
+            // The last segment of previous lines that spans the current line
+            var wrapped: Segment? = null
+            for (line in segments.first().line..segments.last().line) {
+                val lineSegments = segmentsByLine[line].orEmpty()
+                val first = lineSegments.firstOrNull()
+                val isStartOfSkippedRegion = first != null && !first.hasCount && first.isRegionEntry
+                val wrappedHits = wrapped?.takeIf { it.hasCount }?.count
+                val hasRegionStart = lineSegments.any { it.isStartOfRegion }
+                val isMapped = !isStartOfSkippedRegion && (wrappedHits != null || hasRegionStart)
+                if (isMapped) {
+                    var hits = wrappedHits ?: 0
+                    for (segment in lineSegments) {
+                        if (segment.isStartOfRegion) hits = maxOf(hits, segment.count)
+                    }
+                    lineHits[line] = hits
+                }
+                if (lineSegments.isNotEmpty()) {
+                    wrapped = lineSegments.last()
+                }
+            }
+            return lineHits
+        }
+    }
+}
@@ -7,6 +7,8 @@ package org.rust.coverage
 
 import com.intellij.codeEditor.printing.ExportToHTMLSettings
 import com.intellij.coverage.*
+import com.intellij.coverage.view.CoverageListNode
+import com.intellij.coverage.view.CoverageListRootNode
 import com.intellij.coverage.view.CoverageViewExtension
 import com.intellij.coverage.view.CoverageViewManager
 import com.intellij.coverage.view.DirectoryCoverageViewExtension
@@ -28,8 +30,11 @@ import com.intellij.openapi.vfs.VfsUtil
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiFile
+import com.intellij.psi.PsiManager
 import com.intellij.rt.coverage.data.ClassData
 import com.intellij.util.ui.ColumnInfo
+import org.rust.cargo.project.model.cargoProjects
+import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.cargo.runconfig.command.CargoCommandConfiguration
 import org.rust.coverage.LcovCoverageReport.Serialization.writeLcov
 import org.rust.lang.RsFileType
@@ -93,8 +98,12 @@ class RsCoverageEngine : CoverageEngine() {
                 return arrayOf(files, percentage)
             }
 
-            override fun getChildrenNodes(node: AbstractTreeNode<*>): List<AbstractTreeNode<*>> =
-                super.getChildrenNodes(node).filter { child ->
+            override fun getChildrenNodes(node: AbstractTreeNode<*>): List<AbstractTreeNode<*>> {
+                if (node is CoverageListRootNode) {
+                    val crateNodes = getCrateNodes()
+                    if (crateNodes.size > 1) return crateNodes
+                }
+                return super.getChildrenNodes(node).filter { child ->
                     val value = child.value
                     if (value is PsiFile) {
                         value.fileType == RsFileType
@@ -102,6 +111,18 @@ class RsCoverageEngine : CoverageEngine() {
                         child.name != Project.DIRECTORY_STORE_FOLDER
                     }
                 }
+            }
+
+            /** Shows workspace members on the top level, so percentages are displayed per crate */
+            private fun getCrateNodes(): List<AbstractTreeNode<*>> {
+                val psiManager = PsiManager.getInstance(project)
+                return project.cargoProjects.allProjects
+                    .flatMap { it.workspace?.packages.orEmpty() }
+                    .filter { it.origin == PackageOrigin.WORKSPACE }
+                    .mapNotNull { pkg -> pkg.contentRoot?.let { psiManager.findDirectory(it) } }
+                    .distinct()
+                    .map { CoverageListNode(project, it, suiteBundle, stateBean) }
+            }
         }
 
     override fun recompileProjectAndRerunAction(
@@ -0,0 +1,194 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.coverage
+
+import com.intellij.coverage.CoverageExecutor
+import com.intellij.coverage.CoverageHelper
+import com.intellij.coverage.CoverageRunnerData
+import com.intellij.execution.ExecutionException
+import com.intellij.execution.configuration.EnvironmentVariablesData
+import com.intellij.execution.configurations.*
+import com.intellij.execution.configurations.coverage.CoverageEnabledConfiguration
+import com.intellij.execution.process.OSProcessHandler
+import com.intellij.execution.process.ProcessAdapter
+import com.intellij.execution.process.ProcessEvent
+import com.intellij.execution.process.ProcessOutputTypes
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.execution.ui.RunContentDescriptor
+import com.intellij.notification.NotificationType
+import com.intellij.openapi.diagnostic.Logger
+import com.intellij.openapi.util.Key
+import com.intellij.openapi.util.io.FileUtil
+import org.rust.cargo.CargoConstants.ProjectLayout
+import org.rust.cargo.runconfig.CargoRunStateBase
+import org.rust.cargo.runconfig.RsDefaultProgramRunnerBase
+import org.rust.cargo.runconfig.buildtool.CargoBuildManager.getBuildConfiguration
+import org.rust.cargo.runconfig.buildtool.CargoBuildManager.isBuildConfiguration
+import org.rust.cargo.runconfig.buildtool.CargoPatch
+import org.rust.cargo.runconfig.buildtool.cargoPatches
+import org.rust.cargo.runconfig.command.CargoCommandConfiguration
+import org.rust.cargo.toolchain.LlvmTools
+import org.rust.cargo.toolchain.Rustup.Companion.checkNeedInstallLlvmTools
+import org.rust.ide.actions.InstallComponentAction
+import org.rust.ide.notifications.showBalloon
+import org.rust.openapiext.execute
+import org.rust.stdext.toPath
+import java.nio.file.Files
+import java.nio.file.Path
+import kotlin.streams.toList
+
+/**
+ * Runs cargo commands with `-C instrument-coverage`.
+ * When the process is terminated, raw profiles of all instrumented processes are merged with `llvm-profdata`
+ * and exported with `llvm-cov` into the coverage file that is read by [RsCoverageRunner]
+ */
+class RsCoverageProgramRunner : RsDefaultProgramRunnerBase() {
+    override fun getRunnerId(): String = RUNNER_ID
+
+    override fun canRun(executorId: String, profile: RunProfile): Boolean {
+        if (executorId != CoverageExecutor.EXECUTOR_ID || profile !is CargoCommandConfiguration ||
+            profile.clean() !is CargoCommandConfiguration.CleanConfiguration.Ok) return false
+        return !isBuildConfiguration(profile) && getBuildConfiguration(profile) != null
+    }
+
+    override fun createConfigurationData(settingsProvider: ConfigurationInfoProvider): RunnerSettings {
+        return CoverageRunnerData()
+    }
+
+    override fun execute(environment: ExecutionEnvironment) {
+        val state = environment.state as CargoRunStateBase
+        val workingDirectory = state.commandLine.workingDirectory
+
+        if (checkNeedInstallLlvmTools(environment.project, workingDirectory)) return
+
+        val profilesDirectory = getProfilesDirectory(workingDirectory)
+        val cargoCoveragePatch = createCargoCoveragePatch(profilesDirectory)
+        state.addCommandLinePatch(cargoCoveragePatch)
+        environment.cargoPatches.add(cargoCoveragePatch)
+
+        cleanOldCoverageData(profilesDirectory)
+
+        super.execute(environment)
+    }
+
+    override fun doExecute(state: RunProfileState, environment: ExecutionEnvironment): RunContentDescriptor? {
+        val descriptor = super.doExecute(state, environment)
+        descriptor?.processHandler?.addProcessListener(object : ProcessAdapter() {
+            override fun processTerminated(event: ProcessEvent) {
+                startCollectingCoverage(state as CargoRunStateBase, environment)
+            }
+        })
+        return descriptor
+    }
+
+    companion object {
+        private val LOG: Logger = Logger.getInstance(RsCoverageProgramRunner::class.java)
+
+        const val RUNNER_ID: String = "RsCoverageProgramRunner"
+
+        private const val PROFRAW_EXTENSION: String = "profraw"
+        private const val RUSTFLAGS: String = "RUSTFLAGS"
+
+        private fun getProfilesDirectory(workingDirectory: Path): Path =
+            workingDirectory.resolve(ProjectLayout.target).resolve("coverage")
+
+        private fun createCargoCoveragePatch(profilesDirectory: Path): CargoPatch = { commandLine ->
+            val oldVariables = commandLine.environmentVariables
+            val rustflags = listOfNotNull(oldVariables.envs[RUSTFLAGS], "-C instrument-coverage").joinToString(" ")
+            val environmentVariables = EnvironmentVariablesData.create(
+                oldVariables.envs + mapOf(
+                    RUSTFLAGS to rustflags,
+                    // `%p` is a process id and `%m` is a binary signature,
+                    // so test binaries and processes spawned by them don't overwrite profiles of each other
+                    "LLVM_PROFILE_FILE" to profilesDirectory.resolve("%p-%m.$PROFRAW_EXTENSION").toString()
+                ),
+                oldVariables.isPassParentEnvs
+            )
+            commandLine.copy(environmentVariables = environmentVariables)
+        }
+
+        /** Removes profiles of previous runs. Otherwise, they would be merged into the new report */
+        private fun cleanOldCoverageData(profilesDirectory: Path) {
+            FileUtil.delete(profilesDirectory.toFile())
+        }
+
+        private fun startCollectingCoverage(state: CargoRunStateBase, environment: ExecutionEnvironment) {
+            val project = environment.project
+            val runConfiguration = environment.runProfile as? RunConfigurationBase<*> ?: return
+            val runnerSettings = environment.runnerSettings ?: return
+            val workingDirectory = state.commandLine.workingDirectory
+
+            val coverageEnabledConfiguration = CoverageEnabledConfiguration.getOrCreate(runConfiguration)
+                as? RsCoverageEnabledConfiguration ?: return
+            val coverageFilePath = coverageEnabledConfiguration.coverageFilePath?.toPath() ?: return
+
+            val llvmTools = state.toolchain.llvmTools(workingDirectory)
+            if (llvmTools == null) {
+                project.showBalloon(
+                    "Could not find llvm-profdata and llvm-cov in the toolchain sysroot",
+                    NotificationType.ERROR,
+                    InstallComponentAction(workingDirectory, LlvmTools.COMPONENT_NAME)
+                )
+                return
+            }
+
+            val profilesDirectory = getProfilesDirectory(workingDirectory)
+            val profraws = if (Files.isDirectory(profilesDirectory)) {
+                Files.list(profilesDirectory).use { files ->
+                    files.filter { it.fileName.toString().endsWith(".$PROFRAW_EXTENSION") }.toList()
+                }
+            } else {
+                emptyList()
+            }
+            if (profraws.isEmpty()) {
+                LOG.warn("No coverage profiles were written to $profilesDirectory")
+                return
+            }
+
+            try {
+                val profdata = profilesDirectory.resolve("merged.profdata")
+                llvmTools.createMergeCommandLine(workingDirectory, profraws, profdata)
+                    .execute(project, ignoreExitCode = false)
+
+                val executables = state.cargo().fetchExecutables(project, project, state.prepareCommandLine())
+                if (executables.isEmpty()) {
+                    LOG.warn("No instrumented executables were found")
+                    return
+                }
+
+                val exportCmd = llvmTools.createExportCommandLine(workingDirectory, profdata, executables)
+                val coverageProcess = OSProcessHandler(exportCmd)
+                coverageEnabledConfiguration.coverageProcess = coverageProcess
+                // Must be added before `CoverageHelper.attachToProcess`
+                // that loads the coverage file when the process is terminated
+                coverageProcess.addProcessListener(CoverageFileWriter(coverageFilePath))
+                CoverageHelper.attachToProcess(runConfiguration, coverageProcess, runnerSettings)
+                coverageProcess.startNotify()
+            } catch (e: ExecutionException) {
+                LOG.warn("Failed to collect coverage data", e)
+                project.showBalloon("Failed to collect coverage data: ${e.message}", NotificationType.ERROR)
+            }
+        }
+    }
+
+    /** Saves JSON printed by `llvm-cov export` to [coverageFilePath] */
+    private class CoverageFileWriter(private val coverageFilePath: Path) : ProcessAdapter() {
+        private val output: StringBuilder = StringBuilder()
+
+        override fun onTextAvailable(event: ProcessEvent, outputType: Key<*>) {
+            if (outputType == ProcessOutputTypes.STDOUT) {
+                output.append(event.text)
+            } else {
+                LOG.debug(event.text)
+            }
+        }
+
+        override fun processTerminated(event: ProcessEvent) {
+            Files.createDirectories(coverageFilePath.parent)
+            Files.write(coverageFilePath, output.toString().toByteArray())
+        }
+    }
+}
@@ -5,15 +5,16 @@
 
 package org.rust.coverage
 
+import com.google.common.annotations.VisibleForTesting
 import com.intellij.coverage.CoverageEngine
 import com.intellij.coverage.CoverageRunner
 import com.intellij.coverage.CoverageSuite
 import com.intellij.openapi.diagnostic.Logger
 import com.intellij.openapi.progress.ProgressManager
+import com.intellij.openapi.util.io.FileUtil
 import com.intellij.openapiext.isDispatchThread
 import com.intellij.rt.coverage.data.LineData
 import com.intellij.rt.coverage.data.ProjectData
-import org.rust.coverage.LcovCoverageReport.Serialization.readLcov
 import org.rust.openapiext.computeWithCancelableProgress
 import java.io.File
 import java.io.IOException
@@ -21,7 +22,7 @@ import java.io.IOException
 class RsCoverageRunner : CoverageRunner() {
     override fun getPresentableName(): String = "Rust"
 
-    override fun getDataFileExtension(): String = "info"
+    override fun getDataFileExtension(): String = "json"
 
     override fun getId(): String = "RsCoverageRunner"
 
@@ -63,19 +64,44 @@ class RsCoverageRunner : CoverageRunner() {
             }
 
             val projectData = ProjectData()
-            val report = readLcov(dataFile, coverageSuite.contextFilePath)
-            for ((filePath, lineHitsList) in report.records) {
+            val report = LlvmCoverageReport.read(dataFile, createPathMapper(coverageSuite.contextFilePath))
+            for ((filePath, fileCoverage) in report.records) {
                 val classData = projectData.getOrCreateClassData(filePath)
-                val max = lineHitsList.lastOrNull()?.lineNumber ?: 0
+                val max = fileCoverage.lineHits.keys.lastOrNull() ?: 0
                 val lines = arrayOfNulls<LineData>(max + 1)
-                for (lineHits in lineHitsList) {
-                    val lineData = LineData(lineHits.lineNumber, null)
-                    lineData.hits = lineHits.hits
-                    lines[lineHits.lineNumber] = lineData
+                for ((lineNumber, hits) in fileCoverage.lineHits) {
+                    val lineData = LineData(lineNumber, null)
+                    lineData.hits = hits.coerceAtMost(Int.MAX_VALUE.toLong()).toInt()
+                    fileCoverage.branches[lineNumber]?.forEachIndexed { index, branch ->
+                        val jumpData = lineData.addJump(index)
+                        jumpData.trueHits = branch.trueHits.coerceAtMost(Int.MAX_VALUE.toLong()).toInt()
+                        jumpData.falseHits = branch.falseHits.coerceAtMost(Int.MAX_VALUE.toLong()).toInt()
+                    }
+                    lineData.fillArrays()
+                    lines[lineNumber] = lineData
                 }
                 classData.setLines(lines)
             }
             return projectData
         }
+
+        /**
+         * rustc writes canonical paths of source files into the coverage mapping,
+         * so the paths should be mapped back if the workspace is opened through a symlink
+         */
+        @VisibleForTesting
+        fun createPathMapper(contextFilePath: String?): (String) -> String {
+            val workspaceRoot = contextFilePath?.let { FileUtil.toSystemIndependentName(it) }
+            val canonicalRoot = contextFilePath?.let { FileUtil.toSystemIndependentName(File(it).canonicalPath) }
+            return { path ->
+                val normalizedPath = FileUtil.toSystemIndependentName(path)
+                if (workspaceRoot != null && canonicalRoot != null && canonicalRoot != workspaceRoot &&
+                    FileUtil.startsWith(normalizedPath, canonicalRoot)) {
+                    workspaceRoot + normalizedPath.substring(canonicalRoot.length)
+                } else {
+                    normalizedPath
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,112 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.coverage
+
+import org.intellij.lang.annotations.Language
+import org.junit.Assert.assertEquals
+import org.junit.Rule
+import org.junit.Test
+import org.junit.rules.TemporaryFolder
+import org.rust.coverage.LlvmCoverageReport.BranchHits
+
+class LlvmCoverageReportTest {
+    @get:Rule
+    val tempDir: TemporaryFolder = TemporaryFolder()
+
+    /**
+     * ```
+     * 1 fn main() {
+     * 2     if x {
+     * 3         foo();
+     * 4     }
+     * 5 }
+     * ```
+     */
+    @Test
+    fun `line hits`() = doTest("""
+        {"data": [{"files": [{
+            "filename": "/src/main.rs",
+            "segments": [
+                [1, 11, 1, true, true, false],
+                [2, 8, 1, true, true, false],
+                [2, 10, 0, true, true, false],
+                [4, 6, 1, true, false, false],
+                [5, 2, 0, false, false, false]
+            ]
+        }]}]}
+    """, mapOf("/src/main.rs" to mapOf(1 to 1L, 2 to 1L, 3 to 0L, 4 to 0L, 5 to 1L)))
+
+    @Test
+    fun `skipped region`() = doTest("""
+        {"data": [{"files": [{
+            "filename": "/src/main.rs",
+            "segments": [
+                [1, 11, 2, true, true, false],
+                [2, 1, 0, false, false, false],
+                [4, 1, 0, false, true, false],
+                [5, 1, 0, false, false, false]
+            ]
+        }]}]}
+    """, mapOf("/src/main.rs" to mapOf(1 to 2L, 2 to 2L)))
+
+    @Test
+    fun `gap region does not affect line hits`() = doTest("""
+        {"data": [{"files": [{
+            "filename": "/src/main.rs",
+            "segments": [
+                [1, 11, 1, true, true, false],
+                [1, 20, 5, true, true, true],
+                [1, 30, 0, false, false, false]
+            ]
+        }]}]}
+    """, mapOf("/src/main.rs" to mapOf(1 to 1L)))
+
+    @Test
+    fun `hits of several exports are merged`() = doTest("""
+        {"data": [
+            {"files": [{"filename": "/src/lib.rs", "segments": [[1, 1, 2, true, true, false], [1, 10, 0, false, false, false]]}]},
+            {"files": [{"filename": "/src/lib.rs", "segments": [[1, 1, 3, true, true, false], [1, 10, 0, false, false, false]]}]}
+        ]}
+    """, mapOf("/src/lib.rs" to mapOf(1 to 5L)))
+
+    @Test
+    fun branches() {
+        val report = readReport("""
+            {"data": [{"files": [{
+                "filename": "/src/main.rs",
+                "segments": [[1, 11, 1, true, true, false], [3, 2, 0, false, false, false]],
+                "branches": [[2, 8, 2, 9, 1, 0, 0, 0, 4], [2, 13, 2, 14, 0, 1, 0, 0, 4]]
+            }]}]}
+        """)
+        val coverage = report.records.single().value
+        assertEquals(mapOf(2 to listOf(BranchHits(1, 0), BranchHits(0, 1))), coverage.branches)
+    }
+
+    @Test
+    fun `file paths are converted`() {
+        val report = readReport("""
+            {"data": [{"files": [{"filename": "/real/src/main.rs", "segments": [[1, 1, 1, true, true, false]]}]}]}
+        """) { it.replace("/real", "/link") }
+        assertEquals(listOf("/link/src/main.rs"), report.records.map { it.key })
+    }
+
+    @Test
+    fun `empty report`() = doTest("""{"data": [{"files": []}]}""", emptyMap())
+
+    private fun doTest(@Language("JSON") json: String, expectedLineHits: Map<String, Map<Int, Long>>) {
+        val report = readReport(json)
+        assertEquals(expectedLineHits, report.records.associate { (path, coverage) -> path to coverage.lineHits })
+    }
+
+    private fun readReport(
+        @Language("JSON") json: String,
+        toLocalPathConverter: (String) -> String = { it }
+    ): LlvmCoverageReport {
+        val file = tempDir.newFile("coverage.json")
+        file.writeText(json.trimIndent())
+        return LlvmCoverageReport.read(file, toLocalPathConverter)
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.coverage
+
+import com.intellij.openapi.util.SystemInfo
+import com.intellij.openapi.util.io.FileUtil
+import org.junit.Assert.assertEquals
+import org.junit.Assume.assumeFalse
+import org.junit.Rule
+import org.junit.Test
+import org.junit.rules.TemporaryFolder
+import java.io.File
+import java.nio.file.Files
+
+class RsCoveragePathMapperTest {
+    @get:Rule
+    val tempDir: TemporaryFolder = TemporaryFolder()
+
+    @Test
+    fun `canonical paths are mapped to the symlinked workspace`() {
+        assumeFalse(SystemInfo.isWindows)
+        val realRoot = tempDir.newFolder("real")
+        val linkRoot = File(tempDir.root, "link")
+        Files.createSymbolicLink(linkRoot.toPath(), realRoot.toPath())
+
+        val mapper = RsCoverageRunner.createPathMapper(linkRoot.path)
+        val canonicalRoot = FileUtil.toSystemIndependentName(realRoot.canonicalPath)
+        assertEquals(
+            "${FileUtil.toSystemIndependentName(linkRoot.path)}/src/main.rs",
+            mapper("$canonicalRoot/src/main.rs")
+        )
+    }
+
+    @Test
+    fun `paths outside of the workspace are not changed`() {
+        assumeFalse(SystemInfo.isWindows)
+        val realRoot = tempDir.newFolder("real")
+        val linkRoot = File(tempDir.root, "link")
+        Files.createSymbolicLink(linkRoot.toPath(), realRoot.toPath())
+
+        val mapper = RsCoverageRunner.createPathMapper(linkRoot.path)
+        assertEquals("/rustc/library/std/src/lib.rs", mapper("/rustc/library/std/src/lib.rs"))
+    }
+
+    @Test
+    fun `paths are not changed without symlinks`() {
+        val root = FileUtil.toSystemIndependentName(tempDir.newFolder("workspace").canonicalPath)
+        val mapper = RsCoverageRunner.createPathMapper(root)
+        assertEquals("$root/src/main.rs", mapper("$root/src/main.rs"))
+    }
+
+    @Test
+    fun `paths are not changed without workspace`() {
+        val mapper = RsCoverageRunner.createPathMapper(null)
+        assertEquals("/src/main.rs", mapper("/src/main.rs"))
+    }
+}
@@ -12,6 +12,11 @@ def is_hashbrown_hashmap(hash_map):
     return len(hash_map.type.fields()) == 1
 
 
+def is_hashbrown_hashset(hash_set):
+    # Since rust 1.48, `HashSet` wraps `hashbrown::HashSet` instead of `std::collections::HashMap`
+    return has_field(hash_set, "base")
+
+
 def classify_rust_type(type):
     type_class = type.code
     if type_class == gdb.TYPE_CODE_STRUCT:
@@ -61,13 +66,15 @@ def lookup(valobj):
         return StdBTreeMapProvider(valobj)
     if rust_type == RustType.STD_HASH_MAP:
         if is_hashbrown_hashmap(valobj):
-            return StdHashMapProvider(valobj)
+            return StdHashMapProvider(valobj["base"])
         else:
             return StdOldHashMapProvider(valobj)
     if rust_type == RustType.STD_HASH_SET:
+        if is_hashbrown_hashset(valobj):
+            return StdHashMapProvider(valobj["base"]["map"], show_values=False)
         hash_map = valobj["map"]
         if is_hashbrown_hashmap(hash_map):
-            return StdHashMapProvider(hash_map, show_values=False)
+            return StdHashMapProvider(hash_map["base"], show_values=False)
         else:
             return StdOldHashMapProvider(hash_map, show_values=False)
 
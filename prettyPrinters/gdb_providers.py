@@ -16,6 +16,32 @@ def unwrap_unique_or_non_null(unique_or_nonnull):
     return ptr if ptr.type.code == gdb.TYPE_CODE_PTR else ptr[ZERO_FIELD]
 
 
+def has_field(valobj, name):
+    return any(field.name == name for field in valobj.type.fields())
+
+
+# Longer strings are truncated
+MAX_STRING_LENGTH = 1024
+
+
+def utf8_to_string(data_ptr, length):
+    """Reads a UTF-8 string of `length` bytes, cutting it on a char boundary if it is too long"""
+    end = min(length, MAX_STRING_LENGTH)
+    data = bytearray(gdb.selected_inferior().read_memory(int(data_ptr), end)) if end > 0 else bytearray()
+    is_truncated = end < length
+    if is_truncated:
+        # Step back over continuation bytes (0b10xxxxxx) to the first byte of the last char
+        start = end - 1
+        while start > 0 and data[start] & 0xC0 == 0x80:
+            start -= 1
+        first_byte = data[start]
+        char_length = 1 if first_byte < 0x80 else 2 if first_byte < 0xE0 else 3 if first_byte < 0xF0 else 4
+        if start + char_length > end:
+            end = start
+    string = data[:end].decode("utf-8", "replace")
+    return u'"{}"...'.format(string) if is_truncated else u'"{}"'.format(string)
+
+
 class StructProvider:
     def __init__(self, valobj):
         self.valobj = valobj
@@ -78,11 +104,7 @@ class StdStringProvider:
         self.data_ptr = unwrap_unique_or_non_null(vec["buf"]["ptr"])
 
     def to_string(self):
-        return self.data_ptr.lazy_string(encoding="utf-8", length=self.length)
-
-    @staticmethod
-    def display_hint():
-        return "string"
+        return utf8_to_string(self.data_ptr, self.length)
 
 
 class StdOsStringProvider:
@@ -109,11 +131,7 @@ class StdStrProvider:
         self.data_ptr = valobj["data_ptr"]
 
     def to_string(self):
-        return self.data_ptr.lazy_string(encoding="utf-8", length=self.length)
-
-    @staticmethod
-    def display_hint():
-        return "string"
+        return utf8_to_string(self.data_ptr, self.length)
 
 
 class StdVecProvider:
@@ -163,8 +181,11 @@ class StdRcProvider:
         self.valobj = valobj
         self.ptr = unwrap_unique_or_non_null(valobj["ptr"])
         self.value = self.ptr["data" if is_atomic else "value"]
-        self.strong = self.ptr["strong"]["v" if is_atomic else "value"]["value"]
-        self.weak = self.ptr["weak"]["v" if is_atomic else "value"]["value"] - 1
+        # `Cell<usize>` and `AtomicUsize` both wrap `UnsafeCell<usize>` in their only field
+        strong = self.ptr["strong"]
+        weak = self.ptr["weak"]
+        self.strong = strong[strong.type.fields()[0]]["value"]
+        self.weak = weak[weak.type.fields()[0]]["value"] - 1
 
     def to_string(self):
         return "strong={}, weak={}".format(int(self.strong), int(self.weak))
@@ -222,14 +243,26 @@ class StdNonZeroNumberProvider:
         return self.value
 
 
-# Yield each key (and optionally value) from a BoxedNode.
-def children_of_node(boxed_node, height, want_values):
+def unwrap_maybe_uninit(value):
+    # union MaybeUninit<T> { uninit: (), value: ManuallyDrop<T> }
+    # struct ManuallyDrop<T> { value: T }
+    if value.type.name.startswith("core::mem::maybe_uninit::MaybeUninit<"):
+        return value["value"]["value"]
+    return value
+
+
+def unwrap_btree_node_ptr(node):
+    # BACKCOMPAT: rust 1.49. struct BoxedNode<K, V> { ptr: Unique<LeafNode<K, V>> }
+    return unwrap_unique_or_non_null(node["ptr"] if has_field(node, "ptr") else node)
+
+
+# Yield each key (and optionally value) from a node.
+def children_of_node(node_ptr, height, want_values):
     def cast_to_internal(node):
-        internal_type_name = str(node.type.target()).replace('LeafNode', 'InternalNode')
+        internal_type_name = str(node.type.target()).replace('LeafNode', 'InternalNode', 1)
         internal_type = lookup_type(internal_type_name)
         return node.cast(internal_type.pointer())
 
-    node_ptr = unwrap_unique_or_non_null(boxed_node['ptr'])
     node_ptr = cast_to_internal(node_ptr) if height > 0 else node_ptr
     leaf = node_ptr['data'] if height > 0 else node_ptr.dereference()
     keys = leaf['keys']
@@ -238,14 +271,30 @@ def children_of_node(boxed_node, height, want_values):
 
     for i in xrange(0, length + 1):
         if height > 0:
-            child_ptr = node_ptr['edges'][i]['value']['value']
+            child_ptr = unwrap_btree_node_ptr(unwrap_maybe_uninit(node_ptr['edges'][i]))
             for child in children_of_node(child_ptr, height - 1, want_values):
                 yield child
         if i < length:
             if want_values:
-                yield (keys[i]['value']['value'], values[i]['value']['value'])
+                yield (unwrap_maybe_uninit(keys[i]), unwrap_maybe_uninit(values[i]))
             else:
-                yield keys[i]['value']['value']
+                yield unwrap_maybe_uninit(keys[i])
+
+
+# Yield each key (and optionally value) from a BTreeMap.
+def children_of_btree_map(btree_map, want_values):
+    if int(btree_map["length"]) == 0:
+        return
+
+    root = btree_map["root"]
+    if not has_field(root, "node"):
+        # `Option<Root>` is always `Some` for non-empty maps and has the same layout thanks to the niche
+        root_type_name = root.type.name[len("core::option::Option<"):-1]
+        root = root.cast(lookup_type(root_type_name))
+
+    node_ptr = unwrap_btree_node_ptr(root["node"])
+    for child in children_of_node(node_ptr, int(root["height"]), want_values):
+        yield child
 
 
 class StdBTreeSetProvider:
@@ -256,9 +305,7 @@ class StdBTreeSetProvider:
         return "size={}".format(self.valobj["map"]["length"])
 
     def children(self):
-        root = self.valobj["map"]["root"]
-        node_ptr = root["node"]
-        for i, child in enumerate(children_of_node(node_ptr, root["height"], want_values=False)):
+        for i, child in enumerate(children_of_btree_map(self.valobj["map"], want_values=False)):
             yield ("[{}]".format(i), child)
 
     @staticmethod
@@ -274,13 +321,9 @@ class StdBTreeMapProvider:
         return "size={}".format(self.valobj["length"])
 
     def children(self):
-        root = self.valobj["root"]
-        node_ptr = root["node"]
-        i = 0
-        for child in children_of_node(node_ptr, root["height"], want_values=True):
+        for i, child in enumerate(children_of_btree_map(self.valobj, want_values=True)):
             yield ("key{}".format(i), child[0])
             yield ("val{}".format(i), child[1])
-            i = i + 1
 
     @staticmethod
     def display_hint():
@@ -346,17 +389,28 @@ class StdOldHashMapProvider:
 
 
 class StdHashMapProvider:
+    """Pretty-printer for hashbrown's HashMap
+
+    The layout of `RawTable` is detected by field names (see `StdHashMapSyntheticProvider` in `lldb_providers.py`),
+    so `valobj` is hashbrown's map rather than the std wrapper.
+    """
+
     def __init__(self, valobj, show_values=True):
         self.valobj = valobj
         self.show_values = show_values
 
-        table = self.valobj["base"]["table"]
-        capacity = int(table["bucket_mask"]) + 1
-        ctrl = table["ctrl"]["pointer"]
+        table = self.valobj["table"]
+        inner_table = table["table"] if has_field(table, "table") else table
+        capacity = int(inner_table["bucket_mask"]) + 1
+        ctrl = inner_table["ctrl"]["pointer"]
 
-        self.size = int(table["items"])
-        self.data_ptr = table["data"]["pointer"]
-        self.pair_type = self.data_ptr.dereference().type
+        self.size = int(inner_table["items"])
+        self.buckets_before_ctrl = not has_field(inner_table, "data")
+        if self.buckets_before_ctrl:
+            pair_type = table["marker"].type.template_argument(0)
+            self.data_ptr = ctrl.cast(pair_type.pointer())
+        else:
+            self.data_ptr = inner_table["data"]["pointer"]
 
         self.valid_indices = []
         for idx in range(capacity):
@@ -374,6 +428,8 @@ class StdHashMapProvider:
 
         for index in range(self.size):
             idx = self.valid_indices[index]
+            if self.buckets_before_ctrl:
+                idx = -(idx + 1)
             element = (pairs_start + idx).dereference()
             if self.show_values:
                 yield ("key{}".format(index), element[ZERO_FIELD])
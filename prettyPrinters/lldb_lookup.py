@@ -9,6 +9,11 @@ def is_hashbrown_hashmap(hash_map):
     return len(hash_map.type.fields) == 1
 
 
+def is_hashbrown_hashset(hash_set):
+    # Since rust 1.48, `HashSet` wraps `hashbrown::HashSet` instead of `std::collections::HashMap`
+    return hash_set.GetChildMemberWithName("base").IsValid()
+
+
 def classify_rust_type(type):
     type_class = type.GetTypeClass()
     if type_class == lldb.eTypeClassStruct:
@@ -40,6 +45,10 @@ def summary_lookup(valobj, dict):
         return SizeSummaryProvider(valobj, dict)
     if rust_type == RustType.STD_HASH_SET:
         return SizeSummaryProvider(valobj, dict)
+    if rust_type == RustType.STD_BTREE_MAP:
+        return StdBTreeSummaryProvider(valobj, dict)
+    if rust_type == RustType.STD_BTREE_SET:
+        return StdBTreeSummaryProvider(valobj, dict)
 
     if rust_type == RustType.STD_RC:
         return StdRcSummaryProvider(valobj, dict)
@@ -87,15 +96,22 @@ def synthetic_lookup(valobj, dict):
 
     if rust_type == RustType.STD_HASH_MAP:
         if is_hashbrown_hashmap(valobj):
-            return StdHashMapSyntheticProvider(valobj, dict)
+            return StdHashMapSyntheticProvider(valobj.GetChildMemberWithName("base"), dict)
         else:
             return StdOldHashMapSyntheticProvider(valobj, dict)
     if rust_type == RustType.STD_HASH_SET:
+        if is_hashbrown_hashset(valobj):
+            hash_map = valobj.GetChildMemberWithName("base").GetChildMemberWithName("map")
+            return StdHashMapSyntheticProvider(hash_map, dict, show_values=False)
         hash_map = valobj.GetChildAtIndex(0)
         if is_hashbrown_hashmap(hash_map):
-            return StdHashMapSyntheticProvider(hash_map, dict, show_values=False)
+            return StdHashMapSyntheticProvider(hash_map.GetChildMemberWithName("base"), dict, show_values=False)
         else:
             return StdOldHashMapSyntheticProvider(hash_map, dict, show_values=False)
+    if rust_type == RustType.STD_BTREE_MAP:
+        return StdBTreeMapSyntheticProvider(valobj, dict)
+    if rust_type == RustType.STD_BTREE_SET:
+        return StdBTreeMapSyntheticProvider(valobj, dict, show_values=False)
 
     if rust_type == RustType.STD_RC:
         return StdRcSyntheticProvider(valobj, dict)
@@ -125,10 +125,33 @@ def SizeSummaryProvider(valobj, dict):
     return 'size=' + str(valobj.GetNumChildren())
 
 
+# Longer strings are truncated in summaries
+MAX_STRING_SUMMARY_LENGTH = 1024
+
+
+def utf8_summary(data, length):
+    # type: (bytearray, int) -> str
+    """Formats the first bytes of a UTF-8 string of `length` bytes, cutting it on a char boundary if truncated"""
+    end = len(data)
+    is_truncated = end < length
+    if is_truncated:
+        # Step back over continuation bytes (0b10xxxxxx) to the first byte of the last char
+        start = end - 1
+        while start > 0 and data[start] & 0xC0 == 0x80:
+            start -= 1
+        first_byte = data[start]
+        char_length = 1 if first_byte < 0x80 else 2 if first_byte < 0xE0 else 3 if first_byte < 0xF0 else 4
+        if start + char_length > end:
+            end = start
+    data = data[:end]
+    string = data.decode(encoding='UTF-8', errors='replace') if PY3 else str(data)
+    return '"%s"...' % string if is_truncated else '"%s"' % string
+
+
 def vec_to_string(vec):
     length = vec.GetNumChildren()
-    chars = [vec.GetChildAtIndex(i).GetValueAsUnsigned() for i in range(length)]
-    return bytes(chars).decode(errors='replace') if PY3 else "".join(chr(char) for char in chars)
+    chars = [vec.GetChildAtIndex(i).GetValueAsUnsigned() for i in range(min(length, MAX_STRING_SUMMARY_LENGTH))]
+    return utf8_summary(bytearray(chars), length)
 
 
 def StdStringSummaryProvider(valobj, dict):
@@ -136,7 +159,7 @@ def StdStringSummaryProvider(valobj, dict):
     logger = Logger.Logger()
     logger >> "[StdStringSummaryProvider] for " + str(valobj.GetName())
     vec = valobj.GetChildAtIndex(0)
-    return '"%s"' % vec_to_string(vec)
+    return vec_to_string(vec)
 
 
 def StdOsStringSummaryProvider(valobj, dict):
@@ -146,7 +169,7 @@ def StdOsStringSummaryProvider(valobj, dict):
     buf = valobj.GetChildAtIndex(0).GetChildAtIndex(0)
     is_windows = "Wtf8Buf" in buf.type.name
     vec = buf.GetChildAtIndex(0) if is_windows else buf
-    return '"%s"' % vec_to_string(vec)
+    return vec_to_string(vec)
 
 
 def StdStrSummaryProvider(valobj, dict):
@@ -163,9 +186,8 @@ def StdStrSummaryProvider(valobj, dict):
     start = data_ptr.GetValueAsUnsigned()
     error = SBError()
     process = data_ptr.GetProcess()
-    data = process.ReadMemory(start, length, error)
-    data = data.decode(encoding='UTF-8') if PY3 else data
-    return '"%s"' % data
+    data = process.ReadMemory(start, min(length, MAX_STRING_SUMMARY_LENGTH), error)
+    return utf8_summary(bytearray(data), length)
 
 
 class StructSyntheticProvider:
@@ -446,7 +468,17 @@ class StdOldHashMapSyntheticProvider:
 
 
 class StdHashMapSyntheticProvider:
-    """Pretty-printer for hashbrown's HashMap"""
+    """Pretty-printer for hashbrown's HashMap
+
+    struct HashMap<K, V, S> { hash_builder: S, table: RawTable<(K, V)> }
+    rust 1.36.0: struct RawTable<T> { bucket_mask: usize, ctrl: NonNull<u8>, data: NonNull<T>, items: usize, ... }
+    rust 1.43.0: struct RawTable<T> { bucket_mask: usize, ctrl: NonNull<u8>, items: usize, marker: PhantomData<T>, ... }
+    rust 1.52.0: struct RawTable<T> { table: RawTableInner, marker: PhantomData<T>, ... }
+    struct RawTableInner { bucket_mask: usize, ctrl: NonNull<u8>, items: usize, ... }
+
+    Without the `data` field, buckets are stored right before the control bytes in reverse order.
+    The layout is detected by field names, so `valobj` is hashbrown's map rather than the std wrapper.
+    """
 
     def __init__(self, valobj, dict, show_values=True):
         # type: (SBValue, dict, bool) -> StdHashMapSyntheticProvider
@@ -468,10 +500,12 @@ class StdHashMapSyntheticProvider:
 
     def get_child_at_index(self, index):
         # type: (int) -> SBValue
-        pairs_start = self.data_ptr.GetValueAsUnsigned()
         idx = self.valid_indices[index]
-        address = pairs_start + idx * self.pair_type_size
-        element = self.data_ptr.CreateValueFromAddress("[%s]" % index, address, self.pair_type)
+        if self.data_ptr is not None:
+            address = self.data_ptr.GetValueAsUnsigned() + idx * self.pair_type_size
+        else:
+            address = self.ctrl.GetValueAsUnsigned() - (idx + 1) * self.pair_type_size
+        element = self.ctrl.CreateValueFromAddress("[%s]" % index, address, self.pair_type)
         if self.show_values:
             return element
         else:
@@ -480,22 +514,32 @@ class StdHashMapSyntheticProvider:
 
     def update(self):
         # type: () -> None
-        table = self.valobj.GetChildMemberWithName("base").GetChildMemberWithName("table")
-        capacity = table.GetChildMemberWithName("bucket_mask").GetValueAsUnsigned() + 1
-        ctrl = table.GetChildMemberWithName("ctrl").GetChildAtIndex(0)
-
-        self.size = table.GetChildMemberWithName("items").GetValueAsUnsigned()
-        self.data_ptr = table.GetChildMemberWithName("data").GetChildAtIndex(0)
-        self.pair_type = self.data_ptr.Dereference().GetType()
+        table = self.valobj.GetChildMemberWithName("table")
+        inner_table = table.GetChildMemberWithName("table")
+        if not inner_table.IsValid():
+            inner_table = table
+
+        capacity = inner_table.GetChildMemberWithName("bucket_mask").GetValueAsUnsigned() + 1
+        self.ctrl = inner_table.GetChildMemberWithName("ctrl").GetChildAtIndex(0)
+        self.size = inner_table.GetChildMemberWithName("items").GetValueAsUnsigned()
+
+        data = inner_table.GetChildMemberWithName("data")
+        if data.IsValid():
+            self.data_ptr = data.GetChildAtIndex(0)
+            self.pair_type = self.data_ptr.Dereference().GetType()
+        else:
+            self.data_ptr = None
+            marker = table.GetChildMemberWithName("marker").GetType()  # type: SBType
+            self.pair_type = marker.template_args[0]
         self.pair_type_size = self.pair_type.GetByteSize()
 
         u8_type = self.valobj.GetTarget().GetBasicType(eBasicTypeUnsignedChar)
-        u8_type_size = self.valobj.GetTarget().GetBasicType(eBasicTypeUnsignedChar).GetByteSize()
+        u8_type_size = u8_type.GetByteSize()
 
         self.valid_indices = []
         for idx in range(capacity):
-            address = ctrl.GetValueAsUnsigned() + idx * u8_type_size
-            value = ctrl.CreateValueFromAddress("ctrl[%s]" % idx, address, u8_type).GetValueAsUnsigned()
+            address = self.ctrl.GetValueAsUnsigned() + idx * u8_type_size
+            value = self.ctrl.CreateValueFromAddress("ctrl[%s]" % idx, address, u8_type).GetValueAsUnsigned()
             is_present = value & 128 == 0
             if is_present:
                 self.valid_indices.append(idx)
@@ -505,6 +549,139 @@ class StdHashMapSyntheticProvider:
         return True
 
 
+def StdBTreeSummaryProvider(valobj, dict):
+    # type: (SBValue, dict) -> str
+    btree_map = valobj.GetChildMemberWithName("map")
+    if not btree_map.IsValid():
+        btree_map = valobj
+    return 'size=' + str(btree_map.GetChildMemberWithName("length").GetValueAsUnsigned())
+
+
+def unwrap_maybe_uninit(value):
+    # type: (SBValue) -> SBValue
+    # union MaybeUninit<T> { uninit: (), value: ManuallyDrop<T> }
+    # struct ManuallyDrop<T> { value: T }
+    if value.GetType().GetName().startswith("core::mem::maybe_uninit::MaybeUninit<"):
+        return value.GetChildMemberWithName("value").GetChildMemberWithName("value")
+    return value
+
+
+def unwrap_btree_node_ptr(node):
+    # type: (SBValue) -> SBValue
+    # BACKCOMPAT: rust 1.49. struct BoxedNode<K, V> { ptr: Unique<LeafNode<K, V>> }
+    ptr = node.GetChildMemberWithName("ptr")
+    return unwrap_unique_or_non_null(ptr if ptr.IsValid() else node)
+
+
+class StdBTreeMapSyntheticProvider:
+    """Pretty-printer for alloc::collections::btree::map::BTreeMap<K, V> and BTreeSet<K>
+
+    struct BTreeMap<K, V> { root: Option<Root<K, V>>, length: usize }
+    rust 1.33.0: struct Root<K, V> { node: BoxedNode<K, V>, height: usize }
+    rust 1.50.0: struct NodeRef<K, V> { height: usize, node: NonNull<LeafNode<K, V>>, ... }
+    struct LeafNode<K, V> { len: u16, keys: [MaybeUninit<K>; CAPACITY], vals: [MaybeUninit<V>; CAPACITY], ... }
+    struct InternalNode<K, V> { data: LeafNode<K, V>, edges: [MaybeUninit<BoxedNode<K, V>>; 2 * B], ... }
+    struct BTreeSet<T> { map: BTreeMap<T, ()> }
+
+    Map entries are named by their keys, like `[key] = value`.
+    If a key has no textual representation, both `key[i]` and `value[i]` children are shown instead.
+    """
+
+    def __init__(self, valobj, dict, show_values=True):
+        # type: (SBValue, dict, bool) -> StdBTreeMapSyntheticProvider
+        self.valobj = valobj
+        self.show_values = show_values
+        self.update()
+
+    def num_children(self):
+        # type: () -> int
+        return len(self.children)
+
+    def get_child_index(self, name):
+        # type: (str) -> int
+        for index, child in enumerate(self.children):
+            if child.GetName() == name:
+                return index
+        return -1
+
+    def get_child_at_index(self, index):
+        # type: (int) -> SBValue
+        if 0 <= index < len(self.children):
+            return self.children[index]
+        return None
+
+    def update(self):
+        # type: () -> None
+        btree_map = self.valobj if self.show_values else self.valobj.GetChildMemberWithName("map")
+        self.children = []
+        if btree_map.GetChildMemberWithName("length").GetValueAsUnsigned() == 0:
+            return
+
+        root = btree_map.GetChildMemberWithName("root")
+        if not root.GetChildMemberWithName("node").IsValid():
+            # `Option<Root>` is always `Some` for non-empty maps
+            root = self.unwrap_some(root)
+            if root is None:
+                return
+
+        node_ptr = unwrap_btree_node_ptr(root.GetChildMemberWithName("node"))
+        height = root.GetChildMemberWithName("height").GetValueAsUnsigned()
+        for key, value in self.entries_of_node(node_ptr, height):
+            self.add_child(key, value)
+
+    def has_children(self):
+        # type: () -> bool
+        return True
+
+    @staticmethod
+    def unwrap_some(option):
+        # type: (SBValue) -> SBValue
+        for i in range(option.GetNumChildren()):
+            payload = option.GetChildAtIndex(i).GetChildMemberWithName("__0")
+            if payload.IsValid():
+                return payload
+        return None
+
+    def entries_of_node(self, node_ptr, height):
+        leaf = node_ptr.Dereference()
+        edges = None
+        if height > 0:
+            internal_type_name = node_ptr.GetType().GetPointeeType().GetName().replace("LeafNode", "InternalNode", 1)
+            internal_type = self.valobj.GetTarget().FindFirstType(internal_type_name)
+            internal = node_ptr.Cast(internal_type.GetPointerType()).Dereference()
+            leaf = internal.GetChildMemberWithName("data")
+            edges = internal.GetChildMemberWithName("edges")
+
+        keys = leaf.GetChildMemberWithName("keys")
+        values = leaf.GetChildMemberWithName("vals")
+        length = leaf.GetChildMemberWithName("len").GetValueAsUnsigned()
+
+        for i in range(length + 1):
+            if edges is not None:
+                child_ptr = unwrap_btree_node_ptr(unwrap_maybe_uninit(edges.GetChildAtIndex(i)))
+                for entry in self.entries_of_node(child_ptr, height - 1):
+                    yield entry
+            if i < length:
+                key = unwrap_maybe_uninit(keys.GetChildAtIndex(i))
+                value = unwrap_maybe_uninit(values.GetChildAtIndex(i)) if self.show_values else None
+                yield key, value
+
+    def add_child(self, key, value):
+        # type: (SBValue, SBValue) -> None
+        if not self.show_values:
+            index = len(self.children)
+            self.children.append(self.valobj.CreateValueFromData("[%s]" % index, key.GetData(), key.GetType()))
+            return
+
+        key_text = key.GetValue() or key.GetSummary()
+        if key_text is not None:
+            self.children.append(self.valobj.CreateValueFromData("[%s]" % key_text, value.GetData(), value.GetType()))
+        else:
+            index = len(self.children) // 2
+            self.children.append(self.valobj.CreateValueFromData("key[%s]" % index, key.GetData(), key.GetType()))
+            self.children.append(self.valobj.CreateValueFromData("value[%s]" % index, value.GetData(), value.GetType()))
+
+
 def StdRcSummaryProvider(valobj, dict):
     # type: (SBValue, dict) -> str
     strong = valobj.GetChildMemberWithName("strong").GetValueAsUnsigned()
@@ -1,5 +1,19 @@
 // min-version: 1.33.0
 
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:print btree_set
+// lldbg-check:[...]$0 = size=15 { [0] = 0 [1] = 1 [2] = 2 [3] = 3 [4] = 4 [5] = 5 [6] = 6 [7] = 7 [8] = 8 [9] = 9 [10] = 10 [11] = 11 [12] = 12 [13] = 13 [14] = 14 }
+// lldbg-check-201:[...]btree_set = size=15 { [0] = 0 [1] = 1 [2] = 2 [3] = 3 [4] = 4 [5] = 5 [6] = 6 [7] = 7 [8] = 8 [9] = 9 [10] = 10 [11] = 11 [12] = 12 [13] = 13 [14] = 14 }
+// lldb-command:print btree_map
+// lldbg-check:[...]$1 = size=15 { [0] = 0 [1] = 1 [2] = 2 [3] = 3 [4] = 4 [5] = 5 [6] = 6 [7] = 7 [8] = 8 [9] = 9 [10] = 10 [11] = 11 [12] = 12 [13] = 13 [14] = 14 }
+// lldbg-check-201:[...]btree_map = size=15 { [0] = 0 [1] = 1 [2] = 2 [3] = 3 [4] = 4 [5] = 5 [6] = 6 [7] = 7 [8] = 8 [9] = 9 [10] = 10 [11] = 11 [12] = 12 [13] = 13 [14] = 14 }
+// lldb-command:print empty_btree_map
+// lldbg-check:[...]$2 = size=0
+// lldbg-check-201:[...]empty_btree_map = size=0
+
 // === GDB TESTS ===================================================================================
 
 // gdb-command:run
@@ -10,6 +24,9 @@
 // gdb-command: print btree_map
 // gdbg-check:$2 = size=15 = {[0] = 0, [1] = 1, [2] = 2, [3] = 3, [4] = 4, [5] = 5, [6] = 6, [7] = 7, [8] = 8, [9] = 9, [10] = 10, [11] = 11, [12] = 12, [13] = 13, [14] = 14}
 
+// gdb-command: print empty_btree_map
+// gdbg-check:$3 = size=0
+
 
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -23,5 +40,7 @@ fn main() {
     for i in 0..15 {
         btree_map.insert(i, i);
     }
+
+    let empty_btree_map: BTreeMap<i32, i32> = BTreeMap::new();
     print!(""); // #break
 }
@@ -0,0 +1,30 @@
+// min-version: 1.33.0
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:print c
+// lldbg-check:[...]$0 = { value = 5 }
+// lldbg-check-201:[...]c = { value = 5 }
+// lldb-command:print r
+// lldbg-check:[...]$1 = borrow=1 { value = 42 }
+// lldbg-check-201:[...]r = borrow=1 { value = 42 }
+
+// === GDB TESTS ==================================================================================
+
+// gdb-command:run
+
+// gdb-command:print c
+// gdbg-check:[...]$1 = {value = 5}
+// gdb-command:print r
+// gdbg-check:[...]$2 = borrow=1 = {value = 42, borrow = 1}
+
+use std::cell::{Cell, RefCell};
+
+fn main() {
+    let c = Cell::new(5);
+    let r = RefCell::new(42);
+    let r1 = r.borrow();
+    print!(""); // #break
+}
@@ -10,6 +10,9 @@
 // lldb-command:print ys
 // lldbg-check:[...]$1 = size=4 { [0] = 1 [1] = 2 [2] = 3 [3] = 4 }
 // lldbg-check-201:[...]ys = size=4 { [0] = 1 [1] = 2 [2] = 3 [3] = 4 }
+// lldb-command:print zs
+// lldbg-check:[...]$2 = size=1 { [0] = { 0 = "a" 1 = size=2 { [0] = 1 [1] = 2 } } }
+// lldbg-check-201:[...]zs = size=1 { [0] = { 0 = "a" 1 = size=2 { [0] = 1 [1] = 2 } } }
 
 // === GDB TESTS ===================================================================================
 
@@ -19,6 +22,8 @@
 // gdbg-check:[...]$1 = size=4 = {[1] = 10, [2] = 20, [3] = 30, [4] = 40}
 // gdb-command:print ys
 // gdbg-check:[...]$2 = size=4 = {1, 2, 3, 4}
+// gdb-command:print zs
+// gdbg-check:[...]$3 = size=1 = {["a"] = size=2 = {1, 2}}
 
 
 use std::collections::{HashMap, HashSet};
@@ -42,5 +47,7 @@ fn main() {
     for y in 1..5 {
         ys.insert(y);
     }
+    let mut zs = HashMap::<String, Vec<u32>>::new();
+    zs.insert(String::from("a"), vec![1, 2]);
     print!(""); // #break
 }
@@ -14,6 +14,12 @@
 // lldb-command:print empty_s2
 // lldbg-check:[...]$3 = "" [...]
 // lldbg-check-201:[...]empty_s2 = "" [...]
+// lldb-command:print long_s1
+// lldbg-check:[...]$4 = "aaaaaaaaaa[...]aaaaaaaaaa"... [...]
+// lldbg-check-201:[...]long_s1 = "aaaaaaaaaa[...]aaaaaaaaaa"... [...]
+// lldb-command:print long_s2
+// lldbg-check:[...]$5 = "∆∆∆∆∆∆∆∆∆∆[...]∆∆∆∆∆∆∆∆∆∆"... [...]
+// lldbg-check-201:[...]long_s2 = "∆∆∆∆∆∆∆∆∆∆[...]∆∆∆∆∆∆∆∆∆∆"... [...]
 
 // === GDB TESTS ==================================================================================
 
@@ -27,6 +33,10 @@
 // gdbg-check:[...]$3 = ""
 // gdb-command:print empty_s2
 // gdbg-check:[...]$4 = ""
+// gdb-command:print long_s1
+// gdbg-check:[...]$5 = "aaaaaaaaaa[...]aaaaaaaaaa"...
+// gdb-command:print long_s2
+// gdbg-check:[...]$6 = "∆∆∆∆∆∆∆∆∆∆[...]∆∆∆∆∆∆∆∆∆∆"...
 
 
 fn main() {
@@ -34,5 +44,7 @@ fn main() {
     let s2 = String::from(s1);
     let empty_s1 = "";
     let empty_s2 = String::from(empty_s1);
+    let long_s1 = "a".repeat(2000);
+    let long_s2 = "∆".repeat(1000);
     print!(""); // #break
 }
@@ -24,4 +24,9 @@ object CargoIcons {
     val TEST_TARGET = IconLoader.getIcon("/icons/target-test.svg")
     val BENCH_TARGET = IconLoader.getIcon("/icons/target-bench.svg")
     val EXAMPLE_TARGET = IconLoader.getIcon("/icons/target-example.svg")
+
+    // Icons for dependency nodes in cargo toolwindow
+    val DEPENDENCIES = AllIcons.Nodes.PpLibFolder
+    val DEPENDENCY = AllIcons.Nodes.PpLib
+    val DUPLICATE_DEPENDENCIES = AllIcons.General.Warning
 }
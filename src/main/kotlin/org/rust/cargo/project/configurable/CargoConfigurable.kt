@@ -9,6 +9,8 @@ import com.intellij.openapi.project.Project
 import com.intellij.openapi.ui.ComboBox
 import com.intellij.ui.EnumComboBoxModel
 import com.intellij.ui.components.JBCheckBox
+import com.intellij.ui.components.JBTextField
+import com.intellij.util.text.nullize
 import org.rust.cargo.toolchain.ExternalLinter
 import org.rust.cargo.util.CargoCommandLineEditor
 import org.rust.ide.ui.layout
@@ -27,12 +29,20 @@ class CargoConfigurable(project: Project) : RsConfigurableBase(project) {
     private val runExternalLinterOnTheFlyCheckbox: JBCheckBox = JBCheckBox()
     private var runExternalLinterOnTheFly: Boolean by CheckboxDelegate(runExternalLinterOnTheFlyCheckbox)
 
+    private val maybeIncorrectFixesCheckbox: JBCheckBox = JBCheckBox()
+    private var maybeIncorrectFixes: Boolean by CheckboxDelegate(maybeIncorrectFixesCheckbox)
+
     private val useOfflineCheckbox: JBCheckBox = JBCheckBox()
     private var useOffline: Boolean by CheckboxDelegate(useOfflineCheckbox)
 
+    private val evaluateBuildScriptsCheckbox: JBCheckBox = JBCheckBox()
+    private var evaluateBuildScripts: Boolean by CheckboxDelegate(evaluateBuildScriptsCheckbox)
+
     private val compileAllTargetsCheckBox = JBCheckBox()
     private var compileAllTargets: Boolean by CheckboxDelegate(compileAllTargetsCheckBox)
 
+    private val buildTargetField: JBTextField = JBTextField()
+
     private lateinit var externalLinterArguments: CargoCommandLineEditor
 
     override fun getDisplayName(): String = "Cargo"
@@ -49,6 +59,14 @@ class CargoConfigurable(project: Project) : RsConfigurableBase(project) {
         row("Offline mode:", useOfflineCheckbox, """
             Pass `--offline` option to cargo not to perform network requests.
         """)
+        row("Evaluate build scripts:", evaluateBuildScriptsCheckbox, """
+            Run build scripts while project refresh to collect `cfg` options,
+            environment variables and `OUT_DIR` generated by them. Can be slow.
+        """)
+        row("Build target:", buildTargetField, """
+            Target triple, e.g. `wasm32-unknown-unknown`, used to evaluate `cfg` attributes
+            and target-specific dependencies. Leave empty to use the host target.
+        """)
         block("External Linter") {
             row("External linter:", externalLinterComboBox, """
                 External tool to use for code analysis.
@@ -60,6 +78,10 @@ class CargoConfigurable(project: Project) : RsConfigurableBase(project) {
                 Enable external linter to add code highlighting based on the used linter result.
                 Can be CPU-consuming.
             """)
+            row("Show fixes that may be incorrect:", maybeIncorrectFixesCheckbox, """
+                Offer quick fixes for suggestions of the external linter which are not machine-applicable,
+                e.g. the ones that may change the meaning of the code or contain placeholders.
+            """)
         }
     }
 
@@ -69,7 +91,10 @@ class CargoConfigurable(project: Project) : RsConfigurableBase(project) {
             || runExternalLinterOnTheFly != settings.runExternalLinterOnTheFly
             || compileAllTargets != settings.compileAllTargets
             || useOffline != settings.useOffline
+            || evaluateBuildScripts != settings.evaluateBuildScripts
+            || buildTargetField.text.trim().nullize() != settings.buildTarget
             || externalLinterArguments.text != settings.externalLinterArguments
+            || maybeIncorrectFixes != settings.externalLinterMaybeIncorrectFixes
 
     override fun apply() {
         settings.modify {
@@ -77,8 +102,11 @@ class CargoConfigurable(project: Project) : RsConfigurableBase(project) {
             it.externalLinter = externalLinter
             it.runExternalLinterOnTheFly = runExternalLinterOnTheFly
             it.externalLinterArguments = externalLinterArguments.text
+            it.externalLinterMaybeIncorrectFixes = maybeIncorrectFixes
             it.compileAllTargets = compileAllTargets
             it.useOffline = useOffline
+            it.evaluateBuildScripts = evaluateBuildScripts
+            it.buildTarget = buildTargetField.text.trim().nullize()
         }
     }
 
@@ -87,7 +115,10 @@ class CargoConfigurable(project: Project) : RsConfigurableBase(project) {
         externalLinter = settings.externalLinter
         runExternalLinterOnTheFly = settings.runExternalLinterOnTheFly
         externalLinterArguments.text = settings.externalLinterArguments
+        maybeIncorrectFixes = settings.externalLinterMaybeIncorrectFixes
         compileAllTargets = settings.compileAllTargets
         useOffline = settings.useOffline
+        evaluateBuildScripts = settings.evaluateBuildScripts
+        buildTargetField.text = settings.buildTarget.orEmpty()
     }
 }
@@ -0,0 +1,95 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.project.configurable
+
+import com.intellij.openapi.project.Project
+import com.intellij.ui.ToolbarDecorator
+import com.intellij.ui.components.JBLabel
+import com.intellij.ui.table.TableView
+import com.intellij.util.ui.ColumnInfo
+import com.intellij.util.ui.ListTableModel
+import com.intellij.util.ui.UIUtil
+import org.rust.ide.injected.RsLanguageInjectionRule
+import org.rust.ide.injected.RsLanguageInjectionSettings
+import java.awt.BorderLayout
+import javax.swing.JComponent
+import javax.swing.JPanel
+
+class RsLanguageInjectionConfigurable(project: Project) : RsConfigurableBase(project) {
+    private val injectionSettings: RsLanguageInjectionSettings = RsLanguageInjectionSettings.getInstance(project)
+
+    private val model: ListTableModel<RsLanguageInjectionRule> =
+        ListTableModel(PathColumn, ArgumentIndexColumn, LanguageColumn, MaskFormatPlaceholdersColumn)
+    private val table: TableView<RsLanguageInjectionRule> = TableView(model)
+
+    override fun getDisplayName(): String = "Language Injections"
+
+    override fun createComponent(): JComponent {
+        val decorator = ToolbarDecorator.createDecorator(table)
+            .setAddAction { model.addRow(RsLanguageInjectionRule()) }
+        val comment = JBLabel(
+            "<html>Languages are injected to string literal arguments of the listed macros (paths ending with `!`) " +
+                "and functions. Argument indices start from 0 and don't count the receiver of a method call</html>",
+            UIUtil.ComponentStyle.SMALL,
+            UIUtil.FontColor.BRIGHTER
+        )
+        return JPanel(BorderLayout()).apply {
+            add(decorator.createPanel(), BorderLayout.CENTER)
+            add(comment, BorderLayout.SOUTH)
+        }
+    }
+
+    override fun isModified(): Boolean =
+        currentRules != injectionSettings.rules
+
+    override fun apply() {
+        table.stopEditing()
+        injectionSettings.setRules(currentRules)
+    }
+
+    override fun reset() {
+        model.items = injectionSettings.rules.map { it.copy() }
+    }
+
+    private val currentRules: List<RsLanguageInjectionRule>
+        get() = model.items.filter { it.path.isNotBlank() && it.languageId.isNotBlank() }
+
+    private object PathColumn : EditableColumn<String>("Macro or function path") {
+        override fun valueOf(item: RsLanguageInjectionRule): String = item.path
+        override fun setValue(item: RsLanguageInjectionRule, value: String) {
+            item.path = value.trim()
+        }
+    }
+
+    private object ArgumentIndexColumn : EditableColumn<Int>("Argument index") {
+        override fun valueOf(item: RsLanguageInjectionRule): Int = item.argumentIndex
+        override fun setValue(item: RsLanguageInjectionRule, value: Int) {
+            item.argumentIndex = value.coerceAtLeast(0)
+        }
+
+        override fun getColumnClass(): Class<*> = Int::class.javaObjectType
+    }
+
+    private object LanguageColumn : EditableColumn<String>("Language ID") {
+        override fun valueOf(item: RsLanguageInjectionRule): String = item.languageId
+        override fun setValue(item: RsLanguageInjectionRule, value: String) {
+            item.languageId = value.trim()
+        }
+    }
+
+    private object MaskFormatPlaceholdersColumn : EditableColumn<Boolean>("Mask format placeholders") {
+        override fun valueOf(item: RsLanguageInjectionRule): Boolean = item.maskFormatPlaceholders
+        override fun setValue(item: RsLanguageInjectionRule, value: Boolean) {
+            item.maskFormatPlaceholders = value
+        }
+
+        override fun getColumnClass(): Class<*> = Boolean::class.javaObjectType
+    }
+
+    private abstract class EditableColumn<T>(name: String) : ColumnInfo<RsLanguageInjectionRule, T>(name) {
+        override fun isCellEditable(item: RsLanguageInjectionRule): Boolean = true
+    }
+}
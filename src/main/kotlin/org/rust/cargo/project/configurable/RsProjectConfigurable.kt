@@ -11,9 +11,13 @@ import com.intellij.openapi.project.Project
 import com.intellij.openapi.ui.ComboBox
 import com.intellij.openapi.util.Disposer
 import com.intellij.ui.EnumComboBoxModel
+import com.intellij.ui.JBIntSpinner
 import com.intellij.ui.SimpleListCellRenderer
 import com.intellij.ui.components.JBCheckBox
 import org.rust.cargo.project.model.cargoProjects
+import org.rust.cargo.project.settings.RustProjectSettingsService.Companion.DEFAULT_MACRO_EXPANSION_DEPTH_LIMIT
+import org.rust.cargo.project.settings.RustProjectSettingsService.Companion.DEFAULT_MACRO_EXPANSION_TOKEN_LIMIT
+import org.rust.cargo.project.settings.RustProjectSettingsService.Companion.MAX_MACRO_EXPANSION_DEPTH_LIMIT
 import org.rust.cargo.project.settings.RustProjectSettingsService.MacroExpansionEngine
 import org.rust.cargo.project.settings.ui.RustProjectSettingsPanel
 import org.rust.cargo.toolchain.RustToolchain
@@ -45,6 +49,22 @@ class RsProjectConfigurable(
         }
     private var macroExpansionEngine: MacroExpansionEngine by ComboBoxDelegate(macroExpansionEngineComboBox)
 
+    private val macroExpansionDepthLimitSpinner: JBIntSpinner =
+        JBIntSpinner(DEFAULT_MACRO_EXPANSION_DEPTH_LIMIT, 1, MAX_MACRO_EXPANSION_DEPTH_LIMIT)
+    private var macroExpansionDepthLimit: Int
+        get() = macroExpansionDepthLimitSpinner.number
+        set(value) {
+            macroExpansionDepthLimitSpinner.number = value
+        }
+
+    private val macroExpansionTokenLimitSpinner: JBIntSpinner =
+        JBIntSpinner(DEFAULT_MACRO_EXPANSION_TOKEN_LIMIT, 1000, Int.MAX_VALUE, 1000)
+    private var macroExpansionTokenLimit: Int
+        get() = macroExpansionTokenLimitSpinner.number
+        set(value) {
+            macroExpansionTokenLimitSpinner.number = value
+        }
+
     private val showTestToolWindowCheckbox: JBCheckBox = JBCheckBox()
     private var showTestToolWindow: Boolean by CheckboxDelegate(showTestToolWindowCheckbox)
 
@@ -59,6 +79,14 @@ class RsProjectConfigurable(
             Allow plugin to process declarative macro invocations
             to extract information for name resolution and type inference.
         """)
+        row("Macro expansion depth limit:", macroExpansionDepthLimitSpinner, """
+            Macro calls nested deeper than the limit are not expanded.
+            Protects from infinite recursion in macro definitions.
+        """)
+        row("Macro expansion token limit:", macroExpansionTokenLimitSpinner, """
+            The maximum number of tokens produced by a single macro call.
+            Larger expansions are discarded.
+        """)
         row("Show test tool window:", showTestToolWindowCheckbox, """
             Show test results in run tool window when testing session begins
             instead of raw console.
@@ -76,6 +104,8 @@ class RsProjectConfigurable(
             explicitPathToStdlib = settings.explicitPathToStdlib
         )
         macroExpansionEngine = settings.macroExpansionEngine
+        macroExpansionDepthLimit = settings.macroExpansionDepthLimit
+        macroExpansionTokenLimit = settings.macroExpansionTokenLimit
         showTestToolWindow = settings.showTestToolWindow
         doctestInjectionEnabled = settings.doctestInjectionEnabled
     }
@@ -88,6 +118,8 @@ class RsProjectConfigurable(
             it.toolchain = rustProjectSettings.data.toolchain
             it.explicitPathToStdlib = rustProjectSettings.data.explicitPathToStdlib
             it.macroExpansionEngine = macroExpansionEngine
+            it.macroExpansionDepthLimit = macroExpansionDepthLimit
+            it.macroExpansionTokenLimit = macroExpansionTokenLimit
             it.showTestToolWindow = showTestToolWindow
             it.doctestInjectionEnabled = doctestInjectionEnabled
         }
@@ -98,6 +130,8 @@ class RsProjectConfigurable(
         return data.toolchain?.location != settings.toolchain?.location
             || data.explicitPathToStdlib != settings.explicitPathToStdlib
             || macroExpansionEngine != settings.macroExpansionEngine
+            || macroExpansionDepthLimit != settings.macroExpansionDepthLimit
+            || macroExpansionTokenLimit != settings.macroExpansionTokenLimit
             || showTestToolWindow != settings.showTestToolWindow
             || doctestInjectionEnabled != settings.doctestInjectionEnabled
     }
@@ -83,7 +83,8 @@ open class CargoProjectsServiceImpl(
     init {
         with(project.messageBus.connect()) {
             if (!isUnitTestMode) {
-                subscribe(VirtualFileManager.VFS_CHANGES, CargoTomlWatcher(fun() {
+                val watchBuildScripts = { project.rustSettings.evaluateBuildScripts }
+                subscribe(VirtualFileManager.VFS_CHANGES, CargoTomlWatcher(watchBuildScripts, fun() {
                     if (!project.rustSettings.autoUpdateEnabled) return
                     refreshAllProjects()
                 }))
@@ -550,25 +551,34 @@ private fun fetchCargoWorkspace(
         }
         val cargo = toolchain.cargoOrWrapper(projectDirectory)
         try {
-            val projectDescriptionData = cargo.fullProjectDescription(project, projectDirectory, object : ProcessAdapter() {
-                override fun onTextAvailable(event: ProcessEvent, outputType: Key<Any>) {
-                    val text = event.text.trim { it <= ' ' }
-                    if (text.startsWith("Updating") || text.startsWith("Downloading")) {
-                        progress.text = text
-                    }
-                }
-            })
-            val manifestPath = projectDirectory.resolve("Cargo.toml")
-
             // Running "cargo rustc -- --print cfg" causes an error when run in a project with multiple targets
             // error: extra arguments to `rustc` can only be passed to one target, consider filtering
             // the package by passing e.g. `--lib` or `--bin NAME` to specify a single target
             // Running "cargo rustc --bin=projectname  -- --print cfg" we can get around this
             // but it also compiles the whole project, which is probably not wanted
-            //TODO: This does not query the target specific cfg flags during cross compilation :-(
-            val rawCfgOptions = toolchain.getCfgOptions(projectDirectory) ?: emptyList()
-            val cfgOptions = CfgOptions.parse(rawCfgOptions)
-            val ws = CargoWorkspace.deserialize(manifestPath, projectDescriptionData, cfgOptions)
+            val buildTarget = project.rustSettings.buildTarget
+            val rawCfgOptions = toolchain.getCfgOptions(projectDirectory, buildTarget)
+            val cfgOptions = rawCfgOptions?.let { CfgOptions.parse(it) }
+            val targetTriple = buildTarget ?: toolchain.queryVersions().rustc?.host
+
+            val projectDescriptionData = cargo.fullProjectDescription(
+                project,
+                projectDirectory,
+                object : ProcessAdapter() {
+                    override fun onTextAvailable(event: ProcessEvent, outputType: Key<Any>) {
+                        val text = event.text.trim { it <= ' ' }
+                        if (text.startsWith("Updating") || text.startsWith("Downloading")) {
+                            progress.text = text
+                        }
+                    }
+                },
+                targetTriple,
+                // Keep all dependencies if we failed to get the target cfg options
+                cfgOptions,
+                project.rustSettings.evaluateBuildScripts
+            )
+            val manifestPath = projectDirectory.resolve("Cargo.toml")
+            val ws = CargoWorkspace.deserialize(manifestPath, projectDescriptionData, cfgOptions ?: CfgOptions.EMPTY)
             ok(ws)
         } catch (e: ExecutionException) {
             err(e.message ?: "failed to run Cargo")
@@ -17,21 +17,28 @@ import org.rust.cargo.toolchain.RustToolchain.Companion.CARGO_TOML
 /**
  * File changes listener, detecting changes inside the `Cargo.toml` files
  * and creation of `*.rs` files acting as automatic crate root.
+ * Changes inside `build.rs` files are detected if [watchBuildScripts] returns `true`,
+ * i.e. if the project model contains information collected from build scripts.
  */
 class CargoTomlWatcher(
+    private val watchBuildScripts: () -> Boolean = { false },
     private val onCargoTomlChange: () -> Unit
 ) : BulkFileListener {
     override fun before(events: List<VFileEvent>) = Unit
 
     override fun after(events: List<VFileEvent>) {
-        if (events.any(::isInterestingEvent)) onCargoTomlChange()
+        if (events.any { isInterestingEvent(it) || isBuildScriptChange(it) && watchBuildScripts() }) {
+            onCargoTomlChange()
+        }
     }
 }
 
+private const val BUILD_SCRIPT_FILE = "/build.rs"
+
 // These are paths and files names used by Cargo to infer targets without Cargo.toml
 // https://github.com/rust-lang/cargo/blob/2c2e07f5cfc9a5de10854654bc1e8abd02ae7b4f/src/cargo/util/toml.rs#L50-L56
 private val IMPLICIT_TARGET_FILES = listOf(
-    "/build.rs", "/src/main.rs", "/src/lib.rs"
+    BUILD_SCRIPT_FILE, "/src/main.rs", "/src/lib.rs"
 )
 
 private val IMPLICIT_TARGET_DIRS = listOf(
@@ -51,5 +58,8 @@ private fun isInterestingEvent(event: VFileEvent): Boolean = when {
     }
 }
 
+private fun isBuildScriptChange(event: VFileEvent): Boolean =
+    event is VFileContentChangeEvent && event.pathEndsWith(BUILD_SCRIPT_FILE)
+
 private fun VFileEvent.pathEndsWith(suffix: String): Boolean = path.endsWith(suffix) ||
     this is VFilePropertyChangeEvent && oldPath.endsWith(suffix)
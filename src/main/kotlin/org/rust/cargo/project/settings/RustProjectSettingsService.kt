@@ -37,15 +37,36 @@ interface RustProjectSettingsService {
         var runExternalLinterOnTheFly: Boolean = false,
         @AffectsHighlighting
         var externalLinterArguments: String = "",
+        // Offers quick fixes for suggestions which are not machine-applicable,
+        // e.g. the ones that may be incorrect or contain placeholders
+        @AffectsHighlighting
+        var externalLinterMaybeIncorrectFixes: Boolean = false,
         @AffectsHighlighting
         var compileAllTargets: Boolean = true,
         var useOffline: Boolean = false,
+        // Runs build scripts during project refresh to collect `cfg` options,
+        // environment variables and `OUT_DIR` generated by them
+        @AffectsCargoMetadata
+        @AffectsHighlighting
+        var evaluateBuildScripts: Boolean = false,
         var macroExpansionEngine: MacroExpansionEngine = defaultMacroExpansionEngine,
+        // Macro calls nested deeper than this limit are not expanded.
+        // Protects from infinite recursion in macro definitions
+        @AffectsHighlighting
+        var macroExpansionDepthLimit: Int = DEFAULT_MACRO_EXPANSION_DEPTH_LIMIT,
+        // Expansions of a single macro call with more tokens are discarded
+        @AffectsHighlighting
+        var macroExpansionTokenLimit: Int = DEFAULT_MACRO_EXPANSION_TOKEN_LIMIT,
         var showTestToolWindow: Boolean = true,
         @AffectsHighlighting
         var doctestInjectionEnabled: Boolean = true,
         var runRustfmtOnSave: Boolean = false,
-        var useSkipChildren: Boolean = false
+        var useSkipChildren: Boolean = false,
+        // Target triple used to evaluate `cfg` attributes and target-specific dependencies.
+        // The host target is used if it is not specified
+        @AffectsCargoMetadata
+        @AffectsHighlighting
+        var buildTarget: String? = null
     ) {
         @get:Transient
         @set:Transient
@@ -81,13 +102,18 @@ interface RustProjectSettingsService {
     val externalLinter: ExternalLinter
     val runExternalLinterOnTheFly: Boolean
     val externalLinterArguments: String
+    val externalLinterMaybeIncorrectFixes: Boolean
     val compileAllTargets: Boolean
     val useOffline: Boolean
+    val evaluateBuildScripts: Boolean
     val macroExpansionEngine: MacroExpansionEngine
+    val macroExpansionDepthLimit: Int
+    val macroExpansionTokenLimit: Int
     val showTestToolWindow: Boolean
     val doctestInjectionEnabled: Boolean
     val runRustfmtOnSave: Boolean
     val useSkipChildren: Boolean
+    val buildTarget: String?
 
     /*
      * Show a dialog for toolchain configuration
@@ -95,6 +121,10 @@ interface RustProjectSettingsService {
     fun configureToolchain()
 
     companion object {
+        const val DEFAULT_MACRO_EXPANSION_DEPTH_LIMIT: Int = 64
+        const val MAX_MACRO_EXPANSION_DEPTH_LIMIT: Int = 256
+        const val DEFAULT_MACRO_EXPANSION_TOKEN_LIMIT: Int = 1_000_000
+
         val RUST_SETTINGS_TOPIC: Topic<RustSettingsListener> = Topic(
             "rust settings changes",
             RustSettingsListener::class.java
@@ -42,13 +42,18 @@ class RustProjectSettingsServiceImpl(
     override val externalLinter: ExternalLinter get() = state.externalLinter
     override val runExternalLinterOnTheFly: Boolean get() = state.runExternalLinterOnTheFly
     override val externalLinterArguments: String get() = state.externalLinterArguments
+    override val externalLinterMaybeIncorrectFixes: Boolean get() = state.externalLinterMaybeIncorrectFixes
     override val compileAllTargets: Boolean get() = state.compileAllTargets
     override val useOffline: Boolean get() = state.useOffline
+    override val evaluateBuildScripts: Boolean get() = state.evaluateBuildScripts
     override val macroExpansionEngine: MacroExpansionEngine get() = state.macroExpansionEngine
+    override val macroExpansionDepthLimit: Int get() = state.macroExpansionDepthLimit
+    override val macroExpansionTokenLimit: Int get() = state.macroExpansionTokenLimit
     override val showTestToolWindow: Boolean get() = state.showTestToolWindow
     override val doctestInjectionEnabled: Boolean get() = state.doctestInjectionEnabled
     override val runRustfmtOnSave: Boolean get() = state.runRustfmtOnSave
     override val useSkipChildren: Boolean get() = state.useSkipChildren
+    override val buildTarget: String? get() = state.buildTarget
 
     override fun getState(): Element {
         val element = Element(serviceName)
@@ -23,9 +23,13 @@ const val EXPLICIT_PATH_TO_STDLIB: String = "explicitPathToStdlib"
 const val EXTERNAL_LINTER: String = "externalLinter"
 const val RUN_EXTERNAL_LINTER_ON_THE_FLY: String = "runExternalLinterOnTheFly"
 const val EXTERNAL_LINTER_ARGUMENTS: String = "externalLinterArguments"
+const val EXTERNAL_LINTER_MAYBE_INCORRECT_FIXES: String = "externalLinterMaybeIncorrectFixes"
 const val COMPILE_ALL_TARGETS: String = "compileAllTargets"
 const val USE_OFFLINE: String = "useOffline"
+const val EVALUATE_BUILD_SCRIPTS: String = "evaluateBuildScripts"
 const val MACRO_EXPANSION_ENGINE: String = "macroExpansionEngine"
+const val MACRO_EXPANSION_DEPTH_LIMIT: String = "macroExpansionDepthLimit"
+const val MACRO_EXPANSION_TOKEN_LIMIT: String = "macroExpansionTokenLimit"
 const val SHOW_TEST_TOOL_WINDOW: String = "showTestToolWindow"
 const val DOCTEST_INJECTION_ENABLED: String = "doctestInjectionEnabled"
 const val RUN_RUSTFMT_ON_SAVE: String = "runRustfmtOnSave"
@@ -0,0 +1,86 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.project.toolwindow
+
+import com.intellij.openapi.actionSystem.AnAction
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.editor.colors.EditorColorsManager
+import com.intellij.openapi.editor.colors.EditorFontType
+import com.intellij.openapi.fileEditor.FileDocumentManager
+import com.intellij.openapi.fileEditor.OpenFileDescriptor
+import com.intellij.openapi.project.DumbAware
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.DialogBuilder
+import com.intellij.openapi.ui.popup.JBPopupFactory
+import com.intellij.openapi.vfs.VirtualFile
+import com.intellij.ui.components.JBScrollPane
+import com.intellij.ui.components.JBTextArea
+import org.rust.cargo.CargoConstants
+import org.rust.cargo.project.toolwindow.CargoProjectTreeStructure.CargoSimpleNode
+
+private val AnActionEvent.dependency: CargoSimpleNode.Dependency?
+    get() = getData(CargoToolWindow.SELECTED_DEPENDENCY)
+
+/** Shows the chains of packages which pull the selected dependency like `cargo tree -i` */
+class ShowDependencyPathsAction : AnAction(), DumbAware {
+    override fun update(e: AnActionEvent) {
+        e.presentation.isEnabledAndVisible = e.project != null && e.dependency != null
+    }
+
+    override fun actionPerformed(e: AnActionEvent) {
+        val project = e.project ?: return
+        val dependency = e.dependency ?: return
+        val textArea = JBTextArea(dependency.graph.renderInverseTree(dependency.pkg)).apply {
+            isEditable = false
+            font = EditorColorsManager.getInstance().globalScheme.getFont(EditorFontType.PLAIN)
+        }
+        DialogBuilder(project).apply {
+            setTitle("Paths to ${dependency.pkg.nameWithVersion}")
+            setCenterPanel(JBScrollPane(textArea))
+            removeAllActions()
+            addOkAction()
+        }.show()
+    }
+}
+
+/** Navigates to the dependencies of workspace members which pull different versions of the selected crate */
+class GoToConflictingDependencyDeclarationsAction : AnAction(), DumbAware {
+    override fun update(e: AnActionEvent) {
+        e.presentation.isVisible = e.dependency != null
+        e.presentation.isEnabled = e.project != null && e.dependency?.isDuplicated == true
+    }
+
+    override fun actionPerformed(e: AnActionEvent) {
+        val project = e.project ?: return
+        val dependency = e.dependency ?: return
+        val graph = dependency.graph
+        val declarations = graph.duplicates[dependency.pkg.name].orEmpty().flatMap { version ->
+            graph.findPullingDependencies(version).mapNotNull { (member, memberDependency) ->
+                val manifest = member.contentRoot?.findChild(CargoConstants.MANIFEST_FILE) ?: return@mapNotNull null
+                val text = FileDocumentManager.getInstance().getDocument(manifest)?.text ?: return@mapNotNull null
+                val offset = findDependencyDeclarationOffset(text, memberDependency) ?: 0
+                val presentation = "${member.name}: ${memberDependency.pkg.nameWithVersion} pulls ${version.nameWithVersion}"
+                DependencyDeclaration(manifest, offset, presentation)
+            }
+        }.distinct()
+
+        when (declarations.size) {
+            0 -> return
+            1 -> declarations.single().navigate(project)
+            else -> JBPopupFactory.getInstance()
+                .createPopupChooserBuilder(declarations)
+                .setTitle("Dependencies pulling ${dependency.pkg.name}")
+                .setItemChosenCallback { it.navigate(project) }
+                .createPopup()
+                .showInBestPositionFor(e.dataContext)
+        }
+    }
+
+    private data class DependencyDeclaration(val manifest: VirtualFile, val offset: Int, val presentation: String) {
+        fun navigate(project: Project) = OpenFileDescriptor(project, manifest, offset).navigate(true)
+        override fun toString(): String = presentation
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.project.toolwindow
+
+import com.intellij.util.text.SemVer
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.project.workspace.CargoWorkspace.Package
+import org.rust.cargo.project.workspace.PackageOrigin
+import java.util.*
+
+/**
+ * Resolved dependency graph of [workspace] shown in the Cargo tool window.
+ * The standard library is not a part of the graph
+ */
+class CargoDependencyGraph(val workspace: CargoWorkspace) {
+    private val packages: List<Package> = workspace.packages.filter { it.origin != PackageOrigin.STDLIB }
+
+    private val dependents: Map<Package, List<Package>> = run {
+        val dependents = hashMapOf<Package, MutableList<Package>>()
+        for (pkg in packages) {
+            for (dependency in dependenciesOf(pkg)) {
+                dependents.getOrPut(dependency.pkg) { mutableListOf() } += pkg
+            }
+        }
+        dependents.mapValues { (_, pkgs) -> pkgs.sortedBy { it.name } }
+    }
+
+    val members: List<Package> = packages.filter { it.origin == PackageOrigin.WORKSPACE }.sortedBy { it.name }
+
+    /** Crates which are present in the graph in several versions, sorted by name */
+    val duplicates: SortedMap<String, List<Package>> = packages
+        .groupBy { it.name }
+        .filterValues { it.size > 1 }
+        .mapValues { (_, versions) -> versions.sortedBy { SemVer.parseFromText(it.version) } }
+        .toSortedMap()
+
+    fun isDuplicated(pkg: Package): Boolean = pkg.name in duplicates
+
+    fun dependenciesOf(pkg: Package): List<CargoWorkspace.Dependency> =
+        pkg.dependencies.filter { it.pkg.origin != PackageOrigin.STDLIB }.sortedBy { it.pkg.name }
+
+    /** Packages which directly or transitively depend on a crate whose name contains [filter], and these crates */
+    fun packagesMatching(filter: String): Set<Package> =
+        withDependents(packages.filter { it.name.contains(filter, ignoreCase = true) })
+
+    /**
+     * Direct dependencies of workspace members which pull [pkg] into the graph,
+     * i.e. the `Cargo.toml` lines to look at when [pkg] conflicts with another version of the crate
+     */
+    fun findPullingDependencies(pkg: Package): List<Pair<Package, CargoWorkspace.Dependency>> {
+        val pulling = withDependents(listOf(pkg))
+        return members.flatMap { member ->
+            dependenciesOf(member).filter { it.pkg in pulling }.map { member to it }
+        }
+    }
+
+    /** Renders the chains of packages which depend on [pkg] like `cargo tree -i` does */
+    fun renderInverseTree(pkg: Package): String = buildString {
+        val rendered = hashSetOf<Package>()
+
+        fun render(pkg: Package, prefix: String, childrenPrefix: String) {
+            append(prefix).append(pkg.nameWithVersion)
+            val parents = dependents[pkg].orEmpty()
+            if (!rendered.add(pkg)) {
+                // Like cargo, don't repeat already shown subtrees
+                if (parents.isNotEmpty()) append(" (*)")
+                append("\n")
+                return
+            }
+            append("\n")
+            for ((index, parent) in parents.withIndex()) {
+                val isLast = index == parents.lastIndex
+                render(
+                    parent,
+                    childrenPrefix + if (isLast) "└── " else "├── ",
+                    childrenPrefix + if (isLast) "    " else "│   "
+                )
+            }
+        }
+
+        render(pkg, "", "")
+    }
+
+    private fun withDependents(pkgs: Collection<Package>): Set<Package> {
+        val result = hashSetOf<Package>()
+        val queue = ArrayDeque(pkgs)
+        while (queue.isNotEmpty()) {
+            val pkg = queue.poll()
+            if (result.add(pkg)) {
+                queue += dependents[pkg].orEmpty()
+            }
+        }
+        return result
+    }
+}
+
+/** Keeps the dependency tree nodes which lead to the crates whose names contain [text] */
+class CargoDependencyFilter(val graph: CargoDependencyGraph, val text: String) {
+    private val matching: Set<Package>? by lazy {
+        if (text.isBlank()) null else graph.packagesMatching(text.trim())
+    }
+
+    fun accepts(pkg: Package): Boolean = matching?.contains(pkg) ?: true
+
+    fun acceptsName(name: String): Boolean = name.contains(text.trim(), ignoreCase = true)
+}
+
+val Package.nameWithVersion: String get() = "$name v$version"
+
+val Package.enabledFeatures: List<String>
+    get() = features.filter { it.state == CargoWorkspace.FeatureState.Enabled }.map { it.name }.sorted()
+
+/**
+ * Finds the offset of the line of `Cargo.toml` which declares [dependency], either `name = ...`
+ * in a dependency table or `[dependencies.name]` table header
+ */
+fun findDependencyDeclarationOffset(manifestText: String, dependency: CargoWorkspace.Dependency): Int? =
+    findDependencyKeyOffset(manifestText, dependency.name) ?: findDependencyKeyOffset(manifestText, dependency.pkg.name)
+
+private fun findDependencyKeyOffset(manifestText: String, name: String): Int? {
+    val normName = name.replace('-', '_')
+    fun isSameName(key: String): Boolean = key.trim().trim('"', '\'').replace('-', '_') == normName
+
+    var lineOffset = 0
+    var isDependencyTable = false
+    for (line in manifestText.split('\n')) {
+        val indent = line.length - line.trimStart().length
+        val header = TABLE_HEADER_REGEX.find(line)
+        if (header != null) {
+            val names = header.groupValues[1].split('.')
+            val tableIndex = names.indexOfLast { it.trim() in DEPENDENCY_TABLES }
+            isDependencyTable = tableIndex != -1 && tableIndex == names.lastIndex
+            if (tableIndex != -1 && tableIndex == names.lastIndex - 1 && isSameName(names.last())) {
+                return lineOffset + indent
+            }
+        } else if (isDependencyTable) {
+            val key = KEY_REGEX.find(line)?.groupValues?.get(1)
+            if (key != null && isSameName(key)) return lineOffset + indent
+        }
+        lineOffset += line.length + 1
+    }
+    return null
+}
+
+private val DEPENDENCY_TABLES: Set<String> = setOf("dependencies", "dev-dependencies", "build-dependencies")
+private val TABLE_HEADER_REGEX: Regex = Regex("""^\s*\[\[?\s*([^\[\]]+?)\s*]""")
+private val KEY_REGEX: Regex = Regex("""^\s*(["']?[\w\-]+["']?)\s*[=.]""")
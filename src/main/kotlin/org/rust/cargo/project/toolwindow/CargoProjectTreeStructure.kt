@@ -30,7 +30,8 @@ class CargoProjectTreeStructure(
 ) : SimpleTreeStructure() {
 
     private val treeModel = StructureTreeModel(this, parentDisposable)
-    private var root = CargoSimpleNode.Root(cargoProjects)
+    private var filter: String = ""
+    private var root = CargoSimpleNode.Root(cargoProjects, filter)
 
     init {
         tree.model = AsyncTreeModel(treeModel, parentDisposable)
@@ -40,35 +41,56 @@ class CargoProjectTreeStructure(
 
     fun updateCargoProjects(cargoProjects: List<CargoProject>) {
         this.cargoProjects = cargoProjects
-        root = CargoSimpleNode.Root(cargoProjects)
+        root = CargoSimpleNode.Root(cargoProjects, filter)
+        treeModel.invalidate()
+    }
+
+    /** Shows only the dependencies which lead to the crates whose names contain [filter] */
+    fun updateFilter(filter: String) {
+        this.filter = filter
+        root = CargoSimpleNode.Root(cargoProjects, filter)
         treeModel.invalidate()
     }
 
     sealed class CargoSimpleNode(parent: SimpleNode?) : CachingSimpleNode(parent) {
         abstract fun toTestString(): String
 
-        class Root(private val cargoProjects: List<CargoProject>) : CargoSimpleNode(null) {
-            override fun buildChildren(): Array<SimpleNode> = cargoProjects.map { Project(it, this) }.sortedBy { it.name }.toTypedArray()
+        class Root(private val cargoProjects: List<CargoProject>, private val filter: String) : CargoSimpleNode(null) {
+            override fun buildChildren(): Array<SimpleNode> =
+                cargoProjects.map { Project(it, filter, this) }.sortedBy { it.name }.toTypedArray()
             override fun getName(): String = ""
             override fun toTestString(): String = "Root"
         }
 
-        class Project(val cargoProject: CargoProject, parent: SimpleNode) : CargoSimpleNode(parent) {
+        class Project(
+            val cargoProject: CargoProject,
+            private val filter: String,
+            parent: SimpleNode
+        ) : CargoSimpleNode(parent) {
 
             init {
                 icon = CargoIcons.ICON
             }
 
             override fun buildChildren(): Array<SimpleNode> {
-                val (ourPackage, workspaceMembers) = cargoProject.workspace
+                val workspace = cargoProject.workspace
+                // Children are built in background, so it's fine to build the whole dependency graph here
+                val dependencyFilter = workspace?.let { CargoDependencyFilter(CargoDependencyGraph(it), filter) }
+                val (ourPackage, workspaceMembers) = workspace
                     ?.packages
                     ?.filter { it.origin == PackageOrigin.WORKSPACE }
                     .orEmpty()
                     .sortedBy { it.name }
                     .partition { it.rootDirectory == cargoProject.workingDirectory }
                 val childrenNodes = mutableListOf<SimpleNode>()
-                ourPackage.mapTo(childrenNodes) { Targets(it.targets, this) }
-                workspaceMembers.mapTo(childrenNodes) { WorkspaceMember(it, this) }
+                for (pkg in ourPackage) {
+                    childrenNodes += Targets(pkg.targets, this)
+                    dependencyFilter?.let { Dependencies.createIfNotEmpty(pkg, it, this) }?.let { childrenNodes += it }
+                }
+                workspaceMembers.mapTo(childrenNodes) { WorkspaceMember(it, dependencyFilter, this) }
+                if (dependencyFilter != null && dependencyFilter.graph.duplicates.isNotEmpty()) {
+                    childrenNodes += DuplicateDependencies(dependencyFilter, this)
+                }
                 return childrenNodes.toTypedArray()
             }
 
@@ -96,13 +118,21 @@ class CargoProjectTreeStructure(
             override fun toTestString(): String = "Project"
         }
 
-        class WorkspaceMember(val pkg: CargoWorkspace.Package, parent: SimpleNode) : CargoSimpleNode(parent) {
+        class WorkspaceMember(
+            val pkg: CargoWorkspace.Package,
+            private val dependencyFilter: CargoDependencyFilter?,
+            parent: SimpleNode
+        ) : CargoSimpleNode(parent) {
 
             init {
                 icon = CargoIcons.ICON
             }
 
-            override fun buildChildren(): Array<SimpleNode> = arrayOf(Targets(pkg.targets, this))
+            override fun buildChildren(): Array<SimpleNode> =
+                listOfNotNull(
+                    Targets(pkg.targets, this),
+                    dependencyFilter?.let { Dependencies.createIfNotEmpty(pkg, it, this) }
+                ).toTypedArray()
             override fun getName(): String = pkg.name
             override fun toTestString(): String = "WorkspaceMember($name)"
         }
@@ -146,5 +176,122 @@ class CargoProjectTreeStructure(
                     Unknown -> null
                 }
         }
+
+        /** Direct dependencies of a workspace package */
+        class Dependencies private constructor(
+            val pkg: CargoWorkspace.Package,
+            private val dependencies: List<CargoWorkspace.Dependency>,
+            private val filter: CargoDependencyFilter,
+            parent: SimpleNode
+        ) : CargoSimpleNode(parent) {
+
+            init {
+                icon = CargoIcons.DEPENDENCIES
+            }
+
+            override fun buildChildren(): Array<SimpleNode> =
+                dependencies.map { Dependency(it.pkg, filter, setOf(pkg), this) }.toTypedArray()
+
+            override fun getName(): String = "dependencies"
+            override fun toTestString(): String = "Dependencies"
+
+            companion object {
+                fun createIfNotEmpty(pkg: CargoWorkspace.Package, filter: CargoDependencyFilter, parent: SimpleNode): Dependencies? {
+                    val dependencies = filter.graph.dependenciesOf(pkg).filter { filter.accepts(it.pkg) }
+                    if (dependencies.isEmpty()) return null
+                    return Dependencies(pkg, dependencies, filter, parent)
+                }
+            }
+        }
+
+        /** A resolved package in the dependency tree. [ancestors] are used to cut dependency cycles */
+        class Dependency(
+            val pkg: CargoWorkspace.Package,
+            private val filter: CargoDependencyFilter,
+            private val ancestors: Set<CargoWorkspace.Package>,
+            parent: SimpleNode
+        ) : CargoSimpleNode(parent) {
+            val graph: CargoDependencyGraph get() = filter.graph
+            val isDuplicated: Boolean get() = graph.isDuplicated(pkg)
+
+            init {
+                icon = CargoIcons.DEPENDENCY
+            }
+
+            override fun buildChildren(): Array<SimpleNode> {
+                if (pkg in ancestors) return emptyArray()
+                return graph.dependenciesOf(pkg)
+                    .filter { filter.accepts(it.pkg) }
+                    .map { Dependency(it.pkg, filter, ancestors + pkg, this) }
+                    .toTypedArray()
+            }
+
+            override fun getName(): String = pkg.name
+
+            override fun update(presentation: PresentationData) {
+                var attrs = SimpleTextAttributes.REGULAR_ATTRIBUTES
+                if (isDuplicated) {
+                    attrs = attrs.derive(SimpleTextAttributes.STYLE_WAVED, null, null, JBColor.ORANGE)
+                    val versions = graph.duplicates[pkg.name].orEmpty().joinToString { it.version }
+                    presentation.tooltip = "Crate `${pkg.name}` is present in several versions: $versions"
+                }
+                presentation.addText(pkg.nameWithVersion, attrs)
+                if (pkg in ancestors) {
+                    presentation.addText(" (cycle)", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                }
+                val features = pkg.enabledFeatures
+                if (features.isNotEmpty()) {
+                    presentation.addText(" features: ${features.joinToString()}", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                }
+                presentation.setIcon(icon)
+            }
+
+            override fun toTestString(): String {
+                val duplicated = if (isDuplicated) " [duplicated]" else ""
+                val features = pkg.enabledFeatures.takeIf { it.isNotEmpty() }?.joinToString(prefix = " [", postfix = "]").orEmpty()
+                return "Dependency(${pkg.nameWithVersion}$duplicated$features)"
+            }
+        }
+
+        /** Crates which are present in the dependency graph in several versions */
+        class DuplicateDependencies(private val filter: CargoDependencyFilter, parent: SimpleNode) : CargoSimpleNode(parent) {
+
+            init {
+                icon = CargoIcons.DUPLICATE_DEPENDENCIES
+            }
+
+            override fun buildChildren(): Array<SimpleNode> = filter.graph.duplicates
+                .filterKeys { filter.acceptsName(it) }
+                .map { (name, versions) -> DuplicateDependency(name, versions, filter, this) }
+                .toTypedArray()
+
+            override fun getName(): String = "duplicate dependencies"
+            override fun toTestString(): String = "DuplicateDependencies"
+        }
+
+        class DuplicateDependency(
+            private val crateName: String,
+            private val versions: List<CargoWorkspace.Package>,
+            private val filter: CargoDependencyFilter,
+            parent: SimpleNode
+        ) : CargoSimpleNode(parent) {
+
+            init {
+                icon = CargoIcons.DEPENDENCY
+            }
+
+            override fun buildChildren(): Array<SimpleNode> =
+                versions.map { Dependency(it, filter, emptySet(), this) }.toTypedArray()
+
+            override fun getName(): String = crateName
+
+            override fun update(presentation: PresentationData) {
+                presentation.addText(crateName, SimpleTextAttributes.REGULAR_ATTRIBUTES)
+                presentation.addText(" ${versions.size} versions", SimpleTextAttributes.GRAYED_ATTRIBUTES)
+                presentation.setIcon(icon)
+            }
+
+            override fun toTestString(): String = "DuplicateDependency($crateName)"
+        }
     }
 }
@@ -26,6 +26,11 @@ class CargoProjectsTree : SimpleTree() {
         return (treeNode.userObject as? CargoSimpleNode.Project)?.cargoProject
     }
 
+    val selectedDependency: CargoSimpleNode.Dependency? get() {
+        val treeNode = selectionPath?.lastPathComponent as? DefaultMutableTreeNode ?: return null
+        return treeNode.userObject as? CargoSimpleNode.Dependency
+    }
+
     init {
         isRootVisible = false
         showsRootHandles = true
@@ -20,16 +20,23 @@ import com.intellij.openapi.wm.ToolWindowFactory
 import com.intellij.openapi.wm.ToolWindowManager
 import com.intellij.openapi.wm.ex.ToolWindowManagerEx
 import com.intellij.ui.ColorUtil
+import com.intellij.ui.DocumentAdapter
+import com.intellij.ui.PopupHandler
 import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.SearchTextField
 import com.intellij.ui.content.ContentFactory
 import com.intellij.util.ui.UIUtil
 import org.rust.cargo.project.model.CargoProject
 import org.rust.cargo.project.model.CargoProjectsService
 import org.rust.cargo.project.model.cargoProjects
 import org.rust.cargo.project.model.guessAndSetupRustProject
+import org.rust.cargo.project.toolwindow.CargoProjectTreeStructure.CargoSimpleNode
 import org.rust.cargo.runconfig.hasCargoProject
+import java.awt.BorderLayout
 import javax.swing.JComponent
 import javax.swing.JEditorPane
+import javax.swing.JPanel
+import javax.swing.event.DocumentEvent
 
 class CargoToolWindowFactory : ToolWindowFactory, Condition<Project>, DumbAware {
     override fun createToolWindowContent(project: Project, toolWindow: ToolWindow) {
@@ -59,6 +66,7 @@ private class CargoToolWindowPanel(project: Project) : SimpleToolWindowPanel(tru
     override fun getData(dataId: String): Any? =
         when {
             CargoToolWindow.SELECTED_CARGO_PROJECT.`is`(dataId) -> cargoTab.selectedProject
+            CargoToolWindow.SELECTED_DEPENDENCY.`is`(dataId) -> cargoTab.selectedDependency
             PlatformDataKeys.TREE_EXPANDER.`is`(dataId) -> cargoTab.treeExpander
             else -> super.getData(dataId)
         }
@@ -85,10 +93,25 @@ class CargoToolWindow(
     }
 
     val selectedProject: CargoProject? get() = projectTree.selectedProject
+    val selectedDependency: CargoSimpleNode.Dependency? get() = projectTree.selectedDependency
 
-    val content: JComponent = ScrollPaneFactory.createScrollPane(projectTree, 0)
+    private val dependencyFilter = SearchTextField().apply {
+        textEditor.emptyText.text = "Filter dependencies by crate name"
+        addDocumentListener(object : DocumentAdapter() {
+            override fun textChanged(e: DocumentEvent) {
+                projectStructure.updateFilter(this@apply.text)
+            }
+        })
+    }
+
+    val content: JComponent = JPanel(BorderLayout()).apply {
+        add(dependencyFilter, BorderLayout.NORTH)
+        add(ScrollPaneFactory.createScrollPane(projectTree, 0), BorderLayout.CENTER)
+    }
 
     init {
+        PopupHandler.installPopupHandler(projectTree, DEPENDENCIES_POPUP_GROUP, DEPENDENCIES_POPUP_PLACE)
+
         with(project.messageBus.connect()) {
             subscribe(CargoProjectsService.CARGO_PROJECTS_TOPIC, object : CargoProjectsService.CargoProjectsListener {
                 override fun cargoProjectsUpdated(service: CargoProjectsService, projects: Collection<CargoProject>) {
@@ -120,8 +143,14 @@ class CargoToolWindow(
         @JvmStatic
         val SELECTED_CARGO_PROJECT: DataKey<CargoProject> = DataKey.create<CargoProject>("SELECTED_CARGO_PROJECT")
 
+        @JvmStatic
+        val SELECTED_DEPENDENCY: DataKey<CargoSimpleNode.Dependency> = DataKey.create<CargoSimpleNode.Dependency>("SELECTED_CARGO_DEPENDENCY")
+
         const val CARGO_TOOLBAR_PLACE: String = "Cargo Toolbar"
 
+        private const val DEPENDENCIES_POPUP_GROUP: String = "Rust.CargoDependencies"
+        private const val DEPENDENCIES_POPUP_PLACE: String = "Cargo Dependencies Popup"
+
         private const val ID: String = "Cargo"
 
         private val LOG: Logger = Logger.getInstance(CargoToolWindow::class.java)
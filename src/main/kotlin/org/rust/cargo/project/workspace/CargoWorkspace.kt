@@ -32,6 +32,9 @@ interface CargoWorkspace {
 
     val workspaceRootPath: Path?
 
+    /** `target` directory of the workspace, `null` if unknown (e.g. for old cargo versions) */
+    val targetDirectoryPath: Path?
+
     val cfgOptions: CfgOptions
 
     /**
@@ -161,6 +164,7 @@ interface CargoWorkspace {
 private class WorkspaceImpl(
     override val manifestPath: Path,
     override val workspaceRootPath: Path?,
+    override val targetDirectoryPath: Path?,
     packagesData: Collection<CargoWorkspaceData.Package>,
     override val cfgOptions: CfgOptions
 ) : CargoWorkspace {
@@ -200,6 +204,7 @@ private class WorkspaceImpl(
         val result = WorkspaceImpl(
             manifestPath,
             workspaceRootPath,
+            targetDirectoryPath,
             packages.map { it.asPackageData() } + stdlib.crates.map { it.asPackageData(rustcInfo) },
             cfgOptions
         )
@@ -242,6 +247,7 @@ private class WorkspaceImpl(
     override fun withEdition(edition: CargoWorkspace.Edition): CargoWorkspace = WorkspaceImpl(
         manifestPath,
         workspaceRootPath,
+        targetDirectoryPath,
         packages.map { pkg ->
             // Currently, stdlib doesn't use 2018 edition
             val packageEdition = if (pkg.origin == PackageOrigin.STDLIB) pkg.edition else edition
@@ -254,6 +260,7 @@ private class WorkspaceImpl(
     override fun withCfgOptions(cfgOptions: CfgOptions): CargoWorkspace = WorkspaceImpl(
         manifestPath,
         workspaceRootPath,
+        targetDirectoryPath,
         packages.map { it.asPackageData() },
         cfgOptions
     ).withDependenciesOf(this)
@@ -272,7 +279,8 @@ private class WorkspaceImpl(
             // handle cycles here.
 
             val workspaceRootPath = data.workspaceRoot?.let { Paths.get(it) }
-            val result = WorkspaceImpl(manifestPath, workspaceRootPath, data.packages, cfgOptions)
+            val targetDirectoryPath = data.targetDirectory?.let { Paths.get(it) }
+            val result = WorkspaceImpl(manifestPath, workspaceRootPath, targetDirectoryPath, data.packages, cfgOptions)
             // Fill package dependencies
             run {
                 val idToPackage = result.packages.associateBy { it.id }
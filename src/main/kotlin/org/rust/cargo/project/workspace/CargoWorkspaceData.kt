@@ -19,7 +19,8 @@ typealias PackageId = String
 data class CargoWorkspaceData(
     val packages: List<Package>,
     val dependencies: Map<PackageId, Set<Dependency>>,
-    val workspaceRoot: String? = null
+    val workspaceRoot: String? = null,
+    val targetDirectory: String? = null
 ) {
     data class Package(
         val id: PackageId,
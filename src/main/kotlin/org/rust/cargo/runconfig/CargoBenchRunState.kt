@@ -0,0 +1,65 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig
+
+import com.google.common.annotations.VisibleForTesting
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.psi.search.GlobalSearchScopes
+import org.rust.cargo.runconfig.command.CargoCommandConfiguration
+import org.rust.cargo.runconfig.console.CargoBenchConsoleBuilder
+import org.rust.cargo.toolchain.CargoCommandLine
+
+class CargoBenchRunState(
+    environment: ExecutionEnvironment,
+    runConfiguration: CargoCommandConfiguration,
+    config: CargoCommandConfiguration.CleanConfiguration.Ok
+) : CargoRunStateBase(environment, runConfiguration, config) {
+    init {
+        val scope = GlobalSearchScopes.executionScope(environment.project, environment.runProfile)
+        consoleBuilder = CargoBenchConsoleBuilder(environment.project, scope)
+        val baselineMode = runConfiguration.benchBaselineMode
+        val baseline = runConfiguration.benchBaseline
+        addCommandLinePatch { patchBaselineArgs(it, baselineMode, baseline) }
+        createFilters(cargoProject).forEach { consoleBuilder.addFilter(it) }
+    }
+
+    companion object {
+        /** Passes `--save-baseline <name>` or `--baseline <name>` to Criterion overriding the ones from the command */
+        @VisibleForTesting
+        fun patchBaselineArgs(commandLine: CargoCommandLine, mode: BenchBaselineMode, baseline: String): CargoCommandLine {
+            val option = mode.option ?: return commandLine
+            if (baseline.isBlank()) return commandLine
+            val (pre, post) = commandLine.splitOnDoubleDash()
+            val patchedPost = mutableListOf<String>()
+            val iterator = post.iterator()
+            while (iterator.hasNext()) {
+                val arg = iterator.next()
+                if (arg in BASELINE_OPTIONS) {
+                    if (iterator.hasNext()) iterator.next()
+                } else {
+                    patchedPost.add(arg)
+                }
+            }
+            patchedPost.add(option)
+            patchedPost.add(baseline.trim())
+            return commandLine.copy(additionalArguments = pre + "--" + patchedPost)
+        }
+
+        private val BASELINE_OPTIONS: List<String> = BenchBaselineMode.values().mapNotNull { it.option }
+    }
+}
+
+enum class BenchBaselineMode(val index: Int, val title: String, val option: String?) {
+    NONE(0, "None", null),
+    SAVE(1, "Save as baseline", "--save-baseline"),
+    COMPARE(2, "Compare against baseline", "--baseline");
+
+    override fun toString(): String = title
+
+    companion object {
+        fun fromIndex(index: Int): BenchBaselineMode = values().find { it.index == index } ?: NONE
+    }
+}
@@ -9,10 +9,12 @@ import com.google.common.annotations.VisibleForTesting
 import com.intellij.execution.DefaultExecutionResult
 import com.intellij.execution.ExecutionResult
 import com.intellij.execution.Executor
+import com.intellij.execution.configuration.EnvironmentVariablesData
 import com.intellij.execution.configurations.RunConfiguration
 import com.intellij.execution.runners.ExecutionEnvironment
 import com.intellij.execution.runners.ProgramRunner
 import com.intellij.execution.testframework.autotest.ToggleAutoTestAction
+import com.intellij.execution.testframework.sm.runner.ui.SMTRunnerConsoleView
 import com.intellij.util.text.SemVer
 import org.rust.cargo.runconfig.buildtool.CargoPatch
 import org.rust.cargo.runconfig.command.CargoCommandConfiguration
@@ -25,17 +27,28 @@ import java.time.LocalDate
 class CargoTestRunState(
     environment: ExecutionEnvironment,
     runConfiguration: CargoCommandConfiguration,
-    config: CargoCommandConfiguration.CleanConfiguration.Ok
+    config: CargoCommandConfiguration.CleanConfiguration.Ok,
+    useNextest: Boolean = false,
+    /** If not null, only tests with these names are run. Supported only by `cargo nextest` */
+    private val nextestFilters: List<String>? = null
 ) : CargoRunStateBase(environment, runConfiguration, config) {
+    /** Falls back to `cargo test` if the command line isn't supported by `cargo nextest` */
+    val useNextest: Boolean = useNextest && patchNextestCommandLine(commandLine, nextestFilters) != null
 
     private val cargoTestPatch: CargoPatch = { commandLine ->
         val rustcVer = cargoProject?.rustcInfo?.version
         commandLine.copy(additionalArguments = patchArgs(commandLine, rustcVer))
     }
 
+    private val nextestPatch: CargoPatch = { patchNextestCommandLine(it, nextestFilters) ?: it }
+
     init {
-        consoleBuilder = CargoTestConsoleBuilder(environment.runProfile as RunConfiguration, environment.executor)
-        addCommandLinePatch(cargoTestPatch)
+        consoleBuilder = CargoTestConsoleBuilder(
+            environment.runProfile as RunConfiguration,
+            environment.executor,
+            this.useNextest
+        )
+        addCommandLinePatch(if (this.useNextest) nextestPatch else cargoTestPatch)
         createFilters(cargoProject).forEach { consoleBuilder.addFilter(it) }
     }
 
@@ -43,7 +56,12 @@ class CargoTestRunState(
         val processHandler = startProcess()
         val console = createConsole(executor)
         console?.attachToProcess(processHandler)
-        return DefaultExecutionResult(console, processHandler).apply { setRestartActions(ToggleAutoTestAction()) }
+        val rerunFailedTestsAction = (console as? SMTRunnerConsoleView)?.let { view ->
+            view.properties.createRerunFailedTestsAction(view)?.apply { setModelProvider { view.resultsViewer } }
+        }
+        return DefaultExecutionResult(console, processHandler).apply {
+            setRestartActions(*listOfNotNull(rerunFailedTestsAction, ToggleAutoTestAction()).toTypedArray())
+        }
     }
 
     companion object {
@@ -73,6 +91,102 @@ class CargoTestRunState(
             return if (post.isEmpty()) pre else pre + "--" + post
         }
 
+        /**
+         * Converts `cargo test` command line into the corresponding `cargo nextest run` one.
+         * Test name filters are converted into a filter expression like `-E 'test(=foo::bar)'`.
+         *
+         * @param filters if not null, overrides the test name filters of [commandLine]. The filters are exact
+         * @return null if [commandLine] contains options not supported by nextest, e.g. `--doc`
+         */
+        @VisibleForTesting
+        fun patchNextestCommandLine(commandLine: CargoCommandLine, filters: List<String>? = null): CargoCommandLine? {
+            val (pre, post) = commandLine.splitOnDoubleDash()
+            val args = mutableListOf("run")
+            val testNames = mutableListOf<String>()
+            var exact = false
+            var noCapture = false
+            var runIgnored: String? = null
+
+            val preIterator = pre.iterator()
+            while (preIterator.hasNext()) {
+                val arg = preIterator.next()
+                when {
+                    arg in NEXTEST_UNSUPPORTED_OPTIONS -> return null
+                    arg == "--no-fail-fast" -> Unit
+                    arg in NEXTEST_RENAMED_OPTIONS -> {
+                        args.add(NEXTEST_RENAMED_OPTIONS.getValue(arg))
+                        if (preIterator.hasNext()) args.add(preIterator.next())
+                    }
+                    arg in CARGO_OPTIONS_WITH_VALUE -> {
+                        args.add(arg)
+                        if (preIterator.hasNext()) args.add(preIterator.next())
+                    }
+                    arg.startsWith("-") -> args.add(arg)
+                    else -> testNames.add(arg)
+                }
+            }
+
+            val postIterator = post.iterator()
+            while (postIterator.hasNext()) {
+                val arg = postIterator.next()
+                when {
+                    arg == "--exact" -> exact = true
+                    arg == "--nocapture" -> noCapture = true
+                    arg == "--ignored" -> runIgnored = "ignored-only"
+                    arg == "--include-ignored" -> runIgnored = "all"
+                    arg == "--show-output" || arg.startsWith("--format=") -> Unit
+                    arg == "-Z" || arg == "--format" -> if (postIterator.hasNext()) postIterator.next()
+                    arg == "--test-threads" -> {
+                        args.add(arg)
+                        if (postIterator.hasNext()) args.add(postIterator.next())
+                    }
+                    arg.startsWith("--test-threads=") -> args.add(arg)
+                    arg.startsWith("-") -> return null
+                    else -> testNames.add(arg)
+                }
+            }
+
+            if (commandLine.allFeatures && "--all-features" !in args) args.add("--all-features")
+            if (noCapture || commandLine.nocapture) args.add("--no-capture")
+            if (runIgnored != null) args.addAll(listOf("--run-ignored", runIgnored))
+            args.addAll(listOf("--no-fail-fast", "--message-format", "libtest-json"))
+
+            val names = filters ?: testNames
+            val exactNames = exact || filters != null
+            if (names.isNotEmpty()) {
+                val expression = names.joinToString(" | ") { if (exactNames) "test(=$it)" else "test($it)" }
+                args.addAll(listOf("-E", expression))
+            }
+
+            val environmentVariables = commandLine.environmentVariables.let {
+                EnvironmentVariablesData.create(it.envs + (NEXTEST_LIBTEST_JSON_ENV_VAR to "1"), it.isPassParentEnvs)
+            }
+            return commandLine.copy(
+                command = "nextest",
+                additionalArguments = args,
+                environmentVariables = environmentVariables,
+                allFeatures = false,
+                nocapture = false
+            )
+        }
+
+        /** Nextest provides libtest-compatible JSON output only if this variable is set */
+        private const val NEXTEST_LIBTEST_JSON_ENV_VAR: String = "NEXTEST_EXPERIMENTAL_LIBTEST_JSON"
+
+        private val NEXTEST_UNSUPPORTED_OPTIONS: List<String> = listOf("--doc", "--no-run", "-Z")
+
+        /** `cargo test` options which are named differently in `cargo nextest run` */
+        private val NEXTEST_RENAMED_OPTIONS: Map<String, String> = mapOf(
+            "--profile" to "--cargo-profile",
+            "-j" to "--build-jobs",
+            "--jobs" to "--build-jobs"
+        )
+
+        private val CARGO_OPTIONS_WITH_VALUE: List<String> = listOf(
+            "-p", "--package", "--exclude", "--bin", "--example", "--test", "--bench",
+            "-F", "--features", "--target", "--target-dir", "--manifest-path", "--color"
+        )
+
         private fun checkShowOutputSupport(ver: RustcVersion?): Boolean {
             if (ver == null) return false
             // --show-output is supported since 1.39.0-nightly/dev with a build date later than 2019-08-27
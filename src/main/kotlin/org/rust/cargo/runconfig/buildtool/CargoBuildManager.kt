@@ -56,7 +56,10 @@ import java.util.concurrent.Future
 object CargoBuildManager {
     private val LOG_NOTIFICATION_GROUP: NotificationGroup = NotificationGroup.logOnlyGroup("Build Log")
 
-    private val BUILDABLE_COMMANDS: List<String> = listOf("run", "test")
+    private val BUILDABLE_COMMANDS: List<String> = listOf("run", "test", "bench")
+
+    /** Commands that produce test harness binaries */
+    val TEST_COMMANDS: List<String> = listOf("test", "bench")
 
     @JvmField
     val CANCELED_BUILD_RESULT: Future<CargoBuildResult> =
@@ -104,7 +107,7 @@ object CargoBuildManager {
             environment = environment,
             taskName = "Build",
             progressTitle = "Building...",
-            isTestBuild = state.commandLine.command == "test"
+            isTestBuild = state.commandLine.command in TEST_COMMANDS
         )) {
             val buildProgressListener = if (isUnitTestMode) {
                 mockBuildProgressListener ?: EmptyBuildProgressListener
@@ -207,7 +210,7 @@ object CargoBuildManager {
         val args = ParametersListUtil.parse(configuration.command)
         return when (val command = args.firstOrNull()) {
             "build" -> true
-            "test" -> {
+            "test", "bench" -> {
                 val additionalArguments = args.drop(1)
                 val (commandArguments, _) = parseArgs(command, additionalArguments)
                 "--no-run" in commandArguments
@@ -232,7 +235,7 @@ object CargoBuildManager {
         buildConfiguration.name = "Build `${buildConfiguration.name}`"
         buildConfiguration.command = when (command) {
             "run" -> ParametersListUtil.join("build", *commandArguments.toTypedArray())
-            "test" -> ParametersListUtil.join("test", "--no-run", *commandArguments.toTypedArray())
+            "test", "bench" -> ParametersListUtil.join(command, "--no-run", *commandArguments.toTypedArray())
             else -> return null
         }
         return buildConfiguration
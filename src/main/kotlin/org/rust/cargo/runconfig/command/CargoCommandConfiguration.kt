@@ -20,11 +20,14 @@ import org.rust.cargo.project.model.CargoProject
 import org.rust.cargo.project.model.cargoProjects
 import org.rust.cargo.project.settings.rustSettings
 import org.rust.cargo.project.settings.toolchain
+import org.rust.cargo.runconfig.BenchBaselineMode
+import org.rust.cargo.runconfig.CargoBenchRunState
 import org.rust.cargo.runconfig.CargoRunState
 import org.rust.cargo.runconfig.CargoTestRunState
 import org.rust.cargo.runconfig.buildtool.CargoBuildTaskProvider
 import org.rust.cargo.runconfig.ui.CargoCommandConfigurationEditor
 import org.rust.cargo.toolchain.BacktraceMode
+import org.rust.cargo.toolchain.Cargo
 import org.rust.cargo.toolchain.CargoCommandLine
 import org.rust.cargo.toolchain.RustChannel
 import org.rust.cargo.toolchain.RustToolchain
@@ -48,10 +51,13 @@ class CargoCommandConfiguration(
     var command: String = "run"
     var allFeatures: Boolean = false
     var nocapture: Boolean = false
+    var useNextest: Boolean = false
     var emulateTerminal: Boolean = false
     var backtrace: BacktraceMode = BacktraceMode.SHORT
     var workingDirectory: Path? = project.cargoProjects.allProjects.firstOrNull()?.workingDirectory
     var env: EnvironmentVariablesData = EnvironmentVariablesData.DEFAULT
+    var benchBaselineMode: BenchBaselineMode = BenchBaselineMode.NONE
+    var benchBaseline: String = ""
 
     override fun getBeforeRunTasks(): List<BeforeRunTask<*>> {
         val tasks = super.getBeforeRunTasks()
@@ -68,9 +74,12 @@ class CargoCommandConfiguration(
         element.writeString("command", command)
         element.writeBool("allFeatures", allFeatures)
         element.writeBool("nocapture", nocapture)
+        element.writeBool("useNextest", useNextest)
         element.writeBool("emulateTerminal", emulateTerminal)
         element.writeEnum("backtrace", backtrace)
         element.writePath("workingDirectory", workingDirectory)
+        element.writeEnum("benchBaselineMode", benchBaselineMode)
+        element.writeString("benchBaseline", benchBaseline)
         env.writeExternal(element)
     }
 
@@ -84,9 +93,12 @@ class CargoCommandConfiguration(
         element.readString("command")?.let { command = it }
         element.readBool("allFeatures")?.let { allFeatures = it }
         element.readBool("nocapture")?.let { nocapture = it }
+        element.readBool("useNextest")?.let { useNextest = it }
         element.readBool("emulateTerminal")?.let { emulateTerminal = it }
         element.readEnum<BacktraceMode>("backtrace")?.let { backtrace = it }
         element.readPath("workingDirectory")?.let { workingDirectory = it }
+        element.readEnum<BenchBaselineMode>("benchBaselineMode")?.let { benchBaselineMode = it }
+        element.readString("benchBaseline")?.let { benchBaseline = it }
         env = EnvironmentVariablesData.readExternal(element)
     }
 
@@ -119,7 +131,10 @@ class CargoCommandConfiguration(
                 project.rustSettings.showTestToolWindow &&
                 !command.contains("--nocapture") &&
                 !nocapture) {
-                CargoTestRunState(environment, this, it)
+                val useNextest = useNextest && !Cargo.checkNeedInstallCargoNextest(project)
+                CargoTestRunState(environment, this, it, useNextest)
+            } else if (command.startsWith("bench")) {
+                CargoBenchRunState(environment, this, it)
             } else {
                 CargoRunState(environment, this, it)
             }
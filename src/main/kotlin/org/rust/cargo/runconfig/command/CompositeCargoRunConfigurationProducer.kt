@@ -15,6 +15,7 @@ import com.intellij.openapi.util.Ref
 import com.intellij.psi.PsiElement
 import org.rust.cargo.runconfig.test.CargoBenchRunConfigurationProducer
 import org.rust.cargo.runconfig.test.CargoTestRunConfigurationProducer
+import org.rust.cargo.runconfig.test.DoctestRunConfigurationProducer
 import java.util.*
 import java.util.function.Function
 
@@ -30,7 +31,8 @@ class CompositeCargoRunConfigurationProducer : CargoRunConfigurationProducer() {
         listOf(
             CargoExecutableRunConfigurationProducer(),
             CargoTestRunConfigurationProducer(),
-            CargoBenchRunConfigurationProducer()
+            CargoBenchRunConfigurationProducer(),
+            DoctestRunConfigurationProducer()
         )
 
     override fun findExistingConfiguration(context: ConfigurationContext): RunnerAndConfigurationSettings? {
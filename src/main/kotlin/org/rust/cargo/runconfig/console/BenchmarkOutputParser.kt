@@ -0,0 +1,124 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.console
+
+data class BenchmarkResult(
+    val name: String,
+    /** A point estimate of the time per iteration, e.g. `26.251 µs` */
+    val time: String,
+    /** A point estimate of the change against the baseline, e.g. `-1.1891%` */
+    val change: String? = null,
+    val verdict: BenchmarkVerdict = BenchmarkVerdict.UNKNOWN,
+    val outliers: Int? = null
+)
+
+enum class BenchmarkVerdict {
+    UNKNOWN,
+    NO_CHANGE,
+    IMPROVED,
+    REGRESSED
+}
+
+/**
+ * Collects benchmark results from the text output of Criterion and `#[bench]` (libtest) benchmarks.
+ * Criterion output looks like
+ *
+ * ```
+ * Benchmarking fib 20: Analyzing
+ * fib 20                  time:   [26.029 µs 26.251 µs 26.505 µs]
+ *                         change: [-2.3917% -1.1891% +0.0556%] (p = 0.07 > 0.05)
+ *                         No change in performance detected.
+ * Found 12 outliers among 100 measurements (12.00%)
+ * ```
+ *
+ * Note that the name of a long benchmark is printed on a separate line before the `time:` line
+ */
+class BenchmarkOutputParser {
+    private val _results: LinkedHashMap<String, BenchmarkResult> = LinkedHashMap()
+    val results: List<BenchmarkResult> get() = _results.values.toList()
+
+    /** The benchmark whose results are being printed */
+    private var current: String? = null
+    /** The benchmark which is being run according to `Benchmarking <name>: ...` lines */
+    private var running: String? = null
+    /** A line that may contain the name of a long benchmark */
+    private var pendingName: String? = null
+
+    /** Returns `true` if [results] are changed */
+    fun processLine(line: String): Boolean {
+        val text = line.trimEnd()
+        val previousLine = pendingName
+        pendingName = null
+
+        LIBTEST_BENCH_REGEX.matchEntire(text)?.let { match ->
+            val (name, time) = match.destructured
+            current = null
+            update(BenchmarkResult(name, time))
+            return true
+        }
+
+        BENCHMARKING_REGEX.matchEntire(text)?.let { match ->
+            running = match.groupValues[1]
+            return false
+        }
+
+        TIME_REGEX.matchEntire(text)?.let { match ->
+            val (namePart, estimate) = match.destructured
+            val name = namePart.trim().ifEmpty { previousLine ?: running } ?: return false
+            current = name
+            update(BenchmarkResult(name, estimate))
+            return true
+        }
+
+        val name = current
+        if (name == null) {
+            if (text.isNotBlank() && !text[0].isWhitespace()) pendingName = text
+            return false
+        }
+        val result = _results[name] ?: return false
+
+        CHANGE_REGEX.matchEntire(text)?.let { match ->
+            update(result.copy(change = match.groupValues[1]))
+            return true
+        }
+
+        val verdict = when (text.trim()) {
+            "Performance has regressed." -> BenchmarkVerdict.REGRESSED
+            "Performance has improved." -> BenchmarkVerdict.IMPROVED
+            "No change in performance detected.", "Change within noise threshold." -> BenchmarkVerdict.NO_CHANGE
+            else -> null
+        }
+        if (verdict != null) {
+            update(result.copy(verdict = verdict))
+            return true
+        }
+
+        OUTLIERS_REGEX.matchEntire(text)?.let { match ->
+            update(result.copy(outliers = match.groupValues[1].toInt()))
+            current = null
+            return true
+        }
+
+        if (text.isNotBlank() && !text[0].isWhitespace()) {
+            // The results of the current benchmark are over
+            current = null
+            pendingName = text
+        }
+        return false
+    }
+
+    private fun update(result: BenchmarkResult) {
+        _results[result.name] = result
+    }
+
+    companion object {
+        private val LIBTEST_BENCH_REGEX: Regex = Regex("""test (\S+) \.\.\. bench:\s+(.+)""")
+        private val BENCHMARKING_REGEX: Regex = Regex("""Benchmarking (.+?)(?:: .*)?""")
+        private val TIME_REGEX: Regex = Regex("""(.*?)\s*time:\s+\[\S+ \S+ (\S+ \S+) \S+ \S+]""")
+        private val CHANGE_REGEX: Regex = Regex("""\s*change:\s+\[\S+ (\S+) \S+].*""")
+        private val OUTLIERS_REGEX: Regex = Regex("""Found (\d+) outliers among \d+ measurements.*""")
+    }
+}
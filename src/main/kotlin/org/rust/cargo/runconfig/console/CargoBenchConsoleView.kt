@@ -0,0 +1,108 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.console
+
+import com.intellij.execution.ui.ConsoleViewContentType
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.project.Project
+import com.intellij.psi.search.GlobalSearchScope
+import com.intellij.ui.ColoredTableCellRenderer
+import com.intellij.ui.JBColor
+import com.intellij.ui.JBSplitter
+import com.intellij.ui.ScrollPaneFactory
+import com.intellij.ui.SimpleTextAttributes
+import com.intellij.ui.table.JBTable
+import com.intellij.util.ui.ColumnInfo
+import com.intellij.util.ui.ListTableModel
+import javax.swing.JComponent
+import javax.swing.JTable
+import javax.swing.table.TableCellRenderer
+
+/**
+ * Console for `cargo bench` which additionally shows a table of benchmark results parsed from the output
+ */
+class CargoBenchConsoleView(
+    project: Project,
+    searchScope: GlobalSearchScope,
+    viewer: Boolean,
+    usePredefinedMessageFilter: Boolean
+) : CargoConsoleView(project, searchScope, viewer, usePredefinedMessageFilter) {
+    private val parser: BenchmarkOutputParser = BenchmarkOutputParser()
+    private val lineBuffer: StringBuilder = StringBuilder()
+
+    private val model: ListTableModel<BenchmarkResult> =
+        ListTableModel(NameColumn, TimeColumn, ChangeColumn, OutliersColumn)
+    private val splitter: JBSplitter = JBSplitter(true, 0.7f).apply {
+        secondComponent = ScrollPaneFactory.createScrollPane(JBTable(model))
+    }
+
+    override fun getComponent(): JComponent {
+        val console = super.getComponent()
+        if (splitter.firstComponent == null) {
+            splitter.firstComponent = console
+        }
+        return splitter
+    }
+
+    override fun print(text: String, contentType: ConsoleViewContentType) {
+        super.print(text, contentType)
+        if (contentType == ConsoleViewContentType.SYSTEM_OUTPUT) return
+
+        val results = synchronized(lineBuffer) {
+            lineBuffer.append(text)
+            var isChanged = false
+            while (true) {
+                val lineEnd = lineBuffer.indexOf("\n")
+                if (lineEnd == -1) break
+                isChanged = parser.processLine(lineBuffer.substring(0, lineEnd)) || isChanged
+                lineBuffer.delete(0, lineEnd + 1)
+            }
+            if (isChanged) parser.results else null
+        } ?: return
+
+        ApplicationManager.getApplication().invokeLater {
+            model.items = results
+        }
+    }
+
+    private object NameColumn : ColumnInfo<BenchmarkResult, String>("Benchmark") {
+        override fun valueOf(item: BenchmarkResult): String = item.name
+    }
+
+    private object TimeColumn : ColumnInfo<BenchmarkResult, String>("Time") {
+        override fun valueOf(item: BenchmarkResult): String = item.time
+    }
+
+    /** Regressions are red, improvements are green */
+    private object ChangeColumn : ColumnInfo<BenchmarkResult, BenchmarkResult>("Change") {
+        override fun valueOf(item: BenchmarkResult): BenchmarkResult = item
+
+        override fun getRenderer(item: BenchmarkResult): TableCellRenderer = ChangeRenderer
+    }
+
+    private object OutliersColumn : ColumnInfo<BenchmarkResult, String>("Outliers") {
+        override fun valueOf(item: BenchmarkResult): String = item.outliers?.toString().orEmpty()
+    }
+
+    private object ChangeRenderer : ColoredTableCellRenderer() {
+        override fun customizeCellRenderer(
+            table: JTable,
+            value: Any?,
+            selected: Boolean,
+            hasFocus: Boolean,
+            row: Int,
+            column: Int
+        ) {
+            val result = value as? BenchmarkResult ?: return
+            val attributes = when (result.verdict) {
+                BenchmarkVerdict.REGRESSED -> SimpleTextAttributes(SimpleTextAttributes.STYLE_PLAIN, JBColor.RED)
+                BenchmarkVerdict.IMPROVED -> SimpleTextAttributes(SimpleTextAttributes.STYLE_PLAIN, JBColor.GREEN)
+                BenchmarkVerdict.NO_CHANGE, BenchmarkVerdict.UNKNOWN -> SimpleTextAttributes.REGULAR_ATTRIBUTES
+            }
+            append(result.change.orEmpty(), attributes)
+        }
+    }
+}
@@ -21,9 +21,14 @@ open class CargoConsoleBuilder(project: Project, scope: GlobalSearchScope) : Tex
     override fun createConsole(): ConsoleView = CargoConsoleView(project, scope, isViewer, true)
 }
 
+class CargoBenchConsoleBuilder(project: Project, scope: GlobalSearchScope) : CargoConsoleBuilder(project, scope) {
+    override fun createConsole(): ConsoleView = CargoBenchConsoleView(project, scope, isViewer, true)
+}
+
 class CargoTestConsoleBuilder(
     private val config: RunConfiguration,
-    private val executor: Executor
+    private val executor: Executor,
+    private val useNextest: Boolean = false
 ) : TextConsoleBuilder() {
     private val filters: MutableList<Filter> = mutableListOf()
 
@@ -34,7 +39,7 @@ class CargoTestConsoleBuilder(
     override fun setViewer(isViewer: Boolean) {}
 
     override fun getConsole(): ConsoleView {
-        val consoleProperties = CargoTestConsoleProperties(config, executor)
+        val consoleProperties = CargoTestConsoleProperties(config, executor, useNextest)
         val consoleView = SMTestRunnerConnectionUtil.createConsole(TEST_FRAMEWORK_NAME, consoleProperties)
         filters.forEach { consoleView.addMessageFilter(it) }
         return consoleView
@@ -29,6 +29,7 @@ import com.intellij.openapi.ui.Messages
 import org.jetbrains.concurrency.AsyncPromise
 import org.jetbrains.concurrency.Promise
 import org.rust.cargo.runconfig.*
+import org.rust.cargo.runconfig.buildtool.CargoBuildManager.TEST_COMMANDS
 import org.rust.cargo.runconfig.buildtool.CargoBuildManager.getBuildConfiguration
 import org.rust.cargo.runconfig.buildtool.CargoBuildManager.isBuildConfiguration
 import org.rust.cargo.runconfig.buildtool.CargoBuildManager.isBuildToolWindowEnabled
@@ -65,7 +66,7 @@ abstract class RsAsyncRunner(
         val commandLine = state.prepareCommandLine()
         val (commandArguments, executableArguments) = parseArgs(commandLine.command, commandLine.additionalArguments)
 
-        val isTestRun = commandLine.command == "test"
+        val isTestRun = commandLine.command in TEST_COMMANDS
         val cmdHasNoRun = "--no-run" in commandLine.additionalArguments
         val buildCommand = if (isTestRun) {
             if (cmdHasNoRun) commandLine else commandLine.prependArgument("--no-run")
@@ -6,23 +6,133 @@
 package org.rust.cargo.runconfig.test
 
 import com.intellij.psi.PsiElement
-import org.rust.lang.core.psi.RsFunction
-import org.rust.lang.core.psi.ext.RsMod
-import org.rust.lang.core.psi.ext.isBench
-import org.rust.lang.core.psi.ext.processExpandedItemsExceptImplsAndUses
+import org.rust.cargo.project.settings.toolchain
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.toolchain.CargoCommandLine
+import org.rust.cargo.toolchain.RustChannel
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
 
 class CargoBenchRunConfigurationProducer : CargoTestRunConfigurationProducerBase() {
     override val commandName: String = "bench"
 
+    init {
+        registerPreferredConfigProvider { elements, climbUp -> createConfigForCriterionBenchmark(elements, climbUp) }
+    }
+
     override fun isSuitable(element: PsiElement): Boolean =
         when (element) {
-            is RsMod -> hasBenchFunction(element)
+            is RsMod -> hasBenchFunction(element) || hasCriterionGroup(element)
             is RsFunction -> element.isBench
             else -> false
         }
 
+    /**
+     * `#[bench]` functions are unstable, so they are run with the nightly toolchain
+     * if the default toolchain of the project isn't nightly and there is rustup to switch it
+     */
+    override fun adjustCommandLine(cmd: CargoCommandLine, testConfig: TestConfig): CargoCommandLine {
+        if (cmd.channel != RustChannel.DEFAULT) return cmd
+        val sourceElement = testConfig.sourceElement as? RsElement ?: return cmd
+        val isLibtestBench = when (sourceElement) {
+            is RsFunction -> sourceElement.isBench
+            is RsMod -> hasBenchFunction(sourceElement)
+            else -> false
+        }
+        if (!isLibtestBench || sourceElement.project.toolchain?.isRustupAvailable != true) return cmd
+        val channel = sourceElement.cargoProject?.rustcInfo?.version?.channel ?: return cmd
+        return if (channel == RustChannel.NIGHTLY) cmd else cmd.copy(channel = RustChannel.NIGHTLY)
+    }
+
+    private fun createConfigForCriterionBenchmark(elements: List<PsiElement>, climbUp: Boolean): TestConfig? {
+        val element = elements.singleOrNull() ?: return null
+        val function = (if (climbUp) element.ancestorOrSelf<RsFunction>() else element as? RsFunction) ?: return null
+        if (!function.isCriterionBenchmark) return null
+        // `--bench <target>` distinguishes benchmarks with the same name from different targets
+        val target = function.containingCargoTarget ?: return null
+        return CriterionBenchConfig(commandName, target, function)
+    }
+
     companion object {
         private fun hasBenchFunction(mod: RsMod): Boolean =
             mod.processExpandedItemsExceptImplsAndUses { it is RsFunction && it.isBench || it is RsMod && hasBenchFunction(it) }
+
+        private fun hasCriterionGroup(mod: RsMod): Boolean =
+            mod is RsFile && mod.criterionGroups.isNotEmpty()
+
+        /** A function registered as a benchmark target via `criterion_group!` */
+        private val RsFunction.isCriterionBenchmark: Boolean
+            get() {
+                val name = name ?: return false
+                if (owner !is RsAbstractableOwner.Free) return false
+                val file = containingFile as? RsFile ?: return false
+                return file.criterionGroups.any { name in it.criterionGroupTargets }
+            }
+
+        private val RsFile.criterionGroups: List<RsMacroCall>
+            get() = stubChildrenOfType<RsMacroCall>().filter { it.macroName == "criterion_group" }
+
+        /**
+         * Supports both `criterion_group!(benches, foo, bar)`
+         * and `criterion_group! { name = benches; config = ...; targets = foo, bar }` forms
+         */
+        private val RsMacroCall.criterionGroupTargets: List<String>
+            get() {
+                val body = macroBody ?: return emptyList()
+                val targets = CRITERION_TARGETS_REGEX.find(body)?.groupValues?.get(1) ?: body.substringAfter(',', "")
+                return targets.split(',')
+                    .map { it.trim().substringAfterLast("::") }
+                    .filter { it.isNotEmpty() }
+            }
+
+        private val CRITERION_TARGETS_REGEX: Regex = Regex("""\btargets\s*=\s*([^;}]*)""")
+    }
+}
+
+/**
+ * Runs a Criterion benchmark function via `cargo bench --bench <target> -- <filter>`.
+ * Criterion filters benchmarks by their ids (which may differ from the function name),
+ * so the filter is built from string literal ids passed to `bench_function` and similar methods
+ */
+private class CriterionBenchConfig(
+    override val commandName: String,
+    val target: CargoWorkspace.Target,
+    override val sourceElement: RsFunction
+) : TestConfig {
+    override val path: String = criterionFilter(sourceElement.criterionBenchmarkIds())
+    override val exact: Boolean = false
+
+    override val targets: List<CargoWorkspace.Target>
+        get() = listOf(target)
+
+    override val configurationName: String
+        get() = "${commandName.capitalize()} ${sourceElement.name}"
+
+    override fun cargoCommandLine(): CargoCommandLine {
+        val filterArgs = if (path.isEmpty()) emptyList() else listOf("--", path)
+        return CargoCommandLine.forTargets(targets, commandName, filterArgs)
+    }
+
+    companion object {
+        private val CRITERION_BENCHMARK_METHODS: Set<String> = setOf("bench_function", "bench_with_input", "benchmark_group")
+
+        private fun RsFunction.criterionBenchmarkIds(): List<String> =
+            descendantsOfType<RsMethodCall>()
+                .filter { it.referenceName in CRITERION_BENCHMARK_METHODS }
+                .mapNotNull { call ->
+                    // `BenchmarkId::new("id", parameter)` is also supported
+                    when (val id = call.valueArgumentList.exprList.firstOrNull()) {
+                        is RsLitExpr -> id.stringValue
+                        is RsCallExpr -> (id.valueArgumentList.exprList.firstOrNull() as? RsLitExpr)?.stringValue
+                        else -> null
+                    }
+                }
+                .distinct()
+
+        /** Criterion interprets the filter as a regex */
+        private fun criterionFilter(ids: List<String>): String =
+            ids.joinToString("|") { id -> id.replace(REGEX_SPECIAL_CHARS) { "\\${it.value}" } }
+
+        private val REGEX_SPECIAL_CHARS: Regex = Regex("""[\\.+*?()|\[\]{}^$#&\-~]""")
     }
 }
@@ -8,14 +8,17 @@ package org.rust.cargo.runconfig.test
 import com.intellij.execution.Executor
 import com.intellij.execution.configurations.RunConfiguration
 import com.intellij.execution.testframework.TestConsoleProperties
+import com.intellij.execution.testframework.actions.AbstractRerunFailedTestsAction
 import com.intellij.execution.testframework.sm.SMCustomMessagesParsing
 import com.intellij.execution.testframework.sm.runner.OutputToGeneralTestEventsConverter
 import com.intellij.execution.testframework.sm.runner.SMTRunnerConsoleProperties
 import com.intellij.execution.testframework.sm.runner.SMTestLocator
+import com.intellij.execution.ui.ConsoleView
 
 class CargoTestConsoleProperties(
     config: RunConfiguration,
-    executor: Executor
+    executor: Executor,
+    private val useNextest: Boolean = false
 ) : SMTRunnerConsoleProperties(config, TEST_FRAMEWORK_NAME, executor), SMCustomMessagesParsing {
 
     init {
@@ -27,7 +30,15 @@ class CargoTestConsoleProperties(
     override fun createTestEventsConverter(
         testFrameworkName: String,
         consoleProperties: TestConsoleProperties
-    ): OutputToGeneralTestEventsConverter = CargoTestEventsConverter(testFrameworkName, consoleProperties)
+    ): OutputToGeneralTestEventsConverter = if (useNextest) {
+        NextestTestEventsConverter(testFrameworkName, consoleProperties)
+    } else {
+        CargoTestEventsConverter(testFrameworkName, consoleProperties)
+    }
+
+    // libtest can't filter tests by several exact names, so only nextest runs can be rerun
+    override fun createRerunFailedTestsAction(consoleView: ConsoleView): AbstractRerunFailedTestsAction? =
+        if (useNextest) NextestRerunFailedTestsAction(consoleView, this) else null
 
     companion object {
         const val TEST_FRAMEWORK_NAME: String = "Cargo Test"
@@ -27,10 +27,11 @@ import org.rust.cargo.runconfig.test.CargoTestEventsConverter.State.*
 import org.rust.stdext.removeLast
 import java.io.File
 import java.io.StringReader
+import kotlin.math.roundToLong
 
 private typealias NodeId = String
 
-class CargoTestEventsConverter(
+open class CargoTestEventsConverter(
     testFrameworkName: String,
     consoleProperties: TestConsoleProperties
 ) : OutputToGeneralTestEventsConverter(testFrameworkName, consoleProperties) {
@@ -64,6 +65,11 @@ class CargoTestEventsConverter(
         return true
     }
 
+    /** Starts the suite of a test target as if it was announced by a `Running <executable>` line */
+    protected fun startTarget(target: String) {
+        suitesStack.add(target)
+    }
+
     /** @return true if message successfully processed. */
     private fun handleStartMessage(text: String): Boolean {
         if (suitesStack.isNotEmpty()) return false
@@ -154,7 +160,7 @@ class CargoTestEventsConverter(
                 messages.add(createTestStartedMessage(testMessage.name))
             }
             "ok" -> {
-                val duration = getTestDuration(testMessage.name)
+                val duration = getTestDuration(testMessage)
                 if (!testMessage.stdout.isNullOrEmpty()) {
                     messages.add(createTestStdOutMessage(testMessage.name, testMessage.stdout))
                 }
@@ -163,7 +169,7 @@ class CargoTestEventsConverter(
                 processFinishedSuites(messages)
             }
             "failed" -> {
-                val duration = getTestDuration(testMessage.name)
+                val duration = getTestDuration(testMessage)
                 val (stdout, failedMessage) = parseFailedTestOutput(testMessage.stdout ?: "")
                 if (stdout.isNotEmpty()) messages.add(createTestStdOutMessage(testMessage.name, stdout + '\n'))
                 messages.add(createTestFailedMessage(testMessage.name, failedMessage))
@@ -211,6 +217,12 @@ class CargoTestEventsConverter(
         suitesToNotFinishedChildren[node.parent]?.remove(node)
     }
 
+    private fun getTestDuration(testMessage: LibtestTestMessage): String {
+        // `exec_time` is reported by nextest and by libtest with `--report-time`
+        val execTime = testMessage.exec_time ?: return getTestDuration(testMessage.name)
+        return (execTime * 1000).roundToLong().toString()
+    }
+
     // Yes, we can't measure the test duration in this way, it should be implemented on the libtest side,
     // but for now, this is an acceptable solution.
     private fun getTestDuration(test: NodeId): String {
@@ -263,6 +275,7 @@ class CargoTestEventsConverter(
         private const val NAME_SEPARATOR: String = "::"
         private const val DOCTESTS_SUFFIX = "doctests"
 
+        private val DOCTEST_LINE_RE: Regex = """ \(line \d+\)""".toRegex()
         private val DOCTEST_PATH_RE: Regex = """"[0-9 a-z_A-Z\-\\.]+ - """.toRegex()
 
         private val ERROR_MESSAGE_RE: Regex =
@@ -286,6 +299,17 @@ class CargoTestEventsConverter(
                 return if (this == parent) ROOT_SUITE else parent
             }
 
+        /**
+         * Keeps the line of doctests, which is used to locate them (see [CargoTestLocator]):
+         * target_name::name1::name2 (line #i) -> target_name::name1::name2 (line #i)
+         * target_name::name1::name2 (other) -> target_name::name1::name2
+         */
+        private val NodeId.locationPath: String
+            get() {
+                val doctestLine = DOCTEST_LINE_RE.find(this) ?: return substringBefore(" (")
+                return substring(0, doctestLine.range.last + 1)
+            }
+
         private fun createTestSuiteStartedMessage(suite: NodeId): ServiceMessageBuilder =
             ServiceMessageBuilder.testSuiteStarted(suite.name)
                 .addAttribute("nodeId", suite)
@@ -302,8 +326,7 @@ class CargoTestEventsConverter(
                 .addAttribute("parentNodeId", test.parent)
                 .addAttribute(
                     "locationHint",
-                    // target_name::name1::name2 (line #i) -> target_name::name1::name2
-                    CargoTestLocator.getTestUrl(test.substringBefore(" ("))
+                    CargoTestLocator.getTestUrl(test.locationPath)
                 )
 
         private fun createTestFailedMessage(test: NodeId, failedMessage: String): ServiceMessageBuilder {
@@ -416,7 +439,8 @@ private data class LibtestTestMessage(
     val type: String,
     val event: String,
     val name: String,
-    val stdout: String?
+    val stdout: String?,
+    val exec_time: Double?
 ) {
     companion object {
         fun fromJson(json: JsonObject): LibtestTestMessage? {
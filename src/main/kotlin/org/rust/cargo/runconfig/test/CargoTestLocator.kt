@@ -8,16 +8,24 @@ package org.rust.cargo.runconfig.test
 import com.intellij.execution.Location
 import com.intellij.execution.PsiLocation
 import com.intellij.execution.testframework.sm.runner.SMTestLocator
+import com.intellij.openapi.fileEditor.OpenFileDescriptor
 import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiDocumentManager
 import com.intellij.psi.PsiElement
 import com.intellij.psi.search.GlobalSearchScope
+import com.intellij.util.text.CharArrayUtil
 import org.rust.cargo.project.model.cargoProjects
 import org.rust.cargo.project.model.impl.allTargets
 import org.rust.cargo.project.workspace.PackageOrigin
+import org.rust.ide.injected.findDoctestCodeBlocks
+import org.rust.lang.core.psi.RsDocCommentImpl
 import org.rust.lang.core.psi.RsFile
 import org.rust.lang.core.psi.RsModDeclItem
+import org.rust.lang.core.psi.ext.RsElement
 import org.rust.lang.core.psi.ext.RsQualifiedNamedElement
+import org.rust.lang.core.psi.ext.containingCargoTarget
 import org.rust.lang.core.psi.ext.qualifiedName
+import org.rust.lang.core.psi.ext.startOffset
 import org.rust.lang.core.stubs.index.RsNamedElementIndex
 import org.rust.openapiext.toPsiFile
 import org.rust.stdext.buildList
@@ -25,6 +33,7 @@ import org.rust.stdext.buildList
 object CargoTestLocator : SMTestLocator {
     private const val NAME_SEPARATOR: String = "::"
     private const val TEST_PROTOCOL: String = "cargo:test"
+    private val DOCTEST_NAME_REGEX: Regex = Regex("""(.+?) ?\(line (\d+)\)""")
 
     override fun getLocation(
         protocol: String,
@@ -35,6 +44,12 @@ object CargoTestLocator : SMTestLocator {
         if (protocol != TEST_PROTOCOL) return emptyList()
         val qualifiedName = toQualifiedName(path)
 
+        val doctest = DOCTEST_NAME_REGEX.matchEntire(qualifiedName)
+        if (doctest != null) {
+            val (itemQualifiedName, line) = doctest.destructured
+            return getDoctestLocations(project, itemQualifiedName, line.toInt(), scope)
+        }
+
         // `RsQualifiedNamedElement.qualifiedName` starts with Cargo target name, so if the `qualifiedName` doesn't
         // contain the name separator, we are looking for a target with the corresponding name
         if (!qualifiedName.contains(NAME_SEPARATOR)) {
@@ -61,11 +76,62 @@ object CargoTestLocator : SMTestLocator {
         }
     }
 
+    /**
+     * Rustdoc names doctests like `src/lib.rs - foo::bar (line 10)`,
+     * which is converted to `target::foo::bar (line 10)` (see [CargoTestEventsConverter])
+     */
+    private fun getDoctestLocations(
+        project: Project,
+        itemQualifiedName: String,
+        line: Int,
+        scope: GlobalSearchScope
+    ): List<Location<PsiElement>> {
+        val targetName = itemQualifiedName.substringBefore(NAME_SEPARATOR)
+        val owners = if (itemQualifiedName.endsWith(NAME_SEPARATOR)) {
+            // Crate level documentation
+            project.cargoProjects.allTargets
+                .filter { it.pkg.origin == PackageOrigin.WORKSPACE && it.normName == targetName }
+                .mapNotNull { it.crateRoot?.toPsiFile(project) as? RsFile }
+                .toList()
+        } else {
+            val name = itemQualifiedName.substringAfterLast(NAME_SEPARATOR)
+            RsNamedElementIndex.findElementsByName(project, name, scope)
+                .mapNotNull { if (it is RsModDeclItem) it.reference.resolve() as? RsFile else it }
+                .filter { it.containingCargoTarget?.normName == targetName }
+        }
+        return owners
+            .mapNotNull { findDoctestAtLine(it, line) }
+            .distinct()
+            .map { DoctestLocation(it.first, it.second) }
+    }
+
+    private fun findDoctestAtLine(owner: RsElement, line: Int): Pair<RsDocCommentImpl, Int>? {
+        val file = owner.containingFile
+        val document = PsiDocumentManager.getInstance(owner.project).getDocument(file) ?: return null
+        if (line < 1 || line > document.lineCount) return null
+        val lineStart = document.getLineStartOffset(line - 1)
+        val offset = CharArrayUtil.shiftForward(document.charsSequence, lineStart, " \t")
+        val comment = file.findElementAt(offset) as? RsDocCommentImpl ?: return null
+        if (comment.parent != owner) return null
+        val fenceOffset = findDoctestCodeBlocks(comment)
+            .map { comment.startOffset + it.fenceOffset }
+            .find { document.getLineNumber(it) == line - 1 }
+            ?: return null
+        return comment to fenceOffset
+    }
+
     fun getTestUrl(name: String): String = "$TEST_PROTOCOL://$name"
 
     fun getTestUrl(function: RsQualifiedNamedElement): String =
         getTestUrl(function.qualifiedName ?: "")
 
+    /** Converts `cargo:test://target-xxxxxxxxxxxxxxxx::name1::name2` to the test name in its target, `name1::name2` */
+    fun toTestName(url: String): String? {
+        val path = url.removePrefix("$TEST_PROTOCOL://")
+        if (path == url) return null
+        return path.substringAfter(NAME_SEPARATOR, "").takeIf { it.isNotEmpty() }
+    }
+
     private fun toQualifiedName(path: String): String {
         val targetName = path.substringBefore(NAME_SEPARATOR).substringBeforeLast("-")
         if (!path.contains(NAME_SEPARATOR)) return targetName
@@ -73,3 +139,14 @@ object CargoTestLocator : SMTestLocator {
         return "$targetName$NAME_SEPARATOR$qualifiedName"
     }
 }
+
+/** Points to a doctest code block at [offset] in [comment] */
+class DoctestLocation(
+    comment: RsDocCommentImpl,
+    val offset: Int
+) : PsiLocation<PsiElement>(comment) {
+    override fun getOpenFileDescriptor(): OpenFileDescriptor? {
+        val virtualFile = psiElement.containingFile?.virtualFile ?: return null
+        return OpenFileDescriptor(project, virtualFile, offset)
+    }
+}
@@ -56,7 +56,7 @@ abstract class CargoTestRunConfigurationProducerBase : CargoRunConfigurationProd
         val testConfig = findTestConfig(context) ?: return false
         sourceElement.set(testConfig.originalElement)
         configuration.name = testConfig.configurationName
-        val cmd = testConfig.cargoCommandLine().mergeWithDefault(configuration)
+        val cmd = adjustCommandLine(testConfig.cargoCommandLine().mergeWithDefault(configuration), testConfig)
         configuration.setFromCmd(cmd)
         return true
     }
@@ -65,6 +65,11 @@ abstract class CargoTestRunConfigurationProducerBase : CargoRunConfigurationProd
         testConfigProviders.add(provider)
     }
 
+    /** Registers [provider] which is tried before the default ones */
+    protected fun registerPreferredConfigProvider(provider: (List<PsiElement>, Boolean) -> TestConfig?) {
+        testConfigProviders.add(0, provider)
+    }
+
     protected fun registerDirectoryConfigProvider(provider: (PsiDirectory) -> TestConfig?) {
         testConfigProviders.add { elements, _ ->
             val dir = elements.singleOrNull() as? PsiDirectory ?: return@add null
@@ -141,6 +146,8 @@ abstract class CargoTestRunConfigurationProducerBase : CargoRunConfigurationProd
 
     protected abstract fun isSuitable(element: PsiElement): Boolean
 
+    protected open fun adjustCommandLine(cmd: CargoCommandLine, testConfig: TestConfig): CargoCommandLine = cmd
+
     private fun isIgnoredTest(element: PsiElement): Boolean =
         element is RsFunction && element.findOuterAttr("ignore") != null
 
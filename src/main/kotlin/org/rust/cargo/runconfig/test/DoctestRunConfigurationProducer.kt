@@ -0,0 +1,160 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.test
+
+import com.intellij.execution.actions.ConfigurationContext
+import com.intellij.lang.injection.InjectedLanguageManager
+import com.intellij.openapi.actionSystem.CommonDataKeys
+import com.intellij.openapi.util.Ref
+import com.intellij.openapi.util.io.FileUtil
+import com.intellij.psi.PsiDocumentManager
+import com.intellij.psi.PsiElement
+import com.intellij.psi.impl.source.tree.injected.InjectedLanguageUtil
+import com.intellij.psi.util.PsiTreeUtil
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.runconfig.command.CargoCommandConfiguration
+import org.rust.cargo.runconfig.command.CargoRunConfigurationProducer
+import org.rust.cargo.runconfig.mergeWithDefault
+import org.rust.cargo.toolchain.CargoCommandLine
+import org.rust.ide.injected.RsDoctestCodeBlock
+import org.rust.ide.injected.areDoctestsEnabled
+import org.rust.ide.injected.findDoctestCodeBlocks
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.openapiext.pathAsPath
+
+/**
+ * Creates `cargo test --doc` configurations for code blocks of documentation comments.
+ * The caret may be either on the code block in the comment itself or inside the injected doctest.
+ */
+class DoctestRunConfigurationProducer : CargoRunConfigurationProducer() {
+    override fun isConfigurationFromContext(
+        configuration: CargoCommandConfiguration,
+        context: ConfigurationContext
+    ): Boolean {
+        val testConfig = findDoctestConfig(context) ?: return false
+        return configuration.canBeFrom(testConfig.cargoCommandLine())
+    }
+
+    override fun setupConfigurationFromContext(
+        configuration: CargoCommandConfiguration,
+        context: ConfigurationContext,
+        sourceElement: Ref<PsiElement>
+    ): Boolean {
+        val testConfig = findDoctestConfig(context) ?: return false
+        sourceElement.set(testConfig.sourceElement)
+        configuration.name = testConfig.configurationName
+        val cmd = testConfig.cargoCommandLine().mergeWithDefault(configuration)
+        configuration.setFromCmd(cmd)
+        return true
+    }
+
+    private fun findDoctestConfig(context: ConfigurationContext): DoctestConfig? {
+        val element = context.psiLocation ?: return null
+        val offset = (context.location as? DoctestLocation)?.offset
+            ?: CommonDataKeys.EDITOR.getData(context.dataContext)
+                ?.let { InjectedLanguageUtil.getTopLevelEditor(it) }
+                ?.takeIf { it.document == PsiDocumentManager.getInstance(element.project).getDocument(element.containingFile) }
+                ?.caretModel
+                ?.offset
+        return findDoctestConfig(element, offset)
+    }
+
+    companion object {
+        /**
+         * @param hostOffset the offset in the file containing the doc comment. If it is `null`,
+         * the comment must contain the only code block
+         */
+        fun findDoctestConfig(element: PsiElement, hostOffset: Int?): DoctestConfig? {
+            val injectedLanguageManager = InjectedLanguageManager.getInstance(element.project)
+            val injectionHost = injectedLanguageManager.getInjectionHost(element)
+            val (comment, offset) = if (injectionHost != null) {
+                val comment = injectionHost as? RsDocCommentImpl ?: return null
+                comment to (hostOffset ?: injectedLanguageManager.injectedToHost(element, element.startOffset))
+            } else {
+                val comment = element as? RsDocCommentImpl ?: return null
+                comment to hostOffset
+            }
+
+            val blocks = findDoctestCodeBlocks(comment)
+            val block = if (offset != null) {
+                val offsetInComment = offset - comment.startOffset
+                blocks.find { offsetInComment >= it.fenceOffset && offsetInComment < it.endOffset }
+            } else {
+                blocks.singleOrNull()
+            } ?: return null
+            return findDoctestConfig(comment, block)
+        }
+
+        fun findDoctestConfig(comment: RsDocCommentImpl, block: RsDoctestCodeBlock): DoctestConfig? {
+            if (!block.isRunnable) return null
+            val owner = comment.parent as? RsElement ?: return null
+            val target = owner.containingCargoTarget ?: return null
+            if (!target.areDoctestsEnabled) return null
+
+            val file = comment.containingFile
+            val filePath = file.virtualFile?.pathAsPath ?: return null
+            val relativeFilePath = FileUtil.toSystemIndependentName(target.pkg.rootDirectory.relativize(filePath).toString())
+            val document = PsiDocumentManager.getInstance(comment.project).getDocument(file) ?: return null
+            val line = document.getLineNumber(comment.startOffset + block.fenceOffset) + 1
+            val itemPath = doctestPath(owner) ?: return null
+
+            // Exactly the same name as rustdoc produces, e.g. `src/lib.rs - foo::bar (line 10)`
+            val testName = if (itemPath.isEmpty()) {
+                "$relativeFilePath - (line $line)"
+            } else {
+                "$relativeFilePath - $itemPath (line $line)"
+            }
+            return DoctestConfig(testName, target, comment, block.fenceOffset)
+        }
+
+        /**
+         * Rustdoc names doctests after the path of the documented item.
+         * Unlike [RsQualifiedNamedElement.crateRelativePath], the path contains the self type
+         * of impls, e.g. `foo::Bar::baz` for the method `baz` in `impl Bar` in module `foo`
+         */
+        private fun doctestPath(item: RsElement): String? {
+            if (item is RsMod) return item.crateRelativePath?.removePrefix("::")
+            val name = when (item) {
+                is RsImplItem -> item.typeReference?.text
+                is RsNamedElement -> item.name
+                else -> null
+            } ?: return null
+            val parent = PsiTreeUtil.getParentOfType(
+                item,
+                RsMod::class.java,
+                RsImplItem::class.java,
+                RsTraitItem::class.java,
+                RsStructItem::class.java,
+                RsEnumItem::class.java
+            ) ?: return null
+            val parentPath = doctestPath(parent) ?: return null
+            return if (parentPath.isEmpty()) name else "$parentPath::$name"
+        }
+    }
+}
+
+class DoctestConfig(
+    override val path: String,
+    val target: CargoWorkspace.Target,
+    override val sourceElement: RsDocCommentImpl,
+    /** The offset of the code block in [sourceElement] */
+    val offset: Int
+) : TestConfig {
+    override val commandName: String = "test"
+    override val exact: Boolean = true
+
+    override val targets: List<CargoWorkspace.Target>
+        get() = listOf(target)
+
+    override val configurationName: String
+        get() = "Doctest ${path.substringAfter(" - ")}"
+
+    // `--doc` can't be combined with other target selection options like `--lib`,
+    // so we select the package explicitly
+    override fun cargoCommandLine(): CargoCommandLine =
+        CargoCommandLine.forPackage(target.pkg, commandName, listOf("--doc", "--", path, "--exact"))
+}
@@ -0,0 +1,44 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.test
+
+import com.intellij.execution.Executor
+import com.intellij.execution.configurations.RunProfileState
+import com.intellij.execution.runners.ExecutionEnvironment
+import com.intellij.execution.testframework.TestConsoleProperties
+import com.intellij.execution.testframework.actions.AbstractRerunFailedTestsAction
+import com.intellij.execution.ui.ConsoleView
+import org.rust.cargo.runconfig.CargoTestRunState
+import org.rust.cargo.runconfig.command.CargoCommandConfiguration
+
+/**
+ * Reruns failed tests of a `cargo nextest` run by passing their names to the filter expression,
+ * e.g. `-E 'test(=foo::test1) | test(=bar::test2)'`
+ */
+class NextestRerunFailedTestsAction(
+    consoleView: ConsoleView,
+    consoleProperties: TestConsoleProperties
+) : AbstractRerunFailedTestsAction(consoleView) {
+
+    init {
+        init(consoleProperties)
+    }
+
+    override fun getRunProfile(environment: ExecutionEnvironment): MyRunProfile? {
+        val configuration = myConsoleProperties.configuration as? CargoCommandConfiguration ?: return null
+        val testNames = getFailedTests(configuration.project)
+            .filter { it.isLeaf }
+            .mapNotNull { it.locationUrl?.let(CargoTestLocator::toTestName) }
+            .distinct()
+        if (testNames.isEmpty()) return null
+        return object : MyRunProfile(configuration) {
+            override fun getState(executor: Executor, environment: ExecutionEnvironment): RunProfileState? {
+                val config = configuration.clean().ok ?: return null
+                return CargoTestRunState(environment, configuration, config, useNextest = true, nextestFilters = testNames)
+            }
+        }
+    }
+}
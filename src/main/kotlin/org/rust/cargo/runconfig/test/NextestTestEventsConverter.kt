@@ -0,0 +1,65 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.test
+
+import com.google.gson.JsonObject
+import com.google.gson.JsonParser
+import com.google.gson.JsonSyntaxException
+import com.intellij.execution.testframework.TestConsoleProperties
+import com.intellij.openapi.util.Key
+import jetbrains.buildServer.messages.serviceMessages.ServiceMessageVisitor
+
+/**
+ * Converts libtest-compatible JSON output of `cargo nextest run --message-format libtest-json`.
+ *
+ * Unlike `cargo test`, nextest doesn't print `Running <executable>` lines, instead, each suite message has
+ * `"nextest": { "crate": "my-crate", "test_binary": "my_crate", "kind": "lib" }` object identifying the test binary,
+ * and test names are prefixed with the binary id, e.g. `my-crate$tests::foo`.
+ * These messages are translated to the `cargo test` ones and are handled by [CargoTestEventsConverter]
+ */
+class NextestTestEventsConverter(
+    testFrameworkName: String,
+    consoleProperties: TestConsoleProperties
+) : CargoTestEventsConverter(testFrameworkName, consoleProperties) {
+
+    override fun processServiceMessages(text: String, outputType: Key<*>, visitor: ServiceMessageVisitor): Boolean {
+        val jsonObject = try {
+            JsonParser.parseString(text).takeIf { it.isJsonObject }?.asJsonObject
+        } catch (e: JsonSyntaxException) {
+            null
+        } ?: return true
+
+        when (jsonObject.getAsJsonPrimitive("type")?.asString) {
+            "suite" -> {
+                if (jsonObject.getAsJsonPrimitive("event")?.asString == "started") {
+                    val target = jsonObject.getAsJsonObject("nextest")?.targetNodeId ?: return true
+                    startTarget(target)
+                }
+            }
+            "test" -> {
+                val name = jsonObject.getAsJsonPrimitive("name")?.asString ?: return true
+                jsonObject.addProperty("name", name.substringAfter(BINARY_ID_SEPARATOR))
+            }
+        }
+
+        return super.processServiceMessages(jsonObject.toString(), outputType, visitor)
+    }
+
+    companion object {
+        private const val BINARY_ID_SEPARATOR: String = "$"
+
+        /**
+         * Node ids of targets have the form of `target_name-suffix`, like the names of `cargo test` executables.
+         * The kind is used as the suffix to distinguish lib and bin targets with the same name
+         */
+        private val JsonObject.targetNodeId: String?
+            get() {
+                val binaryName = getAsJsonPrimitive("test_binary")?.asString ?: return null
+                val kind = getAsJsonPrimitive("kind")?.asString ?: return null
+                return "${binaryName.replace('-', '_')}-${kind.replace('-', '_')}"
+            }
+    }
+}
@@ -18,6 +18,7 @@ import com.intellij.openapi.util.SystemInfo
 import com.intellij.openapi.vfs.LocalFileSystem
 import com.intellij.ui.SimpleListCellRenderer
 import com.intellij.ui.components.CheckBox
+import com.intellij.ui.components.JBTextField
 import com.intellij.ui.components.Label
 import com.intellij.ui.layout.CCFlags
 import com.intellij.ui.layout.LayoutBuilder
@@ -28,6 +29,7 @@ import org.rust.cargo.project.model.CargoProject
 import org.rust.cargo.project.model.cargoProjects
 import org.rust.cargo.project.settings.toolchain
 import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.runconfig.BenchBaselineMode
 import org.rust.cargo.runconfig.command.CargoCommandConfiguration
 import org.rust.cargo.runconfig.command.workingDirectory
 import org.rust.cargo.toolchain.BacktraceMode
@@ -92,17 +94,30 @@ class CargoCommandConfigurationEditor(private val project: Project) : SettingsEd
     private val environmentVariables = EnvironmentVariablesComponent()
     private val allFeatures = CheckBox("Use all features in tests", false)
     private val nocapture = CheckBox("Show stdout/stderr in tests (and disable test tool window)", false)
+    private val useNextest = CheckBox("Run tests with cargo nextest", false)
     private val emulateTerminal = CheckBox("Emulate terminal in output console", false)
 
+    private val benchBaselineMode = ComboBox<BenchBaselineMode>().apply {
+        BenchBaselineMode.values()
+            .sortedBy { it.index }
+            .forEach { addItem(it) }
+        addItemListener { benchBaseline.isEnabled = selectedIndex != BenchBaselineMode.NONE.index }
+    }
+    private val benchBaseline = JBTextField()
+
     override fun resetEditorFrom(configuration: CargoCommandConfiguration) {
         channel.selectedIndex = configuration.channel.index
         command.text = configuration.command
         allFeatures.isSelected = configuration.allFeatures
         nocapture.isSelected = configuration.nocapture
+        useNextest.isSelected = configuration.useNextest
         emulateTerminal.isSelected = configuration.emulateTerminal
         backtraceMode.selectedIndex = configuration.backtrace.index
         workingDirectory.component.text = configuration.workingDirectory?.toString() ?: ""
         environmentVariables.envData = configuration.env
+        benchBaselineMode.selectedIndex = configuration.benchBaselineMode.index
+        benchBaseline.text = configuration.benchBaseline
+        benchBaseline.isEnabled = configuration.benchBaselineMode != BenchBaselineMode.NONE
         val vFile = currentWorkingDirectory?.let { LocalFileSystem.getInstance().findFileByIoFile(it.toFile()) }
         if (vFile == null) {
             cargoProject.selectedIndex = -1
@@ -120,10 +135,13 @@ class CargoCommandConfigurationEditor(private val project: Project) : SettingsEd
         configuration.command = command.text
         configuration.allFeatures = allFeatures.isSelected
         configuration.nocapture = nocapture.isSelected
+        configuration.useNextest = useNextest.isSelected
         configuration.emulateTerminal = emulateTerminal.isSelected && !SystemInfo.isWindows
         configuration.backtrace = BacktraceMode.fromIndex(backtraceMode.selectedIndex)
         configuration.workingDirectory = currentWorkingDirectory
         configuration.env = environmentVariables.envData
+        configuration.benchBaselineMode = BenchBaselineMode.fromIndex(benchBaselineMode.selectedIndex)
+        configuration.benchBaseline = benchBaseline.text
 
         val rustupAvailable = project.toolchain?.isRustupAvailable ?: false
         channel.isEnabled = rustupAvailable || configChannel != RustChannel.DEFAULT
@@ -142,6 +160,7 @@ class CargoCommandConfigurationEditor(private val project: Project) : SettingsEd
 
         row { allFeatures() }
         row { nocapture() }
+        row { useNextest() }
 
         if (!SystemInfo.isWindows) {
             row { emulateTerminal() }
@@ -155,6 +174,10 @@ class CargoCommandConfigurationEditor(private val project: Project) : SettingsEd
             }
         }
         labeledRow("Back&trace:", backtraceMode) { backtraceMode() }
+        labeledRow("Criterion &baseline:", benchBaselineMode) {
+            benchBaselineMode()
+            benchBaseline(CCFlags.pushX, CCFlags.growX)
+        }
     }
 
     private fun LayoutBuilder.labeledRow(labelText: String, component: JComponent, init: Row.() -> Unit) {
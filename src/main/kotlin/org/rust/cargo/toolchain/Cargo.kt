@@ -6,6 +6,7 @@
 package org.rust.cargo.toolchain
 
 import com.google.gson.Gson
+import com.google.gson.JsonObject
 import com.google.gson.JsonParser
 import com.google.gson.JsonSyntaxException
 import com.intellij.execution.ExecutionException
@@ -22,11 +23,13 @@ import com.intellij.openapi.util.SystemInfo
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.openapiext.Testmark
 import com.intellij.openapiext.isDispatchThread
+import com.intellij.util.PathUtil
 import com.intellij.util.execution.ParametersListUtil
 import com.intellij.util.net.HttpConfigurable
 import com.intellij.util.text.SemVer
 import org.jetbrains.annotations.TestOnly
 import org.rust.cargo.CargoConstants.RUST_BACKTRACE_ENV_VAR
+import org.rust.cargo.CfgOptions
 import org.rust.cargo.project.model.cargoProjects
 import org.rust.cargo.project.settings.rustSettings
 import org.rust.cargo.project.settings.toolchain
@@ -42,6 +45,7 @@ import org.rust.ide.actions.InstallBinaryCrateAction
 import org.rust.ide.experiments.RsExperiments
 import org.rust.ide.notifications.showBalloon
 import org.rust.openapiext.*
+import org.rust.stdext.CollectionBuilder
 import org.rust.stdext.buildList
 import java.nio.file.Path
 
@@ -55,7 +59,10 @@ import java.nio.file.Path
  * It is impossible to guarantee that paths to the project or executables are valid,
  * because the user can always just `rm ~/.cargo/bin -rf`.
  */
-class Cargo(private val cargoExecutable: Path) {
+class Cargo(
+    private val cargoExecutable: Path,
+    private val pathMapper: RsPathMapper = RsPathMapper.Local
+) {
 
     data class BinaryCrate(val name: String, val version: SemVer? = null) {
         companion object {
@@ -100,22 +107,32 @@ class Cargo(private val cargoExecutable: Path) {
      * to resolve dependencies. Hence it is mandatory to
      * pass an [owner] to correctly kill the process if it
      * runs for too long.
+     *
+     * If [evaluateBuildScripts] is `true`, build scripts are run to collect
+     * `cfg` options, environment variables and `OUT_DIR` of packages.
      */
     @Throws(ExecutionException::class)
     fun fullProjectDescription(
         owner: Project,
         projectDirectory: Path,
-        listener: ProcessListener? = null
+        listener: ProcessListener? = null,
+        targetTriple: String? = null,
+        cfgOptions: CfgOptions? = null,
+        evaluateBuildScripts: Boolean = false
     ): CargoWorkspaceData {
         val rawData = fetchMetadata(owner, projectDirectory, listener)
-        val buildScriptsInfo = fetchBuildScriptsInfo(owner, projectDirectory, listener)
+        val buildScriptsInfo = if (evaluateBuildScripts) {
+            fetchBuildScriptsInfo(owner, projectDirectory, listener)
+        } else {
+            null
+        }
         val buildPlan = if (buildScriptsInfo?.containsOutDirInfo != true) {
             fetchBuildPlan(owner, projectDirectory, listener)
         } else {
             null
         }
 
-        return CargoMetadata.clean(rawData, buildScriptsInfo, buildPlan)
+        return CargoMetadata.clean(rawData, buildScriptsInfo, buildPlan, targetTriple, cfgOptions)
     }
 
     @Throws(ExecutionException::class)
@@ -141,7 +158,6 @@ class Cargo(private val cargoExecutable: Path) {
         projectDirectory: Path,
         listener: ProcessListener?
     ): BuildScriptsInfo? {
-        if (!isFeatureEnabled(RsExperiments.EVALUATE_BUILD_SCRIPTS)) return null
         val additionalArgs = listOf("--message-format", "json")
         val commandLine = CargoCommandLine("check", projectDirectory, additionalArgs)
 
@@ -229,6 +245,8 @@ class Cargo(private val cargoExecutable: Path) {
         val arguments = buildList<String> {
             add("--message-format=json")
 
+            addRemoteManifestPath(cargoProjectDirectory)
+
             if (cargoPackageName != null) {
                 add("--package")
                 add(cargoPackageName)
@@ -247,6 +265,59 @@ class Cargo(private val cargoExecutable: Path) {
             .execute(project, owner, ignoreExitCode = true)
     }
 
+    /**
+     * Applies machine-applicable suggestions of clippy to all packages of the project with `cargo clippy --fix`.
+     * Uncommitted changes are fixed too, so the fixes are mixed with them in VCS
+     */
+    @Throws(ExecutionException::class)
+    fun fixWithClippy(project: Project, owner: Disposable, cargoProjectDirectory: Path): ProcessOutput =
+        clippyFixCommandLine(project, cargoProjectDirectory).execute(project, owner)
+
+    fun clippyFixCommandLine(project: Project, cargoProjectDirectory: Path): CargoCommandLine {
+        val settings = project.rustSettings
+        val arguments = buildList<String> {
+            add("--fix")
+            add("--allow-dirty")
+            addRemoteManifestPath(cargoProjectDirectory)
+            add("--workspace")
+            if (settings.compileAllTargets && checkSupportForBuildCheckAllTargets()) add("--all-targets")
+            addAll(ParametersListUtil.parse(settings.externalLinterArguments))
+        }
+        return CargoCommandLine("clippy", cargoProjectDirectory, arguments)
+    }
+
+    private fun CollectionBuilder<String>.addRemoteManifestPath(cargoProjectDirectory: Path) {
+        if (pathMapper == RsPathMapper.Local) return
+        // The working directory of a remote toolchain process may differ from the local one
+        val manifestPath = cargoProjectDirectory.resolve(RustToolchain.CARGO_TOML).toString()
+        add("--manifest-path")
+        add(pathMapper.toRemote(PathUtil.toSystemIndependentName(manifestPath)))
+    }
+
+    /**
+     * Builds the targets of `cargo test`, `cargo bench` or `cargo run` [commandLine] without running them
+     * and returns paths of the produced executables
+     */
+    @Throws(ExecutionException::class)
+    fun fetchExecutables(project: Project, owner: Disposable, commandLine: CargoCommandLine): List<String> {
+        val (pre, _) = commandLine.splitOnDoubleDash()
+        val buildCommandLine = when (commandLine.command) {
+            "test", "bench" -> commandLine.copy(additionalArguments = pre + "--no-run" + "--message-format=json")
+            else -> commandLine.copy(command = "build", additionalArguments = pre + "--message-format=json")
+        }
+        return buildCommandLine.execute(project, owner)
+            .stdoutLines
+            .mapNotNull { line ->
+                val json = try {
+                    JsonParser.parseString(line) as? JsonObject
+                } catch (ignore: JsonSyntaxException) {
+                    null
+                }
+                json?.let { CargoMetadata.Artifact.fromJson(it) }?.executable
+            }
+            .distinct()
+    }
+
     fun toColoredCommandLine(project: Project, commandLine: CargoCommandLine): GeneralCommandLine =
         toGeneralCommandLine(project, commandLine, colors = true)
 
@@ -371,9 +442,9 @@ class Cargo(private val cargoExecutable: Path) {
             }
         }
 
-        fun checkNeedInstallGrcov(project: Project): Boolean {
-            val crateName = "grcov"
-            val minVersion = SemVer("v0.4.3", 0, 4, 3)
+        fun checkNeedInstallCargoExpand(project: Project): Boolean {
+            val crateName = "cargo-expand"
+            val minVersion = SemVer("v0.4.9", 0, 4, 9)
             return checkNeedInstallBinaryCrate(
                 project,
                 crateName,
@@ -383,9 +454,9 @@ class Cargo(private val cargoExecutable: Path) {
             )
         }
 
-        fun checkNeedInstallCargoExpand(project: Project): Boolean {
-            val crateName = "cargo-expand"
-            val minVersion = SemVer("v0.4.9", 0, 4, 9)
+        fun checkNeedInstallEvcxr(project: Project): Boolean {
+            val crateName = "evcxr_repl"
+            val minVersion = SemVer("v0.5.1", 0, 5, 1)
             return checkNeedInstallBinaryCrate(
                 project,
                 crateName,
@@ -395,14 +466,14 @@ class Cargo(private val cargoExecutable: Path) {
             )
         }
 
-        fun checkNeedInstallEvcxr(project: Project): Boolean {
-            val crateName = "evcxr_repl"
-            val minVersion = SemVer("v0.5.1", 0, 5, 1)
+        fun checkNeedInstallCargoNextest(project: Project): Boolean {
+            val crateName = "cargo-nextest"
+            val minVersion = SemVer("v0.9.66", 0, 9, 66)
             return checkNeedInstallBinaryCrate(
                 project,
                 crateName,
-                NotificationType.ERROR,
-                "Need at least $crateName $minVersion",
+                NotificationType.WARNING,
+                "Need at least $crateName $minVersion. Tests will be run with <code>cargo test</code>",
                 minVersion
             )
         }
@@ -0,0 +1,67 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.toolchain
+
+import com.intellij.execution.configurations.GeneralCommandLine
+import com.intellij.openapi.util.SystemInfo
+import org.rust.openapiext.GeneralCommandLine
+import org.rust.openapiext.withWorkDirectory
+import java.nio.file.Files
+import java.nio.file.Path
+
+/**
+ * `llvm-profdata` and `llvm-cov` shipped with the toolchain by `llvm-tools-preview` rustup component.
+ * They are located in `<sysroot>/lib/rustlib/<host>/bin` and match the LLVM version used by rustc
+ */
+class LlvmTools(private val binDirectory: Path) {
+
+    /** Merges raw profiles written by instrumented binaries into a single indexed profile */
+    fun createMergeCommandLine(workingDirectory: Path, profraws: List<Path>, output: Path): GeneralCommandLine =
+        GeneralCommandLine(pathToExecutable(LLVM_PROFDATA))
+            .withWorkDirectory(workingDirectory)
+            .withParameters("merge", "-sparse")
+            .withParameters(profraws.map { it.toString() })
+            .withParameters("-o", output.toString())
+            .withCharset(Charsets.UTF_8)
+
+    /** Exports coverage of instrumented [objects] to stdout in JSON format. [objects] must not be empty */
+    fun createExportCommandLine(workingDirectory: Path, profdata: Path, objects: List<String>): GeneralCommandLine {
+        // `llvm-cov export <first> -object <second> -object <third>`
+        val objectArgs = listOf(objects.first()) + objects.drop(1).flatMap { listOf("-object", it) }
+        return GeneralCommandLine(pathToExecutable(LLVM_COV))
+            .withWorkDirectory(workingDirectory)
+            .withParameters(
+                "export",
+                "-format=text",
+                "-instr-profile=$profdata",
+                "-ignore-filename-regex=$IGNORED_FILES_REGEX"
+            )
+            .withParameters(objectArgs)
+            .withCharset(Charsets.UTF_8)
+    }
+
+    private fun pathToExecutable(toolName: String): Path {
+        val exeName = if (SystemInfo.isWindows) "$toolName.exe" else toolName
+        return binDirectory.resolve(exeName).toAbsolutePath()
+    }
+
+    companion object {
+        const val COMPONENT_NAME: String = "llvm-tools-preview"
+
+        private const val LLVM_PROFDATA: String = "llvm-profdata"
+        private const val LLVM_COV: String = "llvm-cov"
+
+        /** Sources of the standard library and of crates.io dependencies */
+        private const val IGNORED_FILES_REGEX: String = """(/rustc/[0-9a-f]+/|[/\\]\.cargo[/\\](registry|git)[/\\])"""
+
+        fun fromSysroot(sysroot: Path, host: String): LlvmTools? {
+            val binDirectory = sysroot.resolve("lib/rustlib/$host/bin")
+            val tools = LlvmTools(binDirectory)
+            val hasTools = listOf(LLVM_PROFDATA, LLVM_COV).all { Files.isExecutable(tools.pathToExecutable(it)) }
+            return if (hasTools) tools else null
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.toolchain
+
+import com.intellij.util.PathUtil
+import java.nio.file.Path
+
+/**
+ * Translates paths between the machine where the IDE runs and the machine (or WSL distribution)
+ * where the toolchain runs. All paths are system-independent, i.e. use `/` as a separator.
+ *
+ * For example, for a toolchain inside WSL a local `//wsl$/Ubuntu/home/user/project` corresponds to
+ * a remote `/home/user/project`
+ */
+interface RsPathMapper {
+    fun toRemote(localPath: String): String
+
+    /** Returns `null` if [remotePath] is not accessible from the IDE */
+    fun toLocal(remotePath: String): String?
+
+    /** Used for toolchains running on the same machine as the IDE */
+    object Local : RsPathMapper {
+        override fun toRemote(localPath: String): String = localPath
+        override fun toLocal(remotePath: String): String? = remotePath
+    }
+
+    /**
+     * Used for toolchains inside the WSL [distribution]. Files of the distribution are available on Windows
+     * as `//wsl$/<distribution>/...`, and Windows drives are mounted in the distribution as `/mnt/<drive>/...`
+     */
+    data class Wsl(val distribution: String) : RsPathMapper {
+        private val uncRoot: String = "$WSL_UNC_PREFIX$distribution"
+
+        override fun toRemote(localPath: String): String {
+            if (localPath.equals(uncRoot, ignoreCase = true)) return "/"
+            if (localPath.startsWith("$uncRoot/", ignoreCase = true)) return localPath.substring(uncRoot.length)
+            val (drive, rest) = WINDOWS_DRIVE_PATH.matchEntire(localPath)?.destructured ?: return localPath
+            return "$WSL_MOUNT_ROOT${drive.toLowerCase()}$rest"
+        }
+
+        override fun toLocal(remotePath: String): String? {
+            if (!remotePath.startsWith("/")) return null
+            val drivePath = WSL_MOUNTED_DRIVE_PATH.matchEntire(remotePath)
+            if (drivePath != null) {
+                val (drive, rest) = drivePath.destructured
+                return "${drive.toUpperCase()}:${rest.ifEmpty { "/" }}"
+            }
+            return uncRoot + remotePath
+        }
+    }
+
+    companion object {
+        private const val WSL_UNC_PREFIX: String = "//wsl$/"
+        private const val WSL_MOUNT_ROOT: String = "/mnt/"
+        private val WINDOWS_DRIVE_PATH: Regex = Regex("""([a-zA-Z]):(/.*)?""")
+        private val WSL_MOUNTED_DRIVE_PATH: Regex = Regex("""/mnt/([a-z])(/.*)?""")
+
+        /** Returns a [Wsl] mapper if [toolchainLocation] is inside a WSL distribution, i.e. `//wsl$/<distribution>/...` */
+        fun forToolchain(toolchainLocation: Path): RsPathMapper {
+            val path = PathUtil.toSystemIndependentName(toolchainLocation.toString())
+            if (!path.startsWith(WSL_UNC_PREFIX, ignoreCase = true)) return Local
+            val distribution = path.substring(WSL_UNC_PREFIX.length).substringBefore('/')
+            return if (distribution.isEmpty()) Local else Wsl(distribution)
+        }
+    }
+}
@@ -16,10 +16,15 @@ import org.rust.openapiext.*
 import java.io.File
 import java.nio.file.Files
 import java.nio.file.Path
+import java.nio.file.Paths
 import java.time.LocalDate
 import java.time.format.DateTimeParseException
 
-data class RustToolchain(val location: Path) {
+data class RustToolchain(
+    val location: Path,
+    /** Maps paths in the output of the tools (e.g. in diagnostics) to the paths of local files */
+    val pathMapper: RsPathMapper = RsPathMapper.forToolchain(location)
+) {
 
     fun looksLikeValidToolchain(): Boolean =
         hasExecutable(CARGO) && hasExecutable(RUSTC)
@@ -50,22 +55,27 @@ data class RustToolchain(val location: Path) {
         return fs.refreshAndFindFileByPath(FileUtil.join(sysroot, "lib/rustlib/src/rust/src"))
     }
 
-    fun getCfgOptions(projectDirectory: Path): List<String>? {
+    /** @param target target triple, the host one is used if it is null */
+    fun getCfgOptions(projectDirectory: Path, target: String? = null): List<String>? {
         val timeoutMs = 10000
+        val parameters = mutableListOf("--print", "cfg")
+        if (target != null) {
+            parameters += listOf("--target", target)
+        }
         val output = GeneralCommandLine(pathToExecutable(RUSTC))
             .withCharset(Charsets.UTF_8)
             .withWorkDirectory(projectDirectory)
-            .withParameters("--print", "cfg")
+            .withParameters(parameters)
             .execute(timeoutMs)
         return if (output?.isSuccess == true) output.stdoutLines else null
     }
 
-    fun rawCargo(): Cargo = Cargo(pathToExecutable(CARGO))
+    fun rawCargo(): Cargo = Cargo(pathToExecutable(CARGO), pathMapper)
 
     fun cargoOrWrapper(cargoProjectDirectory: Path?): Cargo {
         val hasXargoToml = cargoProjectDirectory?.resolve(XARGO_TOML)?.let { Files.isRegularFile(it) } == true
         val cargoWrapper = if (hasXargoToml && hasExecutable(XARGO)) XARGO else CARGO
-        return Cargo(pathToExecutable(cargoWrapper))
+        return Cargo(pathToExecutable(cargoWrapper), pathMapper)
     }
 
     fun rustup(cargoProjectDirectory: Path): Rustup? =
@@ -76,7 +86,11 @@ data class RustToolchain(val location: Path) {
 
     fun rustfmt(): Rustfmt = Rustfmt(pathToExecutable(RUSTFMT))
 
-    fun grcov(): Grcov? = if (hasExecutable(GRCOV)) Grcov(pathToExecutable(GRCOV)) else null
+    fun llvmTools(projectDirectory: Path): LlvmTools? {
+        val sysroot = getSysroot(projectDirectory) ?: return null
+        val host = queryVersions().rustc?.host ?: return null
+        return LlvmTools.fromSysroot(Paths.get(sysroot), host)
+    }
 
     fun evcxr(): Evcxr? = if (hasExecutable(EVCXR)) Evcxr(pathToExecutable(EVCXR)) else null
 
@@ -102,7 +116,6 @@ data class RustToolchain(val location: Path) {
         private const val CARGO = "cargo"
         private const val RUSTUP = "rustup"
         private const val XARGO = "xargo"
-        private const val GRCOV = "grcov"
         private const val EVCXR = "evcxr"
 
         const val CARGO_TOML = "Cargo.toml"
@@ -95,6 +95,9 @@ class Rustup(
         fun checkNeedInstallRustfmt(project: Project, cargoProjectDirectory: Path): Boolean =
             checkNeedInstallComponent(project, cargoProjectDirectory, "rustfmt")
 
+        fun checkNeedInstallLlvmTools(project: Project, cargoProjectDirectory: Path): Boolean =
+            checkNeedInstallComponent(project, cargoProjectDirectory, LlvmTools.COMPONENT_NAME)
+
         // We don't want to install the component if:
         // 1. It is already installed
         // 2. We don't have Rustup
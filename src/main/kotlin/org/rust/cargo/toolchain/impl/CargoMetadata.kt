@@ -56,7 +56,13 @@ object CargoMetadata {
         /**
          * Path to workspace root folder. Can be null for old cargo version
          */
-        val workspace_root: String
+        val workspace_root: String,
+
+        /**
+         * Path to the directory of build artifacts. Respects `CARGO_TARGET_DIR` and `build.target-dir`.
+         * Can be null for old cargo version
+         */
+        val target_directory: String?
     )
 
 
@@ -226,7 +232,26 @@ object CargoMetadata {
         /**
          * Dependency name that should be used in code as extern crate name
          */
-        val name: String?
+        val name: String?,
+
+        /**
+         * Kinds of the dependency and platforms where they are enabled.
+         * Since Cargo 1.41
+         */
+        val dep_kinds: List<DepKindInfo>?
+    )
+
+    data class DepKindInfo(
+        /**
+         * `null` for normal dependencies, `"dev"` or `"build"` otherwise
+         */
+        val kind: String?,
+
+        /**
+         * Platform from `[target.'cfg(...)'.dependencies]` or `[target.<triple>.dependencies]` table.
+         * `null` if the dependency is not target-specific
+         */
+        val target: String?
     )
 
     // The next two things do not belong here,
@@ -251,10 +276,17 @@ object CargoMetadata {
         val test: Boolean
     )
 
+    /**
+     * @param targetTriple and [cfgOptions] describe the platform the project is built for.
+     * Dependencies that apply only to other platforms are dropped like `cargo metadata --filter-platform` does.
+     * If [cfgOptions] is null, all dependencies are kept
+     */
     fun clean(
         project: Project,
         buildScriptsInfo: BuildScriptsInfo?,
-        buildPlan: CargoBuildPlan?
+        buildPlan: CargoBuildPlan?,
+        targetTriple: String? = null,
+        cfgOptions: CfgOptions? = null
     ): CargoWorkspaceData {
         val fs = LocalFileSystem.getInstance()
         val members = project.workspace_members
@@ -286,16 +318,31 @@ object CargoMetadata {
             },
             project.resolve.nodes.associate { (id, dependencies, deps) ->
                 val dependencySet = if (deps != null) {
-                    deps.mapToSet { (pkgId, name) -> CargoWorkspaceData.Dependency(pkgId, name) }
+                    deps
+                        .filter { cfgOptions == null || it.isEnabledOn(targetTriple, cfgOptions) }
+                        .mapToSet { (pkgId, name) -> CargoWorkspaceData.Dependency(pkgId, name) }
                 } else {
                     dependencies.mapToSet { CargoWorkspaceData.Dependency(it) }
                 }
                 id to dependencySet
             },
-            project.workspace_root
+            project.workspace_root,
+            project.target_directory
         )
     }
 
+    private fun Dep.isEnabledOn(targetTriple: String?, cfgOptions: CfgOptions): Boolean {
+        if (dep_kinds.isNullOrEmpty()) return true
+        return dep_kinds.any { info ->
+            val target = info.target ?: return@any true
+            // Build dependencies are compiled for the host platform, which can differ from the target one
+            if (info.kind == "build") return@any true
+            // Be conservative if we don't understand the platform
+            val platform = CargoPlatform.parse(target) ?: return@any true
+            platform.matches(targetTriple, cfgOptions)
+        }
+    }
+
     private fun Package.clean(
         fs: LocalFileSystem,
         isWorkspaceMember: Boolean,
@@ -0,0 +1,140 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.toolchain.impl
+
+import org.rust.cargo.CfgOptions
+
+/**
+ * A platform of a target-specific dependency, i.e. `cfg(windows)` in `[target.'cfg(windows)'.dependencies]`
+ * or a target triple in `[target.x86_64-pc-windows-gnu.dependencies]`.
+ *
+ * See `cargo-platform` crate
+ */
+sealed class CargoPlatform {
+    abstract fun matches(targetTriple: String?, cfgOptions: CfgOptions): Boolean
+
+    data class Name(val triple: String) : CargoPlatform() {
+        override fun matches(targetTriple: String?, cfgOptions: CfgOptions): Boolean = triple == targetTriple
+    }
+
+    data class Cfg(val expr: CfgExpr) : CargoPlatform() {
+        override fun matches(targetTriple: String?, cfgOptions: CfgOptions): Boolean = expr.evaluate(cfgOptions)
+    }
+
+    companion object {
+        /** @return null if [text] is not a valid `cfg(...)` expression */
+        fun parse(text: String): CargoPlatform? {
+            val trimmed = text.trim()
+            if (!trimmed.startsWith("cfg(") || !trimmed.endsWith(")")) return Name(trimmed)
+            val expr = CfgExpr.parse(trimmed.substring("cfg(".length, trimmed.length - 1)) ?: return null
+            return Cfg(expr)
+        }
+    }
+}
+
+sealed class CfgExpr {
+    abstract fun evaluate(options: CfgOptions): Boolean
+
+    data class Not(val expr: CfgExpr) : CfgExpr() {
+        override fun evaluate(options: CfgOptions): Boolean = !expr.evaluate(options)
+    }
+
+    data class And(val exprs: List<CfgExpr>) : CfgExpr() {
+        override fun evaluate(options: CfgOptions): Boolean = exprs.all { it.evaluate(options) }
+    }
+
+    data class Or(val exprs: List<CfgExpr>) : CfgExpr() {
+        override fun evaluate(options: CfgOptions): Boolean = exprs.any { it.evaluate(options) }
+    }
+
+    /** `unix` */
+    data class Name(val name: String) : CfgExpr() {
+        override fun evaluate(options: CfgOptions): Boolean = options.isNameEnabled(name)
+    }
+
+    /** `target_os = "linux"` */
+    data class KeyValue(val key: String, val value: String) : CfgExpr() {
+        override fun evaluate(options: CfgOptions): Boolean = options.isNameValueEnabled(key, value)
+    }
+
+    companion object {
+        /** Parses the contents of `cfg(...)`. Returns null if [text] is malformed */
+        fun parse(text: String): CfgExpr? = CfgExprParser(text).parse()
+    }
+}
+
+private class CfgExprParser(private val text: String) {
+    private var offset: Int = 0
+
+    fun parse(): CfgExpr? {
+        val expr = parseExpr() ?: return null
+        skipWhitespaces()
+        return if (offset == text.length) expr else null
+    }
+
+    private fun parseExpr(): CfgExpr? {
+        val name = parseIdentifier() ?: return null
+        return when {
+            consume('(') -> {
+                val args = parseArguments() ?: return null
+                when (name) {
+                    "all" -> CfgExpr.And(args)
+                    "any" -> CfgExpr.Or(args)
+                    "not" -> args.singleOrNull()?.let { CfgExpr.Not(it) }
+                    else -> null
+                }
+            }
+            consume('=') -> parseString()?.let { CfgExpr.KeyValue(name, it) }
+            else -> CfgExpr.Name(name)
+        }
+    }
+
+    /** Parses comma separated expressions until the closing `)`. The trailing comma is allowed */
+    private fun parseArguments(): List<CfgExpr>? {
+        val args = mutableListOf<CfgExpr>()
+        while (!consume(')')) {
+            args += parseExpr() ?: return null
+            if (!consume(',') && !lookAt(')')) return null
+        }
+        return args
+    }
+
+    private fun parseIdentifier(): String? {
+        skipWhitespaces()
+        val start = offset
+        while (offset < text.length && (text[offset].isLetterOrDigit() || text[offset] == '_')) {
+            offset++
+        }
+        if (start == offset || text[start].isDigit()) return null
+        return text.substring(start, offset)
+    }
+
+    private fun parseString(): String? {
+        if (!consume('"')) return null
+        val end = text.indexOf('"', offset)
+        if (end == -1) return null
+        val value = text.substring(offset, end)
+        offset = end + 1
+        return value
+    }
+
+    private fun consume(char: Char): Boolean {
+        if (!lookAt(char)) return false
+        offset++
+        return true
+    }
+
+    private fun lookAt(char: Char): Boolean {
+        skipWhitespaces()
+        return offset < text.length && text[offset] == char
+    }
+
+    private fun skipWhitespaces() {
+        while (offset < text.length && text[offset].isWhitespace()) {
+            offset++
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.actions
+
+import com.intellij.execution.ExecutionException
+import com.intellij.openapi.actionSystem.AnActionEvent
+import com.intellij.openapi.project.DumbAwareAction
+import com.intellij.openapi.vfs.VfsUtil
+import com.intellij.openapiext.isUnitTestMode
+import org.rust.cargo.project.model.CargoProject
+import org.rust.cargo.project.settings.toolchain
+import org.rust.cargo.runconfig.command.workingDirectory
+import org.rust.cargo.runconfig.getAppropriateCargoProject
+import org.rust.cargo.toolchain.Cargo
+import org.rust.cargo.toolchain.Rustup.Companion.checkNeedInstallClippy
+import org.rust.openapiext.computeWithCancelableProgress
+import org.rust.openapiext.saveAllDocumentsAsTheyAre
+
+/** Runs `cargo clippy --fix --allow-dirty` for the whole cargo project */
+class RsClippyFixCargoProjectAction : DumbAwareAction() {
+
+    override fun update(e: AnActionEvent) {
+        super.update(e)
+        e.presentation.isEnabled = getContext(e) != null
+    }
+
+    override fun actionPerformed(e: AnActionEvent) {
+        val (cargoProject, cargo) = getContext(e) ?: return
+        val project = cargoProject.project
+        saveAllDocumentsAsTheyAre(reformatLater = false)
+        try {
+            if (checkNeedInstallClippy(project, cargoProject.workingDirectory)) return
+            project.computeWithCancelableProgress("Applying Clippy Fixes...") {
+                cargo.fixWithClippy(project, project, cargoProject.workingDirectory)
+            }
+            val rootDir = cargoProject.rootDir ?: return
+            // We want to refresh file synchronously only in unit test to get new text right after the fix
+            VfsUtil.markDirtyAndRefresh(!isUnitTestMode, true, true, rootDir)
+        } catch (e: ExecutionException) {
+            // Just easy way to know that something wrong happened
+            if (isUnitTestMode) throw e
+        }
+    }
+
+    private fun getContext(e: AnActionEvent): Pair<CargoProject, Cargo>? {
+        val cargoProject = getAppropriateCargoProject(e.dataContext) ?: return null
+        val cargo = cargoProject.project.toolchain?.cargoOrWrapper(cargoProject.workingDirectory) ?: return null
+        return Pair(cargoProject, cargo)
+    }
+}
@@ -11,12 +11,14 @@ import com.intellij.ide.annotator.AnnotatorBase
 import com.intellij.lang.annotation.AnnotationHolder
 import com.intellij.lang.annotation.AnnotationSession
 import com.intellij.openapi.util.Key
+import com.intellij.openapi.util.text.StringUtil
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiFile
 import com.intellij.psi.util.PsiTreeUtil
 import org.rust.cargo.project.workspace.CargoWorkspace.Edition
 import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.ide.annotator.fixes.*
+import org.rust.ide.inspections.fixes.AddTypeParameterBoundFix
 import org.rust.ide.refactoring.RsNamesValidator.Companion.RESERVED_LIFETIME_NAMES
 import org.rust.ide.utils.isCfgUnknown
 import org.rust.ide.utils.isEnabledByCfg
@@ -25,13 +27,16 @@ import org.rust.lang.core.FeatureAvailability.CAN_BE_ADDED
 import org.rust.lang.core.FeatureAvailability.NOT_AVAILABLE
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.RsElementTypes.IDENTIFIER
+import org.rust.lang.core.macros.macroExpansionManager
 import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.ImplLookup
 import org.rust.lang.core.resolve.Namespace
 import org.rust.lang.core.resolve.knownItems
 import org.rust.lang.core.resolve.namespaces
 import org.rust.lang.core.resolve.ref.deepResolve
 import org.rust.lang.core.types.*
 import org.rust.lang.core.types.consts.asLong
+import org.rust.lang.core.types.infer.bounds
 import org.rust.lang.core.types.ty.*
 import org.rust.lang.utils.RsDiagnostic
 import org.rust.lang.utils.RsErrorCode
@@ -45,7 +50,7 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
         val holder = RsAnnotationHolder(rawHolder)
         val visitor = object : RsVisitor() {
             override fun visitBaseType(o: RsBaseType) = checkBaseType(holder, o)
-            override fun visitCondition(o: RsCondition) = checkCondition(holder, o)
+            override fun visitLetExpr(o: RsLetExpr) = checkLetExpr(holder, o)
             override fun visitConstant(o: RsConstant) = checkConstant(holder, o)
             override fun visitTypeArgumentList(o: RsTypeArgumentList) = checkTypeArgumentList(holder, o)
             override fun visitValueParameterList(o: RsValueParameterList) = checkValueParameterList(holder, o)
@@ -64,6 +69,8 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
             override fun visitPatField(o: RsPatField) = checkPatField(holder, o)
             override fun visitPatBinding(o: RsPatBinding) = checkPatBinding(holder, o)
             override fun visitPatRest(o: RsPatRest): Unit = checkPatRest(holder, o)
+            override fun visitPatRange(o: RsPatRange) = checkPatRange(holder, o)
+            override fun visitPatOr(o: RsPatOr) = checkPatOr(holder, o)
             override fun visitPath(o: RsPath) = checkPath(holder, o)
             override fun visitNamedFieldDecl(o: RsNamedFieldDecl) = checkDuplicates(holder, o)
             override fun visitRetExpr(o: RsRetExpr) = checkRetExpr(holder, o)
@@ -96,6 +103,7 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
             override fun visitPatStruct(o: RsPatStruct) = checkRsPatStruct(holder, o)
             override fun visitPatTupleStruct(o: RsPatTupleStruct) = checkRsPatTupleStruct(holder, o)
             override fun visitStructLiteralField(o: RsStructLiteralField) = checkReferenceIsPublic(o, o, holder)
+            override fun visitMacroCall(o: RsMacroCall) = checkMacroCall(holder, o)
         }
 
         element.accept(visitor)
@@ -110,6 +118,31 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
         }
     }
 
+    private fun checkMacroCall(holder: RsAnnotationHolder, call: RsMacroCall) {
+        checkMacroExpansionTokenLimit(holder, call)
+        checkMacroExpansionDepthLimit(holder, call)
+    }
+
+    private fun checkMacroExpansionTokenLimit(holder: RsAnnotationHolder, call: RsMacroCall) {
+        if (call.project.macroExpansionManager.getMacroCallWithReachedTokenLimit(call) == null) return
+        val message = "Macro expansion token limit reached"
+        val annotation = holder.createErrorAnnotation(call.path, message) ?: return
+        annotation.tooltip =
+            "$message: consider increasing `Macro expansion token limit` in Settings | Languages & Frameworks | Rust"
+    }
+
+    private fun checkMacroExpansionDepthLimit(holder: RsAnnotationHolder, call: RsMacroCall) {
+        val innermostCall = call.project.macroExpansionManager.getMacroCallWithReachedDepthLimit(call) ?: return
+        val message = "Macro expansion depth limit reached"
+        val annotation = holder.createErrorAnnotation(call.path, message) ?: return
+
+        // Point to the macro whose expansion is nested too deep, most likely it is a recursive one
+        val def = innermostCall.resolveToMacro() ?: return
+        val defFile = def.containingFile?.virtualFile?.takeIf { it.isInLocalFileSystem } ?: return
+        val link = "<a href=\"#navigation/${defFile.path}:${def.textOffset}\">${StringUtil.escapeXmlEntities(def.name ?: "macro")}</a>"
+        annotation.tooltip = "<html>$message: consider increasing the limit or check recursive macro $link</html>"
+    }
+
     private fun checkRsPatStruct(holder: RsAnnotationHolder, patStruct: RsPatStruct) {
         val declaration = patStruct.path.reference?.deepResolve() as? RsFieldsOwner ?: return
         val declarationFieldNames = declaration.fields.map { it.name }
@@ -244,12 +277,49 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
     private fun checkCallExpr(holder: RsAnnotationHolder, o: RsCallExpr) {
         val path = (o.expr as? RsPathExpr)?.path ?: return
         checkNotCallingDrop(o, holder)
-        val owner = path.reference?.deepResolve() as? RsFieldsOwner ?: return
+        val target = path.reference?.deepResolve()
+        if (target is RsFunction) {
+            checkArgumentsSatisfyBounds(holder, o, target)
+        }
+        val owner = target as? RsFieldsOwner ?: return
         if (owner.tupleFields == null && !owner.implLookup.isAnyFn(owner.asTy())) {
             RsDiagnostic.ExpectedFunction(o).addToHolder(holder)
         }
     }
 
+    // E0277: a value of a type parameter is passed to a generic function whose bounds the parameter doesn't have,
+    // e.g. `fn foo<T>(t: T) { bar(t) }` where `fn bar<U: Clone>(u: U)`
+    private fun checkArgumentsSatisfyBounds(holder: RsAnnotationHolder, call: RsCallExpr, fn: RsFunction) {
+        if (fn.hasSelfParameters) return
+        if ((call.expr as? RsPathExpr)?.path?.typeArgumentList != null) return
+        val arguments = call.valueArgumentList.exprList
+        val parameters = fn.valueParameters
+        if (arguments.size != parameters.size) return
+        val bounds = fn.bounds
+        if (bounds.isEmpty()) return
+
+        val lookup = ImplLookup.relativeTo(call)
+        for ((parameter, argument) in parameters.zip(arguments)) {
+            val parameterTy = parameter.typeReference?.type as? TyTypeParameter ?: continue
+            val argumentTy = argument.type as? TyTypeParameter ?: continue
+            val argumentTypeParameter = (argumentTy.parameter as? TyTypeParameter.Named)?.parameter ?: continue
+            for (bound in bounds) {
+                if (bound.selfTy != parameterTy) continue
+                val trait = bound.trait.element
+                // The substitution of a generic trait depends on the inferred types
+                if (trait.typeParameters.isNotEmpty()) continue
+                if (lookup.canSelect(TraitRef(argumentTy, bound.trait))) continue
+                val traitName = trait.name ?: continue
+                val fixes = if (argumentTypeParameter.containingFile == call.containingFile) {
+                    listOf(AddTypeParameterBoundFix(argumentTypeParameter, trait))
+                } else {
+                    emptyList()
+                }
+                RsDiagnostic.TraitBoundIsNotSatisfied(argument, argumentTy, traitName, fixes).addToHolder(holder)
+            }
+        }
+    }
+
     private fun checkTraitRef(holder: RsAnnotationHolder, o: RsTraitRef) {
         val item = o.path.reference?.resolve() as? RsItemElement ?: return
         if (item !is RsTraitItem) {
@@ -358,6 +428,15 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
         BOX_PATTERNS.check(holder, box.box, "`box` pattern syntax")
     }
 
+    private fun checkPatRange(holder: RsAnnotationHolder, range: RsPatRange) {
+        if (range.end != null) return
+        HALF_OPEN_RANGE_PATTERNS.check(holder, range, "half-open range pattern")
+    }
+
+    private fun checkPatOr(holder: RsAnnotationHolder, patOr: RsPatOr) {
+        OR_PATTERNS.check(holder, patOr, "or-patterns syntax")
+    }
+
     private fun checkPatField(holder: RsAnnotationHolder, field: RsPatField) {
         val box = field.box ?: return
         BOX_PATTERNS.check(holder, box, "`box` pattern syntax")
@@ -675,7 +754,7 @@ class RsErrorAnnotator : AnnotatorBase(), HighlightRangeExtension {
         }
     }
 
-    private fun checkCondition(holder: RsAnnotationHolder, element: RsCondition) {
+    private fun checkLetExpr(holder: RsAnnotationHolder, element: RsLetExpr) {
         val patList = element.patList
         val pat = patList.singleOrNull()
         if (pat != null && pat.isIrrefutable) {
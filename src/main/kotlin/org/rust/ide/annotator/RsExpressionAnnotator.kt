@@ -81,6 +81,13 @@ private class RedundantParenthesisVisitor(private val holder: RsAnnotationHolder
     override fun visitCondition(o: RsCondition) =
         o.expr.warnIfParens("Predicate expression has unnecessary parentheses")
 
+    override fun visitLetExpr(o: RsLetExpr) {
+        val expr = o.expr
+        // `if let Some(x) = (a || b) {}` - the parentheses are required
+        if (expr is RsParenExpr && (expr.expr as? RsBinaryExpr)?.operatorType is LogicOp) return
+        expr.warnIfParens("Scrutinee expression has unnecessary parentheses")
+    }
+
     override fun visitRetExpr(o: RsRetExpr) =
         o.expr.warnIfParens("Return expression has unnecessary parentheses")
 
@@ -5,6 +5,7 @@
 
 package org.rust.ide.annotator
 
+import com.google.common.annotations.VisibleForTesting
 import com.google.gson.JsonParser
 import com.google.gson.stream.JsonReader
 import com.intellij.CommonBundle
@@ -25,6 +26,7 @@ import com.intellij.openapi.project.Project
 import com.intellij.openapi.util.Computable
 import com.intellij.openapi.util.Disposer
 import com.intellij.openapi.util.TextRange
+import com.intellij.openapi.util.io.FileUtil
 import com.intellij.openapiext.isUnitTestMode
 import com.intellij.psi.PsiFile
 import com.intellij.psi.impl.AnyPsiChangeListener
@@ -33,10 +35,13 @@ import com.intellij.psi.util.PsiModificationTracker
 import com.intellij.util.PathUtil
 import com.intellij.util.messages.MessageBus
 import org.apache.commons.lang.StringEscapeUtils
+import org.rust.cargo.CargoConstants
+import org.rust.cargo.project.settings.rustSettings
 import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.cargo.toolchain.*
 import org.rust.ide.annotator.RsExternalLinterFilteredMessage.Companion.filterMessage
 import org.rust.ide.annotator.RsExternalLinterUtils.TEST_MESSAGE
+import org.rust.ide.annotator.fixes.ApplyAllSuggestionsFix
 import org.rust.ide.annotator.fixes.ApplySuggestionFix
 import org.rust.lang.RsConstants
 import org.rust.lang.core.psi.RsFile
@@ -139,7 +144,7 @@ object RsExternalLinterUtils {
         }
         ProgressManager.checkCanceled()
         if (output.isCancelled) return null
-        return RsExternalLinterResult(output.stdoutLines)
+        return RsExternalLinterResult(output.stdoutLines, toolchain.pathMapper)
     }
 
     private data class Key(
@@ -168,16 +173,28 @@ fun MessageBus.createDisposableOnAnyPsiChange(): Disposable {
 }
 
 fun AnnotationHolder.createAnnotationsForFile(file: RsFile, annotationResult: RsExternalLinterResult) {
-    val cargoPackageOrigin = file.containingCargoPackage?.origin
-    if (cargoPackageOrigin != PackageOrigin.WORKSPACE) return
+    val cargoPackage = file.containingCargoPackage
+    if (cargoPackage?.origin != PackageOrigin.WORKSPACE) return
 
     val doc = file.viewProvider.document
         ?: error("Can't find document for $file in external linter")
 
+    val workspace = cargoPackage.workspace
+    val targetDir = workspace.targetDirectoryPath ?: workspace.contentRoot.resolve(CargoConstants.ProjectLayout.target)
+    val spanMatcher = RsSpanFileMatcher(file, workspace.contentRoot, targetDir, annotationResult.pathMapper)
     val filteredMessages = annotationResult.messages
-        .mapNotNull { (topMessage) -> filterMessage(file, doc, topMessage) }
+        .mapNotNull { (topMessage) -> filterMessage(file, doc, topMessage, spanMatcher) }
         // Cargo can duplicate some error messages when `--all-targets` attribute is used
         .distinct()
+    val machineApplicableFixes = filteredMessages
+        .flatMap { it.quickFixes }
+        .filter { it.applicability == Applicability.MACHINE_APPLICABLE }
+        .distinct()
+    val applyAllFix = if (machineApplicableFixes.size > 1) {
+        ApplyAllSuggestionsFix(file.project.rustSettings.externalLinter, file, machineApplicableFixes)
+    } else {
+        null
+    }
     for (message in filteredMessages) {
         // We can't control what messages cargo generates, so we can't test them well.
         // Let's use special message for tests to distinguish annotation from external linter
@@ -189,11 +206,18 @@ fun AnnotationHolder.createAnnotationsForFile(file: RsFile, annotationResult: Rs
                 problemGroup = ProblemGroup { annotationMessage }
                 setNeedsUpdateOnTyping(true)
                 message.quickFixes.forEach(::registerFix)
+                if (applyAllFix != null && message.quickFixes.any { it in machineApplicableFixes }) {
+                    registerFix(applyAllFix)
+                }
             }
     }
 }
 
-class RsExternalLinterResult(commandOutput: List<String>) {
+class RsExternalLinterResult(
+    commandOutput: List<String>,
+    /** Maps the paths of the files in the messages to the local ones */
+    val pathMapper: RsPathMapper = RsPathMapper.Local
+) {
     val messages: List<CargoTopMessage> = commandOutput.asSequence()
         .filter { MESSAGE_REGEX.matches(it) }
         .map { JsonReader(StringReader(it)).apply { isLenient = true } }
@@ -215,7 +239,12 @@ private data class RsExternalLinterFilteredMessage(
     val quickFixes: List<ApplySuggestionFix>
 ) {
     companion object {
-        fun filterMessage(file: PsiFile, document: Document, message: RustcMessage): RsExternalLinterFilteredMessage? {
+        fun filterMessage(
+            file: PsiFile,
+            document: Document,
+            message: RustcMessage,
+            spanMatcher: RsSpanFileMatcher
+        ): RsExternalLinterFilteredMessage? {
             if (message.message.startsWith("aborting due to") || message.message.startsWith("cannot continue")) {
                 return null
             }
@@ -235,8 +264,7 @@ private data class RsExternalLinterFilteredMessage(
                 return null
             }
 
-            val spanFilePath = PathUtil.toSystemIndependentName(span.file_name)
-            if (!file.virtualFile.path.endsWith(spanFilePath)) return null
+            if (!spanMatcher.matches(span)) return null
 
             val textRange = span.toTextRange(document) ?: return null
 
@@ -261,24 +289,66 @@ private data class RsExternalLinterFilteredMessage(
                 textRange,
                 message.message.capitalize(),
                 tooltip,
-                message.collectQuickFixes(file, document)
+                message.collectQuickFixes(file, document, spanMatcher)
             )
         }
     }
 }
 
+/**
+ * Checks whether a [RustcSpan] refers to [file]. The span path is either relative to the workspace root
+ * or an absolute path on the machine where the toolchain runs, so absolute paths are translated with [pathMapper].
+ *
+ * [targetDir] is the local path of the workspace `target` directory (it can be moved with `CARGO_TARGET_DIR`
+ * or `build.target-dir`), spans in it are never matched
+ */
+@VisibleForTesting
+class RsSpanFileMatcher(file: PsiFile, workspaceRoot: Path, targetDir: Path, private val pathMapper: RsPathMapper) {
+    private val filePath: String = file.virtualFile.path
+    private val remoteTargetDir: String = pathMapper.toRemote(PathUtil.toSystemIndependentName(targetDir.toString()))
+
+    /** `null` if the target directory is outside the workspace root */
+    private val relativeTargetDir: String? = FileUtil.getRelativePath(
+        PathUtil.toSystemIndependentName(workspaceRoot.toString()),
+        PathUtil.toSystemIndependentName(targetDir.toString()),
+        '/'
+    )?.takeIf { !it.startsWith("..") }
+
+    fun matches(span: RustcSpan): Boolean {
+        val spanPath = PathUtil.toSystemIndependentName(span.file_name)
+        if (!isAbsolute(spanPath)) {
+            val isInTargetDir = relativeTargetDir != null && FileUtil.isAncestor(relativeTargetDir, spanPath, false)
+            return !isInTargetDir && filePath.endsWith(spanPath)
+        }
+        // Code generated by build scripts and macros can't be mapped back to the sources
+        if (FileUtil.isAncestor(remoteTargetDir, spanPath, false)) return false
+        val localPath = pathMapper.toLocal(spanPath) ?: return false
+        return FileUtil.pathsEqual(localPath, filePath)
+    }
+
+    // Remote paths can be Unix ones even on Windows
+    private fun isAbsolute(path: String): Boolean = path.startsWith("/") || FileUtil.isAbsolute(path)
+}
+
 fun RustcSpan.isValid(): Boolean =
     line_end > line_start || (line_end == line_start && column_end >= column_start)
 
 private fun ErrorCode?.formatAsLink(): String? =
     if (this?.code.isNullOrBlank()) null else "<a href=\"${RsConstants.ERROR_INDEX_URL}#${this?.code}\">${this?.code}</a>"
 
-private fun RustcMessage.collectQuickFixes(file: PsiFile, document: Document): List<ApplySuggestionFix> {
+private fun RustcMessage.collectQuickFixes(
+    file: PsiFile,
+    document: Document,
+    spanMatcher: RsSpanFileMatcher
+): List<ApplySuggestionFix> {
+    val showMaybeIncorrect = file.project.rustSettings.externalLinterMaybeIncorrectFixes
     val quickFixes = mutableListOf<ApplySuggestionFix>()
 
     fun go(message: RustcMessage) {
-        val span = message.spans.firstOrNull { it.is_primary && it.isValid() }
-        createQuickFix(file, document, span, message.message)?.let { quickFixes.add(it) }
+        val fix = createQuickFix(file, document, message, spanMatcher)
+        if (fix != null && (fix.applicability == Applicability.MACHINE_APPLICABLE || showMaybeIncorrect)) {
+            quickFixes.add(fix)
+        }
         message.children.forEach(::go)
     }
 
@@ -286,12 +356,39 @@ private fun RustcMessage.collectQuickFixes(file: PsiFile, document: Document): L
     return quickFixes
 }
 
-private fun createQuickFix(file: PsiFile, document: Document, span: RustcSpan?, message: String): ApplySuggestionFix? {
-    if (span?.suggested_replacement == null || span.suggestion_applicability == null) return null
-    val textRange = span.toTextRange(document) ?: return null
-    val endElement = file.findElementAt(textRange.endOffset - 1) ?: return null
-    val startElement = file.findElementAt(textRange.startOffset) ?: endElement
-    return ApplySuggestionFix(message, span.suggested_replacement, startElement, endElement)
+/** Creates a fix which applies all replacements suggested by [message] at once */
+private fun createQuickFix(
+    file: PsiFile,
+    document: Document,
+    message: RustcMessage,
+    spanMatcher: RsSpanFileMatcher
+): ApplySuggestionFix? {
+    val spans = message.spans.filter { it.suggested_replacement != null && it.suggestion_applicability != null }
+    if (spans.isEmpty()) return null
+    val replacements = spans
+        .map { span ->
+            // Suggestions can refer to other files, their ranges don't make sense in [document]
+            if (!span.isValid() || !spanMatcher.matches(span)) return null
+            // A span inside a macro expansion can't be mapped to the text of the macro call
+            if (span.expansion != null) return null
+            val range = span.toReplacementRange(document) ?: return null
+            ApplySuggestionFix.Replacement(range, span.suggested_replacement ?: return null)
+        }
+        // Alternative suggestions, e.g. different paths of an item to import, replace the same range.
+        // Only the first of them is used
+        .distinctBy { it.range }
+        .sortedBy { it.range.startOffset }
+    if (replacements.zipWithNext().any { (prev, next) -> prev.range.endOffset > next.range.startOffset }) return null
+    // If the parts of the suggestion differ in applicability, the least certain one is used
+    val applicability = spans.mapNotNull { it.suggestion_applicability }.max() ?: return null
+    return ApplySuggestionFix(message.message, applicability, file, replacements)
+}
+
+/** Unlike [RustcSpan.toTextRange], allows empty ranges which are used to suggest insertions */
+private fun RustcSpan.toReplacementRange(document: Document): TextRange? {
+    val startOffset = RustcSpan.toOffset(document, line_start, column_start) ?: return null
+    val endOffset = RustcSpan.toOffset(document, line_end, column_end) ?: return null
+    return if (startOffset <= endOffset) TextRange(startOffset, endOffset) else null
 }
 
 private fun formatMessage(message: String): String {
@@ -11,7 +11,6 @@ import com.intellij.lang.annotation.HighlightSeverity
 import com.intellij.openapi.util.TextRange
 import com.intellij.openapiext.isUnitTestMode
 import com.intellij.psi.PsiElement
-import org.intellij.lang.annotations.Language
 import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.ide.colors.RsColor
 import org.rust.ide.injected.isDoctestInjection
@@ -30,13 +29,14 @@ import org.rust.lang.core.types.ty.TyNever
 import org.rust.lang.core.types.ty.TyUnknown
 import org.rust.lang.core.types.ty.stripReferences
 import org.rust.lang.core.types.type
-import org.rust.lang.utils.parseRustStringCharacters
+import org.rust.lang.utils.FormatStringParseContext
+import org.rust.lang.utils.parseFormatString
 
 class RsFormatMacroAnnotator : AnnotatorBase() {
     override fun annotateInternal(element: PsiElement, holder: AnnotationHolder) {
         val formatMacro = element as? RsMacroCall ?: return
 
-        val macroPos = macroToFormatPos(formatMacro.macroName) ?: return
+        val macroPos = formatMacro.formatStringArgumentIndex ?: return
         val macroArgs = formatMacro.formatMacroArgument?.formatMacroArgList ?: return
         val macro = formatMacro.path.reference?.resolve() as? RsMacro ?: return
         if (macro.containingCargoPackage?.origin != PackageOrigin.STDLIB) return
@@ -46,7 +46,7 @@ class RsFormatMacroAnnotator : AnnotatorBase() {
             ?.descendantOfTypeStrict<RsLitExpr>()
             ?: return
 
-        val parseCtx = parseParameters(formatStr) ?: return
+        val parseCtx = parseFormatString(formatStr) ?: return
 
         val errors = checkSyntaxErrors(parseCtx)
         for (error in errors) {
@@ -159,67 +159,9 @@ private data class FormatContext(
     val knownItems: KnownItems = macro.knownItems
 }
 
-private data class ParsedParameter(
-    val completeMatch: MatchResult,
-    val innerContentMatch: MatchResult? = null
-) {
-    val innerContent = completeMatch.groups[2]
-    val range: IntRange = completeMatch.range
-}
-
-private data class ParseContext(val sourceMap: IntArray, val offset: Int, val parameters: List<ParsedParameter>) {
-    fun toSourceRange(range: IntRange, additionalOffset: Int = 0): TextRange =
-        TextRange(sourceMap[range.first + additionalOffset], sourceMap[range.last + additionalOffset] + 1)
-            .shiftRight(offset)
-}
-
 private data class ErrorAnnotation(val range: TextRange, val error: String, val isTraitError: Boolean = false)
 
-private val formatParser = Regex("""\{\{|}}|(\{([^}]*)}?)|(})""")
-
-@Language("Regexp")
-private const val argument = """([a-zA-Z_][\w+]*|\d+)"""
-private val formatParameterParser = Regex("""(?x) # enable comments
-^(?<id>$argument)?
-(:
-    (.?[\^<>])?[+\-]?\#?
-    0?(?!\$) # negative lookahead to parse 0$ as width and 00$ as zero padding followed by width
-    (?<width>$argument\$|\d+)?
-    (\.(?<precision>$argument\$|\d+|\*))?
-    (?<type>\w?\??)?
-)?\s*""")
-
-private fun parseParameters(formatStr: RsLitExpr): ParseContext? {
-    val literalKind = (formatStr.kind as? RsLiteralKind.String) ?: return null
-    if (literalKind.node.elementType in RS_BYTE_STRING_LITERALS) return null
-
-    val rawTextRange = literalKind.offsets.value ?: return null
-    val text = literalKind.rawValue ?: return null
-    val (unescapedText, sourceMap) = if (literalKind.node.elementType == RsElementTypes.RAW_STRING_LITERAL) {
-        val map = text.indices.toList().toIntArray()
-        text to map
-    } else {
-        val (parsedText, map, _) = parseRustStringCharacters(text)
-        parsedText.toString() to map
-    }
-
-    val arguments = formatParser.findAll(unescapedText)
-    val parsed = arguments.map { arg ->
-        if (arg.groups[1] != null) {
-            val innerContent = arg.groups[2] ?: error(" should not be null because can match empty string")
-            val innerContentMatch = formatParameterParser.find(innerContent.value)
-                ?: error(" should be not null because can match empty string")
-
-            ParsedParameter(arg, innerContentMatch)
-        } else {
-            ParsedParameter(arg)
-        }
-    }.toList()
-
-    return ParseContext(sourceMap, formatStr.startOffset + rawTextRange.startOffset, parsed)
-}
-
-private fun checkSyntaxErrors(ctx: ParseContext): List<ErrorAnnotation> {
+private fun checkSyntaxErrors(ctx: FormatStringParseContext): List<ErrorAnnotation> {
     val errors = mutableListOf<ErrorAnnotation>()
 
     for (parameter in ctx.parameters) {
@@ -254,7 +196,7 @@ private fun checkSyntaxErrors(ctx: ParseContext): List<ErrorAnnotation> {
     return errors
 }
 
-private fun highlightParametersOutside(ctx: ParseContext, holder: AnnotationHolder) {
+private fun highlightParametersOutside(ctx: FormatStringParseContext, holder: AnnotationHolder) {
     val key = RsColor.FORMAT_SPECIFIER
     val highlightSeverity = if (isUnitTestMode) key.testSeverity else HighlightSeverity.INFORMATION
 
@@ -266,7 +208,7 @@ private fun highlightParametersOutside(ctx: ParseContext, holder: AnnotationHold
     }
 }
 
-private fun highlightParametersInside(ctx: ParseContext, holder: AnnotationHolder) {
+private fun highlightParametersInside(ctx: FormatStringParseContext, holder: AnnotationHolder) {
     fun highlight(range: IntRange?, offset: Int, color: RsColor = RsColor.IDENTIFIER) {
         if (range != null && !range.isEmpty()) {
             // BACKCOMPAT: 2019.3
@@ -301,7 +243,7 @@ private fun buildLookup(value: String): ParameterLookup {
     }
 }
 
-private fun buildParameters(ctx: ParseContext): List<FormatParameter> {
+private fun buildParameters(ctx: FormatStringParseContext): List<FormatParameter> {
     val ignored = setOf("{{", "}}")
     var implicitPositionCounter = 0
 
@@ -494,18 +436,4 @@ private fun checkArguments(ctx: FormatContext): List<ErrorAnnotation> {
     return errors
 }
 
-// TODO: handle log, tracing
-private fun macroToFormatPos(macro: String): Int? = when (macro) {
-    "println",
-    "print",
-    "eprintln",
-    "eprint",
-    "format",
-    "format_args",
-    "format_args_nl" -> 0
-    "write",
-    "writeln" -> 1
-    else -> null
-}
-
 private fun RsFormatMacroArg.name(): String? = this.identifier?.text
@@ -0,0 +1,66 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.annotator.fixes
+
+import com.intellij.codeInspection.LocalQuickFixAndIntentionActionOnPsiElement
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.cargo.toolchain.ExternalLinter
+
+/**
+ * Applies all machine-applicable [fixes] of the file like `cargo clippy --fix` does.
+ * A fix which overlaps with a preceding one is skipped, it will be suggested again by the next linter run
+ */
+class ApplyAllSuggestionsFix(
+    private val linter: ExternalLinter,
+    file: PsiFile,
+    private val fixes: List<ApplySuggestionFix>
+) : LocalQuickFixAndIntentionActionOnPsiElement(file) {
+    override fun getFamilyName(): String = "Apply all suggested replacements made by external linter"
+    override fun getText(): String = "Apply all $linter fixes in file"
+
+    override fun invoke(
+        project: Project,
+        file: PsiFile,
+        editor: Editor?,
+        startElement: PsiElement,
+        endElement: PsiElement
+    ) {
+        val document = editor?.document ?: file.viewProvider.document ?: return
+        val replacements = mutableListOf<ApplySuggestionFix.Replacement>()
+        var lastEndOffset = -1
+        // Replacements of a fix are sorted by their offsets
+        val fixReplacements = fixes.mapNotNull { it.currentReplacements()?.takeIf { r -> r.isNotEmpty() } }
+        for (fix in fixReplacements.sortedBy { it.first().range.startOffset }) {
+            if (fix.first().range.startOffset < lastEndOffset) continue
+            replacements += fix
+            lastEndOffset = fix.last().range.endOffset
+        }
+        ApplySuggestionFix.applyReplacements(document, replacements)
+    }
+
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (javaClass != other?.javaClass) return false
+
+        other as ApplyAllSuggestionsFix
+
+        if (linter != other.linter) return false
+        if (fixes != other.fixes) return false
+        if (myStartElement != other.myStartElement) return false
+
+        return true
+    }
+
+    override fun hashCode(): Int {
+        var result = linter.hashCode()
+        result = 31 * result + fixes.hashCode()
+        result = 31 * result + (myStartElement?.hashCode() ?: 0)
+        return result
+    }
+}
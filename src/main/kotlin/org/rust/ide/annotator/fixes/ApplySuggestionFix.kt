@@ -6,19 +6,32 @@
 package org.rust.ide.annotator.fixes
 
 import com.intellij.codeInspection.LocalQuickFixAndIntentionActionOnPsiElement
+import com.intellij.openapi.editor.Document
 import com.intellij.openapi.editor.Editor
 import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.TextRange
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiFile
-import org.rust.lang.core.psi.ext.endOffset
-import org.rust.lang.core.psi.ext.startOffset
+import com.intellij.psi.SmartPointerManager
+import com.intellij.psi.SmartPsiFileRange
+import org.rust.cargo.toolchain.Applicability
 
+/**
+ * Applies a suggestion made by external linter. A suggestion can consist of several [replacements],
+ * e.g. clippy's `needless_return` removes both `return` and the trailing semicolon. All of them are applied at once.
+ * The ranges are tracked with smart pointers, so the fix stays correct if the document is changed
+ * after the suggestion is made
+ */
 class ApplySuggestionFix(
     private val message: String,
-    private val replacement: String,
-    startElement: PsiElement,
-    endElement: PsiElement
-) : LocalQuickFixAndIntentionActionOnPsiElement(startElement, endElement) {
+    val applicability: Applicability,
+    file: PsiFile,
+    private val replacements: List<Replacement>
+) : LocalQuickFixAndIntentionActionOnPsiElement(file) {
+    private val rangePointers: List<SmartPsiFileRange> = replacements.map {
+        SmartPointerManager.getInstance(file.project).createSmartPsiFileRangePointer(file, it.range)
+    }
+
     override fun getFamilyName(): String = "Apply the suggested replacement made by external linter"
     override fun getText(): String = "External Linter: $message"
 
@@ -30,9 +43,17 @@ class ApplySuggestionFix(
         endElement: PsiElement
     ) {
         val document = editor?.document ?: file.viewProvider.document ?: return
-        document.replaceString(startElement.startOffset, endElement.endOffset, replacement)
+        applyReplacements(document, currentReplacements() ?: return)
+    }
+
+    /** Returns the replacements with the ranges in the current document, or `null` if some of them are invalidated */
+    fun currentReplacements(): List<Replacement>? = replacements.zip(rangePointers) { replacement, pointer ->
+        val range = pointer.range ?: return null
+        Replacement(TextRange.create(range), replacement.text)
     }
 
+    data class Replacement(val range: TextRange, val text: String)
+
     override fun equals(other: Any?): Boolean {
         if (this === other) return true
         if (javaClass != other?.javaClass) return false
@@ -40,18 +61,30 @@ class ApplySuggestionFix(
         other as ApplySuggestionFix
 
         if (message != other.message) return false
-        if (replacement != other.replacement) return false
+        if (applicability != other.applicability) return false
+        if (replacements != other.replacements) return false
         if (myStartElement != other.myStartElement) return false
-        if (myEndElement != other.myEndElement) return false
 
         return true
     }
 
     override fun hashCode(): Int {
         var result = message.hashCode()
-        result = 31 * result + replacement.hashCode()
+        result = 31 * result + applicability.hashCode()
+        result = 31 * result + replacements.hashCode()
         result = 31 * result + (myStartElement?.hashCode() ?: 0)
-        result = 31 * result + (myEndElement?.hashCode() ?: 0)
         return result
     }
+
+    companion object {
+        /**
+         * Replaces the ranges of the document in one go. The replacements must not overlap.
+         * They are applied from the end of the document, so the offsets of the preceding ones stay valid
+         */
+        fun applyReplacements(document: Document, replacements: List<Replacement>) {
+            for ((range, text) in replacements.sortedByDescending { it.range.startOffset }) {
+                document.replaceString(range.startOffset, range.endOffset, text)
+            }
+        }
+    }
 }
@@ -8,7 +8,6 @@ package org.rust.ide.experiments
 object RsExperiments {
     const val BUILD_TOOL_WINDOW = "org.rust.cargo.build.tool.window"
     const val FETCH_OUT_DIR = "org.rust.cargo.fetch.out.dir"
-    const val EVALUATE_BUILD_SCRIPTS = "org.rust.cargo.evaluate.build.scripts"
     const val MOVE_REFACTORING = "org.rust.ide.refactoring.move"
     const val MACROS_NEW_ENGINE = "org.rust.macros.new.engine"
 }
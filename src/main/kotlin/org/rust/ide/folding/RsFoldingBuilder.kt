@@ -43,6 +43,7 @@ class RsFoldingBuilder : CustomFoldingBuilder(), DumbAware {
             node.psi is RsExternCrateItem -> "/* crates */"
             node.psi is PsiComment -> "/* ... */"
             node.psi is RsValueParameterList -> "(...)"
+            node.psi is RsMetaItemArgs -> "(...)"
             else -> "{...}"
         }
 
@@ -102,6 +103,14 @@ class RsFoldingBuilder : CustomFoldingBuilder(), DumbAware {
             foldBetween(o, o.firstChild, o.lastChild)
         }
 
+        override fun visitMetaItemArgs(o: RsMetaItemArgs) {
+            // Multiline `cfg` predicates and their operators
+            val metaItem = o.parent as? RsMetaItem ?: return
+            if (metaItem.name != "cfg" && !metaItem.isCfgOperator) return
+            if (!o.textContains('\n')) return
+            foldBetween(o, o.firstChild, o.lastChild)
+        }
+
         override fun visitComment(comment: PsiComment) {
             when (comment.tokenType) {
                 BLOCK_COMMENT,
@@ -7,6 +7,7 @@ package org.rust.ide.formatter
 
 import com.intellij.formatting.Alignment
 import com.intellij.formatting.SpacingBuilder
+import com.intellij.formatting.Wrap
 import com.intellij.psi.codeStyle.CodeStyleSettings
 import com.intellij.psi.codeStyle.CommonCodeStyleSettings
 import org.rust.ide.formatter.impl.createSpacingBuilder
@@ -34,7 +35,14 @@ data class RsFmtContext private constructor(
      *
      * @see isFlatBlock
      */
-    val metLBrace: Boolean = false
+    val metLBrace: Boolean = false,
+
+    /**
+     * Stores the wrap shared by the links of a method call chain.
+     *
+     * @see org.rust.ide.formatter.impl.getChainWrap
+     */
+    val chainWrap: Wrap? = null
 ) {
     companion object {
         fun create(settings: CodeStyleSettings): RsFmtContext {
@@ -37,12 +37,14 @@ class RsFmtBlock(
             in FN_DECLS -> Alignment.createAlignment()
             VALUE_PARAMETER_LIST -> ctx.sharedAlignment
             DOT_EXPR ->
-                if (node.treeParent.elementType == DOT_EXPR)
+                if (node.isChainReceiver)
                     ctx.sharedAlignment
                 else
                     Alignment.createAlignment()
+            TRY_EXPR -> if (node.isChainReceiver) ctx.sharedAlignment else null
             else -> null
         }
+        val chainWrap = getChainWrap()
         var metLBrace = false
         val alignment = getAlignmentStrategy()
 
@@ -55,7 +57,8 @@ class RsFmtBlock(
 
                 val childCtx = ctx.copy(
                     metLBrace = metLBrace,
-                    sharedAlignment = sharedAlignment)
+                    sharedAlignment = sharedAlignment,
+                    chainWrap = chainWrap)
 
                 RsFormattingModelBuilder.createBlock(
                     node = childNode,
@@ -77,6 +80,7 @@ class RsFmtBlock(
                 val syntheticBlock = SyntheticRsFmtBlock(
                     representative = dotBlock,
                     subBlocks = children.subList(dotIndex, children.size),
+                    wrap = getChainLinkWrap(node, chainWrap, ctx),
                     ctx = ctx)
                 return children.subList(0, dotIndex).plusElement(syntheticBlock)
             }
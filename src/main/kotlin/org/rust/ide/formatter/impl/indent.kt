@@ -43,6 +43,11 @@ fun RsFmtBlock.computeIndent(child: ASTNode, childCtx: RsFmtContext): Indent? {
                 Indent.getNoneIndent()
             }
 
+    // let Some(x) = foo() else {
+    //     return;
+    // };
+        childType == LET_ELSE_BRANCH || parentType == LET_ELSE_BRANCH -> Indent.getNoneIndent()
+
     //  Indent let declarations
         parentType == LET_DECL -> Indent.getContinuationWithoutFirstIndent()
 
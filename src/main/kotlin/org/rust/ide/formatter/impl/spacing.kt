@@ -50,7 +50,7 @@ fun createSpacingBuilder(commonSettings: CommonCodeStyleSettings, rustSettings:
         .after(SEMICOLON).spaceIf(true)
         .before(SEMICOLON).spaceIf(false)
         .afterInside(AND, ts(REF_LIKE_TYPE, SELF_PARAMETER, PAT_REF, VALUE_PARAMETER)).spaces(0)
-        .beforeInside(Q, TRY_EXPR).spaces(0)
+        .beforeInside(Q, TRY_EXPR).spacing(0, 0, 0, false, 0)
         .afterInside(UNARY_OPS, UNARY_EXPR).spaces(0)
         // `use ::{bar}`
         .between(USE, COLONCOLON).spaces(1)
@@ -138,6 +138,8 @@ fun createSpacingBuilder(commonSettings: CommonCodeStyleSettings, rustSettings:
         .between(MATCH_ARM, MATCH_ARM).spacing(1, 1, if (rustSettings.ALLOW_ONE_LINE_MATCH) 0 else 1, true, 1)
         .between(BLOCK, ELSE_BRANCH).spacing(1, 1, 0, false, 0)
         .betweenInside(ELSE, BLOCK, ELSE_BRANCH).spacing(1, 1, 0, false, 0)
+        .before(LET_ELSE_BRANCH).spacing(1, 1, 0, true, 0)
+        .betweenInside(ELSE, BLOCK, LET_ELSE_BRANCH).spacing(1, 1, 0, false, 0)
 
         //== macros
         .beforeInside(EXCL, MACRO_CALL).spaces(0)
@@ -19,7 +19,7 @@ import org.rust.lang.core.psi.ext.RsMod
 import org.rust.lang.core.psi.ext.elementType
 import com.intellij.psi.tree.TokenSet.create as ts
 
-val SPECIAL_MACRO_ARGS = ts(FORMAT_MACRO_ARGUMENT, LOG_MACRO_ARGUMENT, EXPR_MACRO_ARGUMENT, VEC_MACRO_ARGUMENT, ASSERT_MACRO_ARGUMENT, INCLUDE_MACRO_ARGUMENT)
+val SPECIAL_MACRO_ARGS = ts(FORMAT_MACRO_ARGUMENT, LOG_MACRO_ARGUMENT, EXPR_MACRO_ARGUMENT, VEC_MACRO_ARGUMENT, ASSERT_MACRO_ARGUMENT, INCLUDE_MACRO_ARGUMENT, ASM_MACRO_ARGUMENT)
 
 val NO_SPACE_AROUND_OPS = ts(COLONCOLON, DOT, DOTDOT, DOTDOTDOT, DOTDOTEQ)
 val SPACE_AROUND_OPS = TokenSet.andNot(RS_OPERATORS, NO_SPACE_AROUND_OPS)
@@ -0,0 +1,117 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.formatter.impl
+
+import com.intellij.formatting.Wrap
+import com.intellij.lang.ASTNode
+import com.intellij.psi.TokenType.WHITE_SPACE
+import com.intellij.psi.codeStyle.CommonCodeStyleSettings
+import com.intellij.psi.impl.source.tree.TreeUtil
+import org.rust.ide.formatter.RsFmtContext
+import org.rust.ide.formatter.blocks.RsFmtBlock
+import org.rust.lang.core.psi.RsElementTypes.*
+import org.rust.lang.core.psi.RsLambdaExpr
+import org.rust.lang.core.psi.RsMethodCall
+
+/**
+ * Returns the wrap shared by all links of the method call chain, e.g. `foo.bar().baz()`.
+ * Like rustfmt, a chain which doesn't fit into the right margin is broken with each `.method()` and `.field`
+ * on its own line, and a small chain stays on one line. A chain is always broken if it is wider than
+ * [RsCodeStyleSettings.CHAIN_WIDTH][org.rust.ide.formatter.settings.RsCodeStyleSettings.CHAIN_WIDTH]
+ * (`chain_width` of rustfmt). Chains with a single method call are never broken
+ */
+fun RsFmtBlock.getChainWrap(): Wrap? = when {
+    node.elementType != DOT_EXPR && node.elementType != TRY_EXPR -> null
+    node.isChainReceiver -> ctx.chainWrap
+    node.elementType == DOT_EXPR -> createChainWrap(node, ctx)
+    else -> null
+}
+
+/**
+ * Returns the wrap of the `.method()` or `.field` part of the [DOT_EXPR] chain link [link].
+ * Note that `?` is a part of the receiver of the next link, so it sticks to its link when the chain is wrapped
+ */
+fun getChainLinkWrap(link: ASTNode, chainWrap: Wrap?, ctx: RsFmtContext): Wrap? {
+    if (chainWrap == null) return null
+    val methodCall = link.findChildByType(METHOD_CALL)
+    if (methodCall == null && link.findChildByType(FIELD_LOOKUP) == null) return null
+
+    // rustfmt doesn't break a chain if its last element is a multi-line closure, e.g.
+    // foo.iter().for_each(|x| {
+    //     ...
+    // });
+    if (!link.isChainReceiver && methodCall?.hasMultilineLambdaArgument == true) return null
+
+    // rustfmt keeps the first elements of a chain on the same line while they are not wider than the indent,
+    // but only if the chain starts a line, e.g.
+    // x.iter()
+    //     .map(|x| x * 2);
+    // self.items
+    //     .iter()
+    //     .map(|x| x * 2);
+    // let y = self
+    //     .items
+    //     .iter();
+    val receiver = link.firstChildNode ?: return null
+    val indentSize = ctx.commonSettings.indentOptions?.INDENT_SIZE ?: DEFAULT_INDENT_SIZE
+    if (receiver.textLength <= indentSize && link.chainStartsLine) return null
+
+    return chainWrap
+}
+
+/** `true` if the node is a receiver of a method call or a field lookup, e.g. `foo.bar()` in `foo.bar().baz()` */
+val ASTNode.isChainReceiver: Boolean
+    get() {
+        val parent = treeParent ?: return false
+        return when (parent.elementType) {
+            DOT_EXPR -> true
+            TRY_EXPR -> parent.isChainReceiver
+            else -> false
+        }
+    }
+
+private val ASTNode.isChainLink: Boolean
+    get() = when (elementType) {
+        DOT_EXPR -> true
+        TRY_EXPR -> firstChildNode?.isChainLink == true
+        else -> false
+    }
+
+private val ASTNode.chainStartsLine: Boolean
+    get() {
+        val innermostLink = generateSequence(this) { link -> link.firstChildNode?.takeIf { it.isChainLink } }.last()
+        val prevLeaf = TreeUtil.prevLeaf(innermostLink) ?: return true
+        return prevLeaf.elementType == WHITE_SPACE && prevLeaf.textContains('\n')
+    }
+
+private fun createChainWrap(root: ASTNode, ctx: RsFmtContext): Wrap? {
+    val wrapType = ctx.commonSettings.METHOD_CALL_CHAIN_WRAP
+    if (wrapType == CommonCodeStyleSettings.DO_NOT_WRAP) return null
+    val methodCalls = generateSequence(root) { link -> link.firstChildNode?.takeIf { it.isChainLink } }
+        .count { it.findChildByType(METHOD_CALL) != null }
+    if (methodCalls < 2) return null
+
+    // Like rustfmt, the last element of a chain isn't taken into account, so it may extend to the right margin
+    val receiver = root.firstChildNode ?: return null
+    val actualWrapType = if (receiver.oneLineWidth > ctx.rustSettings.CHAIN_WIDTH) {
+        CommonCodeStyleSettings.WRAP_ALWAYS
+    } else {
+        wrapType
+    }
+    return Wrap.createWrap(actualWrapType, false)
+}
+
+/** The width of the node if it is written in one line, e.g. as it would look after the chain is joined */
+private val ASTNode.oneLineWidth: Int
+    get() = text.lineSequence().sumBy { it.trim().length }
+
+private val ASTNode.hasMultilineLambdaArgument: Boolean
+    get() {
+        val lastArgument = (psi as? RsMethodCall)?.valueArgumentList?.exprList?.lastOrNull()
+        return lastArgument is RsLambdaExpr && lastArgument.textContains('\n')
+    }
+
+private const val DEFAULT_INDENT_SIZE: Int = 4
@@ -43,7 +43,8 @@ class RsTrailingCommaFormatProcessor : PostFormatProcessor {
                 val commaList = CommaList.forElement(element.elementType) ?: return
                 when {
                     commaList.removeTrailingComma(element) -> helper.updateResultRange(1, 0)
-                    commaList.addTrailingCommaForElement(element) -> helper.updateResultRange(0, 1)
+                    settings.rust.ADD_TRAILING_COMMA_IN_MULTILINE && commaList.addTrailingCommaForElement(element) ->
+                        helper.updateResultRange(0, 1)
                 }
             }
         })
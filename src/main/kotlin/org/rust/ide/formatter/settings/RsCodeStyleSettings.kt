@@ -19,6 +19,41 @@ class RsCodeStyleSettings(container: CodeStyleSettings) :
     @JvmField var ALLOW_ONE_LINE_MATCH = false
     @JvmField var MIN_NUMBER_OF_BLANKS_BETWEEN_ITEMS = 1
     @JvmField var PRESERVE_PUNCTUATION = false
+    @JvmField var ADD_TRAILING_COMMA_IN_MULTILINE = true
+    /** The same as `chain_width` option of rustfmt */
+    @JvmField var CHAIN_WIDTH = 60
 
     @JvmField var SPACE_AROUND_ASSOC_TYPE_BINDING = false
+
+    /** Ordinal of [ImportsGranularity] */
+    @JvmField var IMPORTS_GRANULARITY = ImportsGranularity.PRESERVE.ordinal
+    /** Ordinal of [ImportsGrouping] */
+    @JvmField var GROUP_IMPORTS = ImportsGrouping.BY_ORIGIN.ordinal
+
+    val importsGranularity: ImportsGranularity
+        get() = ImportsGranularity.values().getOrNull(IMPORTS_GRANULARITY) ?: ImportsGranularity.PRESERVE
+
+    val importsGrouping: ImportsGrouping
+        get() = ImportsGrouping.values().getOrNull(GROUP_IMPORTS) ?: ImportsGrouping.BY_ORIGIN
+}
+
+/** The same as `imports_granularity` option of rustfmt */
+enum class ImportsGranularity(val presentableName: String) {
+    /** Do not change the granularity of any imports */
+    PRESERVE("Preserve"),
+    /** Merge imports from the same crate into a single use item */
+    CRATE("Crate"),
+    /** Merge imports from the same module into a single use item */
+    MODULE("Module"),
+    /** Import each item with a separate use item */
+    ITEM("Item")
+}
+
+enum class ImportsGrouping(val presentableName: String) {
+    /** std, extern crates, workspace crates, `crate::`, `super::` and `self::` imports */
+    BY_ORIGIN("By origin"),
+    /** Keep groups separated by blank lines, the same as `group_imports = "Preserve"` of rustfmt */
+    PRESERVE("Preserve"),
+    /** std, core and alloc; external crates; `crate::`, `super::` and `self::` imports */
+    STD_EXTERNAL_CRATE("StdExternalCrate")
 }
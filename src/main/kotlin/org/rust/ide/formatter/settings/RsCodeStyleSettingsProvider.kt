@@ -31,6 +31,7 @@ class RsCodeStyleSettingsProvider : CodeStyleSettingsProvider() {
             addSpacesTab(settings)
             addWrappingAndBracesTab(settings)
             addBlankLinesTab(settings)
+            addTab(RsImportsCodeStylePanel(settings))
             addTab(GenerationCodeStylePanel(settings, RsLanguage))
         }
     }
@@ -0,0 +1,82 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.formatter.settings
+
+import com.intellij.application.options.CodeStyleAbstractPanel
+import com.intellij.openapi.editor.colors.EditorColorsScheme
+import com.intellij.openapi.editor.highlighter.EditorHighlighter
+import com.intellij.openapi.fileTypes.FileType
+import com.intellij.openapi.ui.ComboBox
+import com.intellij.psi.codeStyle.CodeStyleSettings
+import com.intellij.ui.EnumComboBoxModel
+import com.intellij.ui.SimpleListCellRenderer
+import org.rust.ide.formatter.rust
+import org.rust.ide.ui.layout
+import org.rust.lang.RsFileType
+import org.rust.lang.RsLanguage
+import javax.swing.JComponent
+
+class RsImportsCodeStylePanel(settings: CodeStyleSettings) : CodeStyleAbstractPanel(RsLanguage, null, settings) {
+    private val granularityComboBox: ComboBox<ImportsGranularity> =
+        ComboBox(EnumComboBoxModel(ImportsGranularity::class.java)).apply {
+            renderer = SimpleListCellRenderer.create("") { it.presentableName }
+        }
+
+    private val groupingComboBox: ComboBox<ImportsGrouping> =
+        ComboBox(EnumComboBoxModel(ImportsGrouping::class.java)).apply {
+            renderer = SimpleListCellRenderer.create("") { it.presentableName }
+        }
+
+    private val panel: JComponent = layout {
+        row("Granularity:", granularityComboBox, """
+            How imports are merged or split by Optimize Imports and auto-import,
+            the same as `imports_granularity` option of rustfmt
+        """)
+        row("Grouping:", groupingComboBox, """
+            How imports are grouped by Optimize Imports,
+            the same as `group_imports` option of rustfmt
+        """)
+    }
+
+    init {
+        resetImpl(settings)
+    }
+
+    override fun getTabTitle(): String = "Imports"
+
+    override fun getRightMargin(): Int = 0
+
+    override fun createHighlighter(scheme: EditorColorsScheme): EditorHighlighter? = null
+
+    override fun getFileType(): FileType = RsFileType
+
+    override fun getPreviewText(): String? = null
+
+    override fun apply(settings: CodeStyleSettings) {
+        val rustSettings = settings.rust
+        rustSettings.IMPORTS_GRANULARITY = selectedGranularity.ordinal
+        rustSettings.GROUP_IMPORTS = selectedGrouping.ordinal
+    }
+
+    override fun isModified(settings: CodeStyleSettings): Boolean {
+        val rustSettings = settings.rust
+        return rustSettings.importsGranularity != selectedGranularity || rustSettings.importsGrouping != selectedGrouping
+    }
+
+    override fun getPanel(): JComponent = panel
+
+    override fun resetImpl(settings: CodeStyleSettings) {
+        val rustSettings = settings.rust
+        granularityComboBox.selectedItem = rustSettings.importsGranularity
+        groupingComboBox.selectedItem = rustSettings.importsGrouping
+    }
+
+    private val selectedGranularity: ImportsGranularity
+        get() = granularityComboBox.selectedItem as? ImportsGranularity ?: ImportsGranularity.PRESERVE
+
+    private val selectedGrouping: ImportsGrouping
+        get() = groupingComboBox.selectedItem as? ImportsGrouping ?: ImportsGrouping.BY_ORIGIN
+}
@@ -53,10 +53,16 @@ class RsLanguageCodeStyleSettingsProvider : LanguageCodeStyleSettingsProvider()
                 consumer.showStandardOptions(
                     WrappingOrBraceOption.KEEP_LINE_BREAKS.name,
                     WrappingOrBraceOption.RIGHT_MARGIN.name,
+                    WrappingOrBraceOption.METHOD_CALL_CHAIN_WRAP.name,
                     WrappingOrBraceOption.ALIGN_MULTILINE_CHAINED_METHODS.name,
                     WrappingOrBraceOption.ALIGN_MULTILINE_PARAMETERS.name,
                     WrappingOrBraceOption.ALIGN_MULTILINE_PARAMETERS_IN_CALLS.name)
 
+                consumer.showCustomOption(RsCodeStyleSettings::class.java,
+                    "CHAIN_WIDTH",
+                    "Chain width",
+                    WRAPPING_CALL_CHAIN)
+
                 consumer.showCustomOption(RsCodeStyleSettings::class.java,
                     "ALLOW_ONE_LINE_MATCH",
                     "Match expressions in one line",
@@ -96,8 +102,8 @@ class RsLanguageCodeStyleSettingsProvider : LanguageCodeStyleSettingsProvider()
             LANGUAGE_SPECIFIC -> {
                 consumer.showStandardOptions()
                 consumer.showCustomOption(RsCodeStyleSettings::class.java,
-                    "NORMALIZE_COMMAS",
-                    "Normalize trailing commas",
+                    "ADD_TRAILING_COMMA_IN_MULTILINE",
+                    "Add trailing comma in multiline constructs",
                     "Other")
 
             }
@@ -116,6 +122,8 @@ class RsLanguageCodeStyleSettingsProvider : LanguageCodeStyleSettingsProvider()
     override fun customizeDefaults(commonSettings: CommonCodeStyleSettings, indentOptions: CommonCodeStyleSettings.IndentOptions) {
         commonSettings.RIGHT_MARGIN = 100
         commonSettings.ALIGN_MULTILINE_PARAMETERS_IN_CALLS = true
+        // Break long method call chains with each `.method()` on its own line like rustfmt
+        commonSettings.METHOD_CALL_CHAIN_WRAP = CommonCodeStyleSettings.WRAP_ON_EVERY_ITEM
 
         // Make default behavior consistent with rustfmt
         commonSettings.LINE_COMMENT_AT_FIRST_COLUMN = false
@@ -5,47 +5,108 @@
 
 package org.rust.ide.hints
 
+import com.google.common.annotations.VisibleForTesting
 import com.intellij.lang.ExpressionTypeProvider
+import com.intellij.openapi.fileEditor.FileEditorManager
+import com.intellij.openapi.util.Key
 import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiDocumentManager
 import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
 import com.intellij.psi.impl.FakePsiElement
-import org.rust.ide.presentation.render
+import com.intellij.psi.util.PsiTreeUtil
+import org.rust.ide.presentation.TypeRenderingLevel
 import org.rust.lang.core.macros.findExpansionElementOrSelf
 import org.rust.lang.core.macros.findMacroCallExpandedFromNonRecursive
 import org.rust.lang.core.macros.mapRangeFromExpansionToCallBodyStrict
-import org.rust.lang.core.psi.RsExpr
-import org.rust.lang.core.psi.RsPat
-import org.rust.lang.core.psi.RsPatField
+import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.RsItemElement
 import org.rust.lang.core.psi.ext.contexts
+import org.rust.lang.core.psi.ext.descendantsOfTypeOrSelf
 import org.rust.lang.core.types.ty.Ty
 import org.rust.lang.core.types.type
 import org.rust.openapiext.escaped
 
+/**
+ * Shows types in "Show Expression Type" action.
+ * The first invocation shows the short form of the type, repeated ones cycle through [TypeRenderingLevel]s
+ */
 class RsExpressionTypeProvider : ExpressionTypeProvider<PsiElement>() {
 
     override fun getErrorHint(): String = "Select an expression!"
 
     override fun getExpressionsAt(pivot: PsiElement): List<PsiElement> =
-        pivot.findExpansionElementOrSelf()
+        getExpressionsAt(pivot, findSelection(pivot))
+
+    /**
+     * If [selection] doesn't match any expression exactly, the largest expression inside it is used,
+     * so the type of `foo.bar()` is shown for ` foo.bar();` selection
+     */
+    @VisibleForTesting
+    fun getExpressionsAt(pivot: PsiElement, selection: TextRange?): List<PsiElement> {
+        val expressions = pivot.findExpansionElementOrSelf()
             .contexts
             .takeWhile { it !is RsItemElement }
             .filter { it is RsExpr || it is RsPat || it is RsPatField }
             .mapNotNull { it.wrapExpandedElements() }
             .toList()
+        if (selection == null || expressions.any { it.textRange == selection }) return expressions
+        val expr = findLargestExpressionInRange(pivot.containingFile, selection) ?: return expressions
+        return listOf(MyFakePsiElement(expr, selection))
+    }
+
+    override fun getInformationHint(element: PsiElement): String = renderHint(element, TypeRenderingLevel.SHORT)
 
-    override fun getInformationHint(element: PsiElement): String {
-        val type = getType(element)
-        return type.render(useAliasNames = true).escaped
+    override fun hasAdvancedInformation(): Boolean = true
+
+    override fun getAdvancedInformationHint(element: PsiElement): String {
+        val level = element.elementWithType.getUserData(LEVEL_KEY) ?: TypeRenderingLevel.SHORT
+        return renderHint(element, level.next)
+    }
+
+    private fun renderHint(element: PsiElement, level: TypeRenderingLevel): String {
+        val elementWithType = element.elementWithType
+        elementWithType.putUserData(LEVEL_KEY, level)
+        TypeRenderingLevel.lastUsed = level
+        return level.render(getType(elementWithType)).escaped
     }
 
     private fun getType(element: PsiElement): Ty = when (element) {
-        is MyFakePsiElement -> getType(element.elementInMacroExpansion)
         is RsExpr -> element.type
         is RsPat -> element.type
         is RsPatField -> element.type
         else -> error("Unexpected element type: $element")
     }
+
+    private fun findSelection(pivot: PsiElement): TextRange? {
+        val file = pivot.containingFile ?: return null
+        val editor = FileEditorManager.getInstance(file.project).selectedTextEditor ?: return null
+        if (PsiDocumentManager.getInstance(file.project).getPsiFile(editor.document) != file) return null
+        val selectionModel = editor.selectionModel
+        if (!selectionModel.hasSelection()) return null
+        return TextRange(selectionModel.selectionStart, selectionModel.selectionEnd)
+    }
+
+    companion object {
+        private val LEVEL_KEY: Key<TypeRenderingLevel> = Key.create("org.rust.ide.hints.TYPE_RENDERING_LEVEL")
+    }
+}
+
+private val PsiElement.elementWithType: PsiElement
+    get() = if (this is MyFakePsiElement) elementInMacroExpansion else this
+
+/** Finds the largest expression which is fully covered by [range] */
+private fun findLargestExpressionInRange(file: PsiFile, range: TextRange): RsExpr? {
+    if (range.isEmpty) return null
+    val start = file.findElementAt(range.startOffset) ?: return null
+    val end = file.findElementAt(range.endOffset - 1) ?: return null
+    var root = PsiTreeUtil.findCommonParent(start, end) ?: return null
+    while (root !is PsiFile && root.parent !is PsiFile && range.contains(root.parent.textRange)) {
+        root = root.parent
+    }
+    return root.descendantsOfTypeOrSelf<RsExpr>()
+        .filter { range.contains(it.textRange) }
+        .maxBy { it.textLength }
 }
 
 /** A hack around [ExpressionTypeProvider] to make it work inside macro calls (by following macro expansions) */
@@ -61,6 +122,10 @@ private fun PsiElement.wrapExpandedElements(): PsiElement? {
     }
 }
 
+/**
+ * An element with a text range different from the range of [elementInMacroExpansion],
+ * e.g. the range of the corresponding macro call argument or of the selection
+ */
 private class MyFakePsiElement(
     val elementInMacroExpansion: PsiElement,
     val textRangeInMacroCall: TextRange
@@ -22,7 +22,7 @@ import org.rust.lang.core.psi.ext.valueParameters
 
 class RsInlayParameterHintsProvider : InlayParameterHintsProvider {
     override fun getSupportedOptions(): List<Option> =
-        RsPlainHint.values.map { it.option } + RsPlainHint.SMART_HINTING
+        RsPlainHint.values.map { it.option } + RsPlainHint.SMART_HINTING + RsPlainHint.TUPLE_FIELD_HINTS
 
     override fun getDefaultBlackList(): Set<String> = emptySet()
 
@@ -127,7 +127,7 @@ class RsInlayTypeHintsProvider : InlayHintsProvider<RsInlayTypeHintsProvider.Set
                         val pat = element.pat ?: return
                         presentTypeForPat(pat, element.expr)
                     }
-                    is RsCondition -> {
+                    is RsLetExpr -> {
                         for (pat in element.patList) {
                             presentTypeForPat(pat, element.expr)
                         }
@@ -0,0 +1,203 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+@file:Suppress("UnstableApiUsage")
+
+package org.rust.ide.hints
+
+import com.intellij.codeInsight.hints.*
+import com.intellij.codeInsight.hints.presentation.InlayPresentation
+import com.intellij.codeInsight.hints.presentation.InsetPresentation
+import com.intellij.codeInsight.hints.presentation.MenuOnClickPresentation
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import com.intellij.ui.components.CheckBox
+import com.intellij.ui.layout.panel
+import com.intellij.util.ui.JBUI
+import org.rust.ide.utils.isEnabledByCfg
+import org.rust.lang.RsLanguage
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import javax.swing.JPanel
+
+/**
+ * Shows lifetimes that the compiler assigns to elided references of a function signature, e.g.
+ * `fn foo(&'1 self, s: &'2 str) -> &'1 str`, and to elided references of impl headers, e.g. `impl Trait for &'1 S`.
+ * The lifetimes are named like in rustc diagnostics.
+ *
+ * See https://doc.rust-lang.org/reference/lifetime-elision.html
+ */
+class RsLifetimeElisionHintsProvider : InlayHintsProvider<RsLifetimeElisionHintsProvider.Settings> {
+    override val key: SettingsKey<Settings> get() = KEY
+
+    override val name: String get() = "Lifetime elision hints"
+
+    override val previewText: String?
+        get() = """
+            struct S { s: String }
+
+            impl S {
+                fn get(&self, key: &str) -> &str { &self.s }
+            }
+
+            fn first_word(s: &str) -> &str { s }
+
+            type Callback = fn(&str) -> &str;
+
+            impl PartialEq<str> for &S {
+                fn eq(&self, other: &str) -> bool { self.s == other }
+            }
+        """.trimIndent()
+
+    override fun createConfigurable(settings: Settings): ImmediateConfigurable = object : ImmediateConfigurable {
+        private val fnPointersField = CheckBox("Show for fn pointer types")
+
+        override fun createComponent(listener: ChangeListener): JPanel {
+            fnPointersField.isSelected = settings.showForFnPointers
+            fnPointersField.addItemListener { handleChange(listener) }
+
+            val panel = panel {
+                row { fnPointersField(pushX) }
+            }
+            panel.border = JBUI.Borders.empty(5)
+            return panel
+        }
+
+        private fun handleChange(listener: ChangeListener) {
+            settings.showForFnPointers = fnPointersField.isSelected
+            listener.settingsChanged()
+        }
+    }
+
+    override fun createSettings(): Settings = Settings()
+
+    override fun getCollectorFor(file: PsiFile, editor: Editor, settings: Settings, sink: InlayHintsSink): InlayHintsCollector? =
+        object : FactoryInlayHintsCollector(editor) {
+
+            override fun collect(element: PsiElement, editor: Editor, sink: InlayHintsSink): Boolean {
+                when (element) {
+                    is RsImplItem -> {
+                        if (!element.isEnabledByCfg) return true
+                        element.collectElidedHeaderReferences().forEachIndexed { index, reference ->
+                            presentLifetime(reference, "'${index + 1}")
+                        }
+                    }
+                    is RsFunction -> {
+                        if (!element.isEnabledByCfg) return true
+                        if (element.lifetimeParameters.isNotEmpty()) return true
+                        val parameters = element.valueParameterList ?: return true
+                        val impl = (element.owner as? RsAbstractableOwner.Impl)?.impl
+                        presentSignature(parameters.selfParameter, parameters.valueParameterList, element.retType, impl)
+                    }
+                    is RsFnPointerType -> {
+                        if (!settings.showForFnPointers) return true
+                        if (!element.isEnabledByCfg) return true
+                        presentSignature(null, element.valueParameters, element.retType)
+                    }
+                }
+                return true
+            }
+
+            /**
+             * @param impl the impl containing the function. Lifetimes of its header are in scope of the signature,
+             * and the numbering continues after the elided lifetimes of the header to not mix them up
+             */
+            private fun presentSignature(
+                self: RsSelfParameter?,
+                parameters: List<RsValueParameter>,
+                retType: RsRetType?,
+                impl: RsImplItem? = null
+            ) {
+                val implLifetimes = impl?.lifetimeParameters?.mapNotNull { it.name }.orEmpty().toSet()
+                val signature = listOfNotNull(self) + parameters + listOfNotNull(retType)
+                if (signature.any { it.hasExplicitLifetimes(except = implLifetimes) }) return
+
+                var lifetimeIndex = impl?.collectElidedHeaderReferences()?.size ?: 0
+                var selfLifetime: String? = null
+                val inputLifetimes = mutableListOf<String>()
+                val typedSelfReference = self?.typeReference?.descendantsOfTypeOrSelf<RsRefLikeType>()
+                    ?.firstOrNull { it.and != null }
+                val selfReference = if (typedSelfReference != null) typedSelfReference.and else self?.and
+                val selfExplicitLifetime = typedSelfReference?.lifetime ?: self?.lifetime
+                if (selfExplicitLifetime != null) {
+                    selfLifetime = selfExplicitLifetime.referenceName
+                    inputLifetimes += selfLifetime
+                } else if (selfReference != null) {
+                    val lifetime = "'${++lifetimeIndex}"
+                    presentLifetime(selfReference, lifetime)
+                    selfLifetime = lifetime
+                    inputLifetimes += lifetime
+                }
+                for (parameter in parameters) {
+                    val typeReference = parameter.typeReference ?: continue
+                    inputLifetimes += typeReference.descendantsOfType<RsLifetime>().map { it.referenceName }
+                    for (reference in typeReference.collectElidedReferences()) {
+                        val lifetime = "'${++lifetimeIndex}"
+                        presentLifetime(reference, lifetime)
+                        inputLifetimes += lifetime
+                    }
+                }
+
+                // If there are several input lifetimes and no `&self`, the output lifetime can't be elided,
+                // so we show nothing instead of a wrong hint
+                val outputLifetime = selfLifetime ?: inputLifetimes.distinct().singleOrNull() ?: return
+                val outputReferences = retType?.typeReference?.collectElidedReferences().orEmpty()
+                for (reference in outputReferences) {
+                    presentLifetime(reference, outputLifetime)
+                }
+            }
+
+            /** @param reference the `&` token of a reference */
+            private fun presentLifetime(reference: PsiElement, lifetime: String) {
+                val presentation = factory.roundWithBackground(factory.smallText(lifetime))
+                val finalPresentation = presentation.withDisableAction(reference.project)
+                sink.addInlineElement(reference.endOffset, false, finalPresentation)
+            }
+        }
+
+    private fun InlayPresentation.withDisableAction(project: Project): InsetPresentation = InsetPresentation(
+        MenuOnClickPresentation(this, project) {
+            listOf(InlayProviderDisablingAction(name, RsLanguage, project, key))
+        }, right = 1
+    )
+
+    data class Settings(
+        var showForFnPointers: Boolean = true
+    )
+
+    companion object {
+        val KEY: SettingsKey<Settings> = SettingsKey("lifetime.elision.hints")
+    }
+}
+
+/** @param except names of lifetimes that are declared outside of the checked signature */
+private fun PsiElement.hasExplicitLifetimes(except: Set<String>): Boolean =
+    descendantsOfType<RsLifetime>().any { it.referenceName !in except }
+
+/** Returns `&` tokens of the references without lifetimes in the trait and the self type of the impl */
+private fun RsImplItem.collectElidedHeaderReferences(): List<PsiElement> =
+    listOfNotNull(traitRef, typeReference).flatMap { it.collectElidedReferences() }
+
+/**
+ * Returns `&` tokens of the references without lifetimes.
+ * Nested fn pointers and `Fn(...)` sugar have their own elision scopes, so they are skipped
+ */
+private fun PsiElement.collectElidedReferences(): List<PsiElement> {
+    val result = mutableListOf<PsiElement>()
+    fun go(element: PsiElement) {
+        if (element is RsFnPointerType) return
+        if ((element is RsValueParameterList || element is RsRetType) && element.parent is RsPath) return
+        if (element is RsRefLikeType) {
+            val and = element.and
+            if (and != null && element.lifetime == null) result += and
+        }
+        for (child in element.children) {
+            go(child)
+        }
+    }
+    go(this)
+    return result
+}
@@ -14,6 +14,8 @@ import com.intellij.openapi.util.TextRange
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiFile
 import org.rust.ide.utils.CallInfo
+import org.rust.lang.core.macros.findExpansionElementOrSelf
+import org.rust.lang.core.macros.isExpandedFromMacro
 import org.rust.lang.core.psi.RsCallExpr
 import org.rust.lang.core.psi.RsElementTypes
 import org.rust.lang.core.psi.RsMethodCall
@@ -36,7 +38,7 @@ class RsParameterInfoHandler : RsAsyncParameterInfoHandler<RsValueArgumentList,
     }
 
     override fun findTargetElement(file: PsiFile, offset: Int): RsValueArgumentList? =
-        file.findElementAt(offset)?.ancestorStrict()
+        file.findElementAt(offset)?.findExpansionElementOrSelf()?.ancestorStrict()
 
     override fun calculateParameterInfo(element: RsValueArgumentList): Array<RsArgumentsDescription>? {
         return RsArgumentsDescription.findDescription(element)?.let { arrayOf(it) }
@@ -47,10 +49,11 @@ class RsParameterInfoHandler : RsAsyncParameterInfoHandler<RsValueArgumentList,
             context.removeHint()
             return
         }
-        val currentParameterIndex = if (parameterOwner.startOffset == context.offset) {
+        val offset = mapOffsetToExpansion(parameterOwner, context.file, context.offset)
+        val currentParameterIndex = if (parameterOwner.startOffset == offset) {
             -1
         } else {
-            ParameterInfoUtils.getCurrentParameterIndex(parameterOwner.node, context.offset, RsElementTypes.COMMA)
+            ParameterInfoUtils.getCurrentParameterIndex(parameterOwner.node, offset, RsElementTypes.COMMA)
         }
         context.setCurrentParameter(currentParameterIndex)
     }
@@ -66,6 +69,18 @@ class RsParameterInfoHandler : RsAsyncParameterInfoHandler<RsValueArgumentList,
             false,
             context.defaultParameterColor)
     }
+
+    /**
+     * If the call is inside a macro call body, [parameterOwner] is an element of the macro expansion,
+     * so the caret offset should be mapped to the expansion as well
+     */
+    private fun mapOffsetToExpansion(parameterOwner: RsValueArgumentList, file: PsiFile, offset: Int): Int {
+        if (!parameterOwner.isExpandedFromMacro) return offset
+        val element = file.findElementAt(offset) ?: return offset
+        val expansionElement = element.findExpansionElementOrSelf()
+        if (expansionElement == element) return offset
+        return expansionElement.startOffset + (offset - element.startOffset)
+    }
 }
 
 /**
@@ -25,6 +25,11 @@ interface RsPlainHint {
 
     companion object {
         val SMART_HINTING = Option("SMART_HINTING", "Show only smart hints", true)
+        val TUPLE_FIELD_HINTS = Option(
+            "SHOW_TUPLE_FIELD_HINTS",
+            "Show positional field hints for tuple struct and enum variant constructors",
+            false
+        )
 
         fun resolve(element: PsiElement): RsPlainHint? =
             values.find { it.isApplicable(element) }
@@ -45,6 +50,13 @@ enum class RsPlainParameterHint(desc: String, enabled: Boolean) : RsPlainHint {
             if (!elem.isEnabledByCfg) return emptyList()
             if (callInfo == null) return emptyList()
 
+            if (callInfo.isTupleConstructor) {
+                if (!RsPlainHint.TUPLE_FIELD_HINTS.get()) return emptyList()
+                return valueArgumentList.exprList
+                    .take(callInfo.parameters.size)
+                    .mapIndexed { index, arg -> InlayInfo("$index:", arg.startOffset) }
+            }
+
             val hints = buildList<String> {
                 if (callInfo.selfParameter != null && elem is RsCallExpr) {
                     add(callInfo.selfParameter)
@@ -35,6 +35,7 @@ object RsIcons {
     val STATIC_MARK = AllIcons.Nodes.StaticMark
     val TEST_MARK = AllIcons.Nodes.JunitTestMark
     val DOCS_MARK = IconLoader.getIcon("/icons/docsrs.svg")
+    val GENERATED_MARK = AllIcons.Nodes.Symlink
 
     // Source code elements
 
@@ -86,6 +87,8 @@ fun Icon.addStaticMark(): Icon = LayeredIcon(this, RsIcons.STATIC_MARK)
 
 fun Icon.addTestMark(): Icon = LayeredIcon(this, RsIcons.TEST_MARK)
 
+fun Icon.addGeneratedMark(): Icon = LayeredIcon(this, RsIcons.GENERATED_MARK)
+
 fun Icon.addVisibilityIcon(pub: Boolean): RowIcon =
     RowIcon(this, if (pub) PlatformIcons.PUBLIC_ICON else PlatformIcons.PRIVATE_ICON)
 
@@ -214,6 +214,28 @@ private fun findDoctestInjectableRanges(text: String, elementType: IElementType)
     }
 }
 
+/**
+ * A fenced code block of a doc comment.
+ * [fenceOffset] and [endOffset] are offsets of the opening and the end of the closing "```" in the comment text
+ */
+class RsDoctestCodeBlock(val fenceOffset: Int, val endOffset: Int, val attributes: List<String>) {
+    /** `ignore` and `no_run` code blocks are not executed by rustdoc; non-Rust blocks are not tests at all */
+    val isRunnable: Boolean
+        get() = attributes.all { it in RUST_LANG_ALIASES && it != "no_run" || it == "compile_fail" }
+}
+
+fun findDoctestCodeBlocks(comment: RsDocCommentImpl): List<RsDoctestCodeBlock> {
+    val text = comment.text
+    return text.indicesOf("```").chunked(2).mapNotNull { idx ->
+        val fenceOffset = idx[0]
+        val closingFenceOffset = idx.getOrNull(1) ?: return@mapNotNull null
+        val langEnd = text.indexOf("\n", fenceOffset)
+        if (langEnd == -1 || langEnd > closingFenceOffset) return@mapNotNull null
+        val attributes = text.substring(fenceOffset + 3, langEnd).split(LANG_SPLIT_REGEX).filter { it.isNotBlank() }
+        RsDoctestCodeBlock(fenceOffset, closingFenceOffset + 3, attributes)
+    }.toList()
+}
+
 val CargoWorkspace.Target.areDoctestsEnabled: Boolean
     get() = doctest && isDoctestable
 
@@ -0,0 +1,97 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.injected
+
+import com.intellij.codeInsight.daemon.DaemonCodeAnalyzer
+import com.intellij.openapi.components.PersistentStateComponent
+import com.intellij.openapi.components.ServiceManager
+import com.intellij.openapi.components.State
+import com.intellij.openapi.components.Storage
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiManager
+import com.intellij.psi.impl.PsiModificationTrackerImpl
+
+/**
+ * Describes a place where [RsLanguageInjector] injects a language.
+ *
+ * [path] is either a macro path ending with `!` (`sqlx::query!`) or a function path (`regex::Regex::new`).
+ * A call matches the rule if its path is a suffix of [path], so `query!(...)` and `Regex::new(...)` match too.
+ * Calls of associated functions must contain the type in their path, so a bare `new(...)` doesn't match.
+ * Method calls (`conn.execute(...)`) match a function rule by the name only, so such rules are applied
+ * only in packages which depend on the crate named by the first segment of [path].
+ *
+ * [argumentIndex] doesn't count the receiver of a method call
+ */
+data class RsLanguageInjectionRule(
+    var path: String = "",
+    var argumentIndex: Int = 0,
+    var languageId: String = "",
+    /** If `true`, format placeholders like `{}` are injected as `?` SQL parameters instead of disabling injection */
+    var maskFormatPlaceholders: Boolean = false
+) {
+    val isMacro: Boolean get() = path.endsWith("!")
+
+    /** Path segments without `!` */
+    val segments: List<String> get() = path.removeSuffix("!").split("::").filter { it.isNotEmpty() }
+}
+
+@State(name = "RsLanguageInjectionSettings", storages = [Storage("rust.xml")])
+class RsLanguageInjectionSettings(private val project: Project) : PersistentStateComponent<RsLanguageInjectionSettings.State> {
+
+    data class State(
+        var rules: MutableList<RsLanguageInjectionRule> = BUILTIN_RULES.mapTo(mutableListOf()) { it.copy() }
+    )
+
+    private var state: State = State()
+
+    val rules: List<RsLanguageInjectionRule> get() = state.rules
+
+    /**
+     * Last path segments of macro rules. Used to cheaply filter out macro calls
+     * which are not injection hosts, see [org.rust.lang.core.psi.ext.RsMacroArgumentMixin.isValidHost]
+     */
+    @Volatile
+    var macroNames: Set<String> = collectMacroNames(state.rules)
+        private set
+
+    override fun getState(): State = state
+
+    override fun loadState(state: State) {
+        this.state = state
+        macroNames = collectMacroNames(state.rules)
+    }
+
+    fun setRules(rules: List<RsLanguageInjectionRule>) {
+        if (rules == state.rules) return
+        state = State(rules.mapTo(mutableListOf()) { it.copy() })
+        macroNames = collectMacroNames(state.rules)
+        // flush injection cache
+        (PsiManager.getInstance(project).modificationTracker as PsiModificationTrackerImpl).incCounter()
+        DaemonCodeAnalyzer.getInstance(project).restart()
+    }
+
+    companion object {
+        val BUILTIN_RULES: List<RsLanguageInjectionRule> = listOf(
+            RsLanguageInjectionRule("sqlx::query!", 0, "SQL"),
+            RsLanguageInjectionRule("sqlx::query_scalar!", 0, "SQL"),
+            RsLanguageInjectionRule("sqlx::query_as!", 1, "SQL"),
+            RsLanguageInjectionRule("sqlx::query_unchecked!", 0, "SQL"),
+            RsLanguageInjectionRule("sqlx::query_as_unchecked!", 1, "SQL"),
+            RsLanguageInjectionRule("diesel::sql_query", 0, "SQL", maskFormatPlaceholders = true),
+            RsLanguageInjectionRule("rusqlite::Connection::execute", 0, "SQL", maskFormatPlaceholders = true),
+            RsLanguageInjectionRule("rusqlite::Connection::execute_batch", 0, "SQL", maskFormatPlaceholders = true),
+            RsLanguageInjectionRule("rusqlite::Connection::prepare", 0, "SQL", maskFormatPlaceholders = true),
+            RsLanguageInjectionRule("regex::Regex::new", 0, "RegExp"),
+            RsLanguageInjectionRule("regex::RegexSet::new", 0, "RegExp")
+        )
+
+        private fun collectMacroNames(rules: List<RsLanguageInjectionRule>): Set<String> =
+            rules.filter { it.isMacro }.mapNotNullTo(hashSetOf()) { it.segments.lastOrNull() }
+
+        fun getInstance(project: Project): RsLanguageInjectionSettings =
+            ServiceManager.getService(project, RsLanguageInjectionSettings::class.java)
+    }
+}
@@ -0,0 +1,242 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.injected
+
+import com.intellij.lang.Language
+import com.intellij.lang.injection.MultiHostInjector
+import com.intellij.lang.injection.MultiHostRegistrar
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiComment
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiLanguageInjectionHost
+import com.intellij.psi.PsiWhiteSpace
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.RsElementTypes.COMMA
+import org.rust.lang.core.psi.RsElementTypes.EXCL
+import org.rust.lang.core.psi.ext.*
+
+/**
+ * Injects languages to string literal arguments of macro and function calls according to
+ * [RsLanguageInjectionRule]s from [RsLanguageInjectionSettings], e.g. SQL to `sqlx::query!("SELECT ...")`
+ * and RegExp to `Regex::new("...")` and `RegexSet::new(&["...", "...", "..."])`.
+ *
+ * Supported arguments are string literals (including raw ones), `concat!` of string literals
+ * (injected as a single fragment), the format string of `format!` and arrays of them (each element is
+ * injected separately).
+ *
+ * Literals with format placeholders (`{}`, `{name}`) are injected only if the rule asks to mask them,
+ * in which case every placeholder is replaced with `?`
+ */
+class RsLanguageInjector : MultiHostInjector {
+    override fun elementsToInjectIn(): List<Class<out PsiElement>> =
+        listOf(RsLitExpr::class.java, RsMacroArgument::class.java)
+
+    override fun getLanguagesToInject(registrar: MultiHostRegistrar, context: PsiElement) {
+        if (!context.isValid) return
+        val rules = RsLanguageInjectionSettings.getInstance(context.project).rules
+        if (rules.isEmpty()) return
+        val injections = when (context) {
+            is RsLitExpr -> findLiteralInjections(context, rules)
+            is RsMacroArgument -> findMacroArgumentInjections(context, rules)
+            else -> return
+        }
+        for (injection in injections) {
+            // An injection spanning several literals is registered only once, from its first literal
+            if (injection.pieces.first().host != context) continue
+            inject(registrar, injection)
+        }
+    }
+
+    private fun inject(registrar: MultiHostRegistrar, injection: Injection) {
+        val placeholders = injection.pieces.map { findFormatPlaceholders(it) }
+        if (placeholders.any { it.isNotEmpty() } && !injection.rule.maskFormatPlaceholders) return
+
+        registrar.startInjecting(injection.language)
+        for ((piece, piecePlaceholders) in injection.pieces.zip(placeholders)) {
+            var start = piece.range.startOffset
+            for (placeholder in piecePlaceholders) {
+                registrar.addPlace(null, SQL_PARAMETER, piece.host, TextRange(start, placeholder.startOffset))
+                start = placeholder.endOffset
+            }
+            registrar.addPlace(null, null, piece.host, TextRange(start, piece.range.endOffset))
+        }
+        registrar.doneInjecting()
+    }
+
+    /** [range] is a range of a string literal value inside [host] */
+    private class Piece(val host: PsiLanguageInjectionHost, val range: TextRange)
+
+    private class Injection(val rule: RsLanguageInjectionRule, val language: Language, val pieces: List<Piece>)
+
+    companion object {
+        private const val SQL_PARAMETER: String = "?"
+        private val FORMAT_PLACEHOLDER: Regex = Regex("""\{\{|}}|\{[^{}]*}""")
+
+        private fun findLiteralInjections(literal: RsLitExpr, rules: List<RsLanguageInjectionRule>): List<Injection> {
+            val argument = findCallArgument(literal) ?: return emptyList()
+            val argumentList = argument.parent as RsValueArgumentList
+            val argumentIndex = argumentList.exprList.indexOf(argument)
+            val rule = when (val call = argumentList.parent) {
+                // We don't use `reference.resolve()` here because this code is sometimes
+                // called from EDT, and resolve can freeze the UI.
+                // See https://github.com/intellij-rust/intellij-rust/issues/2733
+                is RsCallExpr -> {
+                    val segments = (call.expr as? RsPathExpr)?.path?.segments ?: return emptyList()
+                    rules.find { !it.isMacro && it.argumentIndex == argumentIndex && it.matches(segments) }
+                }
+                is RsMethodCall -> rules.find {
+                    !it.isMacro && it.argumentIndex == argumentIndex && it.matchesMethod(call)
+                }
+                else -> null
+            } ?: return emptyList()
+            val language = Language.findLanguageByID(rule.languageId) ?: return emptyList()
+            return collectLiterals(argument).map { literals ->
+                Injection(rule, language, literals.map { Piece(it, it.valueRange!!) })
+            }
+        }
+
+        /**
+         * Returns the outermost expression containing [literal] which is a value argument of a call,
+         * if the literal is a (part of the) string value of the argument
+         */
+        private fun findCallArgument(literal: RsLitExpr): RsExpr? {
+            var expr: RsExpr = literal
+            while (true) {
+                expr = when (val parent = expr.parent) {
+                    is RsValueArgumentList -> return expr
+                    is RsParenExpr -> parent
+                    is RsUnaryExpr -> if (parent.and != null) parent else return null
+                    is RsArrayExpr -> if (parent.arrayElements != null) parent else return null
+                    is RsConcatMacroArgument -> (parent.parent?.parent as? RsMacroExpr) ?: return null
+                    is RsFormatMacroArg -> {
+                        val macroArgument = parent.parent as? RsFormatMacroArgument ?: return null
+                        if (macroArgument.formatMacroArgList.firstOrNull() != parent) return null
+                        (macroArgument.parent?.parent as? RsMacroExpr) ?: return null
+                    }
+                    else -> return null
+                }
+            }
+        }
+
+        /** Each returned list is concatenated into a single injected fragment */
+        private fun collectLiterals(expr: RsExpr): List<List<RsLitExpr>> = when (expr) {
+            is RsLitExpr -> if (expr.valueRange != null) listOf(listOf(expr)) else emptyList()
+            is RsParenExpr -> expr.expr?.let(::collectLiterals).orEmpty()
+            is RsUnaryExpr -> if (expr.and != null) expr.expr?.let(::collectLiterals).orEmpty() else emptyList()
+            is RsArrayExpr -> expr.arrayElements.orEmpty().flatMap(::collectLiterals)
+            is RsMacroExpr -> {
+                val macroCall = expr.macroCall
+                when (macroCall.macroName) {
+                    "concat" -> {
+                        val parts = macroCall.concatMacroArgument?.exprList.orEmpty().map(::collectLiterals)
+                        // The value is known only if all the parts are string literals
+                        if (parts.isNotEmpty() && parts.all { it.size == 1 }) {
+                            listOf(parts.flatMap { it.single() })
+                        } else {
+                            emptyList()
+                        }
+                    }
+                    "format", "format_args" -> {
+                        val formatString = macroCall.formatMacroArgument?.formatMacroArgList?.firstOrNull()?.expr
+                        formatString?.let(::collectLiterals).orEmpty()
+                    }
+                    else -> emptyList()
+                }
+            }
+            else -> emptyList()
+        }
+
+        private fun findMacroArgumentInjections(
+            argument: RsMacroArgument,
+            rules: List<RsLanguageInjectionRule>
+        ): List<Injection> {
+            val macroCall = argument.parent as? RsMacroCall ?: return emptyList()
+            val segments = macroCall.path.segments
+            val matchingRules = rules.filter { it.isMacro && it.matches(segments) }
+            if (matchingRules.isEmpty()) return emptyList()
+            val arguments = argument.macroArgumentTT?.let(::splitByCommas) ?: return emptyList()
+            return matchingRules.mapNotNull { rule ->
+                val tokens = arguments.getOrNull(rule.argumentIndex) ?: return@mapNotNull null
+                val language = Language.findLanguageByID(rule.languageId) ?: return@mapNotNull null
+                val literals = collectLiteralTokens(tokens) ?: return@mapNotNull null
+                Injection(rule, language, literals.map { Piece(argument, it.valueRangeIn(argument)!!) })
+            }
+        }
+
+        /** `"..."` or `concat!("...", "...")` */
+        private fun collectLiteralTokens(tokens: List<PsiElement>): List<PsiElement>? {
+            val literal = tokens.singleOrNull()
+            if (literal != null) return if (literal.isStringLiteral) listOf(literal) else null
+
+            if (tokens.size != 3 || tokens[0].text != "concat" || tokens[1].elementType != EXCL) return null
+            val nested = tokens[2] as? RsMacroArgumentTT ?: return null
+            val parts = splitByCommas(nested).map { it.singleOrNull()?.takeIf { token -> token.isStringLiteral } }
+            if (parts.isEmpty() || parts.any { it == null }) return null
+            return parts.filterNotNull()
+        }
+
+        /**
+         * Splits the token tree into macro arguments. The tokens of nested token trees (`(...)`, `[...]`, `{...}`)
+         * are represented by a single [RsMacroArgumentTT] element, braces are dropped
+         */
+        private fun splitByCommas(tt: RsMacroArgumentTT): List<List<PsiElement>> {
+            val arguments = mutableListOf<MutableList<PsiElement>>(mutableListOf())
+            for (child in tt.childrenWithLeaves) {
+                when {
+                    child is PsiWhiteSpace || child is PsiComment -> Unit
+                    child.elementType == COMMA -> arguments += mutableListOf<PsiElement>()
+                    MacroBraces.fromToken(child.elementType) != null -> Unit
+                    else -> arguments.last() += child
+                }
+            }
+            // A trailing comma
+            if (arguments.size > 1 && arguments.last().isEmpty()) arguments.removeAt(arguments.lastIndex)
+            return arguments
+        }
+
+        private fun findFormatPlaceholders(piece: Piece): List<TextRange> {
+            val text = piece.range.substring(piece.host.text)
+            return FORMAT_PLACEHOLDER.findAll(text)
+                .filter { it.value != "{{" && it.value != "}}" }
+                .map { TextRange(it.range.first, it.range.last + 1).shiftRight(piece.range.startOffset) }
+                .toList()
+        }
+
+        private val RsPath.segments: List<String>
+            get() = text.split("::").map { it.trim() }.filter { it.isNotEmpty() }
+
+        private fun RsLanguageInjectionRule.matches(callSegments: List<String>): Boolean {
+            val ruleSegments = segments
+            // A bare `new("...")` or `prepare("...")` can be anything, so a call of an associated function
+            // must be qualified at least with the type, e.g. `Regex::new("...")`
+            val typeIndex = if (isMacro) -1 else ruleSegments.dropLast(1).indexOfLast { it.first().isUpperCase() }
+            val minSize = if (typeIndex == -1) 1 else ruleSegments.size - typeIndex
+            return callSegments.size >= minSize && ruleSegments.size >= callSegments.size &&
+                ruleSegments.takeLast(callSegments.size) == callSegments
+        }
+
+        private fun RsLanguageInjectionRule.matchesMethod(methodCall: RsMethodCall): Boolean {
+            val ruleSegments = segments
+            if (ruleSegments.size < 2 || ruleSegments.last() != methodCall.referenceName) return false
+            val pkg = methodCall.containingCargoPackage ?: return true
+            return pkg.findDependency(ruleSegments.first()) != null
+        }
+
+        private val RsLitExpr.valueRange: TextRange?
+            get() = node.findChildByType(RS_ALL_STRING_LITERALS)?.psi?.valueRangeIn(this)
+
+        private val PsiElement.isStringLiteral: Boolean
+            get() = elementType in RS_ALL_STRING_LITERALS
+
+        /** The range of the value of this string literal token inside [host] */
+        private fun PsiElement.valueRangeIn(host: PsiElement): TextRange? {
+            val kind = RsLiteralKind.fromAstNode(node) as? RsLiteralKind.String ?: return null
+            if (kind.hasUnpairedQuotes) return null
+            val value = kind.offsets.value ?: return null
+            return value.shiftRight(textRange.startOffset - host.textRange.startOffset)
+        }
+    }
+}
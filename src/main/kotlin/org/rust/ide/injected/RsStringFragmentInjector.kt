@@ -0,0 +1,53 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.injected
+
+import com.intellij.lang.injection.MultiHostInjector
+import com.intellij.lang.injection.MultiHostRegistrar
+import com.intellij.openapi.fileTypes.PlainTextLanguage
+import com.intellij.openapi.util.Key
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiManager
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsLiteralKind
+import org.rust.lang.core.psi.kind
+
+/**
+ * Injects plain text to the value of a string literal which is edited in a fragment editor
+ * (see [org.rust.ide.intentions.EditStringFragmentIntention]). The edited text is written back
+ * to the literal by [RsStringLiteralManipulator], which escapes it and keeps the literal marked
+ */
+class RsStringFragmentInjector : MultiHostInjector {
+    override fun elementsToInjectIn(): List<Class<out PsiElement>> = listOf(RsLitExpr::class.java)
+
+    override fun getLanguagesToInject(registrar: MultiHostRegistrar, context: PsiElement) {
+        if (context !is RsLitExpr || !context.isEditedStringFragment) return
+        val kind = context.kind as? RsLiteralKind.String ?: return
+        if (kind.hasUnpairedQuotes) return
+        val valueRange = kind.offsets.value ?: return
+        registrar.startInjecting(PlainTextLanguage.INSTANCE)
+            .addPlace(null, null, context, valueRange)
+            .doneInjecting()
+    }
+
+    companion object {
+        private val EDITED_STRING_FRAGMENT: Key<Boolean> = Key.create("EDITED_STRING_FRAGMENT")
+
+        val RsLitExpr.isEditedStringFragment: Boolean
+            get() = getUserData(EDITED_STRING_FRAGMENT) == true
+
+        fun startEditing(literal: RsLitExpr) {
+            literal.putUserData(EDITED_STRING_FRAGMENT, true)
+            // Injections are cached until the next PSI modification
+            PsiManager.getInstance(literal.project).dropPsiCaches()
+        }
+
+        /** The literal is replaced on every change of the fragment, so the mark is moved to the new one */
+        fun copyMark(oldLiteral: RsLitExpr, newLiteral: RsLitExpr) {
+            if (oldLiteral.isEditedStringFragment) newLiteral.putUserData(EDITED_STRING_FRAGMENT, true)
+        }
+    }
+}
@@ -7,10 +7,13 @@ package org.rust.ide.injected
 
 import com.intellij.lang.psi.LiteralTextEscaperBase
 import com.intellij.lang.psi.SimpleMultiLineTextEscaper
+import com.intellij.openapi.util.TextRange
 import com.intellij.psi.LiteralTextEscaper
 import org.rust.lang.core.psi.RS_ALL_STRING_LITERALS
 import org.rust.lang.core.psi.RsElementTypes.*
 import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsMacroArgument
+import org.rust.lang.core.psi.ext.elementType
 import org.rust.lang.utils.parseRustStringCharacters
 
 private class RsNormalStringLiteralEscaper(host: RsLitExpr) : LiteralTextEscaperBase<RsLitExpr>(host) {
@@ -28,3 +31,28 @@ fun escaperForLiteral(lit: RsLitExpr): LiteralTextEscaper<RsLitExpr> {
     }
     return if (isRaw) SimpleMultiLineTextEscaper(lit) else RsNormalStringLiteralEscaper(lit)
 }
+
+/**
+ * Decodes string literal tokens inside a macro argument token tree.
+ * The kind of the literal (raw or not) is determined by the token at the start of the decoded range
+ */
+private class RsMacroArgumentLiteralEscaper(host: RsMacroArgument) : LiteralTextEscaperBase<RsMacroArgument>(host) {
+    private var isRaw: Boolean = false
+
+    override fun decode(rangeInsideHost: TextRange, outChars: StringBuilder): Boolean {
+        val elementType = myHost.findElementAt(rangeInsideHost.startOffset)?.elementType
+        isRaw = elementType == RAW_STRING_LITERAL || elementType == RAW_BYTE_STRING_LITERAL
+        return super.decode(rangeInsideHost, outChars)
+    }
+
+    override fun parseStringCharacters(chars: String, outChars: StringBuilder): Pair<IntArray, Boolean> {
+        if (!isRaw) return parseRustStringCharacters(chars, outChars)
+        outChars.append(chars)
+        return IntArray(chars.length + 1) { it } to true
+    }
+
+    override fun isOneLine(): Boolean = false
+}
+
+fun escaperForMacroArgument(argument: RsMacroArgument): LiteralTextEscaper<RsMacroArgument> =
+    RsMacroArgumentLiteralEscaper(argument)
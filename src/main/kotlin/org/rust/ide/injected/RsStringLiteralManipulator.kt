@@ -7,23 +7,39 @@ package org.rust.ide.injected
 
 import com.intellij.openapi.util.TextRange
 import com.intellij.psi.AbstractElementManipulator
-import org.rust.lang.core.psi.RsLitExpr
-import org.rust.lang.core.psi.RsLiteralKind
-import org.rust.lang.core.psi.RsPsiFactory
-import org.rust.lang.core.psi.kind
+import org.rust.lang.core.psi.*
+import org.rust.lang.utils.escapeRust
+import org.rust.lang.utils.rawStringHashesCount
 
+/**
+ * Replaces a part of the value of a string literal with the unescaped [newContent], e.g. when a fragment
+ * of an injected language is edited. The new content is escaped in regular strings, and the delimiters
+ * of raw strings get more `#` if the new value requires them
+ */
 class RsStringLiteralManipulator : AbstractElementManipulator<RsLitExpr>() {
     override fun handleContentChange(element: RsLitExpr, range: TextRange, newContent: String): RsLitExpr {
-        if (range != getRangeInElement(element)) {
-            // FIXME not supported for now
-            return element
-        }
+        val kind = element.kind as? RsLiteralKind.String ?: return element
+        val valueRange = kind.offsets.value ?: return element
+        if (!valueRange.contains(range)) return element
 
         val oldText = element.text
-        val newText = "${oldText.substring(0, range.startOffset)}$newContent${oldText.substring(range.endOffset)}"
+        val newText = if (kind.node.elementType in RS_RAW_LITERALS) {
+            val prefix = kind.offsets.prefix?.substring(oldText).orEmpty()
+            val suffix = kind.offsets.suffix?.substring(oldText).orEmpty()
+            val value = TextRange(valueRange.startOffset, range.startOffset).substring(oldText) +
+                newContent +
+                TextRange(range.endOffset, valueRange.endOffset).substring(oldText)
+            val oldHashes = (kind.offsets.openDelim?.length ?: 1) - 1
+            val hashes = "#".repeat(maxOf(oldHashes, rawStringHashesCount(value)))
+            "$prefix$hashes\"$value\"$hashes$suffix"
+        } else {
+            "${oldText.substring(0, range.startOffset)}${newContent.escapeRust(kind.isByte)}${oldText.substring(range.endOffset)}"
+        }
 
         val newLitExpr = RsPsiFactory(element.project).createExpression(newText)
-        return element.replace(newLitExpr) as RsLitExpr
+        val result = element.replace(newLitExpr) as RsLitExpr
+        RsStringFragmentInjector.copyMark(element, result)
+        return result
     }
 
     override fun getRangeInElement(element: RsLitExpr): TextRange {
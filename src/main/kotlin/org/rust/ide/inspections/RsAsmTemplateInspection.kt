@@ -0,0 +1,86 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.openapi.util.TextRange
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.isExplicitRegister
+import org.rust.lang.core.psi.ext.operandName
+import org.rust.lang.utils.parseRustStringCharacters
+
+/**
+ * Checks that placeholders of `asm!` and `global_asm!` template strings (`{}`, `{0}` or `{name}`)
+ * refer to existing operands
+ */
+class RsAsmTemplateInspection : RsLocalInspectionTool() {
+    override fun getDisplayName(): String = "Invalid asm template placeholder"
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) = object : RsVisitor() {
+        override fun visitMacroCall(o: RsMacroCall) {
+            val argument = o.asmMacroArgument ?: return
+            val operands = argument.asmOperandList
+            // Operands with explicit registers are not counted, they can't be used in the template
+            val positionalCount = operands.count { it.operandName == null && !it.isExplicitRegister }
+            val names = operands.mapNotNullTo(hashSetOf()) { it.operandName }
+
+            var implicitIndex = 0
+            for (template in argument.exprList.filterIsInstance<RsLitExpr>()) {
+                for ((name, range) in template.asmPlaceholders()) {
+                    val message = when {
+                        name.isEmpty() -> {
+                            val index = implicitIndex++
+                            if (index < positionalCount) null else "Invalid reference to argument at index $index"
+                        }
+                        name.all { it.isDigit() } -> {
+                            val index = name.toIntOrNull()
+                            if (index != null && index < positionalCount) null else "Invalid reference to argument at index $name"
+                        }
+                        name in names -> null
+                        else -> "There is no argument named `$name`"
+                    } ?: continue
+                    holder.registerProblem(template, range, message)
+                }
+            }
+        }
+    }
+}
+
+/**
+ * Returns argument names of `{...}` placeholders (the part before `:`, possibly empty)
+ * and their ranges in the literal. Escaped braces (`{{` and `}}`) are skipped
+ */
+private fun RsLitExpr.asmPlaceholders(): List<Pair<String, TextRange>> {
+    val kind = kind as? RsLiteralKind.String ?: return emptyList()
+    if (kind.isByte) return emptyList()
+    val valueRange = kind.offsets.value ?: return emptyList()
+    val rawText = valueRange.substring(text)
+    val (text, sourceMap) = if (node.firstChildNode.elementType == RsElementTypes.RAW_STRING_LITERAL) {
+        rawText to IntArray(rawText.length + 1) { it }
+    } else {
+        val (parsedText, map, _) = parseRustStringCharacters(rawText)
+        parsedText.toString() to map
+    }
+
+    val result = mutableListOf<Pair<String, TextRange>>()
+    var i = 0
+    while (i < text.length) {
+        val c = text[i]
+        if ((c == '{' || c == '}') && text.getOrNull(i + 1) == c) {
+            i += 2
+            continue
+        }
+        if (c == '{') {
+            val end = text.indexOf('}', i)
+            if (end == -1) break
+            val name = text.substring(i + 1, end).substringBefore(':').trim()
+            val range = TextRange(sourceMap[i], sourceMap[end] + 1).shiftRight(valueRange.startOffset)
+            result += name to range
+            i = end
+        }
+        i++
+    }
+    return result
+}
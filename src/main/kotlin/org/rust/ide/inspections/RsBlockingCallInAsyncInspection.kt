@@ -0,0 +1,157 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.LocalQuickFix
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.codeInspection.ui.ListEditForm
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.types.ty.TyAdt
+import org.rust.lang.core.types.type
+import javax.swing.JComponent
+
+/**
+ * Checks for calls of known blocking functions (like `std::thread::sleep`) inside async functions, closures and blocks.
+ * Such calls block the executor thread, so other tasks can't make progress while they're running.
+ */
+class RsBlockingCallInAsyncInspection : RsLocalInspectionTool() {
+
+    /**
+     * Paths of blocking functions. A path ending with `*` matches all functions of a module or a type.
+     * Methods can also be specified relative to the crate root (`std::sync::Mutex::lock`),
+     * because types are usually re-exported from private modules
+     */
+    var blockingFunctions: MutableList<String> = DEFAULT_BLOCKING_FUNCTIONS.toMutableList()
+
+    override fun getDisplayName(): String = "Blocking call in async context"
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) = object : RsVisitor() {
+        override fun visitCallExpr(o: RsCallExpr) {
+            val path = (o.expr as? RsPathExpr)?.path ?: return
+            val function = path.reference?.resolve() as? RsFunction ?: return
+            checkCall(holder, o, path.referenceNameElement ?: path, function)
+        }
+
+        override fun visitMethodCall(o: RsMethodCall) {
+            val function = o.reference.resolve() as? RsFunction ?: return
+            checkCall(holder, o, o.identifier, function)
+        }
+    }
+
+    private fun checkCall(holder: RsProblemsHolder, call: RsElement, anchor: PsiElement, function: RsFunction) {
+        val functionPath = function.blockingFunctionPaths.firstOrNull { path ->
+            blockingFunctions.any { matchesBlockingFunction(it.trim(), path, function.isMethod) }
+        } ?: return
+        if (!call.isInAsyncContext) return
+
+        val fixes = mutableListOf<LocalQuickFix>()
+        if (call is RsCallExpr && functionPath == THREAD_SLEEP && call.hasTokioDependency) {
+            fixes += ReplaceWithTokioSleepFix(call)
+        }
+        holder.registerProblem(
+            anchor,
+            "Blocking call of `${function.name}` in async context",
+            *fixes.toTypedArray()
+        )
+    }
+
+    override fun createOptionsPanel(): JComponent =
+        ListEditForm("Blocking functions", blockingFunctions).contentPanel
+
+    private class ReplaceWithTokioSleepFix(call: RsCallExpr) : LocalQuickFixOnPsiElement(call) {
+        override fun getText(): String = "Replace with `tokio::time::sleep(...).await`"
+        override fun getFamilyName(): String = text
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val call = startElement as? RsCallExpr ?: return
+            val arguments = call.valueArgumentList.exprList.joinToString(", ") { it.text }
+            call.replace(RsPsiFactory(project).createExpression("tokio::time::sleep($arguments).await"))
+        }
+    }
+
+    companion object {
+        private const val THREAD_SLEEP = "std::thread::sleep"
+
+        val DEFAULT_BLOCKING_FUNCTIONS: List<String> = listOf(
+            THREAD_SLEEP,
+            "std::fs::*",
+            "std::net::TcpListener::accept",
+            "std::net::TcpListener::incoming",
+            "std::net::TcpStream::connect",
+            "std::net::TcpStream::connect_timeout",
+            "std::net::UdpSocket::recv",
+            "std::net::UdpSocket::recv_from",
+            "std::sync::Mutex::lock",
+            "std::sync::RwLock::read",
+            "std::sync::RwLock::write"
+        )
+
+        private fun matchesBlockingFunction(pattern: String, path: String, isMethod: Boolean): Boolean {
+            if (pattern.isEmpty()) return false
+            if (pattern.endsWith("*")) return path.startsWith(pattern.dropLast(1))
+            if (pattern == path) return true
+            if (!isMethod) return false
+            // `std::sync::Mutex::lock` matches `std::sync::mutex::Mutex::lock`
+            val patternSegments = pattern.split("::")
+            val pathSegments = path.split("::")
+            return patternSegments.size == 3 &&
+                patternSegments.first() == pathSegments.first() &&
+                patternSegments.takeLast(2) == pathSegments.takeLast(2)
+        }
+    }
+}
+
+private val RsFunction.isMethod: Boolean
+    get() = owner.isImplOrTrait
+
+/** E.g. `std::thread::sleep` or `std::sync::mutex::Mutex::lock` */
+private val RsFunction.blockingFunctionPaths: List<String>
+    get() {
+        val name = name ?: return emptyList()
+        val ownerPath = when (val owner = owner) {
+            is RsAbstractableOwner.Impl -> {
+                val selfType = owner.impl.typeReference?.type as? TyAdt
+                listOfNotNull(selfType?.item?.qualifiedName, owner.impl.traitRef?.resolveToTrait()?.qualifiedName)
+            }
+            is RsAbstractableOwner.Trait -> listOfNotNull(owner.trait.qualifiedName)
+            else -> return listOfNotNull(qualifiedName)
+        }
+        return ownerPath.map { "$it::$name" }
+    }
+
+private val RsElement.isInAsyncContext: Boolean
+    get() {
+        for (ancestor in ancestors) {
+            when (ancestor) {
+                is RsFunction -> return ancestor.isAsync
+                is RsLambdaExpr -> return ancestor.isAsync && !ancestor.isArgumentOfBlockingContextFunction
+                is RsBlockExpr -> if (ancestor.isAsync) return !ancestor.isArgumentOfBlockingContextFunction
+                is RsItemElement -> return false
+            }
+        }
+        return false
+    }
+
+private val RsExpr.isArgumentOfBlockingContextFunction: Boolean
+    get() {
+        val argumentList = parent as? RsValueArgumentList ?: return false
+        val name = when (val call = argumentList.parent) {
+            is RsCallExpr -> (call.expr as? RsPathExpr)?.path?.referenceName
+            is RsMethodCall -> call.referenceName
+            else -> null
+        }
+        return name in BLOCKING_CONTEXT_FUNCTIONS
+    }
+
+/** Names of functions whose closure or async block arguments are run outside of the async executor */
+private val BLOCKING_CONTEXT_FUNCTIONS: Set<String> = setOf("spawn_blocking", "block_on", "block_in_place")
+
+private val RsElement.hasTokioDependency: Boolean
+    get() = containingCargoPackage?.dependencies?.any { it.name == "tokio" } == true
@@ -0,0 +1,50 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.LocalQuickFix
+import com.intellij.codeInspection.ProblemDescriptor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElementVisitor
+import org.rust.ide.utils.ReturnTypeConversion
+import org.rust.lang.core.psi.RsFunction
+import org.rust.lang.core.psi.RsPsiFactory
+import org.rust.lang.core.psi.RsVisitor
+import org.rust.lang.core.psi.ext.ancestorStrict
+
+/**
+ * Suggests replacing a `Box<dyn Trait>` return type with `impl Trait`
+ * if all return points of the function box values of the same type.
+ *
+ * @see org.rust.ide.intentions.ConvertBoxDynToImplTraitIntention
+ */
+class RsBoxedTraitObjectReturnInspection : RsLocalInspectionTool() {
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean): PsiElementVisitor? {
+        return object : RsVisitor() {
+            override fun visitFunction(o: RsFunction) {
+                val conversion = ReturnTypeConversion.forFunction(o) as? ReturnTypeConversion.ToImplTrait ?: return
+                if (conversion.findConflict() != null) return
+                holder.registerProblem(
+                    conversion.returnType,
+                    "Boxed trait object can be replaced with `impl Trait`",
+                    ConvertToImplTraitFix()
+                )
+            }
+        }
+    }
+
+    private class ConvertToImplTraitFix : LocalQuickFix {
+        override fun getName(): String = "Convert to `impl Trait`"
+        override fun getFamilyName(): String = name
+
+        override fun applyFix(project: Project, descriptor: ProblemDescriptor) {
+            val function = descriptor.psiElement.ancestorStrict<RsFunction>() ?: return
+            val conversion = ReturnTypeConversion.forFunction(function) as? ReturnTypeConversion.ToImplTrait ?: return
+            conversion.convert(RsPsiFactory(project))
+        }
+    }
+}
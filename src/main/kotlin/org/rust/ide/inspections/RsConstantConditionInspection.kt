@@ -0,0 +1,131 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.codeInspection.ProblemHighlightType
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.types.VariantFact
+import org.rust.lang.core.types.variantFlow
+
+/**
+ * Uses the known variants of `Option` and `Result` variables to find `unwrap` calls which always panic,
+ * `unwrap` calls which can be replaced with `if let`, and `is_some`-like checks which are always `true` or `false`
+ */
+class RsConstantConditionInspection : RsLocalInspectionTool() {
+    override fun getDisplayName(): String = "Constant conditions"
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) =
+        object : RsVisitor() {
+            override fun visitMethodCall(methodCall: RsMethodCall) {
+                val dotExpr = methodCall.parent as? RsDotExpr ?: return
+                val receiver = unwrapParenExprs(dotExpr.expr) as? RsPathExpr ?: return
+                val owner = receiver.ancestorStrict<RsInferenceContextOwner>() ?: return
+                val fact = owner.variantFlow?.factAt(receiver) ?: return
+                val methodName = methodCall.referenceName
+
+                when (methodName) {
+                    "unwrap", "expect" -> when (fact) {
+                        VariantFact.NONE, VariantFact.ERR -> registerPanic(holder, methodCall, fact)
+                        VariantFact.SOME, VariantFact.OK -> {
+                            val fix = ReplaceWithIfLetFix.createIfApplicable(receiver)
+                            holder.registerProblem(
+                                methodCall,
+                                "Unnecessary `$methodName`: `${receiver.text}` is always `${fact.variantName}` here",
+                                ProblemHighlightType.WEAK_WARNING,
+                                *listOfNotNull(fix).toTypedArray()
+                            )
+                        }
+                    }
+                    "unwrap_err", "expect_err" -> if (fact == VariantFact.OK) registerPanic(holder, methodCall, fact)
+                    "is_some", "is_none", "is_ok", "is_err" -> {
+                        val value = methodName.removePrefix("is_") == fact.variantName.toLowerCase()
+                        holder.registerProblem(dotExpr, "Condition `${dotExpr.text}` is always `$value`")
+                    }
+                }
+            }
+        }
+
+    private fun registerPanic(holder: RsProblemsHolder, methodCall: RsMethodCall, fact: VariantFact) {
+        holder.registerProblem(
+            methodCall,
+            "`${methodCall.referenceName}` on `${fact.variantName}` value always panics"
+        )
+    }
+
+    /**
+     * Replaces `if x.is_some() { x.unwrap() }` with `if let Some(x) = x { x }`.
+     * Applicable only if all usages of `x` in the block are `unwrap` or `expect` calls
+     */
+    private class ReplaceWithIfLetFix(ifExpr: RsIfExpr) : LocalQuickFixOnPsiElement(ifExpr) {
+        override fun getFamilyName(): String = text
+        override fun getText(): String = "Replace with `if let`"
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val ifExpr = startElement as? RsIfExpr ?: return
+            val (condition, binding, variantName) = findCheck(ifExpr) ?: return
+            val name = binding.name ?: return
+            val usages = findUsages(ifExpr, binding) ?: return
+
+            val factory = RsPsiFactory(project)
+            for (usage in usages) {
+                usage.parent.replace(factory.createExpression(name))
+            }
+            val ifLet = factory.createExpression("if let $variantName($name) = $name {}") as RsIfExpr
+            val letExpr = ifLet.condition?.expr ?: return
+            condition.replace(letExpr)
+        }
+
+        companion object {
+            fun createIfApplicable(receiver: RsPathExpr): ReplaceWithIfLetFix? {
+                val ifExpr = receiver.ancestors
+                    .takeWhile { it !is RsItemElement && it !is RsLambdaExpr }
+                    .filterIsInstance<RsIfExpr>()
+                    .firstOrNull { it.block?.isAncestorOf(receiver) == true }
+                    ?: return null
+                val (_, binding) = findCheck(ifExpr) ?: return null
+                if (receiver.path.reference?.resolve() != binding) return null
+                if (findUsages(ifExpr, binding) == null) return null
+                return ReplaceWithIfLetFix(ifExpr)
+            }
+
+            /** Matches `x.is_some()` or `x.is_ok()` in the condition of [ifExpr] */
+            private fun findCheck(ifExpr: RsIfExpr): Triple<RsDotExpr, RsPatBinding, String>? {
+                val condition = ifExpr.condition?.expr as? RsDotExpr ?: return null
+                val variantName = when (condition.methodCall?.referenceName) {
+                    "is_some" -> VariantFact.SOME.variantName
+                    "is_ok" -> VariantFact.OK.variantName
+                    else -> return null
+                }
+                val receiver = condition.expr as? RsPathExpr ?: return null
+                val binding = receiver.path.reference?.resolve() as? RsPatBinding ?: return null
+                return Triple(condition, binding, variantName)
+            }
+
+            /** Returns usages of [binding] in the block of [ifExpr] or `null` if some of them aren't `unwrap` calls */
+            private fun findUsages(ifExpr: RsIfExpr, binding: RsPatBinding): List<RsPathExpr>? {
+                val usages = ifExpr.block?.descendantsOfType<RsPathExpr>()
+                    ?.filter { it.path.reference?.resolve() == binding }
+                    ?: return null
+                return usages.takeIf { it.all { usage -> usage.isUnwrapReceiver } }
+            }
+
+            private val RsPathExpr.isUnwrapReceiver: Boolean
+                get() {
+                    val dotExpr = parent as? RsDotExpr ?: return false
+                    return when (dotExpr.methodCall?.referenceName) {
+                        "unwrap" -> true
+                        "expect" -> dotExpr.methodCall?.valueArgumentList?.exprList?.size == 1
+                        else -> false
+                    }
+                }
+        }
+    }
+}
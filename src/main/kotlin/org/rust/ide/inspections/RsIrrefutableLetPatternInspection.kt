@@ -0,0 +1,158 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.LocalQuickFix
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import com.intellij.psi.PsiWhiteSpace
+import com.intellij.psi.codeStyle.CodeStyleManager
+import org.rust.ide.inspections.checkMatch.checkIrrefutable
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+
+/**
+ * Detects `if let` and `while let` expressions (and `match` expressions with a single arm)
+ * whose patterns match all values of the scrutinee type.
+ * Analogue of rustc's `irrefutable_let_patterns` lint.
+ * Quick fix: convert to `let` statement (`loop` with `let` statement for `while let`)
+ */
+class RsIrrefutableLetPatternInspection : RsLintInspection() {
+    override fun getDisplayName(): String = "Irrefutable let pattern"
+
+    override fun getLint(element: PsiElement): RsLint = RsLint.IrrefutableLetPatterns
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) = object : RsVisitor() {
+        override fun visitIfExpr(o: RsIfExpr) {
+            val letExpr = o.condition?.singleLetExpr ?: return
+            // With `else` branch, the fix would leave dead code.
+            // The unreachable `else` is reported by `RsRedundantElseInspection` using the same exhaustiveness check
+            val fix = if (o.elseBranch == null && o.canBeHoisted) ConvertIfLetToLetFix(o) else null
+            inspect(holder, letExpr, "if let", fix)
+        }
+
+        override fun visitWhileExpr(o: RsWhileExpr) {
+            val letExpr = o.condition?.singleLetExpr ?: return
+            inspect(holder, letExpr, "while let", ConvertWhileLetToLoopFix(o))
+        }
+
+        override fun visitMatchExpr(o: RsMatchExpr) {
+            // Irrefutable arms followed by other arms are reported by `RsMatchCheckInspection` as unreachable ones
+            val arm = o.matchBody?.matchArmList?.singleOrNull() ?: return
+            if (arm.matchArmGuard != null || o.expr == null) return
+            val orPats = arm.orPats
+            if (checkIrrefutable(orPats.patList) != true) return
+            val fix = if (orPats.patList.size == 1 && o.canBeHoisted) ConvertMatchToLetFix(o) else null
+            holder.registerProblem(orPats, "Irrefutable `match` arm", *listOfNotNull(fix).toTypedArray())
+        }
+    }
+
+    private fun inspect(holder: RsProblemsHolder, letExpr: RsLetExpr, kind: String, fix: LocalQuickFix?) {
+        val orPats = letExpr.orPats ?: return
+        val pats = orPats.patList
+        if (letExpr.expr == null) return
+        val isIrrefutable = checkIrrefutable(pats)
+            // Slice patterns are not supported by the exhaustiveness checker yet
+            ?: (pats.size == 1 && pats.single().isIrrefutable)
+        if (!isIrrefutable) return
+        // `let A | B = x;` is not allowed
+        val applicableFix = fix?.takeIf { pats.size == 1 }
+        holder.registerProblem(orPats, "Irrefutable `$kind` pattern", *listOfNotNull(applicableFix).toTypedArray())
+    }
+
+    private class ConvertIfLetToLetFix(ifExpr: RsIfExpr) : LocalQuickFixOnPsiElement(ifExpr) {
+        override fun getText(): String = "Convert to `let`"
+        override fun getFamilyName(): String = text
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val ifExpr = startElement as? RsIfExpr ?: return
+            val letExpr = ifExpr.condition?.singleLetExpr ?: return
+            val body = ifExpr.block ?: return
+            hoist(project, ifExpr, letExpr.letStatementText ?: return, body.innerText)
+        }
+    }
+
+    private class ConvertMatchToLetFix(matchExpr: RsMatchExpr) : LocalQuickFixOnPsiElement(matchExpr) {
+        override fun getText(): String = "Convert to `let`"
+        override fun getFamilyName(): String = text
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val matchExpr = startElement as? RsMatchExpr ?: return
+            val arm = matchExpr.matchBody?.matchArmList?.singleOrNull() ?: return
+            val pat = arm.patList.singleOrNull() ?: return
+            val scrutinee = matchExpr.expr ?: return
+            val armExpr = arm.expr
+            val body = if (armExpr is RsBlockExpr) armExpr.block.innerText else armExpr?.text.orEmpty()
+            hoist(project, matchExpr, "let ${pat.text} = ${scrutinee.text};", body)
+        }
+    }
+
+    private class ConvertWhileLetToLoopFix(whileExpr: RsWhileExpr) : LocalQuickFixOnPsiElement(whileExpr) {
+        override fun getText(): String = "Convert to `loop` with `let`"
+        override fun getFamilyName(): String = text
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val whileExpr = startElement as? RsWhileExpr ?: return
+            val letExpr = whileExpr.condition?.singleLetExpr ?: return
+            val letStatement = letExpr.letStatementText ?: return
+            val body = whileExpr.block?.innerText.orEmpty()
+            val label = whileExpr.labelDecl?.text?.let { "$it " }.orEmpty()
+            val loopExpr = RsPsiFactory(project).createExpression("${label}loop {\n$letStatement\n$body\n}")
+            val inserted = whileExpr.replace(loopExpr)
+            CodeStyleManager.getInstance(project).reformat(inserted)
+        }
+    }
+}
+
+/** `true` if the expression is a statement or the tail expression of a block, so its body can be moved to the block */
+private val RsExpr.canBeHoisted: Boolean
+    get() = parent is RsExprStmt || parent is RsBlock
+
+private val RsLetExpr.letStatementText: String?
+    get() {
+        val pat = patList.singleOrNull() ?: return null
+        val expr = expr ?: return null
+        return "let ${pat.text} = ${expr.text};"
+    }
+
+/** The text between the braces of the block */
+private val RsBlock.innerText: String
+    get() {
+        val lbrace = lbrace
+        val rbrace = rbrace ?: return ""
+        return text.substring(lbrace.textRangeInParent.endOffset, rbrace.startOffsetInParent).trim()
+    }
+
+/**
+ * Replaces [expr] (a statement or the tail expression of a block) with [letStatement]
+ * followed by [body] statements
+ */
+private fun hoist(project: Project, expr: RsExpr, letStatement: String, body: String) {
+    val psiFactory = RsPsiFactory(project)
+    val anchor = expr.parent as? RsExprStmt ?: expr
+    val block = anchor.parent as? RsBlock ?: return
+    val newBlock = psiFactory.createBlockExpr("$letStatement\n$body").block
+
+    // The tail expression of the body is not the tail of the outer block anymore
+    val newTailExpr = newBlock.expr
+    val isLastInBlock = block.expr == null && block.stmtList.lastOrNull() == anchor
+    if (anchor is RsExprStmt && newTailExpr != null && !isLastInBlock) {
+        newBlock.addAfter(psiFactory.createSemicolon(), newTailExpr)
+    }
+
+    val rbrace = newBlock.rbrace ?: return
+    val first = newBlock.lbrace.rightSiblings.find { it !is PsiWhiteSpace } ?: return
+    val last = rbrace.leftSiblings.find { it !is PsiWhiteSpace } ?: return
+    val firstInserted = block.addRangeBefore(first, last, anchor)
+    val lastInserted = anchor.prevSibling
+    anchor.delete()
+
+    val file = block.containingFile
+    val range = firstInserted.textRange.union(lastInserted.textRange)
+    CodeStyleManager.getInstance(project).adjustLineIndent(file, range)
+}
@@ -21,6 +21,9 @@ enum class RsLint(
     NonUpperCaseGlobals("non_upper_case_globals", listOf("bad_style")),
     Deprecated("deprecated"),
     UnusedVariables("unused_variables", listOf("unused")),
+    UnusedMustUse("unused_must_use", listOf("unused")),
+    UnusedMut("unused_mut", listOf("unused")),
+    IrrefutableLetPatterns("irrefutable_let_patterns"),
     NeedlessLifetimes("clippy::needless_lifetimes", listOf("clippy::complexity", "clippy::all", "clippy"));
 
     /**
@@ -6,6 +6,7 @@
 package org.rust.ide.inspections
 
 import com.intellij.psi.PsiElement
+import org.rust.ide.inspections.checkMatch.checkIrrefutable
 import org.rust.ide.inspections.fixes.SubstituteTextFix
 import org.rust.lang.core.psi.RsCondition
 import org.rust.lang.core.psi.RsElseBranch
@@ -13,12 +14,15 @@ import org.rust.lang.core.psi.RsIfExpr
 import org.rust.lang.core.psi.RsVisitor
 import org.rust.lang.core.psi.ext.isIrrefutable
 import org.rust.lang.core.psi.ext.leftSiblings
+import org.rust.lang.core.psi.ext.letExprs
+import org.rust.lang.core.psi.ext.patList
 import org.rust.lang.core.psi.ext.rangeWithPrevSpace
+import org.rust.lang.core.psi.ext.singleLetExpr
 import org.rust.lang.core.types.consts.asBool
 import org.rust.lang.utils.evaluation.evaluate
 
 /**
- * Detects redundant `else` statements preceded by an irrefutable pattern.
+ * Detects redundant `else` statements preceded by an irrefutable pattern or a constant `true` condition.
  * Quick fix: Remove `else`
  */
 class RsRedundantElseInspection : RsLocalInspectionTool() {
@@ -58,8 +62,10 @@ class RsRedundantElseInspection : RsLocalInspectionTool() {
             }
         private val RsCondition.isRedundant: Boolean
             get() {
-                val patList = orPats?.patList
-                return patList?.all { pat -> pat?.isIrrefutable ?: false } ?: (this.expr.evaluate().asBool() ?: false)
+                if (letExprs.isEmpty()) return expr.evaluate().asBool() ?: false
+                val patList = singleLetExpr?.patList ?: return false
+                // The exhaustiveness checker also handles or-patterns like `Some(_) | None`
+                return checkIrrefutable(patList) ?: patList.all { pat -> pat.isIrrefutable }
             }
     }
 }
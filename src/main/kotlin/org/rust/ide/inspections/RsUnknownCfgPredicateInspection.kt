@@ -0,0 +1,56 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.lang.core.psi.RsMetaItem
+import org.rust.lang.core.psi.RsVisitor
+import org.rust.lang.core.psi.ext.containingCargoPackage
+import org.rust.lang.core.psi.ext.hasEq
+import org.rust.lang.core.psi.ext.isCfgPredicate
+import org.rust.lang.core.psi.ext.name
+import org.rust.lang.utils.evaluation.KnownCfgOptions
+
+/**
+ * Reports unknown options and operators in `#[cfg]` and `#[cfg_attr]` predicates, e.g. `feture` in
+ * `#[cfg(feture = "foo")]`. Options set for the package (e.g. by a build script) are considered known.
+ * Also reports malformed arguments of operators, e.g. `#[cfg(not(unix, windows))]`.
+ */
+class RsUnknownCfgPredicateInspection : RsLocalInspectionTool() {
+    override fun getDisplayName() = "Unknown cfg predicate"
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) =
+        object : RsVisitor() {
+            override fun visitMetaItem(o: RsMetaItem) {
+                if (!o.isCfgPredicate) return
+                val path = o.path ?: return
+                val name = o.name ?: return
+                val pkg = o.containingCargoPackage ?: return
+                val options = arrayOf(pkg.cfgOptions, pkg.workspace.cfgOptions)
+
+                val isOperator = o.metaItemArgs != null
+                val isKnown = when {
+                    isOperator -> name in KnownCfgOptions.OPERATORS
+                    o.hasEq -> KnownCfgOptions.isKnownNameValueKey(name, *options)
+                    else -> KnownCfgOptions.isKnownName(name, *options)
+                }
+                if (!isKnown) {
+                    holder.registerProblem(path, "Unknown cfg ${if (isOperator) "operator" else "option"} `$name`")
+                } else if (isOperator) {
+                    checkOperatorArguments(holder, o, name)
+                }
+            }
+        }
+
+    private fun checkOperatorArguments(holder: RsProblemsHolder, operator: RsMetaItem, name: String) {
+        val args = operator.metaItemArgs ?: return
+        for (literal in args.litExprList) {
+            holder.registerProblem(literal, "Expected a cfg predicate, found a literal")
+        }
+        if (name == "not" && args.metaItemList.size + args.litExprList.size != 1) {
+            holder.registerProblem(operator.path ?: return, "`not` takes exactly one cfg predicate")
+        }
+    }
+}
@@ -0,0 +1,68 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.ProblemHighlightType
+import com.intellij.psi.PsiElement
+import com.intellij.psi.search.LocalSearchScope
+import com.intellij.psi.search.searches.ReferencesSearch
+import org.rust.ide.inspections.fixes.RemoveMutableFix
+import org.rust.ide.utils.isEnabledByCfg
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.types.borrowCheckResult
+
+/**
+ * Detects `mut` bindings that are never mutated. Analogue of rustc's `unused_mut` lint.
+ * Mutations are collected by the borrow checker, see [org.rust.lang.core.types.borrowck.CheckLoanContext]
+ */
+class RsUnnecessaryMutInspection : RsLintInspection() {
+    override fun getDisplayName(): String = "Unnecessary mut"
+
+    override fun getLint(element: PsiElement): RsLint = RsLint.UnusedMut
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) =
+        object : RsVisitor() {
+            override fun visitPatBinding(o: RsPatBinding) {
+                if (!o.mutability.isMut) return
+                val owner = o.ancestorStrict<RsInferenceContextOwner>() ?: return
+                val unusedMutBindings = owner.borrowCheckResult?.unusedMutBindings ?: return
+                if (o !in unusedMutBindings) return
+
+                val name = o.identifier.text
+                if (isMutatedInCfgDisabledCode(o, owner)) {
+                    holder.registerProblem(
+                        o,
+                        "Variable `$name` is mutated only in code disabled by `#[cfg]`. " +
+                            "Making it immutable breaks other configurations",
+                        ProblemHighlightType.WEAK_WARNING
+                    )
+                } else {
+                    holder.registerProblem(o, "Variable `$name` does not need to be mutable", RemoveMutableFix())
+                }
+            }
+        }
+
+    /** Code disabled by `#[cfg]` is not analyzed, so its usages are checked only syntactically */
+    private fun isMutatedInCfgDisabledCode(binding: RsPatBinding, owner: RsInferenceContextOwner): Boolean =
+        ReferencesSearch.search(binding, LocalSearchScope(owner))
+            .asSequence()
+            .map { it.element }
+            .any { !it.isEnabledByCfg && isMutatingOccurrence(it.parent) }
+
+    private fun isMutatingOccurrence(occurrence: PsiElement): Boolean {
+        when (val parent = occurrence.parent) {
+            is RsUnaryExpr -> return parent.mut != null
+            is RsBinaryExpr -> return parent.left == occurrence && parent.isAssignBinaryExpr
+            is RsDotExpr -> {
+                val methodCall = parent.methodCall ?: return isMutatingOccurrence(parent)
+                val function = methodCall.reference.resolve() as? RsFunction ?: return true
+                return function.selfParameter?.mutability?.isMut == true
+            }
+        }
+        return false
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.ProblemHighlightType
+import com.intellij.psi.impl.source.resolve.reference.impl.providers.FileReference
+import org.rust.ide.inspections.fixes.CreateIncludedFileFix
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsMacroCall
+import org.rust.lang.core.psi.RsVisitor
+import org.rust.lang.core.psi.ext.findIncludedFile
+import org.rust.lang.core.psi.ext.includedFilePath
+
+/**
+ * Reports `include!`, `include_str!` and `include_bytes!` macro calls with a file that doesn't exist.
+ * If the path is relative, provides a quick fix to create the file.
+ */
+class RsUnresolvedIncludeFileInspection : RsLocalInspectionTool() {
+    override fun getDisplayName() = "Unresolved include file"
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) =
+        object : RsVisitor() {
+            override fun visitMacroCall(call: RsMacroCall) {
+                val expr = call.includeMacroArgument?.expr ?: return
+                // The path may contain unknown `env!` variables
+                val path = call.includedFilePath ?: return
+                if (call.findIncludedFile() != null) return
+
+                val fixes = listOfNotNull(CreateIncludedFileFix.createIfApplicable(call))
+                if (expr is RsLitExpr) {
+                    val reference = expr.references
+                        .filterIsInstance<FileReference>()
+                        .firstOrNull { it.multiResolve(false).isEmpty() }
+                    if (reference != null) {
+                        holder.registerProblem(
+                            expr,
+                            "Unresolved file `${reference.canonicalText}`",
+                            ProblemHighlightType.LIKE_UNKNOWN_SYMBOL,
+                            reference.rangeInElement,
+                            *fixes.toTypedArray()
+                        )
+                        return
+                    }
+                }
+                holder.registerProblem(
+                    expr,
+                    "File not found: `$path`",
+                    ProblemHighlightType.LIKE_UNKNOWN_SYMBOL,
+                    *fixes.toTypedArray()
+                )
+            }
+        }
+}
@@ -9,17 +9,17 @@ import com.intellij.codeInspection.LocalQuickFix
 import com.intellij.codeInspection.ProblemHighlightType
 import com.intellij.codeInspection.ui.MultipleCheckboxOptionsPanel
 import com.intellij.psi.PsiElementVisitor
+import org.rust.ide.inspections.fixes.AddTypeParameterBoundFix
 import org.rust.ide.inspections.import.AutoImportFix
 import org.rust.ide.inspections.import.AutoImportHintFix
 import org.rust.ide.settings.RsCodeInsightSettings
+import org.rust.lang.core.RsPsiPattern
 import org.rust.lang.core.psi.RsMetaItem
 import org.rust.lang.core.psi.RsMethodCall
 import org.rust.lang.core.psi.RsPath
 import org.rust.lang.core.psi.RsVisitor
-import org.rust.lang.core.psi.ext.RsReferenceElement
-import org.rust.lang.core.psi.ext.ancestorStrict
-import org.rust.lang.core.psi.ext.isUnresolved
-import org.rust.lang.core.psi.ext.qualifier
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.ref.RsAttributeProcMacroReferenceImpl
 import javax.swing.JComponent
 
 class RsUnresolvedReferenceInspection : RsLocalInspectionTool() {
@@ -34,8 +34,15 @@ class RsUnresolvedReferenceInspection : RsLocalInspectionTool() {
             override fun visitPath(path: RsPath) {
                 if (path.reference == null) return
 
-                // Don't show unresolved reference error in attributes for now
-                if (path.ancestorStrict<RsMetaItem>() != null) return
+                // Don't show unresolved reference error in attributes for now,
+                // except for names of unknown outer attributes, e.g. `foo` in `#[foo]`.
+                // Such names are resolved only to attribute proc macros and helper attributes of custom derives
+                if (path.ancestorStrict<RsMetaItem>() != null) {
+                    if (path.isUnknownAttribute) {
+                        holder.registerProblem(path, null)
+                    }
+                    return
+                }
 
                 val isPathUnresolved = path.isUnresolved
                 val qualifier = path.qualifier
@@ -56,6 +63,10 @@ class RsUnresolvedReferenceInspection : RsLocalInspectionTool() {
                     else -> null
                 }
 
+                // If the file of `include!` is not found, we don't know which items are declared in the module,
+                // so we don't report a cascade of errors for the missing items
+                if (isPathUnresolved && path.mayBeDeclaredInUnresolvedInclude()) return
+
                 if (isPathUnresolved || context != null) {
                     holder.registerProblem(path, context)
                 }
@@ -64,19 +75,22 @@ class RsUnresolvedReferenceInspection : RsLocalInspectionTool() {
             override fun visitMethodCall(methodCall: RsMethodCall) {
                 val isMethodResolved = methodCall.reference.multiResolve().isNotEmpty()
                 val context = AutoImportFix.findApplicableContext(holder.project, methodCall)
+                // The method may be unresolved because the receiver is a type parameter without a required bound
+                val boundFixes = if (isMethodResolved) emptyList() else AddTypeParameterBoundFix.createFixes(methodCall)
 
                 if (!isMethodResolved || context != null) {
-                    holder.registerProblem(methodCall, context)
+                    holder.registerProblem(methodCall, context, boundFixes)
                 }
             }
         }
 
     private fun RsProblemsHolder.registerProblem(
         element: RsReferenceElement,
-        context: AutoImportFix.Context?
+        context: AutoImportFix.Context?,
+        additionalFixes: List<LocalQuickFix> = emptyList()
     ) {
         val candidates = context?.candidates
-        if (candidates.isNullOrEmpty() && ignoreWithoutQuickFix) return
+        if (candidates.isNullOrEmpty() && additionalFixes.isEmpty() && ignoreWithoutQuickFix) return
 
         val referenceName = element.referenceName
         val description = if (referenceName == null) "Unresolved reference" else "Unresolved reference: `$referenceName`"
@@ -94,10 +108,33 @@ class RsUnresolvedReferenceInspection : RsLocalInspectionTool() {
             highlightedElement,
             description,
             ProblemHighlightType.LIKE_UNKNOWN_SYMBOL,
-            *listOfNotNull(fix).toTypedArray()
+            *(listOfNotNull(fix) + additionalFixes).toTypedArray()
         )
     }
 
+    /**
+     * Helper attributes can be declared by attribute macros (e.g. `#[pin]` of `#[pin_project]`) and by
+     * custom derives which we can't resolve, so an attribute is reported only if there are no other non-builtin
+     * attributes on the item and its ancestors, and all derives of the item are resolved
+     */
+    private val RsPath.isUnknownAttribute: Boolean
+        get() {
+            if (reference !is RsAttributeProcMacroReferenceImpl || qualifier != null || !isUnresolved) return false
+            val metaItem = parent as? RsMetaItem ?: return false
+            val owner = metaItem.parent?.parent as? RsOuterAttributeOwner ?: return false
+            val hasAttributeMacros = owner.ancestors
+                .filterIsInstance<RsOuterAttributeOwner>()
+                .flatMap { it.outerAttrList.asSequence() }
+                .any { it.metaItem != metaItem && RsPsiPattern.nonStdOuterAttributeMetaItem.accepts(it.metaItem) }
+            return !hasAttributeMacros && owner.derivingItem?.hasUnresolvedDerives != true
+        }
+
+    private fun RsPath.mayBeDeclaredInUnresolvedInclude(): Boolean {
+        val qualifier = qualifier
+        val mod = if (qualifier == null) containingMod else qualifier.reference?.resolve() as? RsMod
+        return mod?.hasUnresolvedIncludeMacro == true
+    }
+
     override fun createOptionsPanel(): JComponent = MultipleCheckboxOptionsPanel(this).apply {
         addCheckbox("Ignore unresolved references without quick fix", "ignoreWithoutQuickFix")
     }
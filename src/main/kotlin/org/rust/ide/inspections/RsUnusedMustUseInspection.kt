@@ -0,0 +1,86 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import com.intellij.codeInspection.LocalQuickFix
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.lang.core.macros.isExpandedFromMacro
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.knownItems
+import org.rust.lang.core.types.ty.TyAdt
+import org.rust.lang.core.types.type
+
+/**
+ * Checks for unused values of `#[must_use]` types and unused results of `#[must_use]` functions.
+ * Analogue of rustc's `unused_must_use` lint.
+ * Quick fixes: add `let _ =` or add `.unwrap()` (the latter only for `Result` and `Option`).
+ */
+class RsUnusedMustUseInspection : RsLintInspection() {
+    override fun getDisplayName() = "Unused `#[must_use]` value"
+
+    override fun getLint(element: PsiElement): RsLint = RsLint.UnusedMustUse
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean) = object : RsVisitor() {
+        override fun visitExprStmt(o: RsExprStmt) {
+            super.visitExprStmt(o)
+            if (o.isExpandedFromMacro) return
+            val expr = o.expr
+            val description = inspectAndGetDescription(expr) ?: return
+            val fixes = mutableListOf<LocalQuickFix>(AddLetUnderscoreFix(o))
+            val item = (expr.type as? TyAdt)?.item
+            if (item != null && (item == o.knownItems.Result || item == o.knownItems.Option)) {
+                fixes += AddUnwrapFix(o)
+            }
+            holder.registerProblem(expr, description, *fixes.toTypedArray())
+        }
+    }
+
+    private fun inspectAndGetDescription(expr: RsExpr): String? {
+        val type = expr.type
+        if (type is TyAdt && type.item.isMustUse) {
+            return "Unused `${type.item.name}` that must be used"
+        }
+        val function = expr.calledFunction ?: return null
+        if (function.isMustUse || (function.superItem as? RsFunction)?.isMustUse == true) {
+            return "Unused return value of `${function.name}` that must be used"
+        }
+        return null
+    }
+
+    private class AddLetUnderscoreFix(stmt: RsExprStmt) : LocalQuickFixOnPsiElement(stmt) {
+        override fun getText(): String = "Add `let _ =`"
+        override fun getFamilyName(): String = text
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val stmt = startElement as? RsExprStmt ?: return
+            stmt.replace(RsPsiFactory(project).createLetDeclaration("_", stmt.expr))
+        }
+    }
+
+    private class AddUnwrapFix(stmt: RsExprStmt) : LocalQuickFixOnPsiElement(stmt) {
+        override fun getText(): String = "Add `.unwrap()`"
+        override fun getFamilyName(): String = text
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val expr = (startElement as? RsExprStmt)?.expr ?: return
+            expr.replace(RsPsiFactory(project).createNoArgsMethodCall(expr, "unwrap"))
+        }
+    }
+}
+
+private val RsDocAndAttributeOwner.isMustUse: Boolean
+    get() = queryAttributes.hasAttribute("must_use")
+
+private val RsExpr.calledFunction: RsFunction?
+    get() = when (this) {
+        is RsCallExpr -> (expr as? RsPathExpr)?.path?.reference?.resolve() as? RsFunction
+        is RsDotExpr -> methodCall?.reference?.resolve() as? RsFunction
+        else -> null
+    }
@@ -8,9 +8,7 @@ package org.rust.ide.inspections.checkMatch
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.types.consts.CtValue
-import org.rust.lang.core.types.ty.Ty
-import org.rust.lang.core.types.ty.TyAdt
-import org.rust.lang.core.types.ty.TyUnknown
+import org.rust.lang.core.types.ty.*
 import org.rust.lang.core.types.type
 import org.rust.lang.utils.evaluation.ConstExpr.Value
 import org.rust.lang.utils.evaluation.evaluate
@@ -52,7 +50,6 @@ private val RsExpr.value: Value<*>?
 private val RsPat.kind: PatternKind
     get() = when (this) {
         is RsPatIdent -> {
-            if (pat != null) throw TODO("Support `x @ pat`")
             when (val resolved = patBinding.reference.resolve()) {
                 is RsEnumVariant -> PatternKind.Variant(resolved.parentEnum, resolved, emptyList())
                 is RsConstant -> {
@@ -66,7 +63,15 @@ private val RsPat.kind: PatternKind
 
         is RsPatWild -> PatternKind.Wild
 
-        is RsPatTup -> PatternKind.Leaf(patList.map { it.lower })
+        is RsPatTup -> {
+            val pat = patList.singleOrNull()
+            if (pat != null && type !is TyTuple) {
+                // Parenthesized pattern like `(1 | 2)`
+                pat.lower.kind
+            } else {
+                PatternKind.Leaf(patList.map { it.lower })
+            }
+        }
 
         is RsPatStruct -> {
             val item = path.reference?.resolve() as? RsFieldsOwner
@@ -115,17 +120,48 @@ private val RsPat.kind: PatternKind
         }
 
         is RsPatRange -> {
-            val lc = patConstList.getOrNull(0)?.expr?.value ?: throw CheckMatchException("Incomplete range")
-            val rc = patConstList.getOrNull(1)?.expr?.value ?: throw CheckMatchException("Incomplete range")
+            val lc = start.expr.value ?: throw CheckMatchException("Incomplete range")
+            val end = end
+            val rc = if (end != null) {
+                end.expr.value ?: throw CheckMatchException("Incomplete range")
+            } else {
+                if (isInclusive) throw CheckMatchException("Inclusive range with no end")
+                null
+            }
             PatternKind.Range(lc, rc, isInclusive)
         }
 
         is RsPatRef -> PatternKind.Deref(pat.lower)
+
+        is RsPatSlice -> {
+            val restIndex = patList.indexOfFirst { it.isRest }
+            if (patList.count { it.isRest } > 1) throw CheckMatchException("Multiple `..` in slice pattern")
+
+            val prefix = if (restIndex == -1) patList else patList.subList(0, restIndex)
+            val suffix = if (restIndex == -1) emptyList() else patList.subList(restIndex + 1, patList.size)
+            val slice = (patList.getOrNull(restIndex) as? RsPatIdent)?.patBinding
+                ?.let { Pattern(it.type, PatternKind.Binding(it.type, it.name.orEmpty())) }
+                ?: Pattern.Wild.takeIf { restIndex != -1 }
+
+            when (type) {
+                is TyArray -> PatternKind.Array(prefix.map { it.lower }, slice, suffix.map { it.lower })
+                is TySlice -> PatternKind.Slice(prefix.map { it.lower }, slice, suffix.map { it.lower })
+                else -> throw CheckMatchException("Unexpected type of slice pattern")
+            }
+        }
+
+        is RsPatOr -> PatternKind.Or(patList.flatMap {
+            val pattern = it.lower
+            (pattern.kind as? PatternKind.Or)?.patterns ?: listOf(pattern)
+        })
+
         is RsPatMacro -> TODO()
-        is RsPatSlice -> TODO()
         else -> TODO()
     }
 
+private val RsPat.isRest: Boolean
+    get() = this is RsPatRest || this is RsPatIdent && pat is RsPatRest
+
 private fun createPatternForField(patField: RsPatField?, field: RsFieldDecl): Pattern =
     if (patField != null) {
         patField.patFieldFull?.pat?.let { return it.lower }
@@ -144,5 +180,9 @@ private fun getLeafOrVariant(item: RsElement, subPatterns: List<Pattern>): Patte
         else -> throw CheckMatchException("Impossible case $item")
     }
 
-private val RsPat.lower: Pattern
-    get() = Pattern(type, kind)
+val RsPat.lower: Pattern
+    get() {
+        // Bindings don't affect matching, so `x @ pat` is checked as `pat`
+        val subPattern = (this as? RsPatIdent)?.pat
+        return subPattern?.lower ?: Pattern(type, kind)
+    }
@@ -14,6 +14,7 @@ import org.rust.lang.core.psi.ext.size
 import org.rust.lang.core.psi.ext.variants
 import org.rust.lang.core.types.ty.*
 import org.rust.lang.utils.evaluation.ConstExpr.Value
+import java.math.BigInteger
 
 sealed class Constructor {
 
@@ -25,7 +26,7 @@ sealed class Constructor {
     /** Enum variants */
     data class Variant(val variant: RsEnumVariant) : Constructor()
 
-    /** Literal values */
+    /** Literal values that can't be represented as [IntRange], e.g. booleans, floats and strings */
     data class ConstantValue(val value: Value<*>) : Constructor() {
         override fun coveredByRange(from: Value<*>, to: Value<*>, included: Boolean): Boolean =
             if (included) {
@@ -35,7 +36,7 @@ sealed class Constructor {
             }
     }
 
-    /** Ranges of literal values (`2..=5` and `2..5`) */
+    /** Ranges of float literal values (`2.0..=5.0` and `2.0..5.0`) */
     data class ConstantRange(val start: Value<*>, val end: Value<*>, val includeEnd: Boolean = false) :
         Constructor() {
         override fun coveredByRange(from: Value<*>, to: Value<*>, included: Boolean): Boolean =
@@ -46,14 +47,91 @@ sealed class Constructor {
             }
     }
 
-    /** Array patterns of length n */
+    /**
+     * Inclusive ranges of integer or char values, e.g. `2..=5`, `2..6` and `2..` (if [end] is the max value of the type).
+     * Single values like `2` or `'a'` are represented by ranges of one element. Chars are represented by their code points.
+     * [BigInteger] is used because the values of 64- and 128-bit types don't fit into [Long]
+     */
+    data class IntRange(val start: BigInteger, val end: BigInteger) : Constructor() {
+        operator fun contains(other: IntRange): Boolean = start <= other.start && other.end <= end
+
+        fun intersects(other: IntRange): Boolean = start <= other.end && other.start <= end
+
+        /**
+         * Splits the range into subranges, each of which is either contained in or disjoint from every range of [others].
+         * This way, a range pattern can be checked against partially overlapping ranges (see `split_grouped_constructors` in rustc)
+         */
+        fun splitBy(others: List<IntRange>): List<IntRange> {
+            val borders = sortedSetOf(start)
+            for (other in others) {
+                if (!intersects(other)) continue
+                if (other.start > start) borders.add(other.start)
+                if (other.end < end) borders.add(other.end + BigInteger.ONE)
+            }
+            val starts = borders.toList()
+            return starts.mapIndexed { index, subrangeStart ->
+                IntRange(subrangeStart, starts.getOrNull(index + 1)?.minus(BigInteger.ONE) ?: end)
+            }
+        }
+
+        /** Returns the maximal subranges which are not covered by [others] */
+        fun subtract(others: List<IntRange>): List<IntRange> =
+            others.fold(listOf(this)) { remaining, other ->
+                remaining.flatMap { range ->
+                    if (!range.intersects(other)) {
+                        listOf(range)
+                    } else {
+                        listOfNotNull(
+                            IntRange(range.start, other.start - BigInteger.ONE).takeIf { other.start > range.start },
+                            IntRange(other.end + BigInteger.ONE, range.end).takeIf { other.end < range.end }
+                        )
+                    }
+                }
+            }
+    }
+
+    /** Array and slice patterns of length [size], e.g. `[a, b]` */
     data class Slice(val size: Int) : Constructor()
 
+    /**
+     * Slice patterns of length at least `prefix + suffix`, e.g. `[first, .., last]`.
+     * It has `prefix + suffix` subpatterns: elements which are not covered by `..` are wildcards
+     */
+    data class VarLenSlice(val prefix: Int, val suffix: Int) : Constructor() {
+        val minLength: Int get() = prefix + suffix
+
+        /**
+         * Splits the constructor into fixed-length slices that can be distinguished by the patterns of [others]
+         * and the longer slices that can't be, e.g. `[..]` becomes `[]`, `[_]` and `[_, _, ..]` for `[]`, `[x]`, `[x, .., y]`
+         * (see `split_grouped_constructors` in rustc)
+         */
+        fun splitBy(others: List<Constructor>): List<Constructor> {
+            var maxPrefix = prefix
+            var maxSuffix = suffix
+            var maxFixedLength = -1
+            for (other in others) {
+                when (other) {
+                    is Slice -> maxFixedLength = maxOf(maxFixedLength, other.size)
+                    is VarLenSlice -> {
+                        maxPrefix = maxOf(maxPrefix, other.prefix)
+                        maxSuffix = maxOf(maxSuffix, other.suffix)
+                    }
+                }
+            }
+            // All fixed-length patterns must be shorter than the variable-length constructor
+            if (maxFixedLength + 1 >= maxPrefix + maxSuffix) {
+                maxPrefix = maxFixedLength + 1 - maxSuffix
+            }
+            return (minLength until maxPrefix + maxSuffix).map { Slice(it) } + VarLenSlice(maxPrefix, maxSuffix)
+        }
+    }
+
     fun arity(type: Ty): Int = when (type) {
         is TyTuple -> type.types.size
 
         is TySlice, is TyArray -> when (this) {
             is Slice -> size
+            is VarLenSlice -> minLength
             is ConstantValue -> 0
             else -> throw CheckMatchException("Incompatible constructor")
         }
@@ -71,11 +149,29 @@ sealed class Constructor {
 
     open fun coveredByRange(from: Value<*>, to: Value<*>, included: Boolean): Boolean = false
 
+    /** Checks if all values of the constructor are matched by the [other] constructor */
+    fun isCoveredBy(other: Constructor): Boolean = when {
+        this is IntRange && other is IntRange -> this in other
+        this is Slice && other is VarLenSlice -> size >= other.minLength
+        this is VarLenSlice && other is VarLenSlice -> minLength >= other.minLength
+        else -> this == other
+    }
+
+    /** @see IntRange.splitBy */
+    fun split(others: List<Constructor>): List<Constructor> = when (this) {
+        is IntRange -> splitBy(others.filterIsInstance<IntRange>())
+        is VarLenSlice -> splitBy(others)
+        else -> listOf(this)
+    }
+
     fun subTypes(type: Ty): List<Ty> = when (type) {
         is TyTuple -> type.types
 
         is TySlice, is TyArray -> when (this) {
-            is Slice -> (0 until this.size).map { type }
+            is Slice, is VarLenSlice -> {
+                val elementType = (type as? TySlice)?.elementType ?: (type as TyArray).base
+                List(arity(type)) { elementType }
+            }
             is ConstantValue -> emptyList()
             else -> throw CheckMatchException("Incompatible constructor")
         }
@@ -97,17 +193,65 @@ sealed class Constructor {
             when {
                 ty is TyBool -> listOf(true, false).map { ConstantValue(Value.Bool(it)) }
 
+                ty is TyInteger -> listOf(IntRange(ty.minValue, ty.maxValue))
+
+                ty is TyChar -> CHAR_RANGES
+
                 ty is TyAdt && ty.item is RsEnumItem -> ty.item.variants.map { Variant(it) }
 
-                // TODO: TyInteger, TyChar (see `all_constructors` at `https://github.com/rust-lang/rust/blob/master/src/librustc_mir/hair/pattern/_match.rs`)
-                ty is TyArray && ty.size != null -> TODO()
-                ty is TyArray || ty is TySlice -> TODO()
+                ty is TyArray -> {
+                    val size = ty.size ?: throw CheckMatchException("Unknown array size")
+                    listOf(Slice(size.toInt()))
+                }
+
+                ty is TySlice -> listOf(VarLenSlice(0, 0))
 
                 else -> listOf(Single)
             }
+
+        /** Valid char values excluding surrogates */
+        private val CHAR_RANGES: List<IntRange> = listOf(
+            IntRange(BigInteger.ZERO, 0xD7FF.toBigInteger()),
+            IntRange(0xE000.toBigInteger(), MAX_CHAR)
+        )
     }
 }
 
+val MAX_CHAR: BigInteger = 0x10FFFF.toBigInteger()
+
+/**
+ * The min value of the integer type.
+ * `usize` and `isize` are assumed to be 64-bit
+ */
+val TyInteger.minValue: BigInteger
+    get() = when (this) {
+        TyInteger.U8, TyInteger.U16, TyInteger.U32, TyInteger.U64, TyInteger.U128, TyInteger.USize -> BigInteger.ZERO
+        TyInteger.I8 -> signedMinValue(8)
+        TyInteger.I16 -> signedMinValue(16)
+        TyInteger.I32 -> signedMinValue(32)
+        TyInteger.I64, TyInteger.ISize -> signedMinValue(64)
+        TyInteger.I128 -> signedMinValue(128)
+    }
+
+/** @see minValue */
+val TyInteger.maxValue: BigInteger
+    get() = when (this) {
+        TyInteger.U8 -> unsignedMaxValue(8)
+        TyInteger.U16 -> unsignedMaxValue(16)
+        TyInteger.U32 -> unsignedMaxValue(32)
+        TyInteger.U64, TyInteger.USize -> unsignedMaxValue(64)
+        TyInteger.U128 -> unsignedMaxValue(128)
+        TyInteger.I8 -> signedMaxValue(8)
+        TyInteger.I16 -> signedMaxValue(16)
+        TyInteger.I32 -> signedMaxValue(32)
+        TyInteger.I64, TyInteger.ISize -> signedMaxValue(64)
+        TyInteger.I128 -> signedMaxValue(128)
+    }
+
+private fun signedMinValue(bits: Int): BigInteger = BigInteger.ONE.shiftLeft(bits - 1).negate()
+private fun signedMaxValue(bits: Int): BigInteger = BigInteger.ONE.shiftLeft(bits - 1) - BigInteger.ONE
+private fun unsignedMaxValue(bits: Int): BigInteger = BigInteger.ONE.shiftLeft(bits) - BigInteger.ONE
+
 private operator fun Value<*>.compareTo(other: Value<*>): Int {
     return when {
         this is Value.Bool && other is Value.Bool -> value.compareTo(other.value)
@@ -12,10 +12,9 @@ import org.rust.lang.core.psi.ext.RsFieldsOwner
 import org.rust.lang.core.psi.ext.findInScope
 import org.rust.lang.core.psi.ext.parentEnum
 import org.rust.lang.core.resolve.VALUES
-import org.rust.lang.core.types.ty.Ty
-import org.rust.lang.core.types.ty.TyAdt
-import org.rust.lang.core.types.ty.TyTuple
-import org.rust.lang.core.types.ty.TyUnknown
+import org.rust.lang.core.types.ty.*
+import org.rust.lang.utils.evaluation.ConstExpr.Value
+import java.math.BigInteger
 
 data class Pattern(val ty: Ty, val kind: PatternKind) {
     fun text(ctx: RsElement?): String =
@@ -54,15 +53,32 @@ data class Pattern(val ty: Ty, val kind: PatternKind) {
                 }
             }
 
-            is PatternKind.Range -> "${kind.lc}${if (kind.isInclusive) ".." else "..="}${kind.rc}"
+            is PatternKind.Range -> {
+                val end = when {
+                    kind.rc != null -> kind.rc.text(ty)
+                    // The max value that doesn't fit into [Value.Integer], see `rangeKind`
+                    kind.isInclusive && ty is TyInteger -> "${ty.name}::MAX"
+                    else -> ""
+                }
+                "${kind.lc.text(ty)}${if (kind.isInclusive) "..=" else ".."}$end"
+            }
 
             is PatternKind.Deref -> "&${kind.subPattern.text(ctx)}"
 
-            is PatternKind.Const -> kind.value.toString()
+            is PatternKind.Const -> kind.value.text(ty)
 
-            is PatternKind.Slice -> TODO()
+            is PatternKind.Slice, is PatternKind.Array -> {
+                val sliceField = kind as PatternKind.SliceField
+                val slice = when (val slicePattern = sliceField.slice?.kind) {
+                    null -> emptyList()
+                    is PatternKind.Binding -> listOf("${slicePattern.name} @ ..")
+                    else -> listOf("..")
+                }
+                val elements = sliceField.prefix.map { it.text(ctx) } + slice + sliceField.suffix.map { it.text(ctx) }
+                elements.joinToString(", ", "[", "]")
+            }
 
-            is PatternKind.Array -> TODO()
+            is PatternKind.Or -> kind.patterns.joinToString(" | ") { it.text(ctx) }
         }
 
     val constructors: List<Constructor>?
@@ -70,17 +86,77 @@ data class Pattern(val ty: Ty, val kind: PatternKind) {
             PatternKind.Wild, is PatternKind.Binding -> null
             is PatternKind.Variant -> listOf(Constructor.Variant(kind.variant))
             is PatternKind.Leaf, is PatternKind.Deref -> listOf(Constructor.Single)
-            is PatternKind.Const -> listOf(Constructor.ConstantValue(kind.value))
-            is PatternKind.Range -> listOf(Constructor.ConstantRange(kind.lc, kind.rc, kind.isInclusive))
-            is PatternKind.Slice -> TODO()
-            is PatternKind.Array -> TODO()
+            is PatternKind.Const -> {
+                val code = kind.value.integralCode
+                listOf(if (code != null) Constructor.IntRange(code, code) else Constructor.ConstantValue(kind.value))
+            }
+            is PatternKind.Range -> listOf(rangeConstructor(kind))
+            is PatternKind.Slice -> {
+                val constructor = if (kind.slice == null) {
+                    Constructor.Slice(kind.prefix.size)
+                } else {
+                    Constructor.VarLenSlice(kind.prefix.size, kind.suffix.size)
+                }
+                listOf(constructor)
+            }
+            is PatternKind.Array -> {
+                val size = (ty as? TyArray)?.size ?: throw CheckMatchException("Unknown array size")
+                listOf(Constructor.Slice(size.toInt()))
+            }
+            is PatternKind.Or -> {
+                val alternatives = kind.patterns.map { it.constructors }
+                if (alternatives.any { it == null }) null else alternatives.flatMap { it.orEmpty() }
+            }
         }
 
+    private fun rangeConstructor(range: PatternKind.Range): Constructor {
+        val start = range.lc.integralCode
+        if (start == null) {
+            val end = range.rc ?: throw CheckMatchException("Half-open ranges are supported only for integers and chars")
+            return Constructor.ConstantRange(range.lc, end, range.isInclusive)
+        }
+        val rc = range.rc
+        val end = when {
+            rc == null -> when (ty) {
+                is TyInteger -> ty.maxValue
+                is TyChar -> MAX_CHAR
+                else -> throw CheckMatchException("Unknown range type")
+            }
+            range.isInclusive -> rc.integralCode
+            else -> rc.integralCode?.minus(BigInteger.ONE)
+        } ?: throw CheckMatchException("Incompatible range bounds")
+        if (start > end) throw CheckMatchException("Empty range")
+        return Constructor.IntRange(start, end)
+    }
+
     companion object {
         val Wild: Pattern get() = Pattern(TyUnknown, PatternKind.Wild)
     }
 }
 
+/** The value of an integer or the code point of a char */
+private val Value<*>.integralCode: BigInteger?
+    get() = when (this) {
+        is Value.Integer -> value.toBigInteger()
+        is Value.Char -> value.takeIf { it.isNotEmpty() }?.codePointAt(0)?.toBigInteger()
+        else -> null
+    }
+
+private fun Value<*>.text(ty: Ty): String = when {
+    this is Value.Integer && ty is TyInteger && value.toBigInteger() == ty.maxValue -> "${ty.name}::MAX"
+    this is Value.Integer && ty is TyInteger && value.toBigInteger() == ty.minValue && value != 0L -> "${ty.name}::MIN"
+    this is Value.Char -> {
+        val code = value.codePointAt(0)
+        val char = when {
+            value == "'" || value == "\\" -> "\\$value"
+            code in 0x20..0x7E -> value
+            else -> "\\u{${Integer.toHexString(code)}}"
+        }
+        "'$char'"
+    }
+    else -> toString()
+}
+
 private fun RsFieldsOwner.initializer(subPatterns: List<Pattern>, ctx: RsElement?): String = when {
     blockFields != null -> {
         subPatterns.withIndex().joinToString(",", "{", "}") { (index, pattern) ->
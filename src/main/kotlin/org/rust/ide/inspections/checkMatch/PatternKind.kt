@@ -27,7 +27,11 @@ sealed class PatternKind {
 
     data class Const(val value: Value<*>) : PatternKind()
 
-    data class Range(val lc: Value<*>, val rc: Value<*>, val isInclusive: Boolean) : PatternKind()
+    /** `lc..rc`, `lc..=rc` or `lc..` (if [rc] is `null`) */
+    data class Range(val lc: Value<*>, val rc: Value<*>?, val isInclusive: Boolean) : PatternKind()
+
+    /** P | Q, where the alternatives are not or-patterns themselves */
+    data class Or(val patterns: List<Pattern>) : PatternKind()
 
 
     interface SliceField {
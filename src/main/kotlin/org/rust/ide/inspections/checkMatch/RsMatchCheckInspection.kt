@@ -16,6 +16,7 @@ import org.rust.ide.inspections.fixes.SubstituteTextFix
 import org.rust.lang.core.psi.RsElementTypes.OR
 import org.rust.lang.core.psi.RsMatchArm
 import org.rust.lang.core.psi.RsMatchExpr
+import org.rust.lang.core.psi.RsPat
 import org.rust.lang.core.psi.RsVisitor
 import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.types.infer.containsTyOfClass
@@ -55,7 +56,7 @@ private fun checkUselessArm(match: RsMatchExpr, holder: RsProblemsHolder) {
 
     for ((i, patterns) in matrix.withIndex()) {
         val armPat = armPats[i]
-        val useful = isUseful(seen, patterns, false)
+        val useful = isUseful(seen, patterns, false, match.crateRoot)
         if (!useful.isUseful) {
             val arm = armPat.ancestorStrict<RsMatchArm>() ?: return
 
@@ -90,7 +91,7 @@ private fun checkExhaustive(match: RsMatchExpr, holder: RsProblemsHolder) {
         .takeIf { it.type !is TyUnknown }
         ?: return
 
-    val useful = isUseful(matrix, listOf(Pattern.Wild), true)
+    val useful = isUseful(matrix, listOf(Pattern.Wild), true, match.crateRoot)
 
     /** if `_` pattern is useful, the match is not exhaustive */
     if (useful is UsefulWithWitness) {
@@ -99,13 +100,41 @@ private fun checkExhaustive(match: RsMatchExpr, holder: RsProblemsHolder) {
     }
 }
 
-/** Use algorithm from 3.1 http://moscova.inria.fr/~maranget/papers/warn/warn004.html */
-private fun isUseful(matrix: Matrix, patterns: List<Pattern>, withWitness: Boolean): Usefulness {
-    fun expandConstructors(constructors: List<Constructor>, type: Ty): Usefulness = constructors
-        .map { isUsefulSpecialized(matrix, patterns, it, type, withWitness) }
-        .find { it.isUseful }
-        ?: Useless
+/**
+ * Returns the patterns of all constructors of [type], e.g. `E::A` and `E::B(_)` for `enum E { A, B(i32) }`.
+ * They are built like the witnesses of a non-exhaustive match, so they can be turned into arms
+ * like [org.rust.ide.inspections.fixes.AddRemainingArmsFix] does
+ */
+fun allConstructorPatterns(type: Ty): List<Pattern> =
+    allConstructors(type).map { Witness().pushWildConstructor(it, type).patterns.single() }
+
+/**
+ * Checks if [pats] (alternatives of `if let`, `while let` or a match arm) match all values of the scrutinee type,
+ * i.e. whether `_` pattern is useless after them.
+ *
+ * @returns `null` if it can't be checked, e.g. if some types are unknown or patterns are not supported yet
+ */
+fun checkIrrefutable(pats: List<RsPat>): Boolean? {
+    val crateRoot = pats.firstOrNull()?.crateRoot
+    return try {
+        val matrix = pats.map { listOf(it.lower) }
+        val type = matrix.type
+        if (type is TyUnknown || type.containsTyOfClass(TyUnknown::class.java)) return null
+        !isUseful(matrix, listOf(Pattern.Wild), false, crateRoot).isUseful
+    } catch (todo: NotImplementedError) {
+        null
+    } catch (e: CheckMatchException) {
+        null
+    }
+}
 
+/**
+ * Use algorithm from 3.1 http://moscova.inria.fr/~maranget/papers/warn/warn004.html
+ *
+ * [crateRoot] is the root of the crate the patterns belong to. It's needed because
+ * `#[non_exhaustive]` enums and structs are exhaustive inside their own crate
+ */
+private fun isUseful(matrix: Matrix, patterns: List<Pattern>, withWitness: Boolean, crateRoot: RsMod?): Usefulness {
     if (patterns.isEmpty()) {
         if (matrix.isEmpty()) {
             return if (withWitness) UsefulWithWitness.Empty else Useful
@@ -113,25 +142,49 @@ private fun isUseful(matrix: Matrix, patterns: List<Pattern>, withWitness: Boole
         return Useless
     }
 
-    val type = matrix.firstColumnType
-    val constructors = patterns.first().constructors
-    if (constructors != null) {
-        return expandConstructors(constructors, type)
+    val head = patterns.first()
+    if (head.kind is PatternKind.Or) {
+        val otherPatterns = patterns.subList(1, patterns.size)
+        return head.kind.patterns.asSequence()
+            .map { isUseful(matrix, listOf(it) + otherPatterns, withWitness, crateRoot) }
+            .find { it.isUseful }
+            ?: Useless
     }
 
+    @Suppress("NAME_SHADOWING")
+    val matrix = matrix.expandOrPatterns()
+
+    fun expandConstructors(constructors: List<Constructor>, type: Ty): Usefulness = constructors
+        .map { isUsefulSpecialized(matrix, patterns, it, type, withWitness, crateRoot) }
+        .find { it.isUseful }
+        ?: Useless
+
+    val type = matrix.firstColumnType.takeIf { it !is TyUnknown } ?: head.ty
     val usedConstructors = matrix.flatMap { it.firstOrNull()?.constructors ?: emptyList() }
+
+    val constructors = head.constructors
+    if (constructors != null) {
+        return expandConstructors(constructors.flatMap { it.split(usedConstructors) }, type)
+    }
+
     val allConstructors = allConstructors(type)
-    val missingConstructor = allConstructors.minus(usedConstructors)
+    val missingConstructor = allConstructors.flatMap { constructor ->
+        if (constructor is Constructor.IntRange) {
+            constructor.subtract(usedConstructors.filterIsInstance<Constructor.IntRange>())
+        } else {
+            constructor.split(usedConstructors).filter { split -> usedConstructors.none { split.isCoveredBy(it) } }
+        }
+    }
 
     val isPrivatelyEmpty = allConstructors.isEmpty()
-    val isDeclaredNonExhaustive = (type as? TyAdt)?.item?.outerAttrList
-        ?.any { it.metaItem.name == "non_exhaustive" }
-        ?: false
+    val item = (type as? TyAdt)?.item
+    val isDeclaredNonExhaustive = item != null && item.crateRoot != crateRoot &&
+        item.outerAttrList.any { it.metaItem.name == "non_exhaustive" }
 
     val isNonExhaustive = isPrivatelyEmpty || isDeclaredNonExhaustive
 
     if (missingConstructor.isEmpty() && !isNonExhaustive) {
-        return expandConstructors(allConstructors, type)
+        return expandConstructors(allConstructors.flatMap { it.split(usedConstructors) }, type)
     }
 
     val newMatrix = matrix.mapNotNull {
@@ -143,10 +196,12 @@ private fun isUseful(matrix: Matrix, patterns: List<Pattern>, withWitness: Boole
         }
     }
     val newPatterns = patterns.subList(1, patterns.size)
-    val res = isUseful(newMatrix, newPatterns, withWitness)
+    val res = isUseful(newMatrix, newPatterns, withWitness, crateRoot)
 
     if (res is UsefulWithWitness) {
-        val newWitness = if (isNonExhaustive || usedConstructors.isEmpty()) {
+        // A lot of missing ranges are hard to read, so a wildcard is suggested instead of them
+        val tooManyRanges = missingConstructor.count { it is Constructor.IntRange } > MAX_MISSING_RANGES
+        val newWitness = if (isNonExhaustive || usedConstructors.isEmpty() || tooManyRanges) {
             res.witnesses.map { witness ->
                 witness.patterns.add(Pattern(type, PatternKind.Wild))
                 witness
@@ -167,12 +222,13 @@ private fun isUsefulSpecialized(
     patterns: List<Pattern>,
     constructor: Constructor,
     type: Ty,
-    withWitness: Boolean
+    withWitness: Boolean,
+    crateRoot: RsMod?
 ): Usefulness {
     val newPatterns = specializeRow(patterns, constructor, type) ?: return Useless
     val newMatrix = matrix.mapNotNull { specializeRow(it, constructor, type) }
 
-    return when (val useful = isUseful(newMatrix, newPatterns, withWitness)) {
+    return when (val useful = isUseful(newMatrix, newPatterns, withWitness, crateRoot)) {
         is UsefulWithWitness -> UsefulWithWitness(useful.witnesses.map { it.applyConstructor(constructor, type) })
         else -> useful
     }
@@ -195,18 +251,40 @@ private fun specializeRow(row: List<Pattern>, constructor: Constructor, type: Ty
 
         is PatternKind.Deref -> listOf(kind.subPattern)
 
-        is PatternKind.Const -> when {
-            constructor is Constructor.Slice -> TODO()
-            constructor.coveredByRange(kind.value, kind.value, true) -> emptyList()
-            else -> null
+        is PatternKind.Const, is PatternKind.Range -> {
+            val isCovered = when (constructor) {
+                is Constructor.IntRange -> {
+                    // the constructor is already split, so it's either contained in the range of the pattern or disjoint with it
+                    val range = pat.constructors?.singleOrNull() as? Constructor.IntRange
+                    range != null && constructor in range
+                }
+                // Constants of slice and array types, e.g. byte string literals, aren't evaluated
+                is Constructor.Slice, is Constructor.VarLenSlice ->
+                    throw CheckMatchException("Constant slice patterns are not supported")
+                else -> when (kind) {
+                    is PatternKind.Const -> constructor.coveredByRange(kind.value, kind.value, true)
+                    is PatternKind.Range -> kind.rc != null && constructor.coveredByRange(kind.lc, kind.rc, kind.isInclusive)
+                    else -> false
+                }
+            }
+            if (isCovered) emptyList() else null
         }
 
-        is PatternKind.Range -> when {
-            constructor.coveredByRange(kind.lc, kind.rc, kind.isInclusive) -> emptyList()
-            else -> null
+        is PatternKind.Slice, is PatternKind.Array -> {
+            val sliceField = kind as PatternKind.SliceField
+            val prefix = sliceField.prefix
+            val suffix = sliceField.suffix
+            when {
+                constructor !is Constructor.Slice && constructor !is Constructor.VarLenSlice -> null
+                // `[a, b]` matches only slices of the same length
+                sliceField.slice == null -> prefix.takeIf { constructor is Constructor.Slice && it.size == wildPatterns.size }
+                // `[a, .., b]` matches all slices that are long enough, and `..` is replaced with wildcards
+                prefix.size + suffix.size > wildPatterns.size -> null
+                else -> prefix + List(wildPatterns.size - prefix.size - suffix.size) { Pattern.Wild } + suffix
+            }
         }
 
-        is PatternKind.Slice, is PatternKind.Array -> TODO()
+        is PatternKind.Or -> throw CheckMatchException("Unexpanded or-pattern")
 
         PatternKind.Wild, is PatternKind.Binding -> wildPatterns
     }
@@ -214,6 +292,15 @@ private fun specializeRow(row: List<Pattern>, constructor: Constructor, type: Ty
     return head?.plus(row.subList(1, row.size))
 }
 
+/** Replaces each row starting with an or-pattern with the rows starting with its alternatives */
+private fun Matrix.expandOrPatterns(): Matrix =
+    flatMap { row ->
+        val head = row.firstOrNull()?.kind as? PatternKind.Or ?: return@flatMap listOf(row)
+        head.patterns.map { listOf(it) + row.subList(1, row.size) }
+    }
+
+private const val MAX_MISSING_RANGES: Int = 3
+
 private fun MutableList<Pattern>.fillWithSubPatterns(subPatterns: List<Pattern>) {
     for ((index, pattern) in subPatterns.withIndex()) {
         while (size <= index) add(Pattern.Wild) // TODO: maybe it's better to throw an exception?
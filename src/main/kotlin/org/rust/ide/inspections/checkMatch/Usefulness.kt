@@ -7,6 +7,8 @@ package org.rust.ide.inspections.checkMatch
 
 import org.rust.lang.core.psi.RsEnumItem
 import org.rust.lang.core.types.ty.*
+import org.rust.lang.utils.evaluation.ConstExpr.Value
+import java.math.BigInteger
 
 class Witness(val patterns: MutableList<Pattern> = mutableListOf()) {
     override fun toString() = patterns.toString()
@@ -45,12 +47,21 @@ class Witness(val patterns: MutableList<Pattern> = mutableListOf()) {
 
             is TyReference -> PatternKind.Deref(pats.first())
 
-            is TySlice, is TyArray -> TODO()
+            is TySlice, is TyArray -> when (constructor) {
+                is Constructor.Slice -> sliceKind(type, pats, null, emptyList())
+                is Constructor.VarLenSlice -> {
+                    val prefix = pats.take(constructor.prefix)
+                    val suffix = pats.drop(constructor.prefix)
+                    sliceKind(type, prefix, Pattern.Wild, suffix)
+                }
+                is Constructor.ConstantValue -> PatternKind.Const(constructor.value)
+                else -> PatternKind.Wild
+            }
 
-            else -> if (constructor is Constructor.ConstantValue) {
-                PatternKind.Const(constructor.value)
-            } else {
-                PatternKind.Wild
+            else -> when (constructor) {
+                is Constructor.ConstantValue -> PatternKind.Const(constructor.value)
+                is Constructor.IntRange -> rangeKind(constructor, type)
+                else -> PatternKind.Wild
             }
         }
         patterns.add(Pattern(type, kind))
@@ -58,6 +69,34 @@ class Witness(val patterns: MutableList<Pattern> = mutableListOf()) {
     }
 }
 
+private fun sliceKind(type: Ty, prefix: List<Pattern>, slice: Pattern?, suffix: List<Pattern>): PatternKind =
+    if (type is TyArray) {
+        PatternKind.Array(prefix, slice, suffix)
+    } else {
+        PatternKind.Slice(prefix, slice, suffix)
+    }
+
+/**
+ * [Value.Integer] can't hold values beyond [Long] bounds, e.g. the upper half of `u64`, so such ranges are
+ * represented by `start..=MAX` without the end value if they reach the max value of the type, or by a wildcard otherwise
+ */
+private fun rangeKind(range: Constructor.IntRange, type: Ty): PatternKind {
+    fun value(code: BigInteger): Value<*>? = when {
+        type is TyChar -> Value.Char(String(Character.toChars(code.toInt())))
+        code.bitLength() >= Long.SIZE_BITS -> null
+        else -> Value.Integer(code.toLong(), type as? TyInteger ?: TyInteger.DEFAULT)
+    }
+
+    val start = value(range.start) ?: return PatternKind.Wild
+    if (range.start == range.end) return PatternKind.Const(start)
+    val end = value(range.end)
+    return when {
+        end != null -> PatternKind.Range(start, end, isInclusive = true)
+        type is TyInteger && range.end == type.maxValue -> PatternKind.Range(start, null, isInclusive = true)
+        else -> PatternKind.Wild
+    }
+}
+
 sealed class Usefulness {
     class UsefulWithWitness(val witnesses: List<Witness>) : Usefulness() {
         companion object {
@@ -0,0 +1,150 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections.fixes
+
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.Key
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import com.intellij.psi.SmartPsiElementPointer
+import com.intellij.psi.util.CachedValue
+import com.intellij.psi.util.CachedValueProvider
+import com.intellij.psi.util.CachedValuesManager
+import org.rust.ide.inspections.import.AutoImportFix
+import org.rust.ide.inspections.import.ImportContext
+import org.rust.ide.inspections.import.import
+import org.rust.ide.search.RsWithMacrosProjectScope
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.stubs.index.RsNamedElementIndex
+import org.rust.lang.core.types.ty.TyReference
+import org.rust.lang.core.types.ty.TyTypeParameter
+import org.rust.lang.core.types.type
+import org.rust.openapiext.createSmartPointer
+import java.util.concurrent.ConcurrentHashMap
+import java.util.concurrent.ConcurrentMap
+
+/**
+ * Adds `T: Trait` bound to the type parameter `T`.
+ * If the declaration of the parameter has a `where` clause, the bound is added to it,
+ * otherwise it is added inline, e.g. `fn foo<T: Trait>()`.
+ * If the trait is not in scope, it is imported after the bound is added.
+ */
+class AddTypeParameterBoundFix(
+    typeParameter: RsTypeParameter,
+    trait: RsTraitItem
+) : LocalQuickFixOnPsiElement(typeParameter) {
+    private val parameterName: String = typeParameter.name.orEmpty()
+    private val traitName: String = trait.name.orEmpty()
+    private val traitPointer: SmartPsiElementPointer<RsTraitItem> = trait.createSmartPointer()
+
+    override fun getFamilyName(): String = "Add trait bound"
+
+    override fun getText(): String = "Add `$parameterName: $traitName` bound"
+
+    override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+        val typeParameter = startElement as? RsTypeParameter ?: return
+        val name = typeParameter.name ?: return
+        val trait = traitPointer.element ?: return
+        val psiFactory = RsPsiFactory(project)
+        val whereClause = (typeParameter.parent?.parent as? RsGenericDeclaration)?.whereClause
+
+        if (whereClause != null) {
+            val pred = whereClause.wherePredList.find { it.typeReference?.text == name && it.forLifetimes == null }
+            val bounds = pred?.typeParamBounds
+            if (bounds != null) {
+                bounds.replace(psiFactory.createTypeParamBounds(bounds.appendBound()))
+            } else {
+                val newPred = psiFactory.createWherePred("$name: $traitName")
+                val preds = whereClause.wherePredList
+                if (preds.isEmpty()) {
+                    whereClause.addAfter(newPred, whereClause.where)
+                    whereClause.addAfter(psiFactory.createWhitespace(" "), whereClause.where)
+                } else {
+                    ensureTrailingComma(preds)
+                    val comma = preds.last().getNextNonCommentSibling() ?: return
+                    whereClause.addAfter(newPred, comma)
+                    whereClause.addAfter(psiFactory.createWhitespace(" "), comma)
+                }
+            }
+        } else {
+            val bounds = typeParameter.typeParamBounds
+            if (bounds != null) {
+                bounds.replace(psiFactory.createTypeParamBounds(bounds.appendBound()))
+            } else {
+                typeParameter.addAfter(psiFactory.createTypeParamBounds(traitName), typeParameter.identifier)
+            }
+        }
+
+        if (listOf(trait).filterInScope(typeParameter).isEmpty()) {
+            val importContext = ImportContext.from(project, typeParameter)
+            AutoImportFix.getImportCandidates(importContext, traitName, traitName) { it.item == trait }
+                .firstOrNull()
+                ?.import(typeParameter)
+        }
+    }
+
+    private fun RsTypeParamBounds.appendBound(): String {
+        val existingBounds = text.removePrefix(":").trim()
+        return if (existingBounds.isEmpty()) traitName else "$existingBounds + $traitName"
+    }
+
+    companion object {
+        /**
+         * Returns fixes for an unresolved method call on a value of a generic type `T`,
+         * one for each trait that can provide the method if `T` is bounded by it.
+         * Returns nothing if the receiver is a concrete type
+         */
+        fun createFixes(methodCall: RsMethodCall): List<AddTypeParameterBoundFix> {
+            var receiverType = methodCall.parentDotExpr.expr.type
+            while (receiverType is TyReference) {
+                receiverType = receiverType.referenced
+            }
+            val parameter = (receiverType as? TyTypeParameter)?.parameter as? TyTypeParameter.Named ?: return emptyList()
+            val typeParameter = parameter.parameter
+            if (typeParameter.containingFile != methodCall.containingFile) return emptyList()
+
+            val traits = findTraitsWithMethod(methodCall.containingFile, methodCall.referenceName)
+            if (traits.isEmpty()) return emptyList()
+
+            val traitsInScope = traits.filterInScope(methodCall)
+            return traits
+                // Traits which are not in scope are imported, so they should be visible from the current crate
+                .filter { it in traitsInScope || it.isPublic || it.containingCrate == methodCall.containingCrate }
+                .sortedBy { it !in traitsInScope }
+                .map { AddTypeParameterBoundFix(typeParameter, it) }
+        }
+
+        private val TRAITS_WITH_METHOD_KEY: Key<CachedValue<ConcurrentMap<String, List<RsTraitItem>>>> =
+            Key.create("TRAITS_WITH_METHOD_KEY")
+
+        /**
+         * Traits that have a method with the given name.
+         * The index is searched once per file and method name until the project structure changes
+         */
+        private fun findTraitsWithMethod(file: PsiFile, methodName: String): List<RsTraitItem> {
+            val project = file.project
+            val cache = CachedValuesManager.getCachedValue(file, TRAITS_WITH_METHOD_KEY) {
+                CachedValueProvider.Result.create(
+                    ConcurrentHashMap<String, List<RsTraitItem>>(),
+                    project.rustStructureModificationTracker
+                )
+            }
+            return cache.getOrPut(methodName) {
+                RsNamedElementIndex.findElementsByName(project, methodName, RsWithMacrosProjectScope(project))
+                    .asSequence()
+                    .filterIsInstance<RsFunction>()
+                    .filter { it.hasSelfParameters }
+                    .mapNotNull { (it.owner as? RsAbstractableOwner.Trait)?.trait }
+                    // A bound on a generic trait requires type arguments we can't guess
+                    .filter { trait -> trait.typeParameters.all { it.typeReference != null } }
+                    .distinct()
+                    .toList()
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections.fixes
+
+import com.intellij.codeInspection.LocalQuickFixAndIntentionActionOnPsiElement
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.vfs.VfsUtil
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.lang.core.psi.RsMacroCall
+import org.rust.lang.core.psi.ext.includeBaseDirectory
+import org.rust.lang.core.psi.ext.includedFilePath
+
+/**
+ * Creates the missing file of `include!`, `include_str!` or `include_bytes!` macro call.
+ * Only relative paths are supported, they are resolved relative to the file containing the macro call
+ */
+class CreateIncludedFileFix(call: RsMacroCall) : LocalQuickFixAndIntentionActionOnPsiElement(call) {
+    private val path: String? = call.includedFilePath
+
+    override fun getFamilyName(): String = "Create file"
+    override fun getText(): String = "Create file `$path`"
+
+    override fun invoke(project: Project, file: PsiFile, editor: Editor?, startElement: PsiElement, endElement: PsiElement) {
+        val call = startElement as? RsMacroCall ?: return
+        val path = path ?: return
+        val baseDir = call.includeBaseDirectory ?: return
+        val dirPath = path.substringBeforeLast('/', "")
+        val dir = if (dirPath.isEmpty()) baseDir else VfsUtil.createDirectoryIfMissing(baseDir, dirPath) ?: return
+        dir.createChildData(this, path.substringAfterLast('/'))
+    }
+
+    companion object {
+        fun createIfApplicable(call: RsMacroCall): CreateIncludedFileFix? {
+            val path = call.includedFilePath ?: return null
+            if (path.startsWith("/") || path.endsWith("/") || path.split('/').any { it == ".." }) return null
+            return CreateIncludedFileFix(call)
+        }
+    }
+}
@@ -5,6 +5,7 @@
 
 package org.rust.ide.inspections.import
 
+import com.intellij.application.options.CodeStyle
 import com.intellij.codeInsight.intention.HighPriorityAction
 import com.intellij.codeInspection.LocalQuickFixOnPsiElement
 import com.intellij.ide.DataManager
@@ -17,9 +18,14 @@ import com.intellij.psi.search.GlobalSearchScope
 import org.rust.cargo.project.workspace.CargoWorkspace
 import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.cargo.util.AutoInjectedCrates
+import org.rust.ide.formatter.settings.ImportsGranularity
+import org.rust.ide.formatter.settings.RsCodeStyleSettings
 import org.rust.ide.injected.isDoctestInjection
 import org.rust.ide.inspections.import.AutoImportFix.Type.*
 import org.rust.ide.search.RsWithMacrosProjectScope
+import org.rust.ide.utils.ImportEntry
+import org.rust.ide.utils.importEntries
+import org.rust.ide.utils.renderUseSpecks
 import org.rust.lang.core.parser.RustParserUtil.PathParsingMode
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
@@ -176,6 +182,24 @@ class AutoImportFix(element: RsElement, private val type: Type) : LocalQuickFixO
                 .filter { canBeResolvedToSuitableItem(importingPathText, importContext, it.info) }
         }
 
+        /**
+         * Returns a sequence of import candidates for the custom derive [derive], i.e. `#[proc_macro_derive(Name)]`
+         * function, including reexports of the derive, e.g. `pub use serde_derive::Serialize` in `serde`.
+         * Unlike other items, a custom derive is imported by its derive name instead of the function name
+         */
+        fun getDeriveImportCandidates(importContext: ImportContext, derive: RsFunction): Sequence<ImportCandidate> {
+            val project = importContext.project
+            val deriveName = derive.procMacroDeriveName ?: return emptySequence()
+            val explicitItem = QualifiedNamedItem.ExplicitItem(derive, deriveName)
+            val reexportedItems = getReexportedItems(project, deriveName, importContext.scope)
+                .filter { it.item == derive }
+
+            return (sequenceOf(explicitItem) + reexportedItems)
+                .flatMap { it.withModuleReexports(project).asSequence() }
+                .mapNotNull { it.toImportCandidate(importContext.superMods) }
+                .filterImportCandidates(importContext.attributes)
+        }
+
         private fun getReexportedItems(
             project: Project,
             targetName: String,
@@ -602,11 +626,31 @@ fun RsItemsOwner.insertUseItem(psiFactory: RsPsiFactory, usePath: String) {
 }
 
 private fun RsItemsOwner.tryGroupWithOtherUseItems(psiFactory: RsPsiFactory, newUseItem: RsUseItem): Boolean {
+    val granularity = CodeStyle.getCustomSettings(containingFile, RsCodeStyleSettings::class.java).importsGranularity
+    when (granularity) {
+        ImportsGranularity.ITEM -> return false
+        ImportsGranularity.CRATE -> {
+            val newEntry = newUseItem.importEntries?.singleOrNull() ?: return false
+            return childrenOfType<RsUseItem>().any { it.tryMergeWithinCrate(psiFactory, newEntry) }
+        }
+        ImportsGranularity.PRESERVE, ImportsGranularity.MODULE -> Unit
+    }
     val newParentPath = newUseItem.parentPath ?: return false
     val newImportingName = newUseItem.importingNames?.singleOrNull() ?: return false
     return childrenOfType<RsUseItem>().any { it.tryGroupWith(psiFactory, newParentPath, newImportingName) }
 }
 
+private fun RsUseItem.tryMergeWithinCrate(psiFactory: RsPsiFactory, newEntry: ImportEntry): Boolean {
+    if (vis != null) return false
+    val crateName = newEntry.parentPath.firstOrNull() ?: return false
+    val entries = importEntries ?: return false
+    if (entries.isEmpty() || entries.any { it.parentPath.firstOrNull() != crateName }) return false
+    if (newEntry in entries) return true
+    val newUsePath = renderUseSpecks(entries + newEntry, ImportsGranularity.CRATE).singleOrNull() ?: return false
+    useSpeck?.replace(psiFactory.createUseSpeck(newUsePath))
+    return true
+}
+
 private fun RsUseItem.tryGroupWith(
     psiFactory: RsPsiFactory,
     newParentPath: List<String>,
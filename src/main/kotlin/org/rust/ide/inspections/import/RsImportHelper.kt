@@ -5,6 +5,7 @@
 
 package org.rust.ide.inspections.import
 
+import org.rust.ide.presentation.typeArgumentsWithoutUnchangedDefaults
 import org.rust.ide.settings.RsCodeInsightSettings
 import org.rust.lang.core.psi.RsMembers
 import org.rust.lang.core.psi.RsModDeclItem
@@ -33,8 +34,13 @@ object RsImportHelper {
         importElements(context, toImport)
     }
 
-    fun importTypeReferencesFromTy(context: RsElement, ty: Ty, useAliases: Boolean = false) {
-        val (toImport, _) = getTypeReferencesInfoFromTy(context, ty, useAliases)
+    fun importTypeReferencesFromTy(
+        context: RsElement,
+        ty: Ty,
+        useAliases: Boolean = false,
+        skipUnchangedDefaultTypeArguments: Boolean = false
+    ) {
+        val (toImport, _) = getTypeReferencesInfoFromTy(context, ty, useAliases, skipUnchangedDefaultTypeArguments)
         importElements(context, toImport)
     }
 
@@ -69,9 +75,10 @@ object RsImportHelper {
     private fun getTypeReferencesInfoFromTy(
         context: RsElement,
         elemTy: Ty,
-        useAliases: Boolean
+        useAliases: Boolean,
+        skipUnchangedDefaultTypeArguments: Boolean = false
     ): TypeReferencesInfo = getTypeReferencesInfo(context, listOf(elemTy)) { ty, result ->
-        collectImportSubjectsFromTy(ty, emptySubstitution, result, useAliases)
+        collectImportSubjectsFromTy(ty, emptySubstitution, result, useAliases, skipUnchangedDefaultTypeArguments)
     }
 
     private fun <T> getTypeReferencesInfo(
@@ -103,7 +110,8 @@ object RsImportHelper {
         ty: Ty,
         subst: Substitution,
         result: MutableSet<RsQualifiedNamedElement>,
-        useAliases: Boolean
+        useAliases: Boolean,
+        skipUnchangedDefaultTypeArguments: Boolean = false
     ) {
         ty.substitute(subst).visitWith(object : TypeVisitor {
             override fun visitTy(ty: Ty): Boolean {
@@ -114,6 +122,10 @@ object RsImportHelper {
                         if (alias != null) {
                             return true
                         }
+                        // Default type arguments aren't rendered, so they don't need to be imported
+                        if (skipUnchangedDefaultTypeArguments) {
+                            return ty.typeArgumentsWithoutUnchangedDefaults.any { it.visitWith(this) }
+                        }
                     }
                     is TyAnon -> result += ty.traits.map { it.element }
                     is TyTraitObject -> result += ty.traits.map { it.element }
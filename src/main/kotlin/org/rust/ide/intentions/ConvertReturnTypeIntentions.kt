@@ -0,0 +1,56 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import com.intellij.codeInsight.hint.HintManager
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.openapiext.Testmark
+import com.intellij.psi.PsiElement
+import org.rust.ide.utils.ReturnTypeConversion
+import org.rust.lang.core.psi.RsFunction
+import org.rust.lang.core.psi.RsPsiFactory
+import org.rust.lang.core.psi.RsRetType
+import org.rust.lang.core.psi.ext.ancestorStrict
+
+abstract class ConvertReturnTypeIntentionBase<T : ReturnTypeConversion> : RsElementBaseIntentionAction<T>() {
+    override fun getFamilyName(): String = text
+
+    protected abstract fun castConversion(conversion: ReturnTypeConversion): T?
+
+    override fun findApplicableContext(project: Project, editor: Editor, element: PsiElement): T? {
+        val function = element.ancestorStrict<RsRetType>()?.parent as? RsFunction ?: return null
+        return castConversion(ReturnTypeConversion.forFunction(function) ?: return null)
+    }
+
+    override fun invoke(project: Project, editor: Editor, ctx: T) {
+        val conflict = ctx.findConflict()
+        if (conflict != null) {
+            Testmarks.conflict.hit()
+            HintManager.getInstance().showErrorHint(editor, conflict, HintManager.UNDER)
+            return
+        }
+        ctx.convert(RsPsiFactory(project))
+    }
+
+    object Testmarks {
+        val conflict = Testmark("conflict")
+    }
+}
+
+class ConvertBoxDynToImplTraitIntention : ConvertReturnTypeIntentionBase<ReturnTypeConversion.ToImplTrait>() {
+    override fun getText(): String = "Convert to `impl Trait`"
+
+    override fun castConversion(conversion: ReturnTypeConversion): ReturnTypeConversion.ToImplTrait? =
+        conversion as? ReturnTypeConversion.ToImplTrait
+}
+
+class ConvertImplTraitToBoxDynIntention : ConvertReturnTypeIntentionBase<ReturnTypeConversion.ToBoxedTraitObject>() {
+    override fun getText(): String = "Convert to `Box<dyn Trait>`"
+
+    override fun castConversion(conversion: ReturnTypeConversion): ReturnTypeConversion.ToBoxedTraitObject? =
+        conversion as? ReturnTypeConversion.ToBoxedTraitObject
+}
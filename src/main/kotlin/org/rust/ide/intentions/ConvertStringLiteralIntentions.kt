@@ -0,0 +1,79 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import com.intellij.codeInsight.hint.HintManager
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.openapiext.Testmark
+import com.intellij.psi.PsiElement
+import com.intellij.psi.tree.TokenSet
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.ancestorOrSelf
+import org.rust.lang.utils.escapeRust
+import org.rust.lang.utils.rawStringHashesCount
+
+abstract class ConvertStringLiteralIntentionBase : RsElementBaseIntentionAction<ConvertStringLiteralIntentionBase.Context>() {
+    override fun getFamilyName(): String = text
+
+    class Context(val literal: RsLitExpr, val kind: RsLiteralKind.String)
+
+    protected abstract val sourceLiterals: TokenSet
+
+    override fun findApplicableContext(project: Project, editor: Editor, element: PsiElement): Context? {
+        val literal = element.ancestorOrSelf<RsLitExpr>() ?: return null
+        val kind = literal.kind as? RsLiteralKind.String ?: return null
+        if (kind.node.elementType !in sourceLiterals || kind.hasUnpairedQuotes) return null
+        return Context(literal, kind)
+    }
+
+    override fun invoke(project: Project, editor: Editor, ctx: Context) {
+        val value = ctx.kind.value ?: return
+        if (ctx.kind.isByte && value.any { it.toInt() >= 0x80 }) {
+            Testmarks.nonAsciiByteString.hit()
+            HintManager.getInstance().showErrorHint(editor, "Byte string literals can contain only ASCII characters")
+            return
+        }
+        val newValue = convert(value, ctx.kind.isByte, editor) ?: return
+        val prefix = if (ctx.kind.isByte) "b" else ""
+        val suffix = ctx.kind.suffix.orEmpty()
+        ctx.literal.replace(RsPsiFactory(project).createExpression("$prefix$newValue$suffix"))
+    }
+
+    /** Returns the literal without the `b` prefix and the suffix, e.g. `r#"foo"#` */
+    protected abstract fun convert(value: String, isByte: Boolean, editor: Editor): String?
+
+    object Testmarks {
+        val nonAsciiByteString = Testmark("nonAsciiByteString")
+        val carriageReturnInRawString = Testmark("carriageReturnInRawString")
+    }
+}
+
+class ConvertToRawStringIntention : ConvertStringLiteralIntentionBase() {
+    override fun getText(): String = "Convert to raw string"
+
+    override val sourceLiterals: TokenSet = RS_STRING_LITERALS
+
+    override fun convert(value: String, isByte: Boolean, editor: Editor): String? {
+        // Raw strings can't contain carriage returns, and there is no way to escape them
+        if ('\r' in value) {
+            Testmarks.carriageReturnInRawString.hit()
+            HintManager.getInstance().showErrorHint(editor, "Raw string literals can't contain `\\r`")
+            return null
+        }
+        val hashes = "#".repeat(rawStringHashesCount(value))
+        return "r$hashes\"$value\"$hashes"
+    }
+}
+
+class ConvertToRegularStringIntention : ConvertStringLiteralIntentionBase() {
+    override fun getText(): String = "Convert to regular string"
+
+    override val sourceLiterals: TokenSet = RS_RAW_LITERALS
+
+    override fun convert(value: String, isByte: Boolean, editor: Editor): String =
+        "\"${value.escapeRust(isByte)}\""
+}
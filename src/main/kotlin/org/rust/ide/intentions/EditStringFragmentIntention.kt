@@ -0,0 +1,51 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import com.intellij.codeInsight.intention.impl.QuickEditAction
+import com.intellij.lang.injection.InjectedLanguageManager
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.openapiext.isUnitTestMode
+import com.intellij.psi.PsiElement
+import org.rust.ide.injected.RsStringFragmentInjector
+import org.rust.ide.injected.RsStringFragmentInjector.Companion.isEditedStringFragment
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsLiteralKind
+import org.rust.lang.core.psi.ext.ancestorOrSelf
+import org.rust.lang.core.psi.ext.startOffset
+import org.rust.lang.core.psi.kind
+
+/**
+ * Opens the unescaped value of a string literal in a fragment editor, like "Edit Java string fragment".
+ * Literals with an injected language already have "Edit <language> fragment" action
+ */
+class EditStringFragmentIntention : RsElementBaseIntentionAction<RsLitExpr>() {
+    override fun getText(): String = "Edit Rust string fragment"
+    override fun getFamilyName(): String = text
+
+    override fun startInWriteAction(): Boolean = false
+
+    override fun findApplicableContext(project: Project, editor: Editor, element: PsiElement): RsLitExpr? {
+        val literal = element.ancestorOrSelf<RsLitExpr>() ?: return null
+        val kind = literal.kind as? RsLiteralKind.String ?: return null
+        if (kind.hasUnpairedQuotes || kind.offsets.value == null) return null
+        if (!literal.isEditedStringFragment &&
+            InjectedLanguageManager.getInstance(project).getInjectedPsiFiles(literal) != null) return null
+        return literal
+    }
+
+    override fun invoke(project: Project, editor: Editor, ctx: RsLitExpr) {
+        val valueRange = (ctx.kind as? RsLiteralKind.String)?.offsets?.value?.shiftRight(ctx.startOffset) ?: return
+        RsStringFragmentInjector.startEditing(ctx)
+        // The fragment editor is opened for the injection at the caret
+        if (!valueRange.containsOffset(editor.caretModel.offset)) {
+            editor.caretModel.moveToOffset(valueRange.startOffset)
+        }
+        if (isUnitTestMode) return
+        QuickEditAction().invokeImpl(project, editor, ctx.containingFile)
+    }
+}
@@ -90,13 +90,13 @@ class IfLetToMatchIntention : RsElementBaseIntentionAction<IfLetToMatchIntention
     }
 
     private fun extractIfLetStatementIfAny(iflet: RsIfExpr, ctx: Context? = null): Context? {
-        val condition = iflet.condition ?: return null
+        val letExpr = iflet.condition?.singleLetExpr ?: return null
 
         //2) Extract the match arm conditions
-        val orPats = condition.orPats ?: return null
+        val orPats = letExpr.orPats ?: return null
 
         //3) Extract the target
-        val target = condition.expr
+        val target = letExpr.expr ?: return null
 
         //4) Extract the if body
         val ifBody = iflet.block ?: return null
@@ -10,6 +10,7 @@ import com.intellij.openapi.project.Project
 import com.intellij.psi.PsiElement
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.ancestorStrict
+import org.rust.lang.core.psi.ext.letExprs
 import org.rust.lang.utils.negate
 
 class InvertIfIntention : RsElementBaseIntentionAction<InvertIfIntention.Context>() {
@@ -45,7 +46,7 @@ class InvertIfIntention : RsElementBaseIntentionAction<InvertIfIntention.Context
     }
 
     private fun getSuitableCondition(ifExpr: RsIfExpr): RsCondition? =
-        ifExpr.condition?.takeIf { it.let == null }
+        ifExpr.condition?.takeIf { it.letExprs.isEmpty() }
 
     override fun getFamilyName(): String = text
 
@@ -7,21 +7,47 @@ package org.rust.ide.intentions
 
 import com.intellij.openapi.editor.Editor
 import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiComment
 import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiWhiteSpace
+import com.intellij.psi.util.PsiTreeUtil
 import org.rust.lang.core.psi.*
-import org.rust.lang.core.psi.ext.ancestorStrict
-import org.rust.lang.core.psi.ext.getNextNonCommentSibling
-import org.rust.lang.core.psi.ext.isIrrefutable
+import org.rust.lang.core.psi.RsElementTypes.COMMA
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.types.ty.TyNever
+import org.rust.lang.core.types.type
 
+/**
+ * Converts
+ * ```
+ * match x {
+ *     Some(a) => foo(a),
+ *     _ => {}
+ * }
+ * ```
+ * to `if let Some(a) = x { foo(a) }`.
+ *
+ * If the second arm is a wildcard `_` with a non-empty body, it becomes the `else` branch.
+ * If the match initializes a `let` and the wildcard arm diverges, e.g.
+ * `let y = match x { Some(a) => a, _ => return };`, it is converted to `let Some(y) = x else { return };`
+ */
 class MatchToIfLetIntention : RsElementBaseIntentionAction<MatchToIfLetIntention.Context>() {
-    override fun getText() = "Convert match statement to if let"
-    override fun getFamilyName(): String = text
+    override fun getFamilyName(): String = "Convert match statement to if let"
 
     data class Context(
         val match: RsMatchExpr,
         val matchTarget: RsExpr,
         val nonVoidArm: RsMatchArm,
-        val orPats: RsOrPats
+        val orPats: RsOrPats,
+        val elseArm: RsMatchArm?,
+        val letElse: LetElseContext?
+    )
+
+    /** `let y = match x { Some(a) => a, _ => return };` */
+    data class LetElseContext(
+        val letDecl: RsLetDecl,
+        /** `a` in `Some(a)`, it's replaced with the pattern of [letDecl] */
+        val binding: RsPatBinding
     )
 
     override fun findApplicableContext(project: Project, editor: Editor, element: PsiElement): Context? {
@@ -30,30 +56,183 @@ class MatchToIfLetIntention : RsElementBaseIntentionAction<MatchToIfLetIntention
         val matchBody = matchExpr.matchBody ?: return null
         val matchArmList = matchBody.matchArmList
 
-        val nonVoidArm = matchArmList.singleOrNull { it.expr?.isVoid == false } ?: return null
+        val nonVoidArms = matchArmList.filter { it.expr?.isVoid == false }
+        val (nonVoidArm, elseArm) = when (nonVoidArms.size) {
+            1 -> nonVoidArms.single() to null
+            2 -> {
+                if (matchArmList.size != 2) return null
+                val (first, second) = matchArmList
+                if (second.matchArmGuard != null || second.outerAttrList.isNotEmpty()) return null
+                if (second.orPats.patList.singleOrNull() !is RsPatWild) return null
+                first to second
+            }
+            else -> return null
+        }
         if (nonVoidArm.matchArmGuard != null || nonVoidArm.outerAttrList.isNotEmpty()) return null
         val orPats = nonVoidArm.orPats
         val pats = orPats.patList
         if (pats.size == 1 && pats[0].isIrrefutable) return null
 
-        return Context(matchExpr, matchTarget, nonVoidArm, orPats)
+        val letElse = elseArm?.let { findLetElseContext(matchExpr, nonVoidArm, it) }
+        text = if (letElse != null) "Convert match statement to let-else" else familyName
+        return Context(matchExpr, matchTarget, nonVoidArm, orPats, elseArm, letElse)
+    }
+
+    private fun findLetElseContext(matchExpr: RsMatchExpr, arm: RsMatchArm, elseArm: RsMatchArm): LetElseContext? {
+        val letDecl = matchExpr.parent as? RsLetDecl ?: return null
+        if (letDecl.expr != matchExpr || letDecl.letElseBranch != null || letDecl.typeReference != null) return null
+        if (letDecl.pat !is RsPatIdent) return null
+        if (elseArm.expr?.type !is TyNever) return null
+        val pat = arm.orPats.patList.singleOrNull() ?: return null
+        val armBody = arm.expr?.unwrapBlock() as? RsPathExpr ?: return null
+        val binding = armBody.path.reference?.resolve() as? RsPatBinding ?: return null
+        if (!PsiTreeUtil.isAncestor(pat, binding, true)) return null
+        val patIdent = binding.parent as? RsPatIdent ?: return null
+        if (patIdent.pat != null || binding.bindingMode != null) return null
+        return LetElseContext(letDecl, binding)
     }
 
     override fun invoke(project: Project, editor: Editor, ctx: Context) {
-        val (matchExpr, matchTarget, arm, orPats) = ctx
+        val factory = RsPsiFactory(project)
+        if (ctx.letElse != null && ctx.elseArm != null) {
+            convertToLetElse(factory, ctx, ctx.letElse, ctx.elseArm)
+        } else {
+            convertToIfLet(factory, ctx)
+        }
+    }
 
-        var bodyText = arm.expr?.text ?: return
-        if (arm.expr !is RsBlockExpr) {
-            bodyText = "{\n$bodyText\n}"
+    private fun convertToIfLet(factory: RsPsiFactory, ctx: Context) {
+        val (matchExpr, matchTarget, arm, orPats, elseArm) = ctx
+        val elseText = if (elseArm != null) " else {}" else ""
+        val ifExpr = factory.createExpression("if let _ = x {}$elseText") as RsIfExpr
+        val letExpr = ifExpr.condition?.expr as RsLetExpr
+        letExpr.orPats?.replace(orPats)
+        letExpr.expr?.replace(matchTarget)
+        ifExpr.block?.replace(arm.toBlock(factory) ?: return)
+        if (elseArm != null) {
+            ifExpr.elseBranch?.block?.replace(elseArm.toBlock(factory) ?: return)
         }
+        matchExpr.replace(ifExpr)
+    }
 
-        val rustIfLetExprElement =
-            RsPsiFactory(project).createExpression("if let ${orPats.text} = ${matchTarget.text} $bodyText")
-                as RsIfExpr
-        matchExpr.replace(rustIfLetExprElement)
+    private fun convertToLetElse(factory: RsPsiFactory, ctx: Context, letElse: LetElseContext, elseArm: RsMatchArm) {
+        val letDecl = letElse.letDecl
+        val newLetDecl = factory.createStatement("let _ = x else {};") as RsLetDecl
+
+        // Replace the binding in a copy of the pattern, so `Some(a)` becomes `Some(y)`
+        val pat = ctx.nonVoidArm.orPats.patList.single()
+        val bindingOffset = letElse.binding.textRange.startOffset - pat.textRange.startOffset
+        val patCopy = pat.copy() as RsPat
+        val bindingCopy = PsiTreeUtil.findElementOfClassAtOffset(
+            patCopy.containingFile, patCopy.textRange.startOffset + bindingOffset, RsPatBinding::class.java, true
+        ) ?: return
+        bindingCopy.parent.replace(letDecl.pat ?: return)
+
+        newLetDecl.pat?.replace(patCopy)
+        newLetDecl.expr?.replace(ctx.matchTarget)
+        newLetDecl.elseBlock?.replace(elseArm.toBlock(factory) ?: return)
+
+        val comments = ctx.nonVoidArm.attachedComments
+        val parent = letDecl.parent
+        for (comment in comments) {
+            parent.addBefore(comment.copy(), letDecl)
+            parent.addBefore(factory.createNewline(), letDecl)
+        }
+        letDecl.replace(newLetDecl)
     }
 
     private val RsExpr.isVoid: Boolean
         get() = (this is RsBlockExpr && block.lbrace.getNextNonCommentSibling() == block.rbrace)
             || this is RsUnitExpr
 }
+
+/** `{ a }` -> `a` */
+private fun RsExpr.unwrapBlock(): RsExpr? {
+    if (this !is RsBlockExpr || !isPlainBlock) return this
+    if (block.stmtList.isNotEmpty()) return null
+    return block.expr?.unwrapBlock()
+}
+
+private val RsBlockExpr.isPlainBlock: Boolean get() = firstChild == block
+
+/**
+ * Returns the arm body as a block. Comments attached to the arm are moved into the block, so they are not lost:
+ * leading comments are placed at the beginning of the block and trailing ones at the end of the block
+ */
+private fun RsMatchArm.toBlock(factory: RsPsiFactory): RsBlock? {
+    val expr = expr ?: return null
+    val leadingComments = leadingComments
+    val trailingComments = trailingComments
+    val block = if (expr is RsBlockExpr && expr.isPlainBlock) {
+        if (leadingComments.isEmpty() && trailingComments.isEmpty()) return expr.block
+        expr.block.copy() as RsBlock
+    } else {
+        val newBlock = factory.createBlockExpr("\n()\n").block
+        newBlock.expr?.replace(expr)
+        newBlock
+    }
+
+    for (comment in leadingComments.asReversed()) {
+        val added = block.addAfter(comment.copy(), block.lbrace)
+        block.addBefore(factory.createNewline(), added)
+        val next = added.nextSibling
+        if (next !is PsiWhiteSpace || '\n' !in next.text) {
+            block.addAfter(factory.createNewline(), added)
+        }
+    }
+    val rbrace = block.rbrace ?: return block
+    for (comment in trailingComments) {
+        val added = block.addBefore(comment.copy(), rbrace)
+        block.addBefore(factory.createNewline(), added)
+    }
+    if (trailingComments.isNotEmpty()) {
+        block.addBefore(factory.createNewline(), rbrace)
+    }
+    return block
+}
+
+private val RsMatchArm.attachedComments: List<PsiComment>
+    get() = leadingComments + trailingComments
+
+/**
+ * Comments placed on their own lines before the arm and comments inside the arm itself,
+ * e.g. `Some(x) => /* comment */ foo(x)`
+ */
+private val RsMatchArm.leadingComments: List<PsiComment>
+    get() {
+        val before = leftSiblings
+            .takeWhile { it is PsiComment || it is PsiWhiteSpace }
+            .filterIsInstance<PsiComment>()
+            .filter { !it.isOnLineOfPreviousArm }
+            .toList()
+            .asReversed()
+        val inner = childrenWithLeaves.filterIsInstance<PsiComment>().toList()
+        return before + inner
+    }
+
+/**
+ * Comments on the same line after the arm. Comments after the last arm belong to it too,
+ * since there is nothing else they can be attached to
+ */
+private val RsMatchArm.trailingComments: List<PsiComment>
+    get() {
+        val isLast = rightSiblings.none { it is RsMatchArm }
+        return rightSiblings
+            .takeWhile { it is PsiComment || it is PsiWhiteSpace && (isLast || '\n' !in it.text) }
+            .filterIsInstance<PsiComment>()
+            .toList()
+    }
+
+/** `// comment` in `Some(x) => foo(x), // comment` */
+private val PsiComment.isOnLineOfPreviousArm: Boolean
+    get() {
+        for (sibling in leftSiblings) {
+            when {
+                sibling is PsiWhiteSpace -> if ('\n' in sibling.text) return false
+                sibling is PsiComment -> continue
+                sibling is RsMatchArm || sibling.elementType == COMMA -> return true
+                else -> return false
+            }
+        }
+        return false
+    }
@@ -0,0 +1,236 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiWhiteSpace
+import org.rust.ide.inspections.import.RsImportHelper.importTypeReferencesFromTy
+import org.rust.ide.presentation.renderInsertionSafe
+import org.rust.lang.core.cfg.ExitPoint
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.ImplLookup
+import org.rust.lang.core.resolve.knownItems
+import org.rust.lang.core.types.ty.*
+import org.rust.lang.core.types.type
+
+/**
+ * Replaces `foo.unwrap()` or `foo.expect("...")` with `foo?`.
+ *
+ * If the receiver is a `Result` or an `Option` and the return type of the enclosing function
+ * is not compatible with it, the return type is changed, and all the returned values are wrapped
+ * in `Ok(...)` or `Some(...)`:
+ *
+ * ```
+ * fn foo() -> i32 {                    fn foo() -> Result<i32, ParseIntError> {
+ *     let x = "1".parse::<i32>()  =>        let x = "1".parse::<i32>()?;
+ *         .unwrap();                       Ok(x)
+ *     x                                }
+ * }
+ * ```
+ */
+class ReplaceUnwrapWithTryIntention : RsElementBaseIntentionAction<ReplaceUnwrapWithTryIntention.Context>() {
+    override fun getFamilyName() = "Replace .unwrap() with try"
+
+    data class Context(
+        val methodCall: RsMethodCall,
+        /** `true` if the error should be converted with `.map_err(...)` stub */
+        val mapError: Boolean = false,
+        val signatureChange: SignatureChange? = null
+    )
+
+    data class SignatureChange(
+        val function: RsFunction,
+        val newReturnType: String,
+        /** `Ok` or `Some` */
+        val wrapper: String,
+        /** A type that was rendered into [newReturnType] and should be imported */
+        val importedTy: Ty?
+    )
+
+    override fun findApplicableContext(project: Project, editor: Editor, element: PsiElement): Context? {
+        val methodCall = element.ancestorOrSelf<RsMethodCall>() ?: return null
+        val methodName = methodCall.referenceName
+        val arguments = methodCall.valueArgumentList.exprList
+        val isApplicable = methodCall.typeArgumentList == null && when (methodName) {
+            "unwrap" -> arguments.isEmpty()
+            "expect" -> arguments.size == 1
+            else -> false
+        }
+        if (!isApplicable) return null
+        text = "Replace .$methodName() with try"
+
+        val receiverTy = methodCall.receiver.type
+        // We can't check anything if the type is unknown, so the call is just replaced
+        if (receiverTy is TyUnknown) return Context(methodCall)
+        val kind = ReceiverKind.of(receiverTy) ?: return null
+
+        val owner = methodCall.ancestors.firstOrNull {
+            it is RsFunction || it is RsLambdaExpr || it is RsBlockExpr && (it.isAsync || it.isTry)
+        } ?: return null
+        val returnTy = when (owner) {
+            is RsFunction -> owner.returnType
+            is RsLambdaExpr -> (owner.type as? TyFunction)?.retType ?: TyUnknown
+            // `?` in `try` block returns from the block itself
+            is RsBlockExpr -> if (owner.isTry) TyUnknown else return null
+            else -> return null
+        }
+        if (returnTy is TyUnknown) return Context(methodCall)
+
+        val returnKind = ReceiverKind.of(returnTy)
+        if (returnKind == kind) {
+            return Context(methodCall, mapError = !isErrorCompatible(methodCall, kind, receiverTy, returnTy))
+        }
+        // `?` on `Option` in a function returning `Result` (and vice versa) needs an explicit conversion
+        if (returnKind != null) return null
+
+        // The return type of closures is usually inferred, and async functions return futures,
+        // so we don't try to change their signatures
+        if (owner !is RsFunction || owner.isAsync || owner.superItem != null) return null
+        val signatureChange = createSignatureChange(owner, kind, receiverTy, returnTy) ?: return null
+        text = "Replace .$methodName() with try and change return type to '${signatureChange.newReturnType}'"
+        return Context(methodCall, signatureChange = signatureChange)
+    }
+
+    override fun invoke(project: Project, editor: Editor, ctx: Context) {
+        val psiFactory = RsPsiFactory(project)
+        val signatureChange = ctx.signatureChange
+        val exitPoints = if (signatureChange != null) collectExitPoints(signatureChange.function) else emptyList()
+
+        val dotExpr = ctx.methodCall.parentDotExpr
+        val receiver = if (ctx.mapError) {
+            psiFactory.createExpression("${ctx.methodCall.receiver.text}.map_err(|e| unimplemented!())")
+        } else {
+            ctx.methodCall.receiver
+        }
+        val tryExpr = dotExpr.replace(psiFactory.createTryExpression(receiver))
+
+        if (signatureChange != null) {
+            val updatedExitPoints = exitPoints.map { if (it == dotExpr) tryExpr else it }.filter { it.isValid }
+            changeSignature(psiFactory, signatureChange, updatedExitPoints)
+        }
+    }
+
+    private enum class ReceiverKind(val wrapper: String) {
+        OPTION("Some"),
+        RESULT("Ok");
+
+        companion object {
+            fun of(ty: Ty): ReceiverKind? {
+                val item = (ty as? TyAdt)?.item ?: return null
+                val knownItems = item.knownItems
+                return when (item) {
+                    knownItems.Option -> OPTION
+                    knownItems.Result -> RESULT
+                    else -> null
+                }
+            }
+        }
+    }
+
+    companion object {
+        private fun isErrorCompatible(context: RsElement, kind: ReceiverKind, receiverTy: Ty, returnTy: Ty): Boolean {
+            if (kind != ReceiverKind.RESULT) return true
+            val errorTy = (receiverTy as TyAdt).typeArguments.getOrNull(1) ?: return true
+            val returnErrorTy = (returnTy as TyAdt).typeArguments.getOrNull(1) ?: return true
+            if (errorTy is TyUnknown || returnErrorTy is TyUnknown || errorTy == returnErrorTy) return true
+            return ImplLookup.relativeTo(context).isInto(errorTy, returnErrorTy)
+        }
+
+        private fun createSignatureChange(
+            function: RsFunction,
+            kind: ReceiverKind,
+            receiverTy: Ty,
+            returnTy: Ty
+        ): SignatureChange? {
+            val okType = if (returnTy is TyUnit) "()" else function.retType?.typeReference?.text ?: return null
+            val isMain = function.name == "main" && function.parent is RsFile
+            if (kind == ReceiverKind.OPTION) {
+                // `main` can't return `Option`
+                if (isMain) return null
+                return SignatureChange(function, "Option<$okType>", kind.wrapper, null)
+            }
+
+            val hasAnyhow = function.containingCargoPackage?.findDependency("anyhow") != null
+            if (hasAnyhow) return SignatureChange(function, "anyhow::Result<$okType>", kind.wrapper, null)
+            if (isMain) return SignatureChange(function, "Result<$okType, Box<dyn std::error::Error>>", kind.wrapper, null)
+
+            val errorTy = (receiverTy as TyAdt).typeArguments.getOrNull(1) ?: return null
+            if (errorTy is TyUnknown) return null
+            val errorType = errorTy.renderInsertionSafe(includeLifetimeArguments = true, useAliasNames = true)
+            return SignatureChange(function, "Result<$okType, $errorType>", kind.wrapper, errorTy)
+        }
+
+        /**
+         * Collects the returned expressions of [function] (or `return` expressions without a value)
+         * excluding ones from nested closures and functions
+         */
+        private fun collectExitPoints(function: RsFunction): List<RsExpr> {
+            val exitPoints = mutableListOf<RsExpr>()
+            val returnsUnit = function.returnType is TyUnit
+            ExitPoint.process(function) { exitPoint ->
+                when (exitPoint) {
+                    is ExitPoint.Return -> exitPoints += exitPoint.e.expr ?: exitPoint.e
+                    // Tail expressions of `()` functions are handled separately in `changeSignature`
+                    is ExitPoint.TailExpr -> if (!returnsUnit) {
+                        val expr = exitPoint.e
+                        val returnedExpr = if (expr is RsBreakExpr) expr.expr else expr
+                        if (returnedExpr != null) exitPoints += returnedExpr
+                    }
+                }
+            }
+            return exitPoints
+        }
+
+        private fun changeSignature(psiFactory: RsPsiFactory, change: SignatureChange, exitPoints: List<PsiElement>) {
+            val function = change.function
+            val returnsUnit = function.returnType is TyUnit
+
+            for (expr in exitPoints) {
+                val wrapped = if (expr is RsRetExpr) {
+                    "return ${change.wrapper}(())"
+                } else {
+                    "${change.wrapper}(${expr.text})"
+                }
+                expr.replace(psiFactory.createExpression(wrapped))
+            }
+
+            if (returnsUnit) {
+                val block = function.block
+                if (block != null) addUnitTailExpr(psiFactory, block, change.wrapper)
+            }
+
+            val newRetType = psiFactory.createRetType(change.newReturnType)
+            val oldRetType = function.retType
+            if (oldRetType != null) {
+                oldRetType.replace(newRetType)
+            } else {
+                function.addAfter(newRetType, function.valueParameterList)
+            }
+            if (change.importedTy != null) {
+                importTypeReferencesFromTy(function, change.importedTy, useAliases = true)
+            }
+        }
+
+        /** Turns the tail expression of `()` function into a statement and adds `Ok(())` after it */
+        private fun addUnitTailExpr(psiFactory: RsPsiFactory, block: RsBlock, wrapper: String) {
+            val tailExpr = block.expr
+            val anchor = tailExpr ?: block.stmtList.lastOrNull() ?: return
+            val indent = (anchor.prevSibling as? PsiWhiteSpace)?.text ?: "\n"
+            val isBlockLike = tailExpr is RsIfExpr || tailExpr is RsMatchExpr || tailExpr is RsBlockExpr ||
+                tailExpr is RsLoopExpr || tailExpr is RsWhileExpr || tailExpr is RsForExpr
+            val lastElement = if (tailExpr != null && !isBlockLike) {
+                block.addAfter(psiFactory.createSemicolon(), tailExpr)
+            } else {
+                anchor
+            }
+            block.addAfter(psiFactory.createExpression("$wrapper(())"), lastElement)
+            block.addAfter(psiFactory.createWhitespace(indent), lastElement)
+        }
+    }
+}
@@ -9,7 +9,7 @@ import com.intellij.openapi.editor.Editor
 import com.intellij.openapi.project.Project
 import com.intellij.psi.PsiElement
 import org.rust.ide.inspections.import.RsImportHelper.importTypeReferencesFromTy
-import org.rust.ide.presentation.renderInsertionSafe
+import org.rust.ide.presentation.TypeRenderingLevel
 import org.rust.lang.core.psi.RsLetDecl
 import org.rust.lang.core.psi.RsPatIdent
 import org.rust.lang.core.psi.RsPsiFactory
@@ -55,11 +55,18 @@ class SpecifyTypeExplicitlyIntention : RsElementBaseIntentionAction<SpecifyTypeE
 
     override fun invoke(project: Project, editor: Editor, ctx: Context) {
         val factory = RsPsiFactory(project)
-        val createdType = factory.createType(ctx.type.renderInsertionSafe(useAliasNames = true))
+        // Insert the type in the form which was chosen the last time in "Show Expression Type"
+        val level = TypeRenderingLevel.lastUsed
+        val createdType = factory.createType(level.renderInsertionSafe(ctx.type))
         val letDecl = ctx.letDecl
         val colon = letDecl.addAfter(factory.createColon(), letDecl.pat)
         letDecl.addAfter(createdType, colon)
-        importTypeReferencesFromTy(ctx.letDecl, ctx.type, useAliases = true)
+        importTypeReferencesFromTy(
+            ctx.letDecl,
+            ctx.type,
+            useAliases = level.useAliasNames,
+            skipUnchangedDefaultTypeArguments = level.skipUnchangedDefaultTypeArguments
+        )
     }
 
 
@@ -14,6 +14,7 @@ import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.LogicOp
 import org.rust.lang.core.psi.ext.ancestorOrSelf
 import org.rust.lang.core.psi.ext.ancestorStrict
+import org.rust.lang.core.psi.ext.letExprs
 import org.rust.lang.core.psi.ext.operatorType
 
 class SplitIfIntention : RsElementBaseIntentionAction<SplitIfIntention.Context>() {
@@ -31,6 +32,8 @@ class SplitIfIntention : RsElementBaseIntentionAction<SplitIfIntention.Context>(
         if (binExpr.operatorType !is LogicOp) return null
 
         val condition = binExpr.findCondition() ?: return null
+        // `let` expressions are not allowed in `||` operands
+        if (binExpr.operatorType == LogicOp.OR && condition.letExprs.isNotEmpty()) return null
         return Context(binExpr.binaryOp, condition)
     }
 
@@ -0,0 +1,52 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.lineMarkers
+
+import com.intellij.codeInsight.daemon.LineMarkerInfo
+import com.intellij.codeInsight.daemon.LineMarkerProvider
+import com.intellij.openapi.editor.markup.GutterIconRenderer
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiElement
+import org.rust.cargo.icons.CargoIcons
+import org.rust.cargo.runconfig.test.DoctestRunConfigurationProducer
+import org.rust.cargo.toolchain.run
+import org.rust.ide.injected.RsDoctestCodeBlock
+import org.rust.ide.injected.findDoctestCodeBlocks
+import org.rust.lang.core.psi.RsDocCommentImpl
+import org.rust.lang.core.psi.RsFile
+import org.rust.lang.core.psi.ext.startOffset
+
+/**
+ * Adds a gutter icon that runs the doctest to each runnable fenced code block of a doc comment.
+ */
+class RsDoctestLineMarkerProvider : LineMarkerProvider {
+
+    override fun getLineMarkerInfo(element: PsiElement): LineMarkerInfo<PsiElement>? = null
+
+    override fun collectSlowLineMarkers(elements: List<PsiElement>, result: MutableCollection<LineMarkerInfo<PsiElement>>) {
+        for (element in elements) {
+            if (element !is RsDocCommentImpl) continue
+            for (block in findDoctestCodeBlocks(element)) {
+                val config = DoctestRunConfigurationProducer.findDoctestConfig(element, block) ?: continue
+                val fenceOffset = element.startOffset + block.fenceOffset
+                result.add(LineMarkerInfo(
+                    element,
+                    TextRange(fenceOffset, fenceOffset + 3),
+                    CargoIcons.TEST,
+                    { "Run ${config.configurationName}" },
+                    { _, _ -> runDoctest(element, block) },
+                    GutterIconRenderer.Alignment.CENTER
+                ))
+            }
+        }
+    }
+
+    private fun runDoctest(comment: RsDocCommentImpl, block: RsDoctestCodeBlock) {
+        val config = DoctestRunConfigurationProducer.findDoctestConfig(comment, block) ?: return
+        val cargoProject = (comment.containingFile as? RsFile)?.cargoProject ?: return
+        config.cargoCommandLine().run(cargoProject, config.configurationName)
+    }
+}
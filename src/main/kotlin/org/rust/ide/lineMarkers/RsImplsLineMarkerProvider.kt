@@ -8,16 +8,21 @@ package org.rust.ide.lineMarkers
 import com.intellij.codeInsight.daemon.LineMarkerInfo
 import com.intellij.codeInsight.daemon.LineMarkerProvider
 import com.intellij.codeInsight.navigation.NavigationGutterIconBuilder
+import com.intellij.openapi.editor.markup.GutterIconRenderer
 import com.intellij.openapi.util.NotNullLazyValue
+import com.intellij.psi.NavigatablePsiElement
 import com.intellij.psi.PsiElement
+import com.intellij.util.FunctionUtil
 import com.intellij.util.Query
+import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.ide.icons.RsIcons
-import org.rust.lang.core.psi.RsEnumItem
-import org.rust.lang.core.psi.RsImplItem
-import org.rust.lang.core.psi.RsStructItem
-import org.rust.lang.core.psi.RsTraitItem
-import org.rust.lang.core.psi.ext.searchForImplementations
-import org.rust.lang.core.psi.ext.union
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.ImplLookup
+import org.rust.lang.core.types.TraitRef
+import org.rust.lang.core.types.ty.TyAdt
+import org.rust.lang.core.types.type
+import javax.swing.Icon
 
 /**
  * Annotates trait declaration with an icon on the gutter that allows to jump to
@@ -35,7 +40,11 @@ class RsImplsLineMarkerProvider : LineMarkerProvider {
             // but that might be costly. To save time, we always show an icon, but calculate
             // the actual icons only when the user clicks it.
             // if (query.isEmptyQuery) return null
-            val query = implsQuery(el) ?: continue
+            val query = implsQuery(el)
+            if (query == null) {
+                createTraitNavigationMarker(el)?.let { result += it }
+                continue
+            }
             val targets: NotNullLazyValue<Collection<PsiElement>> = NotNullLazyValue.createValue { query.findAll() }
             val info = NavigationGutterIconBuilder
                 .create(RsIcons.IMPLEMENTED)
@@ -48,6 +57,51 @@ class RsImplsLineMarkerProvider : LineMarkerProvider {
         }
     }
 
+    /**
+     * Markers for `dyn Trait` (navigates to the impls of `Trait`), generic parameters with bounds
+     * (navigates to workspace types satisfying all the bounds) and `impl Trait for Type` headers
+     * (navigates to `Trait` and other impls of it)
+     */
+    private fun createTraitNavigationMarker(el: PsiElement): LineMarkerInfo<PsiElement>? {
+        val parent = el.parent
+        if (parent is RsTypeParameter && parent.identifier == el) {
+            if (parent.bounds.none { !it.hasQ }) return null
+            val handler = RsImplsNavigationHandler("Types satisfying the bounds of `${parent.name}`") {
+                findTypesSatisfyingBounds(parent)
+            }
+            return createLineMarkerInfo(el, RsIcons.IMPLEMENTED, "Has types satisfying the bounds", handler)
+        }
+
+        if (parent !is RsPath || parent.referenceNameElement != el) return null
+        val traitRef = parent.parent as? RsTraitRef ?: return null
+        val owner = traitRef.parent
+        return when {
+            owner is RsImplItem -> {
+                val trait = traitRef.resolveToTrait() ?: return null
+                val handler = RsImplsNavigationHandler("Trait `${trait.name}` and its implementations") {
+                    listOf(trait) + trait.findImplementations().filter { it != owner }
+                }
+                createLineMarkerInfo(el, RsIcons.IMPLEMENTING_METHOD, "Implements `${trait.name}`", handler)
+            }
+            (owner?.parent?.parent as? RsTraitType)?.dyn != null -> {
+                val trait = traitRef.resolveToTrait() ?: return null
+                val handler = RsImplsNavigationHandler("Implementations of `${trait.name}`") {
+                    trait.findImplementations()
+                }
+                createLineMarkerInfo(el, RsIcons.IMPLEMENTED, "Has implementations", handler)
+            }
+            else -> null
+        }
+    }
+
+    private fun createLineMarkerInfo(
+        el: PsiElement,
+        icon: Icon,
+        tooltip: String,
+        handler: RsImplsNavigationHandler
+    ): LineMarkerInfo<PsiElement> =
+        LineMarkerInfo(el, el.textRange, icon, FunctionUtil.constant(tooltip), handler, GutterIconRenderer.Alignment.RIGHT)
+
     companion object {
         fun implsQuery(psi: PsiElement): Query<RsImplItem>? {
             val parent = psi.parent
@@ -62,3 +116,24 @@ class RsImplsLineMarkerProvider : LineMarkerProvider {
         }
     }
 }
+
+/**
+ * Workspace types implementing all the trait bounds of [parameter] and blanket impls of its first bound.
+ * Candidates are taken from the impls of the first non-auto trait, because
+ * auto traits like `Send` are implemented for most types implicitly
+ */
+private fun findTypesSatisfyingBounds(parameter: RsTypeParameter): List<NavigatablePsiElement> {
+    val traits = parameter.bounds
+        .filter { !it.hasQ }
+        .mapNotNull { it.bound.traitRef?.resolveToBoundTrait() }
+    val primaryTrait = traits.find { !it.element.isAuto } ?: return emptyList()
+    val lookup = ImplLookup.relativeTo(parameter)
+
+    val (blanketImpls, impls) = primaryTrait.element.findImplementations().partition { it.isBlanketImpl }
+    val types = impls.mapNotNull { impl ->
+        val ty = impl.typeReference?.type as? TyAdt ?: return@mapNotNull null
+        if (ty.item.containingCargoPackage?.origin != PackageOrigin.WORKSPACE) return@mapNotNull null
+        if (traits.all { lookup.canSelect(TraitRef(ty, it)) }) ty.item else null
+    }
+    return types.distinct() + blanketImpls
+}
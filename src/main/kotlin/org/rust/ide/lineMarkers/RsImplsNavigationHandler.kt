@@ -0,0 +1,67 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.lineMarkers
+
+import com.intellij.codeInsight.daemon.GutterIconNavigationHandler
+import com.intellij.codeInsight.daemon.impl.PsiElementListNavigator
+import com.intellij.codeInsight.navigation.BackgroundUpdaterTask
+import com.intellij.ide.util.PsiElementListCellRenderer
+import com.intellij.openapi.application.runReadAction
+import com.intellij.openapi.progress.ProgressIndicator
+import com.intellij.openapi.progress.ProgressManager
+import com.intellij.psi.NavigatablePsiElement
+import com.intellij.psi.PsiElement
+import org.rust.lang.core.psi.RsImplItem
+import org.rust.lang.core.psi.ext.isBlanketImpl
+import java.awt.event.MouseEvent
+
+/**
+ * Opens a popup with navigation targets that are searched in a background task,
+ * so the popup is shown immediately with a "Searching..." placeholder even in large workspaces.
+ * Blanket impls (`impl<T: Foo> Bar for T`) are shown after other targets.
+ *
+ * [findTargets] is invoked under a read action
+ */
+class RsImplsNavigationHandler(
+    private val title: String,
+    private val findTargets: (PsiElement) -> List<NavigatablePsiElement>
+) : GutterIconNavigationHandler<PsiElement> {
+
+    override fun navigate(e: MouseEvent, element: PsiElement) {
+        val renderer = RsImplTargetRenderer()
+        val comparator = compareBy<PsiElement> { it is RsImplItem && it.isBlanketImpl }
+            .thenBy { (it as? NavigatablePsiElement)?.presentation?.presentableText.orEmpty() }
+
+        val updater = object : BackgroundUpdaterTask(element.project, "Searching...", comparator) {
+            override fun getCaption(size: Int): String = "$title ($size found)"
+
+            override fun run(indicator: ProgressIndicator) {
+                super.run(indicator)
+                val targets = runReadAction { findTargets(element) }
+                for (target in targets) {
+                    ProgressManager.checkCanceled()
+                    if (!updateComponent(target)) {
+                        indicator.cancel()
+                        break
+                    }
+                }
+            }
+        }
+
+        PsiElementListNavigator.openTargets(e, emptyArray(), title, null, renderer, updater)
+    }
+}
+
+private class RsImplTargetRenderer : PsiElementListCellRenderer<NavigatablePsiElement>() {
+    override fun getElementText(element: NavigatablePsiElement): String? = element.presentation?.presentableText
+
+    override fun getContainerText(element: NavigatablePsiElement, name: String?): String? {
+        val location = element.presentation?.locationString
+        return if (element is RsImplItem && element.isBlanketImpl) "blanket impl ${location.orEmpty()}" else location
+    }
+
+    override fun getIconFlags(): Int = 0
+}
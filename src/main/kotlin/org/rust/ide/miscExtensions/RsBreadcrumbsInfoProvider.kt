@@ -12,6 +12,7 @@ import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.RsElement
 import org.rust.lang.core.psi.ext.RsNamedElement
 import org.rust.lang.core.psi.ext.RsStructOrEnumItemElement
+import org.rust.lang.core.psi.ext.macroName
 import org.rust.lang.core.psi.ext.skipParens
 
 class RsBreadcrumbsInfoProvider : BreadcrumbsProvider {
@@ -27,6 +28,7 @@ class RsBreadcrumbsInfoProvider : BreadcrumbsProvider {
         RsImplHandler,
         RsBlockExprHandler,
         RsMacroHandler,
+        RsMacroCallHandler,
         RsFunctionHandler,
         RsIfHandler,
         RsElseHandler,
@@ -84,6 +86,12 @@ class RsBreadcrumbsInfoProvider : BreadcrumbsProvider {
         override fun elementInfo(e: RsMacro): String = e.name.let { "$it!" }
     }
 
+    private object RsMacroCallHandler : RsElementHandler<RsMacroCall> {
+        override fun accepts(e: PsiElement): Boolean = e is RsMacroCall
+
+        override fun elementInfo(e: RsMacroCall): String = "${e.macroName}!"
+    }
+
     private object RsFunctionHandler : RsElementHandler<RsFunction> {
         override fun accepts(e: PsiElement): Boolean = e is RsFunction
 
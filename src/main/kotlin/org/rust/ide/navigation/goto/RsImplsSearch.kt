@@ -10,12 +10,14 @@ import com.intellij.openapi.util.Condition
 import com.intellij.psi.PsiElement
 import com.intellij.psi.search.searches.DefinitionsScopedSearch
 import com.intellij.psi.util.PsiTreeUtil
+import com.intellij.util.CollectionQuery
 import com.intellij.util.Processor
 import org.rust.lang.core.psi.RsEnumItem
 import org.rust.lang.core.psi.RsStructItem
 import org.rust.lang.core.psi.RsTraitItem
 import org.rust.lang.core.psi.ext.RsAbstractable
 import org.rust.lang.core.psi.ext.RsAbstractableOwner
+import org.rust.lang.core.psi.ext.findImplementations
 import org.rust.lang.core.psi.ext.owner
 import org.rust.lang.core.psi.ext.searchForImplementations
 import org.rust.openapiext.filterQuery
@@ -27,7 +29,7 @@ class RsImplsSearch : QueryExecutorBase<PsiElement, DefinitionsScopedSearch.Sear
         val query = when (val psi = queryParameters.element) {
             is RsStructItem -> psi.searchForImplementations()
             is RsEnumItem -> psi.searchForImplementations()
-            is RsTraitItem -> psi.searchForImplementations()
+            is RsTraitItem -> CollectionQuery(psi.findImplementations())
             is RsAbstractable -> {
                 val owner = psi.owner as? RsAbstractableOwner.Trait ?: return
                 owner.trait
@@ -20,47 +20,65 @@ import com.intellij.util.Processor
 import com.intellij.util.indexing.FindSymbolParameters
 import com.intellij.util.indexing.IdFilter
 import org.rust.ide.search.RsWithMacrosScope
+import org.rust.lang.core.macros.MacroExpansionMode
 import org.rust.lang.core.macros.findMacroCallExpandedFrom
+import org.rust.lang.core.macros.macroExpansionManager
 import org.rust.lang.core.psi.ext.RsNamedElement
 import org.rust.lang.core.psi.ext.RsQualifiedNamedElement
 import org.rust.lang.core.psi.ext.contextualFile
 import org.rust.lang.core.psi.ext.qualifiedName
 import java.util.*
 
+/**
+ * @param additionalIndexKeys indices with additional names of the elements, e.g. `#[doc(alias = "...")]` values
+ */
 abstract class RsNavigationContributorBase<T> protected constructor(
     private val indexKey: StubIndexKey<String, T>,
-    private val clazz: Class<T>
+    private val clazz: Class<T>,
+    private val additionalIndexKeys: List<StubIndexKey<String, T>> = emptyList()
 ) : ChooseByNameContributorEx,
     GotoClassContributor where T : NavigationItem, T : RsNamedElement {
 
     override fun processNames(processor: Processor<in String>, scope: GlobalSearchScope, filter: IdFilter?) {
         checkFilter(filter)
-        StubIndex.getInstance().processAllKeys(
-            indexKey,
-            processor,
-            scope.withMacrosScope(),
-            null // see `checkFilter`
-        )
+        val project = scope.project
+        val searchScope = if (project != null && project.isMacroExpansionEnabled) scope.withMacrosScope() else scope
+        for (key in listOf(indexKey) + additionalIndexKeys) {
+            val shouldContinue = StubIndex.getInstance().processAllKeys(
+                key,
+                processor,
+                searchScope,
+                null // see `checkFilter`
+            )
+            if (!shouldContinue) return
+        }
     }
 
     override fun processElementsWithName(name: String, processor: Processor<in NavigationItem>, parameters: FindSymbolParameters) {
         checkFilter(parameters.idFilter)
         val originScope = parameters.searchScope
-        StubIndex.getInstance().processElements(
-            indexKey,
-            name,
-            parameters.project,
-            originScope.withMacrosScope(),
-            null, // see `checkFilter`
-            clazz
-        ) { element ->
-            // Filter out elements expanded from macros that are not in the scope
-            val macroVFile = element.findMacroCallExpandedFrom()?.contextualFile?.originalFile?.virtualFile
-            if (macroVFile == null || macroVFile in originScope) {
-                processor.process(element)
-            } else {
-                true
+        // Items expanded from macros are shown only if macro expansion is enabled in the settings
+        val isMacroExpansionEnabled = parameters.project.isMacroExpansionEnabled
+        val searchScope = if (isMacroExpansionEnabled) originScope.withMacrosScope() else originScope
+        for (key in listOf(indexKey) + additionalIndexKeys) {
+            val shouldContinue = StubIndex.getInstance().processElements(
+                key,
+                name,
+                parameters.project,
+                searchScope,
+                null, // see `checkFilter`
+                clazz
+            ) { element ->
+                val macroCall = element.findMacroCallExpandedFrom()
+                // Filter out elements expanded from macros that are not in the scope
+                val macroVFile = macroCall?.contextualFile?.originalFile?.virtualFile
+                when {
+                    macroCall == null -> processor.process(element)
+                    isMacroExpansionEnabled && macroVFile != null && macroVFile in originScope -> processor.process(element)
+                    else -> true
+                }
             }
+            if (!shouldContinue) return
         }
     }
 
@@ -84,6 +102,9 @@ abstract class RsNavigationContributorBase<T> protected constructor(
     override fun getQualifiedNameSeparator(): String = "::"
 }
 
+private val Project.isMacroExpansionEnabled: Boolean
+    get() = macroExpansionManager.macroExpansionMode !is MacroExpansionMode.Disabled
+
 private fun GlobalSearchScope.withMacrosScope(): GlobalSearchScope {
     val project = project
     return if (project != null && this !is EverythingGlobalScope) RsWithMacrosScope(project, this) else this
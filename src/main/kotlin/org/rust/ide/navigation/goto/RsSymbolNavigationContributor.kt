@@ -6,7 +6,11 @@
 package org.rust.ide.navigation.goto
 
 import org.rust.lang.core.psi.ext.RsNamedElement
+import org.rust.lang.core.stubs.index.RsDocAliasIndex
 import org.rust.lang.core.stubs.index.RsNamedElementIndex
 
-class RsSymbolNavigationContributor
-    : RsNavigationContributorBase<RsNamedElement>(RsNamedElementIndex.KEY, RsNamedElement::class.java)
+class RsSymbolNavigationContributor : RsNavigationContributorBase<RsNamedElement>(
+    RsNamedElementIndex.KEY,
+    RsNamedElement::class.java,
+    listOf(RsDocAliasIndex.KEY)
+)
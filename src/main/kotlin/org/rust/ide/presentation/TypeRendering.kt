@@ -8,16 +8,20 @@ package org.rust.ide.presentation
 import org.rust.lang.core.psi.RsTraitItem
 import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.types.BoundElement
+import org.rust.lang.core.types.borrowck.closureKind
 import org.rust.lang.core.types.consts.Const
 import org.rust.lang.core.types.consts.CtConstParameter
+import org.rust.lang.core.types.consts.CtUnevaluated
 import org.rust.lang.core.types.consts.CtUnknown
 import org.rust.lang.core.types.consts.CtValue
+import org.rust.lang.core.types.infer.hasCtInfer
 import org.rust.lang.core.types.regions.ReEarlyBound
 import org.rust.lang.core.types.regions.ReStatic
 import org.rust.lang.core.types.regions.ReUnknown
 import org.rust.lang.core.types.regions.Region
 import org.rust.lang.core.types.ty.*
 import org.rust.lang.core.types.type
+import org.rust.lang.utils.evaluation.ConstExpr
 import org.rust.stdext.withPrevious
 
 private const val MAX_SHORT_TYPE_LEN = 50
@@ -33,7 +37,8 @@ fun Ty.render(
     includeTypeArguments: Boolean = true,
     includeLifetimeArguments: Boolean = false,
     useAliasNames: Boolean = false,
-    skipUnchangedDefaultTypeArguments: Boolean = false
+    skipUnchangedDefaultTypeArguments: Boolean = false,
+    renderClosuresAsTraits: Boolean = false
 ): String = TypeRenderer(
     unknown = unknown,
     anonymous = anonymous,
@@ -44,7 +49,8 @@ fun Ty.render(
     includeTypeArguments = includeTypeArguments,
     includeLifetimeArguments = includeLifetimeArguments,
     useAliasNames = useAliasNames,
-    skipUnchangedDefaultTypeArguments = skipUnchangedDefaultTypeArguments
+    skipUnchangedDefaultTypeArguments = skipUnchangedDefaultTypeArguments,
+    renderClosuresAsTraits = renderClosuresAsTraits
 ).render(this, level)
 
 fun Ty.renderInsertionSafe(
@@ -63,7 +69,8 @@ fun Ty.renderInsertionSafe(
     includeTypeArguments = includeTypeArguments,
     includeLifetimeArguments = includeLifetimeArguments,
     useAliasNames = useAliasNames,
-    skipUnchangedDefaultTypeArguments = skipUnchangedDefaultTypeArguments
+    skipUnchangedDefaultTypeArguments = skipUnchangedDefaultTypeArguments,
+    renderClosuresAsTraits = false
 ).render(this, level)
 
 val Ty.shortPresentableText: String
@@ -74,6 +81,15 @@ val Ty.shortPresentableText: String
             cur != prev && (prev == null || cur.length <= MAX_SHORT_TYPE_LEN)
         }.last().first
 
+/**
+ * Type arguments of the ADT without the trailing ones which are equal to the defaults of their type parameters,
+ * e.g. `HashMap<K, V>` for `HashMap<K, V, RandomState>`
+ */
+val TyAdt.typeArgumentsWithoutUnchangedDefaults: List<Ty>
+    get() = item.typeParameters.zip(typeArguments).dropLastWhile { (param, argument) ->
+        param.typeReference != null && param.typeReference?.type == argument
+    }.map { (_, argument) -> argument }
+
 private data class TypeRenderer(
     val unknown: String,
     val anonymous: String,
@@ -84,7 +100,9 @@ private data class TypeRenderer(
     val includeTypeArguments: Boolean,
     val includeLifetimeArguments: Boolean,
     val useAliasNames: Boolean,
-    val skipUnchangedDefaultTypeArguments: Boolean
+    val skipUnchangedDefaultTypeArguments: Boolean,
+    /** Closures have unnamed types, so `impl Fn(i32) -> bool` is rendered instead of a function pointer type */
+    val renderClosuresAsTraits: Boolean
 ) {
     fun render(ty: Ty, level: Int): String {
         require(level >= 0)
@@ -110,7 +128,15 @@ private data class TypeRenderer(
         }
 
         return when (ty) {
-            is TyFunction -> formatFnLike("fn", ty.paramTypes, ty.retType, render)
+            is TyFunction -> {
+                val closure = ty.closure
+                val fnType = if (renderClosuresAsTraits && closure != null) {
+                    "impl ${closure.closureKind.traitName}"
+                } else {
+                    "fn"
+                }
+                formatFnLike(fnType, ty.paramTypes, ty.retType, render)
+            }
             is TySlice -> "[${render(ty.elementType)}]"
 
             is TyTuple -> ty.types.joinToString(", ", "(", ")", transform = render)
@@ -172,9 +198,40 @@ private data class TypeRenderer(
         when (const) {
             is CtValue -> const.toString()
             is CtConstParameter -> if (wrapParameterInBraces) "{ $const }" else const.toString()
+            // Symbolic expressions over const parameters, e.g. `N * 2`
+            is CtUnevaluated -> if (const.hasCtInfer) {
+                unknownConst
+            } else {
+                val expr = render(const.expr)
+                if (wrapParameterInBraces) "{ $expr }" else expr
+            }
             else -> unknownConst
         }
 
+    private fun render(expr: ConstExpr<*>): String =
+        when (expr) {
+            is ConstExpr.Constant -> render(expr.const)
+            is ConstExpr.Value -> expr.toString()
+            is ConstExpr.Unary -> when (expr.operator) {
+                UnaryOperator.MINUS -> "-${renderOperand(expr.expr)}"
+                UnaryOperator.NOT -> "!${renderOperand(expr.expr)}"
+                else -> unknownConst
+            }
+            is ConstExpr.Binary -> {
+                val sign = when (val operator = expr.operator) {
+                    is OverloadableBinaryOperator -> operator.sign
+                    LogicOp.AND -> "&&"
+                    LogicOp.OR -> "||"
+                    else -> return unknownConst
+                }
+                "${renderOperand(expr.left)} $sign ${renderOperand(expr.right)}"
+            }
+            is ConstExpr.Error -> unknownConst
+        }
+
+    private fun renderOperand(expr: ConstExpr<*>): String =
+        if (expr is ConstExpr.Binary) "(${render(expr)})" else render(expr)
+
     private fun formatFnLike(fnType: String, paramTypes: List<Ty>, retType: Ty, render: (Ty) -> String): String =
         buildString {
             paramTypes.joinTo(this, ", ", "$fnType(", ")", transform = render)
@@ -207,9 +264,7 @@ private data class TypeRenderer(
         val typeArguments = adt.typeArguments
 
         val typeArgumentNames = if (skipUnchangedDefaultTypeArguments) {
-            adt.item.typeParameters.zip(typeArguments).filter { (param, argument) ->
-                param.typeReference == null || param.typeReference?.type != argument
-            }.map { (_, argument) -> render(argument) }
+            adt.typeArgumentsWithoutUnchangedDefaults.map(render)
         } else {
             typeArguments.map(render)
         }
@@ -0,0 +1,55 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.presentation
+
+import com.intellij.ide.util.PropertiesComponent
+import org.rust.lang.core.types.ty.Ty
+
+/**
+ * Detail levels of types shown by "Show Expression Type" action.
+ * Repeated invocation of the action cycles through them, and the last shown level
+ * is also used by "Specify type explicitly" intention
+ */
+enum class TypeRenderingLevel(
+    val useAliasNames: Boolean,
+    val skipUnchangedDefaultTypeArguments: Boolean,
+    val includeLifetimeArguments: Boolean
+) {
+    /** Type aliases are kept and default type arguments are omitted, e.g. `Result<String>` for `io::Result<String>` */
+    SHORT(useAliasNames = true, skipUnchangedDefaultTypeArguments = true, includeLifetimeArguments = false),
+
+    /** Type aliases are expanded and default type arguments are shown, e.g. `Result<String, Error>` */
+    EXPANDED(useAliasNames = false, skipUnchangedDefaultTypeArguments = false, includeLifetimeArguments = false),
+
+    /** Like [EXPANDED] but with lifetimes, e.g. `Ref<'a, Vec<i32>>` */
+    FULL(useAliasNames = false, skipUnchangedDefaultTypeArguments = false, includeLifetimeArguments = true);
+
+    val next: TypeRenderingLevel get() = values()[(ordinal + 1) % values().size]
+
+    fun render(ty: Ty): String = ty.render(
+        useAliasNames = useAliasNames,
+        skipUnchangedDefaultTypeArguments = skipUnchangedDefaultTypeArguments,
+        includeLifetimeArguments = includeLifetimeArguments,
+        renderClosuresAsTraits = true
+    )
+
+    fun renderInsertionSafe(ty: Ty): String = ty.renderInsertionSafe(
+        useAliasNames = useAliasNames,
+        skipUnchangedDefaultTypeArguments = skipUnchangedDefaultTypeArguments,
+        includeLifetimeArguments = includeLifetimeArguments
+    )
+
+    companion object {
+        private const val LAST_USED_LEVEL_KEY: String = "org.rust.type.rendering.level"
+
+        var lastUsed: TypeRenderingLevel
+            get() {
+                val name = PropertiesComponent.getInstance().getValue(LAST_USED_LEVEL_KEY) ?: return SHORT
+                return values().find { it.name == name } ?: SHORT
+            }
+            set(value) = PropertiesComponent.getInstance().setValue(LAST_USED_LEVEL_KEY, value.name, SHORT.name)
+    }
+}
@@ -8,6 +8,7 @@ package org.rust.ide.presentation
 import com.intellij.ide.projectView.PresentationData
 import com.intellij.navigation.ItemPresentation
 import org.rust.ide.colors.RsColor
+import org.rust.ide.icons.addGeneratedMark
 import org.rust.ide.icons.addVisibilityIcon
 import org.rust.lang.core.macros.isExpandedFromMacro
 import org.rust.lang.core.psi.*
@@ -24,7 +25,7 @@ fun getPresentation(psi: RsElement): ItemPresentation {
     return PresentationData(name, location, psi.getIcon(0), null)
 }
 
-fun getPresentationForStructure(psi: RsElement): ItemPresentation {
+fun getPresentationForStructure(psi: RsElement): PresentationData {
     val presentation = buildString {
         fun appendCommaList(xs: List<String>) {
             append('(')
@@ -56,12 +57,16 @@ fun getPresentationForStructure(psi: RsElement): ItemPresentation {
             }
         }
     }
+    val isExpandedFromMacro = psi.isExpandedFromMacro
     var icon = psi.getIcon(0)
+    if (isExpandedFromMacro) {
+        icon = icon.addGeneratedMark()
+    }
     if ((psi as? RsVisibilityOwner)?.isPublic == true) {
         icon = icon.addVisibilityIcon(true)
     }
 
-    val textAttributes = if (psi.isExpandedFromMacro) RsColor.GENERATED_ITEM.textAttributesKey else null
+    val textAttributes = if (isExpandedFromMacro) RsColor.GENERATED_ITEM.textAttributesKey else null
 
     return PresentationData(presentation, null, icon, textAttributes)
 }
@@ -5,12 +5,16 @@
 
 package org.rust.ide.refactoring
 
+import com.intellij.application.options.CodeStyle
 import com.intellij.lang.ImportOptimizer
 import com.intellij.psi.PsiDocumentManager
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiFile
 import com.intellij.psi.PsiWhiteSpace
 import org.rust.cargo.project.workspace.PackageOrigin
+import org.rust.ide.formatter.settings.ImportsGrouping
+import org.rust.ide.formatter.settings.RsCodeStyleSettings
+import org.rust.ide.utils.mergeUseItems
 import org.rust.lang.core.psi.ext.asTrivial
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
@@ -78,23 +82,19 @@ class RsImportOptimizer : ImportOptimizer {
             val first = mod.childrenOfType<RsElement>()
                 .firstOrNull { it.textOffset >= offset && it !is RsExternCrateItem && it !is RsAttr } ?: return
             val psiFactory = RsPsiFactory(mod.project)
-            val sortedUsesGroups = uses
-                .map { UseItemWrapper(it) }
-                .groupBy({ it.packageGroupLevel }, { it })
-                .map { (groupLevel, useItemWrapper) ->
-                    val useItems = useItemWrapper
-                        .sortedBy { it.useSpeckText }
-                        .mapNotNull { it.useItem.copy() as? RsUseItem }
-                    groupLevel to useItems
+            val settings = CodeStyle.getCustomSettings(mod.containingFile, RsCodeStyleSettings::class.java)
+            val sortedUsesGroups = groupUseItems(uses.toList(), settings.importsGrouping)
+                .map { group ->
+                    mergeUseItems(psiFactory, group, settings.importsGranularity)
+                        .sortedBy { it.useSpeck?.pathText }
                 }
-                .sortedBy { it.first }
-            sortedUsesGroups.forEach { (_, group) ->
+            sortedUsesGroups.forEach { group ->
                 group
                     .mapNotNull { it.useSpeck }
                     .forEach { optimizeUseSpeck(psiFactory, it) }
             }
 
-            for ((_, sortedUses) in sortedUsesGroups) {
+            for (sortedUses in sortedUsesGroups) {
                 var lastAddedUseItem: PsiElement? = null
                 for (importPath in sortedUses) {
                     lastAddedUseItem = mod.addBefore(importPath, first)
@@ -107,6 +107,42 @@ class RsImportOptimizer : ImportOptimizer {
                 it.delete()
             }
         }
+
+        private fun groupUseItems(uses: List<RsUseItem>, grouping: ImportsGrouping): List<List<RsUseItem>> =
+            when (grouping) {
+                ImportsGrouping.BY_ORIGIN -> uses
+                    .map { UseItemWrapper(it) }
+                    .groupBy { it.packageGroupLevel }
+                    .toSortedMap()
+                    .values
+                    .map { group -> group.map { it.useItem } }
+                ImportsGrouping.STD_EXTERNAL_CRATE -> uses
+                    .map { UseItemWrapper(it) }
+                    .groupBy { it.stdExternalCrateGroupLevel }
+                    .toSortedMap()
+                    .values
+                    .map { group -> group.map { it.useItem } }
+                ImportsGrouping.PRESERVE -> splitByBlankLines(uses)
+            }
+
+        /** Use items separated by blank lines or by other elements belong to different groups */
+        private fun splitByBlankLines(uses: List<RsUseItem>): List<List<RsUseItem>> {
+            val groups = mutableListOf<MutableList<RsUseItem>>()
+            for (useItem in uses) {
+                val lastGroup = groups.lastOrNull()
+                val whitespace = useItem.prevSibling as? PsiWhiteSpace
+                val isNextInGroup = lastGroup != null &&
+                    whitespace != null &&
+                    whitespace.prevSibling == lastGroup.last() &&
+                    whitespace.text.count { it == '\n' } < 2
+                if (isNextInGroup) {
+                    lastGroup!! += useItem
+                } else {
+                    groups += mutableListOf(useItem)
+                }
+            }
+            return groups
+        }
     }
 }
 
@@ -115,8 +151,6 @@ private val RsUseSpeck.pathText get() = path?.text?.toLowerCase()
 private class UseItemWrapper(val useItem: RsUseItem) {
     private val basePath: RsPath? = useItem.useSpeck?.path?.basePath()
 
-    val useSpeckText: String? = useItem.useSpeck?.pathText
-
     // `use` order:
     // 1. Standard library (stdlib)
     // 2. Related third party (extern crate)
@@ -137,4 +171,15 @@ private class UseItemWrapper(val useItem: RsUseItem) {
             else -> 3
         }
     }
+
+    // `group_imports = "StdExternalCrate"` order of rustfmt:
+    // 1. std, core and alloc
+    // 2. Other external crates
+    // 3. `crate::`, `super::` and `self::`
+    val stdExternalCrateGroupLevel: Int
+        get() = when (packageGroupLevel) {
+            1 -> 1
+            2, 3 -> 2
+            else -> 3
+        }
 }
@@ -13,6 +13,9 @@ import org.rust.ide.refactoring.introduceConstant.RsIntroduceConstantHandler
 import org.rust.ide.refactoring.introduceParameter.RsIntroduceParameterHandler
 import org.rust.ide.refactoring.introduceVariable.RsIntroduceVariableHandler
 import org.rust.lang.core.macros.isExpandedFromMacro
+import org.rust.lang.core.psi.RsEnumVariant
+import org.rust.lang.core.psi.RsNamedFieldDecl
+import org.rust.lang.core.psi.ext.RsItemElement
 import org.rust.lang.core.psi.ext.RsNameIdentifierOwner
 
 class RsRefactoringSupportProvider : RefactoringSupportProvider() {
@@ -20,6 +23,10 @@ class RsRefactoringSupportProvider : RefactoringSupportProvider() {
     override fun isMemberInplaceRenameAvailable(element: PsiElement, context: PsiElement?): Boolean =
         element is RsNameIdentifierOwner && !element.isExpandedFromMacro
 
+    override fun isSafeDeleteAvailable(element: PsiElement): Boolean =
+        (element is RsItemElement || element is RsNamedFieldDecl || element is RsEnumVariant) &&
+            !element.isExpandedFromMacro
+
     override fun getIntroduceVariableHandler(): RefactoringActionHandler = RsIntroduceVariableHandler()
 
     // needed this one too to get it to show up in the dialog.
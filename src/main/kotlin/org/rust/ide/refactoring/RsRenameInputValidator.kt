@@ -0,0 +1,27 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.patterns.ElementPattern
+import com.intellij.patterns.PlatformPatterns.psiElement
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import com.intellij.refactoring.rename.RenameInputValidator
+import com.intellij.util.ProcessingContext
+import org.rust.lang.core.psi.escapeIdentifierIfNeeded
+import org.rust.lang.core.psi.ext.RsNamedElement
+
+/**
+ * Allows renaming to a keyword (e.g. `match`) since [RsRenameProcessor] writes it as a raw identifier (`r#match`).
+ * Files are validated by the platform
+ */
+class RsRenameInputValidator : RenameInputValidator {
+    override fun getPattern(): ElementPattern<out PsiElement> =
+        psiElement(RsNamedElement::class.java).andNot(psiElement(PsiFile::class.java))
+
+    override fun isInputValid(newName: String, element: PsiElement, context: ProcessingContext): Boolean =
+        RsNamesValidator.isIdentifier(newName) || RsNamesValidator.isIdentifier(newName.escapeIdentifierIfNeeded())
+}
@@ -15,6 +15,7 @@ import com.intellij.refactoring.listeners.RefactoringElementListener
 import com.intellij.refactoring.rename.RenameDialog
 import com.intellij.refactoring.rename.RenamePsiElementProcessor
 import com.intellij.usageView.UsageInfo
+import com.intellij.util.containers.MultiMap
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
 
@@ -38,7 +39,10 @@ class RsRenameProcessor : RenamePsiElementProcessor() {
                 val field = it.element?.ancestorOrSelf<RsStructLiteralField>(RsBlock::class.java) ?: return@forEach
                 when {
                     field.colon == null -> {
-                        val newPatField = psiFactory.createStructLiteralField(element.text, newName)
+                        val newPatField = psiFactory.createStructLiteralField(
+                            element.text,
+                            newName.escapeIdentifierIfNeeded(field.edition)
+                        )
                         field.replace(newPatField)
                     }
                     field.referenceName == newName && field.expr is RsPathExpr -> {
@@ -60,10 +64,25 @@ class RsRenameProcessor : RenamePsiElementProcessor() {
         if (element is RsLifetime || element is RsLifetimeParameter || element is RsLabel || element is RsLabelDecl) {
             allRenames.put(element, newName.ensureQuote())
         } else {
-            allRenames.put(element, newName.trimStart('\''))
+            // The name is escaped with `r#` (if needed) separately in each usage since they can
+            // belong to crates of different editions
+            allRenames.put(element, newName.trimStart('\'').unescapeIdentifier())
         }
     }
 
+    override fun findExistingNameConflicts(element: PsiElement, newName: String, conflicts: MultiMap<PsiElement, String>) {
+        val name = newName.trimStart('\'')
+        if (!name.requiresRawIdentifier(element.edition)) return
+        val kind = when (element) {
+            is RsMacro -> "a macro name"
+            is RsExternCrateItem -> "a crate name"
+            is RsLifetime, is RsLifetimeParameter -> "a lifetime name"
+            is RsLabel, is RsLabelDecl -> "a label name"
+            else -> return
+        }
+        conflicts.putValue(element, "`$name` is a keyword and can't be used as $kind")
+    }
+
     override fun substituteElementToRename(element: PsiElement, editor: Editor?): PsiElement =
         (element as? RsAbstractable)?.superItem ?: element
 
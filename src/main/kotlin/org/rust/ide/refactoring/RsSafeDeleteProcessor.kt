@@ -0,0 +1,258 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.openapi.application.ApplicationManager
+import com.intellij.openapi.module.Module
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.Messages
+import com.intellij.openapi.util.Condition
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiWhiteSpace
+import com.intellij.psi.search.searches.ReferencesSearch
+import com.intellij.psi.util.PsiTreeUtil
+import com.intellij.refactoring.RefactoringSettings
+import com.intellij.refactoring.safeDelete.NonCodeUsageSearchInfo
+import com.intellij.refactoring.safeDelete.SafeDeleteProcessorDelegateBase
+import com.intellij.refactoring.safeDelete.usageInfo.SafeDeleteReferenceSimpleDeleteUsageInfo
+import com.intellij.usageView.UsageInfo
+import org.rust.lang.core.macros.findNavigationTargetIfMacroExpansion
+import org.rust.lang.core.macros.isExpandedFromMacro
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.RsElementTypes.COMMA
+import org.rust.lang.core.psi.RsElementTypes.RBRACE
+import org.rust.lang.core.psi.ext.*
+
+/**
+ * Safe delete for Rust items, fields, enum variants and module files.
+ *
+ * Besides plain usages, which are reported as unsafe, it:
+ * - removes `use` items and use group members which import deleted elements;
+ * - removes `mod` declarations of deleted module files;
+ * - deletes implementations of deleted trait members and reports impls which
+ *   lose an implementation of a required trait member.
+ */
+class RsSafeDeleteProcessor : SafeDeleteProcessorDelegateBase() {
+
+    override fun handlesElement(element: PsiElement): Boolean =
+        element is RsFile || element is RsItemElement || element is RsNamedFieldDecl || element is RsEnumVariant
+
+    override fun getElementsToSearch(
+        element: PsiElement,
+        module: Module?,
+        allElementsToDelete: Collection<PsiElement>
+    ): Collection<PsiElement> = listOf(element)
+
+    override fun findUsages(
+        element: PsiElement,
+        allElementsToDelete: Array<out PsiElement>,
+        result: MutableList<UsageInfo>
+    ): NonCodeUsageSearchInfo {
+        val isInsideDeleted = Condition<PsiElement> { isInsideDeleted(it, allElementsToDelete) }
+        for (target in targetsToSearch(element)) {
+            for (reference in ReferencesSearch.search(target).findAll()) {
+                val usage = reference.element
+                if (!isInsideDeleted.value(usage)) {
+                    result += createUsageInfo(usage, element)
+                }
+            }
+        }
+        return NonCodeUsageSearchInfo(isInsideDeleted, element)
+    }
+
+    override fun getAdditionalElementsToDelete(
+        element: PsiElement,
+        allElementsToDelete: Collection<PsiElement>,
+        askUser: Boolean
+    ): Collection<PsiElement>? = when (element) {
+        is RsFile -> element.declarations
+        is RsAbstractable -> {
+            val implementations = findImplementations(element).filter { it !in allElementsToDelete }
+            if (implementations.isNotEmpty() && (!askUser || confirmCascadeDeletion(element, implementations.size))) {
+                implementations
+            } else {
+                null
+            }
+        }
+        else -> null
+    }
+
+    override fun findConflicts(element: PsiElement, allElementsToDelete: Array<out PsiElement>): Collection<String>? {
+        val conflicts = mutableListOf<String>()
+        when (element) {
+            is RsAbstractable -> {
+                findLostRequiredMember(element, allElementsToDelete)?.let { conflicts += it }
+                for (implementation in findImplementations(element)) {
+                    if (implementation in allElementsToDelete) continue
+                    val impl = (implementation.owner as? RsAbstractableOwner.Impl)?.impl ?: continue
+                    conflicts += "${element.kindName.capitalize()} `${element.name}` of ${impl.displayText} " +
+                        "would not be a member of the trait"
+                }
+            }
+            is RsNamedFieldDecl -> for (reference in ReferencesSearch.search(element).findAll()) {
+                val usage = reference.element
+                if (isInsideDeleted(usage, allElementsToDelete)) continue
+                val structLiteral = (usage as? RsStructLiteralField)?.parentStructLiteral
+                val patStruct = (usage.parent as? RsPatField)?.ancestorStrict<RsPatStruct>()
+                when {
+                    structLiteral != null ->
+                        conflicts += "Struct literal `${structLiteral.path.text} { .. }` initializes field `${element.name}`"
+                    patStruct != null ->
+                        conflicts += "Pattern `${patStruct.path.text} { .. }` destructures field `${element.name}`"
+                }
+            }
+        }
+        return conflicts
+    }
+
+    override fun preprocessUsages(project: Project, usages: Array<UsageInfo>): Array<UsageInfo> = usages
+
+    override fun prepareForDeletion(element: PsiElement) {
+        when (element) {
+            is RsNamedFieldDecl, is RsEnumVariant -> deleteListSeparator(element)
+            is RsItemElement -> deleteSurroundingWhitespace(element)
+        }
+    }
+
+    override fun isToSearchInComments(element: PsiElement): Boolean =
+        RefactoringSettings.getInstance().SAFE_DELETE_SEARCH_IN_COMMENTS
+
+    override fun setToSearchInComments(element: PsiElement, enabled: Boolean) {
+        RefactoringSettings.getInstance().SAFE_DELETE_SEARCH_IN_COMMENTS = enabled
+    }
+
+    override fun isToSearchForTextOccurrences(element: PsiElement): Boolean =
+        RefactoringSettings.getInstance().SAFE_DELETE_SEARCH_IN_NON_JAVA
+
+    override fun setToSearchForTextOccurrences(element: PsiElement, enabled: Boolean) {
+        RefactoringSettings.getInstance().SAFE_DELETE_SEARCH_IN_NON_JAVA = enabled
+    }
+
+    /** Items of a deleted file become inaccessible too, so their usages from other files are searched as well */
+    private fun targetsToSearch(element: PsiElement): List<PsiElement> {
+        if (element !is RsFile) return listOf(element)
+        val innerElements = element.descendantsOfType<RsNamedElement>()
+            .filter { it is RsItemElement || it is RsNamedFieldDecl || it is RsEnumVariant }
+        return listOf(element) + innerElements
+    }
+
+    /**
+     * Usages in `use` items outside of macro calls are deleted along with the element.
+     * All other usages, including ones in macro calls, are unsafe
+     */
+    private fun createUsageInfo(usage: PsiElement, referencedElement: PsiElement): UsageInfo {
+        val useSpeck = usage.ancestorStrict<RsUseSpeck>()
+        if (useSpeck != null && !usage.isExpandedFromMacro) {
+            return RsSafeDeleteUseSpeckUsageInfo(useSpeck, referencedElement)
+        }
+        val element = usage.findNavigationTargetIfMacroExpansion() ?: usage
+        return SafeDeleteReferenceSimpleDeleteUsageInfo(element, referencedElement, false)
+    }
+
+    private fun isInsideDeleted(usage: PsiElement, allElementsToDelete: Array<out PsiElement>): Boolean {
+        val element = usage.findNavigationTargetIfMacroExpansion() ?: usage
+        return allElementsToDelete.any { PsiTreeUtil.isAncestor(it, element, false) }
+    }
+
+    private fun findImplementations(element: RsAbstractable): List<RsAbstractable> {
+        val trait = (element.owner as? RsAbstractableOwner.Trait)?.trait ?: return emptyList()
+        return trait.searchForImplementations()
+            .mapNotNull { it.findCorrespondingElement(element) }
+            .filter { !it.isExpandedFromMacro }
+    }
+
+    /** Deleting an implementation of a trait member without a default is an error unless the trait member is deleted too */
+    private fun findLostRequiredMember(element: RsAbstractable, allElementsToDelete: Array<out PsiElement>): String? {
+        val impl = (element.owner as? RsAbstractableOwner.Impl)?.impl ?: return null
+        val superItem = element.superItem ?: return null
+        if (!superItem.isAbstract || superItem in allElementsToDelete || impl in allElementsToDelete) return null
+        return "${impl.displayText.capitalize()} would not implement required ${element.kindName} `${element.name}`"
+    }
+
+    private fun confirmCascadeDeletion(element: RsAbstractable, implementationsCount: Int): Boolean {
+        if (ApplicationManager.getApplication().isUnitTestMode) return true
+        val implementations = if (implementationsCount == 1) "its implementation" else "its $implementationsCount implementations"
+        return Messages.showYesNoDialog(
+            element.project,
+            "Do you want to delete $implementations as well?",
+            "Delete ${element.kindName} `${element.name}`",
+            Messages.getQuestionIcon()
+        ) == Messages.YES
+    }
+}
+
+private class RsSafeDeleteUseSpeckUsageInfo(
+    private val useSpeck: RsUseSpeck,
+    referencedElement: PsiElement
+) : SafeDeleteReferenceSimpleDeleteUsageInfo(useSpeck, referencedElement, true) {
+    override fun deleteElement() = deleteUseSpeck(useSpeck)
+}
+
+/** Deletes [useSpeck] and the groups and `use` items which become empty */
+private fun deleteUseSpeck(useSpeck: RsUseSpeck) {
+    if (!useSpeck.isValid) return
+    when (val parent = useSpeck.parent) {
+        is RsUseItem -> {
+            deleteSurroundingWhitespace(parent)
+            parent.delete()
+        }
+        is RsUseGroup -> {
+            val specks = parent.useSpeckList
+            val index = specks.indexOf(useSpeck)
+            when {
+                specks.size == 1 -> deleteUseSpeck(parent.parentUseSpeck)
+                index < specks.lastIndex -> parent.deleteChildRange(useSpeck, specks[index + 1].prevSibling)
+                else -> parent.deleteChildRange(specks[index - 1].nextSibling, useSpeck)
+            }
+        }
+    }
+}
+
+/**
+ * Deletes one of the whitespaces around [element] so that the blank lines which separate
+ * it from its neighbours are kept after its deletion
+ */
+private fun deleteSurroundingWhitespace(element: PsiElement) {
+    val prevWhitespace = (element.prevSibling as? PsiWhiteSpace)?.takeIf { it.prevSibling != null }
+    val nextWhitespace = (element.nextSibling as? PsiWhiteSpace)
+        ?.takeIf { it.nextSibling != null && it.nextSibling.elementType != RBRACE }
+    when {
+        prevWhitespace == null -> (element.nextSibling as? PsiWhiteSpace)?.delete()
+        nextWhitespace == null -> prevWhitespace.delete()
+        prevWhitespace.lineBreaks < nextWhitespace.lineBreaks -> prevWhitespace.delete()
+        else -> nextWhitespace.delete()
+    }
+}
+
+private val PsiWhiteSpace.lineBreaks: Int get() = text.count { it == '\n' }
+
+/** Deletes the comma and the whitespace which separate [element] from its neighbours in a comma separated list */
+private fun deleteListSeparator(element: PsiElement) {
+    val nextComma = element.getNextNonCommentSibling()?.takeIf { it.elementType == COMMA }
+    val prevWhitespace = element.prevSibling as? PsiWhiteSpace
+    if (nextComma != null) {
+        if (prevWhitespace != null && '\n' in prevWhitespace.text) {
+            prevWhitespace.delete()
+        } else {
+            (nextComma.nextSibling as? PsiWhiteSpace)?.delete()
+        }
+        element.parent.deleteChildRange(element.nextSibling, nextComma)
+    } else {
+        val prevComma = element.getPrevNonCommentSibling()?.takeIf { it.elementType == COMMA } ?: return
+        element.parent.deleteChildRange(prevComma, element.prevSibling)
+    }
+}
+
+private val RsImplItem.displayText: String
+    get() = "impl ${traitRef?.text?.let { "$it for " }.orEmpty()}${typeReference?.text.orEmpty()}".let { "`$it`" }
+
+private val RsAbstractable.kindName: String
+    get() = when (this) {
+        is RsFunction -> if (isMethod) "method" else "function"
+        is RsConstant -> "constant"
+        is RsTypeAlias -> "associated type"
+        else -> "member"
+    }
@@ -17,16 +17,10 @@ import com.intellij.usageView.UsageInfo
 import com.intellij.usageView.UsageViewDescriptor
 import org.rust.ide.inspections.import.RsImportHelper
 import org.rust.ide.refactoring.RsInPlaceVariableIntroducer
+import org.rust.ide.refactoring.buildWhereClause
+import org.rust.ide.refactoring.filterTypeParameters
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
-import org.rust.lang.core.types.consts.Const
-import org.rust.lang.core.types.consts.CtConstParameter
-import org.rust.lang.core.types.infer.TypeVisitor
-import org.rust.lang.core.types.infer.hasCtConstParameters
-import org.rust.lang.core.types.infer.hasTyTypeParameters
-import org.rust.lang.core.types.ty.Ty
-import org.rust.lang.core.types.ty.TyTypeParameter
-import org.rust.lang.core.types.type
 
 class RsExtractEnumVariantProcessor(
     project: Project,
@@ -220,130 +214,6 @@ private class StructVariant(fields: RsBlockFields, factory: RsPsiFactory) : Vari
     }
 }
 
-private data class CollectTypeParametersVisitor(
-    val parameters: Map<String, RsTypeParameter>,
-    val collected: MutableSet<RsTypeParameter>
-) : RsRecursiveVisitor() {
-    override fun visitTypeReference(ref: RsTypeReference) {
-        super.visitTypeReference(ref)
-        val type = ref.type as? TyTypeParameter ?: return
-        val parameter = parameters[type.name] ?: return
-        collected.add(parameter)
-        parameter.bounds.forEach { bound ->
-            bound.accept(this)
-        }
-    }
-}
-
-private fun gatherTypeParameters(
-    references: List<RsTypeReference>,
-    parameters: List<RsTypeParameter>
-): List<RsTypeParameter> {
-    val parameterMap = parameters.filter { it.name != null }.associateBy { it.name!! }
-    val collected = mutableSetOf<RsTypeParameter>()
-    for (ref in references) {
-        ref.accept(CollectTypeParametersVisitor(parameterMap, collected))
-    }
-    return collected.sortedBy { parameters.indexOf(it) }
-}
-
-private data class CollectLifetimesVisitor(
-    val parameters: Map<String, RsTypeParameter>,
-    val lifetimeMap: Map<String, RsLifetimeParameter>,
-    val collected: MutableSet<RsLifetimeParameter>
-) : RsRecursiveVisitor() {
-
-    override fun visitTypeReference(ref: RsTypeReference) {
-        super.visitTypeReference(ref)
-        val type = ref.type as? TyTypeParameter ?: return
-        val parameter = parameters[type.name] ?: return
-        parameter.bounds.forEach { bound ->
-            bound.accept(this)
-        }
-    }
-
-    override fun visitLifetime(lifetime: RsLifetime) {
-        super.visitLifetime(lifetime)
-        val parameter = lifetimeMap[lifetime.name] ?: return
-        if (parameter !in collected) {
-            collected.add(parameter)
-            parameter.accept(this)
-        }
-    }
-}
-
-private fun gatherLifetimes(
-    references: List<RsTypeReference>,
-    lifetimes: List<RsLifetimeParameter>,
-    parameters: List<RsTypeParameter>
-): List<RsLifetimeParameter> {
-    val parameterMap = parameters.filter { it.name != null }.associateBy { it.name!! }
-    val lifetimeMap = lifetimes.filter { it.name != null }.associateBy { it.name!! }
-    val collected = mutableSetOf<RsLifetimeParameter>()
-
-    for (ref in references) {
-        ref.accept(CollectLifetimesVisitor(parameterMap, lifetimeMap, collected))
-    }
-
-    return collected.sortedBy { lifetimes.indexOf(it) }
-}
-
-
-private data class HasConstParameterVisitor(val parameter: RsConstParameter) : TypeVisitor {
-
-    override fun visitTy(ty: Ty): Boolean =
-        if (ty.hasCtConstParameters) ty.superVisitWith(this) else false
-
-    override fun visitConst(const: Const): Boolean =
-        when {
-            const is CtConstParameter -> const.parameter == parameter
-            const.hasCtConstParameters -> const.superVisitWith(this)
-            else -> false
-        }
-}
-
-private data class HasTypeParameterVisitor(
-    val parameters: Map<String, RsTypeParameter>,
-    val ref: RsTypeReference
-) : TypeVisitor {
-    override fun visitTy(ty: Ty): Boolean =
-        when {
-            ty is TyTypeParameter -> ty.name in parameters
-            ty.hasTyTypeParameters -> ty.superVisitWith(this)
-            else -> false
-        }
-}
-
-private data class Parameters(
-    val lifetimes: List<RsLifetimeParameter> = emptyList(),
-    val typeParameters: List<RsTypeParameter> = emptyList(),
-    val constParameters: List<RsConstParameter> = emptyList()
-) {
-    fun format(): String {
-        val all = lifetimes + typeParameters + constParameters
-        return if (all.isNotEmpty()) {
-            all.joinToString(", ", prefix = "<", postfix = ">") { it.text }
-        } else {
-            ""
-        }
-    }
-}
-
-private fun filterTypeParameters(
-    references: List<RsTypeReference>,
-    parameters: RsTypeParameterList?
-): Parameters {
-    if (parameters == null) return Parameters()
-    val typeParameters = gatherTypeParameters(references, parameters.typeParameterList)
-    val lifetimes = gatherLifetimes(references, parameters.lifetimeParameterList, typeParameters)
-    val constParameters = parameters.constParameterList
-        .filter { param -> references.any { matchesConstParameter(it, param) } }
-    return Parameters(lifetimes, typeParameters, constParameters)
-}
-
-private fun matchesConstParameter(ref: RsTypeReference, parameter: RsConstParameter): Boolean =
-    ref.type.visitWith(HasConstParameterVisitor(parameter))
-
 private fun offerStructRename(
     project: Project,
     editor: Editor,
@@ -369,60 +239,5 @@ private fun createElement(variant: RsEnumVariant, factory: RsPsiFactory): Varian
     }
 }
 
-private fun buildWhereClause(whereClause: RsWhereClause?, parameters: Parameters): String {
-    val where = whereClause ?: return ""
-    if (where.wherePredList.isEmpty()) return ""
-
-    val parameterMap = parameters.typeParameters.filter { it.name != null }.associateBy { it.name!! }
-    val lifetimeMap = parameters.lifetimes.filter { it.name != null }.associateBy { it.name!! }
-    val predicates = where.wherePredList.mapNotNull { predicate ->
-        val typeRef = predicate.typeReference
-        if (typeRef != null && hasTypeParameter(typeRef, parameterMap)) {
-            return@mapNotNull predicate.text
-        }
-
-        val lifetime = predicate.lifetime
-        if (lifetime != null) {
-            return@mapNotNull createLifetimePredicate(
-                predicate,
-                lifetime,
-                predicate.lifetimeParamBounds,
-                lifetimeMap
-            )
-        }
-
-        null
-    }
-
-    return if (predicates.isNotEmpty()) {
-        predicates.joinToString(separator = ",", prefix = " where ")
-    } else {
-        ""
-    }
-}
-
-/**
- * Create a predicate if the lifetime is in the map and at least one of its bounds is in the map.
- * Bounds that are not in the map are removed.
- */
-private fun createLifetimePredicate(
-    predicate: RsWherePred,
-    lifetime: RsLifetime,
-    lifetimeParamBounds: RsLifetimeParamBounds?,
-    lifetimeMap: Map<String, RsLifetimeParameter>
-): String? {
-    if (lifetime.name !in lifetimeMap) return null
-    if (lifetimeParamBounds == null) return predicate.text
-    val bounds = lifetimeParamBounds.lifetimeList.filter { it.name in lifetimeMap }
-    return if (bounds.isNotEmpty()) {
-        "${lifetime.text}: ${bounds.joinToString(" + ") { it.text }}"
-    } else {
-        null
-    }
-}
-
-private fun hasTypeParameter(ref: RsTypeReference, map: Map<String, RsTypeParameter>): Boolean =
-    HasTypeParameterVisitor(map, ref).visitTy(ref.type)
-
 private fun findTransitiveAttributes(enum: RsEnumItem, supportedAttributes: Set<String>): List<RsOuterAttr> =
     enum.outerAttrList.filter { it.metaItem.name in supportedAttributes }
@@ -0,0 +1,51 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.extractTrait
+
+import com.intellij.openapi.actionSystem.DataContext
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.ide.refactoring.RsBaseEditorRefactoringAction
+import org.rust.lang.core.psi.RsImplItem
+import org.rust.lang.core.psi.ext.RsAbstractable
+import org.rust.lang.core.psi.ext.ancestorOrSelf
+import org.rust.lang.core.psi.ext.stubChildrenOfType
+
+class RsExtractTraitAction : RsBaseEditorRefactoringAction() {
+    override fun isAvailableOnElementInEditorAndFile(
+        element: PsiElement,
+        editor: Editor,
+        file: PsiFile,
+        context: DataContext
+    ): Boolean =
+        findApplicableContext(editor, file) != null
+
+    override fun invoke(project: Project, editor: Editor, file: PsiFile, dataContext: DataContext?) {
+        val impl = findApplicableContext(editor, file) ?: return
+        val options = showExtractTraitUi(project, impl, impl.extractableMembers) ?: return
+        if (options.members.isEmpty()) return
+
+        val processor = RsExtractTraitProcessor(project, impl, options)
+        processor.setPreviewUsages(false)
+        processor.run()
+    }
+
+    companion object {
+        private fun findApplicableContext(editor: Editor, file: PsiFile): RsImplItem? {
+            val offset = editor.caretModel.offset
+            val impl = file.findElementAt(offset)?.ancestorOrSelf<RsImplItem>() ?: return null
+            if (impl.traitRef != null || impl.typeReference == null) return null
+            if (impl.extractableMembers.isEmpty()) return null
+            return impl
+        }
+    }
+}
+
+/** Members declared directly in an inherent impl (i.e. not produced by macros) */
+val RsImplItem.extractableMembers: List<RsAbstractable>
+    get() = members?.stubChildrenOfType<RsAbstractable>().orEmpty()
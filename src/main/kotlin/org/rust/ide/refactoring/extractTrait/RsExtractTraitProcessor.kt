@@ -0,0 +1,210 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.extractTrait
+
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.Ref
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiWhiteSpace
+import com.intellij.psi.search.searches.ReferencesSearch
+import com.intellij.refactoring.BaseRefactoringProcessor
+import com.intellij.usageView.BaseUsageViewDescriptor
+import com.intellij.usageView.UsageInfo
+import com.intellij.usageView.UsageViewDescriptor
+import com.intellij.util.containers.MultiMap
+import org.rust.ide.inspections.import.RsImportHelper
+import org.rust.ide.refactoring.GenericParameters
+import org.rust.ide.refactoring.buildWhereClause
+import org.rust.ide.refactoring.filterTypeParameters
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.RsElementTypes.EQ
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.TYPES
+
+/**
+ * Extracts [ExtractTraitOptions.members] of an inherent [impl] to a new trait:
+ * ```
+ * impl S {                    trait T {
+ *     pub fn foo(&self) {}        fn foo(&self);
+ *     fn bar(&self) {}        }
+ * }                     =>    impl T for S {
+ *                                 fn foo(&self) {}
+ *                             }
+ *                             impl S {
+ *                                 fn bar(&self) {}
+ *                             }
+ * ```
+ */
+class RsExtractTraitProcessor(
+    project: Project,
+    private val impl: RsImplItem,
+    private val options: ExtractTraitOptions
+) : BaseRefactoringProcessor(project) {
+
+    private val traitName: String get() = options.traitName
+
+    /** The most permissive visibility of the extracted members, so the trait is accessible from the same places */
+    private val traitVis: RsVis?
+        get() {
+            val visibilities = options.members.mapNotNull { (it as? RsVisibilityOwner)?.vis }
+            return visibilities.find { it.visibility == RsVisibility.Public } ?: visibilities.firstOrNull()
+        }
+
+    override fun findUsages(): Array<UsageInfo> =
+        options.members.flatMap { member -> ReferencesSearch.search(member).map { UsageInfo(it) } }.toTypedArray()
+
+    override fun preprocessUsages(refUsages: Ref<Array<UsageInfo>>): Boolean {
+        val conflicts = MultiMap<PsiElement, String>()
+
+        if (impl.findInScope(traitName, TYPES) != null) {
+            conflicts.putValue(impl, "`$traitName` is already declared in this scope")
+        }
+
+        val isTraitPublic = traitVis?.visibility.let { it != null && it != RsVisibility.Private }
+        for (member in options.members) {
+            if (isTraitPublic) {
+                for (path in member.signatureTypeReferences.flatMap { it.descendantsOfType<RsPath>() }) {
+                    val item = path.reference?.resolve() as? RsItemElement ?: continue
+                    if (item !is RsStructOrEnumItemElement && item !is RsTraitItem && item !is RsTypeAlias) continue
+                    if (item.visibility != RsVisibility.Private || item is RsAbstractable && item.owner.isImplOrTrait) continue
+                    conflicts.putValue(path, "`${member.name}` uses private type `${item.name}`, but trait `$traitName` would be public")
+                }
+            }
+
+            if (options.defaultBodies && member is RsFunction) {
+                for (fieldLookup in member.block?.descendantsOfType<RsFieldLookup>().orEmpty()) {
+                    if (fieldLookup.reference.resolve() !is RsFieldDecl) continue
+                    conflicts.putValue(
+                        fieldLookup,
+                        "`${member.name}` uses field `${fieldLookup.referenceName}`, so it can't have a default implementation in trait `$traitName`"
+                    )
+                }
+                // `Self` is generic in a default body, so inherent members that stay in the impl are inaccessible there
+                val references = member.block?.descendantsOfType<RsMethodCall>().orEmpty() +
+                    member.block?.descendantsOfType<RsPath>().orEmpty()
+                for (reference in references) {
+                    val target = reference.reference?.resolve() as? RsAbstractable ?: continue
+                    if (!target.owner.isInherentImpl || target in options.members) continue
+                    conflicts.putValue(
+                        reference,
+                        "`${member.name}` uses `${target.name}`, which is not a member of trait `$traitName`, so it can't have a default implementation"
+                    )
+                }
+            }
+        }
+
+        return showConflicts(conflicts, refUsages.get())
+    }
+
+    override fun performRefactoring(usages: Array<out UsageInfo>) {
+        val factory = RsPsiFactory(myProject)
+        val members = options.members
+        val parameters = filterTypeParameters(
+            members.flatMap { it.genericTypeReferences(options.defaultBodies) },
+            impl.typeParameterList
+        )
+
+        val trait = impl.parent.addBefore(factory.createTraitItem(createTraitText(parameters)), impl) as RsTraitItem
+
+        // Without default bodies, type aliases are the only members that still need an implementation
+        val implMembers = if (options.defaultBodies) members.filterIsInstance<RsTypeAlias>() else members
+        val traitRefText = "$traitName${parameters.formatAsArguments()}"
+        if (impl.extractableMembers.all { it in members } && impl.members?.stubChildrenOfType<RsMacroCall>().isNullOrEmpty()) {
+            convertToTraitImpl(factory, traitRefText, members - implMembers)
+        } else {
+            val typeParameters = impl.typeParameterList?.text.orEmpty()
+            val whereClause = impl.whereClause?.text?.let { " $it" }.orEmpty()
+            val membersText = implMembers.joinToString("\n\n") { it.textWithoutVisibility() }
+            val traitImpl = factory.createImplItem(
+                "impl$typeParameters $traitRefText for ${impl.typeReference!!.text}$whereClause {\n$membersText\n}"
+            )
+            impl.parent.addAfter(traitImpl, impl)
+            members.forEach { it.delete() }
+        }
+
+        for (usage in usages) {
+            val element = usage.element as? RsElement ?: continue
+            if (listOf(trait).filterInScope(element).isEmpty()) {
+                RsImportHelper.importElements(element, setOf(trait))
+            }
+        }
+    }
+
+    private fun createTraitText(parameters: GenericParameters): String {
+        val vis = traitVis?.text?.let { "$it " }.orEmpty()
+        val whereClause = buildWhereClause(impl.whereClause, parameters)
+        val membersText = options.members.joinToString("\n\n") { it.traitMemberText() }
+        return "${vis}trait $traitName${parameters.format()}$whereClause {\n$membersText\n}"
+    }
+
+    /** Turns an inherent impl into the trait impl in-place if all its members are extracted */
+    private fun convertToTraitImpl(factory: RsPsiFactory, traitRefText: String, membersToDelete: List<RsAbstractable>) {
+        for (member in impl.extractableMembers) {
+            (member as? RsVisibilityOwner)?.vis?.deleteWithFollowingWhitespace()
+        }
+        if (impl.extractableMembers.all { it in membersToDelete }) {
+            impl.members?.replace(factory.createMembers(""))
+        } else {
+            membersToDelete.forEach { it.delete() }
+        }
+        val traitImpl = factory.createImplItem("impl $traitRefText for T {}")
+        val selfType = traitImpl.typeReference!!
+        impl.addRangeBefore(traitImpl.traitRef!!, selfType.prevSibling, impl.typeReference!!)
+    }
+
+    private fun RsAbstractable.traitMemberText(): String {
+        val copy = copyWithoutVisibility()
+        return when (copy) {
+            is RsFunction -> if (options.defaultBodies) copy.text else copy.textBefore(copy.block) + ";"
+            is RsConstant -> if (options.defaultBodies) copy.text else copy.textBefore(copy.eq) + ";"
+            is RsTypeAlias -> copy.textBefore(copy.eq) + ";"
+            else -> copy.text
+        }
+    }
+
+    override fun getCommandName(): String = "Extracting trait $traitName"
+
+    override fun createUsageViewDescriptor(usages: Array<out UsageInfo>): UsageViewDescriptor =
+        BaseUsageViewDescriptor(impl)
+
+    override fun getRefactoringId(): String? = "refactoring.extractTrait"
+}
+
+/** Type references that become a part of the trait declaration */
+private val RsAbstractable.signatureTypeReferences: List<RsTypeReference>
+    get() = when (this) {
+        is RsFunction -> listOfNotNull(valueParameterList, retType).flatMap { it.descendantsOfType<RsTypeReference>() }
+        is RsConstant -> listOfNotNull(typeReference)
+        else -> emptyList()
+    }
+
+private fun RsAbstractable.genericTypeReferences(withBodies: Boolean): List<RsTypeReference> {
+    val declarations = when (this) {
+        is RsFunction -> listOfNotNull(typeParameterList, whereClause, if (withBodies) block else null)
+        is RsConstant -> listOfNotNull(if (withBodies) expr else null)
+        else -> emptyList()
+    }
+    return signatureTypeReferences + declarations.flatMap { it.descendantsOfType<RsTypeReference>() }
+}
+
+private val PsiElement.eq: PsiElement? get() = node.findChildByType(EQ)?.psi
+
+private fun PsiElement.textBefore(child: PsiElement?): String =
+    if (child == null) text else text.substring(0, child.startOffsetInParent).trimEnd()
+
+private fun RsAbstractable.copyWithoutVisibility(): RsAbstractable {
+    val copy = copy() as RsAbstractable
+    (copy as? RsVisibilityOwner)?.vis?.deleteWithFollowingWhitespace()
+    return copy
+}
+
+/** Trait impls can't have visibility modifiers */
+private fun RsAbstractable.textWithoutVisibility(): String = copyWithoutVisibility().text
+
+private fun RsVis.deleteWithFollowingWhitespace() {
+    (nextSibling as? PsiWhiteSpace)?.delete()
+    delete()
+}
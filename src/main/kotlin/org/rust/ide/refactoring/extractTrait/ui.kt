@@ -0,0 +1,89 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.extractTrait
+
+import com.intellij.codeInsight.generation.MemberChooserObjectBase
+import com.intellij.ide.util.MemberChooser
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.ValidationInfo
+import com.intellij.openapiext.isUnitTestMode
+import com.intellij.ui.components.JBCheckBox
+import com.intellij.ui.components.JBLabel
+import com.intellij.ui.components.JBTextField
+import org.jetbrains.annotations.TestOnly
+import org.rust.ide.refactoring.implementMembers.RsTraitMemberChooserMember
+import org.rust.ide.refactoring.isValidRustVariableIdentifier
+import org.rust.lang.core.psi.RsBaseType
+import org.rust.lang.core.psi.RsImplItem
+import org.rust.lang.core.psi.ext.RsAbstractable
+import org.rust.lang.core.psi.ext.skipParens
+import java.awt.BorderLayout
+import javax.swing.JComponent
+import javax.swing.JPanel
+
+data class ExtractTraitOptions(
+    val traitName: String,
+    val members: List<RsAbstractable>,
+    /** Move bodies of methods and values of constants to the trait as default implementations */
+    val defaultBodies: Boolean
+)
+
+interface ExtractTraitUi {
+    fun chooseOptions(project: Project, impl: RsImplItem, members: List<RsAbstractable>): ExtractTraitOptions?
+}
+
+fun showExtractTraitUi(project: Project, impl: RsImplItem, members: List<RsAbstractable>): ExtractTraitOptions? {
+    val ui = if (isUnitTestMode) {
+        MOCK ?: error("You should set mock ui via `withMockExtractTraitUi`")
+    } else {
+        DialogExtractTraitUi
+    }
+    return ui.chooseOptions(project, impl, members)
+}
+
+private var MOCK: ExtractTraitUi? = null
+
+@TestOnly
+fun withMockExtractTraitUi(mockUi: ExtractTraitUi, action: () -> Unit) {
+    MOCK = mockUi
+    try {
+        action()
+    } finally {
+        MOCK = null
+    }
+}
+
+private object DialogExtractTraitUi : ExtractTraitUi {
+    override fun chooseOptions(project: Project, impl: RsImplItem, members: List<RsAbstractable>): ExtractTraitOptions? {
+        val typeName = (impl.typeReference?.skipParens() as? RsBaseType)?.path?.referenceName.orEmpty()
+        val base = MemberChooserObjectBase(typeName, impl.getIcon(0))
+        val all = members.map { RsTraitMemberChooserMember(base, it) }
+
+        val nameField = JBTextField("${typeName}Trait")
+        val header = JPanel(BorderLayout(8, 0)).apply {
+            add(JBLabel("Trait name:"), BorderLayout.WEST)
+            add(nameField, BorderLayout.CENTER)
+        }
+        val defaultBodies = JBCheckBox("Move bodies to the trait as default implementations")
+
+        val chooser = object : MemberChooser<RsTraitMemberChooserMember>(
+            all.toTypedArray(), false, true, project, header, arrayOf<JComponent>(defaultBodies)
+        ) {
+            override fun doValidate(): ValidationInfo? {
+                if (isValidRustVariableIdentifier(nameField.text)) return null
+                return ValidationInfo("Invalid trait name", nameField)
+            }
+        }.apply {
+            title = "Extract Trait"
+            selectElements(all.toTypedArray())
+            setCopyJavadocVisible(false)
+        }
+        if (!chooser.showAndGet()) return null
+
+        val selected = chooser.selectedElements?.map { it.member } ?: return null
+        return ExtractTraitOptions(nameField.text, selected, defaultBodies.isSelected)
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.generateBuilder
+
+import com.intellij.codeInsight.CodeInsightActionHandler
+import com.intellij.codeInsight.actions.CodeInsightAction
+import com.intellij.codeInsight.hint.HintManager
+import com.intellij.lang.LanguageCodeInsightActionHandler
+import com.intellij.openapi.application.runWriteAction
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiFile
+import org.rust.ide.refactoring.generateConstructor.ConstructorArgument
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.knownItems
+import org.rust.lang.core.types.emptySubstitution
+import org.rust.lang.core.types.ty.TyTypeParameter
+import org.rust.lang.core.types.ty.TyUnknown
+import org.rust.lang.core.types.type
+import org.rust.openapiext.checkWriteAccessAllowed
+import org.rust.openapiext.checkWriteAccessNotAllowed
+
+class GenerateBuilderAction : CodeInsightAction() {
+
+    private val generateBuilderHandler: GenerateBuilderHandler = GenerateBuilderHandler()
+
+    override fun getHandler(): CodeInsightActionHandler = generateBuilderHandler
+
+    override fun isValidForFile(project: Project, editor: Editor, file: PsiFile): Boolean =
+        generateBuilderHandler.isValidFor(editor, file)
+}
+
+/**
+ * Generates `FooBuilder` struct for a struct `Foo` with named fields:
+ *
+ * ```
+ * pub struct FooBuilder {
+ *     a: Option<i32>,
+ * }
+ *
+ * impl FooBuilder {
+ *     pub fn new() -> Self { FooBuilder { a: None } }
+ *     pub fn a(mut self, a: impl Into<i32>) -> Self { self.a = Some(a.into()); self }
+ *     pub fn build(self) -> Result<Foo, &'static str> { Ok(Foo { a: self.a.ok_or("`a` is not set")? }) }
+ * }
+ * ```
+ *
+ * If the builder already exists, setters for the fields it doesn't know about are added to it
+ */
+class GenerateBuilderHandler : LanguageCodeInsightActionHandler {
+    override fun isValidFor(editor: Editor, file: PsiFile): Boolean = getStruct(editor, file) != null
+
+    override fun startInWriteAction() = false
+
+    private fun getStruct(editor: Editor, file: PsiFile): RsStructItem? {
+        val element = file.findElementAt(editor.caretModel.offset) ?: return null
+        val struct = element.ancestorOrSelf<RsStructItem>() ?: return null
+        if (struct.kind != RsStructKind.STRUCT || struct.blockFields == null || struct.namedFields.isEmpty()) return null
+        if (struct.name == null) return null
+        return struct
+    }
+
+    override fun invoke(project: Project, editor: Editor, file: PsiFile) {
+        checkWriteAccessNotAllowed()
+        val struct = getStruct(editor, file) ?: return
+        val builderName = "${struct.name}Builder"
+        val fields = ConstructorArgument.fromStruct(struct, emptySubstitution)
+
+        val existingBuilder = struct.findSibling<RsStructItem>(builderName)?.takeIf { it.blockFields != null }
+        if (existingBuilder == null) {
+            val options = showBuilderOptionsChooser(project, struct, fields) ?: return
+            runWriteAction {
+                insertNewBuilder(struct, builderName, fields, options, editor)
+            }
+        } else {
+            val builderFields = existingBuilder.namedFields.mapNotNull { it.name }.toSet()
+            val newFields = fields.filter { it.argumentIdentifier !in builderFields }
+            if (newFields.isEmpty()) {
+                HintManager.getInstance().showInformationHint(editor, "`$builderName` is up to date")
+                return
+            }
+            if (!confirmBuilderUpdate(project, builderName)) return
+            runWriteAction {
+                updateBuilder(struct, existingBuilder, newFields)
+            }
+        }
+    }
+
+    private fun insertNewBuilder(
+        struct: RsStructItem,
+        builderName: String,
+        fields: List<ConstructorArgument>,
+        options: BuilderOptions,
+        editor: Editor
+    ) {
+        checkWriteAccessAllowed()
+        val psiFactory = RsPsiFactory(struct.project)
+        val vis = struct.vis?.text?.let { "$it " }.orEmpty()
+        val typeParameters = struct.typeParameterList?.text.orEmpty()
+        val whereClause = struct.whereClause?.text?.let { " $it" }.orEmpty()
+
+        val fieldsText = fields.joinToString(",\n", postfix = psiFactory.multilineTrailingComma) {
+            "${it.argumentIdentifier}: Option<${it.typeReferenceText}>"
+        }
+        val builderStruct = psiFactory.createStruct("${vis}struct $builderName$typeParameters$whereClause {\n$fieldsText\n}")
+        val insertedStruct = struct.parent.addAfter(builderStruct, struct) as RsStructItem
+
+        val impl = psiFactory.createInherentImplItem(builderName, struct.typeParameterList, struct.whereClause)
+        val insertedImpl = struct.parent.addAfter(impl, insertedStruct) as RsImplItem
+
+        val members = insertedImpl.members ?: return
+        val rbrace = members.rbrace ?: return
+        val noneFields = fields.joinToString(", ") { "${it.argumentIdentifier}: None" }
+        val functions = listOf(psiFactory.createTraitMethodMember("${vis}fn new() -> Self {\n$builderName { $noneFields }\n}")) +
+            fields.map { createSetter(psiFactory, vis, it, options.useInto) } +
+            createBuild(psiFactory, struct, vis, fields, options.requiredFields)
+        for ((index, function) in functions.withIndex()) {
+            if (index != 0) members.addBefore(psiFactory.createWhitespace("\n\n"), rbrace)
+            members.addBefore(function, rbrace)
+        }
+
+        editor.caretModel.moveToOffset(insertedStruct.textOffset)
+    }
+
+    private fun updateBuilder(struct: RsStructItem, builder: RsStructItem, newFields: List<ConstructorArgument>) {
+        checkWriteAccessAllowed()
+        val psiFactory = RsPsiFactory(struct.project)
+        val builderName = builder.name ?: return
+
+        val blockFields = builder.blockFields ?: return
+        val fieldsRBrace = blockFields.rbrace ?: return
+        for (field in newFields) {
+            ensureTrailingComma(blockFields.namedFieldDeclList)
+            val newField = psiFactory.createStruct("struct S { ${field.argumentIdentifier}: Option<${field.typeReferenceText}> }")
+                .namedFields.single()
+            blockFields.addBefore(newField, fieldsRBrace)
+        }
+
+        val impl = struct.parent.childrenOfType<RsImplItem>().find {
+            it.traitRef == null && (it.typeReference?.skipParens() as? RsBaseType)?.path?.referenceName == builderName
+        }
+        val members = impl?.members ?: return
+        val functions = members.childrenOfType<RsFunction>()
+        val constructor = functions.find { it.name == "new" }
+        val build = functions.find { it.name == "build" }
+        val vis = (build ?: constructor)?.vis?.text?.let { "$it " }.orEmpty()
+        val setters = functions.filter { it != constructor && it != build }
+        val useInto = setters.isEmpty() || setters.any { fn ->
+            fn.valueParameters.any { it.typeReference?.text?.startsWith("impl Into<") == true }
+        }
+
+        constructor?.findStructLiteral(builderName)?.let { literal ->
+            for (field in newFields) {
+                literal.addField(psiFactory.createStructLiteralField(field.argumentIdentifier, "None"))
+            }
+        }
+
+        val anchor = build ?: members.rbrace ?: return
+        for (field in newFields) {
+            val setter = createSetter(psiFactory, vis, field, useInto)
+            val blankLine = psiFactory.createWhitespace("\n\n")
+            if (build != null) {
+                members.addBefore(setter, anchor)
+                members.addBefore(blankLine, anchor)
+            } else {
+                members.addBefore(blankLine, anchor)
+                members.addBefore(setter, anchor)
+            }
+        }
+
+        val structName = struct.name ?: return
+        val returnsResult = build?.retType?.typeReference?.text?.startsWith("Result") == true
+        build?.findStructLiteral(structName)?.let { literal ->
+            for (field in newFields) {
+                val value = if (returnsResult) field.requiredValue else field.defaultedValue(struct, psiFactory)
+                literal.addField(psiFactory.createStructLiteralField(field.argumentIdentifier, value))
+            }
+        }
+    }
+
+    private fun createSetter(
+        psiFactory: RsPsiFactory,
+        vis: String,
+        field: ConstructorArgument,
+        useInto: Boolean
+    ): RsFunction {
+        val name = field.argumentIdentifier
+        val (type, value) = if (useInto) {
+            "impl Into<${field.typeReferenceText}>" to "$name.into()"
+        } else {
+            field.typeReferenceText to name
+        }
+        return psiFactory.createTraitMethodMember("${vis}fn $name(mut self, $name: $type) -> Self {\nself.$name = Some($value);\nself\n}")
+    }
+
+    private fun createBuild(
+        psiFactory: RsPsiFactory,
+        struct: RsStructItem,
+        vis: String,
+        fields: List<ConstructorArgument>,
+        requiredFields: List<ConstructorArgument>
+    ): RsFunction {
+        val structType = struct.name + struct.typeArgumentsText
+        val initializers = fields.joinToString(",\n", postfix = psiFactory.multilineTrailingComma) {
+            val value = if (it in requiredFields) it.requiredValue else it.defaultedValue(struct, psiFactory)
+            "${it.argumentIdentifier}: $value"
+        }
+        val literal = "${struct.name} {\n$initializers\n}"
+        val text = if (requiredFields.isEmpty()) {
+            "${vis}fn build(self) -> $structType {\n$literal\n}"
+        } else {
+            "${vis}fn build(self) -> Result<$structType, &'static str> {\nOk($literal)\n}"
+        }
+        return psiFactory.createTraitMethodMember(text)
+    }
+}
+
+private val ConstructorArgument.requiredValue: String
+    get() = "self.$argumentIdentifier.ok_or(\"`$argumentIdentifier` is not set\")?"
+
+/**
+ * `self.a.unwrap_or_default()` if the field type implements `Default`,
+ * otherwise `self.a.unwrap_or(value)` with a value built by [RsDefaultValueBuilder]
+ */
+private fun ConstructorArgument.defaultedValue(struct: RsStructItem, psiFactory: RsPsiFactory): String {
+    val unwrapOrDefault = "self.$argumentIdentifier.unwrap_or_default()"
+    val items = struct.knownItems
+    // Without `Default` trait we can't check anything
+    if (items.Default == null) return unwrapOrDefault
+    val ty = struct.namedFields.find { it.name == argumentIdentifier }?.typeReference?.type ?: return unwrapOrDefault
+    // For a type parameter, the compiler error will suggest adding `Default` bound
+    if (ty is TyUnknown || ty is TyTypeParameter || struct.implLookup.isDefault(ty)) return unwrapOrDefault
+    val value = RsDefaultValueBuilder(items, struct.containingMod, psiFactory, recursive = true).buildFor(ty, emptyMap())
+    return "self.$argumentIdentifier.unwrap_or(${value.text})"
+}
+
+private val RsStructItem.typeArgumentsText: String
+    get() {
+        val parameters = typeParameterList ?: return ""
+        val names = parameters.lifetimeParameterList.map { it.quoteIdentifier.text } +
+            parameters.typeParameterList.map { it.name }
+        return names.joinToString(", ", "<", ">")
+    }
+
+private inline fun <reified T : RsNamedElement> RsStructItem.findSibling(name: String): T? =
+    parent.childrenOfType<T>().find { it.name == name }
+
+private fun RsFunction.findStructLiteral(structName: String): RsStructLiteral? =
+    block?.descendantsOfType<RsStructLiteral>()?.find { it.path.referenceName == structName }
+
+private fun RsStructLiteral.addField(field: RsStructLiteralField) {
+    val body = structLiteralBody
+    val rbrace = body.rbrace ?: return
+    ensureTrailingComma(body.structLiteralFieldList)
+    body.addBefore(field, rbrace)
+}
@@ -0,0 +1,99 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.generateBuilder
+
+import com.intellij.codeInsight.generation.MemberChooserObjectBase
+import com.intellij.ide.util.MemberChooser
+import com.intellij.ide.util.PropertiesComponent
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.Messages
+import com.intellij.openapiext.isUnitTestMode
+import com.intellij.ui.components.JBCheckBox
+import org.jetbrains.annotations.TestOnly
+import org.rust.ide.refactoring.generateConstructor.ConstructorArgument
+import org.rust.ide.refactoring.generateConstructor.RsStructMemberChooserObject
+import org.rust.lang.core.psi.RsStructItem
+import javax.swing.JComponent
+
+private var MOCK: GenerateBuilderUi? = null
+
+data class BuilderOptions(
+    /** Fields that must be set before `build` is called. Other fields fall back to `Default::default()` */
+    val requiredFields: List<ConstructorArgument>,
+    /** If true, setters take `impl Into<FieldType>` instead of `FieldType` */
+    val useInto: Boolean
+)
+
+fun showBuilderOptionsChooser(
+    project: Project,
+    structItem: RsStructItem,
+    fields: List<ConstructorArgument>
+): BuilderOptions? {
+    val base = MemberChooserObjectBase(structItem.name, structItem.getIcon(0))
+    val members = fields.map { RsStructMemberChooserObject(base, it) }
+    return getUi().chooseOptions(project, members)
+}
+
+fun confirmBuilderUpdate(project: Project, builderName: String): Boolean =
+    getUi().confirmUpdate(project, builderName)
+
+private fun getUi(): GenerateBuilderUi = if (isUnitTestMode) {
+    MOCK ?: error("You should set mock ui via `withMockGenerateBuilderUi`")
+} else {
+    DialogGenerateBuilderUi
+}
+
+@TestOnly
+fun withMockGenerateBuilderUi(mockUi: GenerateBuilderUi, action: () -> Unit) {
+    MOCK = mockUi
+    try {
+        action()
+    } finally {
+        MOCK = null
+    }
+}
+
+interface GenerateBuilderUi {
+    /** @param all fields of the struct. The selected ones are considered required */
+    fun chooseOptions(project: Project, all: List<RsStructMemberChooserObject>): BuilderOptions?
+
+    fun confirmUpdate(project: Project, builderName: String): Boolean
+}
+
+private object DialogGenerateBuilderUi : GenerateBuilderUi {
+    private const val USE_INTO_KEY: String = "org.rust.generate.builder.use.into"
+
+    override fun chooseOptions(project: Project, all: List<RsStructMemberChooserObject>): BuilderOptions? {
+        val properties = PropertiesComponent.getInstance()
+        val useIntoCheckbox = JBCheckBox("Use impl Into<T> in setters", properties.getBoolean(USE_INTO_KEY, true))
+        val chooser = MemberChooser(
+            all.toTypedArray(),
+            true,
+            true,
+            project,
+            null,
+            arrayOf<JComponent>(useIntoCheckbox)
+        ).apply {
+            title = "Select Required Fields"
+            selectElements(all.toTypedArray())
+            setCopyJavadocVisible(false)
+        }
+        if (!chooser.showAndGet()) return null
+        properties.setValue(USE_INTO_KEY, useIntoCheckbox.isSelected, true)
+        val required = chooser.selectedElements.orEmpty().map { it.member }
+        return BuilderOptions(required, useIntoCheckbox.isSelected)
+    }
+
+    override fun confirmUpdate(project: Project, builderName: String): Boolean {
+        val answer = Messages.showYesNoDialog(
+            project,
+            "`$builderName` already exists. Add setters for the new fields?",
+            "Generate Builder",
+            Messages.getQuestionIcon()
+        )
+        return answer == Messages.YES
+    }
+}
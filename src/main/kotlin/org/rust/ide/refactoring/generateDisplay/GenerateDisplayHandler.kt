@@ -0,0 +1,312 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.generateDisplay
+
+import com.intellij.codeInsight.CodeInsightActionHandler
+import com.intellij.codeInsight.actions.CodeInsightAction
+import com.intellij.codeInsight.hint.HintManager
+import com.intellij.lang.LanguageCodeInsightActionHandler
+import com.intellij.openapi.application.runWriteAction
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.ide.inspections.import.AutoImportFix
+import org.rust.ide.inspections.import.import
+import org.rust.ide.inspections.toSnakeCase
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.ImplLookup
+import org.rust.lang.core.types.type
+import org.rust.lang.doc.documentation
+import org.rust.openapiext.checkWriteAccessAllowed
+import org.rust.openapiext.checkWriteAccessNotAllowed
+
+class GenerateDisplayAction : CodeInsightAction() {
+
+    private val generateDisplayHandler: GenerateDisplayHandler = GenerateDisplayHandler()
+
+    override fun getHandler(): CodeInsightActionHandler = generateDisplayHandler
+
+    override fun isValidForFile(project: Project, editor: Editor, file: PsiFile): Boolean =
+        generateDisplayHandler.isValidFor(editor, file)
+}
+
+/**
+ * Generates `Display` impl for an enum `Foo`, and optionally `std::error::Error` and `FromStr` impls:
+ *
+ * ```
+ * impl fmt::Display for Foo {
+ *     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+ *         match self {
+ *             Foo::NotFound => write!(f, "Not found"),
+ *             Foo::Io(..) => write!(f, "io"),
+ *         }
+ *     }
+ * }
+ *
+ * impl std::error::Error for Foo {
+ *     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+ *         match self {
+ *             Foo::Io(e) => Some(e),
+ *             _ => None,
+ *         }
+ *     }
+ * }
+ * ```
+ *
+ * The text of a variant is the first paragraph of its documentation or its snake-cased name.
+ * `FromStr` impl parses the same texts, so it's available only for enums without fields.
+ *
+ * If `Display` impl already exists, arms for the new variants are added to the existing impls
+ */
+class GenerateDisplayHandler : LanguageCodeInsightActionHandler {
+    override fun isValidFor(editor: Editor, file: PsiFile): Boolean = getEnum(editor, file) != null
+
+    override fun startInWriteAction() = false
+
+    private fun getEnum(editor: Editor, file: PsiFile): RsEnumItem? {
+        val element = file.findElementAt(editor.caretModel.offset) ?: return null
+        val enum = element.ancestorOrSelf<RsEnumItem>() ?: return null
+        if (enum.name == null || enum.variants.isEmpty()) return null
+        return enum
+    }
+
+    override fun invoke(project: Project, editor: Editor, file: PsiFile) {
+        checkWriteAccessNotAllowed()
+        val enum = getEnum(editor, file) ?: return
+        val enumName = enum.name ?: return
+
+        val displayImpl = enum.findTraitImpl("Display")
+        if (displayImpl == null) {
+            val canGenerateFromStr = enum.variants.all { it.isFieldless } && enum.findTraitImpl("FromStr") == null
+            val options = showDisplayOptionsChooser(project, enum, canGenerateFromStr) ?: return
+            runWriteAction {
+                insertImpls(enum, options, editor)
+            }
+        } else {
+            val displayMatch = displayImpl.findMatchInFunction("fmt")
+            if (displayMatch == null) {
+                HintManager.getInstance().showErrorHint(editor, "Can't find `match` in `Display` impl for `$enumName`")
+                return
+            }
+            val coveredVariants = displayMatch.variantNamesInPatterns()
+            val newVariants = enum.variants.filter { it.name !in coveredVariants }
+            if (newVariants.isEmpty()) {
+                HintManager.getInstance().showInformationHint(editor, "`Display` impl for `$enumName` is up to date")
+                return
+            }
+            if (!confirmDisplayUpdate(project, enumName, newVariants.mapNotNull { it.name })) return
+            runWriteAction {
+                updateImpls(enum, displayMatch, newVariants)
+            }
+        }
+    }
+
+    private fun insertImpls(enum: RsEnumItem, options: DisplayOptions, editor: Editor) {
+        checkWriteAccessAllowed()
+        val psiFactory = RsPsiFactory(enum.project)
+        val enumName = enum.name ?: return
+        val typeParameters = enum.typeParameterList?.text.orEmpty()
+        val enumType = enumName + enum.typeArgumentsText
+        val whereClause = enum.whereClause?.text?.let { " $it" }.orEmpty()
+        fun implHeader(trait: String): String = "impl$typeParameters $trait for $enumType$whereClause"
+
+        val displayArms = enum.variants.joinToString("\n") {
+            if (it in options.variants) it.displayArm(enumName) else "${it.pattern(enumName)} => todo!(),"
+        }
+        val impls = mutableListOf(
+            """
+            ${implHeader("fmt::Display")} {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    match self {
+                        $displayArms
+                    }
+                }
+            }
+            """
+        )
+
+        if (options.generateError) {
+            val lookup = ImplLookup.relativeTo(enum)
+            val sourceArms = enum.variants.mapNotNull { it.sourceArm(enumName, lookup) }
+            impls += if (sourceArms.isEmpty()) {
+                "${implHeader("std::error::Error")} {}"
+            } else {
+                val wildcardArm = if (sourceArms.size < enum.variants.size) "\n_ => None," else ""
+                """
+                ${implHeader("std::error::Error")} {
+                    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                        match self {
+                            ${sourceArms.joinToString("\n")}$wildcardArm
+                        }
+                    }
+                }
+                """
+            }
+        }
+
+        if (options.generateFromStr) {
+            val fromStrArms = options.variants.joinToString("\n") { it.fromStrArm(enumName) }
+            impls += """
+                ${implHeader("FromStr")} {
+                    type Err = String;
+
+                    fn from_str(s: &str) -> Result<Self, Self::Err> {
+                        match s {
+                            $fromStrArms
+                            _ => Err(format!("Unknown $enumName: {}", s)),
+                        }
+                    }
+                }
+            """
+        }
+
+        var anchor: PsiElement = enum
+        val insertedImpls = impls.map { text ->
+            val impl = psiFactory.createImplItem(text.trimIndent())
+            val inserted = enum.parent.addAfter(impl, anchor) as RsImplItem
+            anchor = inserted
+            inserted
+        }
+        for (impl in insertedImpls) {
+            importTraitPath(impl)
+        }
+
+        editor.caretModel.moveToOffset(insertedImpls.first().textOffset)
+    }
+
+    private fun updateImpls(enum: RsEnumItem, displayMatch: RsMatchExpr, newVariants: List<RsEnumVariant>) {
+        checkWriteAccessAllowed()
+        val psiFactory = RsPsiFactory(enum.project)
+        val enumName = enum.name ?: return
+
+        displayMatch.addArms(psiFactory, newVariants.map { it.displayArm(enumName) })
+
+        val sourceMatch = enum.findTraitImpl("Error")?.findMatchInFunction("source")
+        if (sourceMatch != null) {
+            val lookup = ImplLookup.relativeTo(enum)
+            // Non-wrapping variants are covered by the wildcard arm, if any
+            val hasWildcardArm = sourceMatch.wildcardArm != null
+            val arms = newVariants.mapNotNull {
+                it.sourceArm(enumName, lookup) ?: if (hasWildcardArm) null else "${it.pattern(enumName)} => None,"
+            }
+            sourceMatch.addArms(psiFactory, arms)
+        }
+
+        val fromStrMatch = enum.findTraitImpl("FromStr")?.findMatchInFunction("from_str")
+        if (fromStrMatch != null) {
+            fromStrMatch.addArms(psiFactory, newVariants.filter { it.isFieldless }.map { it.fromStrArm(enumName) })
+        }
+    }
+
+    /** Adds `use std::fmt;` or `use std::str::FromStr;` if the trait path of [impl] is unresolved */
+    private fun importTraitPath(impl: RsImplItem) {
+        val path = impl.traitRef?.path ?: return
+        val context = AutoImportFix.findApplicableContext(impl.project, path) ?: return
+        val candidate = context.candidates.find { it.info.usePath.substringBefore("::") in STD_CRATES }
+            ?: return
+        candidate.import(impl)
+    }
+
+    companion object {
+        private val STD_CRATES: List<String> = listOf("std", "core")
+    }
+}
+
+private fun RsEnumItem.findTraitImpl(traitName: String): RsImplItem? =
+    parent.childrenOfType<RsImplItem>().find {
+        it.traitRef?.path?.referenceName == traitName &&
+            (it.typeReference?.skipParens() as? RsBaseType)?.path?.referenceName == name
+    }
+
+private fun RsImplItem.findMatchInFunction(functionName: String): RsMatchExpr? =
+    members?.childrenOfType<RsFunction>()
+        ?.find { it.name == functionName }
+        ?.block
+        ?.descendantOfTypeStrict()
+
+/** Names of variants mentioned in the patterns of the match arms, like `B` in `Foo::A | Foo::B(..) =>` */
+private fun RsMatchExpr.variantNamesInPatterns(): Set<String> =
+    matchBody?.matchArmList.orEmpty()
+        .flatMap { it.patList }
+        .flatMap { pat -> pat.descendantsOfType<RsPath>().mapNotNull { it.referenceName } }
+        .toSet()
+
+private val RsMatchExpr.wildcardArm: RsMatchArm?
+    get() = matchBody?.matchArmList?.find { arm -> arm.patList.singleOrNull() is RsPatWild }
+
+/** Adds [arms] before the wildcard arm or at the end of the match */
+private fun RsMatchExpr.addArms(psiFactory: RsPsiFactory, arms: List<String>) {
+    if (arms.isEmpty()) return
+    val body = matchBody ?: return
+    val rbrace = body.rbrace ?: return
+    val anchor = wildcardArm ?: rbrace
+    if (anchor == rbrace) {
+        val lastArm = body.matchArmList.lastOrNull()
+        if (lastArm != null && lastArm.expr !is RsBlockExpr && lastArm.comma == null) {
+            lastArm.add(psiFactory.createComma())
+        }
+    }
+    val newArms = (psiFactory.createExpression("match x { ${arms.joinToString("\n")} }") as RsMatchExpr)
+        .matchBody?.matchArmList.orEmpty()
+    for (arm in newArms) {
+        body.addBefore(arm, anchor)
+    }
+}
+
+/** `Foo::A`, `Foo::B(..)` or `Foo::C { .. }` */
+private fun RsEnumVariant.pattern(enumName: String): String {
+    val suffix = when {
+        tupleFields != null -> "(..)"
+        blockFields != null -> " { .. }"
+        else -> ""
+    }
+    return "$enumName::$name$suffix"
+}
+
+private fun RsEnumVariant.displayArm(enumName: String): String {
+    val text = displayText.replace("{", "{{").replace("}", "}}")
+    return "${pattern(enumName)} => write!(f, \"$text\"),"
+}
+
+private fun RsEnumVariant.fromStrArm(enumName: String): String =
+    "\"$displayText\" => Ok($enumName::$name),"
+
+/** `Foo::Io(e) => Some(e),` if the variant wraps an error, `null` otherwise */
+private fun RsEnumVariant.sourceArm(enumName: String, lookup: ImplLookup): String? {
+    val field = positionalFields.singleOrNull()
+        ?: namedFields.find { it.name == "source" }
+        ?: namedFields.singleOrNull()
+        ?: return null
+    val ty = field.typeReference?.type ?: return null
+    if (!lookup.isError(ty)) return null
+    return if (field is RsNamedFieldDecl) {
+        "$enumName::$name { ${field.name}, .. } => Some(${field.name}),"
+    } else {
+        "$enumName::$name(e) => Some(e),"
+    }
+}
+
+/** The first paragraph of the documentation or snake-cased name, escaped for a string literal */
+private val RsEnumVariant.displayText: String
+    get() {
+        val paragraph = documentation().orEmpty().lineSequence()
+            .map { it.trim() }
+            .dropWhile { it.isEmpty() }
+            .takeWhile { it.isNotEmpty() }
+            .joinToString(" ")
+        val text = if (paragraph.isNotEmpty()) paragraph else name.orEmpty().toSnakeCase(false)
+        return text.replace("\\", "\\\\").replace("\"", "\\\"")
+    }
+
+private val RsEnumItem.typeArgumentsText: String
+    get() {
+        val parameters = typeParameterList ?: return ""
+        val names = parameters.lifetimeParameterList.map { it.quoteIdentifier.text } +
+            parameters.typeParameterList.map { it.name }
+        return names.joinToString(", ", "<", ">")
+    }
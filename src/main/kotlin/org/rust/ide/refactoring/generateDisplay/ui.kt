@@ -0,0 +1,121 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.generateDisplay
+
+import com.intellij.codeInsight.generation.ClassMember
+import com.intellij.codeInsight.generation.MemberChooserObject
+import com.intellij.codeInsight.generation.MemberChooserObjectBase
+import com.intellij.ide.util.MemberChooser
+import com.intellij.ide.util.PropertiesComponent
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.ui.Messages
+import com.intellij.openapiext.isUnitTestMode
+import com.intellij.ui.components.JBCheckBox
+import org.jetbrains.annotations.TestOnly
+import org.rust.ide.icons.RsIcons
+import org.rust.lang.core.psi.RsEnumItem
+import org.rust.lang.core.psi.RsEnumVariant
+import javax.swing.JComponent
+
+private var MOCK: GenerateDisplayUi? = null
+
+data class DisplayOptions(
+    /** Variants to generate `Display` text for. Other variants get `todo!()` arms */
+    val variants: List<RsEnumVariant>,
+    val generateError: Boolean,
+    /** Available only for enums without fields */
+    val generateFromStr: Boolean
+)
+
+fun showDisplayOptionsChooser(project: Project, enum: RsEnumItem, canGenerateFromStr: Boolean): DisplayOptions? {
+    val base = MemberChooserObjectBase(enum.name, enum.getIcon(0))
+    val variants = enum.variants.map { RsEnumVariantChooserObject(base, it) }
+    return getUi().chooseOptions(project, variants, canGenerateFromStr)
+}
+
+fun confirmDisplayUpdate(project: Project, enumName: String, newVariants: List<String>): Boolean =
+    getUi().confirmUpdate(project, enumName, newVariants)
+
+private fun getUi(): GenerateDisplayUi = if (isUnitTestMode) {
+    MOCK ?: error("You should set mock ui via `withMockGenerateDisplayUi`")
+} else {
+    DialogGenerateDisplayUi
+}
+
+@TestOnly
+fun withMockGenerateDisplayUi(mockUi: GenerateDisplayUi, action: () -> Unit) {
+    MOCK = mockUi
+    try {
+        action()
+    } finally {
+        MOCK = null
+    }
+}
+
+class RsEnumVariantChooserObject(
+    val base: MemberChooserObjectBase,
+    val variant: RsEnumVariant
+) : MemberChooserObjectBase(variant.name, RsIcons.ENUM_VARIANT),
+    ClassMember {
+
+    override fun getParentNodeDelegate(): MemberChooserObject? = base
+    override fun equals(other: Any?): Boolean = variant == (other as? RsEnumVariantChooserObject)?.variant
+    override fun hashCode() = text.hashCode()
+}
+
+interface GenerateDisplayUi {
+    fun chooseOptions(
+        project: Project,
+        all: List<RsEnumVariantChooserObject>,
+        canGenerateFromStr: Boolean
+    ): DisplayOptions?
+
+    fun confirmUpdate(project: Project, enumName: String, newVariants: List<String>): Boolean
+}
+
+private object DialogGenerateDisplayUi : GenerateDisplayUi {
+    private const val GENERATE_ERROR_KEY: String = "org.rust.generate.display.error"
+    private const val GENERATE_FROM_STR_KEY: String = "org.rust.generate.display.from.str"
+
+    override fun chooseOptions(
+        project: Project,
+        all: List<RsEnumVariantChooserObject>,
+        canGenerateFromStr: Boolean
+    ): DisplayOptions? {
+        val properties = PropertiesComponent.getInstance()
+        val errorCheckbox = JBCheckBox("Implement std::error::Error", properties.getBoolean(GENERATE_ERROR_KEY, true))
+        val fromStrCheckbox = JBCheckBox("Implement FromStr", properties.getBoolean(GENERATE_FROM_STR_KEY, false)).apply {
+            isEnabled = canGenerateFromStr
+        }
+        val chooser = MemberChooser(
+            all.toTypedArray(),
+            true,
+            true,
+            project,
+            null,
+            arrayOf<JComponent>(errorCheckbox, fromStrCheckbox)
+        ).apply {
+            title = "Select Variants to Describe"
+            selectElements(all.toTypedArray())
+            setCopyJavadocVisible(false)
+        }
+        if (!chooser.showAndGet()) return null
+        properties.setValue(GENERATE_ERROR_KEY, errorCheckbox.isSelected, true)
+        if (canGenerateFromStr) properties.setValue(GENERATE_FROM_STR_KEY, fromStrCheckbox.isSelected, false)
+        val selected = chooser.selectedElements.orEmpty().map { it.variant }
+        return DisplayOptions(selected, errorCheckbox.isSelected, canGenerateFromStr && fromStrCheckbox.isSelected)
+    }
+
+    override fun confirmUpdate(project: Project, enumName: String, newVariants: List<String>): Boolean {
+        val answer = Messages.showYesNoDialog(
+            project,
+            "Impls for `$enumName` already exist. Add match arms for ${newVariants.joinToString { "`$it`" }}?",
+            "Generate Display",
+            Messages.getQuestionIcon()
+        )
+        return answer == Messages.YES
+    }
+}
@@ -12,6 +12,7 @@ import com.intellij.openapi.editor.ScrollType
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiWhiteSpace
 import org.rust.ide.inspections.import.RsImportHelper.importTypeReferencesFromElements
+import org.rust.ide.presentation.renderInsertionSafe
 import org.rust.ide.presentation.shortPresentableText
 import org.rust.lang.core.macros.expandedFromRecursively
 import org.rust.lang.core.psi.*
@@ -19,11 +20,11 @@ import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.resolve.knownItems
 import org.rust.lang.core.types.BoundElement
 import org.rust.lang.core.types.Substitution
+import org.rust.lang.core.types.infer.containsTyOfClass
 import org.rust.lang.core.types.infer.resolve
 import org.rust.lang.core.types.infer.substitute
 import org.rust.lang.core.types.regions.ReUnknown
-import org.rust.lang.core.types.ty.Mutability
-import org.rust.lang.core.types.ty.TyUnknown
+import org.rust.lang.core.types.ty.*
 import org.rust.lang.core.types.type
 import org.rust.openapiext.checkReadAccessAllowed
 import org.rust.openapiext.checkWriteAccessAllowed
@@ -159,6 +160,7 @@ private fun RsPsiFactory.createTraitMembers(
     impl: RsImplItem
 ): RsMembers {
     val subst = trait.subst
+    val inferredTypes = inferAssociatedTypes(trait.element, impl)
     val body = members.joinToString(separator = "\n", transform = {
         when (it) {
             is RsConstant -> {
@@ -166,10 +168,17 @@ private fun RsPsiFactory.createTraitMembers(
                     .buildFor(it.typeReference?.type?.substitute(subst) ?: TyUnknown, emptyMap())
                 "    const ${it.nameLikeElement.text}: ${it.typeReference?.substAndGetText(subst) ?: "_"} = ${initialValue.text};"
             }
-            is RsTypeAlias ->
-                "    type ${it.escapedName} = ();"
+            is RsTypeAlias -> {
+                val generics = it.typeParameterList?.substTypesAndGetText(subst) ?: ""
+                val where = it.whereClause?.substTypesAndGetText(subst)?.let { where -> " $where" } ?: ""
+                // The type inferred from the implemented members, the default from the trait or a placeholder
+                val value = inferredTypes[it]?.renderInsertionSafe(includeLifetimeArguments = true, useAliasNames = true)
+                    ?: it.typeReference?.substAndGetText(subst)
+                    ?: "()"
+                "    type ${it.escapedName}$generics$where = $value;"
+            }
             is RsFunction ->
-                "    ${it.getSignatureText(subst, trait, impl) ?: ""}{\n        unimplemented!()\n    }"
+                "    ${it.getSignatureText(subst, trait, impl) ?: ""}{\n        ${it.getBodyText(impl, this)}\n    }"
             else ->
                 error("Unknown trait member")
         }
@@ -178,6 +187,82 @@ private fun RsPsiFactory.createTraitMembers(
     return createMembers(body)
 }
 
+/**
+ * Functions returning `Self` get a struct literal with all the fields of the implementing struct,
+ * if the struct is declared in the current crate and can be instantiated
+ */
+private fun RsFunction.getBodyText(impl: RsImplItem, factory: RsPsiFactory): String {
+    val placeholder = "unimplemented!()"
+    val returnsSelf = (retType?.typeReference?.skipParens() as? RsBaseType)?.path?.hasCself == true
+    if (!returnsSelf) return placeholder
+    val selfType = impl.typeReference?.type as? TyAdt ?: return placeholder
+    val struct = selfType.item as? RsStructItem ?: return placeholder
+    if (struct.kind != RsStructKind.STRUCT || struct.containingCrate != impl.containingCrate) return placeholder
+    val mod = impl.containingMod
+    if (!struct.canBeInstantiatedIn(mod)) return placeholder
+
+    val builder = RsDefaultValueBuilder(impl.knownItems, mod, factory)
+    fun RsFieldDecl.defaultValueText(): String =
+        builder.buildFor(typeReference?.type?.substitute(selfType.typeParameterValues) ?: TyUnknown, emptyMap()).text
+
+    val blockFields = struct.blockFields
+    val tupleFields = struct.tupleFields
+    return when {
+        blockFields != null -> {
+            val fields = blockFields.namedFieldDeclList
+            if (fields.isEmpty()) {
+                "Self {}"
+            } else {
+                fields.joinToString(separator = "", prefix = "Self {\n", postfix = "        }") { field ->
+                    "            ${field.escapedName ?: "_"}: ${field.defaultValueText()},\n"
+                }
+            }
+        }
+        tupleFields != null -> tupleFields.tupleFieldDeclList.joinToString(prefix = "Self(", postfix = ")") { field ->
+            field.defaultValueText()
+        }
+        else -> "Self"
+    }
+}
+
+/**
+ * Infers associated types from the members which are already implemented, e.g. `Item = u32` for
+ * `fn next(&mut self) -> Option<u32>` if the trait declares `fn next(&mut self) -> Option<Self::Item>`
+ */
+private fun inferAssociatedTypes(trait: RsTraitItem, impl: RsImplItem): Map<RsTypeAlias, Ty> {
+    val result = mutableMapOf<RsTypeAlias, Ty>()
+    val traitFunctions = trait.expandedMembers.filterIsInstance<RsFunction>().associateBy { it.name }
+    for (implFunction in impl.expandedMembers.filterIsInstance<RsFunction>()) {
+        val traitFunction = traitFunctions[implFunction.name] ?: continue
+        val traitTypes = listOf(traitFunction.retType?.typeReference) + traitFunction.valueParameters.map { it.typeReference }
+        val implTypes = listOf(implFunction.retType?.typeReference) + implFunction.valueParameters.map { it.typeReference }
+        for ((traitType, implType) in traitTypes.zip(implTypes)) {
+            if (traitType == null || implType == null) continue
+            matchAssociatedTypes(traitType.type, implType.type, result)
+        }
+    }
+    return result
+}
+
+private fun matchAssociatedTypes(traitTy: Ty, implTy: Ty, result: MutableMap<RsTypeAlias, Ty>) {
+    when {
+        traitTy is TyProjection -> {
+            val isSelfProjection = (traitTy.type as? TyTypeParameter)?.parameter is TyTypeParameter.Self
+            if (isSelfProjection && !implTy.containsTyOfClass(TyUnknown::class.java, TyProjection::class.java)) {
+                result.putIfAbsent(traitTy.target, implTy)
+            }
+        }
+        traitTy is TyAdt && implTy is TyAdt && traitTy.item == implTy.item ->
+            traitTy.typeArguments.zip(implTy.typeArguments).forEach { (t, i) -> matchAssociatedTypes(t, i, result) }
+        traitTy is TyReference && implTy is TyReference -> matchAssociatedTypes(traitTy.referenced, implTy.referenced, result)
+        traitTy is TyPointer && implTy is TyPointer -> matchAssociatedTypes(traitTy.referenced, implTy.referenced, result)
+        traitTy is TySlice && implTy is TySlice -> matchAssociatedTypes(traitTy.elementType, implTy.elementType, result)
+        traitTy is TyArray && implTy is TyArray -> matchAssociatedTypes(traitTy.base, implTy.base, result)
+        traitTy is TyTuple && implTy is TyTuple ->
+            traitTy.types.zip(implTy.types).forEach { (t, i) -> matchAssociatedTypes(t, i, result) }
+    }
+}
+
 private fun RsFunction.getSignatureText(
     subst: Substitution,
     trait: BoundElement<RsTraitItem>,
@@ -188,7 +273,7 @@ private fun RsFunction.getSignatureText(
     // We can't simply take a substring of original method declaration
     // because of anonymous parameters.
     val name = escapedName ?: return null
-    val generics = typeParameterList?.text ?: ""
+    val generics = typeParameterList?.substTypesAndGetText(subst) ?: ""
 
     val selfArgument = listOfNotNull(selfParameter?.substAndGetText(subst))
     val typeParameters = trait.element.typeParameters.mapIndexed { index, typeParameter ->
@@ -211,7 +296,7 @@ private fun RsFunction.getSignatureText(
     val allArguments = selfArgument + valueArguments
 
     val ret = retType?.typeReference?.substAndGetText(subst)?.let { "-> $it " } ?: ""
-    val where = whereClause?.text ?: ""
+    val where = whereClause?.substTypesAndGetText(subst)?.let { "$it " } ?: ""
     return "${async}${unsafe}fn $name$generics(${allArguments.joinToString(",")}) $ret$where"
 }
 
@@ -232,3 +317,16 @@ private fun RsSelfParameter.substAndGetText(subst: Substitution): String =
             append(self.text)
         }
     }
+
+/**
+ * Returns the text of the element where the types depending on the trait type parameters are substituted,
+ * e.g. `where U: Into<i32>` for `where U: Into<T>` in `impl Foo<i32> for S`
+ */
+private fun PsiElement.substTypesAndGetText(subst: Substitution): String {
+    if (this is RsTypeReference) {
+        val type = type
+        return if (type.substitute(subst) == type) text else substAndGetText(subst)
+    }
+    if (firstChild == null) return text
+    return childrenWithLeaves.joinToString(separator = "") { it.substTypesAndGetText(subst) }
+}
@@ -0,0 +1,54 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.inline
+
+import com.intellij.lang.Language
+import com.intellij.lang.refactoring.InlineActionHandler
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.refactoring.util.CommonRefactoringUtil
+import org.rust.lang.RsLanguage
+import org.rust.lang.core.psi.RsFile
+import org.rust.lang.core.psi.RsModDeclItem
+import org.rust.lang.core.psi.ext.descendantsOfType
+import org.rust.lang.core.psi.ext.pathAttribute
+
+/** Inlines `mod foo;` declaration, see [RsInlineModuleProcessor] */
+class RsInlineModuleHandler : InlineActionHandler() {
+    override fun isEnabledForLanguage(l: Language?): Boolean = l == RsLanguage
+
+    override fun canInlineElement(element: PsiElement): Boolean =
+        element is RsModDeclItem || element is RsFile && element.declaration != null
+
+    override fun canInlineElementInEditor(element: PsiElement, editor: Editor?): Boolean = canInlineElement(element)
+
+    override fun inlineElement(project: Project, editor: Editor, element: PsiElement) {
+        val decl = when (element) {
+            is RsModDeclItem -> element
+            is RsFile -> element.declaration
+            else -> null
+        } ?: return
+        val file = decl.reference.resolve() as? RsFile
+
+        when {
+            file == null -> errorHint(project, editor, "Cannot inline module without a file")
+            !file.isWritable -> errorHint(project, editor, "Cannot inline module from a read-only file")
+            decl.pathAttribute != null -> errorHint(project, editor, "Cannot inline module with `#[path]` attribute")
+            file.descendantsOfType<RsModDeclItem>().isNotEmpty() ->
+                errorHint(project, editor, "Cannot inline module with child modules in other files")
+            else -> {
+                val processor = RsInlineModuleProcessor(project, decl, file)
+                processor.setPreviewUsages(false)
+                processor.run()
+            }
+        }
+    }
+
+    private fun errorHint(project: Project, editor: Editor, message: String) {
+        CommonRefactoringUtil.showErrorHint(project, editor, message, "Inline Module", null)
+    }
+}
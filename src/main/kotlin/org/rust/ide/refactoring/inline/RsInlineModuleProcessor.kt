@@ -0,0 +1,70 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.inline
+
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.Ref
+import com.intellij.psi.PsiElement
+import com.intellij.psi.codeStyle.CodeStyleManager
+import com.intellij.refactoring.BaseRefactoringProcessor
+import com.intellij.usageView.BaseUsageViewDescriptor
+import com.intellij.usageView.UsageInfo
+import com.intellij.usageView.UsageViewDescriptor
+import com.intellij.util.containers.MultiMap
+import org.rust.lang.RsConstants
+import org.rust.lang.core.psi.RsFile
+import org.rust.lang.core.psi.RsModDeclItem
+import org.rust.lang.core.psi.RsPsiFactory
+
+/**
+ * Moves the contents of a module file into an inline module and deletes the file:
+ * ```
+ * mod parser;              mod parser {
+ *                     =>       fn parse() {}
+ * // parser.rs             }
+ * fn parse() {}
+ * ```
+ * The module path doesn't change, so no paths have to be updated.
+ *
+ * @see org.rust.ide.refactoring.move.RsMoveModuleToFileProcessor
+ */
+class RsInlineModuleProcessor(
+    project: Project,
+    private val decl: RsModDeclItem,
+    private val file: RsFile
+) : BaseRefactoringProcessor(project) {
+
+    override fun findUsages(): Array<UsageInfo> = UsageInfo.EMPTY_ARRAY
+
+    override fun preprocessUsages(refUsages: Ref<Array<UsageInfo>>): Boolean {
+        val conflicts = MultiMap<PsiElement, String>()
+        val otherDeclarations = file.declarations.filter { it != decl }
+        for (otherDecl in otherDeclarations) {
+            conflicts.putValue(otherDecl, "File `${file.name}` is also included by this module declaration")
+        }
+        return showConflicts(conflicts, refUsages.get())
+    }
+
+    override fun performRefactoring(usages: Array<out UsageInfo>) {
+        // Outer attributes (including `#[cfg(test)]`), doc comments and visibility of the declaration are kept,
+        // inner attributes and doc comments of the file become inner attributes of the inline module
+        val header = decl.text.removeSuffix(";").trimEnd()
+        val mod = RsPsiFactory(myProject).createModItem("$header {\n${file.text.trim()}\n}")
+        val inlineMod = decl.replace(mod)
+        CodeStyleManager.getInstance(myProject).adjustLineIndent(inlineMod.containingFile, inlineMod.textRange)
+
+        val directory = file.containingDirectory
+        file.delete()
+        if (file.name == RsConstants.MOD_RS_FILE && directory != null && directory.children.isEmpty()) {
+            directory.delete()
+        }
+    }
+
+    override fun createUsageViewDescriptor(usages: Array<out UsageInfo>): UsageViewDescriptor =
+        BaseUsageViewDescriptor(decl)
+
+    override fun getCommandName(): String = "Inline module `${decl.name}`"
+}
@@ -0,0 +1,49 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.move
+
+import com.intellij.openapi.actionSystem.DataContext
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiFile
+import org.rust.ide.refactoring.RsBaseEditorRefactoringAction
+import org.rust.lang.core.macros.isExpandedFromMacro
+import org.rust.lang.core.psi.RsBlock
+import org.rust.lang.core.psi.RsModItem
+import org.rust.lang.core.psi.ext.ancestorOrSelf
+import org.rust.lang.core.psi.ext.ancestorStrict
+
+class RsMoveModuleToFileAction : RsBaseEditorRefactoringAction() {
+    override fun isAvailableOnElementInEditorAndFile(
+        element: PsiElement,
+        editor: Editor,
+        file: PsiFile,
+        context: DataContext
+    ): Boolean =
+        findApplicableContext(editor, file) != null
+
+    override fun invoke(project: Project, editor: Editor, file: PsiFile, dataContext: DataContext?) {
+        val mod = findApplicableContext(editor, file) ?: return
+        val processor = RsMoveModuleToFileProcessor(project, mod)
+        processor.setPreviewUsages(false)
+        processor.run()
+    }
+
+    companion object {
+        private fun findApplicableContext(editor: Editor, file: PsiFile): RsModItem? {
+            val offset = editor.caretModel.offset
+            val mod = file.findElementAt(offset)?.ancestorOrSelf<RsModItem>() ?: return null
+            // Available only on the module header, e.g. on its name
+            if (offset > mod.lbrace.textOffset) return null
+            if (mod.name == null || mod.isExpandedFromMacro) return null
+            // `#[path]` of an inline module specifies the directory of its child modules, not the module file.
+            // Module files can't be declared inside function bodies
+            if (mod.pathAttribute != null || mod.ancestorStrict<RsBlock>() != null) return null
+            return mod
+        }
+    }
+}
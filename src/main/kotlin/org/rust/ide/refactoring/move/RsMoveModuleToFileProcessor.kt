@@ -0,0 +1,117 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.move
+
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.Ref
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiWhiteSpace
+import com.intellij.psi.codeStyle.CodeStyleManager
+import com.intellij.refactoring.BaseRefactoringProcessor
+import com.intellij.usageView.BaseUsageViewDescriptor
+import com.intellij.usageView.UsageInfo
+import com.intellij.usageView.UsageViewDescriptor
+import com.intellij.util.containers.MultiMap
+import org.rust.lang.RsConstants
+import org.rust.lang.core.psi.RsFile
+import org.rust.lang.core.psi.RsModDeclItem
+import org.rust.lang.core.psi.RsModItem
+import org.rust.lang.core.psi.RsPsiFactory
+import org.rust.lang.core.psi.ext.RsMod
+import org.rust.lang.core.psi.ext.expandedItemsExceptImplsAndUses
+import org.rust.lang.core.psi.ext.isEdition2018
+import org.rust.lang.core.psi.ext.leftSiblings
+import org.rust.lang.core.psi.ext.rightSiblings
+
+/**
+ * Moves the contents of an inline module to a separate file:
+ * ```
+ * #[cfg(test)]                 #[cfg(test)]
+ * mod tests {                  mod tests;
+ *     #[test]          =>
+ *     fn foo() {}              // tests.rs
+ * }                            #[test]
+ *                              fn foo() {}
+ * ```
+ * The file is created either as `tests.rs` or as `tests/mod.rs`, depending on the layout of sibling file modules.
+ * The module path doesn't change, so `super::`, `self::` and `crate::` paths inside and outside of the module
+ * keep their meaning and don't need to be updated.
+ *
+ * @see org.rust.ide.refactoring.inline.RsInlineModuleProcessor
+ */
+class RsMoveModuleToFileProcessor(
+    project: Project,
+    private val mod: RsModItem
+) : BaseRefactoringProcessor(project) {
+
+    private val name: String = mod.name ?: error("Module without name")
+    private val useModRsLayout: Boolean = mod.`super`.prefersModRsLayout
+
+    /** The path of the new file relative to the directory owned by the parent module */
+    private val filePath: String
+        get() = if (useModRsLayout) "$name/${RsConstants.MOD_RS_FILE}" else "$name.rs"
+
+    override fun findUsages(): Array<UsageInfo> = UsageInfo.EMPTY_ARRAY
+
+    override fun preprocessUsages(refUsages: Ref<Array<UsageInfo>>): Boolean {
+        val conflicts = MultiMap<PsiElement, String>()
+
+        val parentMod = mod.`super`
+        val directory = parentMod.getOwnedDirectory()?.virtualFile
+        for (path in listOf("$name.rs", "$name/${RsConstants.MOD_RS_FILE}")) {
+            if (directory?.findFileByRelativePath(path) != null) {
+                conflicts.putValue(mod, "File `$path` already exists")
+            }
+        }
+
+        if (parentMod is RsFile && !parentMod.isCrateRoot && parentMod.name != RsConstants.MOD_RS_FILE && !mod.isEdition2018) {
+            conflicts.putValue(mod, "Module files can't be declared in `${parentMod.name}` in Rust 2015, only in `mod.rs` files")
+        }
+
+        return showConflicts(conflicts, refUsages.get())
+    }
+
+    override fun performRefactoring(usages: Array<out UsageInfo>) {
+        val directory = mod.`super`.getOwnedDirectory(createIfNotExists = true) ?: return
+        val file = if (useModRsLayout) {
+            directory.createSubdirectory(name).createFile(RsConstants.MOD_RS_FILE)
+        } else {
+            directory.createFile("$name.rs")
+        }
+
+        // Inner attributes and doc comments (`#![allow(...)]`, `//! ...`) are moved along with the items
+        val rbrace = mod.rbrace
+        val first = mod.lbrace.rightSiblings.find { it !is PsiWhiteSpace }?.takeIf { it != rbrace }
+        val last = rbrace?.leftSiblings?.find { it !is PsiWhiteSpace }?.takeIf { it != mod.lbrace }
+        if (first != null && last != null) {
+            file.addRange(first, last)
+            CodeStyleManager.getInstance(myProject).adjustLineIndent(file, file.textRange)
+        }
+
+        // Outer attributes (including `#[cfg(test)]`), doc comments and visibility stay on the declaration
+        val decl = RsPsiFactory(myProject).createModDeclItem(name)
+        if (mod.firstChild != mod.mod) {
+            decl.addRangeBefore(mod.firstChild, mod.mod.prevSibling, decl.mod)
+        }
+        mod.replace(decl)
+    }
+
+    override fun createUsageViewDescriptor(usages: Array<out UsageInfo>): UsageViewDescriptor =
+        BaseUsageViewDescriptor(mod)
+
+    override fun getCommandName(): String = "Move module `$name` to `$filePath`"
+}
+
+/**
+ * `true` if most of file modules declared in this module use `foo/mod.rs` layout rather than `foo.rs`
+ */
+private val RsMod.prefersModRsLayout: Boolean
+    get() {
+        val files = expandedItemsExceptImplsAndUses
+            .filterIsInstance<RsModDeclItem>()
+            .mapNotNull { it.reference.resolve() as? RsFile }
+        return files.count { it.name == RsConstants.MOD_RS_FILE } * 2 > files.size
+    }
@@ -13,7 +13,7 @@ import org.rust.lang.core.psi.ext.*
 
 class RsReadWriteAccessDetector : ReadWriteAccessDetector() {
     override fun isReadWriteAccessible(element: PsiElement): Boolean =
-        element is RsPatBinding || element is RsFieldDecl
+        element is RsPatBinding || element is RsFieldDecl || (element is RsConstant && !element.isConst)
 
     override fun getReferenceAccess(referencedElement: PsiElement, reference: PsiReference): Access =
         getExpressionAccess(reference.element)
@@ -41,6 +41,9 @@ class RsReadWriteAccessDetector : ReadWriteAccessDetector() {
             }
         } else if (context is RsUnaryExpr && context.isDereference) {
             getExpressionAccess(context)
+        } else if (context is RsUnaryExpr && context.operatorType == UnaryOperator.REF_MUT) {
+            // `&mut a` is considered as a write because `a` can be modified through the reference
+            Access.Write
         } else {
             Access.Read
         }
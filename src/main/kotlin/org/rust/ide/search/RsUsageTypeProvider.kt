@@ -11,6 +11,7 @@ import com.intellij.usages.impl.rules.UsageType
 import com.intellij.usages.impl.rules.UsageTypeProviderEx
 import org.rust.lang.core.macros.findExpansionElements
 import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
 
 object RsUsageTypeProvider : UsageTypeProviderEx {
     // Instantiate each UsageType only once, so that the equality check in UsageTypeGroup.equals() works correctly
@@ -18,11 +19,14 @@ object RsUsageTypeProvider : UsageTypeProviderEx {
 
     private val TYPE_REFERENCE = UsageType { "type reference" }
     private val TRAIT_REFERENCE = UsageType { "trait reference" }
+    private val TRAIT_BOUND = UsageType { "trait bound" }
+    private val RECEIVER_TYPE = UsageType { "receiver type" }
 
     private val EXPR = UsageType { "expr" }
     private val DOT_EXPR = UsageType { "dot expr" }
 
     private val FUNCTION_CALL = UsageType { "function call" }
+    private val FUNCTION_REFERENCE = UsageType { "function reference" }
     private val METHOD_CALL = UsageType { "method call" }
     private val ARGUMENT = UsageType { "argument" }
 
@@ -33,41 +37,62 @@ object RsUsageTypeProvider : UsageTypeProviderEx {
     private val INIT_FIELD = UsageType { "init field" }
 
     private val PAT_BINDING = UsageType { "variable binding" }
+    private val PATTERN = UsageType { "pattern" }
 
     private val FIELD = UsageType { "field" }
 
     private val META_ITEM = UsageType { "meta item" }
 
     private val USE = UsageType { "use" }
+    private val REEXPORT = UsageType { "re-export" }
     private val MOD = UsageType { "mod" }
 
     override fun getUsageType(element: PsiElement?): UsageType? = getUsageType(element, UsageTarget.EMPTY_ARRAY)
 
     override fun getUsageType(element: PsiElement?, targets: Array<out UsageTarget>): UsageType? {
         val refinedElement = element?.findExpansionElements()?.firstOrNull()?.parent ?: element
+        if (refinedElement is RsPath) {
+            getQualifierUsageType(refinedElement)?.let { return it }
+        }
         val parent = refinedElement?.goUp<RsPath>() ?: return null
         return when (parent) {
             is RsBaseType -> when (parent.parent) {
                 is RsImplItem -> IMPL
                 else -> TYPE_REFERENCE
             }
-            is RsPathExpr -> when (parent.goUp<RsPathExpr>()) {
-                is RsDotExpr -> DOT_EXPR
-                is RsCallExpr -> FUNCTION_CALL
-                is RsValueArgumentList -> ARGUMENT
-                is RsFormatMacroArg -> MACRO_ARGUMENT
-                is RsExpr -> EXPR
-                else -> null
+            is RsPathExpr -> {
+                val context = parent.goUp<RsPathExpr>()
+                val resolved = parent.path.reference?.resolve()
+                when {
+                    context is RsDotExpr -> DOT_EXPR
+                    context is RsCallExpr -> if (resolved is RsFieldsOwner) INIT_STRUCT else FUNCTION_CALL
+                    context is RsPatConst -> PATTERN
+                    context is RsFormatMacroArg || context is RsAsmOperand -> MACRO_ARGUMENT
+                    // `foo` in `bar(foo)` or `let f = foo;`
+                    resolved is RsFunction -> FUNCTION_REFERENCE
+                    // Unit struct or enum variant: `let s = S;`
+                    resolved is RsFieldsOwner -> INIT_STRUCT
+                    context is RsValueArgumentList -> ARGUMENT
+                    context is RsExpr -> EXPR
+                    else -> null
+                }
             }
-            is RsUseSpeck -> USE
+            is RsUseSpeck -> if (parent.ancestorStrict<RsUseItem>()?.isPublic == true) REEXPORT else USE
             is RsStructLiteral -> INIT_STRUCT
             is RsStructLiteralField -> INIT_FIELD
-            is RsTraitRef -> TRAIT_REFERENCE
+            is RsPatStruct, is RsPatTupleStruct -> PATTERN
+            is RsTraitRef -> when (parent.parent) {
+                is RsImplItem -> TRAIT_REFERENCE
+                else -> TRAIT_BOUND
+            }
             is RsMethodCall -> METHOD_CALL
             is RsMetaItem -> META_ITEM
             is RsFieldLookup -> FIELD
             is RsMacroCall -> MACRO_CALL
-            is RsPatBinding -> PAT_BINDING
+            // `None` in `let None = x;` is a reference to the variant, not a new binding
+            is RsPatBinding -> if (parent.isReferenceToConstant) PATTERN else PAT_BINDING
+            // An identifier in a macro call body that can't be mapped to the expansion
+            is RsMacroBodyIdent -> MACRO_ARGUMENT
             else -> when (parent.parent) {
                 is RsModDeclItem -> MOD
                 else -> null
@@ -75,6 +100,14 @@ object RsUsageTypeProvider : UsageTypeProviderEx {
         }
     }
 
+    /** `S` in `S::new()` */
+    private fun getQualifierUsageType(path: RsPath): UsageType? {
+        val qualified = path.parent as? RsPath ?: return null
+        if (path.reference?.resolve() !is RsTypeDeclarationElement) return null
+        if (qualified.parent !is RsPathExpr) return null
+        return if (qualified.reference?.resolve() is RsFunction) RECEIVER_TYPE else null
+    }
+
     private inline fun <reified T : PsiElement> PsiElement.goUp(): PsiElement {
         var context = this
         while (context is T) {
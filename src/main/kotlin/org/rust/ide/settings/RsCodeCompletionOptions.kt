@@ -15,20 +15,30 @@ import javax.swing.JPanel
 class RsCodeCompletionConfigurable : CodeCompletionOptionsCustomSection {
     private val suggestOutOfScopeItemsCheckbox: JBCheckBox = JBCheckBox("Suggest out of scope items")
     private var suggestOutOfScopeItems: Boolean by CheckboxDelegate(suggestOutOfScopeItemsCheckbox)
+    private val suggestDocHiddenItemsCheckbox: JBCheckBox =
+        JBCheckBox("Suggest items marked with #[doc(hidden)] from other modules")
+    private var suggestDocHiddenItems: Boolean by CheckboxDelegate(suggestDocHiddenItemsCheckbox)
 
     override fun createComponent(): JPanel = panel {
         row { suggestOutOfScopeItemsCheckbox() }
+        row { suggestDocHiddenItemsCheckbox() }
     }.apply { border = IdeBorderFactory.createTitledBorder("Rust") }
 
     override fun isModified(): Boolean {
-        return suggestOutOfScopeItems != RsCodeInsightSettings.getInstance().suggestOutOfScopeItems
+        val settings = RsCodeInsightSettings.getInstance()
+        return suggestOutOfScopeItems != settings.suggestOutOfScopeItems ||
+            suggestDocHiddenItems != settings.suggestDocHiddenItems
     }
 
     override fun apply() {
-        RsCodeInsightSettings.getInstance().suggestOutOfScopeItems = suggestOutOfScopeItems
+        val settings = RsCodeInsightSettings.getInstance()
+        settings.suggestOutOfScopeItems = suggestOutOfScopeItems
+        settings.suggestDocHiddenItems = suggestDocHiddenItems
     }
 
     override fun reset() {
-        suggestOutOfScopeItems = RsCodeInsightSettings.getInstance().suggestOutOfScopeItems
+        val settings = RsCodeInsightSettings.getInstance()
+        suggestOutOfScopeItems = settings.suggestOutOfScopeItems
+        suggestDocHiddenItems = settings.suggestDocHiddenItems
     }
 }
@@ -8,12 +8,17 @@ package org.rust.ide.structure
 import com.intellij.ide.structureView.StructureViewModel
 import com.intellij.ide.structureView.StructureViewModelBase
 import com.intellij.ide.structureView.StructureViewTreeElement
+import com.intellij.ide.util.EditSourceUtil
 import com.intellij.ide.util.treeView.smartTree.TreeElement
 import com.intellij.navigation.ItemPresentation
 import com.intellij.openapi.editor.Editor
 import com.intellij.pom.Navigatable
 import com.intellij.psi.NavigatablePsiElement
 import org.rust.ide.presentation.getPresentationForStructure
+import org.rust.lang.core.macros.MacroExpansionManager
+import org.rust.lang.core.macros.findMacroCallExpandedFrom
+import org.rust.lang.core.macros.findNavigationTargetIfMacroExpansion
+import org.rust.lang.core.macros.isExpandedFromMacro
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
 import org.rust.stdext.buildList
@@ -42,15 +47,59 @@ class RsStructureViewModel(editor: Editor?, file: RsFileBase)
 }
 
 private class RsStructureViewElement(
-    val psi: RsElement
+    val psi: RsElement,
+    /** The ordinal number of the item among the items with the same name expanded from macros, if there are several */
+    private val expansionOrdinal: Int? = null
 ) : StructureViewTreeElement, Navigatable by (psi as NavigatablePsiElement) {
 
     override fun getValue(): RsElement = psi
 
-    override fun getPresentation(): ItemPresentation = getPresentationForStructure(psi)
+    override fun getPresentation(): ItemPresentation {
+        val presentation = getPresentationForStructure(psi)
+        val macroCall = psi.findMacroCallExpandedFrom()
+        if (macroCall != null) {
+            presentation.locationString = buildString {
+                append("${macroCall.macroName}!")
+                if (expansionOrdinal != null) append(" #$expansionOrdinal")
+            }
+        }
+        return presentation
+    }
 
-    override fun getChildren(): Array<TreeElement> =
-        childElements.map(::RsStructureViewElement).toTypedArray()
+    // Items expanded from macros don't have real source, so we navigate to the macro call.
+    // Note that items of `include!` are located in the included file, so they are navigated as usual
+    override fun navigate(requestFocus: Boolean) {
+        val file = psi.containingFile.virtualFile
+        val target = if (file == null || MacroExpansionManager.isExpansionFile(file)) {
+            psi.findNavigationTargetIfMacroExpansion()
+        } else {
+            null
+        }
+        if (target != null) {
+            EditSourceUtil.getDescriptor(target)?.navigate(requestFocus)
+        } else {
+            (psi as NavigatablePsiElement).navigate(requestFocus)
+        }
+    }
+
+    override fun getChildren(): Array<TreeElement> {
+        val children = childElements
+        // Disambiguate items with the same name, e.g. from repeated expansions of the same macro
+        val duplicateNames = children
+            .filter { it.isExpandedFromMacro }
+            .groupBy { (it as? RsNamedElement)?.name }
+            .filter { (name, items) -> name != null && items.size > 1 }
+        val ordinals = hashMapOf<String, Int>()
+        return children.map { child ->
+            val name = (child as? RsNamedElement)?.name
+            val ordinal = if (name != null && name in duplicateNames && child.isExpandedFromMacro) {
+                ordinals.merge(name, 1) { a, b -> a + b }
+            } else {
+                null
+            }
+            RsStructureViewElement(child, ordinal)
+        }.toTypedArray()
+    }
 
     private val childElements: List<RsElement>
         get() {
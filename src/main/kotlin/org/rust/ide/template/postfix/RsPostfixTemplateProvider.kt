@@ -36,7 +36,8 @@ class RsPostfixTemplateProvider : PostfixTemplateProvider {
         DbgPostfixTemplate(this),
         OkPostfixTemplate(this),
         SomePostfixTemplate(this),
-        ErrPostfixTemplate(this)
+        ErrPostfixTemplate(this),
+        ArcPostfixTemplate(this)
     )
 
     override fun getTemplates(): Set<PostfixTemplate> = templates
@@ -9,13 +9,21 @@ import com.intellij.codeInsight.template.Template
 import com.intellij.codeInsight.template.impl.TextExpression
 import com.intellij.codeInsight.template.postfix.templates.StringBasedPostfixTemplate
 import com.intellij.psi.PsiElement
+import org.rust.ide.inspections.checkMatch.Pattern
+import org.rust.ide.inspections.checkMatch.allConstructorPatterns
 import org.rust.lang.core.psi.RsBinaryExpr
+import org.rust.lang.core.psi.RsEnumItem
 import org.rust.lang.core.psi.RsExpr
+import org.rust.lang.core.psi.RsPsiFactory
 import org.rust.lang.core.psi.ext.EqualityOp
+import org.rust.lang.core.psi.ext.findInScope
 import org.rust.lang.core.psi.ext.operatorType
+import org.rust.lang.core.resolve.TYPES
 import org.rust.lang.core.types.implLookup
+import org.rust.lang.core.types.ty.TyAdt
 import org.rust.lang.core.types.ty.TyPointer
 import org.rust.lang.core.types.ty.TyReference
+import org.rust.lang.core.types.ty.stripReferences
 import org.rust.lang.core.types.type
 
 abstract class AssertPostfixTemplateBase(
@@ -84,17 +92,31 @@ class DerefPostfixTemplate(provider: RsPostfixTemplateProvider) :
 }
 
 class MatchPostfixTemplate(provider: RsPostfixTemplateProvider) :
-    StringBasedPostfixTemplate("match", "match expr {...}", RsTopMostInScopeSelector(), provider) {
-
-    override fun getTemplateString(element: PsiElement): String = "match ${element.text} {\n\$PAT\$ => {\$END\$}\n}"
-
-    override fun getElementToRemove(expr: PsiElement): PsiElement = expr
+    StringBasedPostfixTemplate(
+        "match",
+        "match expr {...}",
+        RsTopMostInScopeSelector { it.matchedEnum != null },
+        provider
+    ) {
 
-    override fun setVariables(template: Template, element: PsiElement) {
-        with(template) {
-            addVariable("PAT", TextExpression("_"), true)
+    override fun getTemplateString(element: PsiElement): String {
+        val expr = element as RsExpr
+        val enumType = expr.type.stripReferences() as? TyAdt
+        val patterns = if (enumType != null && enumType.item is RsEnumItem) {
+            allConstructorPatterns(enumType)
+        } else {
+            listOf(Pattern.Wild)
+        }
+        // The arms are the same as the ones added by "Add remaining patterns" quick fix to an empty match
+        val matchArms = RsPsiFactory(expr.project).createMatchBody(patterns, expr).matchArmList
+        val arms = matchArms.withIndex().joinToString("\n") { (index, arm) ->
+            val body = if (index == 0) "{\$END\$}" else "{}"
+            "${arm.patList.joinToString(" | ") { it.text }} => $body"
         }
+        return "match ${expr.text} {\n$arms\n}"
     }
+
+    override fun getElementToRemove(expr: PsiElement): PsiElement = expr
 }
 
 class IterPostfixTemplate(name: String, provider: RsPostfixTemplateProvider) :
@@ -146,8 +168,24 @@ class ErrPostfixTemplate(provider: RsPostfixTemplateProvider) :
     override fun getElementToRemove(expr: PsiElement): PsiElement = expr
 }
 
+class ArcPostfixTemplate(provider: RsPostfixTemplateProvider) :
+    StringBasedPostfixTemplate("arc", "Arc::new(expr)", RsTopMostInScopeSelector(), provider) {
+
+    override fun getTemplateString(element: PsiElement): String {
+        val expr = element as RsExpr
+        val arc = if (expr.findInScope("Arc", TYPES) != null) "Arc" else "std::sync::Arc"
+        return "$arc::new(${expr.text})"
+    }
+
+    override fun getElementToRemove(expr: PsiElement): PsiElement = expr
+}
+
 private val RsExpr.isIntoIterator: Boolean
     get() = implLookup.isIntoIterator(type)
 
 private val RsExpr.implementsDeref: Boolean
     get() = implLookup.isDeref(this.type)
+
+/** Enum that can be matched by the expression, e.g. `Option` for `&Option<i32>` */
+private val RsExpr.matchedEnum: RsEnumItem?
+    get() = (type.stripReferences() as? TyAdt)?.item as? RsEnumItem
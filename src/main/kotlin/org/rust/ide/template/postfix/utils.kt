@@ -14,6 +14,8 @@ import com.intellij.util.Function
 import org.rust.lang.core.psi.RsBlock
 import org.rust.lang.core.psi.RsExpr
 import org.rust.lang.core.psi.RsPsiFactory
+import org.rust.lang.core.psi.RsTypeReference
+import org.rust.lang.core.psi.ext.RsAttr
 import org.rust.lang.core.psi.ext.ancestors
 import org.rust.lang.core.psi.ext.endOffset
 import org.rust.lang.core.types.ty.TyBool
@@ -32,6 +34,7 @@ abstract class RsExprParentsSelectorBase(val pred: (RsExpr) -> Boolean) : Postfi
     override fun getRenderer(): Function<PsiElement, String> = Function { it.text }
 
     final override fun getExpressions(context: PsiElement, document: Document, offset: Int): List<PsiElement> {
+        if (context.isInTypeOrAttribute) return emptyList()
         val expressions = getExpressionsInternal(context, document, offset)
         // `PostfixTemplateWithExpressionSelector#expand` selects only one item from this list in unit tests.
         // But in different platform versions different items are selected (of course, it's very convenient).
@@ -39,7 +42,12 @@ abstract class RsExprParentsSelectorBase(val pred: (RsExpr) -> Boolean) : Postfi
         return if (isUnitTestMode) listOfNotNull(expressions.lastOrNull()) else expressions
     }
 
+    final override fun hasExpression(context: PsiElement, copyDocument: Document, newOffset: Int): Boolean =
+        !context.isInTypeOrAttribute && hasExpressionInternal(context, copyDocument, newOffset)
+
     protected abstract fun getExpressionsInternal(context: PsiElement, document: Document, offset: Int): List<PsiElement>
+
+    protected abstract fun hasExpressionInternal(context: PsiElement, copyDocument: Document, newOffset: Int): Boolean
 }
 
 class RsTopMostInScopeSelector(pred: ((RsExpr) -> Boolean) = { true }) : RsExprParentsSelectorBase(pred) {
@@ -50,7 +58,7 @@ class RsTopMostInScopeSelector(pred: ((RsExpr) -> Boolean) = { true }) : RsExprP
             .filter { it is RsExpr && pred(it) }
             .toList()
 
-    override fun hasExpression(context: PsiElement, copyDocument: Document, newOffset: Int): Boolean =
+    override fun hasExpressionInternal(context: PsiElement, copyDocument: Document, newOffset: Int): Boolean =
         context
             .ancestors
             .takeWhile { it !is RsBlock }
@@ -65,7 +73,7 @@ class RsAllParentsSelector(pred: ((RsExpr) -> Boolean) = { true }) : RsExprParen
             .filter { it is RsExpr && pred(it) }
             .toList()
 
-    override fun hasExpression(context: PsiElement, copyDocument: Document, newOffset: Int): Boolean =
+    override fun hasExpressionInternal(context: PsiElement, copyDocument: Document, newOffset: Int): Boolean =
         context
             .ancestors
             .takeWhile { it !is RsBlock }
@@ -73,3 +81,7 @@ class RsAllParentsSelector(pred: ((RsExpr) -> Boolean) = { true }) : RsExprParen
 }
 
 fun RsExpr.isBool() = type == TyBool
+
+/** Expressions in types (e.g. array lengths) and attributes can't be transformed into arbitrary code */
+private val PsiElement.isInTypeOrAttribute: Boolean
+    get() = ancestors.takeWhile { it !is RsBlock }.any { it is RsTypeReference || it is RsAttr }
@@ -0,0 +1,84 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.typing
+
+import com.intellij.codeInsight.editorActions.CopyPastePreProcessor
+import com.intellij.openapi.application.runWriteAction
+import com.intellij.openapi.editor.Editor
+import com.intellij.openapi.editor.RawText
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiDocumentManager
+import com.intellij.psi.PsiFile
+import org.rust.lang.core.lexer.RsEscapesLexer
+import org.rust.lang.core.psi.RS_ALL_STRING_LITERALS
+import org.rust.lang.core.psi.RS_RAW_LITERALS
+import org.rust.lang.core.psi.RsFile
+import org.rust.lang.core.psi.RsLiteralKind
+import org.rust.lang.core.psi.ext.elementType
+import org.rust.lang.core.psi.ext.startOffset
+import org.rust.lang.utils.escapeRust
+import org.rust.lang.utils.rawStringHashesCount
+import org.rust.lang.utils.unescapeRust
+
+/**
+ * Text copied from the value of a string literal is unescaped, and text pasted into a string literal is escaped.
+ * Pasting into a raw string literal adds `#` to its delimiters if the pasted text would terminate the literal.
+ * String literal tokens of macro calls (e.g. `concat!("...")`) are handled the same way
+ */
+class RsStringLiteralCopyPasteProcessor : CopyPastePreProcessor {
+    override fun preprocessOnCopy(file: PsiFile, startOffsets: IntArray, endOffsets: IntArray, text: String): String? {
+        if (file !is RsFile) return null
+        val range = TextRange(startOffsets.singleOrNull() ?: return null, endOffsets.singleOrNull() ?: return null)
+        val literal = findStringLiteral(file, range) ?: return null
+        if (literal.node.elementType in RS_RAW_LITERALS) return null
+        val copied = range.shiftLeft(literal.node.startOffset).substring(literal.node.text)
+        val unescaped = copied.unescapeRust(RsEscapesLexer.of(literal.node.elementType))
+        // Non-ASCII characters of byte strings are bytes rather than unicode characters
+        return if (literal.isByte && unescaped.any { it.toInt() >= 0x80 }) null else unescaped
+    }
+
+    override fun preprocessOnPaste(project: Project, file: PsiFile, editor: Editor, text: String, rawText: RawText?): String {
+        if (file !is RsFile) return text
+        val document = editor.document
+        PsiDocumentManager.getInstance(project).commitDocument(document)
+        val selectionModel = editor.selectionModel
+        val range = TextRange(selectionModel.selectionStart, selectionModel.selectionEnd)
+        val literal = findStringLiteral(file, range) ?: return text
+        if (literal.node.elementType !in RS_RAW_LITERALS) return text.escapeRust(literal.isByte)
+
+        val offsets = literal.offsets
+        val literalStart = literal.node.startOffset
+        val value = offsets.value?.shiftRight(literalStart) ?: return text
+        val newValue = TextRange(value.startOffset, range.startOffset).subSequence(document.charsSequence).toString() +
+            text +
+            TextRange(range.endOffset, value.endOffset).subSequence(document.charsSequence)
+        val hashes = (offsets.openDelim?.length ?: 1) - 1
+        val missingHashes = rawStringHashesCount(newValue) - hashes
+        if (missingHashes > 0) {
+            val closeDelimEnd = offsets.closeDelim?.endOffset?.plus(literalStart) ?: return text
+            val openDelimStart = offsets.openDelim?.startOffset?.plus(literalStart) ?: return text
+            runWriteAction {
+                document.insertString(closeDelimEnd, "#".repeat(missingHashes))
+                document.insertString(openDelimStart, "#".repeat(missingHashes))
+            }
+            PsiDocumentManager.getInstance(project).commitDocument(document)
+        }
+        return text
+    }
+
+    companion object {
+        /** Returns a string literal token if [range] is inside its value */
+        private fun findStringLiteral(file: RsFile, range: TextRange): RsLiteralKind.String? {
+            val token = file.findElementAt(range.startOffset) ?: return null
+            if (token.elementType !in RS_ALL_STRING_LITERALS) return null
+            val literal = RsLiteralKind.fromAstNode(token.node) as? RsLiteralKind.String ?: return null
+            if (literal.hasUnpairedQuotes) return null
+            val value = literal.offsets.value?.shiftRight(token.startOffset) ?: return null
+            return if (value.contains(range)) literal else null
+        }
+    }
+}
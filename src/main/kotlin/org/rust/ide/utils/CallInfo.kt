@@ -6,18 +6,19 @@
 package org.rust.ide.utils
 
 import org.rust.ide.presentation.getStubOnlyText
-import org.rust.lang.core.psi.RsCallExpr
-import org.rust.lang.core.psi.RsFunction
-import org.rust.lang.core.psi.RsMethodCall
-import org.rust.lang.core.psi.RsPathExpr
+import org.rust.ide.presentation.render
+import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
-import org.rust.lang.core.types.ty.TyFunction
+import org.rust.lang.core.resolve.ImplLookup
+import org.rust.lang.core.types.ty.*
 import org.rust.lang.core.types.type
 
 class CallInfo private constructor(
     val methodName: String?,
     val selfParameter: String?,
-    val parameters: List<Parameter>
+    val parameters: List<Parameter>,
+    /** `true` for a tuple struct or an enum variant constructor. All parameter patterns are `_` in this case */
+    val isTupleConstructor: Boolean = false
 ) {
     class Parameter(val pattern: String, val type: String)
 
@@ -25,6 +26,14 @@ class CallInfo private constructor(
         fun resolve(call: RsCallExpr): CallInfo? {
             val fn = (call.expr as? RsPathExpr)?.path?.reference?.resolve() ?: return null
             if (fn is RsFunction) return CallInfo(fn)
+            val constructor: RsFieldsOwner? = when (fn) {
+                is RsStructItem -> fn
+                is RsEnumVariant -> fn
+                // `Self(...)` inside an impl of a tuple struct
+                is RsImplItem -> (fn.typeReference?.type as? TyAdt)?.item as? RsStructItem
+                else -> null
+            }
+            if (constructor != null && constructor.tupleFields != null) return CallInfo(constructor)
 
             val ty = call.expr.type
             if (ty is TyFunction) return CallInfo(ty)
@@ -45,7 +54,19 @@ class CallInfo private constructor(
                 append("self")
             }
         },
-        fn.valueParameters.map { Parameter(it.patText ?: "_", it.typeReference?.getStubOnlyText() ?: "?") }
+        lazy(LazyThreadSafetyMode.NONE) { ImplLookup.relativeTo(fn) }.let { lookup ->
+            fn.valueParameters.map {
+                val type = it.closureSignature(lookup) ?: it.typeReference?.getStubOnlyText() ?: "?"
+                Parameter(it.patText ?: "_", type)
+            }
+        }
+    )
+
+    private constructor(constructor: RsFieldsOwner) : this(
+        constructor.name,
+        null,
+        constructor.positionalFields.map { Parameter("_", it.typeReference.getStubOnlyText()) },
+        isTupleConstructor = true
     )
 
     private constructor(fn: TyFunction) : this(
@@ -54,3 +75,28 @@ class CallInfo private constructor(
         fn.paramTypes.map { Parameter("_", it.toString()) }
     )
 }
+
+/**
+ * Returns the expected signature of a closure passed as the parameter if the type of the parameter
+ * is bound by one of `Fn` traits, e.g. `FnMut(&T) -> bool` for `f: F` where `F: FnMut(&T) -> bool`
+ */
+private fun RsValueParameter.closureSignature(lazyLookup: Lazy<ImplLookup>): String? {
+    val ty = typeReference?.type ?: return null
+    if (ty !is TyTypeParameter && ty !is TyAnon) return null
+    val lookup = lazyLookup.value
+    val fnTrait = when {
+        lookup.isFn(ty) -> "Fn"
+        lookup.isFnMut(ty) -> "FnMut"
+        lookup.isFnOnce(ty) -> "FnOnce"
+        else -> return null
+    }
+    val fnTy = lookup.asTyFunction(ty)?.value ?: return null
+    return buildString {
+        append(fnTrait)
+        fnTy.paramTypes.joinTo(this, prefix = "(", postfix = ")") { it.render() }
+        if (fnTy.retType !is TyUnit) {
+            append(" -> ")
+            append(fnTy.retType.render())
+        }
+    }
+}
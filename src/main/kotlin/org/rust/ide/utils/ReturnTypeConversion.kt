@@ -0,0 +1,206 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.utils
+
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.ImplLookup
+import org.rust.lang.core.types.infer.TypeVisitor
+import org.rust.lang.core.types.regions.ReStatic
+import org.rust.lang.core.types.regions.Region
+import org.rust.lang.core.types.ty.Ty
+import org.rust.lang.core.types.ty.TyNever
+import org.rust.lang.core.types.ty.TyUnknown
+import org.rust.lang.core.types.ty.isBox
+import org.rust.lang.core.types.type
+
+/**
+ * Conversion of a function return type between a boxed trait object and `impl Trait`, e.g.
+ * `fn foo() -> Box<dyn Iterator<Item = i32>>` and `fn foo() -> impl Iterator<Item = i32>`.
+ * Values are boxed or unboxed at every return point of the function
+ */
+sealed class ReturnTypeConversion(val function: RsFunction, val traitType: RsTraitType) {
+    /** The whole return type, i.e. `Box<dyn Trait>` or `impl Trait` */
+    abstract val returnType: RsTypeReference
+
+    /**
+     * The reason why the return type can't be converted, or `null` if it can.
+     * `impl Trait` is not allowed in trait methods, and a trait implementation must keep the signature of the trait
+     */
+    open fun findConflict(): String? {
+        val owner = function.owner
+        return when {
+            owner is RsAbstractableOwner.Trait -> "`impl Trait` is not allowed in the return type of trait methods"
+            owner.isTraitImpl -> "The return type of a trait method implementation must match the trait declaration"
+            else -> null
+        }
+    }
+
+    abstract fun convert(factory: RsPsiFactory)
+
+    /** `Box<dyn Trait>` to `impl Trait`. All return points must box values of the same concrete type */
+    class ToImplTrait(
+        function: RsFunction,
+        override val returnType: RsTypeReference,
+        traitType: RsTraitType
+    ) : ReturnTypeConversion(function, traitType) {
+
+        override fun findConflict(): String? {
+            super.findConflict()?.let { return it }
+            var concreteType: Ty? = null
+            for (expr in function.returnedExprs) {
+                if (expr.type is TyNever) continue
+                val value = expr.unboxedValue ?: return "Cannot unwrap `${expr.text}` returned from the function"
+                val type = value.type
+                if (type is TyUnknown) return "Cannot infer the type of `${value.text}`"
+                if (concreteType != null && concreteType != type) {
+                    return "Return points have different types: `$concreteType` and `$type`"
+                }
+                concreteType = type
+            }
+            return if (concreteType == null) "The function doesn't return any value" else null
+        }
+
+        override fun convert(factory: RsPsiFactory) {
+            val boxedValues = function.returnedExprs.mapNotNull { expr ->
+                val value = expr.unboxedValue ?: return@mapNotNull null
+                expr to value
+            }
+            val captures = needsLifetimeCapture(boxedValues.map { (_, value) -> value.type })
+            for ((expr, value) in boxedValues) {
+                expr.replace(value)
+            }
+            returnType.replace(factory.createType("impl ${traitType.boundsText(captures)}"))
+        }
+    }
+
+    /** `impl Trait` to `Box<dyn Trait>` */
+    class ToBoxedTraitObject(
+        function: RsFunction,
+        override val returnType: RsTraitType
+    ) : ReturnTypeConversion(function, returnType) {
+        override fun convert(factory: RsPsiFactory) {
+            val returnedExprs = function.returnedExprs.filter { it.type !is TyNever }
+            val returnedTypes = returnedExprs.map { it.type }
+            val captures = needsLifetimeCapture(returnedTypes)
+            val autoTraits = implementedAutoTraits(returnedTypes)
+            for (expr in returnedExprs) {
+                expr.replace(factory.createExpression("Box::new(${expr.text})"))
+            }
+            returnType.replace(factory.createType("Box<dyn ${traitType.boundsText(captures, autoTraits)}>"))
+        }
+
+        /**
+         * `impl Trait` leaks auto traits of the concrete type, so callers may rely on e.g. `foo(): Send`.
+         * A trait object is `Send` or `Sync` only if the trait is listed in its bounds
+         */
+        private fun implementedAutoTraits(returnedTypes: List<Ty>): List<String> {
+            if (returnedTypes.isEmpty()) return emptyList()
+            val lookup = ImplLookup.relativeTo(function)
+            val boundNames = traitType.polyboundList.mapNotNull { it.bound.traitRef?.path?.referenceName }
+            return listOf("Send" to lookup::isSend, "Sync" to lookup::isSync)
+                .filter { (name, isImplemented) -> name !in boundNames && returnedTypes.all(isImplemented) }
+                .map { (name, _) -> name }
+        }
+    }
+
+    /**
+     * A returned value which borrows from a parameter requires the `'_` bound, e.g. `impl Iterator<Item = &T> + '_`.
+     * Only unambiguous elided lifetimes are supported, i.e. `&self` or a single reference parameter
+     */
+    protected fun needsLifetimeCapture(returnedTypes: List<Ty>): Boolean {
+        if (traitType.polyboundList.any { it.bound.lifetime != null }) return false
+        val hasElidedInputLifetime = when (val self = function.selfParameter) {
+            null -> function.valueParameters.count { it.typeReference?.isReference == true } == 1
+            else -> self.isRef || self.typeReference?.isReference == true
+        }
+        return hasElidedInputLifetime && returnedTypes.any { it.hasNonStaticRegions }
+    }
+
+    companion object {
+        fun forFunction(function: RsFunction): ReturnTypeConversion? {
+            val returnType = function.retType?.typeReference?.skipParens() ?: return null
+            if (returnType is RsTraitType && returnType.isImpl) {
+                return ToBoxedTraitObject(function, returnType)
+            }
+
+            val boxType = returnType as? RsBaseType ?: return null
+            val boxTy = boxType.type
+            if (!boxTy.isBox) return null
+            val traitType = boxType.path?.typeArgumentList?.typeReferenceList?.singleOrNull()?.skipParens() as? RsTraitType
+                ?: return null
+            if (traitType.dyn == null) return null
+            return ToImplTrait(function, boxType, traitType)
+        }
+    }
+}
+
+/**
+ * Expressions returned from the function: values of `return` expressions and the tail expression.
+ * Branches of `if` and `match` expressions and nested blocks are returned separately
+ */
+val RsFunction.returnedExprs: List<RsExpr>
+    get() {
+        val block = block ?: return emptyList()
+        val result = mutableListOf<RsExpr>()
+        block.expr?.let { collectReturnedExprs(it, result) }
+        for (retExpr in block.descendantsOfType<RsRetExpr>()) {
+            val owner = retExpr.ancestors.find { it is RsFunction || it is RsLambdaExpr || it is RsBlockExpr && it.isAsync }
+            if (owner != this) continue
+            retExpr.expr?.let { collectReturnedExprs(it, result) }
+        }
+        return result
+    }
+
+private fun collectReturnedExprs(expr: RsExpr, result: MutableList<RsExpr>) {
+    when (expr) {
+        is RsParenExpr -> expr.expr?.let { collectReturnedExprs(it, result) }
+        is RsBlockExpr -> if (expr.isAsync) result += expr else expr.block.expr?.let { collectReturnedExprs(it, result) }
+        is RsIfExpr -> {
+            expr.block?.expr?.let { collectReturnedExprs(it, result) }
+            val elseBranch = expr.elseBranch
+            (elseBranch?.ifExpr ?: elseBranch?.block?.expr)?.let { collectReturnedExprs(it, result) }
+        }
+        is RsMatchExpr -> for (arm in expr.arms) {
+            arm.expr?.let { collectReturnedExprs(it, result) }
+        }
+        // Values of `return` expressions are collected separately
+        is RsRetExpr -> Unit
+        else -> result += expr
+    }
+}
+
+/** `x` from `Box::new(x)` or `x.boxed()` */
+private val RsExpr.unboxedValue: RsExpr?
+    get() = when (this) {
+        is RsCallExpr -> {
+            val path = (expr as? RsPathExpr)?.path
+            if (path?.referenceName == "new" && path.path?.referenceName == "Box") {
+                valueArgumentList.exprList.singleOrNull()
+            } else {
+                null
+            }
+        }
+        is RsDotExpr -> {
+            val methodCall = methodCall
+            if (methodCall?.referenceName == "boxed" && methodCall.valueArgumentList.exprList.isEmpty()) expr else null
+        }
+        else -> null
+    }
+
+private val RsTypeReference.isReference: Boolean
+    get() = (skipParens() as? RsRefLikeType)?.isRef == true
+
+private fun RsTraitType.boundsText(addLifetimeCapture: Boolean, extraBounds: List<String> = emptyList()): String {
+    val bounds = polyboundList.map { it.text } + extraBounds
+    return (if (addLifetimeCapture) bounds + "'_" else bounds).joinToString(" + ")
+}
+
+private val Ty.hasNonStaticRegions: Boolean
+    get() = visitWith(object : TypeVisitor {
+        override fun visitTy(ty: Ty): Boolean = ty.superVisitWith(this)
+        override fun visitRegion(region: Region): Boolean = region != ReStatic
+    })
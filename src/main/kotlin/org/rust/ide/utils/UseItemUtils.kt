@@ -0,0 +1,126 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.utils
+
+import com.intellij.psi.PsiComment
+import org.rust.ide.formatter.settings.ImportsGranularity
+import org.rust.lang.core.psi.RsElementTypes.COLONCOLON
+import org.rust.lang.core.psi.RsPsiFactory
+import org.rust.lang.core.psi.RsUseItem
+import org.rust.lang.core.psi.RsUseSpeck
+import org.rust.lang.core.psi.ext.descendantsOfType
+import org.rust.lang.core.psi.ext.isStarImport
+
+/**
+ * A single name imported by a use item, e.g. `HashSet` in `use std::collections::{HashMap, HashSet};`
+ * is `ImportEntry(["std", "collections"], "HashSet")`.
+ * [name] can also be `self` or contain an alias (`Foo as Bar`)
+ */
+data class ImportEntry(val parentPath: List<String>, val name: String)
+
+/**
+ * Splits the use item into [ImportEntry]s or returns `null` if the use item can't be rearranged
+ * without changing its semantics or losing something, i.e. if it has attributes (including `#[cfg]`),
+ * comments or glob imports
+ */
+val RsUseItem.importEntries: List<ImportEntry>?
+    get() {
+        if (outerAttrList.isNotEmpty() || descendantsOfType<PsiComment>().isNotEmpty()) return null
+        val useSpeck = useSpeck ?: return null
+        val result = mutableListOf<ImportEntry>()
+        return if (useSpeck.collectImportEntries(emptyList(), result)) result else null
+    }
+
+private fun RsUseSpeck.collectImportEntries(prefix: List<String>, result: MutableList<ImportEntry>): Boolean {
+    if (isStarImport) return false
+    val pathText = path?.text
+    if (pathText != null && (pathText.startsWith("::") || pathText.any { it.isWhitespace() })) return false
+    val segments = pathText?.split("::").orEmpty()
+
+    val group = useGroup
+    if (group != null) {
+        // `use ::{foo, bar}` is not the same as `use {foo, bar}` in 2015 edition
+        if (pathText == null && node.findChildByType(COLONCOLON) != null) return false
+        return group.useSpeckList.all { it.collectImportEntries(prefix + segments, result) }
+    }
+
+    val name = segments.lastOrNull() ?: return false
+    // `self` is allowed only inside a group: `use foo::{self}`
+    if (name == "self" && (segments.size != 1 || prefix.isEmpty())) return false
+    val alias = alias?.text?.let { " $it" }.orEmpty()
+    result += ImportEntry(prefix + segments.dropLast(1), name + alias)
+    return true
+}
+
+/**
+ * Returns the texts of use specks that import all [entries] with the given [granularity].
+ *
+ * Imports without a parent path (like `use foo;`) are never merged,
+ * because `use foo::{self}` imports `foo` only from the type namespace
+ */
+fun renderUseSpecks(entries: Collection<ImportEntry>, granularity: ImportsGranularity): List<String> {
+    val (rootEntries, nestedEntries) = entries.distinct().partition { it.parentPath.isEmpty() }
+    val nestedTexts = when (granularity) {
+        ImportsGranularity.PRESERVE, ImportsGranularity.ITEM -> nestedEntries.map {
+            it.parentPath.joinToString("::") + "::" + renderGroup(listOf(it.name))
+        }
+        ImportsGranularity.MODULE -> nestedEntries.groupBy { it.parentPath }.map { (parentPath, moduleEntries) ->
+            parentPath.joinToString("::") + "::" + renderGroup(moduleEntries.map { it.name })
+        }
+        ImportsGranularity.CRATE -> nestedEntries.groupBy { it.parentPath.first() }.map { (crateName, crateEntries) ->
+            "$crateName::" + renderTree(crateEntries.map { ImportEntry(it.parentPath.drop(1), it.name) })
+        }
+    }
+    return rootEntries.map { it.name } + nestedTexts
+}
+
+private fun renderTree(entries: List<ImportEntry>): String {
+    val (leaves, nested) = entries.partition { it.parentPath.isEmpty() }
+    val children = nested.groupBy { it.parentPath.first() }.map { (segment, childEntries) ->
+        "$segment::" + renderTree(childEntries.map { ImportEntry(it.parentPath.drop(1), it.name) })
+    }
+    return renderGroup(leaves.map { it.name } + children)
+}
+
+private fun renderGroup(names: List<String>): String {
+    val single = names.singleOrNull()
+    if (single != null && single != "self") return single
+    return names.distinct()
+        .sortedWith(compareBy<String> { it != "self" }.thenBy { it.toLowerCase() })
+        .joinToString(", ", "{", "}")
+}
+
+/**
+ * Merges or splits [useItems] according to [granularity]. Use items that can't be rearranged
+ * (see [importEntries]) are kept as is. Only use items with the same visibility are merged.
+ * Returns new (non-physical) use items
+ */
+fun mergeUseItems(psiFactory: RsPsiFactory, useItems: List<RsUseItem>, granularity: ImportsGranularity): List<RsUseItem> {
+    if (granularity == ImportsGranularity.PRESERVE) return useItems.map { it.copy() as RsUseItem }
+
+    val result = mutableListOf<RsUseItem>()
+    val mergeableByVisibility = LinkedHashMap<String, MutableList<Pair<RsUseItem, List<ImportEntry>>>>()
+    for (useItem in useItems) {
+        val entries = useItem.importEntries
+        if (entries == null) {
+            result += useItem.copy() as RsUseItem
+        } else {
+            mergeableByVisibility.getOrPut(useItem.vis?.text.orEmpty()) { mutableListOf() } += useItem to entries
+        }
+    }
+
+    for ((visibility, items) in mergeableByVisibility) {
+        val texts = renderUseSpecks(items.flatMap { it.second }, granularity)
+        val singleItem = items.singleOrNull()?.first
+        if (singleItem != null && texts.size == 1) {
+            // Already has the requested granularity, so keep its formatting
+            result += singleItem.copy() as RsUseItem
+        } else {
+            result += texts.map { psiFactory.createUseItem(it, visibility) }
+        }
+    }
+    return result
+}
@@ -41,7 +41,7 @@ class DataFlowContext<O : DataFlowOperator>(
 
     private fun getCfgNodes(element: RsElement): List<CFGNode> = cfgTable.getOrDefault(element, mutableListOf())
 
-    private fun hasBitSetForElement(element: RsElement): Boolean = cfgTable.containsKey(element)
+    fun hasBitSetForElement(element: RsElement): Boolean = cfgTable.containsKey(element)
 
     private fun getRange(node: CFGNode): Pair<Int, Int> {
         val start = node.index * wordsPerElement
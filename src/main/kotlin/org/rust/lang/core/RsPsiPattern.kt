@@ -26,6 +26,7 @@ object RsPsiPattern {
 
     /**
      * Source of attributes: [https://doc.rust-lang.org/1.30.0/reference/attributes.html]
+     * and [https://doc.rust-lang.org/reference/attributes.html#built-in-attributes-index]
      */
     private val STD_ATTRIBUTES: Set<String> = setOf(
         "crate_name",
@@ -35,6 +36,7 @@ object RsPsiPattern {
         "no_start",
         "no_std",
         "recursion_limit",
+        "type_length_limit",
         "windows_subsystem",
 
         "no_implicit_prelude",
@@ -47,7 +49,6 @@ object RsPsiPattern {
         "link_name",
         "linkage",
 
-        "macro_use",
         "macro_use",
         "macro_reexport",
         "macro_export",
@@ -59,15 +60,19 @@ object RsPsiPattern {
 
         "export_name",
         "global_allocator",
+        "panic_handler",
         "link_section",
         "no_mangle",
+        "used",
 
         "deprecated",
 
         "doc",
 
         "test",
+        "ignore",
         "should_panic",
+        "bench",
 
         "cfg",
         "cfg_attr",
@@ -81,10 +86,30 @@ object RsPsiPattern {
 
         "cold",
         "inline",
+        "target_feature",
+        "track_caller",
+
+        "repr",
+        "non_exhaustive",
+        "automatically_derived",
+        "thread_local",
+        "naked",
+        "no_coverage",
+        "test_case",
+
+        // Attributes of the standard library and the compiler
+        "stable",
+        "unstable",
+        "lang",
+        "allow_internal_unstable",
+        "allow_internal_unsafe",
 
         "derive"
     )
 
+    /** Internal attributes of the compiler, e.g. `#[rustc_const_stable]` */
+    private const val RUSTC_ATTRIBUTE_PREFIX: String = "rustc_"
+
     const val META_ITEM_IDENTIFIER_DEPTH = 4
 
     val onStatementBeginning: PsiElementPattern.Capture<PsiElement> = psiElement().with(OnStatementBeginning())
@@ -167,7 +192,10 @@ object RsPsiPattern {
     val nonStdOuterAttributeMetaItem: PsiElementPattern.Capture<RsMetaItem> =
         psiElement<RsMetaItem>()
             .withSuperParent(2, RsOuterAttributeOwner::class.java)
-            .with("nonStdAttributeCondition") { e -> e.name !in STD_ATTRIBUTES }
+            .with("nonStdAttributeCondition") { e ->
+                val name = e.name
+                name !in STD_ATTRIBUTES && name?.startsWith(RUSTC_ATTRIBUTE_PREFIX) != true
+            }
 
     val includeMacroLiteral: PsiElementPattern.Capture<RsLitExpr> = psiElement<RsLitExpr>()
         .withParent(psiElement<RsIncludeMacroArgument>())
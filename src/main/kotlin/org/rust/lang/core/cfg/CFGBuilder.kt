@@ -207,10 +207,17 @@ class CFGBuilder(
     override fun visitLetDecl(letDecl: RsLetDecl) {
         val initExit = process(letDecl.expr, pred)
         val exit = process(letDecl.pat, initExit)
+        process(letDecl.elseBlock, initExit)
 
         finishWithAstNode(letDecl, exit)
     }
 
+    override fun visitLetExpr(letExpr: RsLetExpr) {
+        val exprExit = process(letExpr.expr, pred)
+        val orPatsExit = processOrPats(letExpr.orPats, exprExit)
+        finishWithAstNode(letExpr, orPatsExit)
+    }
+
     override fun visitNamedFieldDecl(fieldDecl: RsNamedFieldDecl) = finishWith(pred)
 
     override fun visitLabelDecl(labelDecl: RsLabelDecl) = finishWith(pred)
@@ -283,6 +290,7 @@ class CFGBuilder(
                     .plus(argument.formatMacroArgList.map { it.expr })
                     .fold(pred) { acc, subExpr -> process(subExpr, acc) }
             }
+            is RsAsmMacroArgument -> argument.allExprs.fold(pred) { acc, subExpr -> process(subExpr, acc) }
 
             is RsMacroArgument -> {
                 val expansion = macroCall.expansion
@@ -337,6 +345,15 @@ class CFGBuilder(
     override fun visitPatSlice(patSlice: RsPatSlice) =
         finishWith { processSubPats(patSlice, patSlice.patList) }
 
+    override fun visitPatOr(patOr: RsPatOr) {
+        val patOrExit = addDummyNode()
+        for (pat in patOr.patList) {
+            val patExit = process(pat, pred)
+            addContainedEdge(patExit, patOrExit)
+        }
+        finishWithAstNode(patOr, patOrExit)
+    }
+
     override fun visitBlockExpr(blockExpr: RsBlockExpr) {
         val labelDeclaration = blockExpr.labelDecl
         if (labelDeclaration != null) {
@@ -372,12 +389,10 @@ class CFGBuilder(
         //     [ifExpr]          [ifExpr]
         //
         val expr = ifExpr.condition?.expr
-        val orPats = ifExpr.condition?.orPats
 
         val exprExit = process(expr, pred)
-        val orPatsExit = processOrPats(orPats, exprExit)
 
-        val thenExit = process(ifExpr.block, orPatsExit)
+        val thenExit = process(ifExpr.block, exprExit)
 
         val elseBranch = ifExpr.elseBranch
 
@@ -418,15 +433,12 @@ class CFGBuilder(
         val loopScope = LoopScope(whileExpr, loopBack, whileExprExit)
 
         withLoopScope(loopScope) {
-            val orPats = whileExpr.condition?.orPats
             val expr = whileExpr.condition?.expr
 
             val exprExit = process(expr, loopBack)
             addContainedEdge(exprExit, whileExprExit)
 
-            val orPatsExit = processOrPats(orPats, exprExit)
-
-            val bodyExit = process(whileExpr.block, orPatsExit)
+            val bodyExit = process(whileExpr.block, exprExit)
 
             addContainedEdge(bodyExit, loopBack)
         }
@@ -45,10 +45,13 @@ const val ASSOC_FN_PRIORITY = 2.0
 const val DEFAULT_PRIORITY = 0.0
 const val MACRO_PRIORITY = -0.1
 const val DEPRECATED_PRIORITY = -1.0
+const val DOC_HIDDEN_PRIORITY = -2.0
 
 private const val EXPECTED_TYPE_PRIORITY_OFFSET = 40.0
 private const val LOCAL_PRIORITY_OFFSET = 20.0
 private const val INHERENT_IMPL_MEMBER_PRIORITY_OFFSET = 0.1
+const val OUT_OF_SCOPE_TRAIT_MEMBER_PRIORITY_OFFSET = -0.2
+const val USED_TRAIT_MEMBER_PRIORITY_OFFSET = 0.1
 
 interface CompletionEntity {
     val ty: Ty?
@@ -65,8 +68,10 @@ class ScopedBaseCompletionEntity(private val scopeEntry: ScopeEntry) : Completio
     override val implLookup: ImplLookup get() = element.implLookup
 
     override fun getBasePriority(context: RsCompletionContext): Double {
+        val attributes = (element as? RsDocAndAttributeOwner)?.queryAttributes
         var priority = when {
-            element is RsDocAndAttributeOwner && element.queryAttributes.deprecatedAttribute != null -> DEPRECATED_PRIORITY
+            attributes?.isDocHidden == true -> DOC_HIDDEN_PRIORITY
+            attributes?.deprecatedAttribute != null -> DEPRECATED_PRIORITY
             element is RsMacro -> MACRO_PRIORITY
             element is RsPatBinding -> VARIABLE_PRIORITY
             element is RsEnumVariant -> ENUM_VARIANT_PRIORITY
@@ -149,11 +154,15 @@ fun LookupElementBuilder.withPriority(priority: Double): LookupElement =
     if (priority == DEFAULT_PRIORITY) this else PrioritizedLookupElement.withPriority(this, priority)
 
 private fun RsElement.getLookupElementBuilder(scopeName: String, subst: Substitution): LookupElementBuilder {
+    val attributes = (this as? RsDocAndAttributeOwner)?.queryAttributes
+    val deprecation = attributes?.deprecation
     val base = LookupElementBuilder.createWithSmartPointer(scopeName, this)
         .withIcon(if (this is RsFile) RsIcons.MODULE else this.getIcon(0))
-        .withStrikeoutness(this is RsDocAndAttributeOwner && queryAttributes.deprecatedAttribute != null)
+        .withStrikeoutness(deprecation != null)
+        // Typing `#[doc(alias = "...")]` value should match the item as well
+        .withLookupStrings(attributes?.docAliases.orEmpty().toList())
 
-    return when (this) {
+    val lookup = when (this) {
         is RsMod -> if (scopeName == "self" || scopeName == "super" || scopeName == "crate") {
             base.withTailText("::")
         } else {
@@ -195,6 +204,18 @@ private fun RsElement.getLookupElementBuilder(scopeName: String, subst: Substitu
 
         else -> base
     }
+    val deprecationTailText = deprecation?.let { getDeprecationTailText(it) }
+    return if (deprecationTailText != null) lookup.appendTailText(deprecationTailText, true) else lookup
+}
+
+private fun getDeprecationTailText(deprecation: Deprecation): String? {
+    val (since, note) = deprecation
+    if (since == null && note == null) return null
+    return buildString {
+        append(" deprecated")
+        if (since != null) append(" since $since")
+        if (note != null) append(": $note")
+    }
 }
 
 private fun getFieldsOwnerTailText(owner: RsFieldsOwner, subst: Substitution): String = when {
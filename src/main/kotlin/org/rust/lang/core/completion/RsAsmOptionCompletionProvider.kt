@@ -0,0 +1,36 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+import com.intellij.codeInsight.completion.CompletionParameters
+import com.intellij.codeInsight.completion.CompletionResultSet
+import com.intellij.codeInsight.lookup.LookupElementBuilder
+import com.intellij.patterns.ElementPattern
+import com.intellij.patterns.PlatformPatterns
+import com.intellij.psi.PsiElement
+import com.intellij.util.ProcessingContext
+import org.rust.lang.core.psi.RsAsmOption
+import org.rust.lang.core.psi.RsAsmOptions
+
+/** Completes options of `asm!` macro, e.g. `asm!("", options(/*caret*/))` */
+object RsAsmOptionCompletionProvider : RsCompletionProvider() {
+    private val ASM_OPTIONS: List<String> = listOf(
+        "pure", "nomem", "readonly", "preserves_flags", "noreturn", "nostack", "att_syntax", "raw", "may_unwind"
+    )
+
+    override val elementPattern: ElementPattern<PsiElement>
+        get() = PlatformPatterns.psiElement().withParent(RsAsmOption::class.java)
+
+    override fun addCompletions(parameters: CompletionParameters, context: ProcessingContext, result: CompletionResultSet) {
+        val option = parameters.position.parent as? RsAsmOption ?: return
+        val options = option.parent as? RsAsmOptions ?: return
+        val presentOptions = options.asmOptionList.filter { it != option }.map { it.text }
+        for (name in ASM_OPTIONS) {
+            if (name in presentOptions) continue
+            result.addElement(LookupElementBuilder.create(name).bold())
+        }
+    }
+}
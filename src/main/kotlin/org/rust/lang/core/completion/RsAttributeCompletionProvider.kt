@@ -39,10 +39,7 @@ import org.rust.lang.core.psi.RsInnerAttr
 import org.rust.lang.core.psi.RsMetaItem
 import org.rust.lang.core.psi.RsOuterAttr
 import org.rust.lang.core.psi.RsPath
-import org.rust.lang.core.psi.ext.RsDocAndAttributeOwner
-import org.rust.lang.core.psi.ext.name
-import org.rust.lang.core.psi.ext.queryAttributes
-import org.rust.lang.core.psi.ext.superParent
+import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.psiElement
 
 object RsAttributeCompletionProvider : RsCompletionProvider() {
@@ -78,6 +75,11 @@ object RsAttributeCompletionProvider : RsCompletionProvider() {
             .filter { it.appliesTo.accepts(parameters.position) && elem.attrMetaItems.none { item -> item == it.name } }
             .map { createLookupElement(it.name) }
         result.addAllElements(suggestions)
+
+        // Helper attributes of custom derives, e.g. `serde` for `#[derive(Serialize)]`
+        val derives = (elem as? RsOuterAttributeOwner)?.derivingItem?.derivedProcMacros.orEmpty()
+        val helperAttributes = derives.flatMap { it.procMacroDeriveHelperAttributes }.distinct()
+        result.addAllElements(helperAttributes.map { createLookupElement(it) })
     }
 
     override val elementPattern: ElementPattern<PsiElement>
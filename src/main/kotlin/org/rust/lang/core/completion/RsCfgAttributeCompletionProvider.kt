@@ -17,43 +17,21 @@ import com.intellij.patterns.PlatformPatterns.psiElement
 import com.intellij.psi.PsiElement
 import com.intellij.util.ProcessingContext
 import org.rust.lang.RsLanguage
+import org.rust.lang.core.psi.RsLitExpr
 import org.rust.lang.core.psi.RsMetaItem
 import org.rust.lang.core.psi.RsMetaItemArgs
 import org.rust.lang.core.psi.ext.name
 import org.rust.lang.core.psiElement
+import org.rust.lang.utils.evaluation.KnownCfgOptions
 
 object RsCfgAttributeCompletionProvider : RsCompletionProvider() {
 
-    private val NAME_OPTIONS: List<String> = listOf(
-        "unix",
-        "windows",
-        "test",
-        "debug_assertions"
-    )
-
-    private val NAME_VALUE_OPTIONS: List<String> = listOf(
-        "target_arch",
-        "target_endian",
-        "target_env",
-        "target_family",
-        "target_feature",
-        "target_os",
-        "target_pointer_width",
-        "target_vendor"
-    )
-
-    private val OPERATORS: List<String> = listOf(
-        "all",
-        "any",
-        "not"
-    )
-
     override fun addCompletions(parameters: CompletionParameters, context: ProcessingContext, result: CompletionResultSet) {
-        for (option in NAME_OPTIONS) {
+        for (option in KnownCfgOptions.NAME_OPTIONS) {
             result.addElement(LookupElementBuilder.create(option))
         }
 
-        for (option in NAME_VALUE_OPTIONS) {
+        for (option in KnownCfgOptions.NAME_VALUE_OPTIONS) {
             result.addElement(
                 LookupElementBuilder.create(option).withInsertHandler { ctx, _ ->
                     if (!ctx.alreadyHasValue) {
@@ -64,7 +42,7 @@ object RsCfgAttributeCompletionProvider : RsCompletionProvider() {
             )
         }
 
-        for (operator in OPERATORS) {
+        for (operator in KnownCfgOptions.OPERATORS) {
             result.addElement(
                 LookupElementBuilder.create(operator).withInsertHandler { ctx, _ ->
                     if (!ctx.alreadyHasCallParens) {
@@ -80,21 +58,35 @@ object RsCfgAttributeCompletionProvider : RsCompletionProvider() {
         get() = nextCharIs('=')
 
     override val elementPattern: ElementPattern<PsiElement>
-        get() {
-            val cfgAttr = psiElement<RsMetaItem>()
-                .with(object : PatternCondition<RsMetaItem>("cfg") {
-                    override fun accepts(t: RsMetaItem, context: ProcessingContext?): Boolean =
-                        t.name == "cfg"
-                })
+        get() = psiElement()
+            .withLanguage(RsLanguage)
+            .andOr(psiElement().inside(cfgOption), psiElement().inside(cfgAttrPredicate))
+            // Feature names are completed by [RsCfgFeatureCompletionProvider]
+            .andNot(psiElement().inside(psiElement<RsLitExpr>()))
 
-            val cfgOption = psiElement<RsMetaItem>()
-                .withParent(
-                    psiElement<RsMetaItemArgs>()
-                        .withParent(cfgAttr)
-                )
+    private val cfgOption: ElementPattern<RsMetaItem>
+        get() = psiElement<RsMetaItem>()
+            .withParent(
+                psiElement<RsMetaItemArgs>()
+                    .withParent(metaItemWithName("cfg"))
+            )
 
-            return psiElement()
-                .withLanguage(RsLanguage)
-                .inside(cfgOption)
-        }
+    /** `unix` in `#[cfg_attr(unix, derive(Debug))]` */
+    private val cfgAttrPredicate: ElementPattern<RsMetaItem>
+        get() = psiElement<RsMetaItem>()
+            .withParent(
+                psiElement<RsMetaItemArgs>()
+                    .withParent(metaItemWithName("cfg_attr"))
+            )
+            .with(object : PatternCondition<RsMetaItem>("firstArgument") {
+                override fun accepts(t: RsMetaItem, context: ProcessingContext?): Boolean =
+                    (t.parent as RsMetaItemArgs).metaItemList.firstOrNull() == t
+            })
+
+    private fun metaItemWithName(name: String): ElementPattern<RsMetaItem> =
+        psiElement<RsMetaItem>()
+            .with(object : PatternCondition<RsMetaItem>(name) {
+                override fun accepts(t: RsMetaItem, context: ProcessingContext?): Boolean =
+                    t.name == name
+            })
 }
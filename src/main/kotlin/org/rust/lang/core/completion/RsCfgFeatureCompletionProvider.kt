@@ -0,0 +1,55 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+import com.intellij.codeInsight.completion.CompletionParameters
+import com.intellij.codeInsight.completion.CompletionResultSet
+import com.intellij.codeInsight.lookup.LookupElementBuilder
+import com.intellij.patterns.ElementPattern
+import com.intellij.patterns.PatternCondition
+import com.intellij.patterns.PlatformPatterns
+import com.intellij.psi.PsiElement
+import com.intellij.util.ProcessingContext
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsMetaItem
+import org.rust.lang.core.psi.ext.containingCargoPackage
+import org.rust.lang.core.psi.ext.isCfgPredicate
+import org.rust.lang.core.psi.ext.name
+import org.rust.lang.core.psi.ext.startOffset
+import org.rust.lang.core.psiElement
+
+/**
+ * Completes features of the package in `#[cfg(feature = "/*caret*/")]` and in the predicate of `#[cfg_attr]`.
+ * Features are taken from the package that owns the file, so a dependency source is completed with
+ * features of the dependency rather than of the workspace package
+ */
+object RsCfgFeatureCompletionProvider : RsCompletionProvider() {
+    override val elementPattern: ElementPattern<PsiElement>
+        get() = PlatformPatterns.psiElement().withParent(
+            psiElement<RsLitExpr>().withParent(
+                psiElement<RsMetaItem>().with(object : PatternCondition<RsMetaItem>("cfgFeature") {
+                    override fun accepts(t: RsMetaItem, context: ProcessingContext?): Boolean =
+                        t.name == "feature" && t.isCfgPredicate
+                })
+            )
+        )
+
+    override fun addCompletions(parameters: CompletionParameters, context: ProcessingContext, result: CompletionResultSet) {
+        val literal = parameters.position.parent as? RsLitExpr ?: return
+        val pkg = literal.containingCargoPackage ?: return
+
+        // Feature names may contain `-`, so the default identifier-based prefix doesn't fit here
+        val valueStart = literal.text.indexOf('"') + 1
+        val caretOffset = parameters.offset - literal.startOffset
+        if (valueStart == 0 || caretOffset < valueStart) return
+        val prefix = literal.text.substring(valueStart, caretOffset)
+
+        val resultWithPrefix = result.withPrefixMatcher(prefix)
+        for (feature in pkg.features) {
+            resultWithPrefix.addElement(LookupElementBuilder.create(feature.name))
+        }
+    }
+}
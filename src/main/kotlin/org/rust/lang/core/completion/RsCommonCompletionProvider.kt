@@ -13,6 +13,9 @@ import com.intellij.patterns.ElementPattern
 import com.intellij.patterns.PatternCondition
 import com.intellij.patterns.PlatformPatterns
 import com.intellij.psi.PsiElement
+import com.intellij.psi.impl.cache.CacheManager
+import com.intellij.psi.search.GlobalSearchScopesCore
+import com.intellij.psi.search.UsageSearchContext
 import com.intellij.psi.stubs.StubIndex
 import com.intellij.psi.util.PsiTreeUtil
 import com.intellij.util.ProcessingContext
@@ -316,46 +319,126 @@ private fun methodAndFieldCompletionProcessor(
     methodOrField: RsMethodOrField,
     result: CompletionResultSet,
     context: RsCompletionContext
-): RsResolveProcessor = fun(e: ScopeEntry): Boolean {
-    when (e) {
-        is FieldResolveVariant -> result.addElement(createLookupElement(
-            scopeEntry = e,
-            context = context
-        ))
-        is MethodResolveVariant -> {
-            if (e.element.isTest) return false
-
-            result.addElement(createLookupElement(
+): RsResolveProcessor {
+    val traitScopes = TraitScopes(methodOrField)
+    return fun(e: ScopeEntry): Boolean {
+        when (e) {
+            is FieldResolveVariant -> result.addElement(createLookupElement(
                 scopeEntry = e,
-                context = context,
-                insertHandler = object : RsDefaultInsertHandler() {
-                    override fun handleInsert(
-                        element: RsElement,
-                        scopeName: String,
-                        context: InsertionContext,
-                        item: LookupElement
-                    ) {
-                        val traitImportCandidate = findTraitImportCandidate(methodOrField, e)
-                        super.handleInsert(element, scopeName, context, item)
-
-                        if (traitImportCandidate != null) {
-                            context.commitDocument()
-                            context.getElementOfType<RsElement>()?.let { traitImportCandidate.import(it) }
+                context = context
+            ))
+            is MethodResolveVariant -> {
+                if (e.element.isTest) return false
+                val traitScope = traitScopes.get(e)
+                if (traitScope is TraitScope.NotSuggested) return false
+                val outOfScopeTrait = traitScope as? TraitScope.OutOfScope
+
+                val baseEntity = ScopedBaseCompletionEntity(e)
+                result.addElement(createLookupElement(
+                    completionEntity = if (outOfScopeTrait != null) {
+                        OutOfScopeTraitMemberCompletionEntity(baseEntity, outOfScopeTrait.isUsedInCrate)
+                    } else {
+                        baseEntity
+                    },
+                    context = context,
+                    locationString = outOfScopeTrait?.traitPath,
+                    insertHandler = object : RsDefaultInsertHandler() {
+                        override fun handleInsert(
+                            element: RsElement,
+                            scopeName: String,
+                            context: InsertionContext,
+                            item: LookupElement
+                        ) {
+                            // Import candidates are searched only for the selected item not to slow down the popup
+                            val traitImportCandidate = outOfScopeTrait
+                                ?.takeIf { RsCodeInsightSettings.getInstance().importOutOfScopeItems }
+                                ?.let { traitScopes.findImportCandidates(e) }
+                                ?.singleOrNull()
+                            super.handleInsert(element, scopeName, context, item)
+
+                            if (traitImportCandidate != null) {
+                                context.commitDocument()
+                                context.getElementOfType<RsElement>()?.let { traitImportCandidate.import(it) }
+                            }
                         }
                     }
-                }
-            ))
+                ))
+            }
         }
+        return false
     }
-    return false
 }
 
-private fun findTraitImportCandidate(methodOrField: RsMethodOrField, resolveVariant: MethodResolveVariant): ImportCandidate? {
-    if (!RsCodeInsightSettings.getInstance().importOutOfScopeItems) return null
-    val ancestor = PsiTreeUtil.getParentOfType(methodOrField, RsBlock::class.java, RsMod::class.java) ?: return null
-    // `AutoImportFix.getImportCandidates` expects original scope element for correct item filtering
-    val scope = CompletionUtil.getOriginalElement(ancestor) as? RsElement ?: return null
-    return AutoImportFix.getImportCandidates(methodOrField.project, scope, listOf(resolveVariant)).orEmpty().singleOrNull()
+private sealed class TraitScope {
+    /** The method is inherent or its trait is already in scope */
+    object InScope : TraitScope()
+    /** The trait is not in scope and its methods should not be suggested */
+    object NotSuggested : TraitScope()
+    /** [traitPath] is shown in the popup, the import candidates are searched only when the method is inserted */
+    class OutOfScope(val traitPath: String?, val isUsedInCrate: Boolean) : TraitScope()
+}
+
+/**
+ * Caches scope information of traits of method completion variants.
+ * Usages of traits in the crate are checked at most for [MAX_OUT_OF_SCOPE_TRAITS] traits
+ * not to slow down the completion popup, methods of other traits are suggested without this check
+ */
+private class TraitScopes(private val methodOrField: RsMethodOrField) {
+    private val scope: RsElement? = run {
+        val ancestor = PsiTreeUtil.getParentOfType(methodOrField, RsBlock::class.java, RsMod::class.java)
+        // `AutoImportFix.getImportCandidates` expects original scope element for correct item filtering
+        ancestor?.let { CompletionUtil.getOriginalElement(it) as? RsElement }
+    }
+    private val cache: MutableMap<RsTraitItem, TraitScope> = hashMapOf()
+    private var outOfScopeTraitsCount: Int = 0
+
+    fun get(variant: MethodResolveVariant): TraitScope {
+        val trait = variant.source.requiredTraitInScope ?: return TraitScope.InScope
+        val scope = scope ?: return TraitScope.InScope
+        return cache.getOrPut(trait) {
+            if (listOf(trait).filterInScope(scope).isNotEmpty()) return@getOrPut TraitScope.InScope
+            if (!RsCodeInsightSettings.getInstance().suggestOutOfScopeItems) return@getOrPut TraitScope.NotSuggested
+            val traitPath = (scope as? RsMod ?: scope.containingMod).let { trait.qualifiedNameRelativeTo(it) }
+            val isUsedInCrate = outOfScopeTraitsCount < MAX_OUT_OF_SCOPE_TRAITS && trait.isUsedInCrate(scope)
+            outOfScopeTraitsCount++
+            TraitScope.OutOfScope(traitPath, isUsedInCrate)
+        }
+    }
+
+    fun findImportCandidates(variant: MethodResolveVariant): List<ImportCandidate> {
+        val scope = scope ?: return emptyList()
+        return AutoImportFix.getImportCandidates(methodOrField.project, scope, listOf(variant)).orEmpty().toList()
+    }
+
+    companion object {
+        private const val MAX_OUT_OF_SCOPE_TRAITS: Int = 50
+    }
+}
+
+/** Checks if the name of the trait is mentioned in a file of the current package other than the trait's one */
+private fun RsTraitItem.isUsedInCrate(context: RsElement): Boolean {
+    val name = name ?: return false
+    val contentRoot = context.containingCargoPackage?.contentRoot ?: return false
+    val traitFile = containingFile.originalFile.virtualFile
+    val searchScope = GlobalSearchScopesCore.directoryScope(project, contentRoot, true)
+    // Only the word index is queried, the found files are not parsed
+    return CacheManager.getInstance(project)
+        .getVirtualFilesWithWord(name, UsageSearchContext.IN_CODE, searchScope, true)
+        .any { it != traitFile }
+}
+
+/** Methods of traits that are not in scope go after the in-scope ones */
+private class OutOfScopeTraitMemberCompletionEntity(
+    private val base: CompletionEntity,
+    private val isTraitUsedInCrate: Boolean
+) : CompletionEntity by base {
+    override fun getBasePriority(context: RsCompletionContext): Double {
+        var priority = base.getBasePriority(context) + OUT_OF_SCOPE_TRAIT_MEMBER_PRIORITY_OFFSET
+        if (isTraitUsedInCrate) {
+            priority += USED_TRAIT_MEMBER_PRIORITY_OFFSET
+        }
+        return priority
+    }
 }
 
 private fun addProcessedPathName(
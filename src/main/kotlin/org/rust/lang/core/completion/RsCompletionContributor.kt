@@ -20,11 +20,15 @@ class RsCompletionContributor : CompletionContributor() {
         extend(CompletionType.BASIC, RsTupleFieldCompletionProvider)
         extend(CompletionType.BASIC, RsDeriveCompletionProvider)
         extend(CompletionType.BASIC, RsAttributeCompletionProvider)
+        extend(CompletionType.BASIC, RsDeriveHelperAttributeCompletionProvider)
         extend(CompletionType.BASIC, RsMacroCompletionProvider)
         extend(CompletionType.BASIC, RsPartialMacroArgumentCompletionProvider)
         extend(CompletionType.BASIC, RsFullMacroArgumentCompletionProvider)
         extend(CompletionType.BASIC, RsCfgAttributeCompletionProvider)
+        extend(CompletionType.BASIC, RsCfgFeatureCompletionProvider)
         extend(CompletionType.BASIC, RsAwaitCompletionProvider)
+        extend(CompletionType.BASIC, RsFormatMacroArgumentCompletionProvider)
+        extend(CompletionType.BASIC, RsAsmOptionCompletionProvider)
     }
 
     fun extend(type: CompletionType?, provider: RsCompletionProvider) {
@@ -12,19 +12,20 @@ import com.intellij.patterns.ElementPattern
 import com.intellij.patterns.PlatformPatterns
 import com.intellij.psi.PsiElement
 import com.intellij.util.ProcessingContext
+import org.rust.cargo.project.workspace.CargoWorkspace
 import org.rust.ide.icons.RsIcons
 import org.rust.ide.icons.multiple
+import org.rust.ide.inspections.import.AutoImportFix
+import org.rust.ide.inspections.import.ImportContext
+import org.rust.ide.inspections.import.import
+import org.rust.ide.settings.RsCodeInsightSettings
 import org.rust.lang.RsLanguage
 import org.rust.lang.core.RsPsiPattern
 import org.rust.lang.core.psi.RsPath
-import org.rust.lang.core.psi.ext.RsStructOrEnumItemElement
-import org.rust.lang.core.psi.ext.ancestorStrict
-import org.rust.lang.core.psi.ext.withDefaultSubst
+import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.psiElement
-import org.rust.lang.core.resolve.ImplLookup
-import org.rust.lang.core.resolve.KnownDerivableTrait
-import org.rust.lang.core.resolve.knownItems
-import org.rust.lang.core.resolve.withDependencies
+import org.rust.lang.core.resolve.*
+import org.rust.lang.core.resolve.indexes.RsProcMacroDeriveIndex
 import org.rust.lang.core.types.TraitRef
 import org.rust.lang.core.with
 
@@ -67,8 +68,70 @@ object RsDeriveCompletionProvider : RsCompletionProvider() {
                     .withIcon(RsIcons.TRAIT)
                 result.addElement(element.withPriority(DEFAULT_PRIORITY))
             }
+
+        addProcMacroDerives(parameters, owner, result)
+    }
+
+    /**
+     * Adds custom derives of proc-macro crates which the current package depends on directly or through
+     * other dependencies, e.g. `Serialize` of `serde_derive` reexported by `serde`.
+     * A `use` item is inserted for a derive which is not in scope
+     */
+    private fun addProcMacroDerives(
+        parameters: CompletionParameters,
+        owner: RsStructOrEnumItemElement,
+        result: CompletionResultSet
+    ) {
+        val path = parameters.position.parent as? RsPath ?: return
+        val pkg = owner.containingCargoPackage ?: return
+        val procMacroPackages = pkg.transitiveDependencies.filter { it.libTarget?.isProcMacro == true }
+        if (procMacroPackages.isEmpty()) return
+        val alreadyDerived = owner.queryAttributes.deriveAttributes
+            .flatMap { it.metaItemArgs?.metaItemList.orEmpty().asSequence() }
+            .mapNotNull { it.name }
+            .toSet()
+        val knownDerivables = KnownDerivableTrait.values().map { it.name }
+        val importContext = ImportContext.from(owner.project, path, true)
+
+        for (derive in RsProcMacroDeriveIndex.findAllDerives(owner.project)) {
+            val name = derive.procMacroDeriveName ?: continue
+            if (name in alreadyDerived || name in knownDerivables) continue
+            val derivePkg = derive.containingCargoPackage ?: continue
+            if (derivePkg !in procMacroPackages) continue
+            val isInScope = derive in collectResolveVariants(name) { processDeriveTraitResolveVariants(path, name, it) }
+            val candidate = if (isInScope) {
+                null
+            } else {
+                // The derive may be unavailable if its crate is neither our dependency, nor reexported by one
+                AutoImportFix.getDeriveImportCandidates(importContext, derive).firstOrNull() ?: continue
+            }
+            val element = LookupElementBuilder.createWithSmartPointer(name, derive)
+                .withIcon(RsIcons.MACRO)
+                .withTypeText(candidate?.qualifiedNamedItem?.containingCargoTarget?.normName ?: derivePkg.normName)
+                .withInsertHandler { context, _ ->
+                    if (candidate != null && context.file.isEdition2018 &&
+                        RsCodeInsightSettings.getInstance().importOutOfScopeItems) {
+                        context.commitDocument()
+                        context.getElementOfType<RsElement>()?.let { candidate.import(it) }
+                    }
+                }
+            result.addElement(element.withPriority(DEFAULT_PRIORITY))
+        }
     }
 
+    /** All packages the package depends on, including dependencies of its dependencies */
+    private val CargoWorkspace.Package.transitiveDependencies: Set<CargoWorkspace.Package>
+        get() {
+            val result = hashSetOf<CargoWorkspace.Package>()
+            fun collect(pkg: CargoWorkspace.Package) {
+                for (dependency in pkg.dependencies) {
+                    if (result.add(dependency.pkg)) collect(dependency.pkg)
+                }
+            }
+            collect(this)
+            return result
+        }
+
     override val elementPattern: ElementPattern<out PsiElement>
         get() {
             return PlatformPatterns.psiElement()
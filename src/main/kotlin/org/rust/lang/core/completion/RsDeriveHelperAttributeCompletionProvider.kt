@@ -0,0 +1,125 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+import com.intellij.codeInsight.completion.CompletionParameters
+import com.intellij.codeInsight.completion.CompletionResultSet
+import com.intellij.codeInsight.lookup.LookupElement
+import com.intellij.codeInsight.lookup.LookupElementBuilder
+import com.intellij.openapi.editor.EditorModificationUtil
+import com.intellij.patterns.ElementPattern
+import com.intellij.patterns.PlatformPatterns.psiElement
+import com.intellij.psi.PsiElement
+import com.intellij.util.ProcessingContext
+import org.rust.ide.icons.RsIcons
+import org.rust.lang.RsLanguage
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.psiElement
+import org.rust.lang.core.with
+
+/**
+ * Completes keys of helper attributes of well-known custom derives, e.g. `rename` in `#[serde(rename = "...")]`.
+ * The keys depend on whether the attribute is applied to the deriving item, to its enum variant or to its field
+ */
+object RsDeriveHelperAttributeCompletionProvider : RsCompletionProvider() {
+
+    private class KnownHelperAttribute(
+        val crateName: String,
+        val attributeName: String,
+        containerKeys: String,
+        variantKeys: String,
+        fieldKeys: String
+    ) {
+        val containerKeys: List<String> = containerKeys.split(' ').filter { it.isNotEmpty() }
+        val variantKeys: List<String> = variantKeys.split(' ').filter { it.isNotEmpty() }
+        val fieldKeys: List<String> = fieldKeys.split(' ').filter { it.isNotEmpty() }
+    }
+
+    // Keys ending with `=` take a string value, keys ending with `()` take a list of arguments
+    private val KNOWN_HELPER_ATTRIBUTES: List<KnownHelperAttribute> = listOf(
+        KnownHelperAttribute(
+            "serde_derive", "serde",
+            containerKeys = "rename= rename_all= deny_unknown_fields tag= content= untagged bound= default " +
+                "remote= transparent from= try_from= into= crate=",
+            variantKeys = "rename= alias= rename_all= skip skip_serializing skip_deserializing serialize_with= " +
+                "deserialize_with= with= bound= borrow other",
+            fieldKeys = "rename= alias= default flatten skip skip_serializing skip_deserializing skip_serializing_if= " +
+                "serialize_with= deserialize_with= with= borrow bound= getter="
+        ),
+        KnownHelperAttribute(
+            "clap_derive", "clap",
+            containerKeys = "name= version author about long_about rename_all= rename_all_env= setting() " +
+                "global_setting() subcommand",
+            variantKeys = "name= about= alias= skip flatten subcommand external_subcommand",
+            fieldKeys = "short long env= default_value= about= help= value_name= parse() possible_values() " +
+                "required conflicts_with= requires= multiple_occurrences arg_enum subcommand flatten skip"
+        ),
+        KnownHelperAttribute(
+            "thiserror_impl", "error",
+            containerKeys = "transparent",
+            variantKeys = "transparent",
+            fieldKeys = ""
+        )
+    )
+
+    override fun addCompletions(
+        parameters: CompletionParameters,
+        context: ProcessingContext,
+        result: CompletionResultSet
+    ) {
+        // `#[helper(key)]`, see `elementPattern`
+        val key = parameters.position.ancestorStrict<RsMetaItem>() ?: return
+        val helperAttribute = key.ancestorStrict<RsMetaItem>() ?: return
+        val owner = helperAttribute.ancestorStrict<RsOuterAttributeOwner>() ?: return
+        val derive = helperAttribute.path?.reference?.resolve() as? RsFunction ?: return
+        if (!derive.isCustomDeriveProcMacroDef) return
+        val crateName = derive.containingCargoPackage?.normName ?: return
+        val known = KNOWN_HELPER_ATTRIBUTES.find {
+            it.crateName == crateName && it.attributeName == helperAttribute.name
+        } ?: return
+
+        val keys = when (owner) {
+            is RsStructOrEnumItemElement -> known.containerKeys
+            is RsEnumVariant -> known.variantKeys
+            is RsFieldDecl -> known.fieldKeys
+            else -> return
+        }
+        val usedKeys = helperAttribute.metaItemArgs?.metaItemList.orEmpty().mapNotNull { it.name }
+        for (key in keys) {
+            val name = key.removeSuffix("=").removeSuffix("()")
+            if (name !in usedKeys) {
+                result.addElement(createLookupElement(key))
+            }
+        }
+    }
+
+    override val elementPattern: ElementPattern<PsiElement>
+        get() = psiElement()
+            .withLanguage(RsLanguage)
+            .withParent(psiElement<RsPath>()
+                .with("PrimitivePath") { path -> path.path == null }
+                .withParent(psiElement<RsMetaItem>()
+                    .withSuperParent(2, psiElement<RsMetaItem>().withParent(psiElement<RsOuterAttr>()))
+                )
+            )
+
+    private fun createLookupElement(key: String): LookupElement = when {
+        key.endsWith("=") -> LookupElementBuilder.create(key.removeSuffix("="))
+            .withTailText(" = \"...\"")
+            .withInsertHandler { context, _ ->
+                context.document.insertString(context.selectionEndOffset, " = \"\"")
+                EditorModificationUtil.moveCaretRelatively(context.editor, 4)
+            }
+        key.endsWith("()") -> LookupElementBuilder.create(key.removeSuffix("()"))
+            .withInsertHandler { context, _ ->
+                context.document.insertString(context.selectionEndOffset, "()")
+                EditorModificationUtil.moveCaretRelatively(context.editor, 1)
+            }
+        else -> LookupElementBuilder.create(key)
+    }
+        .withIcon(RsIcons.ATTRIBUTE)
+}
@@ -0,0 +1,125 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+import com.intellij.codeInsight.completion.CompletionParameters
+import com.intellij.codeInsight.completion.CompletionResultSet
+import com.intellij.codeInsight.lookup.LookupElementBuilder
+import com.intellij.patterns.ElementPattern
+import com.intellij.patterns.PlatformPatterns.psiElement
+import com.intellij.patterns.StandardPatterns.or
+import com.intellij.psi.PsiElement
+import com.intellij.util.ProcessingContext
+import org.rust.ide.icons.RsIcons
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.formatStringArgumentIndex
+import org.rust.lang.core.psiElement
+import org.rust.lang.core.resolve.processLocalVariables
+import org.rust.lang.utils.parseFormatString
+
+/**
+ * Provides completion inside `{}` placeholders of a format string, e.g. `println!("{/*caret*/}", a = 1)`.
+ * Completes named and positional arguments, local variables (for inline captured identifiers)
+ * and format traits (after `:`)
+ */
+object RsFormatMacroArgumentCompletionProvider : RsCompletionProvider() {
+
+    private val FORMAT_TRAITS: List<Pair<String, String>> = listOf(
+        "?" to "Debug",
+        "#?" to "Debug, pretty-printed",
+        "x" to "LowerHex",
+        "X" to "UpperHex",
+        "x?" to "Debug, lower-case hex integers",
+        "X?" to "Debug, upper-case hex integers",
+        "o" to "Octal",
+        "b" to "Binary",
+        "e" to "LowerExp",
+        "E" to "UpperExp",
+        "p" to "Pointer"
+    )
+
+    override val elementPattern: ElementPattern<PsiElement>
+        get() = psiElement()
+            .withElementType(or(psiElement(RsElementTypes.STRING_LITERAL), psiElement(RsElementTypes.RAW_STRING_LITERAL)))
+            .withParent(psiElement<RsLitExpr>().withParent(psiElement<RsFormatMacroArg>()))
+
+    override fun addCompletions(parameters: CompletionParameters, context: ProcessingContext, result: CompletionResultSet) {
+        val literal = parameters.position.parent as? RsLitExpr ?: return
+        val formatArg = literal.parent as? RsFormatMacroArg ?: return
+        val arguments = formatArg.argumentsIfFormatString() ?: return
+        val placeholder = findPlaceholderBeforeCaret(literal, parameters.offset) ?: return
+
+        val colon = placeholder.indexOf(':')
+        if (colon == -1) {
+            addArgumentCompletions(result.withPrefixMatcher(placeholder), literal, arguments)
+        } else {
+            val spec = placeholder.substring(colon + 1)
+            val dot = spec.lastIndexOf('.')
+            if (dot == -1) {
+                val specResult = result.withPrefixMatcher(spec)
+                for ((trait, description) in FORMAT_TRAITS) {
+                    specResult.addElement(LookupElementBuilder.create(trait).withTypeText(description))
+                }
+                addWidthOrPrecisionCompletions(specResult, arguments)
+            } else {
+                addWidthOrPrecisionCompletions(result.withPrefixMatcher(spec.substring(dot + 1)), arguments)
+            }
+        }
+    }
+
+    private fun addArgumentCompletions(result: CompletionResultSet, literal: RsLitExpr, arguments: List<RsFormatMacroArg>) {
+        val namedArguments = arguments.mapNotNull { it.identifier?.text }
+        for (name in namedArguments) {
+            result.addElement(LookupElementBuilder.create(name).withTypeText("named argument"))
+        }
+        for (position in arguments.indices) {
+            if (arguments[position].identifier != null) break
+            result.addElement(LookupElementBuilder.create(position.toString()).withTypeText("positional argument"))
+        }
+        processLocalVariables(literal) { binding ->
+            val name = binding.name ?: return@processLocalVariables
+            if (name !in namedArguments) {
+                result.addElement(LookupElementBuilder.create(name).withIcon(RsIcons.BINDING))
+            }
+        }
+    }
+
+    /** `{:width$}` and `{:.precision$}` */
+    private fun addWidthOrPrecisionCompletions(result: CompletionResultSet, arguments: List<RsFormatMacroArg>) {
+        for (argument in arguments) {
+            val name = argument.identifier?.text ?: continue
+            result.addElement(LookupElementBuilder.create("$name$").withTypeText("named argument"))
+        }
+    }
+
+    /**
+     * Returns the text between the `{` of the placeholder and the caret or `null` if the caret is not inside
+     * a placeholder. The format string is split into placeholders by [parseFormatString] like in highlighting
+     */
+    private fun findPlaceholderBeforeCaret(literal: RsLitExpr, caretOffset: Int): String? {
+        val parseCtx = parseFormatString(literal) ?: return null
+        val caretOffsetInValue = parseCtx.toValueOffset(caretOffset)
+        val innerContent = parseCtx.parameters
+            .mapNotNull { it.innerContent }
+            .find { caretOffsetInValue in it.range.first..it.range.last + 1 }
+            ?: return null
+        return innerContent.value.substring(0, caretOffsetInValue - innerContent.range.first)
+    }
+
+    /** If this argument is the format string, returns the arguments following it, otherwise `null` */
+    private fun RsFormatMacroArg.argumentsIfFormatString(): List<RsFormatMacroArg>? {
+        val (arguments, formatStringIndex) = when (val parent = parent) {
+            is RsFormatMacroArgument -> {
+                val macroCall = parent.parent as? RsMacroCall ?: return null
+                parent.formatMacroArgList to (macroCall.formatStringArgumentIndex ?: return null)
+            }
+            is RsLogMacroArgument -> parent.formatMacroArgList to 0
+            else -> return null
+        }
+        if (arguments.getOrNull(formatStringIndex) != this) return null
+        return arguments.drop(formatStringIndex + 1)
+    }
+}
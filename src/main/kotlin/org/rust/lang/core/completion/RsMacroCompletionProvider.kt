@@ -15,6 +15,7 @@ import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiErrorElement
 import com.intellij.psi.PsiWhiteSpace
 import com.intellij.util.ProcessingContext
+import org.rust.ide.settings.RsCodeInsightSettings
 import org.rust.lang.RsLanguage
 import org.rust.lang.core.RsPsiPattern
 import org.rust.lang.core.psi.RsElementTypes.COLONCOLON
@@ -82,5 +83,6 @@ object RsMacroCompletionProvider : RsCompletionProvider() {
         }
 
     private fun isHidden(macro: RsMacro, mod: RsMod): Boolean =
-        macro.queryAttributes.isDocHidden && macro.containingMod != mod
+        !RsCodeInsightSettings.getInstance().suggestDocHiddenItems &&
+            macro.queryAttributes.isDocHidden && macro.containingMod != mod
 }
@@ -15,6 +15,7 @@ import com.intellij.openapi.project.Project
 import com.intellij.openapi.util.Key
 import com.intellij.openapi.util.ModificationTracker
 import com.intellij.openapi.util.SimpleModificationTracker
+import com.intellij.openapi.util.TextRange
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.openapi.vfs.VirtualFileManager
 import com.intellij.openapi.vfs.newvfs.FileAttribute
@@ -25,6 +26,8 @@ import com.intellij.psi.StubBasedPsiElement
 import com.intellij.psi.impl.source.StubbedSpine
 import com.intellij.util.indexing.FileBasedIndexScanRunnableCollector
 import gnu.trove.TIntObjectHashMap
+import org.rust.cargo.project.settings.RustProjectSettingsService.Companion.MAX_MACRO_EXPANSION_DEPTH_LIMIT
+import org.rust.cargo.project.settings.rustSettings
 import org.rust.cargo.project.workspace.PackageOrigin
 import org.rust.lang.RsFileType
 import org.rust.lang.core.macros.MacroExpansionManagerImpl.Testmarks
@@ -35,10 +38,11 @@ import org.rust.lang.core.psi.ext.bodyHash
 import org.rust.lang.core.psi.ext.containingCargoTarget
 import org.rust.lang.core.psi.ext.resolveToMacro
 import org.rust.lang.core.psi.ext.stubDescendantsOfTypeStrict
-import org.rust.lang.core.resolve.DEFAULT_RECURSION_LIMIT
 import org.rust.lang.core.stubs.RsFileStub
 import org.rust.openapiext.*
 import org.rust.stdext.HashCode
+import org.rust.stdext.readVarInt
+import org.rust.stdext.writeVarInt
 import java.io.DataInputStream
 import java.io.DataOutputStream
 import java.io.IOException
@@ -50,12 +54,16 @@ class ExpandedMacroStorage(val project: Project) {
     // Data structures are guarded by the platform RWLock
     private val sourceFiles: TIntObjectHashMap<SourceFile> = TIntObjectHashMap()
     private val expandedFileToInfo: TIntObjectHashMap<ExpandedMacroInfoImpl> = TIntObjectHashMap()
-    private val stepped: Array<MutableList<SourceFile>?> = arrayOfNulls(DEFAULT_RECURSION_LIMIT)
+    private val stepped: Array<MutableList<SourceFile>?> = arrayOfNulls(MAX_MACRO_EXPANSION_DEPTH_LIMIT)
     private val _modificationTracker: SimpleModificationTracker = SimpleModificationTracker()
 
     val isEmpty: Boolean get() = sourceFiles.isEmpty
     val modificationTracker: ModificationTracker get() = _modificationTracker
 
+    /** Macro calls in files of this depth (i.e. the number of macro expansions they are nested in) are not expanded */
+    val depthLimit: Int
+        get() = project.rustSettings.macroExpansionDepthLimit.coerceIn(1, MAX_MACRO_EXPANSION_DEPTH_LIMIT)
+
     fun deserialize(sfs: List<SourceFile>) {
         for (sf in sfs) {
             sourceFiles.put(sf.fileId, sf)
@@ -101,16 +109,24 @@ class ExpandedMacroStorage(val project: Project) {
         }
     }
 
+    /**
+     * @param changedFiles if not `null`, only macro calls located in these root files (or in expansions
+     * of their macro calls) are validated
+     */
     fun makeValidationTask(
         workspaceOnly: Boolean,
-        map: MutableMap<SourceFile, List<RsMacroCall>> = mutableMapOf()
+        map: MutableMap<SourceFile, List<RsMacroCall>> = mutableMapOf(),
+        changedFiles: Set<VirtualFile>? = null
     ): Sequence<List<Extractable>> {
         checkReadAccessAllowed()
+        if (changedFiles != null) Testmarks.changedFilesValidation.hit()
 
         return stepped.asSequence().withIndex().map { (i, step) ->
             // Release memory. [map] contains prefetched calls only for the first step, so clear it on the second step
             if (i == 1) map.clear()
-            step?.map { sf -> Extractable(sf, workspaceOnly, map[sf]) }.orEmpty()
+            step.orEmpty()
+                .filter { sf -> changedFiles == null || sf.rootFile in changedFiles }
+                .map { sf -> Extractable(sf, workspaceOnly, map[sf]) }
         }
     }
 
@@ -133,7 +149,10 @@ class ExpandedMacroStorage(val project: Project) {
         defHash: HashCode?,
         expansionFile: VirtualFile?,
         ranges: RangeMap?,
-        expansionTextHash: Long
+        callBodyTokens: List<TextRange>,
+        expansionTextHash: Long,
+        isDepthLimitReached: Boolean,
+        isTokenLimitReached: Boolean
     ): ExpandedMacroInfoImpl {
         checkWriteAccessAllowed()
         _modificationTracker.incModificationCount()
@@ -148,7 +167,9 @@ class ExpandedMacroStorage(val project: Project) {
             callHash,
             expansionTextHash,
             oldInfo.macroCallStubIndex,
-            oldInfo.macroCallStrongRef
+            oldInfo.macroCallStrongRef,
+            isDepthLimitReached,
+            isTokenLimitReached
         )
 
         sourceFile.replaceInfo(oldInfo, newInfo)
@@ -163,7 +184,7 @@ class ExpandedMacroStorage(val project: Project) {
         newInfo.expansionFile?.let { getOrCreateSourceFile(it) }
 
         if (newInfo.expansionFile != null && ranges != null) {
-            newInfo.expansionFile.writeRangeMap(ranges)
+            newInfo.expansionFile.writeRangeMap(MacroExpansionRanges(ranges, callBodyTokens))
         }
 
         return newInfo
@@ -196,7 +217,7 @@ class ExpandedMacroStorage(val project: Project) {
         val fileId = callFile.fileId
         return sourceFiles[fileId] ?: run {
             val depth = (getInfoForExpandedFile(callFile)?.sourceFile?.depth ?: -1) + 1
-            if (depth >= DEFAULT_RECURSION_LIMIT) return null
+            if (depth >= depthLimit) return null
             val sf = SourceFile(this, callFile, depth)
             getOrPutStagedList(sf).add(sf)
             sourceFiles.put(fileId, sf)
@@ -223,6 +244,27 @@ class ExpandedMacroStorage(val project: Project) {
         return sf.getInfoForCall(call)
     }
 
+    /**
+     * Walks expansions nested into [info] (including [info] itself) and returns the first one
+     * matching [predicate], e.g. the one whose macro calls are not expanded because of [depthLimit]
+     */
+    fun findInfoInExpansionTree(
+        info: ExpandedMacroInfo,
+        predicate: (ExpandedMacroInfoImpl) -> Boolean
+    ): ExpandedMacroInfo? {
+        checkReadAccessAllowed()
+
+        val stack = mutableListOf(info as ExpandedMacroInfoImpl)
+        while (stack.isNotEmpty()) {
+            val current = stack.removeAt(stack.lastIndex)
+            if (predicate(current)) return current
+            val expansionFile = current.expansionFile ?: continue
+            // Depth strictly increases from the source file to the expansion file, so there are no cycles
+            stack += getSourceFile(expansionFile)?.getInfos() ?: continue
+        }
+        return null
+    }
+
     companion object {
         private val LOG = Logger.getInstance(ExpandedMacroStorage::class.java)
 
@@ -238,8 +280,8 @@ class ExpandedMacroStorage(val project: Project) {
     }
 }
 
-private const val STORAGE_VERSION = 12
-private const val RANGE_MAP_ATTRIBUTE_VERSION = 2
+private const val STORAGE_VERSION = 15
+private const val RANGE_MAP_ATTRIBUTE_VERSION = 3
 
 class SerializedExpandedMacroStorage private constructor(
     private val serSourceFiles: List<SerializedSourceFile>
@@ -350,6 +392,9 @@ class SourceFile(
         findRootSourceFile()
     }
 
+    /** A non-expansion file, from which macro calls of this file are (transitively) expanded */
+    val rootFile: VirtualFile get() = rootSourceFile.file
+
     // Should not be lazy b/c target/pkg/origin can be changed
     private val isBelongToWorkspace: Boolean
         get() = rootSourceFile.loadPsi()?.containingCargoTarget?.pkg?.origin == PackageOrigin.WORKSPACE
@@ -400,6 +445,12 @@ class SourceFile(
         infos.isEmpty()
     }
 
+    fun getInfos(): List<ExpandedMacroInfoImpl> = syncAndMapInfos { it }
+
+    /** Macro calls in expansions of this file are not expanded because of [ExpandedMacroStorage.depthLimit] */
+    val isLastExpansionStep: Boolean
+        get() = sync { depth + 1 >= storage.depthLimit }
+
     private enum class RefKind {
         /** The [file] is not valid (e.g. is deleted) */
         INVALID,
@@ -835,7 +886,11 @@ class ExpandedMacroInfoImpl(
     val callHash: HashCode?,
     override val expansionFileHash: Long = 0,
     var macroCallStubIndex: Int = -1,
-    var macroCallStrongRef: RsMacroCall? = null
+    var macroCallStrongRef: RsMacroCall? = null,
+    /** The expansion contains macro calls that are not expanded because of [ExpandedMacroStorage.depthLimit] */
+    val isDepthLimitReached: Boolean = false,
+    /** The macro call is not expanded because its expansion exceeds the macro expansion token limit */
+    val isTokenLimitReached: Boolean = false
 ) : ExpandedMacroInfo {
     private val expansionFileUrl: String? get() = expansionFile?.url
     val fileId: Int get() = expansionFile?.fileId ?: -1
@@ -843,6 +898,11 @@ class ExpandedMacroInfoImpl(
     override fun getMacroCall(): RsMacroCall? =
         sourceFile.getCallForInfo(this)
 
+    /**
+     * [callHash] is a hash of the macro call body tokens, so whitespace and comment edits inside the body
+     * don't make the info outdated. The stored ranges are remapped to the new body in such a case
+     * (see [MacroExpansionRanges])
+     */
     override fun isUpToDate(call: RsMacroCall, def: RsMacro?): Boolean =
         callHash == call.bodyHash && def?.bodyHash == defHash
 
@@ -872,6 +932,8 @@ class ExpandedMacroInfoImpl(
             writeHashCodeNullable(callHash)
             writeLong(expansionFileHash)
             writeInt(macroCallStubIndex)
+            writeBoolean(isDepthLimitReached)
+            writeBoolean(isTokenLimitReached)
         }
     }
 
@@ -886,7 +948,9 @@ private data class SerializedExpandedMacroInfo(
     val callHash: HashCode?,
     val defHash: HashCode?,
     val expansionFileHash: Long,
-    val stubIndex: Int
+    val stubIndex: Int,
+    val isDepthLimitReached: Boolean,
+    val isTokenLimitReached: Boolean
 ) {
     fun toExpandedMacroInfo(sourceFile: SourceFile): ExpandedMacroInfoImpl? {
         val file = if (expansionFileUrl != null) {
@@ -900,7 +964,9 @@ private data class SerializedExpandedMacroInfo(
             callHash,
             defHash,
             expansionFileHash,
-            stubIndex
+            stubIndex,
+            isDepthLimitReached = isDepthLimitReached,
+            isTokenLimitReached = isTokenLimitReached
         )
     }
 
@@ -911,7 +977,9 @@ private data class SerializedExpandedMacroInfo(
                 data.readHashCodeNullable(),
                 data.readHashCodeNullable(),
                 data.readLong(),
-                data.readInt()
+                data.readInt(),
+                data.readBoolean(),
+                data.readBoolean()
             )
         }
     }
@@ -971,15 +1039,59 @@ private fun StubBasedPsiElement<*>.calcStubIndex(): Int {
 }
 
 
+/**
+ * The [ranges] of a macro expansion and the macro call body [tokens][org.rust.lang.core.psi.ext.bodyTokens]
+ * the ranges are computed for. The call is not re-expanded if its body is edited without changing the tokens,
+ * so the ranges are remapped to the current tokens of the call body
+ */
+class MacroExpansionRanges(private val ranges: RangeMap, private val callBodyTokens: List<TextRange>) {
+    @Volatile
+    private var rebased: Pair<List<TextRange>, RangeMap>? = null
+
+    fun mapTo(currentTokens: List<TextRange>): RangeMap {
+        if (currentTokens === callBodyTokens) return ranges
+        rebased?.let { (tokens, rebasedRanges) -> if (tokens === currentTokens) return rebasedRanges }
+        // The tokens differ only if the call is not re-expanded yet, so the stale ranges are used as is
+        if (currentTokens.size != callBodyTokens.size) return ranges
+        Testmarks.rangesRebased.hit()
+        return ranges.rebase(callBodyTokens, currentTokens).also { rebased = currentTokens to it }
+    }
+
+    fun writeTo(data: DataOutputStream) {
+        ranges.writeTo(data)
+        data.writeInt(callBodyTokens.size)
+        var prevEndOffset = 0
+        for (token in callBodyTokens) {
+            data.writeVarInt(token.startOffset - prevEndOffset)
+            data.writeVarInt(token.length)
+            prevEndOffset = token.endOffset
+        }
+    }
+
+    companion object {
+        fun readFrom(data: DataInputStream): MacroExpansionRanges {
+            val ranges = RangeMap.readFrom(data)
+            val size = data.readInt()
+            var prevEndOffset = 0
+            val tokens = (0 until size).map {
+                val startOffset = prevEndOffset + data.readVarInt()
+                TextRange(startOffset, startOffset + data.readVarInt()).also { prevEndOffset = it.endOffset }
+            }
+            return MacroExpansionRanges(ranges, tokens)
+        }
+    }
+}
+
 /** We use [WeakReference] because uncached [loadRangeMap] is quite cheap */
-private val MACRO_RANGE_MAP_CACHE_KEY: Key<WeakReference<RangeMap>> = Key.create("MACRO_RANGE_MAP_CACHE_KEY")
+private val MACRO_RANGE_MAP_CACHE_KEY: Key<WeakReference<MacroExpansionRanges>> =
+    Key.create("MACRO_RANGE_MAP_CACHE_KEY")
 private val RANGE_MAP_ATTRIBUTE = FileAttribute(
     "org.rust.macro.RangeMap",
     RANGE_MAP_ATTRIBUTE_VERSION,
     /*fixedSize = */ true // don't allocate extra space for each record
 )
 
-private fun VirtualFile.writeRangeMap(ranges: RangeMap) {
+private fun VirtualFile.writeRangeMap(ranges: MacroExpansionRanges) {
     checkWriteAccessAllowed()
 
     RANGE_MAP_ATTRIBUTE.writeAttribute(this).use {
@@ -991,13 +1103,13 @@ private fun VirtualFile.writeRangeMap(ranges: RangeMap) {
     }
 }
 
-fun VirtualFile.loadRangeMap(): RangeMap? {
+fun VirtualFile.loadRangeMap(): MacroExpansionRanges? {
     checkReadAccessAllowed()
 
     getUserData(MACRO_RANGE_MAP_CACHE_KEY)?.get()?.let { return it }
 
     val data = RANGE_MAP_ATTRIBUTE.readAttribute(this) ?: return null
-    val ranges = RangeMap.readFrom(data)
+    val ranges = MacroExpansionRanges.readFrom(data)
     putUserData(MACRO_RANGE_MAP_CACHE_KEY, WeakReference(ranges))
     return ranges
 }
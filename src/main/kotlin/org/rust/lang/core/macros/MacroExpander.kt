@@ -18,6 +18,8 @@ import com.intellij.psi.tree.TokenSet
 import com.intellij.psi.util.CachedValueProvider
 import com.intellij.psi.util.CachedValuesManager
 import com.intellij.util.SmartList
+import org.rust.cargo.project.settings.rustSettings
+import org.rust.lang.core.lexer.RsLexer
 import org.rust.lang.core.parser.RustParserUtil.collapsedTokenType
 import org.rust.lang.core.parser.createAdaptedRustPsiBuilder
 import org.rust.lang.core.parser.rawLookupText
@@ -99,10 +101,24 @@ data class MetaVarValue(
     val offsetInCallBody: Int
 )
 
+sealed class MacroExpansionTextResult {
+    class Ok(val text: CharSequence, val ranges: RangeMap) : MacroExpansionTextResult()
+    /** The expansion has more tokens than [org.rust.cargo.project.settings.RustProjectSettingsService.macroExpansionTokenLimit] */
+    object TokenLimitReached : MacroExpansionTextResult()
+    object Fail : MacroExpansionTextResult()
+}
+
 class MacroExpander(val project: Project) {
     fun expandMacroAsText(def: RsMacro, call: RsMacroCall): Pair<CharSequence, RangeMap>? {
-        val (case, subst, loweringRanges) = findMatchingPattern(def, call) ?: return null
-        val macroExpansion = case.macroExpansion?.macroExpansionContents ?: return null
+        val result = expandMacroAsTextWithLimits(def, call) as? MacroExpansionTextResult.Ok ?: return null
+        return result.text to result.ranges
+    }
+
+    /** Same as [expandMacroAsText], but allows to distinguish too large expansions from failed ones */
+    fun expandMacroAsTextWithLimits(def: RsMacro, call: RsMacroCall): MacroExpansionTextResult {
+        val fail = MacroExpansionTextResult.Fail
+        val (case, subst, loweringRanges) = findMatchingPattern(def, call) ?: return fail
+        val macroExpansion = case.macroExpansion?.macroExpansionContents ?: return fail
 
         val substWithGlobalVars = WithParent(
             subst,
@@ -115,9 +131,33 @@ class MacroExpander(val project: Project) {
             )
         )
 
-        return substituteMacro(macroExpansion, substWithGlobalVars)?.let { (text, ranges) ->
-            text to loweringRanges.mapAll(ranges)
+        val (text, ranges) = substituteMacro(macroExpansion, substWithGlobalVars) ?: return fail
+        val tokenCount = countTokensOverLimit(text)
+        if (tokenCount != null) {
+            MacroExpansionMarks.tokenLimitReached.hit()
+            val path = call.containingFile?.virtualFile?.path
+            MACRO_LOG.info("Macro expansion token limit reached: `${call.path.referenceName}!` in $path ($tokenCount tokens)")
+            return MacroExpansionTextResult.TokenLimitReached
         }
+        return MacroExpansionTextResult.Ok(text, loweringRanges.mapAll(ranges))
+    }
+
+    /** Returns the number of tokens in [text] if it's greater than the token limit, `null` otherwise */
+    private fun countTokensOverLimit(text: CharSequence): Int? {
+        val limit = project.rustSettings.macroExpansionTokenLimit
+        // Each token takes at least one char, so there is no need to lex short expansions
+        if (text.length <= limit) return null
+        val lexer = RsLexer()
+        lexer.start(text)
+        var tokens = 0
+        while (lexer.tokenType != null) {
+            val tokenType = lexer.tokenType
+            if (tokenType != TokenType.WHITE_SPACE && tokenType !in RS_COMMENTS) {
+                tokens++
+            }
+            lexer.advance()
+        }
+        return tokens.takeIf { it > limit }
     }
 
     private fun findMatchingPattern(
@@ -260,23 +300,6 @@ class MacroExpander(val project: Project) {
         append(str)
     }
 
-    private fun MutableList<MappedTextRange>.mergeAdd(range: MappedTextRange) {
-        val last = lastOrNull() ?: run {
-            add(range)
-            return
-        }
-
-        if (last.srcEndOffset == range.srcOffset && last.dstEndOffset == range.dstOffset) {
-            set(size - 1, MappedTextRange(
-                last.srcOffset,
-                last.dstOffset,
-                last.length + range.length
-            ))
-        } else {
-            add(range)
-        }
-    }
-
     companion object {
         const val EXPANDER_VERSION = 5
         private val USELESS_PARENS_EXPRS = tokenSetOf(
@@ -536,4 +559,6 @@ object MacroExpansionMarks {
     val groupMatchedEmptyTT = Testmark("groupMatchedEmptyTT")
     val substMetaVarNotFound = Testmark("substMetaVarNotFound")
     val docsLowering = Testmark("docsLowering")
+    val depthLimitReached = Testmark("depthLimitReached")
+    val tokenLimitReached = Testmark("tokenLimitReached")
 }
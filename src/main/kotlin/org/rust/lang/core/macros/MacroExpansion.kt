@@ -8,6 +8,7 @@ package org.rust.lang.core.macros
 import com.intellij.openapi.util.Key
 import com.intellij.openapi.vfs.VirtualFileWithId
 import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.bodyTokens
 import org.rust.lang.core.psi.ext.stubChildrenOfType
 import org.rust.lang.core.psi.ext.stubDescendantOfTypeOrStrict
 
@@ -125,14 +126,14 @@ fun MacroExpander.expandMacro(
 
 private val MACRO_RANGE_MAP_KEY: Key<RangeMap> = Key.create("MACRO_RANGE_MAP_KEY")
 
-val MacroExpansion.ranges: RangeMap
-    get() {
-        val file = file
-        val virtualFile = file.virtualFile
-        return if (virtualFile is VirtualFileWithId) {
-            virtualFile.loadRangeMap()
-        } else {
-            // NEVER_CHANGED b/c light vfile will be fully replaced along with all caches after the macro change
-            file.getUserData(MACRO_RANGE_MAP_KEY)
-        } ?: RangeMap.EMPTY
-    }
+/** The ranges of the expansion mapped to the current body of the [call] the expansion is expanded from */
+fun MacroExpansion.rangesFor(call: RsMacroCall): RangeMap {
+    val file = file
+    val virtualFile = file.virtualFile
+    return if (virtualFile is VirtualFileWithId) {
+        virtualFile.loadRangeMap()?.mapTo(call.bodyTokens)
+    } else {
+        // NEVER_CHANGED b/c light vfile will be fully replaced along with all caches after the macro change
+        file.getUserData(MACRO_RANGE_MAP_KEY)
+    } ?: RangeMap.EMPTY
+}
@@ -30,7 +30,9 @@ import com.intellij.psi.PsiFile
 import com.intellij.psi.PsiManager
 import com.intellij.psi.search.GlobalSearchScope
 import com.intellij.psi.search.GlobalSearchScopes
+import com.intellij.psi.util.CachedValue
 import com.intellij.psi.util.CachedValueProvider
+import com.intellij.psi.util.CachedValuesManager
 import com.intellij.util.indexing.FileBasedIndex
 import com.intellij.util.indexing.IndexableFileSet
 import com.intellij.util.io.*
@@ -68,6 +70,16 @@ interface MacroExpansionManager {
     /** Optimized equivalent for `getExpandedFrom(element)?.context` */
     fun getContextOfMacroCallExpandedFrom(element: RsExpandedElement): PsiElement?
     fun isExpansionFileOfCurrentProject(file: VirtualFile): Boolean
+    /**
+     * Returns a macro call from the expansion tree of [call] whose expansion contains macro calls
+     * that are not expanded because of the macro expansion depth limit, or `null` if there is no such call
+     */
+    fun getMacroCallWithReachedDepthLimit(call: RsMacroCall): RsMacroCall?
+    /**
+     * Returns a macro call from the expansion tree of [call] that is not expanded because its expansion
+     * exceeds the macro expansion token limit, or `null` if there is no such call
+     */
+    fun getMacroCallWithReachedTokenLimit(call: RsMacroCall): RsMacroCall?
     fun reexpand()
 
     val macroExpansionMode: MacroExpansionMode
@@ -232,6 +244,24 @@ class MacroExpansionManagerImpl(
     override fun isExpansionFileOfCurrentProject(file: VirtualFile): Boolean =
         inner?.isExpansionFileOfCurrentProject(file) == true
 
+    override fun getMacroCallWithReachedDepthLimit(call: RsMacroCall): RsMacroCall? {
+        val inner = inner
+        return if (inner != null && inner.isExpansionModeNew) {
+            inner.getMacroCallWithReachedDepthLimit(call)
+        } else {
+            null
+        }
+    }
+
+    override fun getMacroCallWithReachedTokenLimit(call: RsMacroCall): RsMacroCall? {
+        val inner = inner
+        return if (inner != null && inner.isExpansionModeNew) {
+            inner.getMacroCallWithReachedTokenLimit(call)
+        } else {
+            null
+        }
+    }
+
     override fun reexpand() {
         inner?.reexpand()
     }
@@ -273,6 +303,10 @@ class MacroExpansionManagerImpl(
         val refsRecoverExactHit = Testmark("refsRecoverExactHit")
         val refsRecoverCallHit = Testmark("refsRecoverCallHit")
         val refsRecoverNotHit = Testmark("refsRecoverNotHit")
+        val validationScheduled = Testmark("validationScheduled")
+        val changedFilesValidation = Testmark("changedFilesValidation")
+        val expansionFileReused = Testmark("expansionFileReused")
+        val rangesRebased = Testmark("rangesRebased")
     }
 }
 
@@ -651,6 +685,10 @@ private class MacroExpansionServiceImplInner(
                 if (!e.affectsCargoMetadata) { // if affect cargo metadata, will be invoked by CARGO_PROJECTS_TOPIC
                     if (e.isChanged(RustProjectSettingsService.State::macroExpansionEngine)) {
                         settingsChanged()
+                    } else if (isExpansionModeNew && (
+                            e.isChanged(RustProjectSettingsService.State::macroExpansionDepthLimit) ||
+                                e.isChanged(RustProjectSettingsService.State::macroExpansionTokenLimit))) {
+                        reexpand()
                     }
                 }
             }
@@ -722,6 +760,9 @@ private class MacroExpansionServiceImplInner(
 
         private var shouldProcessChangedMacrosOnWriteActionFinish: ChangedMacrosScope = ChangedMacrosScope.NONE
 
+        /** Root files with changed macro calls. `null` means that macro calls in all files should be validated */
+        private var changedFiles: MutableSet<VirtualFile>? = hashSetOf()
+
         override fun handleEvent(event: RsPsiTreeChangeEvent) {
             if (!isExpansionModeNew) return
             val file = event.file as? RsFile ?: return
@@ -742,12 +783,14 @@ private class MacroExpansionServiceImplInner(
                 else -> return
             }
 
-            val macroCalls = element.descendantsOfTypeOrSelf<RsMacroCall>()
+            // Macro calls inside function bodies are expanded lazily, so reparse of a function body
+            // (i.e. typing inside it) must not lead to a validation of macro calls of the file
+            val macroCalls = element.descendantsOfTypeOrSelf<RsMacroCall>().filter { it.isTopLevelExpansion }
             if (macroCalls.isNotEmpty()) {
                 val sf = storage.getOrCreateSourceFile(virtualFile) ?: return
                 sf.newMacroCallsAdded(macroCalls)
                 if (!MacroExpansionManager.isExpansionFile(virtualFile)) {
-                    scheduleChangedMacrosUpdate(file.isWorkspaceMember())
+                    scheduleChangedMacrosUpdate(file.isWorkspaceMember(), virtualFile)
                 }
             }
         }
@@ -759,22 +802,47 @@ private class MacroExpansionServiceImplInner(
                     file.virtualFile?.let { MacroExpansionManager.isExpansionFile(it) } == false
             if (shouldScheduleUpdate && file is RsFile) {
                 val isWorkspace = file.isWorkspaceMember()
-                scheduleChangedMacrosUpdate(isWorkspace)
+                val changedFile = file.virtualFile.takeIf { element.isChangeLocalToFile() }
+                scheduleChangedMacrosUpdate(isWorkspace, changedFile)
             }
         }
 
         override fun writeActionFinished(action: Any) {
             when (shouldProcessChangedMacrosOnWriteActionFinish) {
                 ChangedMacrosScope.NONE -> Unit
-                ChangedMacrosScope.WORKSPACE -> processChangedMacros(true)
-                ChangedMacrosScope.ALL -> processChangedMacros(false)
+                ChangedMacrosScope.WORKSPACE -> processChangedMacros(true, changedFiles)
+                ChangedMacrosScope.ALL -> processChangedMacros(false, changedFiles)
             }
             shouldProcessChangedMacrosOnWriteActionFinish = ChangedMacrosScope.NONE
+            changedFiles = hashSetOf()
         }
 
-        private fun scheduleChangedMacrosUpdate(workspaceOnly: Boolean) {
+        /** @param changedFile the only file which macro calls can be affected, or `null` if it's unknown */
+        private fun scheduleChangedMacrosUpdate(workspaceOnly: Boolean, changedFile: VirtualFile?) {
             shouldProcessChangedMacrosOnWriteActionFinish += if (workspaceOnly) ChangedMacrosScope.WORKSPACE else ChangedMacrosScope.ALL
+            if (changedFile == null) {
+                changedFiles = null
+            } else {
+                changedFiles?.add(changedFile)
+            }
+        }
+    }
+
+    /**
+     * Returns `true` if the change can't affect macro calls in other files, i.e. it can't change
+     * macro definitions or macro name resolution. For example, typing inside a struct or
+     * a function signature can only add or remove macro calls in the same file.
+     * Changes inside macro calls are not local because their expansions can define macros
+     */
+    private fun PsiElement.isChangeLocalToFile(): Boolean {
+        if (ancestorOrSelf<RsMacroCall>() != null || ancestorOrSelf<RsMacro>() != null) return false
+        val item = ancestorOrSelf<RsItemElement>() ?: return false
+        if (item !is RsStructItem && item !is RsEnumItem && item !is RsFunction && item !is RsTypeAlias &&
+            item !is RsConstant && item !is RsImplItem && item !is RsTraitItem) {
+            return false
         }
+        // E.g. the whole `impl` with macro calls inside it is added
+        return descendantsOfType<RsMacroCall>().isEmpty() && descendantsOfType<RsMacro>().isEmpty()
     }
 
     private fun RsFile.isWorkspaceMember(): Boolean {
@@ -847,14 +915,27 @@ private class MacroExpansionServiceImplInner(
         submitTask(ProcessUnprocessedMacrosTask())
     }
 
-    private fun processChangedMacros(workspaceOnly: Boolean) {
+    /**
+     * Root files which macro calls should be validated by the next [processChangedMacros] task.
+     * `null` means all files. The files are forgotten only when a task is successfully finished,
+     * so they are not lost if the task is canceled by a newer one. Accessed only from EDT
+     */
+    private var changedFilesToValidate: Set<VirtualFile>? = emptySet()
+
+    private fun processChangedMacros(workspaceOnly: Boolean, changedFiles: Set<VirtualFile>?) {
         MACRO_LOG.info("processChangedMacros")
         checkStorageConsistencyOrClearMacrosDirectoryIfNeeded()
         if (!isExpansionModeNew) return
 
+        val pendingFiles = changedFilesToValidate
+        val filesToValidate = if (pendingFiles != null && changedFiles != null) pendingFiles + changedFiles else null
+        changedFilesToValidate = filesToValidate
+
         // Fixes inplace rename when the renamed element is referenced from a macro call body
         if (isTemplateActiveInAnyEditor()) return
 
+        Testmarks.validationScheduled.hit()
+
         class ProcessModifiedMacrosTask(private val workspaceOnly: Boolean) : MacroExpansionTaskBase(
             project,
             storage,
@@ -864,7 +945,16 @@ private class MacroExpansionServiceImplInner(
             stepModificationTracker
         ) {
             override fun getMacrosToExpand(dumbService: DumbService): Sequence<List<Extractable>> {
-                return runReadAction { storage.makeValidationTask(workspaceOnly) }
+                return runReadAction { storage.makeValidationTask(workspaceOnly, changedFiles = filesToValidate) }
+            }
+
+            override fun onSuccess() {
+                val currentFiles = changedFilesToValidate
+                changedFilesToValidate = when {
+                    currentFiles === filesToValidate -> emptySet()
+                    currentFiles != null && filesToValidate != null -> currentFiles - filesToValidate
+                    else -> currentFiles
+                }
             }
 
             override val taskType: RsTask.TaskType
@@ -903,6 +993,26 @@ private class MacroExpansionServiceImplInner(
         return CachedValueProvider.Result.create(expansion, storage.modificationTracker, call.modificationTracker)
     }
 
+    fun getMacroCallWithReachedDepthLimit(call: RsMacroCall): RsMacroCall? =
+        findMacroCallInExpansionTree(call, DEPTH_LIMIT_REACHED_CALL_KEY) { it.isDepthLimitReached }
+
+    fun getMacroCallWithReachedTokenLimit(call: RsMacroCall): RsMacroCall? =
+        findMacroCallInExpansionTree(call, TOKEN_LIMIT_REACHED_CALL_KEY) { it.isTokenLimitReached }
+
+    /** The expansion tree is walked on each highlighting pass, so the result is cached until the next expansion */
+    private fun findMacroCallInExpansionTree(
+        call: RsMacroCall,
+        key: Key<CachedValue<RsMacroCall?>>,
+        predicate: (ExpandedMacroInfoImpl) -> Boolean
+    ): RsMacroCall? {
+        checkReadAccessAllowed()
+        return CachedValuesManager.getCachedValue(call, key) {
+            val info = storage.getInfoForCall(call)
+            val found = info?.let { storage.findInfoInExpansionTree(it, predicate)?.getMacroCall() }
+            CachedValueProvider.Result.create(found, storage.modificationTracker, call.modificationTracker)
+        }
+    }
+
     fun getExpandedFrom(element: RsExpandedElement): RsMacroCall? {
         checkReadAccessAllowed()
         val parent = element.stubParent
@@ -1045,6 +1155,8 @@ private fun nullExpansionResult(call: RsMacroCall): CachedValueProvider.Result<M
     CachedValueProvider.Result.create(null, call.rustStructureOrAnyPsiModificationTracker, call.modificationTracker)
 
 private val RS_EXPANSION_MACRO_CALL = Key.create<RsMacroCall>("org.rust.lang.core.psi.RS_EXPANSION_MACRO_CALL")
+private val DEPTH_LIMIT_REACHED_CALL_KEY: Key<CachedValue<RsMacroCall?>> = Key.create("DEPTH_LIMIT_REACHED_CALL_KEY")
+private val TOKEN_LIMIT_REACHED_CALL_KEY: Key<CachedValue<RsMacroCall?>> = Key.create("TOKEN_LIMIT_REACHED_CALL_KEY")
 
 private fun RsExpandedElement.setExpandedFrom(call: RsMacroCall) {
     putUserData(RS_EXPANSION_MACRO_CALL, call)
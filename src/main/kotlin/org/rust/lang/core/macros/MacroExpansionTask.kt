@@ -16,6 +16,7 @@ import com.intellij.openapi.progress.util.AbstractProgressIndicatorExBase
 import com.intellij.openapi.project.DumbService
 import com.intellij.openapi.project.Project
 import com.intellij.openapi.util.SimpleModificationTracker
+import com.intellij.openapi.util.TextRange
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.openapi.wm.ex.ProgressIndicatorEx
 import com.intellij.openapiext.isDispatchThread
@@ -24,13 +25,16 @@ import com.intellij.psi.search.GlobalSearchScope
 import com.intellij.util.containers.ContainerUtil
 import com.intellij.util.io.storage.HeavyProcessLatch
 import org.rust.RsTask
+import org.rust.lang.core.macros.MacroExpansionManagerImpl.Testmarks
 import org.rust.lang.core.psi.RsMacroCall
 import org.rust.lang.core.psi.RsMembers
+import org.rust.lang.core.psi.RsPsiFactory
 import org.rust.lang.core.psi.ext.RsMod
 import org.rust.lang.core.psi.ext.bodyHash
+import org.rust.lang.core.psi.ext.bodyTokens
+import org.rust.lang.core.psi.ext.descendantsOfType
 import org.rust.lang.core.psi.ext.macroBody
 import org.rust.lang.core.psi.ext.resolveToMacro
-import org.rust.lang.core.resolve.DEFAULT_RECURSION_LIMIT
 import org.rust.lang.core.resolve.ref.RsMacroPathReferenceImpl
 import org.rust.lang.core.resolve.ref.RsResolveCache
 import org.rust.openapiext.*
@@ -145,7 +149,7 @@ abstract class MacroExpansionTaskBase(
         1 -> 0.3
         2 -> 0.2
         3 -> 0.1
-        else -> 0.4 / (DEFAULT_RECURSION_LIMIT - 3)
+        else -> 0.4 / max(storage.depthLimit - 3, 1)
     }
 
     private fun submitExpansionTask() {
@@ -169,7 +173,7 @@ abstract class MacroExpansionTaskBase(
             return
         }
 
-        realTaskIndicator.text = "Expanding Rust macros. Step " + (currentStep.get() + 1) + "/$DEFAULT_RECURSION_LIMIT"
+        realTaskIndicator.text = "Expanding Rust macros. Step " + (currentStep.get() + 1) + "/${storage.depthLimit}"
         estimateStages.set(0)
         doneStages.set(0)
 
@@ -232,7 +236,10 @@ abstract class MacroExpansionTaskBase(
                 // re-checked
 
                 val batch = vfsBatchFactory()
-                val stages3 = stages2.map { stage2 ->
+                // Expansion files of removed and failed macro calls are released first, so they can be reused
+                // by new expansions with the same content. [stages3] keeps this order, so the old infos are removed
+                // from the storage before the reused files are bound to new infos
+                val stages3 = stages2.sortedBy { !it.releasesExpansionFile }.map { stage2 ->
                     val result = stage2.writeExpansionToFs(batch, currentStep.get())
                     doneStages.incrementAndGet()
                     result
@@ -338,6 +345,9 @@ object Pipeline {
     }
 
     interface Stage2WriteToFs {
+        /** `true` if the stage [releases][MacroExpansionVfsBatch.releaseFile] the old expansion file */
+        val releasesExpansionFile: Boolean get() = false
+
         fun writeExpansionToFs(batch: MacroExpansionVfsBatch, stepNumber: Int): Stage3SaveToStorage
     }
 
@@ -357,10 +367,13 @@ object InvalidationPipeline {
     }
 
     class Stage2(val info: ExpandedMacroInfo) : Pipeline.Stage2WriteToFs {
+        override val releasesExpansionFile: Boolean
+            get() = true
+
         override fun writeExpansionToFs(batch: MacroExpansionVfsBatch, stepNumber: Int): Pipeline.Stage3SaveToStorage {
             val expansionFile = info.expansionFile
             if (expansionFile != null && expansionFile.isValid) {
-                batch.deleteFile(expansionFile)
+                batch.releaseFile(expansionFile, info.expansionFileHash)
             }
             return Stage3(info)
         }
@@ -411,14 +424,26 @@ object ExpansionPipeline {
                 return EmptyPipeline // old expansion is up-to-date
             }
 
-            val expansion = expander.expandMacroAsText(def, call)
-            if (expansion == null) {
-                MACRO_LOG.debug("Failed to expand macro: `${call.path.referenceName}!(${call.macroBody})`")
-                return nextStageFail(callHash, defHash)
+            val expansion = when (val result = expander.expandMacroAsTextWithLimits(def, call)) {
+                is MacroExpansionTextResult.Ok -> result
+                MacroExpansionTextResult.TokenLimitReached ->
+                    return Stage2Fail(info, callHash, defHash, isTokenLimitReached = true)
+                MacroExpansionTextResult.Fail -> {
+                    MACRO_LOG.debug("Failed to expand macro: `${call.path.referenceName}!(${call.macroBody})`")
+                    return nextStageFail(callHash, defHash)
+                }
             }
 
-            val expansionBytes = expansion.first.toString().toByteArray()
-            val ranges = expansion.second
+            val expansionBytes = expansion.text.toString().toByteArray()
+            val ranges = expansion.ranges
+            val callBodyTokens = call.bodyTokens
+
+            // Macro calls in the expansion would be nested too deep, so they won't be expanded
+            val isDepthLimitReached = info.sourceFile.isLastExpansionStep && hasMacroCalls(project, expansion.text)
+            if (isDepthLimitReached) {
+                MacroExpansionMarks.depthLimitReached.hit()
+                MACRO_LOG.info("Macro expansion depth limit reached: `${call.path.referenceName}!(${call.macroBody})`")
+            }
 
             val expansionBytesHash = VfsInternals.calculateContentHash(expansionBytes).getLeading64bits()
 
@@ -431,13 +456,36 @@ object ExpansionPipeline {
                 if (expansionBytes.contentEquals(oldExpansionBytes)) {
                     // Expansion text isn't changed, but [callHash] or [defHash] or [ranges]
                     // are changed and should be updated
-                    return Stage2OkRangesOnly(info, callHash, defHash, oldExpansionFile, ranges, expansionBytesHash)
+                    return Stage2OkRangesOnly(
+                        info,
+                        callHash,
+                        defHash,
+                        oldExpansionFile,
+                        ranges,
+                        callBodyTokens,
+                        expansionBytesHash,
+                        isDepthLimitReached
+                    )
                 }
             }
 
-            return Stage2Ok(info, callHash, defHash, expansionBytes, ranges, expansionBytesHash)
+            return Stage2Ok(
+                info,
+                callHash,
+                defHash,
+                expansionBytes,
+                ranges,
+                callBodyTokens,
+                expansionBytesHash,
+                isDepthLimitReached
+            )
         }
 
+        private fun hasMacroCalls(project: Project, expansionText: CharSequence): Boolean =
+            RsPsiFactory(project, markGenerated = false).createFile(expansionText)
+                .descendantsOfType<RsMacroCall>()
+                .any { it.isTopLevelExpansion }
+
         private fun nextStageFail(callHash: HashCode?, defHash: HashCode?): Pipeline.Stage2WriteToFs =
             Stage2Fail(info, callHash, defHash)
 
@@ -451,7 +499,9 @@ object ExpansionPipeline {
         private val defHash: HashCode?,
         private val expansionBytes: ByteArray,
         private val ranges: RangeMap,
-        private val expansionBytesHash: Long
+        private val callBodyTokens: List<TextRange>,
+        private val expansionBytesHash: Long,
+        private val isDepthLimitReached: Boolean
     ) : Pipeline.Stage2WriteToFs {
         override fun writeExpansionToFs(batch: MacroExpansionVfsBatch, stepNumber: Int): Pipeline.Stage3SaveToStorage {
             val oldExpansionFile = info.expansionFile
@@ -459,9 +509,20 @@ object ExpansionPipeline {
                 if (!oldExpansionFile.isValid) throw CorruptedExpansionStorageException()
                 batch.writeFile(oldExpansionFile, expansionBytes)
             } else {
-                batch.createFileWithContent(expansionBytes, stepNumber)
+                batch.reuseReleasedFile(expansionBytes, expansionBytesHash)
+                    ?.also { Testmarks.expansionFileReused.hit() }
+                    ?: batch.createFileWithContent(expansionBytes, stepNumber)
             }
-            return Stage3(info, callHash, defHash, file, ranges, expansionBytesHash)
+            return Stage3(
+                info,
+                callHash,
+                defHash,
+                file,
+                ranges,
+                callBodyTokens,
+                expansionBytesHash,
+                isDepthLimitReached
+            )
         }
     }
 
@@ -471,25 +532,40 @@ object ExpansionPipeline {
         private val defHash: HashCode?,
         private val oldExpansionFile: VirtualFile,
         private val ranges: RangeMap,
-        private val expansionBytesHash: Long
+        private val callBodyTokens: List<TextRange>,
+        private val expansionBytesHash: Long,
+        private val isDepthLimitReached: Boolean
     ) : Pipeline.Stage2WriteToFs {
         override fun writeExpansionToFs(batch: MacroExpansionVfsBatch, stepNumber: Int): Pipeline.Stage3SaveToStorage {
             val file = batch.resolve(oldExpansionFile)
-            return Stage3(info, callHash, defHash, file, ranges, expansionBytesHash)
+            return Stage3(
+                info,
+                callHash,
+                defHash,
+                file,
+                ranges,
+                callBodyTokens,
+                expansionBytesHash,
+                isDepthLimitReached
+            )
         }
     }
 
     class Stage2Fail(
         private val info: ExpandedMacroInfo,
         private val callHash: HashCode?,
-        private val defHash: HashCode?
+        private val defHash: HashCode?,
+        private val isTokenLimitReached: Boolean = false
     ) : Pipeline.Stage2WriteToFs {
+        override val releasesExpansionFile: Boolean
+            get() = true
+
         override fun writeExpansionToFs(batch: MacroExpansionVfsBatch, stepNumber: Int): Pipeline.Stage3SaveToStorage {
             val oldExpansionFile = info.expansionFile
             if (oldExpansionFile != null && oldExpansionFile.isValid) {
-                batch.deleteFile(oldExpansionFile)
+                batch.releaseFile(oldExpansionFile, info.expansionFileHash)
             }
-            return Stage3(info, callHash, defHash, null, null, 0)
+            return Stage3(info, callHash, defHash, null, null, emptyList(), 0, false, isTokenLimitReached)
         }
     }
 
@@ -499,14 +575,27 @@ object ExpansionPipeline {
         private val defHash: HashCode?,
         private val expansionFile: MacroExpansionVfsBatch.Path?,
         private val ranges: RangeMap?,
-        private val expansionBytesHash: Long
+        private val callBodyTokens: List<TextRange>,
+        private val expansionBytesHash: Long,
+        private val isDepthLimitReached: Boolean,
+        private val isTokenLimitReached: Boolean = false
     ) : Pipeline.Stage3SaveToStorage {
         override fun save(storage: ExpandedMacroStorage) {
             checkWriteAccessAllowed()
             val virtualFile = expansionFile?.toVirtualFile()
                 // Optimization: skip charset guessing in `VirtualFileImpl.contentToByteArray()`
                 ?.also { it.setCharset(Charsets.UTF_8, null, false) }
-            storage.addExpandedMacro(info, callHash, defHash, virtualFile, ranges, expansionBytesHash)
+            storage.addExpandedMacro(
+                info,
+                callHash,
+                defHash,
+                virtualFile,
+                ranges,
+                callBodyTokens,
+                expansionBytesHash,
+                isDepthLimitReached,
+                isTokenLimitReached
+            )
             // If a document exists for expansion file (e.g. when AST tree is loaded), the changes in
             // a virtual file will not be committed to the PSI immediately. We have to commit it manually
             // to see the changes (or somehow wait for DocumentCommitThread, but it isn't possible for now)
@@ -9,7 +9,9 @@ import com.intellij.openapi.util.text.StringUtil
 import com.intellij.openapi.vfs.VfsUtil
 import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.openapi.vfs.newvfs.RefreshQueue
+import com.intellij.util.SmartList
 import org.rust.stdext.randomLowercaseAlphabetic
+import java.io.IOException
 
 interface MacroExpansionVfsBatch {
     interface Path {
@@ -22,12 +24,22 @@ interface MacroExpansionVfsBatch {
     fun deleteFile(file: VirtualFile)
     fun writeFile(file: VirtualFile, content: ByteArray): Path
 
+    /**
+     * Marks the expansion [file] with the [contentHash] as no longer used. The file is deleted in [applyToVfs]
+     * unless it is [reused][reuseReleasedFile] by another expansion with the same content in this batch
+     */
+    fun releaseFile(file: VirtualFile, contentHash: Long)
+
+    /** Returns a [released][releaseFile] file with the same [content] if any, so it is not re-created and re-indexed */
+    fun reuseReleasedFile(content: ByteArray, contentHash: Long): Path?
+
     fun applyToVfs(async: Boolean, callback: Runnable?)
 }
 
 class MacroExpansionVfsBatchImpl(rootDirName: String) : MacroExpansionVfsBatch {
     private val contentRoot = "/$MACRO_EXPANSION_VFS_ROOT/$rootDirName"
     private val batch: VfsBatch = VfsBatch()
+    private val releasedFiles: MutableMap<Long, MutableList<VirtualFile>> = hashMapOf()
 
     override fun resolve(file: VirtualFile): MacroExpansionVfsBatch.Path =
         PathImpl.VFile(file)
@@ -50,7 +62,28 @@ class MacroExpansionVfsBatchImpl(rootDirName: String) : MacroExpansionVfsBatch {
         return resolve(file)
     }
 
+    override fun releaseFile(file: VirtualFile, contentHash: Long) {
+        releasedFiles.getOrPut(contentHash) { SmartList() }.add(file)
+    }
+
+    override fun reuseReleasedFile(content: ByteArray, contentHash: Long): MacroExpansionVfsBatch.Path? {
+        val files = releasedFiles[contentHash] ?: return null
+        val index = files.indexOfFirst { it.isValid && it.hasContent(content) }
+        if (index == -1) return null
+        return resolve(files.removeAt(index))
+    }
+
+    private fun VirtualFile.hasContent(content: ByteArray): Boolean = try {
+        contentsToByteArray().contentEquals(content)
+    } catch (e: IOException) {
+        false
+    }
+
     override fun applyToVfs(async: Boolean, callback: Runnable?) {
+        for (file in releasedFiles.values.flatten()) {
+            batch.deleteFile(file)
+        }
+        releasedFiles.clear()
         batch.applyToVfs(async, callback)
     }
 
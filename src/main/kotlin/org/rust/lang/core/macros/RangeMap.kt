@@ -53,6 +53,44 @@ data class RangeMap private constructor(private val ranges: List<MappedTextRange
         return RangeMap(other.ranges.flatMap(::mapMappedTextRangeFromExpansionToCallBody))
     }
 
+    /**
+     * Maps the call body offsets of this map from the body with [oldTokens] to the body with the same
+     * tokens placed at [newTokens] (e.g. after a whitespace edit). Offsets inside tokens are shifted along with
+     * the tokens; offsets between tokens are kept only if the text between the tokens has the same length
+     */
+    fun rebase(oldTokens: List<TextRange>, newTokens: List<TextRange>): RangeMap {
+        require(oldTokens.size == newTokens.size) { "The token lists must be of the same size" }
+        if (oldTokens == newTokens) return this
+
+        val result = SmartList<MappedTextRange>()
+        for (range in ranges) {
+            var offset = range.srcOffset
+            var index = oldTokens.indexOfFirstEndingAfter(offset)
+            while (offset < range.srcEndOffset && index < oldTokens.size) {
+                val token = oldTokens[index]
+                val pieceEnd: Int
+                val newOffset: Int?
+                if (offset >= token.startOffset) {
+                    pieceEnd = minOf(token.endOffset, range.srcEndOffset)
+                    newOffset = newTokens[index].startOffset + (offset - token.startOffset)
+                    index++
+                } else {
+                    val gapStart = oldTokens.getOrNull(index - 1)?.endOffset ?: 0
+                    val newGapStart = newTokens.getOrNull(index - 1)?.endOffset ?: 0
+                    val isSameGap = token.startOffset - gapStart == newTokens[index].startOffset - newGapStart
+                    pieceEnd = minOf(token.startOffset, range.srcEndOffset)
+                    newOffset = if (isSameGap) newGapStart + (offset - gapStart) else null
+                }
+                if (newOffset != null) {
+                    val dstOffset = range.dstOffset + (offset - range.srcOffset)
+                    result.mergeAdd(MappedTextRange(newOffset, dstOffset, pieceEnd - offset))
+                }
+                offset = pieceEnd
+            }
+        }
+        return from(result)
+    }
+
     fun writeTo(data: DataOutputStream) {
         data.writeInt(ranges.size)
         ranges.forEach {
@@ -75,6 +113,35 @@ data class RangeMap private constructor(private val ranges: List<MappedTextRange
     }
 }
 
+/** The index of the first token that ends after the [offset], or `size` if there is no such token */
+private fun List<TextRange>.indexOfFirstEndingAfter(offset: Int): Int {
+    var low = 0
+    var high = size
+    while (low < high) {
+        val mid = (low + high) ushr 1
+        if (this[mid].endOffset > offset) high = mid else low = mid + 1
+    }
+    return low
+}
+
+/** Adds the [range] or extends the last range if they are adjacent both in the call body and in the expansion */
+fun MutableList<MappedTextRange>.mergeAdd(range: MappedTextRange) {
+    val last = lastOrNull() ?: run {
+        add(range)
+        return
+    }
+
+    if (last.srcEndOffset == range.srcOffset && last.dstEndOffset == range.dstOffset) {
+        set(size - 1, MappedTextRange(
+            last.srcOffset,
+            last.dstOffset,
+            last.length + range.length
+        ))
+    } else {
+        add(range)
+    }
+}
+
 private fun DataInputStream.readMappedTextRange(): MappedTextRange = MappedTextRange(
     readVarInt(),
     readVarInt(),
@@ -167,7 +167,7 @@ private fun mapOffsetFromExpansionToCallBody(call: RsMacroCall, offset: Int): In
 private fun mapOffsetFromExpansionToCallBodyRelative(call: RsMacroCall, offset: Int): Int? {
     val expansion = call.expansion ?: return null
     val fileOffset = call.expansionContext.expansionFileStartOffset
-    return expansion.ranges.mapOffsetFromExpansionToCallBody(offset - fileOffset)
+    return expansion.rangesFor(call).mapOffsetFromExpansionToCallBody(offset - fileOffset)
 }
 
 fun PsiElement.cameFromMacroCall(): Boolean {
@@ -243,7 +243,7 @@ private fun mapOffsetFromCallBodyToExpansion(
 ): List<Int>? {
     val relOffsetInCallBody = absOffsetInCallBody.toBodyRelativeOffset(call) ?: return null
     val fileOffset = call.expansionContext.expansionFileStartOffset
-    return expansion.ranges.mapOffsetFromCallBodyToExpansion(relOffsetInCallBody)
+    return expansion.rangesFor(call).mapOffsetFromCallBodyToExpansion(relOffsetInCallBody)
         .map { it + fileOffset }
 }
 
@@ -296,7 +296,8 @@ private fun mapRangeFromExpansionToCallBody(
 ): List<MappedTextRange> {
     val fileOffset = call.expansionContext.expansionFileStartOffset
     if (range.srcOffset - fileOffset < 0) return emptyList()
-    val mappedRanges = expansion.ranges.mapMappedTextRangeFromExpansionToCallBody(range.srcShiftLeft(fileOffset))
+    val mappedRanges = expansion.rangesFor(call)
+        .mapMappedTextRangeFromExpansionToCallBody(range.srcShiftLeft(fileOffset))
         .mapNotNull { it.fromBodyRelativeRange(call) }
     val parentCall = call.findMacroCallExpandedFromNonRecursive() ?: return mappedRanges
     return mappedRanges.flatMap {
@@ -105,6 +105,7 @@ object RustParserUtil : GeneratedParserUtilBase() {
     private val MACRO_BRACE_PARENS: Int = makeBitMask(7)
     private val MACRO_BRACE_BRACKS: Int = makeBitMask(8)
     private val MACRO_BRACE_BRACES: Int = makeBitMask(9)
+    private val LET_EXPR_ALLOWED: Int = makeBitMask(10)
     private fun setPathMod(flags: Int, mode: PathParsingMode): Int {
         val flag = when (mode) {
             PathParsingMode.VALUE -> PATH_VALUE
@@ -191,12 +192,33 @@ object RustParserUtil : GeneratedParserUtilBase() {
         val newFlags = oldFlags
             .setFlag(STRUCT_ALLOWED, structLiterals == StructLiteralsMode.ON)
             .setFlag(STMT_EXPR_MODE, stmtMode == StmtMode.ON)
+            .setFlag(LET_EXPR_ALLOWED, false)
         b.flags = newFlags
         val result = parser.parse(b, level)
         b.flags = oldFlags
         return result
     }
 
+    /**
+     * Parses `if`/`while` condition: struct literals are not allowed, but `let` expressions
+     * (`if let Some(x) = foo() && x > 0 {}`) are
+     */
+    @JvmStatic
+    fun conditionExprMode(b: PsiBuilder, level: Int, parser: Parser): Boolean {
+        val oldFlags = b.flags
+        val newFlags = oldFlags
+            .setFlag(STRUCT_ALLOWED, false)
+            .setFlag(STMT_EXPR_MODE, false)
+            .setFlag(LET_EXPR_ALLOWED, true)
+        b.flags = newFlags
+        val result = parser.parse(b, level)
+        b.flags = oldFlags
+        return result
+    }
+
+    @JvmStatic
+    fun checkLetExprAllowed(b: PsiBuilder, level: Int): Boolean = BitUtil.isSet(b.flags, LET_EXPR_ALLOWED)
+
     @JvmStatic
     fun isCompleteBlockExpr(b: PsiBuilder, level: Int): Boolean =
         isBlock(b, level) && BitUtil.isSet(b.flags, STMT_EXPR_MODE)
@@ -389,6 +411,8 @@ object RustParserUtil : GeneratedParserUtilBase() {
         put(RustParser::IncludeMacroArgument, false, "include")
         put(RustParser::ConcatMacroArgument, true, "concat")
         put(RustParser::EnvMacroArgument, true, "env")
+        put(RustParser::AsmMacroArgument, true, "asm")
+        put(RustParser::AsmMacroArgument, false, "global_asm")
     }
 
     fun isSpecialMacro(name: String): Boolean =
@@ -5,12 +5,14 @@
 
 package org.rust.lang.core.psi
 
+import com.intellij.application.options.CodeStyle
 import com.intellij.openapi.project.Project
 import com.intellij.psi.PsiComment
 import com.intellij.psi.PsiElement
 import com.intellij.psi.PsiFileFactory
 import com.intellij.psi.PsiParserFacade
 import com.intellij.util.LocalTimeCounter
+import org.rust.ide.formatter.rust
 import org.rust.ide.inspections.checkMatch.Pattern
 import org.rust.ide.presentation.renderInsertionSafe
 import org.rust.lang.RsFileType
@@ -34,6 +36,10 @@ class RsPsiFactory(
     private val markGenerated: Boolean = true,
     private val eventSystemEnabled: Boolean = false
 ) {
+    /** A trailing comma of a multiline comma-separated list, if it is enabled in the code style settings */
+    val multilineTrailingComma: String
+        get() = if (CodeStyle.getSettings(project).rust.ADD_TRAILING_COMMA_IN_MULTILINE) "," else ""
+
     fun createFile(text: CharSequence): RsFile =
         PsiFileFactory.getInstance(project)
             .createFileFromText(
@@ -181,6 +187,14 @@ class RsPsiFactory(
         createFromText(text)
             ?: error("Failed to create struct from text: `$text`")
 
+    fun createTraitItem(text: String): RsTraitItem =
+        createFromText(text)
+            ?: error("Failed to create trait from text: `$text`")
+
+    fun createImplItem(text: String): RsImplItem =
+        createFromText(text)
+            ?: error("Failed to create impl from text: `$text`")
+
     fun createStatement(text: String): RsStmt =
         createFromText("fn main() { $text 92; }")
             ?: error("Failed to create statement from text: `$text`")
@@ -228,6 +242,9 @@ class RsPsiFactory(
         return createFromText(text) ?: error("Failed to create mod item with name: `$modName` from text: `$modText`")
     }
 
+    fun createModItem(text: String): RsModItem =
+        createFromText(text) ?: error("Failed to create mod item from text: `$text`")
+
     fun createTraitMethodMember(text: String): RsFunction {
         return createFromText<RsFunction>("trait Foo { $text }")
             ?: error("Failed to create a method member from text: `$text`")
@@ -291,6 +308,14 @@ class RsPsiFactory(
             ?: error("Failed to create type parameters from text: `<$params`>")
     }
 
+    fun createTypeParamBounds(bounds: String): RsTypeParamBounds =
+        createFromText<RsTypeParameter>("fn foo<T: $bounds>() {}")?.typeParamBounds
+            ?: error("Failed to create type bounds from text: `$bounds`")
+
+    fun createWherePred(text: String): RsWherePred =
+        createFromText("fn foo() where $text {}")
+            ?: error("Failed to create where predicate from text: `$text`")
+
     fun createTypeArgumentList(
         params: Iterable<String>
     ): RsTypeArgumentList {
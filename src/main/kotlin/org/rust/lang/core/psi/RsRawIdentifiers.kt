@@ -6,6 +6,7 @@
 package org.rust.lang.core.psi
 
 import com.intellij.psi.PsiElement
+import org.rust.cargo.project.workspace.CargoWorkspace
 import org.rust.ide.refactoring.isValidRustVariableIdentifier
 import org.rust.lang.core.psi.RsElementTypes.IDENTIFIER
 import org.rust.lang.core.psi.ext.elementType
@@ -13,9 +14,26 @@ import org.rust.lang.core.psi.ext.elementType
 const val RS_RAW_PREFIX = "r#"
 val CAN_NOT_BE_ESCAPED = listOf("self", "super", "crate", "Self")
 
+/** Keywords of Edition 2018 that are lexed as identifiers (because they aren't keywords in Edition 2015) */
+private val EDITION_2018_RESERVED_NAMES = setOf("async", "await", "dyn", "try")
+
 fun String.unescapeIdentifier(): String = removePrefix(RS_RAW_PREFIX)
-fun String.escapeIdentifierIfNeeded(): String =
-    if (isValidRustVariableIdentifier(this) || this in CAN_NOT_BE_ESCAPED) this else "$RS_RAW_PREFIX$this"
+
+/**
+ * Adds `r#` prefix if the identifier is a keyword. If [edition] is `null` (e.g. the element doesn't
+ * belong to any crate), Edition 2018 keywords are not escaped
+ */
+fun String.escapeIdentifierIfNeeded(edition: CargoWorkspace.Edition? = null): String {
+    val isKeyword = !isValidRustVariableIdentifier(this) ||
+        edition == CargoWorkspace.Edition.EDITION_2018 && this in EDITION_2018_RESERVED_NAMES
+    return if (isKeyword && this !in CAN_NOT_BE_ESCAPED) "$RS_RAW_PREFIX$this" else this
+}
+
+/** `true` if the identifier can only be written as a raw identifier (`r#match`) in the [edition] */
+fun String.requiresRawIdentifier(edition: CargoWorkspace.Edition?): Boolean {
+    val escaped = escapeIdentifierIfNeeded(edition)
+    return escaped != this && isValidRustVariableIdentifier(escaped)
+}
 
 val PsiElement.unescapedText: String get() {
     val text = text ?: return ""
@@ -0,0 +1,81 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.psi.ext
+
+import com.intellij.psi.PsiComment
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiWhiteSpace
+import org.rust.lang.core.psi.RsAsmMacroArgument
+import org.rust.lang.core.psi.RsAsmOperand
+import org.rust.lang.core.psi.RsElementTypes.*
+import org.rust.lang.core.psi.RsExpr
+
+enum class RsAsmOperandKind(val keyword: String) {
+    IN("in"),
+    OUT("out"),
+    LATEOUT("lateout"),
+    INOUT("inout"),
+    INLATEOUT("inlateout"),
+    SYM("sym"),
+    CONST("const")
+}
+
+/** `x` in `x = in(reg) a` */
+val RsAsmOperand.nameIdentifier: PsiElement?
+    get() {
+        val first = firstChild ?: return null
+        return if (first.elementType == IDENTIFIER && first.getNextNonCommentSibling()?.elementType == EQ) first else null
+    }
+
+val RsAsmOperand.operandName: String? get() = nameIdentifier?.text
+
+val RsAsmOperand.kind: RsAsmOperandKind?
+    get() {
+        val tokens = childrenWithLeaves.filter { it !is PsiWhiteSpace && it !is PsiComment }
+        val keyword = tokens.drop(if (nameIdentifier != null) 2 else 0).firstOrNull() ?: return null
+        return RsAsmOperandKind.values().find { it.keyword == keyword.text }
+    }
+
+/** Operands with explicit registers like `in("eax") x` can't be referenced from the template string */
+val RsAsmOperand.isExplicitRegister: Boolean
+    get() = node.findChildByType(STRING_LITERAL) != null
+
+/** The expression read by the operand, e.g. `x` in `in(reg) x`, `inout(reg) x => y` or `sym x` */
+val RsAsmOperand.inputExpr: RsExpr?
+    get() = when (kind) {
+        RsAsmOperandKind.IN, RsAsmOperandKind.SYM, RsAsmOperandKind.CONST,
+        RsAsmOperandKind.INOUT, RsAsmOperandKind.INLATEOUT -> exprsAroundArrow.first
+        else -> null
+    }
+
+/**
+ * The place written by the operand, e.g. `x` in `out(reg) x` or `y` in `inout(reg) x => y`.
+ * `null` if the output is discarded with `_`
+ */
+val RsAsmOperand.outputExpr: RsExpr?
+    get() = when (kind) {
+        RsAsmOperandKind.OUT, RsAsmOperandKind.LATEOUT -> exprList.firstOrNull()
+        RsAsmOperandKind.INOUT, RsAsmOperandKind.INLATEOUT -> {
+            val (input, output) = exprsAroundArrow
+            if (node.findChildByType(FAT_ARROW) == null) input else output
+        }
+        else -> null
+    }
+
+private val RsAsmOperand.exprsAroundArrow: Pair<RsExpr?, RsExpr?>
+    get() {
+        val arrow = node.findChildByType(FAT_ARROW)?.psi ?: return exprList.firstOrNull() to null
+        val arrowOffset = arrow.startOffsetInParent
+        return exprList.find { it.startOffsetInParent < arrowOffset } to exprList.find { it.startOffsetInParent > arrowOffset }
+    }
+
+/** Options like `nostack` in `options(nostack, nomem)` */
+val RsAsmMacroArgument.options: List<String>
+    get() = asmOptionsList.flatMap { it.asmOptionList }.map { it.identifier.text }
+
+/** All expressions of the macro call (template strings and operands) in the order of evaluation */
+val RsAsmMacroArgument.allExprs: List<RsExpr>
+    get() = exprList + asmOperandList.flatMap { it.exprList }
@@ -5,7 +5,35 @@
 
 package org.rust.lang.core.psi.ext
 
+import org.rust.lang.core.psi.RsBinaryExpr
 import org.rust.lang.core.psi.RsCondition
+import org.rust.lang.core.psi.RsExpr
+import org.rust.lang.core.psi.RsLetExpr
 import org.rust.lang.core.psi.RsPat
 
-val RsCondition.patList: List<RsPat> get() = orPats?.patList.orEmpty()
+/**
+ * `let` expressions of the condition that bind variables visible in the `if`/`while` body,
+ * e.g. `let Some(a) = x` and `let Some(b) = a` in `if let Some(a) = x && let Some(b) = a && b > 0 {}`
+ */
+val RsCondition.letExprs: List<RsLetExpr>
+    get() = expr.letChainOperands.filterIsInstance<RsLetExpr>()
+
+/**
+ * The only `let` expression of the condition if it is not a chain, i.e. `let Some(x) = foo()` in
+ * `if let Some(x) = foo() {}`
+ */
+val RsCondition.singleLetExpr: RsLetExpr? get() = expr as? RsLetExpr
+
+val RsCondition.patList: List<RsPat> get() = letExprs.flatMap { it.patList }
+
+val RsLetExpr.patList: List<RsPat> get() = orPats?.patList.orEmpty()
+
+/**
+ * Operands of `&&` chain: `a && let Some(b) = c && d` -> `[a, let Some(b) = c, d]`.
+ * Returns the expression itself if it is not `&&` expression
+ */
+val RsExpr.letChainOperands: List<RsExpr>
+    get() {
+        if (this !is RsBinaryExpr || operatorType != LogicOp.AND) return listOf(this)
+        return left.letChainOperands + right?.letChainOperands.orEmpty()
+    }
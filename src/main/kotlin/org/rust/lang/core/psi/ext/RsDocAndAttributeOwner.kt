@@ -166,6 +166,30 @@ class QueryAttributes(
     val deprecatedAttribute: RsMetaItem?
         get() = (attrsByName("deprecated") + attrsByName("rustc_deprecated")).firstOrNull()
 
+    // #[deprecated(since = "1.0.0", note = "...")], #[deprecated = "..."], #[rustc_deprecated(since, reason)]
+    val deprecation: Deprecation?
+        get() {
+            val attr = deprecatedAttribute ?: return null
+            val params = attr.metaItemArgs?.metaItemList.orEmpty()
+            fun param(name: String): String? = params.find { it.name == name }?.value
+            val note = if (attr.name == "deprecated") param("note") ?: attr.value else param("reason")
+            return Deprecation(param("since"), note)
+        }
+
+    // #[doc(alias = "foo")], #[doc(alias("foo", "bar"))]
+    val docAliases: Sequence<String>
+        get() = attrsByName("doc")
+            .flatMap { it.metaItemArgs?.metaItemList.orEmpty().asSequence() }
+            .filter { it.name == "alias" }
+            .flatMap { alias ->
+                val value = alias.value
+                if (value != null) {
+                    sequenceOf(value)
+                } else {
+                    alias.metaItemArgs?.litExprList.orEmpty().asSequence().mapNotNull { it.stringValue }
+                }
+            }
+
     val cfgAttributes: Sequence<RsMetaItem>
         get() = attrsByName("cfg")
 
@@ -231,3 +255,6 @@ private fun RsDocAndAttributeOwner.evaluateCfg(): ThreeValuedLogic {
 }
 
 private val CFG_ATTRIBUTES_ENABLED_KEY = Registry.get("org.rust.lang.cfg.attributes")
+
+/** Arguments of `#[deprecated]` attribute */
+data class Deprecation(val since: String?, val note: String?)
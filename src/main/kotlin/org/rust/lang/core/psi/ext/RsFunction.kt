@@ -151,6 +151,20 @@ val RsFunction.isAttributeProcMacroDef: Boolean
 val RsFunction.isCustomDeriveProcMacroDef: Boolean
     get() = queryAttributes.hasAttribute("proc_macro_derive")
 
+/** `Name` in `#[proc_macro_derive(Name, attributes(helper1, helper2))]` */
+val RsFunction.procMacroDeriveName: String?
+    get() = queryAttributes.getFirstArgOfSingularAttribute("proc_macro_derive")
+
+/** `helper1` and `helper2` in `#[proc_macro_derive(Name, attributes(helper1, helper2))]` */
+val RsFunction.procMacroDeriveHelperAttributes: List<String>
+    get() = queryAttributes.metaItems
+        .filter { it.name == "proc_macro_derive" }
+        .flatMap { it.metaItemArgs?.metaItemList.orEmpty().asSequence() }
+        .filter { it.name == "attributes" }
+        .flatMap { it.metaItemArgs?.metaItemList.orEmpty().asSequence() }
+        .mapNotNull { it.name }
+        .toList()
+
 val RsFunction.isProcMacroDef: Boolean
     get() = greenStub?.isProcMacroDef ?: (queryAttributes.isProcMacroDef)
 
@@ -27,6 +27,14 @@ val RsImplItem.default: PsiElement?
 val RsImplItem.isReservationImpl: Boolean
     get() = queryAttributes.hasAttribute("rustc_reservation_impl")
 
+/** `impl<T: Foo> Bar for T {}` */
+val RsImplItem.isBlanketImpl: Boolean
+    get() {
+        val path = (typeReference?.skipParens() as? RsBaseType)?.path ?: return false
+        if (path.path != null || path.typeArgumentList != null) return false
+        return typeParameters.any { it.name == path.referenceName }
+    }
+
 abstract class RsImplItemImplMixin : RsStubbedElementImpl<RsImplItemStub>, RsImplItem {
 
     constructor(node: ASTNode) : super(node)
@@ -43,6 +43,21 @@ val RsItemsOwner.itemsAndMacros: Sequence<RsElement>
         }.filterIsInstance<RsElement>()
     }
 
+/**
+ * `true` if there is an item-level `include!` macro call whose file can't be found.
+ * In this case we can't know all the items of the owner.
+ * `include!` calls disabled by `#[cfg]` are ignored since they don't contribute any items
+ */
+val RsItemsOwner.hasUnresolvedIncludeMacro: Boolean
+    get() = CachedValuesManager.getCachedValue(this, HAS_UNRESOLVED_INCLUDE_MACRO_KEY) {
+        val result = itemsAndMacros.any {
+            it is RsMacroCall && it.macroName == "include" && it.isEnabledByCfg && it.findIncludingFile() == null
+        }
+        CachedValueProvider.Result.create(result, rustStructureOrAnyPsiModificationTracker)
+    }
+
+private val HAS_UNRESOLVED_INCLUDE_MACRO_KEY: Key<CachedValue<Boolean>> = Key.create("HAS_UNRESOLVED_INCLUDE_MACRO_KEY")
+
 inline fun RsItemsOwner.processExpandedItemsExceptImplsAndUses(processor: (RsItemElement) -> Boolean): Boolean {
     for (element in expandedItemsExceptImplsAndUses) {
         if (processor(element)) return true
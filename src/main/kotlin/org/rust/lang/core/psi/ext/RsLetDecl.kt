@@ -0,0 +1,12 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.psi.ext
+
+import org.rust.lang.core.psi.RsBlock
+import org.rust.lang.core.psi.RsLetDecl
+
+/** The diverging `else` block of `let Some(x) = foo() else { return; };` */
+val RsLetDecl.elseBlock: RsBlock? get() = letElseBranch?.block
@@ -0,0 +1,42 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.psi.ext
+
+import com.intellij.lang.ASTNode
+import com.intellij.psi.LiteralTextEscaper
+import com.intellij.psi.PsiLanguageInjectionHost
+import org.rust.ide.injected.RsLanguageInjectionSettings
+import org.rust.ide.injected.escaperForMacroArgument
+import org.rust.lang.core.psi.RsMacroArgument
+import org.rust.lang.core.psi.RsMacroCall
+import org.rust.lang.core.psi.RsPsiFactory
+
+/**
+ * String literals inside token trees of macro calls like `sqlx::query!("SELECT ...")` are not wrapped
+ * into [org.rust.lang.core.psi.RsLitExpr], so the whole macro argument is used as an injection host
+ * and the ranges of the literals are injected (see [org.rust.ide.injected.RsLanguageInjector]).
+ *
+ * Only arguments of macros named in [RsLanguageInjectionSettings] are hosts. The check is called for every
+ * macro call, so it must not traverse the token tree
+ */
+abstract class RsMacroArgumentMixin(node: ASTNode) : RsElementImpl(node), RsMacroArgument {
+
+    override fun isValidHost(): Boolean {
+        val macroCall = parent as? RsMacroCall ?: return false
+        val macroNames = RsLanguageInjectionSettings.getInstance(project).macroNames
+        return macroCall.macroName in macroNames && textContains('"')
+    }
+
+    override fun updateText(text: String): PsiLanguageInjectionHost {
+        val newArgument = RsPsiFactory(project).createFile("m!$text;")
+            .descendantOfTypeStrict<RsMacroArgument>()
+            ?: error("Failed to create macro argument from `$text`")
+        return replace(newArgument) as RsMacroArgument
+    }
+
+    override fun createLiteralTextEscaper(): LiteralTextEscaper<RsMacroArgument> =
+        escaperForMacroArgument(this)
+}
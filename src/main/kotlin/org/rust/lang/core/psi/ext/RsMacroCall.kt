@@ -9,11 +9,14 @@ import com.intellij.codeInsight.completion.CompletionUtil
 import com.intellij.lang.ASTNode
 import com.intellij.openapi.util.SimpleModificationTracker
 import com.intellij.openapi.util.TextRange
+import com.intellij.openapi.vfs.VirtualFile
 import com.intellij.psi.PsiElement
+import com.intellij.psi.TokenType
 import com.intellij.psi.stubs.IStubElementType
 import com.intellij.psi.tree.TokenSet
 import com.intellij.psi.util.CachedValueProvider
 import com.intellij.psi.util.CachedValuesManager
+import org.rust.lang.core.lexer.RsLexer
 import org.rust.lang.core.macros.*
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.RsElementTypes.*
@@ -52,7 +55,8 @@ val RsMacroCall.macroBody: String?
         val stub = greenStub
         if (stub != null) return stub.macroBody
         // Note: `node` is usually an instance of `LazyParseableElement` where `chars` is cached
-        return macroArgumentElement?.node?.chars?.let { it.subSequence(1, it.length - if (it.length == 1) 0 else 1) }?.toString()
+        val chars = macroArgumentElement?.node?.chars ?: return null
+        return chars.macroBodyRange().subSequence(chars).toString()
     }
 
 val RsMacroCall.bodyTextRange: TextRange?
@@ -67,19 +71,52 @@ val RsMacroCall.bodyTextRange: TextRange?
                 null
             }
         } else {
-            macroArgumentElement?.textRange?.let { TextRange(it.startOffset + 1, it.endOffset - if (it.length == 1) 0 else 1) }
+            val argument = macroArgumentElement ?: return null
+            argument.node.chars.macroBodyRange().shiftRight(argument.startOffset)
         }
     }
 
+/**
+ * Returns the range of a macro call body inside the macro argument text, i.e. the text
+ * without the delimiters. All of `foo!()`, `foo![]` and `foo!{}` should be expanded in the same way,
+ * so the delimiters must never be a part of [macroBody] (and hence of [bodyHash]).
+ * Note that the closing delimiter can be missing in incomplete code like `foo!(bar`
+ */
+private fun CharSequence.macroBodyRange(): TextRange {
+    if (isEmpty()) return TextRange.EMPTY_RANGE
+    val braces = MacroBraces.values().find { it.openText[0] == this[0] }
+    val hasCloseBrace = length > 1 && braces != null && this[length - 1] == braces.closeText[0]
+    return TextRange(1, if (hasCloseBrace) length - 1 else length)
+}
+
 private val MACRO_ARGUMENT_TYPES: TokenSet = tokenSetOf(
     MACRO_ARGUMENT, FORMAT_MACRO_ARGUMENT, LOG_MACRO_ARGUMENT,
     ASSERT_MACRO_ARGUMENT, EXPR_MACRO_ARGUMENT, VEC_MACRO_ARGUMENT,
-    CONCAT_MACRO_ARGUMENT, ENV_MACRO_ARGUMENT
+    CONCAT_MACRO_ARGUMENT, ENV_MACRO_ARGUMENT, ASM_MACRO_ARGUMENT
 )
 
 val RsMacroCall.macroArgumentElement: RsElement?
     get() = node.findChildByType(MACRO_ARGUMENT_TYPES)?.psi as? RsElement
 
+/**
+ * The index of the format string in [RsFormatMacroArgument.getFormatMacroArgList],
+ * e.g. `0` for `println!("{}", 1)` and `1` for `write!(f, "{}", 1)`.
+ * Format macros of `log` and `tracing` are not handled yet
+ */
+val RsMacroCall.formatStringArgumentIndex: Int?
+    get() = when (macroName) {
+        "println",
+        "print",
+        "eprintln",
+        "eprint",
+        "format",
+        "format_args",
+        "format_args_nl" -> 0
+        "write",
+        "writeln" -> 1
+        else -> null
+    }
+
 private val RsExpr.value: String? get() {
     return when (this) {
         is RsLitExpr -> stringValue
@@ -100,6 +137,7 @@ private val RsExpr.value: String? get() {
                     val pkg = expr.containingCargoPackage ?: return null
                     when (val variableName = expr.value) {
                         "OUT_DIR" -> pkg.outDir?.path
+                        "CARGO_MANIFEST_DIR" -> pkg.contentRoot?.path
                         else -> pkg.env[variableName]
                     }
                 }
@@ -110,20 +148,82 @@ private val RsExpr.value: String? get() {
     }
 }
 
+/**
+ * The evaluated path argument of `include!`, `include_str!` or `include_bytes!` macro call.
+ * `concat!` and `env!` with known environment variables are supported
+ */
+val RsMacroCall.includedFilePath: String?
+    get() = includeMacroArgument?.expr?.value
+
+/**
+ * Finds the file included by `include!`, `include_str!` or `include_bytes!` macro call.
+ * Like in rustc, relative paths are resolved relative to the file containing the macro call
+ */
+fun RsMacroCall.findIncludedFile(): VirtualFile? {
+    val path = includedFilePath ?: return null
+    return includeBaseDirectory?.findFileByMaybeRelativePath(path)
+}
+
+/** The directory relative paths of `include!`-like macro calls are resolved against */
+val RsMacroCall.includeBaseDirectory: VirtualFile?
+    get() = (findMacroCallExpandedFrom() ?: this).containingFile?.originalFile?.virtualFile?.parent
+
 fun RsMacroCall.findIncludingFile(): RsFile? {
     if (macroName != "include") return null
-    val path = includeMacroArgument?.expr?.value ?: return null
-    val file = (findMacroCallExpandedFrom() ?: this).containingFile?.originalFile?.virtualFile ?: return null
-    return file.parent?.findFileByMaybeRelativePath(path)?.toPsiFile(project)?.rustFile
+    return findIncludedFile()?.toPsiFile(project)?.rustFile
 }
 
+/**
+ * Text ranges of [macroBody] tokens (relative to the body start) except whitespaces and regular comments.
+ * Doc comments are kept because they are lowered to `#[doc]` attributes before the expansion
+ */
+val RsMacroCall.bodyTokens: List<TextRange>
+    get() = CachedValuesManager.getCachedValue(this) {
+        val tokens = macroBody?.let { lexSignificantTokens(it) }.orEmpty()
+        CachedValueProvider.Result.create(tokens, modificationTracker)
+    }
+
+private fun lexSignificantTokens(text: CharSequence): List<TextRange> {
+    val lexer = RsLexer()
+    lexer.start(text)
+    val tokens = mutableListOf<TextRange>()
+    while (lexer.tokenType != null) {
+        val tokenType = lexer.tokenType
+        if (tokenType != TokenType.WHITE_SPACE && tokenType !in RS_REGULAR_COMMENTS) {
+            tokens += TextRange(lexer.tokenStart, lexer.tokenEnd)
+        }
+        lexer.advance()
+    }
+    return tokens
+}
+
+/**
+ * A hash of the flattened [bodyTokens], so whitespace and comment edits inside the body don't change it.
+ * Adjacent punctuation tokens are marked as joint because `<<` and `< <` are different for the parser
+ */
 val RsMacroCall.bodyHash: HashCode?
     get() = CachedValuesManager.getCachedValue(this) {
         val body = macroBody
-        val hash = body?.let { HashCode.compute(it) }
+        val hash = body?.let { HashCode.compute(flattenTokens(it, bodyTokens)) }
         CachedValueProvider.Result.create(hash, modificationTracker)
     }
 
+private fun flattenTokens(body: String, tokens: List<TextRange>): String = buildString {
+    var prev: TextRange? = null
+    for (token in tokens) {
+        if (prev != null) {
+            val isJoint = prev.endOffset == token.startOffset &&
+                body[prev.endOffset - 1].isPunctuation && body[token.startOffset].isPunctuation
+            append(if (isJoint) '\u0001' else '\u0000')
+        }
+        append(body, token.startOffset, token.endOffset)
+        prev = token
+    }
+}
+
+private val Char.isPunctuation: Boolean
+    get() = !isLetterOrDigit() && this != '_' && this != '"' && this != '\''
+
 fun RsMacroCall.resolveToMacro(): RsMacro? =
     path.reference?.resolve() as? RsMacro
 
@@ -6,7 +6,9 @@
 package org.rust.lang.core.psi.ext
 
 import org.rust.lang.core.psi.RsMetaItem
+import org.rust.lang.core.psi.RsMetaItemArgs
 import org.rust.lang.core.psi.RsTraitItem
+import org.rust.lang.utils.evaluation.KnownCfgOptions
 
 /**
  * Returns identifier name if path inside meta item consists only of this identifier.
@@ -31,3 +33,23 @@ val RsMetaItem.hasEq: Boolean get() = greenStub?.hasEq ?: (eq != null)
 
 fun RsMetaItem.resolveToDerivedTrait(): RsTraitItem? =
     path?.reference?.resolve() as? RsTraitItem
+
+/**
+ * `true` if this meta item is a configuration predicate or a part of it, e.g. `any(unix, windows)`, `unix`
+ * and `windows` in `#[cfg(any(unix, windows))]`. The first argument of `#[cfg_attr]` is a predicate too
+ */
+val RsMetaItem.isCfgPredicate: Boolean
+    get() {
+        val args = parent as? RsMetaItemArgs ?: return false
+        val owner = args.parent as? RsMetaItem ?: return false
+        return when (owner.name) {
+            "cfg" -> true
+            "cfg_attr" -> args.metaItemList.firstOrNull() == this
+            in KnownCfgOptions.OPERATORS -> owner.isCfgPredicate
+            else -> false
+        }
+    }
+
+/** `true` if this meta item is `all(..)`, `any(..)` or `not(..)` operator of a configuration predicate */
+val RsMetaItem.isCfgOperator: Boolean
+    get() = metaItemArgs != null && name in KnownCfgOptions.OPERATORS && isCfgPredicate
@@ -34,7 +34,7 @@ abstract class RsNamedElementImpl(node: ASTNode) : RsElementImpl(node),
     override fun getName(): String? = nameIdentifier?.unescapedText
 
     override fun setName(name: String): PsiElement? {
-        nameIdentifier?.replace(RsPsiFactory(project).createIdentifier(name))
+        nameIdentifier?.replace(RsPsiFactory(project).createIdentifier(name.escapeIdentifierIfNeeded(edition)))
         return this
     }
 
@@ -57,7 +57,7 @@ where StubT : RsNamedStub, StubT : StubElement<*> {
     }
 
     override fun setName(name: String): PsiElement? {
-        nameIdentifier?.replace(RsPsiFactory(project).createIdentifier(name))
+        nameIdentifier?.replace(RsPsiFactory(project).createIdentifier(name.escapeIdentifierIfNeeded(edition)))
         return this
     }
 
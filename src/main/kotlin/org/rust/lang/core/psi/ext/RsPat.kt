@@ -34,6 +34,8 @@ val RsPat.isIrrefutable: Boolean
             (pat.expr as? RsPathExpr)?.path?.isIrrefutable ?: false
         is RsPatRange ->
             false
+        is RsPatOr ->
+            pat.patList.any { it.isIrrefutable }
         else ->
             true
     }
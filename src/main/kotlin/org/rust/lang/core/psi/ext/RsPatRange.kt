@@ -5,7 +5,15 @@
 
 package org.rust.lang.core.psi.ext
 
+import org.rust.lang.core.psi.RsPatConst
 import org.rust.lang.core.psi.RsPatRange
 
 val RsPatRange.isInclusive: Boolean
     get() = dotdotdot != null || dotdoteq != null
+
+val RsPatRange.start: RsPatConst
+    get() = patConstList.first()
+
+/** `null` for half-open ranges like `10..` */
+val RsPatRange.end: RsPatConst?
+    get() = patConstList.getOrNull(1)
@@ -483,8 +483,12 @@ sealed class QualifiedNamedItem(val item: RsQualifiedNamedElement) {
         return "${containingCargoTarget?.normName}::$crateRelativePath"
     }
 
-    class ExplicitItem(item: RsQualifiedNamedElement) : QualifiedNamedItem(item) {
-        override val itemName: String? get() = item.name
+    /**
+     * [name] is the name the item is visible by if it differs from its own name,
+     * e.g. the custom derive name of a `#[proc_macro_derive]` function
+     */
+    class ExplicitItem(item: RsQualifiedNamedElement, private val name: String? = null) : QualifiedNamedItem(item) {
+        override val itemName: String? get() = name ?: item.name
         override val isPublic: Boolean get() = (item as? RsVisible)?.isPublic == true
         override val superMods: List<ModWithName>?
             get() = (if (item is RsMod) item.`super` else item.containingMod)?.superMods?.map { ModWithName(it) }
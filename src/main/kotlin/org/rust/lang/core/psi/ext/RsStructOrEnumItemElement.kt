@@ -25,6 +25,28 @@ val RsStructOrEnumItemElement.derivedTraitsToMetaItems: Map<RsTraitItem, RsMetaI
         .mapNotNull { meta -> (meta.resolveToDerivedTrait())?.let { it to meta } }
         .toMap()
 
+/** Custom derives, i.e. `#[proc_macro_derive]` functions, applied to the item */
+val RsStructOrEnumItemElement.derivedProcMacros: Collection<RsFunction>
+    get() = deriveMetaItems
+        .mapNotNull { it.path?.reference?.resolve() as? RsFunction }
+        .filter { it.isCustomDeriveProcMacroDef }
+        .toList()
+
+/**
+ * The struct or enum whose derives may declare helper attributes used on this element,
+ * i.e. the element itself or the struct or enum containing a field or an enum variant
+ */
+val RsOuterAttributeOwner.derivingItem: RsStructOrEnumItemElement?
+    get() = when (this) {
+        is RsStructOrEnumItemElement -> this
+        is RsFieldDecl, is RsEnumVariant -> ancestorStrict()
+        else -> null
+    }
+
+/** Helper attributes of unresolved custom derives are unknown */
+val RsStructOrEnumItemElement.hasUnresolvedDerives: Boolean
+    get() = deriveMetaItems.any { it.path?.reference?.resolve() == null }
+
 private val RsStructOrEnumItemElement.deriveMetaItems: Sequence<RsMetaItem>
     get() = queryAttributes
         .deriveAttributes
@@ -15,8 +15,10 @@ import com.intellij.psi.stubs.IStubElementType
 import com.intellij.util.Query
 import org.rust.ide.icons.RsIcons
 import org.rust.lang.core.macros.RsExpandedElement
+import org.rust.lang.core.macros.expandedFrom
 import org.rust.lang.core.psi.*
 import org.rust.lang.core.resolve.KNOWN_DERIVABLE_TRAITS
+import org.rust.lang.core.resolve.indexes.RsImplIndex
 import org.rust.lang.core.resolve.knownItems
 import org.rust.lang.core.stubs.RsTraitItemStub
 import org.rust.lang.core.types.*
@@ -79,6 +81,27 @@ fun RsTraitItem.searchForImplementations(): Query<RsImplItem> {
         .filterQuery(Condition { it.typeReference != null })
 }
 
+/**
+ * Unlike [searchForImplementations], also finds blanket impls (`impl<T: Foo> Bar for T`) in [RsImplIndex],
+ * puts them after the others and deduplicates impls expanded from the same macro call
+ */
+fun RsTraitItem.findImplementations(): List<RsImplItem> {
+    val impls = searchForImplementations().findAll().filter { !it.isBlanketImpl }
+    val blanketImpls = mutableListOf<RsImplItem>()
+    RsImplIndex.findFreeImpls(project) { cachedImpl ->
+        if (cachedImpl.implementedTrait?.element == this) {
+            blanketImpls += cachedImpl.impl
+        }
+        false
+    }
+    // An impl expanded from a macro call can be found several times, so they are compared by the macro call,
+    // the trait and the self type. Different impls produced by the same macro call are kept
+    return (impls + blanketImpls).distinctBy { impl ->
+        val macroCall = impl.expandedFrom ?: return@distinctBy impl
+        Triple(macroCall, impl.traitRef?.text, impl.typeReference?.text)
+    }
+}
+
 private val RsTraitItem.superTraits: Sequence<BoundElement<RsTraitItem>> get() {
     // trait Foo where Self: Bar {}
     val whereBounds = whereClause?.wherePredList.orEmpty().asSequence()
@@ -948,12 +948,78 @@ class ImplLookup(
     fun isSized(ty: Ty): Boolean = ty.isTraitImplemented(items.Sized)
     fun isDebug(ty: Ty): Boolean = ty.isTraitImplemented(items.Debug)
     fun isDefault(ty: Ty): Boolean = ty.isTraitImplemented(items.Default)
+    fun isError(ty: Ty): Boolean = ty.isTraitImplemented(items.Error)
     fun isPartialEq(ty: Ty, rhsType: Ty = ty): Boolean = ty.isTraitImplemented(items.PartialEq, rhsType)
     fun isIntoIterator(ty: Ty): Boolean = ty.isTraitImplemented(items.IntoIterator)
+    fun isInto(ty: Ty, targetTy: Ty): Boolean = ty.isTraitImplemented(items.Into, targetTy)
     fun isFn(ty: Ty): Boolean = ty.isTraitImplemented(items.Fn)
     fun isFnOnce(ty: Ty): Boolean = ty.isTraitImplemented(items.FnOnce)
     fun isFnMut(ty: Ty): Boolean = ty.isTraitImplemented(items.FnMut)
     fun isAnyFn(ty: Ty): Boolean = isFn(ty) || isFnOnce(ty) || isFnMut(ty)
+    fun isSend(ty: Ty): Boolean = ty.implementsAutoTrait(items.Send)
+    fun isSync(ty: Ty): Boolean = ty.implementsAutoTrait(items.Sync)
+
+    /**
+     * Unlike [canSelect], which considers that any type implements auto traits (see [autoTraitCandidates]),
+     * checks the components of [this] type: `Rc<T>` is not `Send` because of `impl<T> !Send for Rc<T>`,
+     * `Mutex<T>` is `Sync` only if `T: Send` because of `unsafe impl<T: Send> Sync for Mutex<T>`,
+     * and a struct is `Send` if all its fields are `Send`. Types of unknown structure implement the trait
+     */
+    private fun Ty.implementsAutoTrait(trait: RsTraitItem?, depth: Int = 0): Boolean {
+        if (trait == null) return false
+        if (depth > DEFAULT_RECURSION_LIMIT) return true
+        val newDepth = depth + 1
+        return when (this) {
+            // `&T: Send` and `&T: Sync` require `T: Sync`, `&mut T` is `Send`/`Sync` if `T` is
+            is TyReference -> {
+                val referencedTrait = if (mutability == IMMUTABLE) items.Sync else trait
+                referenced.implementsAutoTrait(referencedTrait, newDepth)
+            }
+            is TyPointer -> false
+            is TyTuple -> types.all { it.implementsAutoTrait(trait, newDepth) }
+            is TyArray -> base.implementsAutoTrait(trait, newDepth)
+            is TySlice -> elementType.implementsAutoTrait(trait, newDepth)
+            is TyTypeParameter -> getEnvBoundTransitivelyFor(this).any { it.element == trait }
+            is TyAnon -> getTraitBoundsTransitively().any { it.element == trait }
+            is TyTraitObject -> getTraitBoundsTransitively().any { it.element == trait }
+            is TyAdt -> adtImplementsAutoTrait(trait, newDepth)
+            else -> true
+        }
+    }
+
+    private fun TyAdt.adtImplementsAutoTrait(trait: RsTraitItem, depth: Int): Boolean {
+        if (item == items.PhantomData) return typeArguments.all { it.implementsAutoTrait(trait, depth) }
+
+        val impls = mutableListOf<RsCachedImplItem>()
+        findExplicitImpls(this) { cachedImpl ->
+            if (cachedImpl.implementedTrait?.element == trait) impls += cachedImpl
+            false
+        }
+        if (impls.any { it.impl.excl != null }) return false
+        val impl = impls.firstOrNull()
+        if (impl != null) {
+            // Only auto trait bounds of impl type parameters are checked, e.g. `T: Send` in
+            // `unsafe impl<T: Send> Sync for Mutex<T>`
+            val formalSelfTy = impl.typeAndGenerics?.first as? TyAdt ?: return true
+            val actualArguments = formalSelfTy.typeArguments.zip(typeArguments).toMap()
+            return impl.impl.bounds.all { bound ->
+                val boundTrait = bound.trait.element
+                if (boundTrait != items.Send && boundTrait != items.Sync) return@all true
+                val actualTy = actualArguments[bound.selfTy] ?: return@all true
+                actualTy.implementsAutoTrait(boundTrait, depth)
+            }
+        }
+
+        val fields = when (val item = item) {
+            is RsStructItem -> item.fields
+            is RsEnumItem -> item.variants.flatMap { it.fields }
+            else -> emptyList()
+        }
+        return fields.all { field ->
+            val fieldTy = field.typeReference?.type?.substitute(typeParameterValues) ?: return@all true
+            fieldTy.implementsAutoTrait(trait, depth)
+        }
+    }
 
 
     private fun Ty.isTraitImplemented(trait: RsTraitItem?, vararg subst: Ty): Boolean {
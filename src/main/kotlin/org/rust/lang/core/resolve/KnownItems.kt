@@ -87,6 +87,7 @@ class KnownItems(
     val AsRef: RsTraitItem? get() = findItem("core::convert::AsRef")
     val AsMut: RsTraitItem? get() = findItem("core::convert::AsMut")
     val From: RsTraitItem? get() = findItem("core::convert::From")
+    val Into: RsTraitItem? get() = findItem("core::convert::Into")
     val TryFrom: RsTraitItem? get() = findItem("core::convert::TryFrom")
     val FromStr: RsTraitItem? get() = findItem("core::str::FromStr")
     val Borrow: RsTraitItem? get() = findItem("core::borrow::Borrow")
@@ -94,6 +95,7 @@ class KnownItems(
     val Hash: RsTraitItem? get() = findItem("core::hash::Hash")
     val Default: RsTraitItem? get() = findItem("core::default::Default")
     val Display: RsTraitItem? get() = findItem("core::fmt::Display")
+    val Error: RsTraitItem? get() = findItem("std::error::Error")
     val ToOwned: RsTraitItem? get() = findItem("alloc::borrow::ToOwned")
     val ToString: RsTraitItem? get() = findItem("alloc::string::ToString")
     val Try: RsTraitItem? get() = findItem("core::ops::try::Try")
@@ -106,6 +108,8 @@ class KnownItems(
     val Binary: RsTraitItem? get() = findItem("core::fmt::Binary")
     val LowerExp: RsTraitItem? get() = findItem("core::fmt::LowerExp")
     val UpperExp: RsTraitItem? get() = findItem("core::fmt::UpperExp")
+    val Send: RsTraitItem? get() = findItem("core::marker::Send")
+    val Sync: RsTraitItem? get() = findItem("core::marker::Sync")
 
     // Lang items
 
@@ -120,6 +124,7 @@ class KnownItems(
     val Clone: RsTraitItem? get() = findLangItem("clone")
     val Copy: RsTraitItem? get() = findLangItem("copy")
     val PartialEq: RsTraitItem? get() = findLangItem("eq")
+    val PhantomData: RsStructOrEnumItemElement? get() = findLangItem("phantom_data")
 
     // `Eq` trait doesn't have its own lang attribute, so use `findItem` to find it
     val Eq: RsTraitItem? get() = findItem("core::cmp::Eq")
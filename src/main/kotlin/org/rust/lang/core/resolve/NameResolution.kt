@@ -732,8 +732,25 @@ fun processMacroReferenceVariants(ref: RsMacroReference, processor: RsResolvePro
 /**
  * Attribute macro can only be resolved to its definition.
  */
-fun processAttributeProcMacroResolveVariants(element: RsPath, processor: RsResolveProcessor): Boolean =
-    processNestedScopesUpwards(element, MACROS, processor)
+fun processAttributeProcMacroResolveVariants(element: RsPath, processor: RsResolveProcessor): Boolean {
+    if (processDeriveHelperAttributeResolveVariants(element, processor)) return true
+    return processNestedScopesUpwards(element, MACROS, processor)
+}
+
+/**
+ * Helper attributes declared by custom derives of the item, e.g. `#[serde(...)]` declared by
+ * `#[proc_macro_derive(Serialize, attributes(serde))]`, are resolved to the `proc_macro_derive` function
+ */
+private fun processDeriveHelperAttributeResolveVariants(element: RsPath, processor: RsResolveProcessor): Boolean {
+    val owner = element.parent?.parent?.parent as? RsOuterAttributeOwner ?: return false
+    val derives = owner.derivingItem?.derivedProcMacros ?: return false
+    for (derive in derives) {
+        for (helper in derive.procMacroDeriveHelperAttributes) {
+            if (processor(helper, derive)) return true
+        }
+    }
+    return false
+}
 
 fun processDeriveTraitResolveVariants(element: RsPath, traitName: String, processor: RsResolveProcessor): Boolean {
     processNestedScopesUpwards(element, MACROS, processor)
@@ -1074,7 +1091,7 @@ private fun asProcMacroDefinition(item: RsFunction): ScopeEntry? {
         item.isBangProcMacroDef || item.isAttributeProcMacroDef -> item.name
 
         item.isCustomDeriveProcMacroDef -> {
-            item.queryAttributes.getFirstArgOfSingularAttribute("proc_macro_derive")
+            item.procMacroDeriveName
         }
 
         else -> null
@@ -1280,10 +1297,18 @@ private fun processLexicalDeclarations(
         return false
     }
 
+    fun processLetExprs(letExprs: List<RsLetExpr>, processor: RsResolveProcessor): Boolean {
+        // Later `let`s of a chain shadow earlier ones: `if let Some(a) = a && let Some(a) = a {}`
+        for (letExpr in letExprs.asReversed()) {
+            val orPats = letExpr.orPats ?: continue
+            if (processOrPats(orPats, processor)) return true
+        }
+        return false
+    }
+
     fun processCondition(condition: RsCondition?, processor: RsResolveProcessor): Boolean {
         if (condition == null || condition == cameFrom) return false
-        val orPats = condition.orPats ?: return false
-        return processOrPats(orPats, processor)
+        return processLetExprs(condition.letExprs, processor)
     }
 
     when (scope) {
@@ -1395,6 +1420,14 @@ private fun processLexicalDeclarations(
         is RsMatchArm -> {
             return processOrPats(scope.orPats, processor)
         }
+
+        // `if let Some(a) = foo() && a > 0 {}`
+        //                            ^ bindings of the preceding `let`s of the chain are visible here
+        is RsBinaryExpr -> {
+            if (cameFrom != scope.right || scope.operatorType != LogicOp.AND) return false
+            val letExprs = scope.left.letChainOperands.filterIsInstance<RsLetExpr>()
+            return processLetExprs(letExprs, processor)
+        }
     }
     return false
 }
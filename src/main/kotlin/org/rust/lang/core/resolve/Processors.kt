@@ -7,6 +7,7 @@ package org.rust.lang.core.resolve
 
 import com.intellij.codeInsight.completion.CompletionResultSet
 import com.intellij.util.SmartList
+import org.rust.ide.settings.RsCodeInsightSettings
 import org.rust.lang.core.completion.RsCompletionContext
 import org.rust.lang.core.completion.createLookupElement
 import org.rust.lang.core.psi.RsFunction
@@ -213,9 +214,12 @@ fun filterCompletionVariantsByVisibility(processor: RsResolveProcessor, mod: RsM
         val element = it.element
         if (element is RsVisible && !element.isVisibleFrom(mod)) return false
 
-        val isHidden = element is RsOuterAttributeOwner && element.queryAttributes.isDocHidden &&
-            element.containingMod != mod
-        if (isHidden) return false
+        // Otherwise, such items are just demoted, see `DOC_HIDDEN_PRIORITY`
+        if (!RsCodeInsightSettings.getInstance().suggestDocHiddenItems) {
+            val isHidden = element is RsOuterAttributeOwner && element.queryAttributes.isDocHidden &&
+                element.containingMod != mod
+            if (isHidden) return false
+        }
 
         return processor(it)
     }
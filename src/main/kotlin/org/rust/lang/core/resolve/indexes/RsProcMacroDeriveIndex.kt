@@ -0,0 +1,47 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.resolve.indexes
+
+import com.intellij.openapi.project.Project
+import com.intellij.psi.search.GlobalSearchScope
+import com.intellij.psi.stubs.IndexSink
+import com.intellij.psi.stubs.StringStubIndexExtension
+import com.intellij.psi.stubs.StubIndex
+import com.intellij.psi.stubs.StubIndexKey
+import org.rust.lang.core.psi.RsFunction
+import org.rust.lang.core.psi.ext.containingCargoTarget
+import org.rust.lang.core.psi.ext.procMacroDeriveName
+import org.rust.lang.core.psi.isValidProjectMember
+import org.rust.lang.core.stubs.RsFileStub
+import org.rust.lang.core.stubs.RsFunctionStub
+import org.rust.openapiext.getElements
+
+/** `#[proc_macro_derive(Name)]` functions by the names of derives they define */
+class RsProcMacroDeriveIndex : StringStubIndexExtension<RsFunction>() {
+    override fun getVersion(): Int = RsFileStub.Type.stubVersion
+    override fun getKey(): StubIndexKey<String, RsFunction> = KEY
+
+    companion object {
+        private val KEY: StubIndexKey<String, RsFunction> =
+            StubIndexKey.createIndexKey("org.rust.lang.core.resolve.indexes.RsProcMacroDeriveIndex")
+
+        fun index(stub: RsFunctionStub, sink: IndexSink) {
+            if (!stub.isProcMacroDef) return
+            val name = stub.psi.procMacroDeriveName ?: return
+            sink.occurrence(KEY, name)
+        }
+
+        /** Returns custom derives declared in proc-macro crates of the project or its dependencies */
+        fun findAllDerives(project: Project): List<RsFunction> {
+            val scope = GlobalSearchScope.allScope(project)
+            return StubIndex.getInstance().getAllKeys(KEY, project).flatMap { name ->
+                getElements(KEY, name, project, scope).filter {
+                    it.isValidProjectMember && it.containingCargoTarget?.isProcMacro == true
+                }
+            }
+        }
+    }
+}
@@ -12,6 +12,7 @@ import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.resolve.*
 import org.rust.lang.core.types.BoundElement
 import org.rust.lang.core.types.Substitution
+import org.rust.lang.core.types.consts.Const
 import org.rust.lang.core.types.consts.CtConstParameter
 import org.rust.lang.core.types.consts.CtUnknown
 import org.rust.lang.core.types.infer.foldTyInferWith
@@ -231,10 +232,20 @@ fun <T : RsElement> instantiatePathGenerics(
         val expectedTy = constParameters.getOrNull(i)?.parameter?.typeReference?.type ?: TyUnknown
         expr.evaluate(expectedTy, resolver)
     }
-    val constSubst = constParameters.withIndex().associate { (i, param) ->
-        val value = constArguments?.getOrNull(i)
-            ?: if (areOptionalArgs && constArguments == null) param else CtUnknown
-        param to value
+    val constSubst = mutableMapOf<CtConstParameter, Const>()
+    for ((i, param) in constParameters.withIndex()) {
+        val value = constArguments?.getOrNull(i) ?: if (areOptionalArgs && constArguments == null) {
+            param
+        } else {
+            // Use default argument from a definition `struct S<const N: usize = 1>;` or falling back to `CtUnknown`.
+            // The default can refer to the previous parameters, e.g. `struct S<const N: usize, const M: usize = N>;`
+            val expectedTy = param.parameter.typeReference?.type ?: TyUnknown
+            param.parameter.expr
+                ?.evaluate(expectedTy)
+                ?.substitute(Substitution(typeSubst, regionSubst, constSubst.toMap()))
+                ?: CtUnknown
+        }
+        constSubst[param] = value
     }
 
     val newSubst = Substitution(typeSubst, regionSubst, constSubst)
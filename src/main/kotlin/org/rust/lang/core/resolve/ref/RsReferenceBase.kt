@@ -18,6 +18,7 @@ import org.rust.lang.core.psi.RsPsiFactory
 import org.rust.lang.core.psi.escapeIdentifierIfNeeded
 import org.rust.lang.core.psi.ext.RsElement
 import org.rust.lang.core.psi.ext.RsReferenceElementBase
+import org.rust.lang.core.psi.ext.edition
 import org.rust.lang.core.psi.ext.elementType
 
 abstract class RsReferenceBase<T : RsReferenceElementBase>(
@@ -61,7 +62,7 @@ abstract class RsReferenceBase<T : RsReferenceElementBase>(
                 IDENTIFIER -> {
                     // Renaming files is tricky: we don't want to change `RenamePsiFileProcessor`,
                     // so we must be ready for invalid names here
-                    val name = newName.replace(".rs", "").escapeIdentifierIfNeeded()
+                    val name = newName.replace(".rs", "").escapeIdentifierIfNeeded(identifier.edition)
                     if (!isValidRustVariableIdentifier(name)) return
                     factory.createIdentifier(name)
 
@@ -9,7 +9,9 @@ import com.intellij.codeInsight.lookup.LookupElement
 import com.intellij.psi.PsiElement
 import org.rust.lang.core.psi.RsPsiFactory
 import org.rust.lang.core.psi.RsStructLiteralField
+import org.rust.lang.core.psi.escapeIdentifierIfNeeded
 import org.rust.lang.core.psi.ext.RsElement
+import org.rust.lang.core.psi.ext.edition
 import org.rust.lang.core.resolve.collectResolveVariants
 import org.rust.lang.core.resolve.processStructLiteralFieldResolveVariants
 
@@ -31,7 +33,7 @@ class RsStructLiteralFieldReferenceImpl(
             val identifier = element.identifier ?: return element
 
             val psiFactory = RsPsiFactory(element.project)
-            val newIdent = psiFactory.createIdentifier(newName)
+            val newIdent = psiFactory.createIdentifier(newName.escapeIdentifierIfNeeded(element.edition))
             val colon = psiFactory.createColon()
             val initExpression = psiFactory.createExpression(identifier.text)
 
@@ -49,7 +49,7 @@ class RsFileStub : PsiFileStubImpl<RsFile> {
     override fun getType() = Type
 
     object Type : IStubFileElementType<RsFileStub>(RsLanguage) {
-        private const val STUB_VERSION = 198
+        private const val STUB_VERSION = 201
 
         // Bump this number if Stub structure changes
         override fun getStubVersion(): Int = RustParserDefinition.PARSER_VERSION + STUB_VERSION
@@ -270,6 +270,7 @@ fun factory(name: String): RsStubElementType<*, *> = when (name) {
     "IF_EXPR" -> RsExprStubType("IF_EXPR", ::RsIfExprImpl)
     "INDEX_EXPR" -> RsExprStubType("INDEX_EXPR", ::RsIndexExprImpl)
     "LAMBDA_EXPR" -> RsExprStubType("LAMBDA_EXPR", ::RsLambdaExprImpl)
+    "LET_EXPR" -> RsExprStubType("LET_EXPR", ::RsLetExprImpl)
     "LIT_EXPR" -> RsLitExprStub.Type
     "LOOP_EXPR" -> RsExprStubType("LOOP_EXPR", ::RsLoopExprImpl)
     "MACRO_EXPR" -> RsExprStubType("MACRO_EXPR", ::RsMacroExprImpl)
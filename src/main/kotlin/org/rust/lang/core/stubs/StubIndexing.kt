@@ -7,6 +7,7 @@ package org.rust.lang.core.stubs
 
 import com.intellij.psi.PsiElement
 import com.intellij.psi.stubs.IndexSink
+import com.intellij.psi.stubs.StubElement
 import org.rust.lang.core.psi.ext.RsAbstractableOwner
 import org.rust.lang.core.psi.ext.getOwner
 import org.rust.lang.core.psi.ext.stubParent
@@ -60,6 +61,7 @@ fun IndexSink.indexTraitAlias(stub: RsTraitAliasStub) {
 
 fun IndexSink.indexFunction(stub: RsFunctionStub) {
     indexNamedStub(stub)
+    RsProcMacroDeriveIndex.index(stub, this)
 }
 
 fun IndexSink.indexConstant(stub: RsConstantStub) {
@@ -104,6 +106,9 @@ private fun IndexSink.indexNamedStub(stub: RsNamedStub) {
     stub.name?.let {
         occurrence(RsNamedElementIndex.KEY, it)
     }
+    if (stub is StubElement<*>) {
+        RsDocAliasIndex.index(stub, this)
+    }
 }
 
 private fun IndexSink.indexGotoClass(stub: RsNamedStub) {
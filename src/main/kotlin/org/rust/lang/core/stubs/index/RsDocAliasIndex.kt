@@ -0,0 +1,35 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.stubs.index
+
+import com.intellij.psi.stubs.IndexSink
+import com.intellij.psi.stubs.StringStubIndexExtension
+import com.intellij.psi.stubs.StubElement
+import com.intellij.psi.stubs.StubIndexKey
+import org.rust.lang.core.psi.ext.RsDocAndAttributeOwner
+import org.rust.lang.core.psi.ext.RsNamedElement
+import org.rust.lang.core.psi.ext.queryAttributes
+import org.rust.lang.core.stubs.RsAttributeOwnerStub
+import org.rust.lang.core.stubs.RsFileStub
+
+/** Named elements by values of their `#[doc(alias = "...")]` attributes. Used by Search Everywhere */
+class RsDocAliasIndex : StringStubIndexExtension<RsNamedElement>() {
+    override fun getVersion(): Int = RsFileStub.Type.stubVersion
+    override fun getKey(): StubIndexKey<String, RsNamedElement> = KEY
+
+    companion object {
+        val KEY: StubIndexKey<String, RsNamedElement> =
+            StubIndexKey.createIndexKey("org.rust.lang.core.stubs.index.RsDocAliasIndex")
+
+        fun index(stub: StubElement<*>, sink: IndexSink) {
+            if (stub is RsAttributeOwnerStub && !stub.hasAttrs) return
+            val element = stub.psi as? RsDocAndAttributeOwner ?: return
+            for (alias in element.queryAttributes.docAliases) {
+                sink.occurrence(KEY, alias)
+            }
+        }
+    }
+}
@@ -157,3 +157,11 @@ val RsInferenceContextOwner.liveness: Liveness?
         val livenessResult = livenessContext?.check()
         createResult(livenessResult)
     }
+
+private val VARIANT_FLOW_KEY: Key<CachedValue<VariantFlow>> = Key.create("VARIANT_FLOW_KEY")
+
+val RsInferenceContextOwner.variantFlow: VariantFlow?
+    get() = CachedValuesManager.getCachedValue(this, VARIANT_FLOW_KEY) {
+        val variantFlowContext = VariantFlowContext.buildFor(this)
+        createResult(variantFlowContext?.analyze())
+    }
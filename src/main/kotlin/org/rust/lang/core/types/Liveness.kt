@@ -14,6 +14,7 @@ import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.*
 import org.rust.lang.core.resolve.ImplLookup
 import org.rust.lang.core.types.borrowck.*
+import org.rust.lang.core.types.infer.BorrowKind
 import org.rust.lang.core.types.infer.Categorization
 import org.rust.lang.core.types.infer.Cmt
 import org.rust.lang.core.types.infer.MemoryCategorizationContext
@@ -133,6 +134,8 @@ class GatherLivenessContext(
         livenessData.addUsage(element, cmt)
     }
 
+    override fun borrow(element: RsElement, cmt: Cmt, kind: BorrowKind) {}
+
     fun gather(): LivenessData {
         val gatherVisitor = ExprUseWalker(this, MemoryCategorizationContext(ctx.implLookup, ctx.inference))
         gatherVisitor.consumeBody(ctx.body)
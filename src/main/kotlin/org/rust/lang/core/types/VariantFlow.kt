@@ -0,0 +1,362 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.types
+
+import org.rust.lang.core.cfg.CFGEdgeData
+import org.rust.lang.core.cfg.CFGNode
+import org.rust.lang.core.cfg.CFGNodeData
+import org.rust.lang.core.cfg.ControlFlowGraph
+import org.rust.lang.core.psi.*
+import org.rust.lang.core.psi.ext.*
+import org.rust.lang.core.resolve.KnownItems
+import org.rust.lang.core.resolve.knownItems
+import org.rust.lang.core.types.ty.TyAdt
+import org.rust.lang.utils.Edge
+
+/** A variant of `Option` or `Result` */
+enum class VariantFact(val variantName: String) {
+    SOME("Some"), NONE("None"), OK("Ok"), ERR("Err");
+
+    val opposite: VariantFact
+        get() = when (this) {
+            SOME -> NONE
+            NONE -> SOME
+            OK -> ERR
+            ERR -> OK
+        }
+}
+
+/**
+ * Known variants of `Option` and `Result` local variables at their usages, e.g. `x` is `None` at `x.unwrap()` in
+ * `let x: Option<i32> = None; x.unwrap()`, and is `Some` in `if x.is_some() { x.unwrap() }`
+ */
+class VariantFlow(private val factsAtUsages: Map<RsPathExpr, VariantFact>) {
+    /** The variant of the variable referenced by [usage] right before [usage] is evaluated */
+    fun factAt(usage: RsPathExpr): VariantFact? = factsAtUsages[usage]
+}
+
+private typealias Facts = Map<RsPatBinding, VariantFact>
+
+/**
+ * Forward dataflow analysis over the control flow graph of a single function.
+ *
+ * Facts come from variable initializers and assignments (`None`, `Some(..)`, `Ok(..)`, `Err(..)`),
+ * and are refined on the branches of `is_some`/`is_none`/`is_ok`/`is_err` checks and `if let` conditions,
+ * on `match` arm entry and on the success path of `?`. A fact is dropped when control flow paths disagree,
+ * when the variable is reassigned, and when it's passed by value or by `&mut`. Variables which are
+ * mutably borrowed or used in closures are not tracked at all.
+ *
+ * Loops are handled by iterating to a fixed point. If it isn't reached in [MAX_ITERATIONS],
+ * nothing is known about any variable
+ */
+class VariantFlowContext private constructor(
+    private val cfg: ControlFlowGraph,
+    private val knownItems: KnownItems
+) {
+    /** Usages of the tracked variables */
+    private val usages: Map<RsPathExpr, RsPatBinding>
+    private val optionBindings: Set<RsPatBinding>
+
+    init {
+        val usages = hashMapOf<RsPathExpr, RsPatBinding>()
+        val optionBindings = hashSetOf<RsPatBinding>()
+        val untracked = hashSetOf<RsPatBinding>()
+        cfg.graph.forEachNode { node ->
+            val path = node.data.element as? RsPathExpr ?: return@forEachNode
+            val binding = path.path.reference?.resolve() as? RsPatBinding ?: return@forEachNode
+            val item = (binding.type as? TyAdt)?.item ?: return@forEachNode
+            when (item) {
+                knownItems.Option -> optionBindings += binding
+                knownItems.Result -> Unit
+                else -> return@forEachNode
+            }
+            if (path.isMutablyBorrowed || path.ancestorStrict<RsLambdaExpr>() != null) {
+                untracked += binding
+            }
+            usages[path] = binding
+        }
+        this.usages = usages.filterValues { it !in untracked }
+        this.optionBindings = optionBindings
+    }
+
+    private val trackedBindings: Set<RsPatBinding> = usages.values.toSet()
+
+    fun analyze(): VariantFlow? {
+        if (usages.isEmpty()) return VariantFlow(emptyMap())
+
+        val graph = cfg.graph
+        val nodes = graph.nodesInPostOrder(cfg.entry).asReversed()
+        // `null` means that a node is unreachable (or isn't reached yet)
+        val entryFacts = arrayOfNulls<Facts>(graph.nodesCount)
+        val exitFacts = arrayOfNulls<Facts>(graph.nodesCount)
+
+        var iterations = 0
+        var changed = true
+        while (changed) {
+            if (++iterations > MAX_ITERATIONS) return null
+            changed = false
+            for (node in nodes) {
+                val entry = if (node == cfg.entry) emptyMap() else joinIncoming(node, exitFacts)
+                entryFacts[node.index] = entry
+                val exit = entry?.let { transfer(node, it) }
+                if (exit != exitFacts[node.index]) {
+                    exitFacts[node.index] = exit
+                    changed = true
+                }
+            }
+        }
+
+        val factsAtUsages = hashMapOf<RsPathExpr, VariantFact>()
+        for (node in nodes) {
+            val path = node.data.element as? RsPathExpr ?: continue
+            val binding = usages[path] ?: continue
+            val fact = entryFacts[node.index]?.get(binding) ?: continue
+            factsAtUsages[path] = fact
+        }
+        return VariantFlow(factsAtUsages)
+    }
+
+    private fun joinIncoming(node: CFGNode, exitFacts: Array<Facts?>): Facts? {
+        var result: Facts? = null
+        for (edge in cfg.graph.incomingEdges(node)) {
+            val facts = exitFacts[edge.source.index] ?: continue
+            val refined = refineOnEdge(edge, facts)
+            result = if (result == null) refined else result.filter { (binding, fact) -> refined[binding] == fact }
+        }
+        return result
+    }
+
+    private fun transfer(node: CFGNode, facts: Facts): Facts {
+        return when (val element = node.data.element) {
+            is RsPathExpr -> {
+                val binding = usages[element] ?: return facts
+                if (element.mayChangeValue) facts - binding else facts
+            }
+
+            // A variable declared in a loop gets a new value on each iteration
+            is RsPatBinding -> facts - element
+
+            is RsLetDecl -> {
+                val pat = element.pat as? RsPatIdent ?: return facts
+                if (pat.pat != null) return facts
+                facts.with(pat.patBinding, element.expr?.let { variantOf(it) })
+            }
+
+            is RsBinaryExpr -> {
+                if (element.operatorType != AssignmentOp.EQ) return facts
+                val left = unwrapParenExprs(element.left) as? RsPathExpr ?: return facts
+                val binding = usages[left] ?: return facts
+                facts.with(binding, element.right?.let { variantOf(it) })
+            }
+
+            is RsPat -> {
+                val arm = (element.parent as? RsOrPats)?.parent as? RsMatchArm ?: return facts
+                val discriminant = arm.ancestorStrict<RsMatchExpr>()?.expr ?: return facts
+                val binding = trackedBinding(discriminant) ?: return facts
+                val fact = variantOf(element) ?: return facts
+                facts + (binding to fact)
+            }
+
+            else -> facts
+        }
+    }
+
+    private fun Facts.with(binding: RsPatBinding, fact: VariantFact?): Facts = when {
+        binding !in trackedBindings -> this
+        fact == null -> this - binding
+        else -> this + (binding to fact)
+    }
+
+    private fun refineOnEdge(edge: Edge<CFGNodeData, CFGEdgeData>, facts: Facts): Facts {
+        val condition = edge.source.data.element as? RsExpr ?: return facts
+        val target = edge.target.data.element ?: return facts
+        val parent = condition.parent
+
+        if (parent is RsTryExpr) {
+            if (target != parent) return facts
+            val binding = trackedBinding(condition) ?: return facts
+            return facts + (binding to if (binding in optionBindings) VariantFact.SOME else VariantFact.OK)
+        }
+
+        val branch = when {
+            parent is RsCondition -> {
+                val (owner, thenBlock) = when (val expr = parent.parent) {
+                    is RsIfExpr -> expr to expr.block
+                    is RsWhileExpr -> expr to expr.block
+                    else -> return facts
+                }
+                val elseBranch = (owner as? RsIfExpr)?.elseBranch
+                when {
+                    thenBlock != null && thenBlock.isAncestorOf(target) -> true
+                    target == owner || elseBranch != null && elseBranch.isAncestorOf(target) -> false
+                    else -> return facts
+                }
+            }
+
+            parent is RsBinaryExpr && parent.left == condition -> {
+                val operator = parent.operatorType as? LogicOp ?: return facts
+                val right = parent.right
+                when {
+                    right != null && right.isAncestorOf(target) -> operator == LogicOp.AND
+                    target == parent -> operator == LogicOp.OR
+                    else -> return facts
+                }
+            }
+
+            else -> return facts
+        }
+
+        return refine(condition, branch, facts)
+    }
+
+    /** Refines [facts] knowing that [condition] evaluates to [value] */
+    private fun refine(condition: RsExpr, value: Boolean, facts: Facts): Facts {
+        return when (condition) {
+            is RsParenExpr -> condition.expr?.let { refine(it, value, facts) } ?: facts
+
+            is RsUnaryExpr -> {
+                val expr = condition.expr
+                if (condition.operatorType == UnaryOperator.NOT && expr != null) refine(expr, !value, facts) else facts
+            }
+
+            is RsBinaryExpr -> {
+                val left = condition.left
+                val right = condition.right ?: return facts
+                when (condition.operatorType) {
+                    LogicOp.AND -> if (value) refine(right, true, refine(left, true, facts)) else facts
+                    LogicOp.OR -> if (!value) refine(right, false, refine(left, false, facts)) else facts
+                    else -> facts
+                }
+            }
+
+            is RsDotExpr -> {
+                val binding = trackedBinding(condition.expr) ?: return facts
+                val fact = when (condition.methodCall?.referenceName) {
+                    "is_some" -> VariantFact.SOME
+                    "is_none" -> VariantFact.NONE
+                    "is_ok" -> VariantFact.OK
+                    "is_err" -> VariantFact.ERR
+                    else -> return facts
+                }
+                facts + (binding to if (value) fact else fact.opposite)
+            }
+
+            is RsLetExpr -> {
+                val binding = condition.expr?.let { trackedBinding(it) } ?: return facts
+                val pats = condition.patList
+                val fact = pats.map { variantOf(it) }.distinct().singleOrNull() ?: return facts
+                when {
+                    value -> facts + (binding to fact)
+                    pats.size == 1 && pats.single().hasIrrefutableSubPatterns -> facts + (binding to fact.opposite)
+                    else -> facts
+                }
+            }
+
+            else -> facts
+        }
+    }
+
+    private fun trackedBinding(expr: RsExpr): RsPatBinding? =
+        (unwrapParenExprs(expr) as? RsPathExpr)?.let { usages[it] }
+
+    private fun variantOf(expr: RsExpr): VariantFact? {
+        val path = when (val e = unwrapParenExprs(expr)) {
+            is RsPathExpr -> e.path
+            is RsCallExpr -> (e.expr as? RsPathExpr)?.path
+            else -> null
+        }
+        return variantOf(path?.reference?.resolve())
+    }
+
+    private fun variantOf(pat: RsPat): VariantFact? {
+        val variant = when (pat) {
+            is RsPatTupleStruct -> pat.path.reference?.resolve()
+            is RsPatStruct -> pat.path.reference?.resolve()
+            is RsPatIdent -> if (pat.pat == null) pat.patBinding.reference.resolve() else null
+            is RsPatConst -> (pat.expr as? RsPathExpr)?.path?.reference?.resolve()
+            else -> null
+        }
+        return variantOf(variant)
+    }
+
+    private fun variantOf(element: Any?): VariantFact? {
+        val variant = element as? RsEnumVariant ?: return null
+        return when (variant.parentEnum) {
+            knownItems.Option -> when (variant.name) {
+                "Some" -> VariantFact.SOME
+                "None" -> VariantFact.NONE
+                else -> null
+            }
+            knownItems.Result -> when (variant.name) {
+                "Ok" -> VariantFact.OK
+                "Err" -> VariantFact.ERR
+                else -> null
+            }
+            else -> null
+        }
+    }
+
+    companion object {
+        private const val MAX_ITERATIONS: Int = 32
+
+        fun buildFor(owner: RsInferenceContextOwner): VariantFlowContext? {
+            // Macro calls which can't be expanded can do anything with variables passed to them
+            if (owner.descendantsWithMacrosOfType<RsMacroCall>().any { it.macroArgument != null && it.expansion == null }) {
+                return null
+            }
+            val cfg = owner.controlFlowGraph ?: return null
+            return VariantFlowContext(cfg, owner.knownItems)
+        }
+    }
+}
+
+private val RsPathExpr.isMutablyBorrowed: Boolean
+    get() {
+        val pats = when (val parent = parent) {
+            is RsUnaryExpr -> return parent.operatorType == UnaryOperator.REF_MUT
+            is RsMatchExpr -> parent.arms.flatMap { it.patList }
+            is RsLetExpr -> parent.patList
+            is RsLetDecl -> listOfNotNull(parent.pat)
+            else -> return false
+        }
+        // E.g. `match x { ref mut y => *y = None }`
+        return pats.any {
+            val kind = (it as? RsPatIdent)?.patBinding?.kind
+            kind is RsBindingModeKind.BindByReference && kind.mutability.isMut
+        }
+    }
+
+/** `false` if the evaluation of the path expression certainly doesn't change or move the variable */
+private val RsPathExpr.mayChangeValue: Boolean
+    get() = when (val parent = parent) {
+        is RsDotExpr -> {
+            val methodCall = parent.methodCall
+            when {
+                parent.expr != this || methodCall == null -> true
+                methodCall.referenceName in NON_MUTATING_METHODS -> false
+                else -> {
+                    val self = (methodCall.reference.resolve() as? RsFunction)?.selfParameter
+                    self == null || !self.isRef || self.mutability.isMut
+                }
+            }
+        }
+        is RsUnaryExpr -> parent.operatorType != UnaryOperator.REF
+        is RsBinaryExpr -> parent.operatorType !is EqualityOp
+        is RsMatchExpr, is RsLetExpr, is RsTryExpr, is RsFormatMacroArg -> false
+        else -> true
+    }
+
+/** Methods which take `Option` or `Result` by value, but don't make a difference for the analysis */
+private val NON_MUTATING_METHODS: Set<String> = setOf(
+    "unwrap", "expect", "unwrap_err", "expect_err", "is_some", "is_none", "is_ok", "is_err"
+)
+
+private val RsPat.hasIrrefutableSubPatterns: Boolean
+    get() = when (this) {
+        is RsPatTupleStruct -> patList.all { it.isIrrefutable }
+        is RsPatIdent, is RsPatConst -> true
+        else -> false
+    }
@@ -7,6 +7,7 @@ package org.rust.lang.core.types.borrowck
 
 import org.rust.lang.core.cfg.ControlFlowGraph
 import org.rust.lang.core.psi.RsBlock
+import org.rust.lang.core.psi.RsPatBinding
 import org.rust.lang.core.psi.ext.RsElement
 import org.rust.lang.core.psi.ext.RsInferenceContextOwner
 import org.rust.lang.core.psi.ext.body
@@ -24,7 +25,9 @@ class BorrowCheckContext private constructor(
     val implLookup: ImplLookup = ImplLookup.relativeTo(body),
     private val usesOfMovedValue: MutableSet<UseOfMovedValueError> = hashSetOf(),
     private val usesOfUninitializedVariable: MutableSet<UseOfUninitializedVariable> = hashSetOf(),
-    private val moveErrors: MutableSet<MoveError> = hashSetOf()
+    private val moveErrors: MutableSet<MoveError> = hashSetOf(),
+    private val mutableDeclarations: MutableSet<RsPatBinding> = linkedSetOf(),
+    private val usedMutableDeclarations: MutableSet<RsElement> = hashSetOf()
 ) {
     companion object {
         fun buildFor(owner: RsInferenceContextOwner): BorrowCheckContext? {
@@ -45,7 +48,8 @@ class BorrowCheckContext private constructor(
         return BorrowCheckResult(
             this.usesOfMovedValue.toList(),
             this.usesOfUninitializedVariable.toList(),
-            this.moveErrors.toList()
+            this.moveErrors.toList(),
+            this.mutableDeclarations.filter { it !in this.usedMutableDeclarations }
         )
     }
 
@@ -67,6 +71,15 @@ class BorrowCheckContext private constructor(
     fun reportMoveError(from: Cmt) {
         moveErrors.add(MoveError(from))
     }
+
+    fun reportMutableDeclaration(binding: RsPatBinding) {
+        mutableDeclarations.add(binding)
+    }
+
+    /** [variable] is mutated or mutably borrowed, so it must be declared as `mut` */
+    fun reportUsedMut(variable: RsElement) {
+        usedMutableDeclarations.add(variable)
+    }
 }
 
 class AnalysisData(val moveData: FlowedMoveData)
@@ -74,7 +87,9 @@ class AnalysisData(val moveData: FlowedMoveData)
 data class BorrowCheckResult(
     val usesOfMovedValue: List<UseOfMovedValueError>,
     val usesOfUninitializedVariable: List<UseOfUninitializedVariable>,
-    val moveErrors: List<MoveError>
+    val moveErrors: List<MoveError>,
+    /** `mut` bindings that are never mutated, e.g. `x` in `let mut x = 1;` without reassignments of `x` */
+    val unusedMutBindings: List<RsPatBinding>
 )
 
 data class UseOfMovedValueError(val use: RsElement, val move: Move)
@@ -5,22 +5,25 @@
 
 package org.rust.lang.core.types.borrowck
 
-import org.rust.lang.core.psi.RsBlock
-import org.rust.lang.core.psi.RsPat
-import org.rust.lang.core.psi.RsPatBinding
-import org.rust.lang.core.psi.RsPatSlice
+import org.rust.lang.core.macros.isExpandedFromMacro
+import org.rust.lang.core.psi.*
 import org.rust.lang.core.psi.ext.RsElement
 import org.rust.lang.core.psi.ext.ancestorOrSelf
+import org.rust.lang.core.psi.ext.ancestorStrict
+import org.rust.lang.core.psi.ext.mutability
 import org.rust.lang.core.types.borrowck.LoanPathElement.Interior
 import org.rust.lang.core.types.borrowck.LoanPathKind.Downcast
 import org.rust.lang.core.types.borrowck.LoanPathKind.Extend
+import org.rust.lang.core.types.borrowck.LoanPathKind.Var
 import org.rust.lang.core.types.borrowck.gatherLoans.isAdtWithDestructor
+import org.rust.lang.core.types.infer.BorrowKind
 import org.rust.lang.core.types.infer.Cmt
 import org.rust.lang.core.types.infer.MemoryCategorizationContext
 
 class CheckLoanContext(private val bccx: BorrowCheckContext, private val moveData: FlowedMoveData) : Delegate {
     override fun consume(element: RsElement, cmt: Cmt, mode: ConsumeMode) {
         consumeCommon(element, cmt)
+        checkMacroUsage(element, cmt)
     }
 
     override fun matchedPat(pat: RsPat, cmt: Cmt, mode: MatchMode) {}
@@ -45,10 +48,14 @@ class CheckLoanContext(private val bccx: BorrowCheckContext, private val moveDat
     private fun isInsideSliceDestructing(loanPath: LoanPath, move: Move): Boolean =
         loanPath.element is RsPatSlice && move.element.ancestorOrSelf<RsPatSlice>() == loanPath.element
 
-    override fun declarationWithoutInit(binding: RsPatBinding) {}
+    override fun declarationWithoutInit(binding: RsPatBinding) {
+        if (binding.mutability.isMut) bccx.reportMutableDeclaration(binding)
+    }
 
     override fun mutate(assignmentElement: RsElement, assigneeCmt: Cmt, mode: MutateMode) {
         val loanPath = LoanPath.computeFor(assigneeCmt) ?: return
+        checkMutability(assignmentElement, loanPath, mode)
+        checkMacroUsage(assignmentElement, assigneeCmt)
         when (mode) {
             MutateMode.Init, MutateMode.JustWrite -> {
                 // In a case like `path = 1`, path does not have to be FULLY initialized, but we still
@@ -62,7 +69,45 @@ class CheckLoanContext(private val bccx: BorrowCheckContext, private val moveDat
         }
     }
 
-    override fun useElement(element: RsElement, cmt: Cmt) {}
+    override fun useElement(element: RsElement, cmt: Cmt) {
+        checkMacroUsage(element, cmt)
+    }
+
+    override fun borrow(element: RsElement, cmt: Cmt, kind: BorrowKind) {
+        val loanPath = LoanPath.computeFor(cmt) ?: return
+        if (kind is BorrowKind.MutableBorrow) {
+            loanPath.mutableRoot?.let { bccx.reportUsedMut(it) }
+        }
+        checkMacroUsage(element, cmt)
+    }
+
+    /** Tracks which `mut` variables are actually mutated to find unused `mut` */
+    private fun checkMutability(assignment: RsElement, loanPath: LoanPath, mode: MutateMode) {
+        val kind = loanPath.kind
+        when {
+            mode == MutateMode.Init -> {
+                val binding = (kind as? Var)?.declaration as? RsPatBinding ?: return
+                if (binding.mutability.isMut) bccx.reportMutableDeclaration(binding)
+            }
+
+            // `x = 1` requires `x` to be mutable only if `x` may be already initialized, e.g. `let x; x = 1;` is fine
+            mode == MutateMode.JustWrite && kind is Var && !kind.declaration.isParameter &&
+                !moveData.isMaybeAssigned(assignment, loanPath) -> Unit
+
+            else -> loanPath.mutableRoot?.let { bccx.reportUsedMut(it) }
+        }
+    }
+
+    /** Usages of variables in macro calls can't be analyzed precisely, so `mut` is conservatively kept there */
+    private fun checkMacroUsage(element: RsElement, cmt: Cmt) {
+        if (element.ancestorOrSelf<RsMacroCall>() == null && !element.isExpandedFromMacro) return
+        val loanPath = LoanPath.computeFor(cmt) ?: return
+        bccx.reportUsedMut(loanPath.rootVariable)
+    }
+
+    /** Function parameters are assigned at the function entry, which is not tracked by data-flow */
+    private val RsElement.isParameter: Boolean
+        get() = ancestorStrict<RsValueParameter>() != null
 
     fun checkLoans(body: RsBlock) {
         val mc = MemoryCategorizationContext(bccx.implLookup, bccx.inference)
@@ -0,0 +1,84 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.types.borrowck
+
+import org.rust.lang.core.psi.RsLambdaExpr
+import org.rust.lang.core.psi.RsPat
+import org.rust.lang.core.psi.RsPatBinding
+import org.rust.lang.core.psi.ext.RsElement
+import org.rust.lang.core.psi.ext.isAncestorOf
+import org.rust.lang.core.resolve.ImplLookup
+import org.rust.lang.core.types.infer.BorrowKind
+import org.rust.lang.core.types.infer.BorrowKind.MutableBorrow
+import org.rust.lang.core.types.infer.Categorization
+import org.rust.lang.core.types.infer.Cmt
+import org.rust.lang.core.types.infer.MemoryCategorizationContext
+import org.rust.lang.core.types.infer.PointerKind
+import org.rust.lang.core.types.inference
+
+/** The most general `Fn` trait implemented by a closure, like `rustc::ty::ClosureKind` */
+enum class ClosureKind(val traitName: String) {
+    Fn("Fn"),
+    FnMut("FnMut"),
+    FnOnce("FnOnce")
+}
+
+/**
+ * A closure is [ClosureKind.FnOnce] if it moves a captured variable out, [ClosureKind.FnMut] if it mutates
+ * or mutably borrows a captured variable, and [ClosureKind.Fn] otherwise
+ */
+val RsLambdaExpr.closureKind: ClosureKind
+    get() {
+        val inference = inference ?: return ClosureKind.Fn
+        val delegate = ClosureKindDelegate(this)
+        val mc = MemoryCategorizationContext(ImplLookup.relativeTo(this), inference)
+        ExprUseWalker(delegate, mc).consumeLambdaBody(this)
+        return delegate.kind
+    }
+
+private class ClosureKindDelegate(private val lambda: RsLambdaExpr) : Delegate {
+    var kind: ClosureKind = ClosureKind.Fn
+        private set
+
+    override fun consume(element: RsElement, cmt: Cmt, mode: ConsumeMode) {
+        if (mode is ConsumeMode.Move && cmt.isCapturedPlace(throughMutableDeref = false)) kind = ClosureKind.FnOnce
+    }
+
+    override fun matchedPat(pat: RsPat, cmt: Cmt, mode: MatchMode) {}
+
+    override fun consumePat(pat: RsPat, cmt: Cmt, mode: ConsumeMode) = consume(pat, cmt, mode)
+
+    override fun declarationWithoutInit(binding: RsPatBinding) {}
+
+    override fun mutate(assignmentElement: RsElement, assigneeCmt: Cmt, mode: MutateMode) = markMutated(assigneeCmt)
+
+    override fun useElement(element: RsElement, cmt: Cmt) {}
+
+    override fun borrow(element: RsElement, cmt: Cmt, kind: BorrowKind) {
+        if (kind is MutableBorrow) markMutated(cmt)
+    }
+
+    private fun markMutated(cmt: Cmt) {
+        if (kind == ClosureKind.Fn && cmt.isCapturedPlace(throughMutableDeref = true)) kind = ClosureKind.FnMut
+    }
+
+    /**
+     * Checks if [this] is a captured variable or its part, e.g. `x.0`.
+     * Writing through a captured `&mut` reference requires a unique borrow of the reference,
+     * so [throughMutableDeref] places like `*x` are considered captured too
+     */
+    private fun Cmt.isCapturedPlace(throughMutableDeref: Boolean): Boolean = when (val category = category) {
+        is Categorization.Local -> !lambda.isAncestorOf(category.declaration)
+        is Categorization.Interior -> category.cmt.isCapturedPlace(throughMutableDeref)
+        is Categorization.Downcast -> category.cmt.isCapturedPlace(throughMutableDeref)
+        is Categorization.Deref -> {
+            val pointerKind = category.pointerKind
+            throughMutableDeref && pointerKind is PointerKind.BorrowedPointer &&
+                pointerKind.borrowKind is MutableBorrow && category.cmt.isCapturedPlace(throughMutableDeref)
+        }
+        else -> false
+    }
+}
@@ -17,6 +17,9 @@ import org.rust.lang.core.types.borrowck.ConsumeMode.Move
 import org.rust.lang.core.types.borrowck.MatchMode.*
 import org.rust.lang.core.types.borrowck.MoveReason.DirectRefMove
 import org.rust.lang.core.types.borrowck.MoveReason.PatBindingMove
+import org.rust.lang.core.types.infer.BorrowKind
+import org.rust.lang.core.types.infer.BorrowKind.ImmutableBorrow
+import org.rust.lang.core.types.infer.BorrowKind.MutableBorrow
 import org.rust.lang.core.types.infer.Categorization.Interior
 import org.rust.lang.core.types.infer.Categorization.Local
 import org.rust.lang.core.types.infer.Cmt
@@ -27,6 +30,7 @@ import org.rust.lang.core.types.regions.ReScope
 import org.rust.lang.core.types.regions.Scope
 import org.rust.lang.core.types.ty.TyAdt
 import org.rust.lang.core.types.ty.TyFunction
+import org.rust.lang.core.types.ty.TyReference
 import org.rust.lang.core.types.ty.TyUnknown
 import org.rust.lang.core.types.ty.isMovesByDefault
 import org.rust.lang.core.types.type
@@ -53,6 +57,9 @@ interface Delegate {
     fun mutate(assignmentElement: RsElement, assigneeCmt: Cmt, mode: MutateMode)
 
     fun useElement(element: RsElement, cmt: Cmt)
+
+    /** The value found at [cmt] is being borrowed by [element], e.g. `&mut x` or autoref of `x` in `x.push(1)` */
+    fun borrow(element: RsElement, cmt: Cmt, kind: BorrowKind)
 }
 
 sealed class ConsumeMode {
@@ -131,6 +138,10 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
         walkBlock(body)
     }
 
+    fun consumeLambdaBody(lambda: RsLambdaExpr) {
+        lambda.expr?.let { consumeExpr(it) }
+    }
+
     private fun delegateConsume(element: RsElement, cmt: Cmt) {
         val mode = copyOrMove(mc, cmt, DirectRefMove)
         delegate.consume(element, cmt, mode)
@@ -180,12 +191,42 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
         }
     }
 
-    private fun mutateExpr(assignmentExpr: RsExpr, expr: RsExpr, mode: MutateMode) {
+    private fun mutateExpr(assignmentElement: RsElement, expr: RsExpr, mode: MutateMode) {
         val cmt = mc.processExpr(expr)
-        delegate.mutate(assignmentExpr, cmt, mode)
+        delegate.mutate(assignmentElement, cmt, mode)
         walkExpr(expr)
     }
 
+    private fun borrowExpr(element: RsElement, expr: RsExpr, kind: BorrowKind) {
+        val cmt = mc.processExpr(expr)
+        delegate.borrow(element, cmt, kind)
+    }
+
+    /**
+     * Reports autoref of the method call receiver, e.g. `&mut v` in `v.push(1)`.
+     * The receiver of an unresolved or ambiguous method is considered mutably borrowed, so `mut` isn't reported
+     * as unnecessary if we can't be sure
+     */
+    private fun walkAutoref(receiver: RsExpr, methodCall: RsMethodCall) {
+        val method = mc.inference.getResolvedMethod(methodCall).singleOrNull()
+        if (method == null) {
+            val cmt = mc.processExpr(receiver)
+            if (cmt.ty is TyReference) return
+            delegate.borrow(receiver, cmt, MutableBorrow)
+            return
+        }
+        val selfParameter = method.element.selfParameter ?: return
+        val mutability = when {
+            selfParameter.isExplicitType -> (selfParameter.typeReference?.type as? TyReference)?.mutability ?: return
+            selfParameter.isRef -> selfParameter.mutability
+            else -> return
+        }
+        val cmt = mc.processExprWithAutoderef(receiver, method.derefCount)
+        // `&mut self` method called on `&mut` reference just reborrows it
+        if (cmt.ty is TyReference) return
+        delegate.borrow(receiver, cmt, BorrowKind.from(mutability))
+    }
+
     private fun selectFromExpr(expr: RsExpr) {
         walkExpr(expr)
         useAllPaths(expr)
@@ -197,7 +238,10 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
                 val base = expr.expr ?: return
                 when {
                     expr.mul != null -> selectFromExpr(base) // `*foo`
-                    expr.and != null -> selectFromExpr(base) // `&foo`
+                    expr.and != null -> { // `&foo`
+                        borrowExpr(expr, base, if (expr.mut != null) MutableBorrow else ImmutableBorrow)
+                        selectFromExpr(base)
+                    }
                     else -> consumeExpr(base) // `-foo`, `!foo`, ...
                 }
             }
@@ -210,6 +254,7 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
                 if (fieldLookup != null) {
                     selectFromExpr(base)
                 } else if (methodCall != null) {
+                    walkAutoref(base, methodCall)
                     selectFromExpr(base)
                     consumeExprs(methodCall.valueArgumentList.exprList)
                 }
@@ -285,6 +330,8 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
 
             is RsLambdaExpr -> expr.expr?.let { walkExpr(it) }
 
+            is RsLetExpr -> walkLetExpr(expr)
+
             is RsBlockExpr -> walkBlock(expr.block)
 
             is RsBreakExpr -> expr.expr?.let { consumeExpr(it) }
@@ -306,6 +353,11 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
     }
 
     private fun walkCallee(callee: RsExpr) {
+        // We don't know whether a closure stored in a variable is `Fn` or `FnMut`,
+        // so the variable is conservatively considered mutably borrowed by the call
+        val calleeCmt = mc.processExpr(callee)
+        if (calleeCmt.category is Local) delegate.borrow(callee, calleeCmt, MutableBorrow)
+
         when (callee.type) {
             is TyFunction -> consumeExpr(callee)
             else -> useAllPaths(callee)
@@ -338,6 +390,8 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
                 argument.formatMacroArgList.map { it.expr }.forEach(::walkExpr)
             }
 
+            is RsAsmMacroArgument -> walkAsmMacroArgument(argument)
+
             is RsMacroArgument -> {
                 val expansion = macroCall.expansion
                 if (expansion != null) {
@@ -357,6 +411,21 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
         nestedMacroCallsCount--
     }
 
+    private fun walkAsmMacroArgument(argument: RsAsmMacroArgument) {
+        argument.exprList.forEach(::walkExpr)
+        for (operand in argument.asmOperandList) {
+            val input = operand.inputExpr
+            val output = operand.outputExpr
+            if (output != null && output == input) {
+                // `inout(reg) x`
+                mutateExpr(operand, output, MutateMode.WriteAndRead)
+            } else {
+                input?.let(::consumeExpr)
+                output?.let { mutateExpr(operand, it, MutateMode.JustWrite) }
+            }
+        }
+    }
+
     private fun walkStmt(stmt: RsStmt) {
         when (stmt) {
             is RsLetDecl -> walkLet(stmt)
@@ -371,6 +440,7 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
             walkExpr(init)
             val initCmt = mc.processExpr(init)
             walkIrrefutablePat(initCmt, pat)
+            declaration.elseBlock?.let { walkBlock(it) }
         } else {
             for (binding in pat.descendantsOfType<RsPatBinding>()) {
                 delegate.declarationWithoutInit(binding)
@@ -379,10 +449,14 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
     }
 
     private fun walkCondition(condition: RsCondition) {
-        val init = condition.expr
+        walkExpr(condition.expr)
+    }
+
+    private fun walkLetExpr(expr: RsLetExpr) {
+        val init = expr.expr ?: return
         walkExpr(init)
         val initCmt = mc.processExpr(init)
-        for (pat in condition.patList) {
+        for (pat in expr.patList) {
             walkIrrefutablePat(initCmt, pat)
         }
     }
@@ -485,11 +559,14 @@ class ExprUseWalker(private val delegate: Delegate, private val mc: MemoryCatego
             delegate.mutate(subPat, bindingCmt, MutateMode.Init)
 
             // It is also a borrow or copy/move of the value being matched.
-            if (binding.kind is BindByValue) {
-                // In case of NonConsumingMatch (e.g. `for x in xs {}`), the pat should not be consumed as copy/move,
-                // but should be consumed as usage
-                if (matchMode != NonConsumingMatch || delegate is GatherLivenessContext) {
-                    delegate.consumePat(subPat, subPatCmt, copyOrMove(mc, subPatCmt, PatBindingMove))
+            when (val kind = binding.kind) {
+                is BindByReference -> delegate.borrow(subPat, subPatCmt, BorrowKind.from(kind.mutability))
+                is BindByValue -> {
+                    // In case of NonConsumingMatch (e.g. `for x in xs {}`), the pat should not be consumed as copy/move,
+                    // but should be consumed as usage
+                    if (matchMode != NonConsumingMatch || delegate is GatherLivenessContext) {
+                        delegate.consumePat(subPat, subPatCmt, copyOrMove(mc, subPatCmt, PatBindingMove))
+                    }
                 }
             }
         }
@@ -15,6 +15,8 @@ import org.rust.lang.core.types.infer.MutabilityCategory
 import org.rust.lang.core.types.infer.PointerKind
 import org.rust.lang.core.types.regions.Scope
 import org.rust.lang.core.types.ty.Ty
+import org.rust.lang.core.types.ty.TyPointer
+import org.rust.lang.core.types.ty.TyReference
 import java.util.*
 
 data class LoanPath(val kind: LoanPathKind, val ty: Ty, val element: RsElement) {
@@ -38,6 +40,29 @@ data class LoanPath(val kind: LoanPathKind, val ty: Ty, val element: RsElement)
             is Extend -> kind.loanPath.killScope(bccx)
         }
 
+    /** The variable this path is based on, e.g. `a` for `(*a).b` */
+    val rootVariable: RsElement
+        get() = when (kind) {
+            is Var -> kind.declaration
+            is Downcast -> kind.loanPath.rootVariable
+            is Extend -> kind.loanPath.rootVariable
+        }
+
+    /**
+     * The variable that must be declared as `mut` to mutate this path, e.g. `a` for `a.b[0]` or for `*a` if `a` is a box.
+     * `null` if the path is mutated through a reference or a raw pointer, e.g. `(*a).b` where `a` is `&mut S`
+     */
+    val mutableRoot: RsElement?
+        get() = when (kind) {
+            is Var -> kind.declaration
+            is Downcast -> kind.loanPath.mutableRoot
+            is Extend -> {
+                val baseTy = kind.loanPath.ty
+                val isPointerDeref = kind.lpElement is Deref && (baseTy is TyReference || baseTy is TyPointer)
+                if (isPointerDeref) null else kind.loanPath.mutableRoot
+            }
+        }
+
     companion object {
         fun computeFor(cmt: Cmt): LoanPath? {
             fun loanPath(kind: LoanPathKind): LoanPath = LoanPath(kind, cmt.ty, cmt.element)
@@ -250,7 +250,7 @@ class MoveData(
 class FlowedMoveData private constructor(
     private val moveData: MoveData,
     private val dfcxMoves: MoveDataFlow,
-    private val dfcxAssign: AssignDataFlow // will be used during borrow checking
+    private val dfcxAssign: AssignDataFlow
 ) {
     fun eachMoveOf(element: RsElement, loanPath: LoanPath, predicate: (Move, LoanPath) -> Boolean): Boolean {
         // Bad scenarios:
@@ -285,6 +285,21 @@ class FlowedMoveData private constructor(
         }
     }
 
+    /**
+     * Returns `true` if some assignment (including initialization) of the variable [loanPath]
+     * may reach [element], i.e. assigning the variable at [element] may be a reassignment
+     */
+    fun isMaybeAssigned(element: RsElement, loanPath: LoanPath): Boolean {
+        // Conservatively consider the variable assigned if the element is not a part of the control flow graph
+        if (!dfcxAssign.hasBitSetForElement(element)) return true
+        var isAssigned = false
+        dfcxAssign.eachBitOnEntry(element) { index ->
+            if (moveData.varAssignments[index].path.loanPath == loanPath) isAssigned = true
+            !isAssigned
+        }
+        return isAssigned
+    }
+
     companion object {
         fun buildFor(moveData: MoveData, bccx: BorrowCheckContext, cfg: ControlFlowGraph): FlowedMoveData {
             val dfcxMoves = DataFlowContext(cfg, MoveDataFlowOperator, moveData.moves.size, FlowDirection.Forward)
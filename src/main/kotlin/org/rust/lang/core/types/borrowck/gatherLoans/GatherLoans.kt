@@ -9,6 +9,7 @@ import org.rust.lang.core.psi.RsPat
 import org.rust.lang.core.psi.RsPatBinding
 import org.rust.lang.core.psi.ext.RsElement
 import org.rust.lang.core.types.borrowck.*
+import org.rust.lang.core.types.infer.BorrowKind
 import org.rust.lang.core.types.infer.Cmt
 import org.rust.lang.core.types.infer.MemoryCategorizationContext
 import org.rust.lang.core.types.type
@@ -36,6 +37,8 @@ class GatherLoanContext(private val bccx: BorrowCheckContext, private val moveDa
 
     override fun useElement(element: RsElement, cmt: Cmt) {}
 
+    override fun borrow(element: RsElement, cmt: Cmt, kind: BorrowKind) {}
+
     /** Guarantees that [cmt] is assignable, or reports an error */
     private fun guaranteeAssignmentValid(assignment: RsElement, cmt: Cmt, mode: MutateMode) {
         // `loanPath` may be null with e.g. `*foo() = 5`
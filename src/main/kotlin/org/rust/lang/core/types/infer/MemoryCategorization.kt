@@ -216,6 +216,13 @@ class MemoryCategorizationContext(val lookup: ImplLookup, val inference: RsInfer
         return processExprAdjustedWith(expr, adjustments.asReversed().iterator())
     }
 
+    /** Categorizes [expr] dereferenced [derefCount] times, e.g. a method call receiver after autoderef */
+    fun processExprWithAutoderef(expr: RsExpr, derefCount: Int): Cmt {
+        var cmt = processExpr(expr)
+        repeat(derefCount) { cmt = processDeref(expr, cmt) }
+        return cmt
+    }
+
     private fun processExprAdjustedWith(expr: RsExpr, adjustments: Iterator<Adjustment>): Cmt {
         return when (val adjustment = adjustments.nextOrNull()) {
             is Adjustment.Deref -> {
@@ -365,6 +372,8 @@ class MemoryCategorizationContext(val lookup: ImplLookup, val inference: RsInfer
                 val elementCmt = cmtOfSliceElement(pat, cmt)
                 pat.patList.forEach { walkPat(elementCmt, it, callback) }
             }
+
+            is RsPatOr -> pat.patList.forEach { walkPat(cmt, it, callback) }
         }
     }
 
@@ -112,6 +112,10 @@ fun RsPat.extractBindings(fcx: RsTypeInferenceWalker, type: Ty, defBm: RsBinding
             fcx.writePatTy(this, expected)
             inferSlicePatsTypes(fcx, patList, bm, expected)
         }
+        is RsPatOr -> {
+            fcx.writePatTy(this, type)
+            patList.forEach { it.extractBindings(fcx, type, defBm) }
+        }
         is RsPatBox -> {
             val (expected, bm) = type.stripReferences(defBm)
             fcx.writePatTy(this, expected)
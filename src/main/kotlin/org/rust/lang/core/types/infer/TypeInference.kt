@@ -54,6 +54,7 @@ interface RsInferenceData {
     fun getPatType(pat: RsPat): Ty
     fun getPatFieldType(patField: RsPatField): Ty
     fun getResolvedPath(expr: RsPathExpr): List<ResolvedPath>
+    fun getResolvedMethod(call: RsMethodCall): List<MethodResolveVariant>
     fun getBindingType(binding: RsPatBinding): Ty =
         when (val parent = binding.parent) {
             is RsPat -> getPatType(parent)
@@ -101,7 +102,7 @@ class RsInferenceResult(
     override fun getResolvedPath(expr: RsPathExpr): List<ResolvedPath> =
         resolvedPaths[expr] ?: emptyList()
 
-    fun getResolvedMethod(call: RsMethodCall): List<MethodResolveVariant> =
+    override fun getResolvedMethod(call: RsMethodCall): List<MethodResolveVariant> =
         resolvedMethods[call] ?: emptyList()
 
     fun getResolvedField(call: RsFieldLookup): List<RsElement> =
@@ -290,6 +291,10 @@ class RsInferenceContext(
         return adjustments[expr] ?: emptyList()
     }
 
+    override fun getResolvedMethod(call: RsMethodCall): List<MethodResolveVariant> {
+        return resolvedMethods[call] ?: emptyList()
+    }
+
     override fun getExprType(expr: RsExpr): Ty {
         return exprTypes[expr] ?: TyUnknown
     }
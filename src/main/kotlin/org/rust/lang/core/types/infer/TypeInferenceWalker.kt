@@ -145,6 +145,14 @@ class RsTypeInferenceWalker(
                 TyUnknown to TyInfer.TyVar()
             }
             psi.pat?.extractBindings(explicitTy ?: resolveTypeVarsWithObligations(coercedInferredTy))
+            // let Some(a) = ... else { return; };
+            val elseBlock = psi.elseBlock
+            if (elseBlock != null) {
+                val elseTy = resolveTypeVarsWithObligations(elseBlock.inferType(TyNever))
+                if (elseTy != TyNever && elseTy !is TyUnknown) {
+                    ctx.addDiagnostic(RsDiagnostic.LetElseNotDivergingError(elseBlock, elseTy))
+                }
+            }
             inferredTy == TyNever
         }
         is RsExprStmt -> psi.expr.inferType() == TyNever
@@ -180,6 +188,7 @@ class RsTypeInferenceWalker(
             is RsMatchExpr -> inferMatchExprType(this, expected)
             is RsUnaryExpr -> inferUnaryExprType(this, expected)
             is RsBinaryExpr -> inferBinaryExprType(this)
+            is RsLetExpr -> inferLetExprType(this)
             is RsTryExpr -> inferTryExprType(this)
             is RsArrayExpr -> inferArrayType(this, expected)
             is RsRangeExpr -> inferRangeType(this)
@@ -916,18 +925,18 @@ class RsTypeInferenceWalker(
     }
 
     private fun RsCondition.inferTypes() {
-        val orPats = orPats
-        if (orPats != null) {
-            // if let Some(a) = ... {}
-            // if let V1(a) | V2(a) = ... {}
-            // or
-            // while let Some(a) = ... {}
-            // while let V1(a) | V2(a) = ... {}
-            val exprTy = resolveTypeVarsWithObligations(expr.inferType())
-            orPats.extractBindings(exprTy)
-        } else {
-            expr.inferType(TyBool)
-        }
+        expr.inferType(TyBool)
+    }
+
+    private fun inferLetExprType(expr: RsLetExpr): Ty {
+        // if let Some(a) = ... {}
+        // if let V1(a) | V2(a) = ... {}
+        // if let Some(a) = ... && let Some(b) = a {}
+        // or
+        // while let Some(a) = ... {}
+        val exprTy = resolveTypeVarsWithObligations(expr.expr?.inferType() ?: TyUnknown)
+        expr.orPats?.extractBindings(exprTy)
+        return TyBool
     }
 
     private fun inferBinaryExprType(expr: RsBinaryExpr): Ty {
@@ -1131,7 +1140,7 @@ class RsTypeInferenceWalker(
         val name = macroCall.macroName
         val exprArg = macroCall.exprMacroArgument
         if (exprArg != null) {
-            val type = exprArg.expr?.inferType() ?: return TyUnknown
+            val type = exprArg.expr?.inferType() ?: return if (name == "dbg") TyUnit else TyUnknown
             return when (name) {
                 "try" -> inferTryMacroArgumentType(type)
                 "dbg" -> type
@@ -1170,6 +1179,7 @@ class RsTypeInferenceWalker(
             macroCall.assertMacroArgument != null || macroCall.logMacroArgument != null -> TyUnit
             macroCall.formatMacroArgument != null -> inferFormatMacro(macroCall)
             macroCall.includeMacroArgument != null -> inferIncludeMacro(macroCall)
+            macroCall.asmMacroArgument != null -> inferAsmMacro(macroCall)
             name == "env" -> TyReference(TyStr, Mutability.IMMUTABLE)
             name == "option_env" -> items.findOptionForElementTy(TyReference(TyStr, Mutability.IMMUTABLE))
             name == "concat" -> TyReference(TyStr, Mutability.IMMUTABLE)
@@ -1178,10 +1188,20 @@ class RsTypeInferenceWalker(
             name == "stringify" -> TyReference(TyStr, Mutability.IMMUTABLE)
             name == "module_path" -> TyReference(TyStr, Mutability.IMMUTABLE)
             name == "cfg" -> TyBool
-            else -> (macroCall.expansion as? MacroExpansion.Expr)?.expr?.inferType() ?: TyUnknown
+            else -> (macroCall.expansion as? MacroExpansion.Expr)?.expr?.inferType() ?: inferUnexpandedStdMacro(name)
         }
     }
 
+    /** Used for well-known std macros if they can't be expanded (e.g. if macro expansion is disabled) */
+    private fun inferUnexpandedStdMacro(name: String): Ty = when (name) {
+        "matches" -> TyBool
+        "assert_matches", "debug_assert_matches" -> TyUnit
+        else -> TyUnknown
+    }
+
+    private fun inferAsmMacro(macroCall: RsMacroCall): Ty =
+        if ("noreturn" in macroCall.asmMacroArgument?.options.orEmpty()) TyNever else TyUnit
+
     private fun inferIncludeMacro(macroCall: RsMacroCall): Ty {
         return when (macroCall.macroName) {
             "include_str" -> TyReference(TyStr, Mutability.IMMUTABLE)
@@ -1196,7 +1216,7 @@ class RsTypeInferenceWalker(
             "print" in name -> TyUnit
             name == "format" -> items.String.asTy()
             name == "format_args" -> items.Arguments.asTy()
-            name == "unimplemented" || name == "unreachable" || name == "panic" -> TyNever
+            name == "unimplemented" || name == "unreachable" || name == "panic" || name == "todo" -> TyNever
             name == "write" || name == "writeln" -> {
                 (macroCall.expansion as? MacroExpansion.Expr)?.expr?.inferType() ?: TyUnknown
             }
@@ -1234,7 +1254,7 @@ class RsTypeInferenceWalker(
 
         val yieldTy = lambdaBodyContext.yieldTy
         return if (yieldTy == null) {
-            TyFunction(paramTypes, if (expr.isAsync) items.makeFuture(actualRetTy) else actualRetTy)
+            TyFunction(paramTypes, if (expr.isAsync) items.makeFuture(actualRetTy) else actualRetTy, expr)
         } else {
             items.makeGenerator(yieldTy, actualRetTy)
         }
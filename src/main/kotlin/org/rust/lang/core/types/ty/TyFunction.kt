@@ -5,14 +5,27 @@
 
 package org.rust.lang.core.types.ty
 
+import org.rust.lang.core.psi.RsLambdaExpr
 import org.rust.lang.core.types.infer.TypeFolder
 import org.rust.lang.core.types.infer.TypeVisitor
 import org.rust.lang.core.types.mergeFlags
 
 data class TyFunction(val paramTypes: List<Ty>, val retType: Ty) : Ty(mergeFlags(paramTypes) or retType.flags) {
 
+    /**
+     * The closure expression this type is inferred from, `null` for function pointers.
+     * It is used only for presentation, and it isn't a part of [equals], so closure types are still
+     * unified with function pointer types
+     */
+    var closure: RsLambdaExpr? = null
+        private set
+
+    constructor(paramTypes: List<Ty>, retType: Ty, closure: RsLambdaExpr?) : this(paramTypes, retType) {
+        this.closure = closure
+    }
+
     override fun superFoldWith(folder: TypeFolder): Ty =
-        TyFunction(paramTypes.map { it.foldWith(folder) }, retType.foldWith(folder))
+        TyFunction(paramTypes.map { it.foldWith(folder) }, retType.foldWith(folder), closure)
 
     override fun superVisitWith(visitor: TypeVisitor): Boolean =
         paramTypes.any { it.visitWith(visitor) } || retType.visitWith(visitor)
@@ -248,6 +248,18 @@ sealed class RsDiagnostic(
         )
     }
 
+    class LetElseNotDivergingError(
+        element: PsiElement,
+        private val actualTy: Ty
+    ) : RsDiagnostic(element, inspectionClass = RsTypeCheckInspection::class.java) {
+        override fun prepare() = PreparedAnnotation(
+            ERROR,
+            E0308,
+            "`else` clause of `let...else` does not diverge",
+            "expected `!`, found `${actualTy.escaped}`"
+        )
+    }
+
     class AccessError(
         element: PsiElement,
         private val errorCode: RsErrorCode,
@@ -937,6 +949,20 @@ sealed class RsDiagnostic(
         )
     }
 
+    class TraitBoundIsNotSatisfied(
+        element: PsiElement,
+        private val ty: Ty,
+        private val traitName: String,
+        private val fixes: List<LocalQuickFix>
+    ) : RsDiagnostic(element) {
+        override fun prepare() = PreparedAnnotation(
+            ERROR,
+            E0277,
+            header = escapeString("the trait bound `$ty: $traitName` is not satisfied"),
+            fixes = fixes
+        )
+    }
+
     class ExperimentalFeature(
         element: PsiElement,
         endElement: PsiElement?,
@@ -29,6 +29,52 @@ fun String.unescapeRust(escapesLexer: RsEscapesLexer): String =
             }
         }
 
+/**
+ * Escapes the string using Rust escaping rules, so it can be used as the value of a (byte) string literal.
+ * Control characters are escaped as `\u{7f}` in strings, and non-ASCII characters are escaped
+ * as UTF-8 bytes (`\xF0\x9F\x98\x80`) in byte strings
+ */
+fun String.escapeRust(isByte: Boolean = false): String {
+    val result = StringBuilder(length)
+    codePoints().forEach { codePoint ->
+        when (codePoint) {
+            '"'.toInt() -> result.append("\\\"")
+            '\\'.toInt() -> result.append("\\\\")
+            '\n'.toInt() -> result.append("\\n")
+            '\r'.toInt() -> result.append("\\r")
+            '\t'.toInt() -> result.append("\\t")
+            0 -> result.append("\\0")
+            else -> when {
+                codePoint >= 0x80 && isByte -> for (byte in String(Character.toChars(codePoint)).toByteArray()) {
+                    result.append("\\x%02X".format(byte.toInt() and 0xFF))
+                }
+                Character.isISOControl(codePoint) && isByte -> result.append("\\x%02x".format(codePoint))
+                Character.isISOControl(codePoint) -> result.append("\\u{%x}".format(codePoint))
+                else -> result.appendCodePoint(codePoint)
+            }
+        }
+    }
+    return result.toString()
+}
+
+/**
+ * The minimal number of `#` in delimiters of a raw string literal with the [value] inside,
+ * e.g. 0 for `foo`, 1 for `"foo"` and 2 for `"#foo"#`
+ */
+fun rawStringHashesCount(value: CharSequence): Int {
+    var result = 0
+    var quote = value.indexOf('"')
+    while (quote != -1) {
+        var hashes = 0
+        while (quote + hashes + 1 < value.length && value[quote + hashes + 1] == '#') {
+            hashes++
+        }
+        result = maxOf(result, hashes + 1)
+        quote = value.indexOf('"', quote + 1)
+    }
+    return result
+}
+
 fun parseRustStringCharacters(chars: String): Triple<StringBuilder, IntArray, Boolean> {
     val outChars = StringBuilder()
     val (offsets, success) = parseRustStringCharacters(chars, outChars)
@@ -59,7 +105,7 @@ private fun decodeEscape(esc: String): String = when (esc) {
         assert(esc[0] == '\\')
         when (esc[1]) {
             'x' -> Integer.parseInt(esc.substring(2), 16).toChar().toString()
-            'u' -> Integer.parseInt(esc.substring(3, esc.length - 1).filter { it != '_' }, 16).toChar().toString()
+            'u' -> String(Character.toChars(Integer.parseInt(esc.substring(3, esc.length - 1).filter { it != '_' }, 16)))
             '\r', '\n' -> ""
             else -> error("unreachable")
         }
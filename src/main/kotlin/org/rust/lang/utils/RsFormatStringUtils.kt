@@ -0,0 +1,94 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.utils
+
+import com.intellij.openapi.util.TextRange
+import org.intellij.lang.annotations.Language
+import org.rust.lang.core.psi.RS_BYTE_STRING_LITERALS
+import org.rust.lang.core.psi.RsElementTypes
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsLiteralKind
+import org.rust.lang.core.psi.ext.startOffset
+import org.rust.lang.core.psi.kind
+
+/**
+ * A `{...}` placeholder (with [innerContentMatch]), an escaped `{{` or `}}`, or an unmatched `}` of a format string.
+ * Ranges are relative to the unescaped value of the string literal
+ */
+data class ParsedFormatParameter(
+    val completeMatch: MatchResult,
+    val innerContentMatch: MatchResult? = null
+) {
+    /** The text between the braces of a placeholder */
+    val innerContent: MatchGroup? = completeMatch.groups[2]
+    val range: IntRange = completeMatch.range
+}
+
+/**
+ * [sourceMap] maps offsets in the unescaped value of the literal to offsets in its raw text,
+ * [offset] is the offset of the raw text in the file
+ */
+data class FormatStringParseContext(
+    val sourceMap: IntArray,
+    val offset: Int,
+    val parameters: List<ParsedFormatParameter>
+) {
+    fun toSourceRange(range: IntRange, additionalOffset: Int = 0): TextRange =
+        TextRange(sourceMap[range.first + additionalOffset], sourceMap[range.last + additionalOffset] + 1)
+            .shiftRight(offset)
+
+    /** Converts the offset in the file to the offset in the unescaped value of the literal */
+    fun toValueOffset(fileOffset: Int): Int {
+        val rawOffset = fileOffset - offset
+        val index = sourceMap.indexOfFirst { it >= rawOffset }
+        return if (index == -1) sourceMap.size else index
+    }
+}
+
+private val formatParser = Regex("""\{\{|}}|(\{([^}]*)}?)|(})""")
+
+@Language("Regexp")
+private const val argument = """([a-zA-Z_][\w+]*|\d+)"""
+private val formatParameterParser = Regex("""(?x) # enable comments
+^(?<id>$argument)?
+(:
+    (.?[\^<>])?[+\-]?\#?
+    0?(?!\$) # negative lookahead to parse 0$ as width and 00$ as zero padding followed by width
+    (?<width>$argument\$|\d+)?
+    (\.(?<precision>$argument\$|\d+|\*))?
+    (?<type>\w?\??)?
+)?\s*""")
+
+/** Splits the format string literal of `format!`-like macros into parameters. Returns `null` for byte strings */
+fun parseFormatString(formatStr: RsLitExpr): FormatStringParseContext? {
+    val literalKind = (formatStr.kind as? RsLiteralKind.String) ?: return null
+    if (literalKind.node.elementType in RS_BYTE_STRING_LITERALS) return null
+
+    val rawTextRange = literalKind.offsets.value ?: return null
+    val text = literalKind.rawValue ?: return null
+    val (unescapedText, sourceMap) = if (literalKind.node.elementType == RsElementTypes.RAW_STRING_LITERAL) {
+        val map = text.indices.toList().toIntArray()
+        text to map
+    } else {
+        val (parsedText, map, _) = parseRustStringCharacters(text)
+        parsedText.toString() to map
+    }
+
+    val arguments = formatParser.findAll(unescapedText)
+    val parsed = arguments.map { arg ->
+        if (arg.groups[1] != null) {
+            val innerContent = arg.groups[2] ?: error(" should not be null because can match empty string")
+            val innerContentMatch = formatParameterParser.find(innerContent.value)
+                ?: error(" should be not null because can match empty string")
+
+            ParsedFormatParameter(arg, innerContentMatch)
+        } else {
+            ParsedFormatParameter(arg)
+        }
+    }.toList()
+
+    return FormatStringParseContext(sourceMap, formatStr.startOffset + rawTextRange.startOffset, parsed)
+}
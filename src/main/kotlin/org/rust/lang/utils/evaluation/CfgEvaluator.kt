@@ -170,12 +170,13 @@ private sealed class CfgPredicate {
             return when {
                 // e.g. `#[cfg(any(foo, bar))]`
                 args != null -> {
-                    val predicates = args.metaItemList.mapNotNull { fromMetaItem(it) }
+                    // Literals are not predicates, e.g. `#[cfg(all("foo"))]` is an error
+                    if (args.litExprList.isNotEmpty()) return Error
+                    val predicates = args.metaItemList.map { fromMetaItem(it) }
                     when (name) {
                         "all" -> All(predicates)
                         "any" -> Any(predicates)
-                        "not" -> Not(predicates.singleOrNull()
-                            ?: Error)
+                        "not" -> Not(predicates.singleOrNull() ?: Error)
                         else -> Error
                     }
                 }
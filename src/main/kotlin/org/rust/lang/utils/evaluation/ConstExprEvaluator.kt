@@ -153,6 +153,9 @@ private fun <T : Ty> simplifyToInteger(expr: ConstExpr<T>): ConstExpr<T> {
                     }
                 left is ConstExpr.Error || right is ConstExpr.Error ->
                     return ConstExpr.Error()
+                // Keep symbolic expressions in a canonical form, so `1 + N` and `N + 1` are equal
+                left is ConstExpr.Value && expr.operator in COMMUTATIVE_OPS ->
+                    return expr.copy(left = right, right = left)
                 else ->
                     return expr.copy(left = left, right = right)
             }
@@ -165,6 +168,9 @@ private fun <T : Ty> simplifyToInteger(expr: ConstExpr<T>): ConstExpr<T> {
     return ConstExpr.Value.Integer(checkedValue, expectedTy) as ConstExpr<T>
 }
 
+private val COMMUTATIVE_OPS: List<ArithmeticOp> =
+    listOf(ArithmeticOp.ADD, ArithmeticOp.MUL, ArithmeticOp.BIT_AND, ArithmeticOp.BIT_OR, ArithmeticOp.BIT_XOR)
+
 private fun Long.validValueOrNull(ty: TyInteger): Long? = takeIf { it in ty.validValuesRange }
 
 // It returns wrong values for large types like `i128` or `usize`, but looks like it's enough for real cases
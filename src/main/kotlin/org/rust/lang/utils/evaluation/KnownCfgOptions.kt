@@ -0,0 +1,55 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.utils.evaluation
+
+import org.rust.cargo.CfgOptions
+
+/**
+ * Configuration options set by the compiler or by Cargo.
+ * See https://doc.rust-lang.org/reference/conditional-compilation.html#set-configuration-options
+ */
+object KnownCfgOptions {
+    /** Options like `#[cfg(unix)]` */
+    val NAME_OPTIONS: List<String> = listOf(
+        "unix",
+        "windows",
+        "test",
+        "debug_assertions",
+        "doc",
+        "doctest",
+        "miri",
+        "proc_macro"
+    )
+
+    /** Options like `#[cfg(target_os = "linux")]` */
+    val NAME_VALUE_OPTIONS: List<String> = listOf(
+        "feature",
+        "panic",
+        "target_arch",
+        "target_endian",
+        "target_env",
+        "target_family",
+        "target_feature",
+        "target_has_atomic",
+        "target_os",
+        "target_pointer_width",
+        "target_vendor"
+    )
+
+    val OPERATORS: List<String> = listOf(
+        "all",
+        "any",
+        "not"
+    )
+
+    /** Returns `true` if [name] is a well-known name option or is set in one of [options] */
+    fun isKnownName(name: String, vararg options: CfgOptions): Boolean =
+        name in NAME_OPTIONS || options.any { name in it.nameOptions }
+
+    /** Returns `true` if [name] is a well-known key of a name-value option or is set in one of [options] */
+    fun isKnownNameValueKey(name: String, vararg options: CfgOptions): Boolean =
+        name in NAME_VALUE_OPTIONS || options.any { name in it.keyValueOptions }
+}
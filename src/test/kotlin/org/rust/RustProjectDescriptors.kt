@@ -80,6 +80,38 @@ open class RustProjectDescriptorBase : LightProjectDescriptor() {
         env = emptyMap(),
         outDirUrl = null
     )
+
+    protected fun externalPackage(
+        contentRoot: String,
+        source: String?,
+        name: String,
+        targetName: String = name,
+        version: String = "0.0.1",
+        origin: PackageOrigin = PackageOrigin.DEPENDENCY,
+        libKind: LibKind = LibKind.LIB,
+        features: List<Feature> = emptyList(),
+        edition: Edition = Edition.EDITION_2015
+    ): Package {
+        return Package(
+            id = "$name $version",
+            contentRootUrl = contentRoot,
+            name = name,
+            version = version,
+            targets = listOf(
+                // don't use `FileUtil.join` here because it uses `File.separator`
+                // which is system dependent although all other code uses `/` as separator
+                Target(source?.let { "$contentRoot/$it" } ?: "", targetName,
+                    TargetKind.Lib(libKind), edition, doctest = true)
+            ),
+            source = source,
+            origin = origin,
+            edition = edition,
+            features = features,
+            cfgOptions = CfgOptions.EMPTY,
+            env = emptyMap(),
+            outDirUrl = null
+        )
+    }
 }
 
 open class WithRustup(private val delegate: RustProjectDescriptorBase) : RustProjectDescriptorBase() {
@@ -138,36 +170,6 @@ open class WithCustomStdlibRustProjectDescriptor(
 }
 
 object WithDependencyRustProjectDescriptor : RustProjectDescriptorBase() {
-    private fun externalPackage(
-        contentRoot: String,
-        source: String?,
-        name: String,
-        targetName: String = name,
-        version: String = "0.0.1",
-        origin: PackageOrigin = PackageOrigin.DEPENDENCY,
-        libKind: LibKind = LibKind.LIB
-    ): Package {
-        return Package(
-            id = "$name $version",
-            contentRootUrl = contentRoot,
-            name = name,
-            version = version,
-            targets = listOf(
-                // don't use `FileUtil.join` here because it uses `File.separator`
-                // which is system dependent although all other code uses `/` as separator
-                Target(source?.let { "$contentRoot/$it" } ?: "", targetName,
-                    TargetKind.Lib(libKind), Edition.EDITION_2015, doctest = true)
-            ),
-            source = source,
-            origin = origin,
-            edition = Edition.EDITION_2015,
-            features = emptyList(),
-            cfgOptions = CfgOptions.EMPTY,
-            env = emptyMap(),
-            outDirUrl = null
-        )
-    }
-
     override fun setUp(fixture: CodeInsightTestFixture) {
         val root = fixture.findFileInTempDir(".")!!
         for (source in listOf("dep-lib/lib.rs", "trans-lib/lib.rs")) {
@@ -185,10 +187,13 @@ object WithDependencyRustProjectDescriptor : RustProjectDescriptorBase() {
             externalPackage("$contentRoot/dep-lib-new", "lib.rs", "dep-lib", "dep-lib-target",
                 version = "0.0.2", origin = PackageOrigin.TRANSITIVE_DEPENDENCY),
             externalPackage("$contentRoot/dep-proc-macro", "lib.rs", "dep-proc-macro", libKind = LibKind.PROC_MACRO),
-            externalPackage("$contentRoot/dep-lib-2", "lib.rs", "dep-lib-2", "dep-lib-target-2"),
+            externalPackage("$contentRoot/dep-lib-2", "lib.rs", "dep-lib-2", "dep-lib-target-2",
+                features = listOf(Feature("foo", FeatureState.Disabled), Feature("bar", FeatureState.Disabled))),
             externalPackage("$contentRoot/trans-lib-2", "lib.rs", "trans-lib-2",
                 origin = PackageOrigin.TRANSITIVE_DEPENDENCY),
-            externalPackage("$contentRoot/no-source-lib", "lib.rs", "no-source-lib").copy(source = null)
+            externalPackage("$contentRoot/no-source-lib", "lib.rs", "no-source-lib").copy(source = null),
+            externalPackage("$contentRoot/trans-proc-macro", "lib.rs", "trans-proc-macro",
+                origin = PackageOrigin.TRANSITIVE_DEPENDENCY, libKind = LibKind.PROC_MACRO)
         )
 
         return CargoWorkspace.deserialize(Paths.get("/my-crate/Cargo.toml"), CargoWorkspaceData(packages, mapOf(
@@ -200,10 +205,11 @@ object WithDependencyRustProjectDescriptor : RustProjectDescriptorBase() {
                 Dependency(packages[6].id),
                 Dependency(packages[8].id)
             ),
-            // dep_lib 0.0.1 depends on trans-lib and dep_lib 0.0.2
+            // dep_lib 0.0.1 depends on trans-lib, dep_lib 0.0.2 and trans-proc-macro
             packages[1].id to setOf(
                 Dependency(packages[3].id),
-                Dependency(packages[4].id)
+                Dependency(packages[4].id),
+                Dependency(packages[10].id)
             ),
             // trans-lib depends on trans-lib-2
             packages[3].id to setOf(
@@ -212,3 +218,43 @@ object WithDependencyRustProjectDescriptor : RustProjectDescriptorBase() {
         )), CfgOptions.DEFAULT)
     }
 }
+
+/** The test package that depends on proc-macro crates with well-known helper attributes, e.g. `serde_derive` */
+object WithKnownDeriveCratesRustProjectDescriptor : RustProjectDescriptorBase() {
+    override fun setUp(fixture: CodeInsightTestFixture) {
+        val root = fixture.findFileInTempDir(".")!!
+        for (source in listOf("serde_derive/lib.rs", "clap_derive/lib.rs")) {
+            VfsTestUtil.createFile(root, source)
+        }
+    }
+
+    override fun testCargoProject(module: Module, contentRoot: String): CargoWorkspace {
+        val packages = listOf(
+            testCargoPackage(contentRoot),
+            externalPackage("$contentRoot/serde_derive", "lib.rs", "serde_derive", libKind = LibKind.PROC_MACRO),
+            externalPackage("$contentRoot/clap_derive", "lib.rs", "clap_derive", libKind = LibKind.PROC_MACRO)
+        )
+        return CargoWorkspace.deserialize(Paths.get("/my-crate/Cargo.toml"), CargoWorkspaceData(packages, mapOf(
+            packages[0].id to setOf(Dependency(packages[1].id), Dependency(packages[2].id))
+        )), CfgOptions.DEFAULT)
+    }
+}
+
+/** The test package of edition 2015 that depends on `dep-lib` of edition 2018 */
+object WithEdition2018DependencyRustProjectDescriptor : RustProjectDescriptorBase() {
+    override fun setUp(fixture: CodeInsightTestFixture) {
+        val root = fixture.findFileInTempDir(".")!!
+        VfsTestUtil.createFile(root, "dep-lib/lib.rs")
+    }
+
+    override fun testCargoProject(module: Module, contentRoot: String): CargoWorkspace {
+        val packages = listOf(
+            testCargoPackage(contentRoot),
+            externalPackage("$contentRoot/dep-lib", "lib.rs", "dep-lib", "dep-lib-target",
+                edition = Edition.EDITION_2018)
+        )
+        return CargoWorkspace.deserialize(Paths.get("/my-crate/Cargo.toml"), CargoWorkspaceData(packages, mapOf(
+            packages[0].id to setOf(Dependency(packages[1].id))
+        )), CfgOptions.DEFAULT)
+    }
+}
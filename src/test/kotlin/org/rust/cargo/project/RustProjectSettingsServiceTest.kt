@@ -25,11 +25,14 @@ class RustProjectSettingsServiceTest : LightPlatformTestCase() {
         val text = """
             <RustProjectSettings>
               <option name="autoUpdateEnabled" value="false" />
+              <option name="buildTarget" value="wasm32-unknown-unknown" />
               <option name="compileAllTargets" value="false" />
               <option name="doctestInjectionEnabled" value="false" />
+              <option name="evaluateBuildScripts" value="true" />
               <option name="explicitPathToStdlib" value="/stdlib" />
               <option name="externalLinter" value="Clippy" />
               <option name="externalLinterArguments" value="--no-default-features" />
+              <option name="externalLinterMaybeIncorrectFixes" value="true" />
               <option name="macroExpansionEngine" value="DISABLED" />
               <option name="runExternalLinterOnTheFly" value="true" />
               <option name="runRustfmtOnSave" value="true" />
@@ -52,13 +55,16 @@ class RustProjectSettingsServiceTest : LightPlatformTestCase() {
         assertEquals("/stdlib", service.explicitPathToStdlib)
         assertEquals(true, service.runExternalLinterOnTheFly)
         assertEquals("--no-default-features", service.externalLinterArguments)
+        assertEquals(true, service.externalLinterMaybeIncorrectFixes)
         assertEquals(false, service.compileAllTargets)
         assertEquals(true, service.useOffline)
+        assertEquals(true, service.evaluateBuildScripts)
         assertEquals(MacroExpansionEngine.DISABLED, service.macroExpansionEngine)
         assertEquals(false, service.showTestToolWindow)
         assertEquals(false, service.doctestInjectionEnabled)
         assertEquals(true, service.runRustfmtOnSave)
         assertEquals(true, service.useSkipChildren)
+        assertEquals("wasm32-unknown-unknown", service.buildTarget)
     }
 
     fun `test update from version 1`() {
@@ -74,6 +74,19 @@ class CargoTomlWatcherTest : RsTestBase() {
         watcher.checkNotTriggered(newCreateEvent("prefix_build.rs").second)
     }
 
+    fun `test build script modifications`() {
+        var watchBuildScripts = false
+        val watcher = CargoTomlWatcher({ watchBuildScripts }) { counter += 1 }
+
+        val (buildScript, createEvent) = newCreateEvent("build.rs")
+        watcher.checkTriggered(createEvent)
+        watcher.checkNotTriggered(newChangeEvent(buildScript))
+
+        watchBuildScripts = true
+        watcher.checkTriggered(newChangeEvent(buildScript))
+        watcher.checkNotTriggered(newChangeEvent(newCreateEvent("prefix_build.rs").first))
+    }
+
     fun `test event properties`() {
         val watcher = CargoTomlWatcher { counter += 1 }
         val (binFile, createEvent) = newCreateEvent("src/foo.rs")
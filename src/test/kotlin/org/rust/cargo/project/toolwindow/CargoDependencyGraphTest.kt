@@ -0,0 +1,157 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.project.toolwindow
+
+import org.rust.RsTestBase
+import org.rust.cargo.CfgOptions
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.project.workspace.CargoWorkspace.*
+import org.rust.cargo.project.workspace.CargoWorkspaceData
+import org.rust.cargo.project.workspace.PackageOrigin
+import java.nio.file.Paths
+
+class CargoDependencyGraphTest : RsTestBase() {
+
+    private val graph: CargoDependencyGraph by lazy { CargoDependencyGraph(workspace()) }
+
+    fun `test duplicates`() {
+        val duplicates = graph.duplicates.mapValues { (_, versions) -> versions.map { it.version } }
+        assertEquals(mapOf("syn" to listOf("0.15.44", "1.0.0")), duplicates)
+        assertTrue(graph.isDuplicated(pkg("syn", "1.0.0")))
+        assertFalse(graph.isDuplicated(pkg("quote")))
+    }
+
+    fun `test standard library is excluded`() {
+        assertEquals(listOf("bar", "syn"), graph.dependenciesOf(pkg("foo")).map { it.pkg.name })
+    }
+
+    fun `test inverse tree`() = assertEquals("""
+        syn v0.15.44
+        ├── proc-macro2 v0.4.30
+        │   └── quote v0.6.13
+        │       └── bar v0.1.0
+        │           └── foo v0.1.0
+        └── quote v0.6.13 (*)
+    """.trimIndent() + "\n", graph.renderInverseTree(pkg("syn", "0.15.44")))
+
+    fun `test inverse tree of direct dependency`() = assertEquals("""
+        syn v1.0.0
+        └── foo v0.1.0
+    """.trimIndent() + "\n", graph.renderInverseTree(pkg("syn", "1.0.0")))
+
+    fun `test pulling dependencies`() {
+        val pulling = graph.findPullingDependencies(pkg("syn", "0.15.44"))
+            .map { (member, dependency) -> "${member.name} -> ${dependency.pkg.name}" }
+        assertEquals(listOf("bar -> quote", "foo -> bar"), pulling)
+    }
+
+    fun `test filter`() {
+        val filter = CargoDependencyFilter(graph, "PROC")
+        val accepted = listOf("foo", "bar", "quote", "proc-macro2", "syn")
+            .flatMap { name -> graph.workspace.packages.filter { it.name == name } }
+            .filter { filter.accepts(it) }
+            .map { it.nameWithVersion }
+        assertEquals(listOf("foo v0.1.0", "bar v0.1.0", "quote v0.6.13", "proc-macro2 v0.4.30"), accepted)
+    }
+
+    fun `test empty filter`() {
+        val filter = CargoDependencyFilter(graph, " ")
+        assertTrue(graph.workspace.packages.all { filter.accepts(it) })
+    }
+
+    fun `test enabled features`() {
+        assertEquals(listOf("full", "parsing"), pkg("syn", "1.0.0").enabledFeatures)
+    }
+
+    fun `test dependency declaration in dependencies table`() = checkDeclaration("foo", "syn", """
+        [package]
+        name = "foo"
+
+        [[bin]]
+        name = "syn"
+
+        [dependencies]
+        bar = { path = "bar" }
+        /*declaration*/"syn" = { version = "1.0", features = ["full"] }
+    """)
+
+    fun `test dependency declaration in table header`() = checkDeclaration("bar", "quote", """
+        [package]
+        name = "bar"
+
+        [target.'cfg(unix)'.dependencies]
+        libc = "0.2"
+
+        /*declaration*/[dev-dependencies.quote]
+        version = "0.6"
+    """)
+
+    private fun checkDeclaration(memberName: String, dependencyName: String, manifest: String) {
+        val text = manifest.trimIndent()
+        val expectedOffset = text.indexOf(DECLARATION_MARKER)
+        val dependency = pkg(memberName).dependencies.single { it.pkg.name == dependencyName }
+        assertEquals(expectedOffset, findDependencyDeclarationOffset(text.replace(DECLARATION_MARKER, ""), dependency))
+    }
+
+    private fun pkg(name: String, version: String? = null): Package =
+        graph.workspace.packages.single { it.name == name && (version == null || it.version == version) }
+
+    private fun workspace(): CargoWorkspace {
+        fun pkg(
+            id: String,
+            name: String,
+            version: String,
+            origin: PackageOrigin,
+            features: List<String> = emptyList()
+        ) = CargoWorkspaceData.Package(
+            id = id,
+            contentRootUrl = "/$id",
+            name = name,
+            version = version,
+            targets = listOf(CargoWorkspaceData.Target(
+                crateRootUrl = "/$id/src/lib.rs",
+                name = name.replace('-', '_'),
+                kind = TargetKind.Lib(LibKind.LIB),
+                edition = Edition.EDITION_2018,
+                doctest = true
+            )),
+            source = null,
+            origin = origin,
+            edition = Edition.EDITION_2018,
+            features = features.map { Feature(it, FeatureState.Enabled) } + Feature("extra-traits", FeatureState.Disabled),
+            cfgOptions = CfgOptions.EMPTY,
+            env = emptyMap(),
+            outDirUrl = null
+        )
+
+        val packages = listOf(
+            pkg("foo", "foo", "0.1.0", PackageOrigin.WORKSPACE),
+            pkg("bar", "bar", "0.1.0", PackageOrigin.WORKSPACE),
+            pkg("quote", "quote", "0.6.13", PackageOrigin.DEPENDENCY),
+            pkg("proc-macro2", "proc-macro2", "0.4.30", PackageOrigin.TRANSITIVE_DEPENDENCY),
+            pkg("syn-0", "syn", "0.15.44", PackageOrigin.TRANSITIVE_DEPENDENCY),
+            pkg("syn-1", "syn", "1.0.0", PackageOrigin.DEPENDENCY, listOf("parsing", "full")),
+            pkg("std", "std", "", PackageOrigin.STDLIB)
+        )
+        val dependencies = mapOf(
+            "foo" to setOf(dep("bar"), dep("syn-1"), dep("std")),
+            "bar" to setOf(dep("quote"), dep("std")),
+            "quote" to setOf(dep("proc-macro2"), dep("syn-0")),
+            "proc-macro2" to setOf(dep("syn-0"))
+        )
+        return CargoWorkspace.deserialize(
+            Paths.get("/foo/Cargo.toml"),
+            CargoWorkspaceData(packages, dependencies),
+            CfgOptions.DEFAULT
+        )
+    }
+
+    private fun dep(id: String): CargoWorkspaceData.Dependency = CargoWorkspaceData.Dependency(id)
+
+    companion object {
+        private const val DECLARATION_MARKER: String = "/*declaration*/"
+    }
+}
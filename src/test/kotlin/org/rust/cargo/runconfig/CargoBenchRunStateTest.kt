@@ -0,0 +1,45 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig
+
+import com.intellij.util.execution.ParametersListUtil
+import org.junit.Assert.assertEquals
+import org.junit.Test
+import org.rust.cargo.toolchain.CargoCommandLine
+import java.nio.file.Paths
+
+class CargoBenchRunStateTest {
+    private fun patch(input: String, mode: BenchBaselineMode, baseline: String): List<String> =
+        CargoBenchRunState.patchBaselineArgs(
+            CargoCommandLine("bench", Paths.get("/my-crate"), ParametersListUtil.parse(input)),
+            mode,
+            baseline
+        ).additionalArguments
+
+    @Test
+    fun `no baseline`() {
+        assertEquals(ParametersListUtil.parse("--bench foo -- fib"), patch("--bench foo -- fib", BenchBaselineMode.NONE, "main"))
+        assertEquals(ParametersListUtil.parse("--bench foo -- fib"), patch("--bench foo -- fib", BenchBaselineMode.SAVE, " "))
+    }
+
+    @Test
+    fun `save baseline`() = assertEquals(
+        ParametersListUtil.parse("--bench foo -- fib --save-baseline main"),
+        patch("--bench foo -- fib", BenchBaselineMode.SAVE, "main")
+    )
+
+    @Test
+    fun `compare against baseline without filter`() = assertEquals(
+        ParametersListUtil.parse("--bench foo -- --baseline main"),
+        patch("--bench foo", BenchBaselineMode.COMPARE, "main")
+    )
+
+    @Test
+    fun `baseline from command is overridden`() = assertEquals(
+        ParametersListUtil.parse("--bench foo -- fib --baseline new"),
+        patch("--bench foo -- --save-baseline old fib", BenchBaselineMode.COMPARE, "new")
+    )
+}
@@ -126,3 +126,76 @@ class CargoTestRunStatePatchArgsShowOutputEnableTest {
         )
     }
 }
+
+@RunWith(Parameterized::class)
+class CargoTestRunStateNextestCommandLineTest(
+    private val input: String,
+    private val expected: String
+) {
+    @Test
+    fun `test nextest command line`() {
+        val commandLine = CargoCommandLine("test", Paths.get("/my-crate"), ParametersListUtil.parse(input))
+        val nextestCommandLine = CargoTestRunState.patchNextestCommandLine(commandLine)
+            ?: error("Failed to convert `$input`")
+        assertEquals("nextest", nextestCommandLine.command)
+        assertEquals(ParametersListUtil.parse(expected), nextestCommandLine.additionalArguments)
+        assertEquals("1", nextestCommandLine.environmentVariables.envs["NEXTEST_EXPERIMENTAL_LIBTEST_JSON"])
+    }
+
+    companion object {
+        private const val FORMAT: String = "--no-fail-fast --message-format libtest-json"
+
+        @Parameterized.Parameters(name = "{index}: {0}")
+        @JvmStatic
+        fun data(): Collection<Array<String>> = listOf(
+            arrayOf("", "run $FORMAT"),
+            arrayOf("--no-fail-fast", "run $FORMAT"),
+            arrayOf("--package foo --lib", "run --package foo --lib $FORMAT"),
+            arrayOf("-p foo --test bar --features baz", "run -p foo --test bar --features baz $FORMAT"),
+            arrayOf("--release --profile custom -j 4", "run --release --cargo-profile custom --build-jobs 4 $FORMAT"),
+            arrayOf("foo", "run $FORMAT -E \"test(foo)\""),
+            arrayOf("-- foo bar", "run $FORMAT -E \"test(foo) | test(bar)\""),
+            arrayOf("--package foo --lib -- tests::bar --exact", "run --package foo --lib $FORMAT -E \"test(=tests::bar)\""),
+            arrayOf("-- --nocapture", "run --no-capture $FORMAT"),
+            arrayOf("-- --ignored", "run --run-ignored ignored-only $FORMAT"),
+            arrayOf("-- --include-ignored", "run --run-ignored all $FORMAT"),
+            arrayOf("-- --test-threads 2", "run --test-threads 2 $FORMAT"),
+            arrayOf("-- -Z unstable-options --format=json --show-output", "run $FORMAT")
+        )
+    }
+}
+
+class CargoTestRunStateNextestFallbackTest {
+    private fun patch(input: String, filters: List<String>? = null): CargoCommandLine? =
+        CargoTestRunState.patchNextestCommandLine(
+            CargoCommandLine("test", Paths.get("/my-crate"), ParametersListUtil.parse(input)),
+            filters
+        )
+
+    @Test
+    fun `unsupported options fall back to cargo test`() {
+        assertNull(patch("--doc"))
+        assertNull(patch("--no-run"))
+        assertNull(patch("-- --unknown-libtest-option"))
+    }
+
+    @Test
+    fun `filters override test names`() {
+        assertEquals(
+            ParametersListUtil.parse("run --lib --no-fail-fast --message-format libtest-json -E \"test(=a::b) | test(=c)\""),
+            patch("--lib -- foo", listOf("a::b", "c"))?.additionalArguments
+        )
+    }
+
+    @Test
+    fun `all features and nocapture settings are converted`() {
+        val commandLine = CargoCommandLine("test", Paths.get("/my-crate"), allFeatures = true, nocapture = true)
+        val nextestCommandLine = CargoTestRunState.patchNextestCommandLine(commandLine)!!
+        assertEquals(
+            ParametersListUtil.parse("run --all-features --no-capture --no-fail-fast --message-format libtest-json"),
+            nextestCommandLine.additionalArguments
+        )
+        assertFalse(nextestCommandLine.allFeatures)
+        assertFalse(nextestCommandLine.nocapture)
+    }
+}
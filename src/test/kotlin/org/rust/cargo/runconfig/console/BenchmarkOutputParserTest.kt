@@ -0,0 +1,68 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.console
+
+import org.junit.Assert.assertEquals
+import org.junit.Test
+
+class BenchmarkOutputParserTest {
+
+    @Test
+    fun `criterion output`() = check("""
+        Benchmarking fib 20
+        Benchmarking fib 20: Warming up for 3.0000 s
+        Benchmarking fib 20: Analyzing
+        fib 20                  time:   [26.029 µs 26.251 µs 26.505 µs]
+                                change: [+4.3917% +5.1891% +6.0556%] (p = 0.00 < 0.05)
+                                Performance has regressed.
+        Found 12 outliers among 100 measurements (12.00%)
+          4 (4.00%) high mild
+          8 (8.00%) high severe
+        Benchmarking fib 10: Analyzing
+        fib 10                  time:   [1.0100 µs 1.0200 µs 1.0300 µs]
+                                change: [-2.3917% -1.1891% +0.0556%] (p = 0.07 > 0.05)
+                                No change in performance detected.
+    """, listOf(
+        BenchmarkResult("fib 20", "26.251 µs", "+5.1891%", BenchmarkVerdict.REGRESSED, 12),
+        BenchmarkResult("fib 10", "1.0200 µs", "-1.1891%", BenchmarkVerdict.NO_CHANGE)
+    ))
+
+    @Test
+    fun `criterion output without baseline`() = check("""
+        Benchmarking parse: Analyzing
+        parse                   time:   [10.000 ns 10.100 ns 10.200 ns]
+
+    """, listOf(BenchmarkResult("parse", "10.100 ns")))
+
+    @Test
+    fun `criterion output with long name`() = check("""
+        Benchmarking group/a very long benchmark name: Analyzing
+        group/a very long benchmark name
+                                time:   [1.0000 ms 1.1000 ms 1.2000 ms]
+                                change: [-12.000% -10.000% -8.0000%] (p = 0.00 < 0.05)
+                                Performance has improved.
+    """, listOf(
+        BenchmarkResult("group/a very long benchmark name", "1.1000 ms", "-10.000%", BenchmarkVerdict.IMPROVED)
+    ))
+
+    @Test
+    fun `libtest output`() = check("""
+        running 2 tests
+        test bench_add ... bench:       1,234 ns/iter (+/- 56)
+        test tests::bench_sub ... bench:          12 ns/iter (+/- 1)
+
+        test result: ok. 0 passed; 0 failed; 0 ignored; 2 measured; 0 filtered out
+    """, listOf(
+        BenchmarkResult("bench_add", "1,234 ns/iter (+/- 56)"),
+        BenchmarkResult("tests::bench_sub", "12 ns/iter (+/- 1)")
+    ))
+
+    private fun check(output: String, expected: List<BenchmarkResult>) {
+        val parser = BenchmarkOutputParser()
+        output.trimIndent().lines().forEach { parser.processLine(it) }
+        assertEquals(expected, parser.results)
+    }
+}
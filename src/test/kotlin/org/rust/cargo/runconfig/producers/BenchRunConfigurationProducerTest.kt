@@ -211,6 +211,38 @@ class BenchRunConfigurationProducerTest : RunConfigurationProducerTestBase() {
         checkOnTopLevel<RsFunction>()
     }
 
+    fun `test bench producer works for criterion benchmarks`() {
+        testProject {
+            bench("a", "benches/a.rs", """
+                fn fib(c: &mut Criterion) { c.bench_function("fib", |b| b.iter(|| 0)); }
+                criterion_group!(benches, fib);
+            """)
+            bench("b", "benches/b.rs", """
+                fn fib(c: &mut Criterion) { c.bench_function("fib", |b| b.it/*caret*/er(|| 0)); }
+                fn not_registered(c: &mut Criterion) { c.bench_function("not_registered", |b| b.iter(|| 0)); }
+                criterion_group!(benches, fib);
+            """).open()
+        }
+        checkOnTopLevel<RsFunction>()
+    }
+
+    fun `test bench producer works for criterion group with config`() {
+        testProject {
+            bench("parse", "benches/parse.rs", """
+                fn bench_parse(c: &mut Criterion) {
+                    c.bench_with_input(BenchmarkId::new("parse", 1), &1, |b, i| b.iter(|| *i));
+                    c.bench_function("parse_all", |b| b.it/*caret*/er(|| 0));
+                }
+                criterion_group! {
+                    name = benches;
+                    config = Criterion::default();
+                    targets = bench_parse
+                }
+            """).open()
+        }
+        checkOnTopLevel<RsFunction>()
+    }
+
     fun `test bench producer works for multiple files`() {
         testProject {
             bench("foo", "benches/foo.rs", """
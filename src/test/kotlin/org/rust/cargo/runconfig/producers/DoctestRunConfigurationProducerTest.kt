@@ -0,0 +1,129 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.producers
+
+import com.intellij.execution.actions.ConfigurationContext
+import com.intellij.lang.injection.InjectedLanguageManager
+import com.intellij.psi.PsiElement
+import org.rust.cargo.runconfig.test.DoctestRunConfigurationProducer
+
+class DoctestRunConfigurationProducerTest : RunConfigurationProducerTestBase() {
+    fun `test doctest producer works for function`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                /// ```
+                /// let x = 1;/*caret*/
+                /// ```
+                pub fn foo() {}
+            """).open()
+        }
+        checkOnLeaf()
+    }
+
+    fun `test doctest producer uses impl self type in path`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                mod bar {
+                    pub struct S;
+                    impl S {
+                        /// Creates `S`
+                        ///
+                        /// ```
+                        /// let s = bar::S::new();/*caret*/
+                        /// ```
+                        pub fn new() -> S { S }
+                    }
+                }
+            """).open()
+        }
+        checkOnLeaf()
+    }
+
+    fun `test doctest producer works for crate documentation`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                //! ```
+                //! let x = 1;/*caret*/
+                //! ```
+            """).open()
+        }
+        checkOnLeaf()
+    }
+
+    fun `test doctest producer works inside injection`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                /// ```
+                /// let a = 1;
+                /// ```
+                ///
+                /// ```
+                /// let b = 2;/*caret*/
+                /// ```
+                pub fn foo() {}
+            """).open()
+        }
+        val injected = InjectedLanguageManager.getInstance(project)
+            .findInjectedElementAt(myFixture.file, myFixture.caretOffset)!!
+        check(ConfigurationContext(injected))
+    }
+
+    fun `test doctest producer disabled for ignored code block`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                /// ```ignore
+                /// let x = 1;/*caret*/
+                /// ```
+                pub fn foo() {}
+            """).open()
+        }
+        checkOnLeaf()
+    }
+
+    fun `test doctest producer disabled for no_run code block`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                /// ```no_run
+                /// let x = 1;/*caret*/
+                /// ```
+                pub fn foo() {}
+            """).open()
+        }
+        checkOnLeaf()
+    }
+
+    fun `test doctest producer disabled for non-rust code block`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                /// ```text
+                /// let x = 1;/*caret*/
+                /// ```
+                pub fn foo() {}
+            """).open()
+        }
+        checkOnLeaf()
+    }
+
+    fun `test doctest producer remembers context`() {
+        testProject {
+            lib("foo", "src/lib.rs", """
+                /// ```
+                /// let a = 1;
+                /// ```
+                pub fn foo() {}
+
+                /// ```
+                /// let b = 2;
+                /// ```
+                pub fn bar() {}
+            """).open()
+        }
+
+        val ctx1 = myFixture.findElementByText("let a", PsiElement::class.java)
+        val ctx2 = myFixture.findElementByText("let b", PsiElement::class.java)
+        doTestRemembersContext(DoctestRunConfigurationProducer(), ctx1, ctx2)
+    }
+}
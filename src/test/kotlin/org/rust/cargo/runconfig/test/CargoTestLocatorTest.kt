@@ -0,0 +1,49 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.test
+
+import com.intellij.execution.Location
+import com.intellij.psi.PsiElement
+import com.intellij.psi.search.GlobalSearchScope
+import org.rust.RsTestBase
+import org.rust.lang.core.psi.RsFunction
+
+class CargoTestLocatorTest : RsTestBase() {
+    fun `test test function`() {
+        InlineFile("""
+            #[test]
+            fn foo() {}
+        """.trimIndent(), "lib.rs")
+        val location = getLocations("test_package-0123456789abcdef::foo").single()
+        assertEquals("foo", (location.psiElement as RsFunction).name)
+    }
+
+    fun `test doctest`() {
+        InlineFile("""
+            /// Adds one
+            ///
+            /// ```
+            /// assert_eq!(foo(1), 2);
+            /// ```
+            pub fn foo(x: i32) -> i32 { x + 1 }
+        """.trimIndent(), "lib.rs")
+        val location = getLocations("test_package-0doctests::foo (line 3)").single() as DoctestLocation
+        assertEquals(myFixture.file.text.indexOf("```"), location.offset)
+    }
+
+    fun `test doctest at wrong line`() {
+        InlineFile("""
+            /// ```
+            /// assert_eq!(foo(1), 2);
+            /// ```
+            pub fn foo(x: i32) -> i32 { x + 1 }
+        """.trimIndent(), "lib.rs")
+        assertEmpty(getLocations("test_package-0doctests::foo (line 2)"))
+    }
+
+    private fun getLocations(path: String): List<Location<PsiElement>> =
+        CargoTestLocator.getLocation("cargo:test", path, project, GlobalSearchScope.allScope(project))
+}
@@ -0,0 +1,120 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.runconfig.test
+
+import com.intellij.execution.executors.DefaultRunExecutor
+import com.intellij.execution.process.ProcessOutputTypes
+import com.intellij.execution.testframework.sm.runner.GeneralIdBasedToSMTRunnerEventsConvertor
+import com.intellij.execution.testframework.sm.runner.SMTestProxy
+import com.intellij.util.ui.UIUtil
+import org.rust.RsTestBase
+import org.rust.cargo.runconfig.command.CargoCommandConfigurationType
+
+class NextestTestEventsConverterTest : RsTestBase() {
+
+    fun `test passed and failed tests`() = doTest("""
+        {"type":"suite","event":"started","test_count":2,"nextest":{"crate":"my-crate","test_binary":"my-crate","kind":"lib"}}
+        {"type":"test","event":"started","name":"my-crate${'$'}tests::foo"}
+        {"type":"test","event":"started","name":"my-crate${'$'}tests::bar"}
+        {"type":"test","name":"my-crate${'$'}tests::foo","event":"ok","exec_time":0.001}
+        {"type":"test","name":"my-crate${'$'}tests::bar","event":"failed","exec_time":0.002,"stdout":"thread 'tests::bar' panicked at 'explicit panic', src/lib.rs:9:9\n"}
+        {"type":"suite","event":"failed","passed":1,"failed":1,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.002}
+    """, """
+        my_crate
+          tests
+            foo: passed
+            bar: failed
+    """)
+
+    fun `test ignored test`() = doTest("""
+        {"type":"suite","event":"started","test_count":2,"nextest":{"crate":"my-crate","test_binary":"my-crate","kind":"lib"}}
+        {"type":"test","event":"started","name":"my-crate${'$'}foo"}
+        {"type":"test","name":"my-crate${'$'}foo","event":"ok","exec_time":0.001}
+        {"type":"test","event":"started","name":"my-crate${'$'}bar"}
+        {"type":"test","name":"my-crate${'$'}bar","event":"ignored"}
+        {"type":"suite","event":"ok","passed":1,"failed":0,"ignored":1,"measured":0,"filtered_out":0,"exec_time":0.001}
+    """, """
+        my_crate
+          foo: passed
+          bar: ignored
+    """)
+
+    fun `test several binaries`() = doTest("""
+        {"type":"suite","event":"started","test_count":1,"nextest":{"crate":"my-crate","test_binary":"my-crate","kind":"lib"}}
+        {"type":"test","event":"started","name":"my-crate${'$'}foo"}
+        {"type":"test","name":"my-crate${'$'}foo","event":"ok","exec_time":0.001}
+        {"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+        {"type":"suite","event":"started","test_count":1,"nextest":{"crate":"my-crate","test_binary":"my-crate","kind":"bin"}}
+        {"type":"test","event":"started","name":"my-crate::bin/my-crate${'$'}foo"}
+        {"type":"test","name":"my-crate::bin/my-crate${'$'}foo","event":"ok","exec_time":0.001}
+        {"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+        {"type":"suite","event":"started","test_count":1,"nextest":{"crate":"my-crate","test_binary":"integration","kind":"test"}}
+        {"type":"test","event":"started","name":"my-crate::integration${'$'}baz"}
+        {"type":"test","name":"my-crate::integration${'$'}baz","event":"ok","exec_time":0.001}
+        {"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+    """, """
+        my_crate
+          foo: passed
+        my_crate
+          foo: passed
+        integration
+          baz: passed
+    """)
+
+    fun `test non-json lines are skipped`() = doTest("""
+        Starting 1 tests across 1 binaries
+        {"type":"suite","event":"started","test_count":1,"nextest":{"crate":"my-crate","test_binary":"my-crate","kind":"lib"}}
+        {"type":"test","event":"started","name":"my-crate${'$'}foo"}
+        {"type":"test","name":"my-crate${'$'}foo","event":"ok","exec_time":0.001}
+        {"type":"suite","event":"ok","passed":1,"failed":0,"ignored":0,"measured":0,"filtered_out":0,"exec_time":0.001}
+        Summary [0.001s] 1 tests run: 1 passed, 0 skipped
+    """, """
+        my_crate
+          foo: passed
+    """)
+
+    private fun doTest(output: String, expectedTree: String) {
+        val configuration = CargoCommandConfigurationType.getInstance().factory.createTemplateConfiguration(project)
+        val executor = DefaultRunExecutor.getRunExecutorInstance()
+        val properties = CargoTestConsoleProperties(configuration, executor, useNextest = true)
+        val converter = NextestTestEventsConverter(CargoTestConsoleProperties.TEST_FRAMEWORK_NAME, properties)
+        val root = SMTestProxy.SMRootTestProxy()
+        val processor = GeneralIdBasedToSMTRunnerEventsConvertor(project, root, CargoTestConsoleProperties.TEST_FRAMEWORK_NAME)
+        converter.setProcessor(processor)
+
+        processor.onStartTesting()
+        for (line in output.trimIndent().lines()) {
+            converter.process("$line\n", ProcessOutputTypes.STDOUT)
+        }
+        converter.flushBufferOnProcessTermination(0)
+        processor.onFinishTesting()
+        UIUtil.dispatchAllInvocationEvents()
+
+        val actualTree = buildString {
+            fun render(proxy: SMTestProxy, depth: Int) {
+                append("  ".repeat(depth)).append(proxy.name)
+                if (proxy.isLeaf) {
+                    val status = when {
+                        proxy.isIgnored -> "ignored"
+                        proxy.isDefect -> "failed"
+                        else -> "passed"
+                    }
+                    append(": ").append(status)
+                }
+                append("\n")
+                for (child in proxy.children) {
+                    render(child, depth + 1)
+                }
+            }
+            for (child in root.children) {
+                render(child, 0)
+            }
+        }
+        assertEquals(expectedTree.trimIndent(), actualTree.trimEnd())
+        processor.dispose()
+        converter.dispose()
+    }
+}
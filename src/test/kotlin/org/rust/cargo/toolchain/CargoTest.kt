@@ -102,6 +102,34 @@ class CargoTest : RsTestBase() {
         """)
     }
 
+    fun `test clippy fix command`() = withLinterSettings(compileAllTargets = false, arguments = "-W clippy::pedantic") {
+        checkCommandLine(cargo.toGeneralCommandLine(project, cargo.clippyFixCommandLine(project, wd)), """
+            cmd: /usr/bin/cargo clippy --fix --allow-dirty --workspace -W clippy::pedantic
+            env: RUST_BACKTRACE=short, TERM=ansi
+            """, """
+            cmd: C:/usr/bin/cargo.exe clippy --fix --allow-dirty --workspace -W clippy::pedantic
+            env: RUST_BACKTRACE=short, TERM=ansi
+        """)
+    }
+
+    private fun withLinterSettings(compileAllTargets: Boolean, arguments: String, action: () -> Unit) {
+        val settings = project.rustSettings
+        val oldCompileAllTargets = settings.compileAllTargets
+        val oldArguments = settings.externalLinterArguments
+        try {
+            settings.modify {
+                it.compileAllTargets = compileAllTargets
+                it.externalLinterArguments = arguments
+            }
+            action()
+        } finally {
+            settings.modify {
+                it.compileAllTargets = oldCompileAllTargets
+                it.externalLinterArguments = oldArguments
+            }
+        }
+    }
+
     private fun withOfflineMode(action: () -> Unit) {
         val oldStatus = project.rustSettings.useOffline
         try {
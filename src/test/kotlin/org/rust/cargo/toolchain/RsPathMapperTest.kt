@@ -0,0 +1,48 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.toolchain
+
+import com.intellij.openapi.util.SystemInfo
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertNull
+import org.junit.Assume.assumeTrue
+import org.junit.Test
+import java.nio.file.Paths
+
+class RsPathMapperTest {
+    private val wsl = RsPathMapper.Wsl("Ubuntu")
+
+    @Test
+    fun `wsl distribution paths`() {
+        assertEquals("/home/user/project/src/main.rs", wsl.toRemote("//wsl$/Ubuntu/home/user/project/src/main.rs"))
+        assertEquals("//wsl$/Ubuntu/home/user/project/src/main.rs", wsl.toLocal("/home/user/project/src/main.rs"))
+        assertEquals("/", wsl.toRemote("//wsl$/Ubuntu"))
+    }
+
+    @Test
+    fun `wsl mounted windows drives`() {
+        assertEquals("/mnt/c/Users/user/project", wsl.toRemote("C:/Users/user/project"))
+        assertEquals("C:/Users/user/project", wsl.toLocal("/mnt/c/Users/user/project"))
+        assertEquals("D:/", wsl.toLocal("/mnt/d"))
+    }
+
+    @Test
+    fun `wsl relative path`() {
+        assertNull(wsl.toLocal("src/main.rs"))
+    }
+
+    @Test
+    fun `mapper for local toolchain`() {
+        assertEquals(RsPathMapper.Local, RsPathMapper.forToolchain(Paths.get("/home/user/.cargo/bin")))
+    }
+
+    @Test
+    fun `mapper for wsl toolchain`() {
+        // UNC paths exist only on Windows
+        assumeTrue(SystemInfo.isWindows)
+        assertEquals(wsl, RsPathMapper.forToolchain(Paths.get("\\\\wsl$\\Ubuntu\\home\\user\\.cargo\\bin")))
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.cargo.toolchain.impl
+
+import org.junit.Assert.assertEquals
+import org.junit.Assert.assertNull
+import org.junit.Test
+import org.rust.cargo.CfgOptions
+
+class CargoPlatformTest {
+    @Test
+    fun `name option`() {
+        doTest("cfg(unix)", true)
+        doTest("cfg(windows)", false)
+    }
+
+    @Test
+    fun `key value option`() {
+        doTest("""cfg(target_os = "linux")""", true)
+        doTest("""cfg(target_arch = "wasm32")""", false)
+    }
+
+    @Test
+    fun `all any not`() {
+        doTest("""cfg(all(unix, target_pointer_width = "64"))""", true)
+        doTest("""cfg(all(unix, target_pointer_width = "32"))""", false)
+        doTest("""cfg(any(windows, target_os = "linux"))""", true)
+        doTest("""cfg(any(windows, target_os = "macos"))""", false)
+        doTest("cfg(not(windows))", true)
+        doTest("cfg(not(unix))", false)
+        doTest("cfg(all())", true)
+        doTest("cfg(any())", false)
+    }
+
+    @Test
+    fun `trailing comma and whitespaces`() {
+        doTest("""cfg( any( windows , target_os="linux", ) )""", true)
+    }
+
+    @Test
+    fun `target triple`() {
+        doTest("x86_64-unknown-linux-gnu", true)
+        doTest("x86_64-pc-windows-msvc", false)
+    }
+
+    @Test
+    fun `malformed cfg`() {
+        assertNull(CargoPlatform.parse("cfg(unix"))
+        assertNull(CargoPlatform.parse("cfg()"))
+        assertNull(CargoPlatform.parse("cfg(unix windows)"))
+        assertNull(CargoPlatform.parse("cfg(not(unix, windows))"))
+        assertNull(CargoPlatform.parse("cfg(foo(unix))"))
+        assertNull(CargoPlatform.parse("""cfg(target_os = "linux)"""))
+    }
+
+    private fun doTest(platform: String, expected: Boolean) {
+        val parsed = CargoPlatform.parse(platform) ?: error("Failed to parse `$platform`")
+        assertEquals(platform, expected, parsed.matches("x86_64-unknown-linux-gnu", CfgOptions.DEFAULT))
+    }
+}
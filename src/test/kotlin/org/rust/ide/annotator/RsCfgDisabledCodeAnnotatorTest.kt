@@ -64,4 +64,21 @@ class RsCfgDisabledCodeAnnotatorTest : RsAnnotatorTestBase(RsCfgDisabledCodeAnno
         </CFG_DISABLED_CODE>
         }
     """)
+
+    @MockAdditionalCfgOptions("intellij_rust")
+    fun `test nested cfg operators`() = checkHighlighting("""
+        #[cfg(all(intellij_rust, any(not(intellij_rust), intellij_rust)))]
+        fn foo() {}
+        <CFG_DISABLED_CODE descr="Conditionally disabled code">#[cfg(any(not(intellij_rust), all(intellij_rust, not(intellij_rust))))]
+        fn bar() {}
+        </CFG_DISABLED_CODE>
+    """)
+
+    @MockAdditionalCfgOptions("intellij_rust")
+    fun `test malformed cfg operators are not evaluated`() = checkHighlighting("""
+        #[cfg(not(all("intellij_rust")))]
+        fn foo() {}
+        #[cfg(not(intellij_rust, intellij_rust))]
+        fn bar() {}
+    """)
 }
@@ -6,6 +6,7 @@
 package org.rust.ide.annotator
 
 import org.rust.*
+import org.rust.cargo.project.settings.rustSettings
 import org.rust.cargo.project.workspace.CargoWorkspace
 
 class RsErrorAnnotatorTest : RsAnnotatorTestBase(RsErrorAnnotator::class) {
@@ -1398,6 +1399,48 @@ class RsErrorAnnotatorTest : RsAnnotatorTestBase(RsErrorAnnotator::class) {
         fn foo2() -> i32 { unimplemented!() }
     """)
 
+    fun `test type parameter without required bound E0277`() = checkErrors("""
+        trait Foo {}
+        trait Bar: Foo {}
+        fn foo<U: Foo>(u: U) {}
+        fn bar<U>(u: U) where U: Foo {}
+        fn baz<T, B: Bar>(t: T, b: B) {
+            foo(<error descr="the trait bound `T: Foo` is not satisfied [E0277]">t</error>);
+            bar(<error descr="the trait bound `T: Foo` is not satisfied [E0277]">t</error>);
+            foo(b);
+        }
+        fn quux<T: Foo>(t: T) {
+            foo(t);
+            bar(t);
+        }
+    """)
+
+    fun `test add type parameter bound fix for E0277`() = checkFixByText("Add `T: Foo` bound", """
+        mod foo {
+            pub trait Foo {
+                fn foo(&self);
+            }
+            pub fn bar<U: Foo>(u: U) {}
+        }
+
+        fn baz<T>(t: T) {
+            foo::bar(<error descr="the trait bound `T: Foo` is not satisfied [E0277]">t/*caret*/</error>);
+        }
+    """, """
+        use foo::Foo;
+
+        mod foo {
+            pub trait Foo {
+                fn foo(&self);
+            }
+            pub fn bar<U: Foo>(u: U) {}
+        }
+
+        fn baz<T: Foo>(t: T) {
+            foo::bar(t);
+        }
+    """)
+
     fun `test trait method without body can have arg with '?Sized' type E0277`() = checkErrors("""
         #[lang = "sized"] trait Sized {}
         trait Foo {
@@ -1617,6 +1660,52 @@ class RsErrorAnnotatorTest : RsAnnotatorTestBase(RsErrorAnnotator::class) {
         }
     """)
 
+    @MockRustcVersion("1.42.0")
+    fun `test or patterns feature E0658 1`() = checkErrors("""
+        enum E { A(i32), B(i32) }
+        fn foo(e: E) {
+            match e {
+                E::A(<error descr="or-patterns syntax is experimental [E0658]">1 | 2</error>) | E::B(_) => {}
+                _ => {}
+            }
+        }
+    """)
+
+    @MockRustcVersion("1.42.0-nightly")
+    fun `test or patterns feature E0658 2`() = checkErrors("""
+        #![feature(or_patterns)]
+
+        enum E { A(i32), B(i32) }
+        fn foo(e: E) {
+            match e {
+                E::A(1 | 2) | E::B(_) => {}
+                _ => {}
+            }
+        }
+    """)
+
+    @MockRustcVersion("1.42.0")
+    fun `test half open range patterns feature E0658 1`() = checkErrors("""
+        fn foo(x: u8) {
+            match x {
+                0..=9 => {}
+                <error descr="half-open range pattern is experimental [E0658]">10..</error> => {}
+            }
+        }
+    """)
+
+    @MockRustcVersion("1.42.0-nightly")
+    fun `test half open range patterns feature E0658 2`() = checkErrors("""
+        #![feature(half_open_range_patterns)]
+
+        fn foo(x: u8) {
+            match x {
+                0..=9 => {}
+                10.. => {}
+            }
+        }
+    """)
+
     @MockRustcVersion("1.31.0")
     fun `test irrefutable let pattern E0658 1`() = checkErrors("""
         fn main() {
@@ -3202,4 +3291,58 @@ class RsErrorAnnotatorTest : RsAnnotatorTestBase(RsErrorAnnotator::class) {
             }
         }
     """)
+
+    @ExpandMacros
+    fun `test self-recursive macro reaches depth limit`() = checkErrors("""
+        macro_rules! foo { () => { foo!(); } }
+        <error descr="Macro expansion depth limit reached">foo</error>!();
+    """)
+
+    @ExpandMacros
+    fun `test mutually recursive macros reach depth limit`() = checkErrors("""
+        macro_rules! foo { () => { bar!(); } }
+        macro_rules! bar { () => { foo!(); } }
+        <error descr="Macro expansion depth limit reached">foo</error>!();
+    """)
+
+    @ExpandMacros
+    fun `test finite expansion just under depth limit`() = withMacroExpansionDepthLimit(3) {
+        checkErrors("""
+            macro_rules! count { () => {}; (x $($ t:tt)*) => { count!($($ t)*); } }
+            count!(x x);
+            <error descr="Macro expansion depth limit reached">count</error>!(x x x);
+        """)
+    }
+
+    @ExpandMacros
+    fun `test macro expansion reaches token limit`() = withMacroExpansionTokenLimit(10) {
+        checkErrors("""
+            macro_rules! small { () => { fn a() {} } }
+            macro_rules! big { () => { fn a() {} fn b() {} fn c() {} } }
+            macro_rules! outer { () => { big!(); } }
+            small!();
+            <error descr="Macro expansion token limit reached">big</error>!();
+            <error descr="Macro expansion token limit reached">outer</error>!();
+        """)
+    }
+
+    private fun withMacroExpansionTokenLimit(limit: Int, action: () -> Unit) {
+        val oldLimit = project.rustSettings.macroExpansionTokenLimit
+        project.rustSettings.modify { it.macroExpansionTokenLimit = limit }
+        try {
+            action()
+        } finally {
+            project.rustSettings.modify { it.macroExpansionTokenLimit = oldLimit }
+        }
+    }
+
+    private fun withMacroExpansionDepthLimit(limit: Int, action: () -> Unit) {
+        val oldLimit = project.rustSettings.macroExpansionDepthLimit
+        project.rustSettings.modify { it.macroExpansionDepthLimit = limit }
+        try {
+            action()
+        } finally {
+            project.rustSettings.modify { it.macroExpansionDepthLimit = oldLimit }
+        }
+    }
 }
@@ -0,0 +1,133 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.annotator
+
+import com.intellij.codeInsight.daemon.impl.AnnotationHolderImpl
+import com.intellij.lang.annotation.AnnotationSession
+import com.intellij.util.PathUtil
+import org.rust.RsTestBase
+import org.rust.cargo.toolchain.RsPathMapper
+import org.rust.cargo.toolchain.RustcSpan
+import org.rust.lang.core.psi.RsFile
+
+class RsExternalLinterPathMappingTest : RsTestBase() {
+
+    fun `test remote absolute path`() = doTest(
+        listOf(message(remotePath(filePath), line = 2, suggestion = "_x")),
+        "x" to 1
+    )
+
+    fun `test relative path`() = doTest(
+        listOf(message("src/main.rs", line = 2)),
+        "x" to 0
+    )
+
+    fun `test local path of remote toolchain`() = doTest(
+        listOf(message(filePath, line = 2))
+    )
+
+    fun `test path not accessible from the IDE`() = doTest(
+        listOf(message("/usr/lib/rustlib/src/main.rs", line = 2))
+    )
+
+    fun `test generated code`() {
+        val targetDir = PathUtil.toSystemIndependentName(workspaceRoot.resolve("target").toString())
+        doTest(
+            listOf(
+                message(remotePath("$targetDir/debug/build/foo-1234/out/main.rs"), line = 2),
+                message("target/debug/build/foo-1234/out/main.rs", line = 2)
+            )
+        )
+    }
+
+    fun `test generated code in custom target directory`() {
+        InlineFile("fn main() {}")
+        val targetDir = workspaceRoot.resolve("build")
+        val generatedFile = PathUtil.toSystemIndependentName(targetDir.resolve("debug/out/main.rs").toString())
+        val matcher = RsSpanFileMatcher(myFixture.file, workspaceRoot, targetDir, FakePathMapper)
+        assertFalse(matcher.matches(spanIn(remotePath(generatedFile))))
+        assertFalse(matcher.matches(spanIn("build/debug/out/main.rs")))
+        assertTrue(matcher.matches(spanIn("src/main.rs")))
+    }
+
+    fun `test suggestion in other file`() = doTest(
+        listOf(message(remotePath(filePath), line = 2, suggestion = "_x", suggestionFile = remotePath("/src/lib.rs"))),
+        "x" to 0
+    )
+
+    private fun doTest(messages: List<String>, vararg expected: Pair<String, Int>) {
+        InlineFile("""
+            fn main() {
+                let x = 0;
+            }
+        """)
+        val result = RsExternalLinterResult(messages, FakePathMapper)
+        val holder = AnnotationHolderImpl(AnnotationSession(myFixture.file))
+        holder.createAnnotationsForFile(myFixture.file as RsFile, result)
+        val actual = holder.map { myFixture.file.text.substring(it.startOffset, it.endOffset) to it.quickFixes.orEmpty().size }
+        assertEquals(expected.toList(), actual)
+    }
+
+    private val filePath: String get() = myFixture.file.virtualFile.path
+
+    private val workspaceRoot get() = (myFixture.file as RsFile).cargoWorkspace!!.contentRoot
+
+    private fun remotePath(localPath: String): String = FakePathMapper.toRemote(localPath)
+
+    /** A diagnostic for `x` at [line] */
+    private fun message(
+        fileName: String,
+        line: Int,
+        suggestion: String? = null,
+        suggestionFile: String = fileName
+    ): String {
+        val children = if (suggestion == null) "" else """
+            {
+                "children": [], "code": null, "level": "help", "message": "rename", "rendered": null,
+                "spans": [${span(suggestionFile, line, suggestion)}]
+            }
+        """
+        return """
+            {
+                "reason": "compiler-message",
+                "package_id": "test-package 0.0.1",
+                "target": { "crate_types": ["bin"], "kind": ["bin"], "name": "test-package", "src_path": "$fileName" },
+                "message": {
+                    "children": [$children], "code": null, "level": "warning", "message": "unused variable: `x`",
+                    "rendered": null, "spans": [${span(fileName, line, null)}]
+                }
+            }
+        """.lines().joinToString(" ") { it.trim() }
+    }
+
+    private fun span(fileName: String, line: Int, suggestion: String?): String {
+        val applicability = if (suggestion == null) "null" else "\"MachineApplicable\""
+        val replacement = if (suggestion == null) "null" else "\"$suggestion\""
+        return """
+            {
+                "file_name": "$fileName", "byte_start": 0, "byte_end": 0,
+                "line_start": $line, "line_end": $line, "column_start": 9, "column_end": 10,
+                "is_primary": true, "text": [], "label": null,
+                "suggested_replacement": $replacement, "suggestion_applicability": $applicability, "expansion": null
+            }
+        """
+    }
+
+    private fun spanIn(fileName: String): RustcSpan =
+        RustcSpan(fileName, 0, 0, 1, 1, 1, 1, true, emptyList(), null, null, null, null)
+
+    /** Emulates a toolchain inside WSL where the local `/src` directory is mounted as `/home/user/project` */
+    private object FakePathMapper : RsPathMapper {
+        private const val LOCAL_ROOT: String = "/src/"
+        private const val REMOTE_ROOT: String = "/home/user/project/"
+
+        override fun toRemote(localPath: String): String =
+            if (localPath.startsWith(LOCAL_ROOT)) REMOTE_ROOT + localPath.removePrefix(LOCAL_ROOT) else localPath
+
+        override fun toLocal(remotePath: String): String? =
+            if (remotePath.startsWith(REMOTE_ROOT)) LOCAL_ROOT + remotePath.removePrefix(REMOTE_ROOT) else null
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.annotator.fixes
+
+import com.intellij.codeInsight.daemon.impl.AnnotationHolderImpl
+import com.intellij.codeInsight.intention.IntentionAction
+import com.intellij.lang.annotation.AnnotationSession
+import com.intellij.openapi.command.WriteCommandAction
+import org.intellij.lang.annotations.Language
+import org.rust.RsTestBase
+import org.rust.cargo.project.settings.rustSettings
+import org.rust.cargo.toolchain.ExternalLinter
+import org.rust.ide.annotator.RsExternalLinterResult
+import org.rust.ide.annotator.createAnnotationsForFile
+import org.rust.lang.core.psi.RsFile
+
+/** Checks fixes created from the recorded output of `cargo clippy --message-format=json` */
+class ApplySuggestionFixJsonTest : RsTestBase() {
+
+    override fun setUp() {
+        super.setUp()
+        project.rustSettings.modify { it.externalLinter = ExternalLinter.CLIPPY }
+    }
+
+    override fun tearDown() {
+        project.rustSettings.modify {
+            it.externalLinter = ExternalLinter.DEFAULT
+            it.externalLinterMaybeIncorrectFixes = false
+        }
+        super.tearDown()
+    }
+
+    fun `test multi-span suggestion`() = checkFix("needless_return.json", "External Linter: remove `return`", """
+        fn foo() -> i32 {
+            return 1;
+        }
+    """, """
+        fn foo() -> i32 {
+            1
+        }
+    """)
+
+    fun `test apply all fixes in file`() = checkFix("needless_bool.json", "Apply all Clippy fixes in file", """
+        fn check(a: bool, b: bool) -> (bool, bool) {
+            let x = if a { true } else { false };
+            let y = if b { true } else { false };
+            (x, y)
+        }
+    """, """
+        fn check(a: bool, b: bool) -> (bool, bool) {
+            let x = a;
+            let y = b;
+            (x, y)
+        }
+    """)
+
+    fun `test apply all fixes in file is not available for single fix`() = checkFixes("needless_return.json", """
+        fn foo() -> i32 {
+            return 1;
+        }
+    """, "External Linter: remove `return`")
+
+    fun `test suggestion inside macro expansion`() = checkFixes("macro_expansion.json", """
+        macro_rules! is_true {
+            (${'$'}e:expr) => { if ${'$'}e { true } else { false } };
+        }
+
+        fn main() {
+            let _ = is_true!(1 > 0);
+        }
+    """)
+
+    fun `test maybe incorrect suggestion is hidden by default`() = checkFixes("maybe_incorrect.json", """
+        fn main() {
+            let v = vec![1, 2, 3];
+            let _ = v.iter().nth(0);
+        }
+    """)
+
+    fun `test maybe incorrect suggestion`() {
+        project.rustSettings.modify { it.externalLinterMaybeIncorrectFixes = true }
+        checkFix("maybe_incorrect.json", "External Linter: calling `.get()` is both faster and more readable", """
+            fn main() {
+                let v = vec![1, 2, 3];
+                let _ = v.iter().nth(0);
+            }
+        """, """
+            fn main() {
+                let v = vec![1, 2, 3];
+                let _ = v.get(0);
+            }
+        """)
+    }
+
+    private fun checkFix(fixture: String, fixName: String, @Language("Rust") before: String, @Language("Rust") after: String) {
+        val fix = collectFixes(fixture, before).single { it.text == fixName }
+        WriteCommandAction.runWriteCommandAction(project) {
+            fix.invoke(project, myFixture.editor, myFixture.file)
+        }
+        myFixture.checkResult(after.trimIndent())
+    }
+
+    private fun checkFixes(fixture: String, @Language("Rust") code: String, vararg expectedFixNames: String) {
+        val actual = collectFixes(fixture, code).map { it.text }
+        assertEquals(expectedFixNames.toList(), actual)
+    }
+
+    private fun collectFixes(fixture: String, code: String): List<IntentionAction> {
+        InlineFile(code.trimIndent())
+        val output = getResourceAsString("$FIXTURES_PATH/$fixture") ?: error("No fixture $fixture")
+        val result = RsExternalLinterResult(output.lines())
+        val holder = AnnotationHolderImpl(AnnotationSession(myFixture.file))
+        holder.createAnnotationsForFile(myFixture.file as RsFile, result)
+        return holder.flatMap { it.quickFixes.orEmpty() }.map { it.quickFix }.distinct()
+    }
+
+    companion object {
+        private const val FIXTURES_PATH: String = "org/rust/ide/annotator/fixes/fixtures/clippy"
+    }
+}
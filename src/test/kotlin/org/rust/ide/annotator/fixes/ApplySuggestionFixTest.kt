@@ -17,7 +17,10 @@ class ApplySuggestionFixTest : RsWithToolchainTestBase() {
 
     override fun setUp() {
         super.setUp()
-        project.rustSettings.modify { it.runExternalLinterOnTheFly = true }
+        project.rustSettings.modify {
+            it.runExternalLinterOnTheFly = true
+            it.externalLinterMaybeIncorrectFixes = true
+        }
     }
 
     fun `test rustc suggestion (machine applicable)`() = checkFixByText("""
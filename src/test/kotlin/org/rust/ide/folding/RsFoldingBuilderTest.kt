@@ -33,6 +33,7 @@ class RsFoldingBuilderTest : RsTestBase() {
     fun `test mods`() = doTest()
     fun `test crates`() = doTest()
     fun `test parameter list`() = doTest()
+    fun `test cfg predicate`() = doTest()
     fun `test custom region`() = doTest()
     fun `test custom region attached to function`() = doTest()
     fun `test custom region in struct`() = doTest()
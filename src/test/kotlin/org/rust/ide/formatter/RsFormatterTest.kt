@@ -5,6 +5,7 @@
 
 package org.rust.ide.formatter
 
+import com.intellij.psi.codeStyle.CommonCodeStyleSettings
 import org.intellij.lang.annotations.Language
 import org.rust.lang.RsLanguage
 
@@ -16,35 +17,49 @@ class RsFormatterTest : RsFormatterTestBase() {
     fun `test traits`() = doTest()
     fun `test tuple alignment`() = doTest()
     fun `test chain call indent`() = doTest()
+    fun `test chain call wrap`() = doTest()
 
-    fun `test chain call alignment`() = doTextTest(common()::ALIGN_MULTILINE_CHAINED_METHODS, """
-        fn main() {
-            let foo = moo.boo().goo()
-             .foo().bar()
-                                     .map(|x| x.foo().doo()
+    fun `test chain width`() {
+        custom().CHAIN_WIDTH = 100
+        checkNotChanged("""
+            fn main() {
+                let ids = items.iter().filter(|item| item.is_valid()).map(|item| item.id).count();
+            }
+        """)
+    }
+
+    fun `test chain call alignment`() {
+        // The chain is wider than `chain_width`, so it would be broken at every method call
+        common().METHOD_CALL_CHAIN_WRAP = CommonCodeStyleSettings.DO_NOT_WRAP
+        doTextTest(common()::ALIGN_MULTILINE_CHAINED_METHODS, """
+            fn main() {
+                let foo = moo.boo().goo()
+                 .foo().bar()
+                                         .map(|x| x.foo().doo()
+                                           .moo().boo()
+                                                        .bar().baz())
+                          .baz().moo();
+            }
+        """, """
+            fn main() {
+                let foo = moo.boo().goo()
+                             .foo().bar()
+                             .map(|x| x.foo().doo()
                                        .moo().boo()
-                                                    .bar().baz())
-                      .baz().moo();
-        }
-    """, """
-        fn main() {
-            let foo = moo.boo().goo()
-                         .foo().bar()
-                         .map(|x| x.foo().doo()
-                                   .moo().boo()
-                                   .bar().baz())
-                         .baz().moo();
-        }
-    """, """
-        fn main() {
-            let foo = moo.boo().goo()
-                .foo().bar()
-                .map(|x| x.foo().doo()
-                    .moo().boo()
-                    .bar().baz())
-                .baz().moo();
-        }
-    """)
+                                       .bar().baz())
+                             .baz().moo();
+            }
+        """, """
+            fn main() {
+                let foo = moo.boo().goo()
+                    .foo().bar()
+                    .map(|x| x.foo().doo()
+                        .moo().boo()
+                        .bar().baz())
+                    .baz().moo();
+            }
+        """)
+    }
 
     fun `test pub`() = doTextTest("""
         pub ( crate ) struct S1;
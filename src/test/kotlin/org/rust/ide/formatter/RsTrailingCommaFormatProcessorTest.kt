@@ -119,4 +119,42 @@ class RsTrailingCommaFormatProcessorTest : RsFormatterTestBase() {
             );
         }
     """)
+
+    fun `test add trailing comma in multiline constructs option`() = doTextTest(settings.rust::ADD_TRAILING_COMMA_IN_MULTILINE, """
+        fn main() {
+            let _ = S {
+                x: 92,
+                y: 62
+            };
+            foo(
+                1,
+                2
+            );
+            let _ = S { x: 92, };
+        }
+    """, """
+        fn main() {
+            let _ = S {
+                x: 92,
+                y: 62,
+            };
+            foo(
+                1,
+                2,
+            );
+            let _ = S { x: 92 };
+        }
+    """, """
+        fn main() {
+            let _ = S {
+                x: 92,
+                y: 62
+            };
+            foo(
+                1,
+                2
+            );
+            let _ = S { x: 92 };
+        }
+    """)
 }
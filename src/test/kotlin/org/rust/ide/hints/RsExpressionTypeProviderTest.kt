@@ -5,8 +5,12 @@
 
 package org.rust.ide.hints
 
+import com.intellij.openapi.util.TextRange
 import org.intellij.lang.annotations.Language
+import org.rust.ProjectDescriptor
 import org.rust.RsTestBase
+import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.presentation.TypeRenderingLevel
 import org.rust.openapiext.escaped
 
 
@@ -31,7 +35,7 @@ class RsExpressionTypeProviderTest : RsTestBase() {
         fn foo<T, U>(c: S<T, U>) {
             let /*caret*/b = c;
         }
-    """, "S<T, U, u32>")
+    """, "S<T, U>")
 
     fun `test ref type`() = doTest("""
         fn foo(c: &mut u32) {
@@ -69,6 +73,112 @@ class RsExpressionTypeProviderTest : RsTestBase() {
         }
     """, "&BoxedS<T>")
 
+    fun `test detail levels`() = doTestLevels("""
+        struct S<'a, T, U=u32> { t: &'a T, u: U }
+
+        type Alias<'a> = S<'a, i32>;
+
+        fn foo(c: Alias<'static>) {
+            let /*caret*/b = c;
+        }
+    """, "Alias", "S<i32, u32>", "S<'static, i32, u32>", "Alias")
+
+    fun `test closure`() = doTest("""
+        fn foo() {
+            let /*caret*/f = |x: i32| x > 0;
+        }
+    """, "impl Fn(i32) -> bool")
+
+    fun `test fn mut closure`() = doTest("""
+        fn foo() {
+            let mut count = 0;
+            let /*caret*/f = || count += 1;
+        }
+    """, "impl FnMut()")
+
+    fun `test fn once closure`() = doTest("""
+        struct S;
+        fn consume(s: S) {}
+        fn foo() {
+            let s = S;
+            let /*caret*/f = || consume(s);
+        }
+    """, "impl FnOnce()")
+
+    fun `test copied capture`() = doTest("""
+        fn consume(x: i32) {}
+        fn foo() {
+            let x = 1;
+            let /*caret*/f = move || consume(x);
+        }
+    """, "impl Fn()")
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test closures in iterator adapter chain`() = doTest("""
+        fn foo() {
+            let mut n = 0;
+            let /*caret*/it = vec![1, 2, 3].into_iter()
+                .filter(|x| *x > 0)
+                .map(|x| { n += 1; x * n });
+        }
+    """, "Map<Filter<IntoIter<i32>, impl Fn(&i32) -> bool>, impl FnMut(i32) -> i32>")
+
+    fun `test function pointer`() = doTest("""
+        fn bar(x: i32) -> bool { x > 0 }
+        fn foo() {
+            let /*caret*/f: fn(i32) -> bool = bar;
+        }
+    """, "fn(i32) -> bool")
+
+    fun `test impl trait`() = doTest("""
+        trait Trait { type Item; }
+        struct S;
+        impl Trait for S { type Item = i32; }
+        fn bar() -> impl Trait<Item=i32> { S }
+
+        fn foo() {
+            let /*caret*/x = bar();
+        }
+    """, "impl Trait<Item=i32>")
+
+    fun `test selection with whitespace`() = doSelectionTest("""
+        fn foo(a: i32, b: u8) {
+            let _ = <selection> a + 1 </selection>;
+        }
+    """, "i32")
+
+    fun `test selection of several expressions`() = doSelectionTest("""
+        fn foo(a: i32, b: u8) {
+            let _ = (a, <selection>b as u64, a</selection>);
+        }
+    """, "u64")
+
+    override fun tearDown() {
+        TypeRenderingLevel.lastUsed = TypeRenderingLevel.SHORT
+        super.tearDown()
+    }
+
+    private fun doTestLevels(@Language("Rust") code: String, vararg types: String) {
+        InlineFile(code).withCaret()
+
+        val provider = RsExpressionTypeProvider()
+        val expression = provider.getExpressionsAt(myFixture.elementAtCaret).first()
+        val hints = listOf(provider.getInformationHint(expression)) +
+            (1 until types.size).map { provider.getAdvancedInformationHint(expression) }
+        assertEquals(types.map { it.escaped }, hints)
+    }
+
+    private fun doSelectionTest(@Language("Rust") code: String, type: String) {
+        InlineFile(code)
+
+        val selectionModel = myFixture.editor.selectionModel
+        val selection = TextRange(selectionModel.selectionStart, selectionModel.selectionEnd)
+        val provider = RsExpressionTypeProvider()
+        val expressions = provider.getExpressionsAt(myFixture.file.findElementAt(selection.startOffset)!!, selection)
+        assertEquals(selection, expressions.single().textRange)
+        assertEquals(type.escaped, provider.getInformationHint(expressions.single()))
+    }
+
     private fun doTest(@Language("Rust") code: String, type: String) {
         InlineFile(code).withCaret()
 
@@ -118,6 +118,27 @@ class RsInlayParameterHintsProviderTest : RsPlainInlayHintsProviderTestBase() {
         }
     """, enabledHints = RsPlainParameterHint.PARAMETER_HINT)
 
+    fun `test no hints for tuple struct when not smart`() = checkByText("""
+        struct TS(i32, f32);
+        fn main() {
+            let s = TS(5i32, 10.0f32);
+        }
+    """, enabledHints = RsPlainParameterHint.PARAMETER_HINT, smart = false)
+
+    fun `test tuple struct field hints`() = checkByText("""
+        struct TS(i32, f32);
+        fn main() {
+            let s = TS(/*hint text="0:"*/5i32, /*hint text="1:"*/10.0f32);
+        }
+    """, enabledHints = RsPlainParameterHint.PARAMETER_HINT, tupleFieldHints = true)
+
+    fun `test enum variant field hints`() = checkByText("""
+        enum E { V(i32, f32) }
+        fn main() {
+            let e = E::V(/*hint text="0:"*/5i32, /*hint text="1:"*/10.0f32);
+        }
+    """, enabledHints = RsPlainParameterHint.PARAMETER_HINT, tupleFieldHints = true)
+
     fun `test fn arg with mut ident`() = checkByText("""
         fn foo(mut arg: u32) {}
         fn main() { foo(/*hint text="arg:"*/0); }
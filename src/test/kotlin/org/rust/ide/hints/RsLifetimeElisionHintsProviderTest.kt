@@ -0,0 +1,126 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.hints
+
+import com.intellij.codeInsight.hints.InlayHintsSettings
+import org.intellij.lang.annotations.Language
+import org.rust.lang.RsLanguage
+
+class RsLifetimeElisionHintsProviderTest : RsInlayTypeHintsTestBase(RsLifetimeElisionHintsProvider::class) {
+    fun `test free function with single input`() = doTest("""
+        fn foo(s: &/*hint text="['1]"*/str) -> &/*hint text="['1]"*/str { s }
+    """)
+
+    fun `test free function without output references`() = doTest("""
+        fn foo(a: &/*hint text="['1]"*/i32, b: &/*hint text="['2]"*/mut i32) {}
+    """)
+
+    fun `test ambiguous output`() = doTest("""
+        fn foo(a: &/*hint text="['1]"*/str, b: &/*hint text="['2]"*/str) -> &str { a }
+    """)
+
+    fun `test nested references`() = doTest("""
+        struct S<T>(T);
+        fn foo(a: S<&/*hint text="['1]"*/&/*hint text="['2]"*/i32>) {}
+    """)
+
+    fun `test method with self`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(&/*hint text="['1]"*/self, s: &/*hint text="['2]"*/str) -> &/*hint text="['1]"*/str { s }
+            fn bar(&/*hint text="['1]"*/mut self) -> &/*hint text="['1]"*/mut S { self }
+        }
+    """)
+
+    fun `test method with typed self`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(self: &/*hint text="['1]"*/Self, s: &/*hint text="['2]"*/str) -> &/*hint text="['1]"*/str { s }
+        }
+    """)
+
+    fun `test method with self by value`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(self, s: &/*hint text="['1]"*/str) -> &/*hint text="['1]"*/str { s }
+        }
+    """)
+
+    fun `test method in impl with lifetime`() = doTest("""
+        struct S<'a>(&'a str);
+        impl<'a> S<'a> {
+            fn foo(&/*hint text="['1]"*/self) -> &/*hint text="['1]"*/str { self.0 }
+            fn bar(&/*hint text="['1]"*/self) -> &'a str { self.0 }
+            fn baz(&'a self) -> &/*hint text="['a]"*/str { self.0 }
+            fn qux(s: &'a str, t: &/*hint text="['1]"*/str) -> &str { s }
+            fn quux(s: S<'a>) -> &/*hint text="['a]"*/str { s.0 }
+        }
+    """)
+
+    fun `test impl header`() = doTest("""
+        struct S;
+        trait Trait<T> {}
+        impl Trait<&/*hint text="['1]"*/str> for &/*hint text="['2]"*/S {}
+    """)
+
+    fun `test impl header with explicit lifetime`() = doTest("""
+        struct S<'a>(&'a str);
+        trait Trait {}
+        impl<'a> Trait for (S<'a>, &/*hint text="['1]"*/S<'a>) {}
+    """)
+
+    fun `test method in impl with elided header lifetime`() = doTest("""
+        struct S;
+        impl PartialEq<str> for &/*hint text="['1]"*/S {
+            fn eq(&/*hint text="['2]"*/self, other: &/*hint text="['3]"*/str) -> bool { true }
+        }
+    """)
+
+    fun `test trait method`() = doTest("""
+        trait Trait {
+            fn foo(&/*hint text="['1]"*/self, s: &/*hint text="['2]"*/str) -> &/*hint text="['1]"*/str;
+        }
+    """)
+
+    fun `test explicit lifetimes`() = doTest("""
+        fn foo<'a>(a: &'a str, b: &str) -> &'a str { a }
+        fn bar(a: &'static str, b: &str) -> &'static str { a }
+        fn baz(a: &'_ str) -> &str { a }
+    """)
+
+    fun `test fn pointer`() = doTest("""
+        type T = fn(&/*hint text="['1]"*/str) -> &/*hint text="['1]"*/str;
+    """)
+
+    fun `test fn pointer parameter has own scope`() = doTest("""
+        fn foo(f: fn(&/*hint text="['1]"*/str), s: &/*hint text="['1]"*/str) -> &/*hint text="['1]"*/str { s }
+    """)
+
+    fun `test fn sugar has own scope`() = doTest("""
+        fn foo(f: &/*hint text="['1]"*/dyn Fn(&i32) -> &i32) -> &/*hint text="['1]"*/i32 { unimplemented!() }
+    """)
+
+    fun `test raw pointers`() = doTest("""
+        fn foo(a: *const i32, b: &/*hint text="['1]"*/i32) -> &/*hint text="['1]"*/i32 { b }
+    """)
+
+    fun `test no hints for fn pointers if disabled`() = doTest("""
+        type T = fn(&str) -> &str;
+    """, showForFnPointers = false)
+
+    private fun doTest(@Language("Rust") code: String, showForFnPointers: Boolean = true) {
+        val service = InlayHintsSettings.instance()
+        val key = RsLifetimeElisionHintsProvider.KEY
+        val settings = RsLifetimeElisionHintsProvider.Settings(showForFnPointers = showForFnPointers)
+        val originalSettings = service.findSettings(key, RsLanguage) { settings }
+        try {
+            service.storeSettings(key, RsLanguage, settings)
+            checkByText(code)
+        } finally {
+            service.storeSettings(key, RsLanguage, originalSettings)
+        }
+    }
+}
@@ -37,6 +37,68 @@ class RsParameterInfoHandlerTest
         fn main() { E::Foo(<caret>); }
     """, "_: u32", 0)
 
+    fun `test generic tuple struct`() = checkByText("""
+        struct Foo<T>(T, u8);
+        fn main() { Foo(1, <caret>); }
+    """, "_: T, _: u8", 1)
+
+    fun `test generic enum variant`() = checkByText("""
+        enum Option<T> { Some(T), None }
+        use Option::*;
+        fn main() { Some(<caret>); }
+    """, "_: T", 0)
+
+    fun `test re-exported enum variant`() = checkByText("""
+        mod a {
+            pub enum E { V(u32, bool) }
+        }
+        use a::E::V as W;
+        fn main() { W(1, <caret>); }
+    """, "_: u32, _: bool", 1)
+
+    fun `test enum variant through type alias`() = checkByText("""
+        enum E { V(u32, bool) }
+        type A = E;
+        fn main() { A::V(<caret>); }
+    """, "_: u32, _: bool", 0)
+
+    fun `test tuple struct through Self`() = checkByText("""
+        struct Foo(u32, bool);
+        impl Foo {
+            fn new() -> Self { Self(<caret>) }
+        }
+    """, "_: u32, _: bool", 0)
+
+    fun `test closure parameter bound`() = checkByText("""
+        #[lang = "fn_once"] trait FnOnce<Args> { type Output; }
+        #[lang = "fn_mut"] trait FnMut<Args>: FnOnce<Args> {}
+        #[lang = "fn"] trait Fn<Args>: FnMut<Args> {}
+        fn filter<P: FnMut(&u32) -> bool>(p: P) {}
+        fn main() { filter(|x<caret>| true); }
+    """, "p: FnMut(&u32) -> bool", 0)
+
+    fun `test closure parameter where clause`() = checkByText("""
+        #[lang = "fn_once"] trait FnOnce<Args> { type Output; }
+        #[lang = "fn_mut"] trait FnMut<Args>: FnOnce<Args> {}
+        #[lang = "fn"] trait Fn<Args>: FnMut<Args> {}
+        fn call<F>(a: u8, f: F) where F: Fn(u8, i32) {}
+        fn main() { call(0, <caret>); }
+    """, "a: u8, f: Fn(u8, i32)", 1)
+
+    fun `test closure parameter impl trait`() = checkByText("""
+        #[lang = "fn_once"] trait FnOnce<Args> { type Output; }
+        #[lang = "fn_mut"] trait FnMut<Args>: FnOnce<Args> {}
+        #[lang = "fn"] trait Fn<Args>: FnMut<Args> {}
+        fn call(f: impl FnOnce() -> i32) {}
+        fn main() { call(<caret>); }
+    """, "f: FnOnce() -> i32", 0)
+
+    fun `test call inside macro argument`() = checkByText("""
+        macro_rules! foo { ($($ t:tt)*) => { $($ t)* }; }
+        fn bar(a: u32, b: u32) {}
+        fn main() { foo!(bar(1, <caret>2)); }
+    """, "a: u32, b: u32", 1)
+
     fun `test fn one arg end`() = checkByText("""
         fn foo(arg: u32) {}
         fn main() { foo(42<caret>); }
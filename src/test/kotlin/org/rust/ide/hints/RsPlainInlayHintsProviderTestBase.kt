@@ -13,7 +13,8 @@ abstract class RsPlainInlayHintsProviderTestBase : RsTestBase() {
     protected fun checkByText(
         @Language("Rust") code: String,
         enabledHints: RsPlainHint? = null,
-        smart: Boolean = true
+        smart: Boolean = true,
+        tupleFieldHints: Boolean = false
     ) {
         InlineFile(code.replace(HINT_COMMENT_PATTERN, "<$1/>"))
         if (enabledHints != null) {
@@ -22,12 +23,14 @@ abstract class RsPlainInlayHintsProviderTestBase : RsTestBase() {
             }
         }
         RsPlainHint.SMART_HINTING.set(smart)
+        RsPlainHint.TUPLE_FIELD_HINTS.set(tupleFieldHints)
 
         try {
             myFixture.testInlays({ (it.renderer as HintRenderer).text }) { it.renderer is HintRenderer }
         } finally {
             RsPlainHint.values.forEach { it.option.set(true) }
             RsPlainHint.SMART_HINTING.set(true)
+            RsPlainHint.TUPLE_FIELD_HINTS.set(false)
         }
     }
 
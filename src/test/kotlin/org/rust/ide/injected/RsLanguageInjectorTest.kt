@@ -0,0 +1,210 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.injected
+
+import com.intellij.lang.injection.InjectedLanguageManager
+import com.intellij.psi.PsiLanguageInjectionHost
+import org.intellij.lang.annotations.Language
+import org.rust.ProjectDescriptor
+import org.rust.RsTestBase
+import org.rust.WithDependencyRustProjectDescriptor
+import org.rust.lang.core.psi.RsMacroArgument
+import org.rust.lang.core.psi.ext.descendantsOfType
+
+class RsLanguageInjectorTest : RsTestBase() {
+
+    fun `test Regex new`() = doTest("""
+        fn main() {
+            let re = Regex::new("\\d+");
+            let re = regex::Regex::new("a+");
+        }
+    """, """RegExp: \d+""", "RegExp: a+")
+
+    fun `test RegexSet new`() = doTest("""
+        fn main() {
+            let set = RegexSet::new(&["a+", "b+"]);
+        }
+    """, "RegExp: a+", "RegExp: b+")
+
+    fun `test other argument`() = doTest("""
+        fn main() {
+            let re = Regex::new(foo, "a+");
+            let re = Foo::new("a+");
+        }
+    """)
+
+    fun `test unqualified associated function`() = doTest("""
+        fn new(s: &str) {}
+        fn main() {
+            new("a+");
+            Regex::new("b+");
+        }
+    """, "RegExp: b+")
+
+    fun `test unqualified associated function of custom rule`() = withRules(
+        RsLanguageInjectionRule("rusqlite::Connection::prepare", 0, "RegExp")
+    ) {
+        doTest("""
+            fn prepare(s: &str) {}
+            fn main() {
+                prepare("SELECT a");
+                Connection::prepare("SELECT b");
+                rusqlite::Connection::prepare("SELECT c");
+            }
+        """, "RegExp: SELECT b", "RegExp: SELECT c")
+    }
+
+    fun `test raw string`() = doTest("""
+        fn main() {
+            let re = Regex::new(r"\d+");
+        }
+    """, """RegExp: \d+""")
+
+    fun `test raw string with hashes`() = doTest("""
+        fn main() {
+            let re = Regex::new(r#"a"b"#);
+            let re = Regex::new(r##"c"#d"##);
+        }
+    """, """RegExp: a"b""", """RegExp: c"#d""")
+
+    fun `test raw string with hashes offsets`() {
+        InlineFile("""
+            fn main() {
+                let re = Regex::new(r##"a"#b"##);
+            }
+        """)
+        val literalStart = myFixture.file.text.indexOf("r##") + "r##\"".length
+        val injectedText = injectedFileAt(literalStart + 2)?.text
+        assertEquals("a\"#b", injectedText)
+        val injectedElement = InjectedLanguageManager.getInstance(project)
+            .findInjectedElementAt(myFixture.file, literalStart + 3)
+        assertEquals("b", injectedElement?.text)
+    }
+
+    fun `test concat`() = doTest("""
+        fn main() {
+            let re = Regex::new(concat!("a+", r#"b""#, "\\d"));
+            let re = Regex::new(concat!("a+", 1));
+        }
+    """, """RegExp: a+b"\d""")
+
+    fun `test macro`() = withRules(RsLanguageInjectionRule("sqlx::query!", 0, "RegExp")) {
+        doTest("""
+            fn main() {
+                sqlx::query!("SELECT \"a\"", 1);
+                query!("SELECT b");
+                other::query!("SELECT c");
+            }
+        """, """RegExp: SELECT "a"""", "RegExp: SELECT b")
+    }
+
+    fun `test only arguments of configured macros are hosts`() = withRules(
+        RsLanguageInjectionRule("sqlx::query!", 0, "RegExp")
+    ) {
+        InlineFile("""
+            fn main() {
+                query!("SELECT a");
+                query!(a);
+                println!("SELECT b");
+            }
+        """)
+        val hosts = myFixture.file.descendantsOfType<RsMacroArgument>().map { it.isValidHost }
+        assertEquals(listOf(true, false, false), hosts)
+    }
+
+    fun `test macro argument index`() = withRules(RsLanguageInjectionRule("sqlx::query_as!", 1, "RegExp")) {
+        doTest("""
+            fn main() {
+                sqlx::query_as!(User, "SELECT a", (1, 2));
+                sqlx::query_as!({ "b" }, "SELECT b");
+            }
+        """, "RegExp: SELECT a", "RegExp: SELECT b")
+    }
+
+    fun `test macro raw string with hashes`() = withRules(RsLanguageInjectionRule("query!", 0, "RegExp")) {
+        doTest("""
+            fn main() {
+                query!(r#"SELECT "a""#);
+                query!(r##"SELECT "#b"##);
+                query!(br"SELECT \c");
+            }
+        """, """RegExp: SELECT "a"""", """RegExp: SELECT "#b""", """RegExp: SELECT \c""")
+    }
+
+    fun `test macro concat`() = withRules(RsLanguageInjectionRule("query!", 0, "RegExp")) {
+        doTest("""
+            fn main() {
+                query!(concat!("SELECT a ", r#"FROM "b""#), 1);
+                query!(concat!("SELECT a ", b));
+            }
+        """, """RegExp: SELECT a FROM "b"""")
+    }
+
+    fun `test no injection with format placeholders`() = withRules(RsLanguageInjectionRule("exec", 0, "RegExp")) {
+        doTest("""
+            fn main() {
+                exec(&format!("SELECT {} FROM {table}", a));
+                exec("SELECT {{a}}");
+            }
+        """, "RegExp: SELECT {{a}}")
+    }
+
+    fun `test mask format placeholders`() = withRules(
+        RsLanguageInjectionRule("exec", 0, "RegExp", maskFormatPlaceholders = true)
+    ) {
+        doTest("""
+            fn main() {
+                exec(&format!("SELECT {} FROM t WHERE a = {:?}", a, b));
+                exec(&format!(r#"{}"#, a));
+            }
+        """, "RegExp: SELECT ? FROM t WHERE a = ?", "RegExp: ?")
+    }
+
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test method call`() = withRules(
+        RsLanguageInjectionRule("dep_lib_target::Connection::execute", 0, "RegExp"),
+        RsLanguageInjectionRule("other_crate::Connection::prepare", 0, "RegExp")
+    ) {
+        doTest("""
+            fn main() {
+                conn.execute("SELECT a", []);
+                conn.prepare("SELECT b");
+            }
+        """, "RegExp: SELECT a")
+    }
+
+    fun `test unknown language`() = withRules(RsLanguageInjectionRule("exec", 0, "UnknownLanguage")) {
+        doTest("""
+            fn main() {
+                exec("SELECT a");
+            }
+        """)
+    }
+
+    private fun doTest(@Language("Rust") code: String, vararg expected: String) {
+        InlineFile(code)
+        val manager = InjectedLanguageManager.getInstance(project)
+        val injected = myFixture.file.descendantsOfType<PsiLanguageInjectionHost>()
+            .flatMap { manager.getInjectedPsiFiles(it).orEmpty() }
+            .map { it.first.containingFile }
+            .distinct()
+            .map { "${it.language.id}: ${it.text}" }
+        assertEquals(expected.toList(), injected)
+    }
+
+    private fun injectedFileAt(offset: Int) =
+        InjectedLanguageManager.getInstance(project).findInjectedElementAt(myFixture.file, offset)?.containingFile
+
+    private fun withRules(vararg rules: RsLanguageInjectionRule, action: () -> Unit) {
+        val settings = RsLanguageInjectionSettings.getInstance(project)
+        settings.setRules(rules.toList())
+        try {
+            action()
+        } finally {
+            settings.setRules(RsLanguageInjectionSettings.BUILTIN_RULES)
+        }
+    }
+}
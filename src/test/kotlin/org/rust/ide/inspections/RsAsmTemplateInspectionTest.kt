@@ -0,0 +1,50 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+class RsAsmTemplateInspectionTest : RsInspectionsTestBase(RsAsmTemplateInspection::class) {
+    fun `test valid placeholders`() = checkByText("""
+        fn main() {
+            let x = 1;
+            let y: i32;
+            unsafe {
+                asm!("mov {0}, {1}", "add {}, {res}", "{{}}", in(reg) x, out(reg) y, res = in(reg) x, in("eax") x);
+            }
+        }
+    """)
+
+    fun `test positional placeholder out of range`() = checkByText("""
+        fn main() {
+            let x = 1;
+            unsafe {
+                asm!("mov {0}, <error descr="Invalid reference to argument at index 1">{1}</error>", in(reg) x, in("eax") x);
+            }
+        }
+    """)
+
+    fun `test implicit placeholder out of range`() = checkByText("""
+        fn main() {
+            let x = 1;
+            unsafe {
+                asm!("mov {}, <error descr="Invalid reference to argument at index 1">{}</error>", in(reg) x);
+            }
+        }
+    """)
+
+    fun `test unknown named placeholder`() = checkByText("""
+        fn main() {
+            let x = 1;
+            unsafe {
+                asm!("mov {x}, <error descr="There is no argument named `y`">{y:e}</error>", x = in(reg) x);
+            }
+        }
+    """)
+
+    fun `test global asm`() = checkByText("""
+        fn foo() {}
+        global_asm!("call {foo}", "call <error descr="There is no argument named `bar`">{bar}</error>", foo = sym foo);
+    """)
+}
@@ -0,0 +1,84 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class RsBlockingCallInAsyncInspectionTest : RsInspectionsTestBase(RsBlockingCallInAsyncInspection::class) {
+
+    fun `test thread sleep in async function`() = checkByText("""
+        use std::time::Duration;
+        async fn foo() {
+            std::thread::<warning descr="Blocking call of `sleep` in async context">sleep</warning>(Duration::from_secs(1));
+        }
+    """)
+
+    fun `test fs function in async block`() = checkByText("""
+        fn foo() {
+            let _ = async {
+                let _ = std::fs::<warning descr="Blocking call of `read_to_string` in async context">read_to_string</warning>("foo.txt");
+            };
+        }
+    """)
+
+    fun `test mutex lock in async function`() = checkByText("""
+        use std::sync::Mutex;
+        async fn foo(m: &Mutex<i32>) {
+            let _ = m.<warning descr="Blocking call of `lock` in async context">lock</warning>();
+        }
+    """)
+
+    fun `test no warning outside of async context`() = checkByText("""
+        use std::time::Duration;
+        fn foo() {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+    """)
+
+    fun `test no warning in nested sync function`() = checkByText("""
+        use std::time::Duration;
+        async fn foo() {
+            fn bar() {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    """)
+
+    fun `test no warning in spawn_blocking closure`() = checkByText("""
+        use std::time::Duration;
+        fn spawn_blocking<F: FnOnce()>(f: F) {}
+        async fn foo() {
+            spawn_blocking(|| std::thread::sleep(Duration::from_secs(1)));
+        }
+    """)
+
+    fun `test no warning in block_on body`() = checkByText("""
+        use std::time::Duration;
+        struct Runtime;
+        impl Runtime {
+            fn block_on<F>(&self, f: F) {}
+        }
+        async fn foo(rt: Runtime) {
+            rt.block_on(async { std::thread::sleep(Duration::from_secs(1)) });
+        }
+    """)
+
+    fun `test no warning for own function with the same name`() = checkByText("""
+        fn sleep() {}
+        async fn foo() {
+            sleep();
+        }
+    """)
+
+    fun `test no tokio fix without tokio dependency`() = checkFixIsUnavailable("Replace with `tokio::time::sleep(...).await`", """
+        use std::time::Duration;
+        async fn foo() {
+            std::thread::<warning descr="Blocking call of `sleep` in async context">sleep/*caret*/</warning>(Duration::from_secs(1));
+        }
+    """)
+}
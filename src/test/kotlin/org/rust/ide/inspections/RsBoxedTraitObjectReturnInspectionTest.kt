@@ -0,0 +1,50 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class RsBoxedTraitObjectReturnInspectionTest : RsInspectionsTestBase(RsBoxedTraitObjectReturnInspection::class) {
+
+    fun `test single type`() = checkFixByText("Convert to `impl Trait`", """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo(a: bool) -> /*weak_warning descr="Boxed trait object can be replaced with `impl Trait`"*/Box<dyn Trait/*caret*/>/*weak_warning**/ {
+            if a { Box::new(S) } else { Box::new(S) }
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo(a: bool) -> impl Trait {
+            if a { S } else { S }
+        }
+    """, checkWeakWarn = true)
+
+    fun `test different types`() = checkByText("""
+        trait Trait {}
+        struct S1;
+        struct S2;
+        impl Trait for S1 {}
+        impl Trait for S2 {}
+        fn foo(a: bool) -> Box<dyn Trait> {
+            if a { Box::new(S1) } else { Box::new(S2) }
+        }
+    """, checkWeakWarn = true)
+
+    fun `test trait impl`() = checkByText("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        trait Foo { fn foo(&self) -> Box<dyn Trait>; }
+        impl Foo for S {
+            fn foo(&self) -> Box<dyn Trait> { Box::new(S) }
+        }
+    """, checkWeakWarn = true)
+}
@@ -0,0 +1,275 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class RsConstantConditionInspectionTest : RsInspectionsTestBase(RsConstantConditionInspection::class) {
+
+    fun `test unwrap on None`() = checkByText("""
+        fn main() {
+            let x: Option<i32> = None;
+            x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>;
+        }
+    """)
+
+    fun `test expect on Err`() = checkByText("""
+        fn main() {
+            let x: Result<i32, ()> = Err(());
+            x.<warning descr="`expect` on `Err` value always panics">expect("oops")</warning>;
+        }
+    """)
+
+    fun `test unwrap_err on Ok`() = checkByText("""
+        fn main() {
+            let x: Result<i32, ()> = Ok(1);
+            x.<warning descr="`unwrap_err` on `Ok` value always panics">unwrap_err()</warning>;
+        }
+    """)
+
+    fun `test unwrap after is_err check`() = checkByText("""
+        fn foo(r: Result<i32, ()>) {
+            if r.is_err() {
+                r.<warning descr="`unwrap` on `Err` value always panics">unwrap()</warning>;
+            }
+        }
+    """)
+
+    fun `test unwrap in else branch of is_some check`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            if !x.is_some() {
+                x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>;
+            } else {
+                x.unwrap();
+            }
+        }
+    """)
+
+    fun `test unwrap after early return`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            if x.is_some() {
+                return;
+            }
+            x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>;
+        }
+    """)
+
+    fun `test unwrap in lazy binary expression`() = checkByText("""
+        fn foo(x: Option<i32>) -> bool {
+            x.is_none() && x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning> > 0
+        }
+    """)
+
+    fun `test unwrap in match arm`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            match x {
+                None => { x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>; }
+                Some(_) => { x.unwrap(); }
+            }
+        }
+    """)
+
+    fun `test unwrap in else branch of if let`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            if let Some(_) = x {
+                x.unwrap();
+            } else {
+                x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>;
+            }
+        }
+    """)
+
+    fun `test no panic in else branch of refutable if let`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            if let Some(1) = x {} else {
+                x.unwrap();
+            }
+        }
+    """)
+
+    fun `test constant condition after question mark`() = checkByText("""
+        fn foo(x: Option<i32>) -> Option<i32> {
+            x?;
+            if <warning descr="Condition `x.is_none()` is always `false`">x.is_none()</warning> {}
+            x
+        }
+    """)
+
+    fun `test paths are joined`() = checkByText("""
+        fn foo(flag: bool) {
+            let x;
+            if flag { x = Some(1); } else { x = None; }
+            x.unwrap();
+            let y: Option<i32>;
+            if flag { y = None; } else { y = None; }
+            y.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>;
+        }
+    """)
+
+    fun `test reassignment`() = checkByText("""
+        fn main() {
+            let mut x = None;
+            x = Some(1);
+            x.unwrap();
+            x = None;
+            x.<warning descr="`unwrap` on `None` value always panics">unwrap()</warning>;
+        }
+    """)
+
+    fun `test reassignment inside loop`() = checkByText("""
+        fn main() {
+            let mut x = None;
+            for i in 0..3 {
+                if i > 0 {
+                    x.unwrap();
+                }
+                x = Some(i);
+            }
+        }
+    """)
+
+    fun `test variable declared inside loop`() = checkByText("""
+        fn foo(n: i32) {
+            let mut i = 0;
+            loop {
+                let x = if i < n { Some(i) } else { None };
+                x.unwrap();
+                if x.is_none() { break; }
+                i += 1;
+            }
+        }
+    """)
+
+    fun `test mutable borrow`() = checkByText("""
+        fn set(x: &mut Option<i32>) { *x = Some(1); }
+        fn main() {
+            let mut x = None;
+            set(&mut x);
+            x.unwrap();
+        }
+    """)
+
+    fun `test ref mut binding`() = checkByText("""
+        fn main() {
+            let mut x = None;
+            match x {
+                ref mut y => *y = Some(1),
+            }
+            x.unwrap();
+        }
+    """)
+
+    fun `test mutating method`() = checkByText("""
+        fn main() {
+            let mut x = Some(1);
+            x.take();
+            x.unwrap();
+        }
+    """, checkWeakWarn = true)
+
+    fun `test closure`() = checkByText("""
+        fn main() {
+            let mut x = None;
+            let mut set = || x = Some(1);
+            set();
+            x.unwrap();
+        }
+    """)
+
+    fun `test interior mutability`() = checkByText("""
+        use std::cell::RefCell;
+        fn main() {
+            let cell: RefCell<Option<i32>> = RefCell::new(None);
+            if cell.borrow().is_none() {
+                *cell.borrow_mut() = Some(1);
+            }
+            cell.borrow().unwrap();
+        }
+    """)
+
+    fun `test value from macro`() = checkByText("""
+        macro_rules! some { () => { Some(1) }; }
+        fn main() {
+            let x: Option<i32> = None;
+            let x = some!();
+            x.unwrap();
+        }
+    """)
+
+    fun `test assignment in macro`() = checkByText("""
+        macro_rules! set { (${'$'}x:ident) => { ${'$'}x = Some(1); }; }
+        fn main() {
+            let mut x = None;
+            set!(x);
+            x.unwrap();
+        }
+    """)
+
+    fun `test unresolved macro`() = checkByText("""
+        fn main() {
+            let mut x: Option<i32> = None;
+            unknown_macro!(x);
+            x.unwrap();
+        }
+    """)
+
+    fun `test no panic inside macro definition`() = checkByText("""
+        macro_rules! foo { () => { let x: Option<i32> = None; x.unwrap(); }; }
+        fn main() {
+            foo!();
+        }
+    """)
+
+    fun `test unnecessary unwrap`() = checkByText("""
+        fn main() {
+            let x = Some(1);
+            x.<weak_warning descr="Unnecessary `unwrap`: `x` is always `Some` here">unwrap()</weak_warning>;
+        }
+    """, checkWeakWarn = true)
+
+    fun `test replace with if let`() = checkFixByText("Replace with `if let`", """
+        fn foo(x: Option<i32>) -> i32 {
+            if x.is_some() {
+                x.<weak_warning descr="Unnecessary `unwrap`: `x` is always `Some` here">unwrap/*caret*/()</weak_warning> + x.<weak_warning descr="Unnecessary `unwrap`: `x` is always `Some` here">expect("x")</weak_warning>
+            } else {
+                0
+            }
+        }
+    """, """
+        fn foo(x: Option<i32>) -> i32 {
+            if let Some(x) = x {
+                x + x
+            } else {
+                0
+            }
+        }
+    """, checkWeakWarn = true)
+
+    fun `test replace with if let for result`() = checkFixByText("Replace with `if let`", """
+        fn foo(x: Result<i32, ()>) {
+            if x.is_ok() {
+                let _ = x.<weak_warning descr="Unnecessary `unwrap`: `x` is always `Ok` here">unwrap/*caret*/()</weak_warning>;
+            }
+        }
+    """, """
+        fn foo(x: Result<i32, ()>) {
+            if let Ok(x) = x {
+                let _ = x;
+            }
+        }
+    """, checkWeakWarn = true)
+
+    fun `test replace with if let is unavailable if variable is used without unwrap`() = checkFixIsUnavailable("Replace with `if let`", """
+        fn foo(x: Option<i32>) {
+            if x.is_some() {
+                let _ = x.<weak_warning descr="Unnecessary `unwrap`: `x` is always `Some` here">unwrap/*caret*/()</weak_warning>;
+                let _ = x == Some(1);
+            }
+        }
+    """, checkWeakWarn = true)
+}
@@ -60,6 +60,30 @@ class RsDeprecationInspectionTest : RsInspectionsTestBase(RsDeprecationInspectio
         }
     """)
 
+    fun `test deprecated function with note as attribute value`() = checkByText("""
+        #[deprecated = "here could be your reason"]
+        pub fn foo() {
+        }
+
+        fn main() {
+            <warning descr="`foo` is deprecated: here could be your reason">foo</warning>();
+        }
+    """)
+
+    fun `test re-exported deprecated function`() = checkByText("""
+        mod inner {
+            #[deprecated(since="1.0.0", note="use `bar` instead")]
+            pub fn foo() {}
+        }
+        pub use inner::<warning descr="`foo` is deprecated since 1.0.0: use `bar` instead">foo</warning>;
+        pub use inner::<warning descr="`foo` is deprecated since 1.0.0: use `bar` instead">foo</warning> as baz;
+
+        fn main() {
+            <warning descr="`foo` is deprecated since 1.0.0: use `bar` instead">foo</warning>();
+            <warning descr="`baz` is deprecated since 1.0.0: use `bar` instead">baz</warning>();
+        }
+    """)
+
     fun `test deprecated struct`() = checkByText("""
         #[deprecated]
         struct Foo;
@@ -0,0 +1,208 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
+
+class RsIrrefutableLetPatternInspectionTest : RsInspectionsTestBase(RsIrrefutableLetPatternInspection::class) {
+
+    fun `test binding`() = checkByText("""
+        fn main() {
+            let a = 5;
+            if let <warning descr="Irrefutable `if let` pattern">x</warning> = a {}
+        }
+    """)
+
+    fun `test tuple`() = checkByText("""
+        fn main() {
+            let a = (1, true);
+            if let <warning descr="Irrefutable `if let` pattern">(x, _)</warning> = a {}
+            if let (x, true) = a {}
+        }
+    """)
+
+    fun `test single variant enum`() = checkByText("""
+        enum E { V(u32) }
+        enum F { A, B }
+        fn main(e: E, f: F) {
+            if let <warning descr="Irrefutable `if let` pattern">E::V(x)</warning> = e {}
+            if let F::A = f {}
+        }
+    """)
+
+    fun `test nested struct`() = checkByText("""
+        struct S { a: (i32, E) }
+        enum E { V { b: bool } }
+        fn main(s: S) {
+            if let <warning descr="Irrefutable `if let` pattern">S { a: (_, E::V { b }) }</warning> = s {}
+            if let S { a: (_, E::V { b: false }) } = s {}
+        }
+    """)
+
+    fun `test or patterns`() = checkByText("""
+        enum E { A, B }
+        fn main(e: E) {
+            if let <warning descr="Irrefutable `if let` pattern">E::A | E::B</warning> = e {}
+            if let E::A = e {}
+        }
+    """)
+
+    fun `test slice patterns`() = checkByText("""
+        fn main(a: &[i32]) {
+            if let [x, ..] = a {}
+            if let <warning descr="Irrefutable `if let` pattern">xs</warning> = a {}
+        }
+    """)
+
+    fun `test unknown type`() = checkByText("""
+        fn main() {
+            if let Some(x) = foo() {}
+        }
+    """)
+
+    fun `test never type`() = checkByText("""
+        fn main() {
+            if let <warning descr="Irrefutable `if let` pattern">x</warning> = panic!() {}
+        }
+    """)
+
+    fun `test let chain`() = checkByText("""
+        fn main() {
+            let a = 5;
+            if let x = a && x > 0 {}
+        }
+    """)
+
+    fun `test while let`() = checkByText("""
+        fn main() {
+            let a = 5;
+            while let <warning descr="Irrefutable `while let` pattern">x</warning> = a {}
+        }
+    """)
+
+    fun `test single arm match`() = checkByText("""
+        struct S(i32);
+        fn main(s: S, a: Option<i32>) {
+            match s {
+                <warning descr="Irrefutable `match` arm">S(x)</warning> => {}
+            }
+            match s {
+                S(x) if x > 0 => {}
+            }
+            match a {
+                Some(x) => {}
+                None => {}
+            }
+        }
+    """)
+
+    fun `test local non_exhaustive enum`() = checkByText("""
+        #[non_exhaustive]
+        enum E { V(i32) }
+        fn main(e: E) {
+            if let <warning descr="Irrefutable `if let` pattern">E::V(x)</warning> = e {}
+        }
+    """)
+
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test non_exhaustive enum from other crate`() = checkByFileTree("""
+    //- main.rs
+        extern crate dep_lib_target;
+        use dep_lib_target::E;
+        fn main(e: E) {
+            if let E::V(x) = e {}/*caret*/
+        }
+    //- dep-lib/lib.rs
+        #[non_exhaustive]
+        pub enum E { V(i32) }
+    """)
+
+    fun `test allow`() = checkByText("""
+        #[allow(irrefutable_let_patterns)]
+        fn main() {
+            let a = 5;
+            if let x = a {}
+        }
+    """)
+
+    fun `test convert if let to let`() = checkFixByText("Convert to `let`", """
+        fn main() {
+            let a = (1, 2);
+            if let <warning descr="Irrefutable `if let` pattern">(x, y)/*caret*/</warning> = a {
+                println!("{}", x);
+                x + y
+            }
+            foo();
+        }
+    """, """
+        fn main() {
+            let a = (1, 2);
+            let (x, y) = a;
+            println!("{}", x);
+            x + y;
+            foo();
+        }
+    """)
+
+    fun `test convert tail if let to let`() = checkFixByText("Convert to `let`", """
+        fn main() -> i32 {
+            if let <warning descr="Irrefutable `if let` pattern">x/*caret*/</warning> = 5 {
+                x
+            }
+        }
+    """, """
+        fn main() -> i32 {
+            let x = 5;
+            x
+        }
+    """)
+
+    fun `test no fix with else branch`() = checkFixIsUnavailable("Convert to `let`", """
+        fn main() {
+            if let <warning descr="Irrefutable `if let` pattern">x/*caret*/</warning> = 5 {} else {}
+        }
+    """)
+
+    fun `test no fix for or patterns`() = checkFixIsUnavailable("Convert to `let`", """
+        enum E { A, B }
+        fn main(e: E) {
+            if let <warning descr="Irrefutable `if let` pattern">E::A | E::B/*caret*/</warning> = e {}
+        }
+    """)
+
+    fun `test convert while let to loop`() = checkFixByText("Convert to `loop` with `let`", """
+        fn main() {
+            'outer: while let <warning descr="Irrefutable `while let` pattern">x/*caret*/</warning> = 5 {
+                foo(x);
+            }
+        }
+    """, """
+        fn main() {
+            'outer: loop {
+                let x = 5;
+                foo(x);
+            }
+        }
+    """)
+
+    fun `test convert match to let`() = checkFixByText("Convert to `let`", """
+        struct S(i32);
+        fn main(s: S) {
+            match s {
+                <warning descr="Irrefutable `match` arm">S(x)/*caret*/</warning> => {
+                    foo(x);
+                }
+            }
+        }
+    """, """
+        struct S(i32);
+        fn main(s: S) {
+            let S(x) = s;
+            foo(x);
+        }
+    """)
+}
@@ -48,6 +48,14 @@ class RsLivenessInspectionTest : RsInspectionsTestBase(RsLivenessInspection::cla
         }
     """)
 
+    fun `test live variables in asm operands`() = checkByText("""
+        fn foo(x: i32) -> i32 {
+            let y: i32;
+            unsafe { asm!("mov {0}, {1}", out(reg) y, in(reg) x); }
+            y
+        }
+    """)
+
     fun `test live argument condition`() = checkByText("""
         fn foo(cond: bool) -> i32 {
             if cond { 1 } else { 2 }
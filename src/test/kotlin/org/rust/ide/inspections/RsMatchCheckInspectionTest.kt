@@ -1227,4 +1227,314 @@ class RsMatchCheckInspectionTest : RsInspectionsTestBase(RsMatchCheckInspection:
             }
         }
     """)
+
+    fun `test local non_exhaustive enum is exhaustive`() = checkByText("""
+        #[non_exhaustive]
+        enum E { A, B }
+
+        fn main(e: E) {
+            match e {
+                E::A => {}
+                E::B => {}
+            }
+        }
+    """)
+
+    fun `test full range of u8 is exhaustive`() = checkByText("""
+        fn foo(x: u8) {
+            match x {
+                0..=31 => {}
+                32 => {}
+                33..128 => {}
+                128..=255 => {}
+            }
+        }
+    """)
+
+    fun `test overlapping integer ranges useless`() = checkByText("""
+        fn foo(x: i8) {
+            match x {
+                -128..=0 => {}
+                1..=127 => {}
+                <warning descr="Unreachable pattern">5..=10</warning> => {}
+            }
+        }
+    """)
+
+    fun `test range split by other ranges useless`() = checkByText("""
+        fn foo(x: u8) {
+            match x {
+                0..=5 => {}
+                6..=10 => {}
+                <warning descr="Unreachable pattern">2..=8</warning> => {}
+                _ => {}
+            }
+        }
+    """)
+
+    fun `test missing integer range`() = checkFixByText("Add remaining patterns", """
+        fn foo(x: u32) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> x {
+                0..=10 => {}
+            }
+        }
+    """, """
+        fn foo(x: u32) {
+            match x {
+                0..=10 => {}
+                11..=u32::MAX => {}
+            }
+        }
+    """)
+
+    fun `test missing u64 range beyond i64 max`() = checkFixByText("Add remaining patterns", """
+        fn foo(x: u64) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> x {
+                0..=9223372036854775807 => {}
+            }
+        }
+    """, """
+        fn foo(x: u64) {
+            match x {
+                0..=9223372036854775807 => {}
+                _ => {}
+            }
+        }
+    """)
+
+    fun `test missing u64 range up to max`() = checkFixByText("Add remaining patterns", """
+        fn foo(x: u64) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> x {
+                0..=10 => {}
+            }
+        }
+    """, """
+        fn foo(x: u64) {
+            match x {
+                0..=10 => {}
+                11..=u64::MAX => {}
+            }
+        }
+    """)
+
+    fun `test byte string literal pattern`() = checkByText("""
+        fn foo(x: &[u8]) {
+            match x {
+                b"foo" => {}
+                _ => {}
+            }
+        }
+    """)
+
+    fun `test missing integer values`() = checkFixByText("Add remaining patterns", """
+        fn foo(x: i32) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> x {
+                3 => {}
+                1 => {}
+            }
+        }
+    """, """
+        fn foo(x: i32) {
+            match x {
+                3 => {}
+                1 => {}
+                i32::MIN..=0 => {}
+                2 => {}
+                4..=i32::MAX => {}
+            }
+        }
+    """)
+
+    fun `test too many missing integer ranges`() = checkFixByText("Add remaining patterns", """
+        fn foo(x: u8) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> x {
+                1 => {}
+                3 => {}
+                5 => {}
+            }
+        }
+    """, """
+        fn foo(x: u8) {
+            match x {
+                1 => {}
+                3 => {}
+                5 => {}
+                _ => {}
+            }
+        }
+    """)
+
+    fun `test half-open range exhaustive`() = checkByText("""
+        fn foo(x: u8) {
+            match x {
+                0..=9 | 10.. => {}
+            }
+        }
+    """)
+
+    fun `test missing char ranges`() = checkFixByText("Add remaining patterns", """
+        fn foo(c: char) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> c {
+                'a'..='z' => {}
+            }
+        }
+    """, """
+        fn foo(c: char) {
+            match c {
+                'a'..='z' => {}
+                '\u{0}'..='`' => {}
+                '{'..='\u{d7ff}' => {}
+                '\u{e000}'..='\u{10ffff}' => {}
+            }
+        }
+    """)
+
+    fun `test pair of u8 and bool exhaustive`() = checkByText("""
+        fn foo(x: (u8, bool)) {
+            match x {
+                (0..=127, _) => {}
+                (128..=255, true) => {}
+                (128..=255, false) => {}
+            }
+        }
+    """)
+
+    fun `test pair of u8 and bool remaining`() = checkFixByText("Add remaining patterns", """
+        fn foo(x: (u8, bool)) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> x {
+                (0..=127, _) => {}
+                (128..=255, true) => {}
+            }
+        }
+    """, """
+        fn foo(x: (u8, bool)) {
+            match x {
+                (0..=127, _) => {}
+                (128..=255, true) => {}
+                (128..=u8::MAX, false) => {}
+            }
+        }
+    """)
+
+    fun `test slice patterns exhaustive`() = checkByText("""
+        fn foo(s: &[bool]) {
+            match s {
+                [] => {}
+                [_] => {}
+                [first, .., last] => {}
+            }
+        }
+    """)
+
+    fun `test slice patterns useless`() = checkByText("""
+        fn foo(s: &[bool]) {
+            match s {
+                [] => {}
+                [.., _] => {}
+                <warning descr="Unreachable pattern">[_, x]</warning> => {}
+            }
+        }
+    """)
+
+    fun `test missing slice of fixed length`() = checkFixByText("Add remaining patterns", """
+        fn foo(s: &[bool]) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> s {
+                [] => {}
+                [first, .., last] => {}
+            }
+        }
+    """, """
+        fn foo(s: &[bool]) {
+            match s {
+                [] => {}
+                [first, .., last] => {}
+                [_] => {}
+            }
+        }
+    """)
+
+    fun `test missing slice of variable length`() = checkFixByText("Add remaining patterns", """
+        fn foo(s: &[bool]) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> s {
+                [] => {}
+                [true, ..] => {}
+            }
+        }
+    """, """
+        fn foo(s: &[bool]) {
+            match s {
+                [] => {}
+                [true, ..] => {}
+                [false, ..] => {}
+            }
+        }
+    """)
+
+    fun `test array patterns exhaustive`() = checkByText("""
+        fn foo(a: [bool; 2]) {
+            match a {
+                [true, _] => {}
+                [false, ..] => {}
+            }
+        }
+    """)
+
+    fun `test nested or-patterns exhaustive`() = checkByText("""
+        enum E { A(bool), B }
+        fn foo(e: E) {
+            match e {
+                E::A(true | false) => {}
+                E::B => {}
+            }
+        }
+    """)
+
+    fun `test nested or-patterns useless`() = checkByText("""
+        enum E { A(bool), B }
+        fn foo(e: E) {
+            match e {
+                E::A(_) => {}
+                <warning descr="Unreachable pattern">E::A(true | false)</warning> => {}
+                E::B => {}
+            }
+        }
+    """)
+
+    fun `test nested or-patterns remaining`() = checkFixByText("Add remaining patterns", """
+        enum E { A(bool, bool), B }
+        fn foo(e: E) {
+            <error descr="Match must be exhaustive [E0004]">match/*caret*/</error> e {
+                E::A(true, true | false) => {}
+                E::B => {}
+            }
+        }
+    """, """
+        enum E { A(bool, bool), B }
+        fn foo(e: E) {
+            match e {
+                E::A(true, true | false) => {}
+                E::B => {}
+                E::A(false, _) => {}
+            }
+        }
+    """)
+
+    fun `test bindings with subpatterns exhaustive`() = checkByText("""
+        fn foo(x: u8) {
+            match x {
+                small @ 0..=127 => {}
+                big @ 128..=255 => {}
+            }
+        }
+    """)
+
+    fun `test binding with wildcard subpattern useless`() = checkByText("""
+        fn foo(x: bool) {
+            match x {
+                a @ true => {}
+                b @ false => {}
+                <warning descr="Unreachable pattern">c @ _</warning> => {}
+            }
+        }
+    """)
 }
@@ -194,4 +194,34 @@ class RsRedundantElseInspectionTest : RsInspectionsTestBase(RsRedundantElseInspe
             }
         }
     """)
+
+    fun `test irrefutable or pattern`() = checkFixByText("Remove `else`", """
+        enum E { A(u32), B }
+
+        fn main() {
+            let e = E::B;
+            if let E::A(_) | E::B = e {
+            } <warning descr="Redundant `else`"><caret>else</warning> {
+            }
+        }
+    """, """
+        enum E { A(u32), B }
+
+        fn main() {
+            let e = E::B;
+            if let E::A(_) | E::B = e {
+            }
+        }
+    """)
+
+    fun `test refutable or pattern`() = checkByText("""
+        enum E { A, B, C }
+
+        fn main() {
+            let e = E::A;
+            if let E::A | E::B = e {
+            } else {
+            }
+        }
+    """)
 }
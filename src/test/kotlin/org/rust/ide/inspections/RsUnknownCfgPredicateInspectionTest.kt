@@ -0,0 +1,54 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+class RsUnknownCfgPredicateInspectionTest : RsInspectionsTestBase(RsUnknownCfgPredicateInspection::class) {
+    fun `test known options`() = checkByText("""
+        #[cfg(all(unix, not(windows), any(test, doc), target_os = "linux", feature = "foo"))]
+        fn foo() {}
+
+        #[cfg_attr(debug_assertions, derive(Debug))]
+        struct S;
+    """, checkWeakWarn = true)
+
+    fun `test unknown name value option`() = checkByText("""
+        #[cfg(<weak_warning descr="Unknown cfg option `feture`">feture</weak_warning> = "foo")]
+        fn foo() {}
+    """, checkWeakWarn = true)
+
+    fun `test unknown name option`() = checkByText("""
+        #[cfg(any(unix, <weak_warning descr="Unknown cfg option `unixx`">unixx</weak_warning>))]
+        fn foo() {}
+    """, checkWeakWarn = true)
+
+    fun `test unknown operator`() = checkByText("""
+        #[cfg(<weak_warning descr="Unknown cfg operator `and`">and</weak_warning>(unix, windows))]
+        fn foo() {}
+    """, checkWeakWarn = true)
+
+    fun `test cfg_attr predicate`() = checkByText("""
+        #[cfg_attr(<weak_warning descr="Unknown cfg option `feture`">feture</weak_warning> = "foo", derive(Debug))]
+        struct S;
+    """, checkWeakWarn = true)
+
+    fun `test attributes of cfg_attr are not predicates`() = checkByText("""
+        #[cfg_attr(unix, foo, bar(baz))]
+        struct S;
+    """, checkWeakWarn = true)
+
+    fun `test literal operator argument`() = checkByText("""
+        #[cfg(any(unix, <weak_warning descr="Expected a cfg predicate, found a literal">"windows"</weak_warning>))]
+        fn foo() {}
+    """, checkWeakWarn = true)
+
+    fun `test not with several arguments`() = checkByText("""
+        #[cfg(<weak_warning descr="`not` takes exactly one cfg predicate">not</weak_warning>(unix, windows))]
+        fn foo() {}
+
+        #[cfg(<weak_warning descr="`not` takes exactly one cfg predicate">not</weak_warning>())]
+        fn bar() {}
+    """, checkWeakWarn = true)
+}
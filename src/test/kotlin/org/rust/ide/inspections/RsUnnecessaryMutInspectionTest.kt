@@ -0,0 +1,302 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.MockAdditionalCfgOptions
+
+class RsUnnecessaryMutInspectionTest : RsInspectionsTestBase(RsUnnecessaryMutInspection::class) {
+
+    fun `test should annotate unused variable`() = checkByText("""
+        fn main() {
+            let <warning>mut f</warning> = 10;
+        }
+    """)
+
+    fun `test should not annotate if there is not mut`() = checkByText("""
+        fn main() {
+            let f = 10;
+        }
+    """)
+
+    fun `test should not annotate self methods`() = checkByText("""
+        struct Foo { foo: bool }
+        impl Foo {
+            fn push(&mut self, test: i32) {
+            }
+        }
+        fn foo() {
+            let <warning>mut t</warning> = 10;
+            let mut f = Foo { foo: true };
+            f.push(t);
+        }
+    """)
+
+    fun `test should not annotate mutated fields`() = checkByText("""
+        struct Foo { foo: bool }
+        fn foo(b: &mut bool) {}
+        fn bar() {
+            let mut f = Foo { foo: true };
+            foo(&mut f.foo);
+        }
+    """)
+
+    fun `test should annotate mutated parameter`() = checkByText("""
+        fn foo(i: i32) {
+            println!("{:?}", i);
+        }
+
+        fn main() {
+            let <warning descr="Variable `f` does not need to be mutable">mut f</warning> = 10;
+            foo(f);
+        }
+    """)
+
+    fun `test should not annotate mutated parameter`() = checkByText("""
+        fn foo(i: &mut i32) {
+            *i = 20;
+        }
+
+        fn main() {
+            let mut f = 10;
+            foo(&mut f);
+        }
+    """)
+
+    fun `test first mut and second not mut`() = checkByText("""
+        fn main() {
+            let <warning>mut f</warning> = 10;
+            let mut f = 10;
+            f = 20;
+        }
+    """)
+
+    fun `test tuple`() = checkByText("""
+        fn bar(s: &mut (u8, u8)) {}
+
+        fn main() {
+            let <warning>mut a</warning> = 1u8;
+            let <warning>mut b</warning> = 2u8;
+            bar(&mut (a, b));
+        }
+    """)
+
+    fun `test match arms`() = checkByText("""
+        fn main() {
+            let mut a = Some(20);
+            if let Some(ref mut b) = a { *b = 10; }
+
+            match a {
+                Some(ref mut b) => *b = 10,
+                None => {},
+            }
+        }
+    """)
+
+    fun `test should not annotate if used in macro call arguments`() = checkByText("""
+        macro_rules! set { ($ e:expr) => { $ e = 20; } }
+
+        fn main() {
+            let mut w = 10;
+            set!(w);
+            let mut v = 10;
+            println!("{}", v);
+        }
+    """)
+
+    fun `test should annotate if macro before expr`() = checkByText("""
+        fn foo(a: &mut u32) { }
+
+        macro_rules! call_foo { () => { let mut w = 20; w = 20; } }
+
+        fn main() {
+            call_foo!();
+            let <warning>mut<caret> f</warning> = 10;
+        }
+    """)
+
+    fun `test should annotate function parameter`() = checkFixByText("Remove mutable", """
+        fn foo(mut test: i32) {
+            test = 10;
+        }
+        fn foo2(<warning>mut<caret> test</warning>: i32) {
+        }
+    """, """
+        fn foo(mut test: i32) {
+            test = 10;
+        }
+        fn foo2(test: i32) {
+        }
+    """)
+
+    fun `test first not mut and second mut`() = checkFixByText("Remove mutable", """
+        fn main() {
+            let mut f = 10;
+            f = 20;
+            let <warning>mut<caret> f</warning> = 10;
+        }
+    """, """
+        fn main() {
+            let mut f = 10;
+            f = 20;
+            let f = 10;
+        }
+    """)
+
+    fun `test function`() = checkByText("""
+        fn main() {
+            let mut a = 10;
+            let mut foo = || a = 20;
+            foo();
+        }
+    """)
+
+    fun `test initialization after declaration`() = checkByText("""
+        fn main(a: bool) {
+            let <warning descr="Variable `x` does not need to be mutable">mut x</warning>;
+            if a {
+                x = 1;
+            } else {
+                x = 2;
+            }
+            let mut y;
+            y = 1;
+            y = 2;
+        }
+    """)
+
+    fun `test assignment in loop`() = checkByText("""
+        fn main() {
+            let mut x;
+            loop {
+                x = 1;
+            }
+            loop {
+                let <warning>mut y</warning>;
+                y = 1;
+            }
+        }
+    """)
+
+    fun `test mutation in nested block`() = checkByText("""
+        fn main(a: bool) {
+            let mut x = 1;
+            let <warning>mut y</warning> = 1;
+            if a {
+                while a {
+                    x += y;
+                }
+            }
+        }
+    """)
+
+    fun `test field assignment`() = checkByText("""
+        struct S { a: i32 }
+        fn main() {
+            let mut s = S { a: 1 };
+            s.a = 2;
+        }
+    """)
+
+    fun `test mutation through reference`() = checkByText("""
+        struct S { a: i32 }
+        fn main(s: &mut S) {
+            let <warning>mut r</warning> = s;
+            r.a = 2;
+            *r = S { a: 3 };
+        }
+    """)
+
+    fun `test mutable method call`() = checkByText("""
+        struct S;
+        impl S {
+            fn foo(&mut self) {}
+            fn bar(&self) {}
+        }
+        fn main(s: &mut S) {
+            let mut a = S;
+            a.foo();
+            let <warning>mut b</warning> = S;
+            b.bar();
+            let <warning>mut c</warning> = s;
+            c.foo();
+        }
+    """)
+
+    fun `test unresolved method call`() = checkByText("""
+        struct S;
+        fn main() {
+            let mut a = S;
+            a.unknown();
+        }
+    """)
+
+    fun `test ambiguous method call`() = checkByText("""
+        struct S;
+        trait A { fn foo(&self); }
+        trait B { fn foo(&mut self); }
+        impl A for S { fn foo(&self) {} }
+        impl B for S { fn foo(&mut self) {} }
+        fn main() {
+            let mut a = S;
+            a.foo();
+        }
+    """)
+
+    fun `test mutable reference argument`() = checkByText("""
+        fn foo(a: &mut i32) {}
+        fn main() {
+            let mut a = 1;
+            foo(&mut a);
+            let <warning>mut b</warning> = 1;
+            foo(&mut { b });
+        }
+    """)
+
+    fun `test ref mut binding`() = checkByText("""
+        fn main() {
+            let mut a = Some(1);
+            if let Some(ref mut x) = a {
+                *x = 2;
+            }
+        }
+    """)
+
+    fun `test tuple pattern`() = checkFixByText("Remove mutable", """
+        fn main() {
+            let (mut a, <warning>mut/*caret*/ b</warning>) = (1, 2);
+            a = b;
+        }
+    """, """
+        fn main() {
+            let (mut a, b) = (1, 2);
+            a = b;
+        }
+    """)
+
+    fun `test mutated parameter`() = checkByText("""
+        fn foo(mut a: i32, <warning>mut b</warning>: i32) {
+            a = b;
+        }
+    """)
+
+    @MockAdditionalCfgOptions("intellij_rust")
+    fun `test mutated only in cfg disabled code`() = checkByText("""
+        fn main() {
+            let <weak_warning descr="Variable `a` is mutated only in code disabled by `#[cfg]`. Making it immutable breaks other configurations">mut a</weak_warning> = 1;
+            #[cfg(not(intellij_rust))]
+            {
+                a = 2;
+            }
+        }
+    """, checkWeakWarn = true)
+
+    fun `test allow`() = checkByText("""
+        #[allow(unused_mut)]
+        fn main() {
+            let mut a = 1;
+        }
+    """)
+}
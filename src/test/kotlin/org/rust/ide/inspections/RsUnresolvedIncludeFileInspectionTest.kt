@@ -0,0 +1,90 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+class RsUnresolvedIncludeFileInspectionTest : RsInspectionsTestBase(RsUnresolvedIncludeFileInspection::class) {
+
+    fun `test existing file`() = checkByFileTree("""
+        //- main.rs
+            include!("foo.rs");/*caret*/
+            fn main() {
+                let s = include_str!("data.txt");
+                let b = include_bytes!("data.txt");
+            }
+        //- foo.rs
+            struct Foo;
+        //- data.txt
+            Hello
+    """)
+
+    fun `test missing file`() = checkByFileTree("""
+        //- main.rs
+            include!("<error descr="Unresolved file `foo.rs`">foo.rs</error>");/*caret*/
+            fn main() {
+                let s = include_str!("<error descr="Unresolved file `data.txt`">data.txt</error>");
+                let b = include_bytes!("<error descr="Unresolved file `data.bin`">data.bin</error>");
+            }
+    """)
+
+    fun `test path is relative to containing file`() = checkByFileTree("""
+        //- main.rs
+            mod foo;
+        //- foo/mod.rs
+            fn foo() {
+                let a = include_str!("data.txt");/*caret*/
+                let b = include_str!("<error descr="Unresolved file `main.rs`">main.rs</error>");
+                let c = include_str!("../main.rs");
+            }
+        //- foo/data.txt
+            Hello
+    """)
+
+    fun `test missing file in concat`() = checkByFileTree("""
+        //- main.rs
+            include!(<error descr="File not found: `gen/foo.rs`">concat!("gen", "/foo.rs")</error>);/*caret*/
+    """)
+
+    fun `test unknown env variable`() = checkByFileTree("""
+        //- main.rs
+            include!(concat!(env!("OUT_DIR"), "/foo.rs"));/*caret*/
+            fn main() {
+                let s = include_str!(concat!(env!("UNKNOWN_VARIABLE"), "/data.txt"));
+            }
+    """)
+
+    fun `test create file fix`() = checkFixByFileTree("Create file `data.txt`", """
+        //- main.rs
+            mod foo;
+        //- foo/mod.rs
+            fn foo() {
+                let s = include_str!("<error descr="Unresolved file `data.txt`">data.txt/*caret*/</error>");
+            }
+    """, """
+        //- main.rs
+            mod foo;
+        //- foo/mod.rs
+            fn foo() {
+                let s = include_str!("data.txt");
+            }
+        //- foo/data.txt
+    """)
+
+    fun `test create file fix creates directories`() = checkFixByFileTree("Create file `gen/foo.rs`", """
+        //- main.rs
+            include!(<error descr="File not found: `gen/foo.rs`">concat!("gen", "/foo.rs")/*caret*/</error>);
+    """, """
+        //- main.rs
+            include!(concat!("gen", "/foo.rs"));
+        //- gen/foo.rs
+    """)
+
+    fun `test no create file fix for parent directory`() = checkFixIsUnavailableByFileTree("Create file `../data.txt`", """
+        //- main.rs
+            fn main() {
+                let s = include_str!("../<error descr="Unresolved file `data.txt`">data.txt/*caret*/</error>");
+            }
+    """)
+}
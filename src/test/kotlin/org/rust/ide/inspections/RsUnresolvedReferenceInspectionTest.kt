@@ -6,6 +6,11 @@
 package org.rust.ide.inspections
 
 import org.intellij.lang.annotations.Language
+import org.rust.MockAdditionalCfgOptions
+import org.rust.MockEdition
+import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
+import org.rust.cargo.project.workspace.CargoWorkspace
 import org.rust.ide.inspections.import.AutoImportFix
 
 class RsUnresolvedReferenceInspectionTest : RsInspectionsTestBase(RsUnresolvedReferenceInspection::class) {
@@ -160,12 +165,140 @@ class RsUnresolvedReferenceInspectionTest : RsInspectionsTestBase(RsUnresolvedRe
         }
     """, false)
 
-    private fun checkByText(@Language("Rust") text: String, ignoreWithoutQuickFix: Boolean) {
+    fun `test no unresolved reference if include file is missing`() = checkByFileTree("""
+        //- main.rs
+            mod foo {
+                pub struct Foo;
+            }
+
+            include!("generated.rs");
+
+            fn main() {
+                let x = Foo;/*caret*/
+            }
+    """)
+
+    fun `test no unresolved reference if include file in module is missing`() = checkByFileTree("""
+        //- main.rs
+            mod bar {
+                pub struct Foo;
+            }
+
+            mod foo {
+                include!("generated.rs");
+            }
+
+            fn main() {
+                let x = foo::Foo;/*caret*/
+                let y = <error descr="Unresolved reference: `Foo`">Foo</error>;
+            }
+    """)
+
+    @MockAdditionalCfgOptions("intellij_rust")
+    fun `test unresolved reference if include with missing file is disabled by cfg`() = checkByFileTree("""
+        //- main.rs
+            mod foo {
+                pub struct Foo;
+            }
+
+            #[cfg(not(intellij_rust))]
+            include!("generated.rs");
+
+            fn main() {
+                let x = <error descr="Unresolved reference: `Foo`">Foo</error>;/*caret*/
+            }
+    """)
+
+    fun `test unknown attribute`() = checkByText("""
+        #[<error descr="Unresolved reference: `foo`">foo</error>]
+        #[repr(C)]
+        struct S {
+            field: i32
+        }
+
+        struct T {
+            #[<error descr="Unresolved reference: `bar`">bar</error>(baz)]
+            field: i32
+        }
+    """, false)
+
+    fun `test no unresolved reference for builtin attributes`() = checkByText("""
+        #[stable(feature = "foo", since = "1.0.0")]
+        #[rustc_const_stable(feature = "foo", since = "1.0.0")]
+        #[lang = "foo"]
+        #[naked]
+        #[no_coverage]
+        pub fn foo() {}
+
+        #[unstable(feature = "bar", issue = "none")]
+        #[test_case]
+        fn bar() {}
+    """, false)
+
+    fun `test no unresolved reference for attribute macro helper attributes`() = checkByText("""
+        #[<error descr="Unresolved reference: `pin_project`">pin_project</error>]
+        struct S {
+            #[pin]
+            field: i32
+        }
+
+        #[<error descr="Unresolved reference: `async_trait`">async_trait</error>]
+        impl S {
+            #[helper]
+            fn foo() {}
+        }
+    """, false)
+
+    fun `test no unresolved reference for helper attributes of unresolved derive`() = checkByText("""
+        #[derive(Debug, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct S {
+            #[serde(skip)]
+            field: i32
+        }
+    """, false)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test no unresolved reference for custom derive helper attributes`() = checkByFileTree("""
+        //- dep-proc-macro/lib.rs
+            #[proc_macro_derive(ProcMacroName, attributes(helper1, helper2))]
+            pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+        //- lib.rs
+            use dep_proc_macro::ProcMacroName;
+
+            #[derive(ProcMacroName)]
+            #[helper1(foo = "bar")]
+            struct S {
+                #[helper2]
+                field: i32/*caret*/
+            }
+
+            #[derive(ProcMacroName)]
+            enum E {
+                #[helper1]
+                A { #[helper2] field: i32 }
+            }
+
+            #[<error descr="Unresolved reference: `helper1`">helper1</error>]
+            struct NotDerived {
+                #[<error descr="Unresolved reference: `helper2`">helper2</error>]
+                field: i32
+            }
+    """, false)
+
+    private fun checkByText(@Language("Rust") text: String, ignoreWithoutQuickFix: Boolean) =
+        withIgnoreWithoutQuickFix(ignoreWithoutQuickFix) { checkByText(text) }
+
+    private fun checkByFileTree(@Language("Rust") text: String, ignoreWithoutQuickFix: Boolean) =
+        withIgnoreWithoutQuickFix(ignoreWithoutQuickFix) { checkByFileTree(text) }
+
+    private fun withIgnoreWithoutQuickFix(ignoreWithoutQuickFix: Boolean, action: () -> Unit) {
         val inspection = inspection as RsUnresolvedReferenceInspection
         val defaultValue = inspection.ignoreWithoutQuickFix
         try {
             inspection.ignoreWithoutQuickFix = ignoreWithoutQuickFix
-            checkByText(text)
+            action()
         } finally {
             inspection.ignoreWithoutQuickFix = defaultValue
         }
@@ -0,0 +1,154 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+class RsUnusedMustUseInspectionTest : RsInspectionsTestBase(RsUnusedMustUseInspection::class) {
+
+    fun `test must_use type`() = checkByText("""
+        #[must_use]
+        struct S;
+        fn foo() -> S { S }
+        fn main() {
+            <warning descr="Unused `S` that must be used">foo()</warning>;
+        }
+    """)
+
+    fun `test must_use function`() = checkByText("""
+        #[must_use]
+        fn foo() -> i32 { 0 }
+        fn main() {
+            <warning descr="Unused return value of `foo` that must be used">foo()</warning>;
+        }
+    """)
+
+    fun `test must_use method at the end of a chain`() = checkByText("""
+        struct S;
+        impl S {
+            fn bar(&self) -> S { S }
+            #[must_use]
+            fn baz(&self) -> i32 { 0 }
+        }
+        fn main() {
+            <warning descr="Unused return value of `baz` that must be used">S.bar().bar().baz()</warning>;
+        }
+    """)
+
+    fun `test must_use trait method`() = checkByText("""
+        trait Foo {
+            #[must_use]
+            fn foo(&self) -> i32;
+        }
+        struct S;
+        impl Foo for S {
+            fn foo(&self) -> i32 { 0 }
+        }
+        fn main() {
+            <warning descr="Unused return value of `foo` that must be used">S.foo()</warning>;
+        }
+    """)
+
+    fun `test used value`() = checkByText("""
+        #[must_use]
+        struct S;
+        #[must_use]
+        fn foo() -> S { S }
+        fn main() {
+            let _ = foo();
+            let _s = foo();
+            bar(foo());
+        }
+        fn bar(_: S) {}
+    """)
+
+    fun `test not must_use`() = checkByText("""
+        struct S;
+        fn foo() -> S { S }
+        fn main() {
+            foo();
+        }
+    """)
+
+    fun `test allow unused_must_use`() = checkByText("""
+        #[must_use]
+        struct S;
+        fn foo() -> S { S }
+        #[allow(unused_must_use)]
+        fn main() {
+            foo();
+        }
+    """)
+
+    fun `test allow unused`() = checkByText("""
+        #![allow(unused)]
+        #[must_use]
+        struct S;
+        fn foo() -> S { S }
+        fn main() {
+            foo();
+        }
+    """)
+
+    fun `test add let underscore`() = checkFixByText("Add `let _ =`", """
+        #[must_use]
+        fn foo() -> i32 { 0 }
+        fn main() {
+            <warning descr="Unused return value of `foo` that must be used">/*caret*/foo()</warning>;
+        }
+    """, """
+        #[must_use]
+        fn foo() -> i32 { 0 }
+        fn main() {
+            let _ = foo();
+        }
+    """)
+
+    fun `test no unwrap fix for non Result or Option`() = checkFixIsUnavailable("Add `.unwrap()`", """
+        #[must_use]
+        fn foo() -> i32 { 0 }
+        fn main() {
+            <warning descr="Unused return value of `foo` that must be used">/*caret*/foo()</warning>;
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test Result`() = checkByText("""
+        fn foo() -> Result<i32, ()> { Ok(0) }
+        fn main() {
+            <warning descr="Unused `Result` that must be used">foo()</warning>;
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test add unwrap to Result`() = checkFixByText("Add `.unwrap()`", """
+        fn foo() -> Result<i32, ()> { Ok(0) }
+        fn main() {
+            <warning descr="Unused `Result` that must be used">/*caret*/foo()</warning>;
+        }
+    """, """
+        fn foo() -> Result<i32, ()> { Ok(0) }
+        fn main() {
+            foo().unwrap();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test add unwrap to Option`() = checkFixByText("Add `.unwrap()`", """
+        #[must_use]
+        fn foo() -> Option<i32> { None }
+        fn main() {
+            <warning descr="Unused return value of `foo` that must be used">/*caret*/foo()</warning>;
+        }
+    """, """
+        #[must_use]
+        fn foo() -> Option<i32> { None }
+        fn main() {
+            foo().unwrap();
+        }
+    """)
+}
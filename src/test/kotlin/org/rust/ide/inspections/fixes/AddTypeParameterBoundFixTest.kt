@@ -0,0 +1,119 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.inspections.fixes
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.inspections.RsInspectionsTestBase
+import org.rust.ide.inspections.RsUnresolvedReferenceInspection
+
+class AddTypeParameterBoundFixTest : RsInspectionsTestBase(RsUnresolvedReferenceInspection::class) {
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test add inline bound`() = checkFixByText("Add `T: Clone` bound", """
+        fn foo<T>(t: T) {
+            t.<error descr="Unresolved reference: `clone`">clone/*caret*/</error>();
+        }
+    """, """
+        fn foo<T: Clone>(t: T) {
+            t.clone();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test add bound to existing bounds`() = checkFixByText("Add `T: Clone` bound", """
+        fn foo<T: Default>(t: &T) {
+            t.<error descr="Unresolved reference: `clone`">clone/*caret*/</error>();
+        }
+    """, """
+        fn foo<T: Default + Clone>(t: &T) {
+            t.clone();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test add bound to existing where predicate`() = checkFixByText("Add `T: Clone` bound", """
+        fn foo<T>(t: T) where T: Default {
+            t.<error descr="Unresolved reference: `clone`">clone/*caret*/</error>();
+        }
+    """, """
+        fn foo<T>(t: T) where T: Default + Clone {
+            t.clone();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test add new where predicate`() = checkFixByText("Add `T: Clone` bound", """
+        fn foo<T, U>(t: T, u: U) where U: Default {
+            t.<error descr="Unresolved reference: `clone`">clone/*caret*/</error>();
+        }
+    """, """
+        fn foo<T, U>(t: T, u: U) where U: Default, T: Clone {
+            t.clone();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test type parameter of impl`() = checkFixByText("Add `T: Clone` bound", """
+        struct S<T>(T);
+        impl<T> S<T> {
+            fn foo(&self) {
+                self.0.<error descr="Unresolved reference: `clone`">clone/*caret*/</error>();
+            }
+        }
+    """, """
+        struct S<T>(T);
+        impl<T: Clone> S<T> {
+            fn foo(&self) {
+                self.0.clone();
+            }
+        }
+    """)
+
+    fun `test import trait`() = checkFixByText("Add `T: Foo` bound", """
+        mod foo {
+            pub trait Foo {
+                fn foo(&self);
+            }
+        }
+
+        fn bar<T>(t: T) {
+            t.<error descr="Unresolved reference: `foo`">foo/*caret*/</error>();
+        }
+    """, """
+        use foo::Foo;
+
+        mod foo {
+            pub trait Foo {
+                fn foo(&self);
+            }
+        }
+
+        fn bar<T: Foo>(t: T) {
+            t.foo();
+        }
+    """)
+
+    fun `test no fix for concrete type`() = checkFixIsUnavailable("Add `T: Foo` bound", """
+        trait Foo {
+            fn foo(&self);
+        }
+        struct S;
+
+        fn bar(s: S) {
+            s.foo/*caret*/();
+        }
+    """)
+
+    fun `test no fix for generic trait`() = checkFixIsUnavailable("Add `T: Foo` bound", """
+        trait Foo<U> {
+            fn foo(&self, u: U);
+        }
+
+        fn bar<T>(t: T) {
+            t.foo/*caret*/(1);
+        }
+    """)
+}
@@ -5,11 +5,14 @@
 
 package org.rust.ide.inspections.import
 
+import com.intellij.application.options.CodeStyle
 import org.rust.ExpandMacros
 import org.rust.MockEdition
 import org.rust.ProjectDescriptor
 import org.rust.WithDependencyRustProjectDescriptor
 import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.ide.formatter.rust
+import org.rust.ide.formatter.settings.ImportsGranularity
 
 class AutoImportFixTest : AutoImportFixTestBase() {
 
@@ -2155,6 +2158,63 @@ class AutoImportFixTest : AutoImportFixTestBase() {
         }
     """)
 
+    fun `test merge import into crate use item with crate granularity`() = withImportsGranularity(ImportsGranularity.CRATE) {
+        checkAutoImportFixByText("""
+            use foo::Foo;
+
+            mod foo {
+                pub struct Foo;
+                pub mod bar {
+                    pub struct Bar;
+                }
+            }
+
+            fn main() {
+                let f = <error descr="Unresolved reference: `Bar`">Bar/*caret*/</error>;
+            }
+        """, """
+            use foo::{bar::Bar, Foo};
+
+            mod foo {
+                pub struct Foo;
+                pub mod bar {
+                    pub struct Bar;
+                }
+            }
+
+            fn main() {
+                let f = Bar/*caret*/;
+            }
+        """)
+    }
+
+    fun `test do not group imports with item granularity`() = withImportsGranularity(ImportsGranularity.ITEM) {
+        checkAutoImportFixByText("""
+            use foo::Foo;
+
+            mod foo {
+                pub struct Foo;
+                pub struct Bar;
+            }
+
+            fn main() {
+                let f = <error descr="Unresolved reference: `Bar`">Bar/*caret*/</error>;
+            }
+        """, """
+            use foo::Foo;
+            use foo::Bar;
+
+            mod foo {
+                pub struct Foo;
+                pub struct Bar;
+            }
+
+            fn main() {
+                let f = Bar/*caret*/;
+            }
+        """)
+    }
+
     @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
     fun `test import outer item in doctest injection`() = checkAutoImportFixByFileTreeWithouHighlighting("""
     //- lib.rs
@@ -2305,4 +2365,6 @@ class AutoImportFixTest : AutoImportFixTestBase() {
         }
     """)
 
+    private fun withImportsGranularity(granularity: ImportsGranularity, action: () -> Unit) =
+        withOptionValue(CodeStyle.getSettings(project).rust::IMPORTS_GRANULARITY, granularity.ordinal, action)
 }
@@ -19,6 +19,19 @@ class RsTypeCheckInspectionTest : RsInspectionsTestBase(RsTypeCheckInspection::c
         }
     """)
 
+    fun `test let else branch does not diverge E0308`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            let Some(_) = x else <error descr="`else` clause of `let...else` does not diverge [E0308]">{ 1 }</error>;
+        }
+    """)
+
+    fun `test let else branch diverges`() = checkByText("""
+        fn foo(x: Option<i32>) {
+            let Some(_) = x else { return };
+            let Some(_) = x else { loop {} };
+        }
+    """)
+
     fun `test typecheck in constant`() = checkByText("""
         const A: u8 = <error>1u16</error>;
     """)
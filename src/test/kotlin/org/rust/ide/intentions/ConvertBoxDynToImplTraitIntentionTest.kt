@@ -0,0 +1,192 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.intentions.ConvertReturnTypeIntentionBase.Testmarks
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class ConvertBoxDynToImplTraitIntentionTest : RsIntentionTestBase(ConvertBoxDynToImplTraitIntention()) {
+    fun `test simple`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> Box<dyn /*caret*/Trait> {
+            Box::new(S)
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> impl Trait {
+            S
+        }
+    """)
+
+    fun `test keep bounds`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> Box<dyn /*caret*/Trait + Send> {
+            Box::new(S)
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> impl Trait + Send {
+            S
+        }
+    """)
+
+    fun `test return points`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo(a: bool, b: i32) -> Box<dyn /*caret*/Trait> {
+            if a {
+                return Box::new(S);
+            }
+            match b {
+                0 => Box::new(S),
+                _ => if a { Box::new(S) } else { panic!() }
+            }
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo(a: bool, b: i32) -> impl Trait {
+            if a {
+                return S;
+            }
+            match b {
+                0 => S,
+                _ => if a { S } else { panic!() }
+            }
+        }
+    """)
+
+    fun `test boxed method`() = doAvailableTest("""
+        trait Trait {
+            fn boxed(self) -> Box<Self> where Self: Sized { Box::new(self) }
+        }
+        struct S;
+        impl Trait for S {}
+        fn foo() -> Box<dyn /*caret*/Trait> {
+            S.boxed()
+        }
+    """, """
+        trait Trait {
+            fn boxed(self) -> Box<Self> where Self: Sized { Box::new(self) }
+        }
+        struct S;
+        impl Trait for S {}
+        fn foo() -> impl Trait {
+            S
+        }
+    """)
+
+    fun `test ignore returns of nested closures`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> Box<dyn /*caret*/Trait> {
+            let f = || { return 1; };
+            Box::new(S)
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> impl Trait {
+            let f = || { return 1; };
+            S
+        }
+    """)
+
+    fun `test add lifetime capture`() = doAvailableTest("""
+        trait Trait {}
+        struct Wrapper<'a>(&'a i32);
+        impl<'a> Trait for Wrapper<'a> {}
+        struct S { x: i32 }
+        impl S {
+            fn foo(&self) -> Box<dyn /*caret*/Trait> {
+                Box::new(Wrapper(&self.x))
+            }
+        }
+    """, """
+        trait Trait {}
+        struct Wrapper<'a>(&'a i32);
+        impl<'a> Trait for Wrapper<'a> {}
+        struct S { x: i32 }
+        impl S {
+            fn foo(&self) -> impl Trait + '_ {
+                Wrapper(&self.x)
+            }
+        }
+    """)
+
+    fun `test different types`() = doAvailableTest("""
+        trait Trait {}
+        struct S1;
+        struct S2;
+        impl Trait for S1 {}
+        impl Trait for S2 {}
+        fn foo(a: bool) -> Box<dyn /*caret*/Trait> {
+            if a { Box::new(S1) } else { Box::new(S2) }
+        }
+    """, """
+        trait Trait {}
+        struct S1;
+        struct S2;
+        impl Trait for S1 {}
+        impl Trait for S2 {}
+        fn foo(a: bool) -> Box<dyn Trait> {
+            if a { Box::new(S1) } else { Box::new(S2) }
+        }
+    """, Testmarks.conflict)
+
+    fun `test returned box`() = doAvailableTest("""
+        trait Trait {}
+        fn bar() -> Box<dyn Trait> { unimplemented!() }
+        fn foo() -> Box<dyn /*caret*/Trait> {
+            bar()
+        }
+    """, """
+        trait Trait {}
+        fn bar() -> Box<dyn Trait> { unimplemented!() }
+        fn foo() -> Box<dyn Trait> {
+            bar()
+        }
+    """, Testmarks.conflict)
+
+    fun `test trait method`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        trait Foo {
+            fn foo(&self) -> Box<dyn /*caret*/Trait> { Box::new(S) }
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        trait Foo {
+            fn foo(&self) -> Box<dyn Trait> { Box::new(S) }
+        }
+    """, Testmarks.conflict)
+
+    fun `test unavailable outside of return type`() = doUnavailableTest("""
+        trait Trait {}
+        fn foo(x: Box<dyn /*caret*/Trait>) -> Box<dyn Trait> { x }
+    """)
+
+    fun `test unavailable for box of non-trait type`() = doUnavailableTest("""
+        fn foo() -> Box</*caret*/i32> { Box::new(0) }
+    """)
+}
@@ -0,0 +1,147 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class ConvertImplTraitToBoxDynIntentionTest : RsIntentionTestBase(ConvertImplTraitToBoxDynIntention()) {
+    fun `test simple`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> impl /*caret*/Trait {
+            S
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> Box<dyn Trait + Send + Sync> {
+            Box::new(S)
+        }
+    """)
+
+    fun `test keep bounds`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> impl /*caret*/Trait + Send {
+            S
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo() -> Box<dyn Trait + Send + Sync> {
+            Box::new(S)
+        }
+    """)
+
+    fun `test return points`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo(a: bool) -> impl /*caret*/Trait {
+            if a {
+                return S;
+            }
+            if !a { S } else { panic!() }
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn foo(a: bool) -> Box<dyn Trait + Send + Sync> {
+            if a {
+                return Box::new(S);
+            }
+            if !a { Box::new(S) } else { panic!() }
+        }
+    """)
+
+    fun `test add lifetime capture`() = doAvailableTest("""
+        trait Trait {}
+        struct Wrapper<'a>(&'a i32);
+        impl<'a> Trait for Wrapper<'a> {}
+        fn foo(x: &i32) -> impl /*caret*/Trait {
+            Wrapper(x)
+        }
+    """, """
+        trait Trait {}
+        struct Wrapper<'a>(&'a i32);
+        impl<'a> Trait for Wrapper<'a> {}
+        fn foo(x: &i32) -> Box<dyn Trait + Send + Sync + '_> {
+            Box::new(Wrapper(x))
+        }
+    """)
+
+    fun `test keep auto traits used by caller`() = doAvailableTest("""
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn spawn<T: Send + 'static>(t: T) {}
+        fn foo() -> impl /*caret*/Trait {
+            S
+        }
+        fn main() {
+            spawn(foo());
+        }
+    """, """
+        trait Trait {}
+        struct S;
+        impl Trait for S {}
+        fn spawn<T: Send + 'static>(t: T) {}
+        fn foo() -> Box<dyn Trait + Send + Sync> {
+            Box::new(S)
+        }
+        fn main() {
+            spawn(foo());
+        }
+    """)
+
+    fun `test no auto traits for rc`() = doAvailableTest("""
+        use std::rc::Rc;
+        trait Trait {}
+        struct S;
+        impl Trait for Rc<S> {}
+        fn foo() -> impl /*caret*/Trait {
+            Rc::new(S)
+        }
+    """, """
+        use std::rc::Rc;
+        trait Trait {}
+        struct S;
+        impl Trait for Rc<S> {}
+        fn foo() -> Box<dyn Trait> {
+            Box::new(Rc::new(S))
+        }
+    """)
+
+    fun `test no sync for field`() = doAvailableTest("""
+        use std::cell::Cell;
+        trait Trait {}
+        struct S { value: Cell<i32> }
+        impl Trait for S {}
+        fn foo() -> impl /*caret*/Trait {
+            S { value: Cell::new(0) }
+        }
+    """, """
+        use std::cell::Cell;
+        trait Trait {}
+        struct S { value: Cell<i32> }
+        impl Trait for S {}
+        fn foo() -> Box<dyn Trait + Send> {
+            Box::new(S { value: Cell::new(0) })
+        }
+    """)
+
+    fun `test unavailable in argument`() = doUnavailableTest("""
+        trait Trait {}
+        fn foo(x: impl /*caret*/Trait) {}
+    """)
+}
@@ -0,0 +1,61 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import org.rust.ide.intentions.ConvertStringLiteralIntentionBase.Testmarks
+
+class ConvertToRawStringIntentionTest : RsIntentionTestBase(ConvertToRawStringIntention()) {
+    fun `test simple`() = doAvailableTest("""
+        const C: &str = "C:\\/*caret*/dir";
+    """, """
+        const C: &str = r"C:\dir";
+    """)
+
+    fun `test quotes`() = doAvailableTest("""
+        const C: &str = "{\"name\": /*caret*/\"foo\"}";
+    """, """
+        const C: &str = r#"{"name": "foo"}"#;
+    """)
+
+    fun `test quotes and hashes`() = doAvailableTest("""
+        const C: &str = "\"#/*caret*/foo\"#";
+    """, """
+        const C: &str = r##""#foo"#"##;
+    """)
+
+    fun `test escapes`() = doAvailableTest("""
+        const C: &str = "/*caret*/a\n\tb\u{44f}\u{1F600}\x41";
+    """, """
+        const C: &str = r"a
+        	bя😀A";
+    """)
+
+    fun `test byte string`() = doAvailableTest("""
+        const C: &[u8] = b"/*caret*/\"a\x41\"";
+    """, """
+        const C: &[u8] = br#""aA""#;
+    """)
+
+    fun `test non-ASCII byte string`() = doAvailableTest("""
+        const C: &[u8] = b"/*caret*/\xFF";
+    """, """
+        const C: &[u8] = b"\xFF";
+    """, Testmarks.nonAsciiByteString)
+
+    fun `test carriage return`() = doAvailableTest("""
+        const C: &str = "/*caret*/a\r\nb";
+    """, """
+        const C: &str = "a\r\nb";
+    """, Testmarks.carriageReturnInRawString)
+
+    fun `test unavailable in raw string`() = doUnavailableTest("""
+        const C: &str = r"/*caret*/foo";
+    """)
+
+    fun `test unavailable in char literal`() = doUnavailableTest("""
+        const C: char = '/*caret*/a';
+    """)
+}
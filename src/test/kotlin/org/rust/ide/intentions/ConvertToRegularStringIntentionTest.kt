@@ -0,0 +1,57 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import org.rust.ide.intentions.ConvertStringLiteralIntentionBase.Testmarks
+
+class ConvertToRegularStringIntentionTest : RsIntentionTestBase(ConvertToRegularStringIntention()) {
+    fun `test simple`() = doAvailableTest("""
+        const C: &str = r"C:\/*caret*/dir";
+    """, """
+        const C: &str = "C:\\dir";
+    """)
+
+    fun `test quotes`() = doAvailableTest("""
+        const C: &str = r#"{"name": /*caret*/"foo"}"#;
+    """, """
+        const C: &str = "{\"name\": \"foo\"}";
+    """)
+
+    fun `test multiline`() = doAvailableTest("""
+        const C: &str = r"/*caret*/a
+        	b";
+    """, """
+        const C: &str = "a\n\tb";
+    """)
+
+    fun `test control characters`() = doAvailableTest("""
+        const C: &str = r"/*caret*/a${"\u0007"}b";
+    """, """
+        const C: &str = "a\u{7}b";
+    """)
+
+    fun `test non-ASCII characters are kept`() = doAvailableTest("""
+        const C: &str = r"/*caret*/я😀";
+    """, """
+        const C: &str = "я😀";
+    """)
+
+    fun `test byte string`() = doAvailableTest("""
+        const C: &[u8] = br#"/*caret*/"a\b""#;
+    """, """
+        const C: &[u8] = b"\"a\\b\"";
+    """)
+
+    fun `test non-ASCII byte string`() = doAvailableTest("""
+        const C: &[u8] = br"/*caret*/я";
+    """, """
+        const C: &[u8] = br"я";
+    """, Testmarks.nonAsciiByteString)
+
+    fun `test unavailable in regular string`() = doUnavailableTest("""
+        const C: &str = "/*caret*/foo";
+    """)
+}
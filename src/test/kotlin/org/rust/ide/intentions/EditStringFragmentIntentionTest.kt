@@ -0,0 +1,67 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import com.intellij.lang.injection.InjectedLanguageManager
+import com.intellij.openapi.command.WriteCommandAction
+import com.intellij.openapi.fileTypes.PlainTextLanguage
+import com.intellij.psi.ElementManipulators
+import org.intellij.lang.annotations.Language
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.ext.descendantsOfType
+
+class EditStringFragmentIntentionTest : RsIntentionTestBase(EditStringFragmentIntention()) {
+    fun `test inject unescaped value`() = doTest("""
+        const C: &str = /*caret*/"{\"name\": \"foo\"}";
+    """, """{"name": "foo"}""")
+
+    fun `test inject raw string value`() = doTest("""
+        const C: &[u8] = br#"a/*caret*/"b"#;
+    """, """a"b""")
+
+    fun `test escape edited text`() = doEditTest("""
+        const C: &str = "/*caret*/";
+    """, "a\"b\\c\nd", """
+        const C: &str = "a\"b\\c\nd";
+    """)
+
+    fun `test add hashes to raw string on edit`() = doEditTest("""
+        const C: &str = r"/*caret*/";
+    """, "a\"#b", """
+        const C: &str = r##"a"#b"##;
+    """)
+
+    fun `test unavailable in number`() = doUnavailableTest("""
+        const C: i32 = /*caret*/123;
+    """)
+
+    fun `test unavailable in unclosed string`() = doUnavailableTest("""
+        const C: &str = "/*caret*/
+    """)
+
+    private fun doTest(@Language("Rust") code: String, expectedFragment: String) {
+        InlineFile(code.trimIndent()).withCaret()
+        myFixture.launchAction(intention)
+        assertEquals(expectedFragment, injectedFragment().text)
+    }
+
+    private fun doEditTest(@Language("Rust") code: String, newFragment: String, @Language("Rust") after: String) {
+        InlineFile(code.trimIndent()).withCaret()
+        myFixture.launchAction(intention)
+        val host = InjectedLanguageManager.getInstance(project).getInjectionHost(injectedFragment()) as RsLitExpr
+        WriteCommandAction.runWriteCommandAction(project) {
+            ElementManipulators.getManipulator(host).handleContentChange(host, newFragment)
+        }
+        myFixture.checkResult(after.trimIndent())
+        // The fragment editor stays attached to the new literal
+        assertEquals(newFragment, injectedFragment().text)
+    }
+
+    private fun injectedFragment() = myFixture.file.descendantsOfType<RsLitExpr>()
+        .flatMap { InjectedLanguageManager.getInstance(project).getInjectedPsiFiles(it).orEmpty() }
+        .map { it.first.containingFile }
+        .single { it.language == PlainTextLanguage.INSTANCE }
+}
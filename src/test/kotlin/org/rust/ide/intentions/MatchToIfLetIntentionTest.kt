@@ -511,4 +511,209 @@ class MatchToIfLetIntentionTest : RsIntentionTestBase(MatchToIfLetIntention()) {
             }
         }
     """)
+
+    fun `test wildcard arm becomes else branch`() = doAvailableTest("""
+        fn main() {
+            let x = Some(42);
+            match x {/*caret*/
+                Some(a) => println!("{}", a),
+                _ => println!("none"),
+            }
+        }
+    """, """
+        fn main() {
+            let x = Some(42);
+            if let Some(a) = x {
+                println!("{}", a)
+            } else {
+                println!("none")
+            }
+        }
+    """)
+
+    fun `test wildcard arm with block becomes else branch`() = doAvailableTest("""
+        fn main() {
+            let x = Some(42);
+            let y = match x {/*caret*/
+                Some(a) => { a + 1 }
+                _ => { 0 }
+            };
+        }
+    """, """
+        fn main() {
+            let x = Some(42);
+            let y = if let Some(a) = x { a + 1 } else { 0 };
+        }
+    """)
+
+    fun `test unavailable second arm is not wildcard`() = doUnavailableTest("""
+        fn main() {
+            let x = Some(42);
+            match x {/*caret*/
+                Some(a) => println!("{}", a),
+                None => println!("none"),
+            }
+        }
+    """)
+
+    fun `test unavailable else arm with guard`() = doUnavailableTest("""
+        fn main() {
+            let x = Some(42);
+            let c = true;
+            match x {/*caret*/
+                Some(a) => println!("{}", a),
+                _ if c => println!("none"),
+            }
+        }
+    """)
+
+    fun `test comments are preserved`() = doAvailableTest("""
+        fn main() {
+            let x = Some(42);
+            match x {/*caret*/
+                // the value is here
+                Some(a) => println!("{}", a),
+                // no value
+                _ => println!("none"),
+            }
+        }
+    """, """
+        fn main() {
+            let x = Some(42);
+            if let Some(a) = x {
+                // the value is here
+                println!("{}", a)
+            } else {
+                // no value
+                println!("none")
+            }
+        }
+    """)
+
+    fun `test nested match`() = doAvailableTest("""
+        fn main() {
+            let x = Some(Some(42));
+            match x {
+                Some(y) => match y {/*caret*/
+                    Some(a) => println!("{}", a),
+                    _ => println!("inner none"),
+                },
+                _ => {}
+            }
+        }
+    """, """
+        fn main() {
+            let x = Some(Some(42));
+            match x {
+                Some(y) => if let Some(a) = y {
+                    println!("{}", a)
+                } else {
+                    println!("inner none")
+                },
+                _ => {}
+            }
+        }
+    """)
+
+    fun `test match inside closure`() = doAvailableTest("""
+        fn main() {
+            let f = |x: Option<i32>| match x {/*caret*/
+                Some(a) => a,
+                _ => 0,
+            };
+        }
+    """, """
+        fn main() {
+            let f = |x: Option<i32>| if let Some(a) = x {
+                a
+            } else {
+                0
+            };
+        }
+    """)
+
+    fun `test trailing comments stay with their arms`() = doAvailableTest("""
+        fn main() {
+            let x = Some(42);
+            match x {/*caret*/
+                Some(a) => println!("{}", a), // the value
+                _ => println!("none"), // no value
+            }
+        }
+    """, """
+        fn main() {
+            let x = Some(42);
+            if let Some(a) = x {
+                println!("{}", a)
+                // the value
+            } else {
+                println!("none")
+                // no value
+            }
+        }
+    """)
+
+    fun `test let else`() = doAvailableTest("""
+        fn foo(x: Option<i32>) {
+            let y = match x {/*caret*/
+                Some(a) => a,
+                _ => return,
+            };
+        }
+    """, """
+        fn foo(x: Option<i32>) {
+            let Some(y) = x else { return };
+        }
+    """)
+
+    fun `test let else with nested pattern`() = doAvailableTest("""
+        fn foo(x: Option<(i32, i32)>) {
+            loop {
+                let y = match x {/*caret*/
+                    Some((a, _)) => { a }
+                    _ => { continue; }
+                };
+            }
+        }
+    """, """
+        fn foo(x: Option<(i32, i32)>) {
+            loop {
+                let Some((y, _)) = x else { continue; };
+            }
+        }
+    """)
+
+    fun `test no let else if wildcard arm does not diverge`() = doAvailableTest("""
+        fn foo(x: Option<i32>) {
+            let y = match x {/*caret*/
+                Some(a) => a,
+                _ => 0,
+            };
+        }
+    """, """
+        fn foo(x: Option<i32>) {
+            let y = if let Some(a) = x {
+                a
+            } else {
+                0
+            };
+        }
+    """)
+
+    fun `test no let else if arm body is not the binding`() = doAvailableTest("""
+        fn foo(x: Option<i32>) {
+            let y = match x {/*caret*/
+                Some(a) => a + 1,
+                _ => return,
+            };
+        }
+    """, """
+        fn foo(x: Option<i32>) {
+            let y = if let Some(a) = x {
+                a + 1
+            } else {
+                return
+            };
+        }
+    """)
 }
@@ -0,0 +1,331 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.intentions
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+class ReplaceUnwrapWithTryIntentionTest : RsIntentionTestBase(ReplaceUnwrapWithTryIntention()) {
+    fun `test available 1`() = doAvailableTest("""
+        fn main() {
+            let a = a.unwrap/*caret*/();
+        }
+    """, """
+        fn main() {
+            let a = a?;
+        }
+    """)
+
+    fun `test available 2`() = doAvailableTest("""
+        fn main() {
+            let a = Ok(12).unwrap/*caret*/();
+        }
+    """, """
+        fn main() {
+            let a = Ok(12)?;
+        }
+    """)
+
+    fun `test available 3`() = doAvailableTest("""
+        fn main() {
+            let a = (a + b).unwrap/*caret*/();
+        }
+    """, """
+        fn main() {
+            let a = (a + b)?;
+        }
+    """)
+
+    fun `test available 4`() = doAvailableTest("""
+        fn main() {
+            let a = a + b.unwrap/*caret*/();
+        }
+    """, """
+        fn main() {
+            let a = a + b?;
+        }
+    """)
+
+    fun `test available 5`() = doAvailableTest("""
+        fn main() {
+            let a = a.unwrap/*caret*/().to_string();
+        }
+    """, """
+        fn main() {
+            let a = a?.to_string();
+        }
+    """)
+
+    fun `test available 6`() = doAvailableTest("""
+        fn main() {
+            let a = a.unwrap().unwrap/*caret*/().unwrap();
+        }
+    """, """
+        fn main() {
+            let a = a.unwrap()?.unwrap();
+        }
+    """)
+
+    fun `test available 7`() = doAvailableTest("""
+        fn main() {
+            let a = a.unwrap  /*caret*/  ();
+        }
+    """, """
+        fn main() {
+            let a = a?;
+        }
+    """)
+
+    fun `test available 8`() = doAvailableTest("""
+        fn main() {
+            let a = a.unwrap(b.unwrap(/*caret*/));
+        }
+    """, """
+        fn main() {
+            let a = a.unwrap(b?);
+        }
+    """)
+
+    fun `test unavailable 1`() = doUnavailableTest("""
+        fn main() {
+            let a = a.foo/*caret*/();
+        }
+    """)
+
+    fun `test unavailable 2`() = doUnavailableTest("""
+        fn main() {
+            let a = a.unwrap::<>/*caret*/();
+        }
+    """)
+
+    fun `test unavailable 3`() = doUnavailableTest("""
+        fn main() {
+            let a = a.unwrap::<i32>/*caret*/();
+        }
+    """)
+
+    fun `test unavailable 4`() = doUnavailableTest("""
+        fn main() {
+            let a = a.unwrap/*caret*/(12);
+        }
+    """)
+
+    fun `test unavailable 5`() = doUnavailableTest("""
+        fn main() {
+            let a = a.unwrap/*caret*/;
+        }
+    """)
+
+    fun `test expect`() = doAvailableTest("""
+        fn main() {
+            let a = a.expect/*caret*/("no a");
+        }
+    """, """
+        fn main() {
+            let a = a?;
+        }
+    """)
+
+    fun `test unavailable expect without message`() = doUnavailableTest("""
+        fn main() {
+            let a = a.expect/*caret*/();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test unavailable for non-result receiver`() = doUnavailableTest("""
+        struct S;
+        impl S { fn unwrap(self) -> i32 { 0 } }
+        fn foo() -> Result<i32, ()> {
+            let a = S.unwrap/*caret*/();
+            Ok(a)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test compatible return type`() = doAvailableTest("""
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar() -> Result<i32, E> {
+            let a = foo().unwrap/*caret*/();
+            Ok(a)
+        }
+    """, """
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar() -> Result<i32, E> {
+            let a = foo()?;
+            Ok(a)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test error type convertible with into`() = doAvailableTest("""
+        struct E1;
+        struct E2;
+        impl From<E1> for E2 { fn from(_: E1) -> Self { E2 } }
+        fn foo() -> Result<i32, E1> { Ok(1) }
+        fn bar() -> Result<i32, E2> {
+            let a = foo().unwrap/*caret*/();
+            Ok(a)
+        }
+    """, """
+        struct E1;
+        struct E2;
+        impl From<E1> for E2 { fn from(_: E1) -> Self { E2 } }
+        fn foo() -> Result<i32, E1> { Ok(1) }
+        fn bar() -> Result<i32, E2> {
+            let a = foo()?;
+            Ok(a)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test incompatible error type`() = doAvailableTest("""
+        struct E1;
+        struct E2;
+        fn foo() -> Result<i32, E1> { Ok(1) }
+        fn bar() -> Result<i32, E2> {
+            let a = foo().unwrap/*caret*/();
+            Ok(a)
+        }
+    """, """
+        struct E1;
+        struct E2;
+        fn foo() -> Result<i32, E1> { Ok(1) }
+        fn bar() -> Result<i32, E2> {
+            let a = foo().map_err(|e| unimplemented!())?;
+            Ok(a)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test change unit return type`() = doAvailableTest("""
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar(flag: bool) {
+            if flag { return; }
+            let a = foo().unwrap/*caret*/();
+            drop(a)
+        }
+    """, """
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar(flag: bool) -> Result<(), E> {
+            if flag { return Ok(()); }
+            let a = foo()?;
+            drop(a);
+            Ok(())
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test change return type`() = doAvailableTest("""
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar(flag: bool) -> i32 {
+            let f = || { return 1; };
+            if flag { return f(); }
+            let a = foo().expect/*caret*/("foo");
+            match a {
+                0 => 1,
+                _ => a,
+            }
+        }
+    """, """
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar(flag: bool) -> Result<i32, E> {
+            let f = || { return 1; };
+            if flag { return Ok(f()); }
+            let a = foo()?;
+            match a {
+                0 => Ok(1),
+                _ => Ok(a),
+            }
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test tail expression`() = doAvailableTest("""
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar() -> i32 {
+            foo().unwrap/*caret*/()
+        }
+    """, """
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn bar() -> Result<i32, E> {
+            Ok(foo()?)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test option`() = doAvailableTest("""
+        fn bar(v: Vec<i32>) -> i32 {
+            let a = v.first().unwrap/*caret*/();
+            *a
+        }
+    """, """
+        fn bar(v: Vec<i32>) -> Option<i32> {
+            let a = v.first()?;
+            Some(*a)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test main`() = doAvailableTest("""
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn main() {
+            let a = foo().unwrap/*caret*/();
+            println!("{}", a);
+        }
+    """, """
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        fn main() -> Result<(), Box<dyn std::error::Error>> {
+            let a = foo()?;
+            println!("{}", a);
+            Ok(())
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test unavailable for option in main`() = doUnavailableTest("""
+        fn main() {
+            let a = Some(1).unwrap/*caret*/();
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test unavailable for option in function returning result`() = doUnavailableTest("""
+        fn foo() -> Result<i32, ()> {
+            let a = Some(1).unwrap/*caret*/();
+            Ok(a)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test unavailable in closure returning unit`() = doUnavailableTest("""
+        fn foo() -> Result<i32, ()> {
+            let f = || {
+                let a = Some(1).unwrap/*caret*/();
+            };
+            Ok(1)
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test unavailable in async fn with incompatible return type`() = doUnavailableTest("""
+        struct E;
+        fn foo() -> Result<i32, E> { Ok(1) }
+        async fn bar() -> i32 {
+            foo().unwrap/*caret*/()
+        }
+    """)
+}
@@ -7,6 +7,7 @@ package org.rust.ide.intentions
 
 import org.rust.ProjectDescriptor
 import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.presentation.TypeRenderingLevel
 
 class SpecifyTypeExplicitlyIntentionTest : RsIntentionTestBase(SpecifyTypeExplicitlyIntention()) {
     fun `test inferred type`() = doAvailableTest(
@@ -143,7 +144,6 @@ class SpecifyTypeExplicitlyIntentionTest : RsIntentionTestBase(SpecifyTypeExplic
             fn main() { let var: B = foo(); }
     """)
 
-    // TODO: Don't render default values of type parameters
     @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
     fun `test import std type`() = doAvailableTest("""
             use a::foo;
@@ -154,6 +154,27 @@ class SpecifyTypeExplicitlyIntentionTest : RsIntentionTestBase(SpecifyTypeExplic
             }
             fn main() { let var/*caret*/ = foo(); }
     """, """
+            use a::foo;
+            use std::collections::HashMap;
+
+            mod a {
+                use std::collections::HashMap;
+                pub fn foo() -> HashMap<i32, i32> { HashMap::new() }
+            }
+            fn main() { let var: HashMap<i32, i32> = foo(); }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test import std type with expanded level`() = withLevel(TypeRenderingLevel.EXPANDED) {
+        doAvailableTest("""
+            use a::foo;
+
+            mod a {
+                use std::collections::HashMap;
+                pub fn foo() -> HashMap<i32, i32> { HashMap::new() }
+            }
+            fn main() { let var/*caret*/ = foo(); }
+        """, """
             use a::foo;
             use std::collections::hash_map::RandomState;
             use std::collections::HashMap;
@@ -163,7 +184,22 @@ class SpecifyTypeExplicitlyIntentionTest : RsIntentionTestBase(SpecifyTypeExplic
                 pub fn foo() -> HashMap<i32, i32> { HashMap::new() }
             }
             fn main() { let var: HashMap<i32, i32, RandomState> = foo(); }
-    """)
+        """)
+    }
+
+    fun `test type alias with expanded level`() = withLevel(TypeRenderingLevel.EXPANDED) {
+        doAvailableTest("""
+            struct S<T>(T);
+            type A = S<i32>;
+            fn foo() -> A { S(0) }
+            fn main() { let var/*caret*/ = foo(); }
+        """, """
+            struct S<T>(T);
+            type A = S<i32>;
+            fn foo() -> A { S(0) }
+            fn main() { let var: S<i32> = foo(); }
+        """)
+    }
 
     fun `test ref pat`() = doAvailableTest(
         """ fn main() { let ref var/*caret*/ = 42; } """,
@@ -174,4 +210,13 @@ class SpecifyTypeExplicitlyIntentionTest : RsIntentionTestBase(SpecifyTypeExplic
         """ fn main() { let ref mut var/*caret*/ = 42; } """,
         """ fn main() { let ref mut var: i32 = 42; } """
     )
+
+    private fun withLevel(level: TypeRenderingLevel, action: () -> Unit) {
+        TypeRenderingLevel.lastUsed = level
+        try {
+            action()
+        } finally {
+            TypeRenderingLevel.lastUsed = TypeRenderingLevel.SHORT
+        }
+    }
 }
@@ -82,4 +82,10 @@ class CargoBenchRunLineMarkerContributorTest : RsLineMarkerProviderTestBase() {
             fn no_icon() { assert(true) }
         }
     """)
+
+    fun `test criterion benchmark`() = doTestByText("""
+        fn has_icon(c: &mut Criterion) {} // - Bench has_icon
+        fn no_icon(c: &mut Criterion) {}
+        criterion_group!(benches, has_icon);
+    """)
 }
@@ -0,0 +1,55 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.lineMarkers
+
+import com.intellij.codeInsight.daemon.impl.DaemonCodeAnalyzerImpl
+import org.intellij.lang.annotations.Language
+import org.rust.RsTestBase
+
+class RsDoctestLineMarkerProviderTest : RsTestBase() {
+    fun `test runnable code blocks`() = doTest("""
+        /// ```
+        /// let a = 1;
+        /// ```
+        ///
+        /// ```ignore
+        /// let b = 2;
+        /// ```
+        ///
+        /// ```no_run
+        /// let c = 3;
+        /// ```
+        ///
+        /// ```should_panic
+        /// panic!();
+        /// ```
+        pub fn foo() {}
+    """, 0 to "Run Doctest foo (line 1)", 12 to "Run Doctest foo (line 13)")
+
+    fun `test non-rust code block`() = doTest("""
+        /// ```text
+        /// let a = 1;
+        /// ```
+        pub fn foo() {}
+    """)
+
+    fun `test crate documentation`() = doTest("""
+        //! ```
+        //! let a = 1;
+        //! ```
+    """, 0 to "Run Doctest (line 1)")
+
+    private fun doTest(@Language("Rust") code: String, vararg expected: Pair<Int, String>) {
+        myFixture.configureByText("lib.rs", code.trimIndent())
+        myFixture.doHighlighting()
+        val document = myFixture.editor.document
+        val actual = DaemonCodeAnalyzerImpl.getLineMarkers(document, project)
+            .filter { it.lineMarkerTooltip?.startsWith("Run Doctest") == true }
+            .map { document.getLineNumber(it.startOffset) to it.lineMarkerTooltip }
+            .sortedBy { it.first }
+        assertEquals(expected.toList(), actual)
+    }
+}
@@ -16,7 +16,7 @@ class RsImplsLineMarkerProviderTest : RsLineMarkerProviderTestBase() {
     fun testOneImpl() = doTestByText("""
         trait Foo {}  // - Has implementations
         struct Bar {} // - Has implementations
-        impl Foo for Bar {}
+        impl Foo for Bar {} // - Implements `Foo`
     """)
 
     fun testMultipleImpl() = doTestByText("""
@@ -24,12 +24,12 @@ class RsImplsLineMarkerProviderTest : RsLineMarkerProviderTestBase() {
         mod bar {
             use super::Foo;
             struct Bar {} // - Has implementations
-            impl Foo for Bar {}
+            impl Foo for Bar {} // - Implements `Foo`
         }
         mod baz {
             use super::Foo;
             struct Baz {}  // - Has implementations
-            impl Foo for Baz {}
+            impl Foo for Baz {} // - Implements `Foo`
         }
     """)
 
@@ -44,6 +44,24 @@ class RsImplsLineMarkerProviderTest : RsLineMarkerProviderTestBase() {
         struct
         Bar // - Has implementations
         {}
-        impl Foo for Bar {}
+        impl Foo for Bar {} // - Implements `Foo`
+    """)
+
+    fun `test dyn trait`() = doTestByText("""
+        trait Foo {}  // - Has implementations
+        fn foo(x: &dyn Foo) {} // - Has implementations
+        fn bar(x: &Foo) {}
+    """)
+
+    fun `test trait impl header`() = doTestByText("""
+        trait Foo {}  // - Has implementations
+        struct Bar;   // - Has implementations
+        impl Foo for Bar {} // - Implements `Foo`
+        impl Bar {}
+    """)
+
+    fun `test generic parameter with bounds`() = doTestByText("""
+        trait Foo {}  // - Has implementations
+        fn foo<T: Foo, U: ?Sized, V>(t: T) {} // - Has types satisfying the bounds
     """)
 }
@@ -22,7 +22,7 @@ class RsTraitItemImplLineMarkerProviderTest : RsLineMarkerProviderTestBase() {
             const C2: u32 = 1;
         }
         struct Bar {} // - Has implementations
-        impl Foo for Bar {
+        impl Foo for Bar { // - Implements `Foo`
             fn foo(&self) { // - Implements method in `Foo`
             }
             fn bar(&self) { // - Overrides method in `Foo`
@@ -53,7 +53,7 @@ class RsTraitItemImplLineMarkerProviderTest : RsLineMarkerProviderTestBase() {
         struct
         Bar // - Has implementations
         {}
-        impl Foo for Bar {
+        impl Foo for Bar { // - Implements `Foo`
             ///
             /// Documentation
             ///
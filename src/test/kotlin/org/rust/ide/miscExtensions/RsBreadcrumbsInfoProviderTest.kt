@@ -66,6 +66,18 @@ class RsBreadcrumbsInfoProviderTest : RsTestBase() {
         if true
         match option
         Some(x) =>
+        println!
+    """)
+
+    fun `test macro call breadcrumbs`() = doTextTest("""
+        macro_rules! foo { ($ i:item) => { $ i }; }
+        foo! {
+            fn bar() {
+                /*caret*/
+            }
+        }
+    """, """
+        foo!
     """)
 
     fun `test if else breadcrumbs`() = doTextTest("""
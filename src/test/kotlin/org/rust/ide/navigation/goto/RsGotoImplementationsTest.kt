@@ -7,7 +7,11 @@ package org.rust.ide.navigation.goto
 
 import com.intellij.openapi.actionSystem.IdeActions
 import org.intellij.lang.annotations.Language
+import org.rust.ExpandMacros
 import org.rust.RsTestBase
+import org.rust.lang.core.psi.RsTraitItem
+import org.rust.lang.core.psi.ext.descendantsOfType
+import org.rust.lang.core.psi.ext.findImplementations
 
 class RsGotoImplementationsTest : RsTestBase() {
     fun `test trait`() = doTest("""
@@ -56,6 +60,45 @@ class RsGotoImplementationsTest : RsTestBase() {
         }
     """)
 
+    fun `test dyn trait usage`() = doTest("""
+        trait T {
+            fn test(&self);
+        }
+        impl T for () {
+            fn test(&self) {}
+        }
+        fn foo(x: &dyn T/*caret*/) {}
+    """, """
+        trait T {
+            fn test(&self);
+        }
+        /*caret*/impl T for () {
+            fn test(&self) {}
+        }
+        fn foo(x: &dyn T) {}
+    """)
+
+    @ExpandMacros
+    fun `test impls expanded from the same macro call`() {
+        InlineFile("""
+            trait T {}
+            macro_rules! impl_t { ($($ t:ty),*) => { $(impl T for $ t {})* } }
+            impl_t!(i32, u8);
+        """)
+        val trait = myFixture.file.descendantsOfType<RsTraitItem>().single()
+        assertEquals(2, trait.findImplementations().size)
+    }
+
+    @ExpandMacros
+    fun `test impl expanded from a macro call`() {
+        InlineFile("""
+            trait T {}
+            macro_rules! impl_t { ($ t:ty) => { impl T for $ t {} } }
+            impl_t!(i32);
+        """)
+        val trait = myFixture.file.descendantsOfType<RsTraitItem>().single()
+        assertEquals(1, trait.findImplementations().size)
+    }
 
     private fun doTest(@Language("Rust") before: String, @Language("Rust") after: String) =
         checkEditorAction(before, after, IdeActions.ACTION_GOTO_IMPLEMENTATION)
@@ -0,0 +1,33 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.navigation.goto
+
+import org.intellij.lang.annotations.Language
+import org.rust.ExpandMacros
+import org.rust.RsTestBase
+import org.rust.lang.core.macros.isExpandedFromMacro
+import org.rust.lang.core.psi.ext.RsElement
+
+class RsSymbolNavigationContributorTest : RsTestBase() {
+    fun `test item`() = doTest("""
+        struct Foo;
+    """, "Foo", expectedExpanded = 0, expectedOrdinary = 1)
+
+    @ExpandMacros
+    fun `test item expanded from macro`() = doTest("""
+        macro_rules! foo { ($ i:ident) => { struct $ i; } }
+        foo!(Foo);
+        foo!(Bar);
+    """, "Foo", expectedExpanded = 1, expectedOrdinary = 0)
+
+    private fun doTest(@Language("Rust") code: String, name: String, expectedExpanded: Int, expectedOrdinary: Int) {
+        InlineFile(code)
+        val items = RsSymbolNavigationContributor().getItemsByName(name, name, project, false)
+            .filterIsInstance<RsElement>()
+        assertEquals(expectedExpanded, items.count { it.isExpandedFromMacro })
+        assertEquals(expectedOrdinary, items.count { !it.isExpandedFromMacro })
+    }
+}
@@ -0,0 +1,357 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.application.options.CodeStyle
+import com.intellij.openapi.actionSystem.ex.ActionManagerEx
+import com.intellij.openapi.project.Project
+import org.intellij.lang.annotations.Language
+import org.rust.ProjectDescriptor
+import org.rust.RsTestBase
+import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.formatter.rust
+import org.rust.ide.formatter.settings.RsCodeStyleSettings
+import org.rust.ide.refactoring.generateBuilder.BuilderOptions
+import org.rust.ide.refactoring.generateBuilder.GenerateBuilderUi
+import org.rust.ide.refactoring.generateBuilder.withMockGenerateBuilderUi
+import org.rust.ide.refactoring.generateConstructor.RsStructMemberChooserObject
+
+class GenerateBuilderActionTest : RsTestBase() {
+
+    fun `test required fields`() = doTest("""
+        pub struct Foo/*caret*/ {
+            pub a: i32,
+            b: String,
+        }
+    """, listOf("a: i32", "b: String"), listOf("a: i32"), """
+        pub struct Foo {
+            pub a: i32,
+            b: String,
+        }
+
+        pub struct FooBuilder {
+            a: Option<i32>,
+            b: Option<String>,
+        }
+
+        impl FooBuilder {
+            pub fn new() -> Self {
+                FooBuilder { a: None, b: None }
+            }
+
+            pub fn a(mut self, a: impl Into<i32>) -> Self {
+                self.a = Some(a.into());
+                self
+            }
+
+            pub fn b(mut self, b: impl Into<String>) -> Self {
+                self.b = Some(b.into());
+                self
+            }
+
+            pub fn build(self) -> Result<Foo, &'static str> {
+                Ok(Foo {
+                    a: self.a.ok_or("`a` is not set")?,
+                    b: self.b.unwrap_or_default(),
+                })
+            }
+        }
+    """)
+
+    fun `test all fields defaulted`() = doTest("""
+        struct Foo/*caret*/ {
+            a: i32,
+        }
+    """, listOf("a: i32"), emptyList(), """
+        struct Foo {
+            a: i32,
+        }
+
+        struct FooBuilder {
+            a: Option<i32>,
+        }
+
+        impl FooBuilder {
+            fn new() -> Self {
+                FooBuilder { a: None }
+            }
+
+            fn a(mut self, a: impl Into<i32>) -> Self {
+                self.a = Some(a.into());
+                self
+            }
+
+            fn build(self) -> Foo {
+                Foo {
+                    a: self.a.unwrap_or_default(),
+                }
+            }
+        }
+    """)
+
+    fun `test without trailing commas`() = withOptionValue(customSettings::ADD_TRAILING_COMMA_IN_MULTILINE, false) {
+        doTest("""
+            struct Foo/*caret*/ {
+                a: i32,
+                b: i32
+            }
+        """, listOf("a: i32", "b: i32"), emptyList(), """
+            struct Foo {
+                a: i32,
+                b: i32
+            }
+
+            struct FooBuilder {
+                a: Option<i32>,
+                b: Option<i32>
+            }
+
+            impl FooBuilder {
+                fn new() -> Self {
+                    FooBuilder { a: None, b: None }
+                }
+
+                fn a(mut self, a: impl Into<i32>) -> Self {
+                    self.a = Some(a.into());
+                    self
+                }
+
+                fn b(mut self, b: impl Into<i32>) -> Self {
+                    self.b = Some(b.into());
+                    self
+                }
+
+                fn build(self) -> Foo {
+                    Foo {
+                        a: self.a.unwrap_or_default(),
+                        b: self.b.unwrap_or_default()
+                    }
+                }
+            }
+        """)
+    }
+
+    fun `test setters without into`() = doTest("""
+        pub(crate) struct Foo/*caret*/ {
+            a: i32,
+        }
+    """, listOf("a: i32"), listOf("a: i32"), """
+        pub(crate) struct Foo {
+            a: i32,
+        }
+
+        pub(crate) struct FooBuilder {
+            a: Option<i32>,
+        }
+
+        impl FooBuilder {
+            pub(crate) fn new() -> Self {
+                FooBuilder { a: None }
+            }
+
+            pub(crate) fn a(mut self, a: i32) -> Self {
+                self.a = Some(a);
+                self
+            }
+
+            pub(crate) fn build(self) -> Result<Foo, &'static str> {
+                Ok(Foo {
+                    a: self.a.ok_or("`a` is not set")?,
+                })
+            }
+        }
+    """, useInto = false)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test unwrap_or for fields without Default`() = doTest("""
+        enum Kind { A, B }
+        #[derive(Default)]
+        struct Bar;
+
+        struct Foo/*caret*/ {
+            a: i32,
+            kind: Kind,
+            bar: Bar,
+        }
+    """, listOf("a: i32", "kind: Kind", "bar: Bar"), emptyList(), """
+        enum Kind { A, B }
+        #[derive(Default)]
+        struct Bar;
+
+        struct Foo {
+            a: i32,
+            kind: Kind,
+            bar: Bar,
+        }
+
+        struct FooBuilder {
+            a: Option<i32>,
+            kind: Option<Kind>,
+            bar: Option<Bar>,
+        }
+
+        impl FooBuilder {
+            fn new() -> Self {
+                FooBuilder { a: None, kind: None, bar: None }
+            }
+
+            fn a(mut self, a: impl Into<i32>) -> Self {
+                self.a = Some(a.into());
+                self
+            }
+
+            fn kind(mut self, kind: impl Into<Kind>) -> Self {
+                self.kind = Some(kind.into());
+                self
+            }
+
+            fn bar(mut self, bar: impl Into<Bar>) -> Self {
+                self.bar = Some(bar.into());
+                self
+            }
+
+            fn build(self) -> Foo {
+                Foo {
+                    a: self.a.unwrap_or_default(),
+                    kind: self.kind.unwrap_or(Kind::A),
+                    bar: self.bar.unwrap_or_default(),
+                }
+            }
+        }
+    """)
+
+    fun `test generic struct`() = doTest("""
+        struct Foo<T>/*caret*/ where T: Clone {
+            a: T,
+        }
+    """, listOf("a: T"), emptyList(), """
+        struct Foo<T> where T: Clone {
+            a: T,
+        }
+
+        struct FooBuilder<T> where T: Clone {
+            a: Option<T>,
+        }
+
+        impl<T> FooBuilder<T> where T: Clone {
+            fn new() -> Self {
+                FooBuilder { a: None }
+            }
+
+            fn a(mut self, a: impl Into<T>) -> Self {
+                self.a = Some(a.into());
+                self
+            }
+
+            fn build(self) -> Foo<T> {
+                Foo {
+                    a: self.a.unwrap_or_default(),
+                }
+            }
+        }
+    """)
+
+    fun `test update existing builder`() = doTest("""
+        struct Foo/*caret*/ {
+            a: i32,
+            b: u8,
+        }
+
+        struct FooBuilder {
+            a: Option<i32>,
+        }
+
+        impl FooBuilder {
+            fn new() -> Self {
+                FooBuilder { a: None }
+            }
+
+            fn a(mut self, a: impl Into<i32>) -> Self {
+                self.a = Some(a.into());
+                self
+            }
+
+            fn build(self) -> Result<Foo, &'static str> {
+                Ok(Foo {
+                    a: self.a.ok_or("`a` is not set")?,
+                })
+            }
+        }
+    """, null, emptyList(), """
+        struct Foo {
+            a: i32,
+            b: u8,
+        }
+
+        struct FooBuilder {
+            a: Option<i32>,
+            b: Option<u8>,
+        }
+
+        impl FooBuilder {
+            fn new() -> Self {
+                FooBuilder { a: None, b: None }
+            }
+
+            fn a(mut self, a: impl Into<i32>) -> Self {
+                self.a = Some(a.into());
+                self
+            }
+
+            fn b(mut self, b: impl Into<u8>) -> Self {
+                self.b = Some(b.into());
+                self
+            }
+
+            fn build(self) -> Result<Foo, &'static str> {
+                Ok(Foo {
+                    a: self.a.ok_or("`a` is not set")?,
+                    b: self.b.ok_or("`b` is not set")?,
+                })
+            }
+        }
+    """)
+
+    fun `test tuple struct`() = checkNotAvailable("""
+        struct Foo/*caret*/(i32);
+    """)
+
+    fun `test struct without fields`() = checkNotAvailable("""
+        struct Foo/*caret*/ {}
+    """)
+
+    private val customSettings: RsCodeStyleSettings
+        get() = CodeStyle.getSettings(project).rust
+
+    private fun doTest(
+        @Language("Rust") code: String,
+        allFields: List<String>?,
+        requiredFields: List<String>,
+        @Language("Rust") expected: String,
+        useInto: Boolean = true
+    ) {
+        withMockGenerateBuilderUi(object : GenerateBuilderUi {
+            override fun chooseOptions(project: Project, all: List<RsStructMemberChooserObject>): BuilderOptions? {
+                check(allFields != null) { "Options shouldn't be requested when builder is updated" }
+                assertEquals(allFields, all.map { it.text })
+                return BuilderOptions(all.filter { it.text in requiredFields }.map { it.member }, useInto)
+            }
+
+            override fun confirmUpdate(project: Project, builderName: String): Boolean {
+                check(allFields == null) { "Update shouldn't be requested when builder is created" }
+                return true
+            }
+        }) {
+            checkEditorAction(code, expected, "Rust.GenerateBuilder")
+        }
+    }
+
+    private fun checkNotAvailable(@Language("Rust") code: String) {
+        InlineFile(code)
+        val presentation = myFixture.testAction(ActionManagerEx.getInstanceEx().getAction("Rust.GenerateBuilder"))
+        check(!presentation.isEnabled)
+    }
+}
@@ -0,0 +1,393 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.openapi.actionSystem.ex.ActionManagerEx
+import com.intellij.openapi.project.Project
+import org.intellij.lang.annotations.Language
+import org.rust.ProjectDescriptor
+import org.rust.RsTestBase
+import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.refactoring.generateDisplay.DisplayOptions
+import org.rust.ide.refactoring.generateDisplay.GenerateDisplayUi
+import org.rust.ide.refactoring.generateDisplay.RsEnumVariantChooserObject
+import org.rust.ide.refactoring.generateDisplay.withMockGenerateDisplayUi
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class GenerateDisplayActionTest : RsTestBase() {
+
+    fun `test doc text and snake case name`() = doTest("""
+        enum Foo/*caret*/ {
+            /// Not found
+            /// at all
+            NotFound,
+            PermissionDenied(u32),
+            Other { code: i32 },
+        }
+    """, listOf("NotFound", "PermissionDenied", "Other"), listOf("NotFound", "PermissionDenied", "Other"), """
+        use std::fmt;
+
+        enum Foo {
+            /// Not found
+            /// at all
+            NotFound,
+            PermissionDenied(u32),
+            Other { code: i32 },
+        }
+
+        impl fmt::Display for Foo {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::NotFound => write!(f, "Not found at all"),
+                    Foo::PermissionDenied(..) => write!(f, "permission_denied"),
+                    Foo::Other { .. } => write!(f, "other"),
+                }
+            }
+        }
+    """)
+
+    fun `test escape text`() = doTest("""
+        enum Foo/*caret*/ {
+            /// Bad "{value}"
+            Bad,
+        }
+    """, listOf("Bad"), listOf("Bad"), """
+        use std::fmt;
+
+        enum Foo {
+            /// Bad "{value}"
+            Bad,
+        }
+
+        impl fmt::Display for Foo {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::Bad => write!(f, "Bad \"{{value}}\""),
+                }
+            }
+        }
+    """)
+
+    fun `test excluded variant`() = doTest("""
+        enum Foo/*caret*/ {
+            A,
+            B(i32),
+        }
+    """, listOf("A", "B"), listOf("A"), """
+        use std::fmt;
+
+        enum Foo {
+            A,
+            B(i32),
+        }
+
+        impl fmt::Display for Foo {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::A => write!(f, "a"),
+                    Foo::B(..) => todo!(),
+                }
+            }
+        }
+    """)
+
+    fun `test fmt is already imported`() = doTest("""
+        use std::fmt;
+
+        enum Foo/*caret*/ {
+            A,
+        }
+    """, listOf("A"), listOf("A"), """
+        use std::fmt;
+
+        enum Foo {
+            A,
+        }
+
+        impl fmt::Display for Foo {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::A => write!(f, "a"),
+                }
+            }
+        }
+    """)
+
+    fun `test generic enum`() = doTest("""
+        enum Foo/*caret*/<'a, T> where T: Copy {
+            A(&'a T),
+        }
+    """, listOf("A"), listOf("A"), """
+        use std::fmt;
+
+        enum Foo<'a, T> where T: Copy {
+            A(&'a T),
+        }
+
+        impl<'a, T> fmt::Display for Foo<'a, T> where T: Copy {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::A(..) => write!(f, "a"),
+                }
+            }
+        }
+    """)
+
+    fun `test error with source`() = doTest("""
+        enum Error/*caret*/ {
+            Io(std::io::Error),
+            Parse { source: std::num::ParseIntError, line: u32 },
+            Other(String),
+        }
+    """, listOf("Io", "Parse", "Other"), listOf("Io", "Parse", "Other"), """
+        use std::fmt;
+
+        enum Error {
+            Io(std::io::Error),
+            Parse { source: std::num::ParseIntError, line: u32 },
+            Other(String),
+        }
+
+        impl fmt::Display for Error {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Error::Io(..) => write!(f, "io"),
+                    Error::Parse { .. } => write!(f, "parse"),
+                    Error::Other(..) => write!(f, "other"),
+                }
+            }
+        }
+
+        impl std::error::Error for Error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                match self {
+                    Error::Io(e) => Some(e),
+                    Error::Parse { source, .. } => Some(source),
+                    _ => None,
+                }
+            }
+        }
+    """, generateError = true)
+
+    fun `test error without source`() = doTest("""
+        enum Error/*caret*/ {
+            A,
+        }
+    """, listOf("A"), listOf("A"), """
+        use std::fmt;
+
+        enum Error {
+            A,
+        }
+
+        impl fmt::Display for Error {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Error::A => write!(f, "a"),
+                }
+            }
+        }
+
+        impl std::error::Error for Error {}
+    """, generateError = true)
+
+    fun `test from str`() = doTest("""
+        enum Color/*caret*/ {
+            Red,
+            /// light blue
+            LightBlue,
+            Green,
+        }
+    """, listOf("Red", "LightBlue", "Green"), listOf("Red", "LightBlue"), """
+        use std::fmt;
+        use std::str::FromStr;
+
+        enum Color {
+            Red,
+            /// light blue
+            LightBlue,
+            Green,
+        }
+
+        impl fmt::Display for Color {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Color::Red => write!(f, "red"),
+                    Color::LightBlue => write!(f, "light blue"),
+                    Color::Green => todo!(),
+                }
+            }
+        }
+
+        impl FromStr for Color {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    "red" => Ok(Color::Red),
+                    "light blue" => Ok(Color::LightBlue),
+                    _ => Err(format!("Unknown Color: {}", s)),
+                }
+            }
+        }
+    """, generateFromStr = true)
+
+    fun `test update existing impls`() = doTest("""
+        use std::fmt;
+        use std::str::FromStr;
+
+        enum Error/*caret*/ {
+            A,
+            B,
+            C,
+        }
+
+        impl fmt::Display for Error {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Error::A => write!(f, "a")
+                }
+            }
+        }
+
+        impl std::error::Error for Error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                match self {
+                    _ => None,
+                }
+            }
+        }
+
+        impl FromStr for Error {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    "a" => Ok(Error::A),
+                    _ => Err(format!("Unknown Error: {}", s)),
+                }
+            }
+        }
+    """, null, emptyList(), """
+        use std::fmt;
+        use std::str::FromStr;
+
+        enum Error {
+            A,
+            B,
+            C,
+        }
+
+        impl fmt::Display for Error {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Error::A => write!(f, "a"),
+                    Error::B => write!(f, "b"),
+                    Error::C => write!(f, "c"),
+                }
+            }
+        }
+
+        impl std::error::Error for Error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                match self {
+                    _ => None,
+                }
+            }
+        }
+
+        impl FromStr for Error {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    "a" => Ok(Error::A),
+                    "b" => Ok(Error::B),
+                    "c" => Ok(Error::C),
+                    _ => Err(format!("Unknown Error: {}", s)),
+                }
+            }
+        }
+    """)
+
+    fun `test up to date impl`() = doTest("""
+        use std::fmt;
+
+        enum Foo/*caret*/ {
+            A,
+        }
+
+        impl fmt::Display for Foo {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::A => write!(f, "a"),
+                }
+            }
+        }
+    """, null, emptyList(), """
+        use std::fmt;
+
+        enum Foo {
+            A,
+        }
+
+        impl fmt::Display for Foo {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Foo::A => write!(f, "a"),
+                }
+            }
+        }
+    """, confirmUpdate = false)
+
+    fun `test struct`() = checkNotAvailable("""
+        struct Foo/*caret*/ {
+            a: i32,
+        }
+    """)
+
+    fun `test enum without variants`() = checkNotAvailable("""
+        enum Foo/*caret*/ {}
+    """)
+
+    private fun doTest(
+        @Language("Rust") code: String,
+        allVariants: List<String>?,
+        selectedVariants: List<String>,
+        @Language("Rust") expected: String,
+        generateError: Boolean = false,
+        generateFromStr: Boolean = false,
+        confirmUpdate: Boolean = true
+    ) {
+        withMockGenerateDisplayUi(object : GenerateDisplayUi {
+            override fun chooseOptions(
+                project: Project,
+                all: List<RsEnumVariantChooserObject>,
+                canGenerateFromStr: Boolean
+            ): DisplayOptions? {
+                check(allVariants != null) { "Options shouldn't be requested when impls are updated" }
+                assertEquals(allVariants, all.map { it.text })
+                if (generateFromStr) check(canGenerateFromStr)
+                val selected = all.filter { it.text in selectedVariants }.map { it.variant }
+                return DisplayOptions(selected, generateError, generateFromStr)
+            }
+
+            override fun confirmUpdate(project: Project, enumName: String, newVariants: List<String>): Boolean {
+                check(allVariants == null) { "Update shouldn't be requested when impls are created" }
+                check(confirmUpdate) { "Update shouldn't be requested when impls are up to date" }
+                return true
+            }
+        }) {
+            checkEditorAction(code, expected, "Rust.GenerateDisplay")
+        }
+    }
+
+    private fun checkNotAvailable(@Language("Rust") code: String) {
+        InlineFile(code)
+        val presentation = myFixture.testAction(ActionManagerEx.getInstanceEx().getAction("Rust.GenerateDisplay"))
+        check(!presentation.isEnabled)
+    }
+}
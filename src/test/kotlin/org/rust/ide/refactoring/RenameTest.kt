@@ -5,10 +5,15 @@
 
 package org.rust.ide.refactoring
 
+import com.intellij.refactoring.BaseRefactoringProcessor
 import org.intellij.lang.annotations.Language
 import org.rust.EmptyDescriptor
+import org.rust.MockEdition
 import org.rust.ProjectDescriptor
 import org.rust.RsTestBase
+import org.rust.TestProject
+import org.rust.WithEdition2018DependencyRustProjectDescriptor
+import org.rust.cargo.project.workspace.CargoWorkspace
 import org.rust.lang.core.psi.RsModDeclItem
 import org.rust.lang.core.psi.ext.descendantsOfType
 import org.rust.openapiext.toPsiDirectory
@@ -529,6 +534,20 @@ class RenameTest : RsTestBase() {
         foo! { type T = Spam; }
     """)
 
+    fun `test rename local used in asm operand`() = doTest("value", """
+        fn main() {
+            let /*caret*/x = 1;
+            let mut y: i32;
+            unsafe { asm!("mov {0}, {1}", out(reg) y, in(reg) x); }
+        }
+    """, """
+        fn main() {
+            let value = 1;
+            let mut y: i32;
+            unsafe { asm!("mov {0}, {1}", out(reg) y, in(reg) value); }
+        }
+    """)
+
     fun `test use initialization shorthand after rename`() = doTest("value", """
         struct Foo {
             value: u32
@@ -613,6 +632,171 @@ class RenameTest : RsTestBase() {
         fn foo(value: u32) -> u32 { unimplemented!() }
     """)
 
+    fun `test rename raw identifier to non-keyword`() = doTest("bar", """
+        fn r#type/*caret*/() {}
+        fn main() {
+            r#type();
+        }
+    """, """
+        fn bar() {}
+        fn main() {
+            bar();
+        }
+    """)
+
+    fun `test rename to raw identifier`() = doTest("r#match", """
+        fn foo/*caret*/() {}
+        fn main() {
+            foo();
+        }
+    """, """
+        fn r#match() {}
+        fn main() {
+            r#match();
+        }
+    """)
+
+    fun `test rename to edition 2018 keyword in edition 2015`() = doTest("async", """
+        fn foo/*caret*/() {}
+        fn main() {
+            foo();
+        }
+    """, """
+        fn async() {}
+        fn main() {
+            async();
+        }
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    fun `test rename to edition 2018 keyword in edition 2018`() = doTest("async", """
+        fn foo/*caret*/() {}
+        fn main() {
+            foo();
+        }
+    """, """
+        fn r#async() {}
+        fn main() {
+            r#async();
+        }
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    fun `test rename field to keyword with initialization shorthand`() = doTest("try", """
+        struct S { foo/*caret*/: i32 }
+        fn main() {
+            let foo = 1;
+            let s = S { foo };
+        }
+    """, """
+        struct S { r#try: i32 }
+        fn main() {
+            let foo = 1;
+            let s = S { r#try: foo };
+        }
+    """)
+
+    @ProjectDescriptor(WithEdition2018DependencyRustProjectDescriptor::class)
+    fun `test rename edition 2018 item to edition 2018 keyword`() = checkByDirectory("""
+    //- main.rs
+        extern crate dep_lib_target;
+        fn main() {
+            dep_lib_target::foo();
+        }
+    //- dep-lib/lib.rs
+        pub fn foo/*caret*/() {}
+    """, """
+    //- main.rs
+        extern crate dep_lib_target;
+        fn main() {
+            dep_lib_target::async();
+        }
+    //- dep-lib/lib.rs
+        pub fn r#async() {}
+    """) {
+        renameElementAtCaret(it, "async")
+    }
+
+    @ProjectDescriptor(WithEdition2018DependencyRustProjectDescriptor::class)
+    fun `test rename edition 2018 item to edition 2018 keyword from edition 2015 crate`() = checkByDirectory("""
+    //- main.rs
+        extern crate dep_lib_target;
+        fn main() {
+            dep_lib_target::foo/*caret*/();
+        }
+    //- dep-lib/lib.rs
+        pub fn foo() {}
+    """, """
+    //- main.rs
+        extern crate dep_lib_target;
+        fn main() {
+            dep_lib_target::try();
+        }
+    //- dep-lib/lib.rs
+        pub fn r#try() {}
+    """) {
+        renameElementAtCaret(it, "try")
+    }
+
+    @ProjectDescriptor(WithEdition2018DependencyRustProjectDescriptor::class)
+    fun `test rename raw identifier from edition 2015 crate`() = checkByDirectory("""
+    //- main.rs
+        extern crate dep_lib_target;
+        fn main() {
+            dep_lib_target::r#async/*caret*/();
+        }
+    //- dep-lib/lib.rs
+        pub fn r#async() {}
+    """, """
+    //- main.rs
+        extern crate dep_lib_target;
+        fn main() {
+            dep_lib_target::foo();
+        }
+    //- dep-lib/lib.rs
+        pub fn foo() {}
+    """) {
+        renameElementAtCaret(it, "foo")
+    }
+
+    fun `test rename macro to keyword`() = doTestConflict("match", "`match` is a keyword and can't be used as a macro name", """
+        macro_rules! foo/*caret*/ { () => {} }
+        fn main() {
+            foo!();
+        }
+    """)
+
+    fun `test rename extern crate to keyword`() = doTestConflict("type", "`type` is a keyword and can't be used as a crate name", """
+        extern crate foo/*caret*/;
+    """)
+
+    fun `test rename lifetime to keyword`() = doTestConflict("'match", "`match` is a keyword and can't be used as a lifetime name", """
+        fn foo<'a/*caret*/>(x: &'a i32) {}
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    fun `test rename label to edition 2018 keyword`() = doTestConflict("'async", "`async` is a keyword and can't be used as a label name", """
+        fn foo() {
+            'a/*caret*/: loop { break 'a; }
+        }
+    """)
+
+    private fun renameElementAtCaret(testProject: TestProject, newName: String) {
+        myFixture.configureFromTempProjectFile(testProject.fileWithCaret)
+        myFixture.renameElement(myFixture.elementAtCaret, newName, true, true)
+    }
+
+    private fun doTestConflict(newName: String, expectedConflict: String, @Language("Rust") code: String) {
+        InlineFile(code).withCaret()
+        val element = myFixture.elementAtCaret
+        try {
+            myFixture.renameElement(element, newName, true, true)
+            error("Conflict `$expectedConflict` is expected")
+        } catch (e: BaseRefactoringProcessor.ConflictsInTestsException) {
+            assertEquals(expectedConflict, e.messages.single())
+        }
+    }
+
     private fun doTest(
         newName: String,
         @Language("Rust") before: String,
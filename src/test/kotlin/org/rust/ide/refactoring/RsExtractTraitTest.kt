@@ -0,0 +1,254 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.openapi.project.Project
+import com.intellij.refactoring.BaseRefactoringProcessor
+import org.intellij.lang.annotations.Language
+import org.rust.RsTestBase
+import org.rust.ide.refactoring.extractTrait.ExtractTraitOptions
+import org.rust.ide.refactoring.extractTrait.ExtractTraitUi
+import org.rust.ide.refactoring.extractTrait.RsExtractTraitAction
+import org.rust.ide.refactoring.extractTrait.withMockExtractTraitUi
+import org.rust.lang.core.psi.RsImplItem
+import org.rust.lang.core.psi.ext.RsAbstractable
+
+class RsExtractTraitTest : RsTestBase() {
+    fun `test not available on trait impl`() = doUnavailableTest("""
+        struct S;
+        trait T { fn foo(&self); }
+        impl T for S {
+            /*caret*/fn foo(&self) {}
+        }
+    """)
+
+    fun `test not available on empty impl`() = doUnavailableTest("""
+        struct S;
+        impl S {/*caret*/}
+    """)
+
+    fun `test extract all members`() = doTest("Foo", listOf("foo", "bar"), """
+        struct S;
+
+        impl S {
+            /*caret*/pub fn foo(&self) -> i32 { 0 }
+            fn bar(&self) {}
+        }
+    """, """
+        struct S;
+
+        pub trait Foo {
+            fn foo(&self) -> i32;
+
+            fn bar(&self);
+        }
+
+        impl Foo for S {
+            fn foo(&self) -> i32 { 0 }
+            fn bar(&self) {}
+        }
+    """)
+
+    fun `test extract some members`() = doTest("Foo", listOf("foo"), """
+        struct S;
+
+        impl S {
+            /*caret*/fn foo(&self) -> i32 { 0 }
+            fn bar(&self) {}
+        }
+    """, """
+        struct S;
+
+        trait Foo {
+            fn foo(&self) -> i32;
+        }
+
+        impl S {
+            fn bar(&self) {}
+        }
+
+        impl Foo for S {
+            fn foo(&self) -> i32 { 0 }
+        }
+    """)
+
+    fun `test associated constant and type`() = doTest("Foo", listOf("SIZE", "Output", "size"), """
+        struct S;
+
+        impl S {
+            /*caret*/const SIZE: usize = 1;
+            type Output = i32;
+            fn size(&self) -> usize { Self::SIZE }
+        }
+    """, """
+        struct S;
+
+        trait Foo {
+            const SIZE: usize;
+
+            type Output;
+
+            fn size(&self) -> usize;
+        }
+
+        impl Foo for S {
+            const SIZE: usize = 1;
+            type Output = i32;
+            fn size(&self) -> usize { Self::SIZE }
+        }
+    """)
+
+    fun `test copy used generic parameters and where clause`() = doTest("Getter", listOf("get"), """
+        struct S<'a, T, U>(&'a T, U);
+
+        impl<'a, T, U> S<'a, T, U> where T: Clone {
+            /*caret*/fn get(&self) -> T { self.0.clone() }
+            fn second(&self) -> &U { &self.1 }
+        }
+    """, """
+        struct S<'a, T, U>(&'a T, U);
+
+        trait Getter<T> where T: Clone {
+            fn get(&self) -> T;
+        }
+
+        impl<'a, T, U> S<'a, T, U> where T: Clone {
+            fn second(&self) -> &U { &self.1 }
+        }
+
+        impl<'a, T, U> Getter<T> for S<'a, T, U> where T: Clone {
+            fn get(&self) -> T { self.0.clone() }
+        }
+    """)
+
+    fun `test default bodies`() = doTest("Foo", listOf("foo", "bar"), """
+        struct S;
+
+        impl S {
+            /*caret*/fn foo(&self) -> i32 { 0 }
+            fn bar(&self) -> i32 { self.foo() + 1 }
+        }
+    """, """
+        struct S;
+
+        trait Foo {
+            fn foo(&self) -> i32 { 0 }
+
+            fn bar(&self) -> i32 { self.foo() + 1 }
+        }
+
+        impl Foo for S {}
+    """, defaultBodies = true)
+
+    fun `test import trait in other modules`() = doTest("Getter", listOf("get"), """
+        use a::Data;
+
+        mod a {
+            pub struct Data(pub i32);
+
+            impl Data {
+                /*caret*/pub fn get(&self) -> i32 { self.0 }
+            }
+        }
+
+        fn main() {
+            let _ = Data(0).get();
+        }
+    """, """
+        use a::{Data, Getter};
+
+        mod a {
+            pub struct Data(pub i32);
+
+            pub trait Getter {
+                fn get(&self) -> i32;
+            }
+
+            impl Getter for Data {
+                fn get(&self) -> i32 { self.0 }
+            }
+        }
+
+        fn main() {
+            let _ = Data(0).get();
+        }
+    """)
+
+    fun `test private type in public trait`() = doConflictTest("Foo", listOf("foo"),
+        "`foo` uses private type `Private`, but trait `Foo` would be public", """
+        mod a {
+            struct Private;
+            pub struct S;
+
+            impl S {
+                /*caret*/pub fn foo(&self, p: Private) {}
+            }
+        }
+    """)
+
+    fun `test field in default body`() = doConflictTest("Foo", listOf("foo"),
+        "`foo` uses field `x`, so it can't have a default implementation in trait `Foo`", """
+        struct S { x: i32 }
+
+        impl S {
+            /*caret*/fn foo(&self) -> i32 { self.x }
+        }
+    """, defaultBodies = true)
+
+    fun `test inherent method in default body`() = doConflictTest("Foo", listOf("foo"),
+        "`foo` uses `helper`, which is not a member of trait `Foo`, so it can't have a default implementation", """
+        struct S;
+
+        impl S {
+            /*caret*/fn foo(&self) -> i32 { self.helper() }
+            fn helper(&self) -> i32 { 0 }
+        }
+    """, defaultBodies = true)
+
+    private fun doTest(
+        traitName: String,
+        memberNames: List<String>,
+        @Language("Rust") before: String,
+        @Language("Rust") after: String,
+        defaultBodies: Boolean = false
+    ) {
+        withMockExtractTraitUi(MockExtractTraitUi(traitName, memberNames, defaultBodies)) {
+            checkEditorAction(before, after, "Rust.RsExtractTrait")
+        }
+    }
+
+    private fun doConflictTest(
+        traitName: String,
+        memberNames: List<String>,
+        expectedConflict: String,
+        @Language("Rust") code: String,
+        defaultBodies: Boolean = false
+    ) {
+        try {
+            doTest(traitName, memberNames, code, code, defaultBodies)
+            error("Conflict `$expectedConflict` is expected")
+        } catch (e: BaseRefactoringProcessor.ConflictsInTestsException) {
+            assertEquals(expectedConflict, e.messages.single())
+        }
+    }
+
+    private fun doUnavailableTest(@Language("Rust") code: String) {
+        InlineFile(code.trimIndent()).withCaret()
+        check(!myFixture.testAction(RsExtractTraitAction()).isEnabled)
+    }
+
+    private class MockExtractTraitUi(
+        private val traitName: String,
+        private val memberNames: List<String>,
+        private val defaultBodies: Boolean
+    ) : ExtractTraitUi {
+        override fun chooseOptions(project: Project, impl: RsImplItem, members: List<RsAbstractable>): ExtractTraitOptions {
+            val selected = members.filter { it.name in memberNames }
+            check(selected.size == memberNames.size) { "Not all of $memberNames are offered for extraction" }
+            return ExtractTraitOptions(traitName, selected, defaultBodies)
+        }
+    }
+}
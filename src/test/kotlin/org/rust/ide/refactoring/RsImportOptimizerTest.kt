@@ -5,11 +5,15 @@
 
 package org.rust.ide.refactoring
 
+import com.intellij.application.options.CodeStyle
 import org.intellij.lang.annotations.Language
 import org.rust.ProjectDescriptor
 import org.rust.RsTestBase
 import org.rust.WithStdlibAndDependencyRustProjectDescriptor
 import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.ide.formatter.rust
+import org.rust.ide.formatter.settings.ImportsGranularity
+import org.rust.ide.formatter.settings.ImportsGrouping
 
 class RsImportOptimizerTest: RsTestBase() {
 
@@ -484,6 +488,123 @@ class RsImportOptimizerTest: RsTestBase() {
         mod ccc {}
     """)
 
+    fun `test merge imports with module granularity`() = doTest(ImportsGranularity.MODULE, """
+        use std::collections::HashSet;
+        use std::io;
+        use std::collections::HashMap;
+
+        fn main() {}
+    """, """
+        use std::collections::{HashMap, HashSet};
+        use std::io;
+
+        fn main() {}
+    """)
+
+    fun `test merge imports with crate granularity`() = doTest(ImportsGranularity.CRATE, """
+        use foo::bar::Baz;
+        use foo::qux;
+        use foo::bar::{self, Quux};
+        use foo;
+
+        fn main() {}
+    """, """
+        use foo;
+        use foo::{bar::{self, Baz, Quux}, qux};
+
+        fn main() {}
+    """)
+
+    fun `test split imports with item granularity`() = doTest(ImportsGranularity.ITEM, """
+        use foo::{bar, baz::{self, Qux as Q}};
+
+        fn main() {}
+    """, """
+        use foo::bar;
+        use foo::baz::{self};
+        use foo::baz::Qux as Q;
+
+        fn main() {}
+    """)
+
+    fun `test do not merge imports with visibility, glob imports and cfg-gated imports`() = doTest(ImportsGranularity.MODULE, """
+        use foo::d;
+        pub use foo::b;
+        use foo::*;
+        #[cfg(test)]
+        use foo::c;
+        use foo::a;
+
+        fn main() {}
+    """, """
+        use foo::*;
+        use foo::{a, d};
+        pub use foo::b;
+        #[cfg(test)]
+        use foo::c;
+
+        fn main() {}
+    """)
+
+    fun `test preserve groups`() = doTest(ImportsGrouping.PRESERVE, """
+        use foo::b;
+        use foo::a;
+
+        use bar::d;
+        use bar::c;
+
+        fn main() {}
+    """, """
+        use foo::a;
+        use foo::b;
+
+        use bar::c;
+        use bar::d;
+
+        fn main() {}
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test group imports std external crate`() = doTest(ImportsGrouping.STD_EXTERNAL_CRATE, """
+        use self::aaa::Foo;
+        use bbb::ccc;
+        use crate::aaa::Bar;
+        use std::io;
+
+        mod aaa {
+            pub struct Foo;
+            pub struct Bar;
+        }
+    """, """
+        use std::io;
+
+        use bbb::ccc;
+
+        use crate::aaa::Bar;
+        use self::aaa::Foo;
+
+        mod aaa {
+            pub struct Foo;
+            pub struct Bar;
+        }
+    """)
+
+    private fun doTest(
+        granularity: ImportsGranularity,
+        @Language("Rust") code: String,
+        @Language("Rust") excepted: String
+    ) = withOptionValue(CodeStyle.getSettings(project).rust::IMPORTS_GRANULARITY, granularity.ordinal) {
+        doTest(code, excepted)
+    }
+
+    private fun doTest(
+        grouping: ImportsGrouping,
+        @Language("Rust") code: String,
+        @Language("Rust") excepted: String
+    ) = withOptionValue(CodeStyle.getSettings(project).rust::GROUP_IMPORTS, grouping.ordinal) {
+        doTest(code, excepted)
+    }
+
     private fun doTest(@Language("Rust") code: String, @Language("Rust") excepted: String) =
         checkEditorAction(code, excepted, "OptimizeImports")
 
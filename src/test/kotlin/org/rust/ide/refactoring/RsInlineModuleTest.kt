@@ -0,0 +1,123 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.refactoring.util.CommonRefactoringUtil
+import org.intellij.lang.annotations.Language
+import org.rust.RsTestBase
+import org.rust.fileTreeFromText
+
+class RsInlineModuleTest : RsTestBase() {
+    fun `test simple`() = doTest("""
+    //- main.rs
+        mod /*caret*/foo;
+
+        fn main() {}
+    //- foo.rs
+        fn bar() {}
+
+        fn baz() {}
+    """, """
+    //- main.rs
+        mod foo {
+            fn bar() {}
+
+            fn baz() {}
+        }
+
+        fn main() {}
+    """)
+
+    fun `test mod rs`() = doTest("""
+    //- main.rs
+        pub mod /*caret*/foo;
+    //- foo/mod.rs
+        pub fn bar() {}
+    """, """
+    //- main.rs
+        pub mod foo {
+            pub fn bar() {}
+        }
+    """)
+
+    fun `test attributes`() = doTest("""
+    //- main.rs
+        /// Tests
+        #[cfg(test)]
+        mod /*caret*/tests;
+    //- tests.rs
+        //! Inner doc
+        #![allow(dead_code)]
+        use super::*;
+
+        #[test]
+        fn test() {}
+    """, """
+    //- main.rs
+        /// Tests
+        #[cfg(test)]
+        mod tests {
+            //! Inner doc
+            #![allow(dead_code)]
+            use super::*;
+
+            #[test]
+            fn test() {}
+        }
+    """)
+
+    fun `test nested inline module`() = doTest("""
+    //- main.rs
+        mod foo;
+    //- foo/mod.rs
+        mod /*caret*/bar;
+    //- foo/bar.rs
+        mod baz {
+            fn f() {}
+        }
+    """, """
+    //- main.rs
+        mod foo;
+    //- foo/mod.rs
+        mod bar {
+            mod baz {
+                fn f() {}
+            }
+        }
+    """)
+
+    fun `test module with child module files`() = doErrorTest("Cannot inline module with child modules in other files", """
+    //- main.rs
+        mod /*caret*/foo;
+    //- foo/mod.rs
+        mod bar;
+    //- foo/bar.rs
+    """)
+
+    fun `test module with path attribute`() = doErrorTest("Cannot inline module with `#[path]` attribute", """
+    //- main.rs
+        #[path = "bar.rs"]
+        mod /*caret*/foo;
+    //- bar.rs
+    """)
+
+    private fun doTest(@Language("Rust") before: String, @Language("Rust") after: String) =
+        checkByDirectory(before.trimIndent(), after.trimIndent()) { testProject ->
+            myFixture.configureFromTempProjectFile(testProject.fileWithCaret)
+            myFixture.performEditorAction("Inline")
+        }
+
+    private fun doErrorTest(expectedError: String, @Language("Rust") code: String) {
+        val testProject = fileTreeFromText(code.trimIndent()).create()
+        myFixture.configureFromTempProjectFile(testProject.fileWithCaret)
+        try {
+            myFixture.performEditorAction("Inline")
+            error("Error `$expectedError` is expected")
+        } catch (e: CommonRefactoringUtil.RefactoringErrorHintException) {
+            assertEquals(expectedError, e.message)
+        }
+    }
+}
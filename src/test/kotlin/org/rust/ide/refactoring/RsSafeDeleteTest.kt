@@ -0,0 +1,330 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring
+
+import com.intellij.psi.PsiElement
+import com.intellij.refactoring.BaseRefactoringProcessor
+import com.intellij.refactoring.safeDelete.SafeDeleteHandler
+import org.intellij.lang.annotations.Language
+import org.rust.RsTestBase
+import org.rust.TestProject
+import org.rust.fileTreeFromText
+
+class RsSafeDeleteTest : RsTestBase() {
+    fun `test unused function`() = doTest("""
+        fn foo() {}
+
+        fn /*caret*/bar() {}
+
+        fn main() {}
+    """, """
+        fn foo() {}
+
+        fn main() {}
+    """)
+
+    fun `test use item`() = doTest("""
+        mod a {
+            pub fn /*caret*/foo() {}
+            pub fn bar() {}
+        }
+
+        use a::foo;
+        use a::bar;
+
+        fn main() { bar(); }
+    """, """
+        mod a {
+            pub fn bar() {}
+        }
+
+        use a::bar;
+
+        fn main() { bar(); }
+    """)
+
+    fun `test use group member`() = doTest("""
+        mod a {
+            pub fn /*caret*/foo() {}
+            pub fn bar() {}
+            pub fn baz() {}
+        }
+
+        use a::{foo, bar, baz};
+
+        fn main() { bar(); baz(); }
+    """, """
+        mod a {
+            pub fn bar() {}
+            pub fn baz() {}
+        }
+
+        use a::{bar, baz};
+
+        fn main() { bar(); baz(); }
+    """)
+
+    fun `test use group which becomes empty`() = doTest("""
+        mod a {
+            pub mod b {
+                pub fn /*caret*/foo() {}
+            }
+            pub fn bar() {}
+        }
+
+        use a::{b::{foo}, bar};
+
+        fn main() { bar(); }
+    """, """
+        mod a {
+            pub mod b {
+            }
+            pub fn bar() {}
+        }
+
+        use a::{bar};
+
+        fn main() { bar(); }
+    """)
+
+    fun `test usage is unsafe`() = doUnsafeTest("""
+        fn /*caret*/foo() {}
+
+        fn main() { foo(); }
+    """)
+
+    fun `test usage in macro call is unsafe`() = doUnsafeTest("""
+        macro_rules! call { (${'$'}e:expr) => { ${'$'}e }; }
+
+        fn /*caret*/foo() {}
+
+        fn main() { call!(foo()); }
+    """)
+
+    fun `test delete anyway removes use items`() = doTestIgnoringConflicts("""
+        macro_rules! call { (${'$'}e:expr) => { ${'$'}e }; }
+
+        mod a {
+            pub fn /*caret*/foo() {}
+        }
+
+        use a::foo;
+
+        fn main() { call!(foo()); }
+    """, """
+        macro_rules! call { (${'$'}e:expr) => { ${'$'}e }; }
+
+        mod a {
+        }
+
+        fn main() { call!(foo()); }
+    """)
+
+    fun `test usages inside deleted element are ignored`() = doTest("""
+        fn /*caret*/foo() { foo(); }
+
+        fn main() {}
+    """, """
+        fn main() {}
+    """)
+
+    fun `test impl of required trait method`() = doConflictTest(
+        "`impl Foo for S` would not implement required method `foo`", """
+        trait Foo { fn foo(&self); }
+        struct S;
+        impl Foo for S {
+            fn /*caret*/foo(&self) {}
+        }
+    """)
+
+    fun `test impl of provided trait method`() = doTest("""
+        trait Foo { fn foo(&self) {} }
+        struct S;
+        impl Foo for S {
+            fn /*caret*/foo(&self) {}
+        }
+    """, """
+        trait Foo { fn foo(&self) {} }
+        struct S;
+        impl Foo for S {
+        }
+    """)
+
+    fun `test trait method with implementations`() = doTest("""
+        trait Foo {
+            fn /*caret*/foo(&self);
+            fn bar(&self);
+        }
+        struct S1;
+        impl Foo for S1 {
+            fn foo(&self) {}
+            fn bar(&self) {}
+        }
+        struct S2;
+        impl Foo for S2 {
+            fn bar(&self) {}
+            fn foo(&self) {}
+        }
+    """, """
+        trait Foo {
+            fn bar(&self);
+        }
+        struct S1;
+        impl Foo for S1 {
+            fn bar(&self) {}
+        }
+        struct S2;
+        impl Foo for S2 {
+            fn bar(&self) {}
+        }
+    """)
+
+    fun `test module file`() = doFileTest("foo.rs", """
+    //- main.rs
+        mod bar;
+        mod foo;
+
+        fn main() {}
+    //- bar.rs
+    //- foo.rs
+        pub fn foo() {}
+    """, """
+    //- main.rs
+        mod bar;
+
+        fn main() {}
+    //- bar.rs
+    """)
+
+    fun `test module file with used items`() = doFileConflictTest("foo.rs", """
+    //- main.rs
+        mod foo;
+
+        fn main() { foo::bar(); }
+    //- foo.rs
+        pub fn bar() {}
+    """)
+
+    fun `test delete anyway removes mod declaration`() = doFileTest("foo.rs", """
+    //- main.rs
+        mod foo;
+        use foo::bar;
+
+        fn main() { bar(); }
+    //- foo.rs
+        pub fn bar() {}
+    """, """
+    //- main.rs
+        fn main() { bar(); }
+    """, ignoreConflicts = true)
+
+    fun `test unused field`() = doTest("""
+        struct S {
+            a: i32,
+            /*caret*/b: i32,
+            c: i32,
+        }
+    """, """
+        struct S {
+            a: i32,
+            c: i32,
+        }
+    """)
+
+    fun `test last field`() = doTest("""
+        struct S { a: i32, /*caret*/b: i32 }
+    """, """
+        struct S { a: i32 }
+    """)
+
+    fun `test field in struct literals and patterns`() = doConflictTest(listOf(
+        "Pattern `S { .. }` destructures field `b`",
+        "Pattern `S { .. }` destructures field `b`",
+        "Struct literal `S { .. }` initializes field `b`",
+        "Struct literal `S { .. }` initializes field `b`"
+    ), """
+        struct S { a: i32, /*caret*/b: i32 }
+
+        fn main() {
+            let b = 2;
+            let s = S { a: 1, b: 2 };
+            let S { a, b: _ } = s;
+            let s = S { a, b };
+            let S { b, .. } = s;
+        }
+    """)
+
+    fun `test enum variant`() = doTest("""
+        enum E { A, /*caret*/B, C }
+    """, """
+        enum E { A, C }
+    """)
+
+    private fun doTest(@Language("Rust") before: String, @Language("Rust") after: String) =
+        checkByText(before, after) { safeDelete(myFixture.elementAtCaret) }
+
+    private fun doTestIgnoringConflicts(@Language("Rust") before: String, @Language("Rust") after: String) =
+        checkByText(before, after) {
+            BaseRefactoringProcessor.ConflictsInTestsException.withIgnoredConflicts<RuntimeException> {
+                safeDelete(myFixture.elementAtCaret)
+            }
+        }
+
+    private fun doConflictTest(expectedConflict: String, @Language("Rust") code: String) =
+        doConflictTest(listOf(expectedConflict), code)
+
+    private fun doConflictTest(expectedConflicts: List<String>, @Language("Rust") code: String) {
+        InlineFile(code.trimIndent()).withCaret()
+        val conflicts = findConflicts { safeDelete(myFixture.elementAtCaret) }
+        assertEquals(expectedConflicts, conflicts.filter { !it.isUnsafeUsagesConflict }.sorted())
+    }
+
+    private fun doUnsafeTest(@Language("Rust") code: String) {
+        InlineFile(code.trimIndent()).withCaret()
+        val conflicts = findConflicts { safeDelete(myFixture.elementAtCaret) }
+        assertTrue(conflicts.any { it.isUnsafeUsagesConflict })
+    }
+
+    private fun doFileTest(
+        path: String,
+        @Language("Rust") before: String,
+        @Language("Rust") after: String,
+        ignoreConflicts: Boolean = false
+    ) = checkByDirectory(before.trimIndent(), after.trimIndent()) { testProject ->
+        if (ignoreConflicts) {
+            BaseRefactoringProcessor.ConflictsInTestsException.withIgnoredConflicts<RuntimeException> {
+                safeDelete(testProject.psiFile(path))
+            }
+        } else {
+            safeDelete(testProject.psiFile(path))
+        }
+        assertNull(testProject.root.findFileByRelativePath(path))
+    }
+
+    private fun doFileConflictTest(path: String, @Language("Rust") code: String) {
+        val testProject = fileTreeFromText(code.trimIndent()).create()
+        val conflicts = findConflicts { safeDelete(testProject.psiFile(path)) }
+        assertTrue(conflicts.any { it.isUnsafeUsagesConflict })
+    }
+
+    private fun findConflicts(action: () -> Unit): Collection<String> {
+        try {
+            action()
+        } catch (e: BaseRefactoringProcessor.ConflictsInTestsException) {
+            return e.messages
+        }
+        error("Conflicts are expected")
+    }
+
+    private fun safeDelete(element: PsiElement) = SafeDeleteHandler.invoke(project, arrayOf(element), true)
+
+    private fun TestProject.psiFile(path: String): PsiElement {
+        val file = root.findFileByRelativePath(path) ?: error("No `$path` file")
+        return myFixture.psiManager.findFile(file)!!
+    }
+
+    private val String.isUnsafeUsagesConflict: Boolean get() = "not safe to delete" in this
+}
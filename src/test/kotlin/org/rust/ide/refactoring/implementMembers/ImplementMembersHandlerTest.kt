@@ -427,7 +427,7 @@ class ImplementMembersHandlerTest : RsTestBase() {
         struct S;
         impl T for S {
             type T1 = <selection>()</selection>;
-            type T4 = ();
+            type T4 = f64;
         }
     """)
 
@@ -979,6 +979,117 @@ class ImplementMembersHandlerTest : RsTestBase() {
         }
     """)
 
+    fun `test infer associated type from implemented members`() = doTest("""
+        struct Wrapper<T>(T);
+        trait T {
+            type Item;
+            type Other;
+            fn get(&self, _: &[Self::Other]) -> Wrapper<Self::Item>;
+        }
+        struct S;
+        impl T for S {
+            fn get(&self, _: &[bool]) -> Wrapper<(u32, i8)> { unimplemented!() }/*caret*/
+        }
+    """, listOf(
+        ImplementMemberSelection("Item", true, isSelected = true),
+        ImplementMemberSelection("Other", true, isSelected = true)
+    ), """
+        struct Wrapper<T>(T);
+        trait T {
+            type Item;
+            type Other;
+            fn get(&self, _: &[Self::Other]) -> Wrapper<Self::Item>;
+        }
+        struct S;
+        impl T for S {
+            type Item = <selection>(u32, i8)</selection>;
+            type Other = bool;
+
+            fn get(&self, _: &[bool]) -> Wrapper<(u32, i8)> { unimplemented!() }
+        }
+    """)
+
+    fun `test generic associated type`() = doTest("""
+        trait T {
+            type Item<'a> where Self: 'a;
+        }
+        struct S;
+        impl T for S {/*caret*/}
+    """, listOf(
+        ImplementMemberSelection("Item", true, isSelected = true)
+    ), """
+        trait T {
+            type Item<'a> where Self: 'a;
+        }
+        struct S;
+        impl T for S {
+            type Item<'a> where Self: 'a = <selection>()</selection>;
+        }
+    """)
+
+    fun `test substitute trait type parameters in where clause`() = doTest("""
+        trait T<A> {
+            fn f<U: Into<A>>(u: U) where U: Clone, A: Copy;
+        }
+        struct S;
+        impl T<i32> for S {/*caret*/}
+    """, listOf(
+        ImplementMemberSelection("f(u: U)", true, isSelected = true)
+    ), """
+        trait T<A> {
+            fn f<U: Into<A>>(u: U) where U: Clone, A: Copy;
+        }
+        struct S;
+        impl T<i32> for S {
+            fn f<U: Into<i32>>(u: U) where U: Clone, i32: Copy {
+                <selection>unimplemented!()</selection>
+            }
+        }
+    """)
+
+    fun `test fill fields of struct returned as Self`() = doTest("""
+        trait T {
+            fn new() -> Self;
+        }
+        struct S { a: i32, b: bool }
+        impl T for S {/*caret*/}
+    """, listOf(
+        ImplementMemberSelection("new() -> Self", true, isSelected = true)
+    ), """
+        trait T {
+            fn new() -> Self;
+        }
+        struct S { a: i32, b: bool }
+        impl T for S {
+            fn new() -> Self {
+                <selection>Self {
+                    a: 0,
+                    b: false,
+                }</selection>
+            }
+        }
+    """)
+
+    fun `test fill fields of tuple struct returned as Self`() = doTest("""
+        trait T {
+            fn new() -> Self;
+        }
+        struct S(i32, f64);
+        impl T for S {/*caret*/}
+    """, listOf(
+        ImplementMemberSelection("new() -> Self", true, isSelected = true)
+    ), """
+        trait T {
+            fn new() -> Self;
+        }
+        struct S(i32, f64);
+        impl T for S {
+            fn new() -> Self {
+                <selection>Self(0, 0.0)</selection>
+            }
+        }
+    """)
+
     fun `test with members defined by a macro`() = doTest("""
         macro_rules! foo {
             ($ i:ident, $ j:tt) => { fn $ i() $ j }
@@ -0,0 +1,185 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.refactoring.move
+
+import com.intellij.refactoring.BaseRefactoringProcessor
+import org.intellij.lang.annotations.Language
+import org.rust.MockEdition
+import org.rust.RsTestBase
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.fileTreeFromText
+
+class RsMoveModuleToFileTest : RsTestBase() {
+    fun `test simple`() = doTest("""
+    //- main.rs
+        mod /*caret*/foo {
+            fn bar() {}
+
+            fn baz() {}
+        }
+
+        fn main() {}
+    """, """
+    //- main.rs
+        mod foo;
+
+        fn main() {}
+    //- foo.rs
+        fn bar() {}
+
+        fn baz() {}
+    """)
+
+    fun `test empty module`() = doTest("""
+    //- main.rs
+        mod /*caret*/foo {}
+    """, """
+    //- main.rs
+        mod foo;
+    //- foo.rs
+    """)
+
+    fun `test mod rs layout of sibling modules`() = doTest("""
+    //- main.rs
+        mod a;
+        mod b;
+        pub mod /*caret*/foo {
+            pub fn bar() {}
+        }
+    //- a/mod.rs
+    //- b/mod.rs
+    """, """
+    //- main.rs
+        mod a;
+        mod b;
+        pub mod foo;
+    //- a/mod.rs
+    //- b/mod.rs
+    //- foo/mod.rs
+        pub fn bar() {}
+    """)
+
+    fun `test outer attributes stay on declaration`() = doTest("""
+    //- main.rs
+        /// Tests
+        #[cfg(test)]
+        mod /*caret*/tests {
+            //! Inner doc
+            #![allow(dead_code)]
+            use super::*;
+
+            #[test]
+            fn test() {}
+        }
+    """, """
+    //- main.rs
+        /// Tests
+        #[cfg(test)]
+        mod tests;
+    //- tests.rs
+        //! Inner doc
+        #![allow(dead_code)]
+        use super::*;
+
+        #[test]
+        fn test() {}
+    """)
+
+    fun `test nested module`() = doTest("""
+    //- main.rs
+        mod foo;
+    //- foo/mod.rs
+        mod /*caret*/bar {
+            mod baz {
+                fn f() {}
+            }
+        }
+    """, """
+    //- main.rs
+        mod foo;
+    //- foo/mod.rs
+        mod bar;
+    //- foo/bar.rs
+        mod baz {
+            fn f() {}
+        }
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    fun `test non mod rs file in 2018 edition`() = doTest("""
+    //- main.rs
+        mod foo;
+    //- foo.rs
+        mod /*caret*/bar {
+            fn f() {}
+        }
+    """, """
+    //- main.rs
+        mod foo;
+    //- foo.rs
+        mod bar;
+    //- foo/bar.rs
+        fn f() {}
+    """)
+
+    fun `test non mod rs file in 2015 edition`() = doConflictTest(
+        "Module files can't be declared in `foo.rs` in Rust 2015, only in `mod.rs` files", """
+    //- main.rs
+        mod foo;
+    //- foo.rs
+        mod /*caret*/bar {}
+    """)
+
+    fun `test file already exists`() = doConflictTest("File `foo.rs` already exists", """
+    //- main.rs
+        mod /*caret*/foo {}
+    //- foo.rs
+        fn f() {}
+    """)
+
+    fun `test not available inside module body`() = doUnavailableTest("""
+        mod foo {
+            fn /*caret*/f() {}
+        }
+    """)
+
+    fun `test not available for module with path attribute`() = doUnavailableTest("""
+        #[path = "bar"]
+        mod /*caret*/foo {}
+    """)
+
+    fun `test not available for module in function`() = doUnavailableTest("""
+        fn main() {
+            mod /*caret*/foo {}
+        }
+    """)
+
+    private fun doTest(@Language("Rust") before: String, @Language("Rust") after: String) =
+        checkByDirectory(before.trimIndent(), after.trimIndent()) { testProject ->
+            myFixture.configureFromTempProjectFile(testProject.fileWithCaret)
+            myFixture.performEditorAction(ACTION_ID)
+        }
+
+    private fun doConflictTest(expectedConflict: String, @Language("Rust") code: String) {
+        val testProject = fileTreeFromText(code.trimIndent()).create()
+        myFixture.configureFromTempProjectFile(testProject.fileWithCaret)
+        try {
+            myFixture.performEditorAction(ACTION_ID)
+            error("Conflict `$expectedConflict` is expected")
+        } catch (e: BaseRefactoringProcessor.ConflictsInTestsException) {
+            assertEquals(expectedConflict, e.messages.single())
+        }
+    }
+
+    private fun doUnavailableTest(@Language("Rust") code: String) {
+        InlineFile(code.trimIndent()).withCaret()
+        check(!myFixture.testAction(RsMoveModuleToFileAction()).isEnabled)
+    }
+
+    companion object {
+        private const val ACTION_ID: String = "Rust.RsMoveModuleToFile"
+    }
+}
@@ -81,12 +81,12 @@ class RsFindUsagesTest : RsTestBase() {
 
         impl B {// - impl
             fn new() -> B {// - type reference
-                B// - null
+                B// - init struct
             }
         }
 
         fn test() {
-            let b = B::new();// - function call
+            let b = B::new();// - receiver type
         }
         mod a {
             use super::B;// - use
@@ -112,7 +112,7 @@ class RsFindUsagesTest : RsTestBase() {
         fn bar<T: A>() {}
 
         fn test() {
-            let b = B::new();// - function call
+            let b = B::new();// - receiver type
             bar::<B>();// - type reference
         }
         mod a {
@@ -120,13 +120,98 @@ class RsFindUsagesTest : RsTestBase() {
         }
     """)
 
+    fun `test function references`() = doTestByText("""
+        fn foo(x: i32) -> i32 { x }
+         //^
+        fn bar(f: fn(i32) -> i32) {}
+
+        fn main() {
+            foo(1);// - function call
+            bar(foo);// - function reference
+            let f = foo;// - function reference
+        }
+    """)
+
+    fun `test tuple struct usages`() = doTestByText("""
+        struct S(i32);
+             //^
+        fn foo(s: S) {// - type reference
+            let S(x) = s;// - pattern
+            match s {
+                S(1) => {}// - pattern
+                _ => {}
+            }
+            let s = S(1);// - init struct
+        }
+    """)
+
+    fun `test enum usages`() = doTestByText("""
+        enum E { A, B(i32) }
+           //^
+        impl E {// - impl
+            fn new() -> Self { E::A }// - init struct
+        }
+        fn foo(e: E) {// - type reference
+            let _ = E::new();// - receiver type
+            match e {
+                E::A => {}// - pattern
+                E::B(_) => {}// - pattern
+            }
+        }
+    """)
+
+    fun `test unit variant usages`() = doTestByText("""
+        enum E { A, B }
+               //^
+        use E::A;// - use
+        fn foo(e: E) {
+            let a = A;// - init struct
+            match e {
+                A => {}// - pattern
+                E::B => {}
+            }
+        }
+    """)
+
+    fun `test re-export`() = doTestByText("""
+        mod a {
+            pub struct S;
+                     //^
+        }
+        pub use a::S;// - re-export
+        use a::S as S1;// - use
+    """)
+
+    fun `test trait bound usages`() = doTestByText("""
+        trait B {}
+            //^
+        trait C: B {}// - trait bound
+        struct A;
+        impl B for A {}// - trait reference
+        fn foo<T>(t: T) where T: B {}// - trait bound
+        fn bar(b: &dyn B) {}// - trait bound
+    """)
+
+    fun `test usage in macro call`() = doTestByText("""
+        macro_rules! id { ($($ t:tt)*) => { $($ t)* }; }
+        struct S;
+             //^
+        impl S {// - impl
+            fn foo() {}
+        }
+        fn foo() {
+            let s = id!(S);// - init struct
+            let f = id!(S::foo);// - receiver type
+        }
+    """)
+
     fun `test trait usages`() = doTestByText("""
         trait B {}
             //^
         struct A;
         impl B for A {}// - trait reference
 
-        fn bar<T: B>() {}// - trait reference
+        fn bar<T: B>() {}// - trait bound
 
         mod a {
             use super::B;// - use
@@ -81,6 +81,49 @@ class RsReadWriteAccessDetectorTest : RsTestBase() {
         }             //^ write
     """)
 
+    fun `test mut borrow`() = doTest("""
+        fn main() {
+            let mut a = 0;
+            let b = &mut a;
+        }              //^ write
+    """)
+
+    fun `test immutable borrow`() = doTest("""
+        fn main() {
+            let a = 0;
+            let b = &a;
+        }          //^ read
+    """)
+
+    fun `test field mut borrow`() = doTest("""
+        struct S { field: i32 }
+        fn foo(s: &mut S) {
+            let a = &mut s.field;
+        }                //^ write
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test field read_write assignment add`() = doTest("""
+        struct S { field: i32 }
+        fn foo(s: &mut S) {
+            s.field += 1;
+        }   //^ read_write
+    """)
+
+    fun `test static read`() = doTest("""
+        static FOO: i32 = 0;
+        fn main() {
+            let a = FOO;
+        }         //^ read
+    """)
+
+    fun `test mut static write`() = doTest("""
+        static mut FOO: i32 = 0;
+        fn main() {
+            unsafe { FOO = 1; }
+        }          //^ write
+    """)
+
     private fun doTest(@Language("Rust") code: String) {
         InlineFile(code.trimIndent())
         val (element, data) = findElementAndDataInEditor<RsReferenceElement>()
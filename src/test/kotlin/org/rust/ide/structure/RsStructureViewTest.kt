@@ -202,6 +202,33 @@ class RsStructureViewTest : RsTestBase() {
          makro
     """)
 
+    fun `test items expanded from macros`() = doTest("""
+        macro_rules! make_fn {
+            ($ name:ident) => { fn $ name() {} };
+        }
+        make_fn!(foo);
+        make_fn!(bar);
+    """, """
+        -main.rs
+         make_fn
+         foo()
+         bar()
+    """)
+
+    fun `test location of items expanded from macros`() = doLocationTest("""
+        macro_rules! make_fn {
+            () => { fn foo() {} };
+        }
+        make_fn!();
+        make_fn!();
+        fn foo() {}
+    """, """
+        make_fn
+        foo() [make_fn! #1]
+        foo() [make_fn! #2]
+        foo()
+    """)
+
     fun `test structs`() = doTest("""
         /// Default implementation of `ExecEngine`.
         #[derive(Clone, Copy)]
@@ -461,5 +488,17 @@ class RsStructureViewTest : RsTestBase() {
         }
     }
 
+    private fun doLocationTest(@Language("Rust") code: String, expected: String) {
+        myFixture.configureByText("main.rs", code)
+        myFixture.testStructureView {
+            val actual = it.treeModel.root.children.joinToString("\n") { child ->
+                val presentation = child.presentation
+                val location = presentation.locationString
+                if (location != null) "${presentation.presentableText} [$location]" else presentation.presentableText.orEmpty()
+            }
+            assertEquals(expected.trimIndent(), actual)
+        }
+    }
+
     private fun doTestForREPL(code: String, expected: String) = doTest(code, expected, RsConsoleView.VIRTUAL_FILE_NAME)
 }
@@ -0,0 +1,69 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.template.postfix
+
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
+@ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+class ArcPostfixTemplateTest : RsPostfixTemplateTest(ArcPostfixTemplate(RsPostfixTemplateProvider())) {
+
+    fun `test arc in scope`() = doTest("""
+        use std::sync::Arc;
+
+        fn foo(number: i32) -> Arc<i32> {
+            number.arc/*caret*/
+        }
+    """, """
+        use std::sync::Arc;
+
+        fn foo(number: i32) -> Arc<i32> {
+            Arc::new(number)
+        }
+    """)
+
+    fun `test arc not in scope`() = doTest("""
+        fn foo(number: i32) {
+            let a = number.arc/*caret*/;
+        }
+    """, """
+        fn foo(number: i32) {
+            let a = std::sync::Arc::new(number);
+        }
+    """)
+
+    fun `test method chain`() = doTest("""
+        use std::sync::Arc;
+
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn baz(s: S) -> Arc<i32> {
+            s.foo().bar().arc/*caret*/
+        }
+    """, """
+        use std::sync::Arc;
+
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn baz(s: S) -> Arc<i32> {
+            Arc::new(s.foo().bar())
+        }
+    """)
+
+    fun `test not expr`() = doTestNotApplicable("""
+        fn main() {
+            println!("Hello!");.arc/*caret*/
+        }
+    """)
+}
@@ -22,4 +22,39 @@ class DbgPostfixTemplateTest : RsPostfixTemplateTest(DbgPostfixTemplate(RsPostfi
             println!("Hello!");.dbg/*caret*/
         }
     """)
+
+    fun `test method chain`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn main() {
+            S.foo().bar().dbg/*caret*/;
+        }
+    """, """
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn main() {
+            dbg!(S.foo().bar())/*caret*/;
+        }
+    """)
+
+    fun `test not applicable in type`() = doTestNotApplicable("""
+        const N: usize = 1;
+
+        fn main() {
+            let a: [i32; N.dbg/*caret*/] = [0];
+        }
+    """)
+
+    fun `test not applicable in attribute`() = doTestNotApplicable("""
+        #[doc = "foo".dbg/*caret*/]
+        fn main() {}
+    """)
 }
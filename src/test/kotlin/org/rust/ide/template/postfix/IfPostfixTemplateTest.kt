@@ -103,4 +103,38 @@ class IfPostfixTemplateTest : RsPostfixTemplateTest(IfExpressionPostfixTemplate(
             if func() {/*caret*/}
         }
     """)
+
+    fun `test method chain`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> bool { true }
+        }
+
+        fn main() {
+            S.foo().bar().if/*caret*/
+        }
+    """, """
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> bool { true }
+        }
+
+        fn main() {
+            if S.foo().bar() {/*caret*/}
+        }
+    """)
+
+    fun `test not applicable in method chain with non boolean result`() = doTestNotApplicable("""
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn main() {
+            S.foo().bar().if/*caret*/
+        }
+    """)
 }
@@ -5,6 +5,9 @@
 
 package org.rust.ide.template.postfix
 
+import org.rust.ProjectDescriptor
+import org.rust.WithStdlibRustProjectDescriptor
+
 class MatchPostfixTemplateTest : RsPostfixTemplateTest(MatchPostfixTemplate(RsPostfixTemplateProvider())) {
     fun `test simple`() = doTest("""
         enum Message {
@@ -29,41 +32,130 @@ class MatchPostfixTemplateTest : RsPostfixTemplateTest(MatchPostfixTemplate(RsPo
         fn process_message() {
             let msg = Message::ChangeColor(255, 255, 255);
             match msg {
-                _ => {}
+                Message::Quit => {/*caret*/}
+                Message::ChangeColor(_, _, _) => {}
+                Message::Move { x: _, y: _ } => {}
+                Message::Write(_) => {}
             }
         }
     """)
 
-    fun `test can match constant`() = doTest("""
+    fun `test not applicable to non enum`() = doTestNotApplicable("""
         const THE_ANSWER: i32 = 42;
 
         fn check(x: i32) -> bool {
             x.match/*caret*/
         }
+    """)
+
+    fun `test not applicable to unknown type`() = doTestNotApplicable("""
+        fn main() {
+            a.match/*caret*/
+        }
+    """)
+
+    fun `test variants in scope`() = doTest("""
+        enum E { A, B }
+        use E::*;
+
+        fn foo(e: E) {
+            e.match/*caret*/
+        }
     """, """
-        const THE_ANSWER: i32 = 42;
+        enum E { A, B }
+        use E::*;
 
-        fn check(x: i32) -> bool {
-            match x {
-                _ => {}
+        fn foo(e: E) {
+            match e {
+                A => {/*caret*/}
+                B => {}
+            }
+        }
+    """)
+
+    fun `test reference to enum`() = doTest("""
+        enum E { A, B }
+
+        fn foo(e: &E) {
+            e.match/*caret*/
+        }
+    """, """
+        enum E { A, B }
+
+        fn foo(e: &E) {
+            match e {
+                E::A => {/*caret*/}
+                E::B => {}
+            }
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test option`() = doTest("""
+        fn foo(a: Option<i32>) {
+            a.match/*caret*/
+        }
+    """, """
+        fn foo(a: Option<i32>) {
+            match a {
+                Some(_) => {/*caret*/}
+                None => {}
+            }
+        }
+    """)
+
+    @ProjectDescriptor(WithStdlibRustProjectDescriptor::class)
+    fun `test result in method chain`() = doTest("""
+        struct S;
+        impl S {
+            fn get(&self) -> S { S }
+            fn parse(&self) -> Result<i32, ()> { Ok(0) }
+        }
+
+        fn foo(s: S) {
+            s.get().parse().match/*caret*/
+        }
+    """, """
+        struct S;
+        impl S {
+            fn get(&self) -> S { S }
+            fn parse(&self) -> Result<i32, ()> { Ok(0) }
+        }
+
+        fn foo(s: S) {
+            match s.get().parse() {
+                Ok(_) => {/*caret*/}
+                Err(_) => {}
             }
         }
     """)
 
     fun `test nested`() = doTest("""
+        enum E { A }
+
         fn main() {
             match a {
-                _ => b.match/*caret*/
+                _ => E::A.match/*caret*/
             }
         }
     """, """
+        enum E { A }
+
         fn main() {
             match a {
-                _ => match b {
-                    _ => {/*caret*/}
+                _ => match E::A {
+                    E::A => {/*caret*/}
                 }
             }
         }
     """)
 
+    fun `test not applicable in type`() = doTestNotApplicable("""
+        enum E { A }
+        const C: E = E::A;
+
+        fn main() {
+            let a: [u8; C.match/*caret*/] = [];
+        }
+    """)
 }
@@ -22,4 +22,26 @@ class OkPostfixTemplateTest : RsPostfixTemplateTest(OkPostfixTemplate(RsPostfixT
             println!("Hello!");.ok/*caret*/
         }
     """)
+
+    fun `test method chain`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn baz(s: S) -> Result<i32, ()> {
+            s.foo().bar().ok/*caret*/
+        }
+    """, """
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn baz(s: S) -> Result<i32, ()> {
+            Ok(s.foo().bar())
+        }
+    """)
 }
@@ -22,4 +22,26 @@ class SomePostfixTemplateTest : RsPostfixTemplateTest(SomePostfixTemplate(RsPost
             println!("Hello!");.some/*caret*/
         }
     """)
+
+    fun `test method chain`() = doTest("""
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn baz(s: S) -> Option<i32> {
+            s.foo().bar().some/*caret*/
+        }
+    """, """
+        struct S;
+        impl S {
+            fn foo(&self) -> S { S }
+            fn bar(&self) -> i32 { 0 }
+        }
+
+        fn baz(s: S) -> Option<i32> {
+            Some(s.foo().bar())
+        }
+    """)
 }
@@ -86,6 +86,11 @@ class RsBraceMatcherTest : RsTestBase() {
         }
     }
 
+    fun `test match parentheses of cfg operators`() {
+        doMatch("#[cfg<caret>(any(unix, all(windows, not(test))))] struct S;", ")")
+        doMatch("#[cfg(any(unix, all<caret>(windows, not(test))))] struct S;", "))")
+    }
+
     fun `test no match`() {
         noMatch("let a = 4 <caret>< 5 && 2 > 1;")
         noMatch("let a = (2 <caret>< 3 || 3 > 2);")
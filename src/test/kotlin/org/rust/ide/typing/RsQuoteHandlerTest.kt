@@ -101,6 +101,12 @@ class RsQuoteHandlerTest : RsTypingTestBase() {
         r#"'<caret>"#
     """, '\'')
 
+    fun `test wrap selection with quotes`() = doTestByText("""
+        fn main() { let _ = <selection>foo</selection>; }
+    """, """
+        fn main() { let _ = "foo"; }
+    """, '"')
+
     private fun checkUnclosedHeuristic(nextLiteral: String) = doTestByText("""
         fn main() { let _ = <caret>; let _ = "$nextLiteral"; }
     """, """
@@ -0,0 +1,108 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.ide.typing
+
+import com.intellij.openapi.actionSystem.IdeActions
+import com.intellij.openapi.ide.CopyPasteManager
+import org.intellij.lang.annotations.Language
+import org.rust.RsTestBase
+import java.awt.datatransfer.DataFlavor
+import java.awt.datatransfer.StringSelection
+
+class RsStringLiteralCopyPasteProcessorTest : RsTestBase() {
+    fun `test paste into string literal`() = doPasteTest("""
+        const C: &str = "/*caret*/";
+    """, """{"path": "C:\dir"}""", """
+        const C: &str = "{\"path\": \"C:\\dir\"}/*caret*/";
+    """)
+
+    fun `test paste multiline text into string literal`() = doPasteTest("""
+        const C: &str = "/*caret*/";
+    """, "foo\n\tbar\n", """
+        const C: &str = "foo\n\tbar\n/*caret*/";
+    """)
+
+    fun `test paste replaces selection`() = doPasteTest("""
+        const C: &str = "foo <selection>bar</selection> baz";
+    """, "\"quoted\"", """
+        const C: &str = "foo \"quoted\"/*caret*/ baz";
+    """)
+
+    fun `test paste outside of string literal`() = doPasteTest("""
+        const C: &str = /*caret*/;
+    """, "\"foo\"", """
+        const C: &str = "foo"/*caret*/;
+    """)
+
+    fun `test paste before string literal`() = doPasteTest("""
+        const C: &str = /*caret*/"";
+    """, "\"foo\"", """
+        const C: &str = "foo"/*caret*/"";
+    """)
+
+    fun `test paste non-ASCII into byte string literal`() = doPasteTest("""
+        const C: &[u8] = b"/*caret*/";
+    """, "ф\"", """
+        const C: &[u8] = b"\xD1\x84\"/*caret*/";
+    """)
+
+    fun `test paste into raw string literal`() = doPasteTest("""
+        const C: &str = r"/*caret*/";
+    """, "C:\\dir", """
+        const C: &str = r"C:\dir/*caret*/";
+    """)
+
+    fun `test paste quotes into raw string literal`() = doPasteTest("""
+        const C: &str = r"/*caret*/";
+    """, """{"a": "b"}""", """
+        const C: &str = r#"{"a": "b"}/*caret*/"#;
+    """)
+
+    fun `test paste quotes and hashes into raw string literal`() = doPasteTest("""
+        const C: &str = r#"foo /*caret*/"#;
+    """, "\"#bar\"#", """
+        const C: &str = r##"foo "#bar"#/*caret*/"##;
+    """)
+
+    fun `test paste into string literal inside macro call`() = doPasteTest("""
+        fn main() {
+            println!("/*caret*/");
+        }
+    """, "\"foo\"", """
+        fn main() {
+            println!("\"foo\"/*caret*/");
+        }
+    """)
+
+    fun `test paste into concat piece`() = doPasteTest("""
+        const C: &str = concat!("foo", "/*caret*/");
+    """, "a\\b", """
+        const C: &str = concat!("foo", "a\\b/*caret*/");
+    """)
+
+    fun `test copy from string literal`() = doCopyTest("""
+        const C: &str = "<selection>{\"path\": \"C:\\dir\"}\n</selection>";
+    """, """{"path": "C:\dir"}""" + "\n")
+
+    fun `test copy from raw string literal`() = doCopyTest("""
+        const C: &str = r"<selection>C:\dir</selection>";
+    """, """C:\dir""")
+
+    fun `test copy the whole string literal`() = doCopyTest("""
+        const C: &str = <selection>"\"foo\""</selection>;
+    """, """"\"foo\""""")
+
+    private fun doPasteTest(@Language("Rust") before: String, text: String, @Language("Rust") after: String) {
+        CopyPasteManager.getInstance().setContents(StringSelection(text))
+        checkEditorAction(before, after, IdeActions.ACTION_PASTE)
+    }
+
+    private fun doCopyTest(@Language("Rust") code: String, expected: String) {
+        InlineFile(code.trimIndent())
+        myFixture.performEditorAction(IdeActions.ACTION_COPY)
+        assertEquals(expected, CopyPasteManager.getInstance().getContents<String>(DataFlavor.stringFlavor))
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+class RsAsmOptionCompletionTest : RsCompletionTestBase() {
+    fun `test complete option`() = doSingleCompletion("""
+        fn main() {
+            asm!("nop", options(nost/*caret*/));
+        }
+    """, """
+        fn main() {
+            asm!("nop", options(nostack/*caret*/));
+        }
+    """)
+
+    fun `test complete second option`() = doSingleCompletion("""
+        fn main() {
+            asm!("nop", options(nostack, nom/*caret*/));
+        }
+    """, """
+        fn main() {
+            asm!("nop", options(nostack, nomem/*caret*/));
+        }
+    """)
+
+    fun `test no present options`() = checkNotContainsCompletion("nostack", """
+        fn main() {
+            asm!("nop", options(nostack, /*caret*/));
+        }
+    """)
+}
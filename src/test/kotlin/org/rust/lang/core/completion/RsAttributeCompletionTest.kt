@@ -5,6 +5,11 @@
 
 package org.rust.lang.core.completion
 
+import org.rust.MockEdition
+import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
+import org.rust.cargo.project.workspace.CargoWorkspace
+
 class RsAttributeCompletionTest : RsCompletionTestBase() {
     fun `test derive on struct`() = doSingleCompletion("""
         #[der/*caret*/]
@@ -192,4 +197,51 @@ class RsAttributeCompletionTest : RsCompletionTestBase() {
         #[dep/*caret*/]
         mod foo {}
     """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test custom derive helper attribute on struct`() = doSingleCompletionByFileTree("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(my_helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        #[derive(dep_proc_macro::ProcMacroName)]
+        #[my_hel/*caret*/]
+        struct S;
+    """, """
+        #[derive(dep_proc_macro::ProcMacroName)]
+        #[my_helper/*caret*/]
+        struct S;
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test custom derive helper attribute on field`() = doSingleCompletionByFileTree("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(my_helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        #[derive(dep_proc_macro::ProcMacroName)]
+        struct S {
+            #[my_hel/*caret*/]
+            field: i32
+        }
+    """, """
+        #[derive(dep_proc_macro::ProcMacroName)]
+        struct S {
+            #[my_helper/*caret*/]
+            field: i32
+        }
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test no custom derive helper attribute without derive`() = checkNoCompletionByFileTree("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(my_helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        #[my_hel/*caret*/]
+        struct S;
+    """)
 }
@@ -5,6 +5,9 @@
 
 package org.rust.lang.core.completion
 
+import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
+
 class RsCfgAttributeCompletionProviderTest : RsCompletionTestBase() {
     fun `test complete unix`() = doSingleCompletion("""
         #[cfg(un/*caret*/)]
@@ -61,4 +64,59 @@ class RsCfgAttributeCompletionProviderTest : RsCompletionTestBase() {
         #[cfg(not(and(unix, target_endian = "/*caret*/")))]
         fn foo() {}
     """)
+
+    fun `test complete feature key`() = doSingleCompletion("""
+        #[cfg(feat/*caret*/)]
+        fn foo() {}
+    """, """
+        #[cfg(feature = "/*caret*/")]
+        fn foo() {}
+    """)
+
+    fun `test cfg_attr predicate`() = doSingleCompletion("""
+        #[cfg_attr(any(wind/*caret*/), derive(Debug))]
+        struct S;
+    """, """
+        #[cfg_attr(any(windows/*caret*/), derive(Debug))]
+        struct S;
+    """)
+
+    fun `test no completion in cfg_attr attributes`() = checkNoCompletion("""
+        #[cfg_attr(unix, wind/*caret*/)]
+        struct S;
+    """)
+
+    fun `test no option completion in value`() = checkNoCompletion("""
+        #[cfg(target_os = "wind/*caret*/")]
+        fn foo() {}
+    """)
+
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test complete feature of dependency package`() = doSingleCompletionByFileTree("""
+    //- lib.rs
+        extern crate dep_lib_target_2;
+    //- dep-lib-2/lib.rs
+        #[cfg(feature = "ba/*caret*/")]
+        fn foo() {}
+    """, """
+        #[cfg(feature = "bar/*caret*/")]
+        fn foo() {}
+    """)
+
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test complete feature in cfg_attr`() = doSingleCompletionByFileTree("""
+    //- lib.rs
+        extern crate dep_lib_target_2;
+    //- dep-lib-2/lib.rs
+        #[cfg_attr(not(feature = "f/*caret*/"), derive(Debug))]
+        struct S;
+    """, """
+        #[cfg_attr(not(feature = "foo/*caret*/"), derive(Debug))]
+        struct S;
+    """)
+
+    fun `test no features of other packages`() = checkNoCompletion("""
+        #[cfg(feature = "/*caret*/")]
+        fn foo() {}
+    """)
 }
@@ -179,7 +179,19 @@ class RsCompletionFilteringTest: RsCompletionTestBase() {
         }
     """)
 
-    fun `test doc(hidden) item`() = checkNoCompletion("""
+    fun `test doc(hidden) item`() = withoutDocHiddenItems {
+        checkNoCompletion("""
+            mod foo {
+                #[doc(hidden)]
+                pub struct MyStruct;
+            }
+            fn main() {
+                foo::My/*caret*/
+            }
+        """)
+    }
+
+    fun `test doc(hidden) item is suggested by default`() = doSingleCompletion("""
         mod foo {
             #[doc(hidden)]
             pub struct MyStruct;
@@ -187,6 +199,14 @@ class RsCompletionFilteringTest: RsCompletionTestBase() {
         fn main() {
             foo::My/*caret*/
         }
+    """, """
+        mod foo {
+            #[doc(hidden)]
+            pub struct MyStruct;
+        }
+        fn main() {
+            foo::MyStruct/*caret*/
+        }
     """)
 
     fun `test doc(hidden) item from the same module isn't filtered`() = doSingleCompletion("""
@@ -29,6 +29,42 @@ class RsCompletionSortingTest : RsTestBase() {
         RsMacro::class to "_foo_bar"
     ))
 
+    fun `test deprecated and doc(hidden) items are low priority`() = doTest("""
+        mod foo {
+            #[doc(hidden)]
+            pub fn foo_hidden() {}
+            #[deprecated]
+            pub fn foo_deprecated() {}
+            pub fn foo_bar() {}
+        }
+
+        fn main() {
+            foo::foo/*caret*/
+        }
+    """, listOf(
+        RsFunction::class to "foo_bar",
+        RsFunction::class to "foo_deprecated",
+        RsFunction::class to "foo_hidden"
+    ))
+
+    fun `test re-exported deprecated items are low priority`() = doTest("""
+        mod inner {
+            #[deprecated]
+            pub fn foo_deprecated() {}
+        }
+        mod foo {
+            pub use super::inner::foo_deprecated;
+            pub fn foo_bar() {}
+        }
+
+        fn main() {
+            foo::foo/*caret*/
+        }
+    """, listOf(
+        RsFunction::class to "foo_bar",
+        RsFunction::class to "foo_deprecated"
+    ))
+
     fun `test named fields before members`() = doTest("""
         struct S  { foo: i32 }
         impl S { fn foo(&self) {} }
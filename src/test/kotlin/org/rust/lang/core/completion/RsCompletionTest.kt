@@ -296,6 +296,27 @@ class RsCompletionTest : RsCompletionTestBase() {
         impl S { fn foo(test: &self::/*caret*/) {}}
     """)
 
+    // Items under attribute macros are not expanded, so the original item is used
+    fun `test method in impl with attribute macro`() = doSingleCompletion("""
+        trait T { async fn foo(&self); }
+        struct S;
+        #[async_trait]
+        impl T for S {
+            async fn foo(&self) {
+                self.fo/*caret*/
+            }
+        }
+    """, """
+        trait T { async fn foo(&self); }
+        struct S;
+        #[async_trait]
+        impl T for S {
+            async fn foo(&self) {
+                self.foo()/*caret*/
+            }
+        }
+    """)
+
     fun `test struct field`() = doSingleCompletion("""
         struct S { foobarbaz: i32 }
         fn main() {
@@ -623,24 +644,70 @@ class RsCompletionTest : RsCompletionTestBase() {
         fn main() { private_macro!(/*caret*/) }
     """)
 
-    fun `test hidden macro is hidden in other module`() = checkNoCompletion("""
+    fun `test hidden macro is hidden in other module`() = withoutDocHiddenItems {
+        checkNoCompletion("""
+            #[doc(hidden)]
+            macro_rules! private_macro {}
+
+            mod inner {
+                fn main() { private_m/*caret*/ }
+            }
+        """)
+    }
+
+    fun `test hidden macro is hidden in other module multiple doc attributes`() = withoutDocHiddenItems {
+        checkNoCompletion("""
+            #[doc="No problems with"]
+            #[doc(hidden)]
+            #[doc="explicit docs"]
+            macro_rules! private_macro {}
+
+            mod inner {
+                fn main() { private_m/*caret*/ }
+            }
+        """)
+    }
+
+    fun `test hidden macro completes in other module by default`() = doSingleCompletion("""
         #[doc(hidden)]
         macro_rules! private_macro {}
 
         mod inner {
             fn main() { private_m/*caret*/ }
         }
-    """)
-
-    fun `test hidden macro is hidden in other module multiple doc attributes`() = checkNoCompletion("""
-        #[doc="No problems with"]
+    """, """
         #[doc(hidden)]
-        #[doc="explicit docs"]
         macro_rules! private_macro {}
 
         mod inner {
-            fn main() { private_m/*caret*/ }
+            fn main() { private_macro!(/*caret*/) }
+        }
+    """)
+
+    fun `test complete by doc alias`() = doSingleCompletion("""
+        struct Vec;
+        impl Vec {
+            #[doc(alias = "push_back")]
+            fn push(&self) {}
+        }
+        fn main() { Vec.push_b/*caret*/ }
+    """, """
+        struct Vec;
+        impl Vec {
+            #[doc(alias = "push_back")]
+            fn push(&self) {}
         }
+        fn main() { Vec.push()/*caret*/ }
+    """)
+
+    fun `test complete by one of doc aliases`() = doSingleCompletion("""
+        #[doc(alias("first", "second"))]
+        fn foo() {}
+        fn main() { seco/*caret*/ }
+    """, """
+        #[doc(alias("first", "second"))]
+        fn foo() {}
+        fn main() { foo()/*caret*/ }
     """)
 
     fun `test explicit associated type binding`() = doSingleCompletion("""
@@ -9,6 +9,7 @@ import com.intellij.psi.PsiElement
 import com.intellij.psi.util.PsiTreeUtil
 import org.intellij.lang.annotations.Language
 import org.rust.RsTestBase
+import org.rust.ide.settings.RsCodeInsightSettings
 
 abstract class RsCompletionTestBase : RsTestBase() {
 
@@ -90,6 +91,17 @@ abstract class RsCompletionTestBase : RsTestBase() {
 
     protected fun executeSoloCompletion() = completionFixture.executeSoloCompletion()
 
+    protected fun withoutDocHiddenItems(action: () -> Unit) {
+        val settings = RsCodeInsightSettings.getInstance()
+        val initialValue = settings.suggestDocHiddenItems
+        settings.suggestDocHiddenItems = false
+        try {
+            action()
+        } finally {
+            settings.suggestDocHiddenItems = initialValue
+        }
+    }
+
     private fun PsiElement.fitsHierarchically(target: String): Boolean = when {
         text == target -> true
         text.length > target.length -> false
@@ -5,8 +5,11 @@
 
 package org.rust.lang.core.completion
 
+import org.rust.MockEdition
 import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
 import org.rust.WithStdlibRustProjectDescriptor
+import org.rust.cargo.project.workspace.CargoWorkspace
 import org.rust.lang.core.resolve.KnownDerivableTrait
 import org.rust.lang.core.resolve.withDependencies
 
@@ -128,4 +131,82 @@ class RsDeriveCompletionProviderTest : RsCompletionTestBase() {
         #[derive(std::marker::Clo/*caret*/)]
         struct S;
     """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test complete custom derive with import`() = doSingleCompletionByFileTree("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        #[derive(ProcMac/*caret*/)]
+        struct S;
+    """, """
+        use dep_proc_macro::ProcMacroName;
+
+        #[derive(ProcMacroName/*caret*/)]
+        struct S;
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test complete imported custom derive`() = doSingleCompletionByFileTree("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName)]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use dep_proc_macro::ProcMacroName;
+
+        #[derive(Debug, ProcMac/*caret*/)]
+        enum E { A }
+    """, """
+        use dep_proc_macro::ProcMacroName;
+
+        #[derive(Debug, ProcMacroName/*caret*/)]
+        enum E { A }
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test doesn't complete already derived custom derive`() = checkNoCompletionByFileTree("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName)]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use dep_proc_macro::ProcMacroName;
+
+        #[derive(ProcMacroName, ProcMac/*caret*/)]
+        struct S;
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test complete reexported custom derive of transitive dependency`() = doSingleCompletionByFileTree("""
+    //- trans-proc-macro/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- dep-lib/lib.rs
+        pub use trans_proc_macro::Serialize;
+    //- lib.rs
+        #[derive(Seria/*caret*/)]
+        struct S;
+    """, """
+        use dep_lib_target::Serialize;
+
+        #[derive(Serialize/*caret*/)]
+        struct S;
+    """)
+
+    @MockEdition(CargoWorkspace.Edition.EDITION_2018)
+    @ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+    fun `test no completion for not reexported transitive custom derive`() = checkNoCompletionByFileTree("""
+    //- trans-proc-macro/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- dep-lib/lib.rs
+        pub struct Foo;
+    //- lib.rs
+        #[derive(Seria/*caret*/)]
+        struct S;
+    """)
 }
@@ -0,0 +1,154 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+import org.rust.MockEdition
+import org.rust.ProjectDescriptor
+import org.rust.WithKnownDeriveCratesRustProjectDescriptor
+import org.rust.cargo.project.workspace.CargoWorkspace
+
+@MockEdition(CargoWorkspace.Edition.EDITION_2018)
+@ProjectDescriptor(WithKnownDeriveCratesRustProjectDescriptor::class)
+class RsDeriveHelperAttributeCompletionProviderTest : RsCompletionTestBase() {
+    fun `test container key`() = doSingleCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(deny_unk/*caret*/)]
+        struct S;
+    """, """
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(deny_unknown_fields/*caret*/)]
+        struct S;
+    """)
+
+    fun `test variant key`() = doSingleCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        enum E {
+            #[serde(skip_se/*caret*/)]
+            A
+        }
+    """, """
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        enum E {
+            #[serde(skip_serializing/*caret*/)]
+            A
+        }
+    """)
+
+    fun `test field key`() = doSingleCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        struct S {
+            #[serde(flat/*caret*/)]
+            field: i32
+        }
+    """, """
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        struct S {
+            #[serde(flatten/*caret*/)]
+            field: i32
+        }
+    """)
+
+    fun `test no field key on container`() = checkNoCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(flat/*caret*/)]
+        struct S {
+            field: i32
+        }
+    """)
+
+    fun `test key with string value`() = doSingleCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(rename_a/*caret*/)]
+        struct S;
+    """, """
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "/*caret*/")]
+        struct S;
+    """)
+
+    fun `test key with arguments`() = doSingleCompletionByFileTree("""
+    //- clap_derive/lib.rs
+        #[proc_macro_derive(Parser, attributes(clap))]
+        pub fn parser(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use clap_derive::Parser;
+
+        #[derive(Parser)]
+        struct Args {
+            #[clap(possible_val/*caret*/)]
+            mode: String
+        }
+    """, """
+        use clap_derive::Parser;
+
+        #[derive(Parser)]
+        struct Args {
+            #[clap(possible_values(/*caret*/))]
+            mode: String
+        }
+    """)
+
+    fun `test no already used key`() = checkNoCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use serde_derive::Serialize;
+
+        #[derive(Serialize)]
+        struct S {
+            #[serde(rename = "f", rena/*caret*/)]
+            field: i32
+        }
+    """)
+
+    fun `test no keys without derive`() = checkNoCompletionByFileTree("""
+    //- serde_derive/lib.rs
+        #[proc_macro_derive(Serialize, attributes(serde))]
+        pub fn derive_serialize(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        #[serde(deny_unk/*caret*/)]
+        struct S;
+    """)
+}
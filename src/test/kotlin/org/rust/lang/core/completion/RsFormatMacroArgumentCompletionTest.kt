@@ -0,0 +1,105 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.lang.core.completion
+
+class RsFormatMacroArgumentCompletionTest : RsCompletionTestBase() {
+    fun `test named argument`() = doSingleCompletion("""
+        fn main() {
+            println!("{fo/*caret*/}", foo = 1);
+        }
+    """, """
+        fn main() {
+            println!("{foo/*caret*/}", foo = 1);
+        }
+    """)
+
+    fun `test named argument in write`() = doSingleCompletion("""
+        fn foo(f: &mut String) {
+            write!(f, "{ba/*caret*/}", bar = 1);
+        }
+    """, """
+        fn foo(f: &mut String) {
+            write!(f, "{bar/*caret*/}", bar = 1);
+        }
+    """)
+
+    fun `test local variable`() = doSingleCompletion("""
+        fn main() {
+            let quux = 1;
+            println!("{qu/*caret*/}");
+        }
+    """, """
+        fn main() {
+            let quux = 1;
+            println!("{quux/*caret*/}");
+        }
+    """)
+
+    fun `test positional argument`() = checkContainsCompletion("1", """
+        fn main() {
+            println!("{/*caret*/}", 1, 2, a = 3);
+        }
+    """)
+
+    fun `test no positional argument beyond arguments count`() = checkNotContainsCompletion("2", """
+        fn main() {
+            println!("{/*caret*/}", 1, 2, a = 3);
+        }
+    """)
+
+    fun `test format trait`() = checkContainsCompletion("#?", """
+        fn main() {
+            println!("{:/*caret*/}", 1);
+        }
+    """)
+
+    fun `test format trait after argument name`() = doSingleCompletion("""
+        fn main() {
+            println!("{foo:#/*caret*/}", foo = 1);
+        }
+    """, """
+        fn main() {
+            println!("{foo:#?/*caret*/}", foo = 1);
+        }
+    """)
+
+    fun `test precision argument`() = doSingleCompletion("""
+        fn main() {
+            println!("{:.pr/*caret*/}", 1.0, prec = 2);
+        }
+    """, """
+        fn main() {
+            println!("{:.prec$/*caret*/}", 1.0, prec = 2);
+        }
+    """)
+
+    fun `test no completion in escaped braces`() = checkNotContainsCompletion("foo", """
+        fn main() {
+            println!("{{fo/*caret*/}}", foo = 1);
+        }
+    """)
+
+    fun `test no completion outside of placeholder`() = checkNotContainsCompletion("foo", """
+        fn main() {
+            println!("{} fo/*caret*/", foo = 1);
+        }
+    """)
+
+    fun `test no completion in not format string`() = checkNotContainsCompletion("foo", """
+        fn main() {
+            let foo = 1;
+            println!("{}", "{fo/*caret*/}");
+        }
+    """)
+
+    fun `test no completion in unknown macro`() = checkNotContainsCompletion("foo", """
+        macro_rules! bar { ($($ t:tt)*) => {}; }
+        fn main() {
+            let foo = 1;
+            bar!("{fo/*caret*/}");
+        }
+    """)
+}
@@ -133,6 +133,18 @@ class RsLookupElementTest : RsTestBase() {
             //^
     """, typeText = "S", isStrikeout = true)
 
+    fun `test deprecated fn with since and note`() = check("""
+        #[deprecated(since = "1.2.0", note = "use `bar` instead")]
+        fn foo() {}
+          //^
+    """, tailText = "() deprecated since 1.2.0: use `bar` instead", typeText = "()", isStrikeout = true)
+
+    fun `test rustc_deprecated fn`() = check("""
+        #[rustc_deprecated(since = "1.2.0", reason = "use `bar` instead")]
+        fn foo() {}
+          //^
+    """, tailText = "() deprecated since 1.2.0: use `bar` instead", typeText = "()", isStrikeout = true)
+
     fun `test deprecated enum`() = check("""
         #[deprecated]
         enum E { X, Y }
@@ -51,4 +51,16 @@ class RsStubOnlyCompletionTest : RsCompletionTestBase() {
         mod foo;
         fn bar(s: foo::S) { s.field/*caret*/ }
     """)
+
+    fun `test doc alias`() = doSingleCompletionByFileTree("""
+    //- foo.rs
+        #[doc(alias = "quux")]
+        pub fn bar() {}
+    //- main.rs
+        mod foo;
+        use foo::qu/*caret*/;
+    """, """
+        mod foo;
+        use foo::bar/*caret*/;
+    """)
 }
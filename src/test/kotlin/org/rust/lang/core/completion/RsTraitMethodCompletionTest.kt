@@ -5,6 +5,7 @@
 
 package org.rust.lang.core.completion
 
+import com.intellij.codeInsight.lookup.LookupElementPresentation
 import org.intellij.lang.annotations.Language
 import org.rust.ide.settings.RsCodeInsightSettings
 
@@ -242,6 +243,171 @@ class RsTraitMethodCompletionTest : RsCompletionTestBase() {
         }
     """, importOutOfScopeItems = false)
 
+    fun `test auto import trait with blanket impl while method completion`() = doTest("""
+        mod baz {
+            pub trait Iter {
+                fn next(&self);
+            }
+
+            pub trait IterExt {
+                fn try_collect(&self) {}
+            }
+
+            impl<T: Iter> IterExt for T {}
+
+            pub struct Bar;
+
+            impl Iter for Bar {
+                fn next(&self) {}
+            }
+        }
+
+        use baz::{Bar, Iter};
+
+        fn main() {
+            Bar.try_col/*caret*/
+        }
+    """, """
+        mod baz {
+            pub trait Iter {
+                fn next(&self);
+            }
+
+            pub trait IterExt {
+                fn try_collect(&self) {}
+            }
+
+            impl<T: Iter> IterExt for T {}
+
+            pub struct Bar;
+
+            impl Iter for Bar {
+                fn next(&self) {}
+            }
+        }
+
+        use baz::{Bar, Iter, IterExt};
+
+        fn main() {
+            Bar.try_collect()/*caret*/
+        }
+    """)
+
+    fun `test out of scope trait path in tail text`() {
+        InlineFile("""
+            mod baz {
+                pub trait Foo {
+                    fn foo(&self) {}
+                }
+
+                impl<T> Foo for T {}
+            }
+
+            struct S;
+
+            fn main() {
+                S.fo/*caret*/
+            }
+        """)
+        val lookup = myFixture.completeBasic().single { it.lookupString == "foo" }
+        val presentation = LookupElementPresentation()
+        lookup.renderElement(presentation)
+        assertEquals("() (baz::Foo)", presentation.tailText)
+    }
+
+    fun `test do not suggest methods of out of scope traits when setting disabled`() =
+        withOptionValue(RsCodeInsightSettings.getInstance()::suggestOutOfScopeItems, false) {
+            checkNotContainsCompletion("foo", """
+                mod baz {
+                    pub trait Foo {
+                        fn foo(&self) {}
+                    }
+
+                    impl<T> Foo for T {}
+                }
+
+                struct S;
+
+                fn main() {
+                    S.fo/*caret*/
+                }
+            """)
+        }
+
+    fun `test suggest methods of in scope traits when setting disabled`() =
+        withOptionValue(RsCodeInsightSettings.getInstance()::suggestOutOfScopeItems, false) {
+            checkContainsCompletion("foo", """
+                mod baz {
+                    pub trait Foo {
+                        fn foo(&self) {}
+                    }
+
+                    impl<T> Foo for T {}
+                }
+
+                use baz::Foo;
+
+                struct S;
+
+                fn main() {
+                    S.fo/*caret*/
+                }
+            """)
+        }
+
+    fun `test methods of traits used in crate go first`() {
+        configureByFileTree("""
+        //- main.rs
+            mod a;
+            mod b;
+            mod c;
+
+            struct S;
+
+            fn main() {
+                S.do_/*caret*/
+            }
+        //- a.rs
+            pub trait DoA {
+                fn do_a(&self) {}
+            }
+
+            impl<T> DoA for T {}
+        //- b.rs
+            pub trait DoB {
+                fn do_b(&self) {}
+            }
+
+            impl<T> DoB for T {}
+        //- c.rs
+            use crate::b::DoB;
+        """)
+        val lookups = myFixture.completeBasic().map { it.lookupString }
+        assertEquals(listOf("do_b", "do_a"), lookups)
+    }
+
+    fun `test suggest methods of out of scope traits over the limit`() {
+        val traits = (0 until 60).joinToString("\n") { i ->
+            """
+                pub trait Trait$i { fn method_$i(&self) {} }
+                impl<T> Trait$i for T {}
+            """
+        }
+        InlineFile("""
+            mod baz {
+                $traits
+            }
+
+            struct S;
+
+            fn main() {
+                S.method_/*caret*/
+            }
+        """)
+        val lookups = myFixture.completeBasic().map { it.lookupString }
+        assertEquals((0 until 60).map { "method_$it" }.sorted(), lookups.sorted())
+    }
+
     private fun doTest(
         @Language("Rust") before: String,
         @Language("Rust") after: String,
@@ -22,6 +22,7 @@ import org.rust.lang.core.psi.RsFile
 import org.rust.lang.core.psi.RsMacroCall
 import org.rust.lang.core.psi.ext.childrenOfType
 import org.rust.lang.core.psi.ext.expansion
+import org.rust.lang.core.psi.ext.startOffset
 import org.rust.lang.core.psi.ext.stubChildrenOfType
 import org.rust.lang.core.psi.ext.stubDescendantsOfTypeOrSelf
 import org.rust.openapiext.toPsiFile
@@ -159,6 +160,30 @@ class RsMacroExpansionCachingTest : RsMacroExpansionTestBase() {
         bar!();
     """, "foo")
 
+    fun `test edit function body`() = Testmarks.validationScheduled.not().checkReExpanded(type(), """
+        macro_rules! foo { ($ i:ident) => { mod $ i {} } }
+        foo!(a);
+        fn main() {
+            foo!(b);
+            let a = 1;/*caret*/
+        }
+        foo!(c);
+    """)
+
+    fun `test edit struct`() = Testmarks.changedFilesValidation.checkReExpanded(type(), """
+        macro_rules! foo { ($ i:ident) => { mod $ i {} } }
+        foo!(a);
+        struct S { f/*caret*/: i32 }
+        foo!(b);
+    """)
+
+    fun `test edit macro call validates all files`() = Testmarks.changedFilesValidation.not().checkReExpanded(type(), """
+        macro_rules! foo { ($ i:ident) => { mod $ i {} } }
+        macro_rules! bar { ($ i:ident) => { mod $ i {} } }
+        foo!(a/*caret*/);
+        bar!(a);
+    """, "foo")
+
     fun `test stub call 1`() = checkReExpandedTree(replaceInFile("main.rs", "aaa", "aab"), """
     //- main.rs
         macro_rules! foo { ($ i:ident) => { mod $ i {} } }
@@ -175,6 +200,45 @@ class RsMacroExpansionCachingTest : RsMacroExpansionTestBase() {
         //bar!(bbb);
     """, listOf("bar"), Testmarks.refsRecoverNotHit)
 
+    fun `test change call delimiters`() = checkReExpandedTree(replaceInFile("main.rs", "foo!(aaa);", "foo!{aaa} "), """
+    //- main.rs
+        macro_rules! foo { ($ i:ident) => { mod $ i {} } }
+        macro_rules! bar { ($ i:ident) => { mod $ i {} } }
+        foo!(aaa);
+        bar!(bbb);
+    """, emptyList())
+
+    fun `test whitespace edit in call`() = Testmarks.refsRecover.not().checkReExpanded(type(" "), """
+        macro_rules! foo { ($ i:ident) => { mod $ i {} } }
+        foo!(/*caret*/a);
+    """)
+
+    fun `test comment edit in call`() = Testmarks.refsRecover.not().checkReExpanded(type("/* a */"), """
+        macro_rules! foo { ($ i:ident) => { mod $ i {} } }
+        foo!(a/*caret*/);
+    """)
+
+    fun `test ranges after whitespace edit in call`() = Testmarks.rangesRebased.checkHit {
+        InlineFile("""
+            macro_rules! foo { ($ a:ident, $ b:ident) => { mod $ a {} mod $ b {} } }
+            foo!(/*caret*/aaa, bbb);
+        """).withCaret()
+        type("  ")()
+        val call = myFixture.file.childrenOfType<RsMacroCall>().single()
+        for (name in listOf("aaa", "bbb")) {
+            val leaf = myFixture.file.findElementAt(call.startOffset + call.text.indexOf(name))!!
+            val expanded = leaf.findExpansionElements()!!.single()
+            assertEquals(name, expanded.text)
+            assertEquals(leaf, expanded.findElementExpandedFrom())
+        }
+    }
+
+    fun `test reuse expansion file of a removed call`() = checkReExpandedTree(replaceInFile("main.rs", "aaa", "bbb"), """
+    //- main.rs
+        macro_rules! foo { ($ i:ident) => { mod m {} } }
+        foo!(aaa);
+    """, emptyList(), Testmarks.expansionFileReused)
+
     fun `test add a call`() = checkExpansionAfterAction(type("\b\b\b"), """
         macro_rules! foo {
             () => { mod foo {} }
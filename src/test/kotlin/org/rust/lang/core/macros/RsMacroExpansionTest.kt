@@ -51,6 +51,23 @@ class RsMacroExpansionTest : RsMacroExpansionTestBase() {
         """}, *keywords.map { "use bar;" }.toTypedArray())
     }
 
+    fun `test expansion does not depend on call delimiters`() = doTest("""
+        macro_rules! foo {
+            ($ i:ident) => (
+                fn $ i() {}
+            )
+        }
+        foo!(bar);
+        foo![bar];
+        foo! { bar }
+    """, """
+        fn bar() {}
+    """, """
+        fn bar() {}
+    """, """
+        fn bar() {}
+    """)
+
     // This test doesn't check result of '$crate' expansion because it's implementation detail.
     // For example rustc expands '$crate' to synthetic token without text representation
     fun `test '$crate' metavar is matched as identifier`() = doTest("""
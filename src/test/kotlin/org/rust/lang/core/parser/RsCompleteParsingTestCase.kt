@@ -17,6 +17,7 @@ class RsCompleteParsingTestCase : RsParsingTestCaseBase("complete") {
     fun `test type`() = doTest(true)
     fun `test shifts`() = doTest(true)
     fun `test patterns`() = doTest(true)
+    fun `test nested or patterns`() = doTest(true)
     fun `test attributes`() = doTest(true)
     fun `test traits`() = doTest(true)
     fun `test macros`() = doTest(true)
@@ -49,9 +50,12 @@ class RsCompleteParsingTestCase : RsParsingTestCaseBase("complete") {
     fun `test polybounds`() = doTest(true)
     fun `test async await`() = doTest(true)
     fun `test conditions`() = doTest(true)
+    fun `test let chains`() = doTest(true)
+    fun `test let else`() = doTest(true)
     fun `test const generics`() = doTest(true)
     fun `test constants`() = doTest(true)
     fun `test raw operator`() = doTest(true)
+    fun `test asm macros`() = doTest(true)
 
     fun `test issue320`() = doTest(true)
     fun `test diesel macros`() = doTest(true)
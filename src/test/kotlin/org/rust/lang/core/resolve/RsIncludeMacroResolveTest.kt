@@ -233,6 +233,44 @@ class RsIncludeMacroResolveTest : RsResolveTestBase() {
             }  //^ lib.rs
     """)
 
+    fun `test nested include in subdirectory is resolved relative to including file`() = checkResolve("""
+    //- lib.rs
+        mod foo;
+        fn bar(x: foo::Foo) {}
+                     //^ foo/gen/b.rs
+    //- foo.rs
+        include!("foo/gen/a.rs");
+    //- foo/gen/a.rs
+        include!("b.rs");
+    //- foo/gen/b.rs
+        pub struct Foo;
+    //- b.rs
+        pub struct Foo;
+    """)
+
+    fun `test include with manifest dir`() = checkResolve("""
+    //- main.rs
+        include!(concat!(env!("CARGO_MANIFEST_DIR"), "/foo.rs"));
+        fn main() {
+            Foo;
+          //^ foo.rs
+        }
+    //- foo.rs
+        struct Foo;
+    """)
+
+    fun `test missing include file does not break resolve`() = checkResolve("""
+    //- main.rs
+        include!("missing.rs");
+        include!("foo.rs");
+        fn main() {
+            Foo;
+          //^ foo.rs
+        }
+    //- foo.rs
+        struct Foo;
+    """)
+
     private fun checkResolve(@Language("Rust") code: String) {
         stubOnlyResolve(code) { element -> element.containingFile.virtualFile }
     }
@@ -541,4 +541,36 @@ class RsMacroExpansionResolveTest : RsResolveTestBase() {
             pub struct Baz;
         }
     """)
+
+    fun `test item-level brace invocation like thread_local`() = stubOnlyResolve("""
+    //- main.rs
+        macro_rules! my_thread_local {
+            ($ (#[$ attr:meta])* static $ name:ident: $ t:ty = $ init:expr;) => (
+                $ (#[$ attr])* static $ name: $ t = $ init;
+            )
+        }
+        mod foo;
+        my_thread_local! { static X: u32 = 0; }
+    //- foo.rs
+        fn main() {
+            let _ = crate::X;
+        }                //^ main.rs
+    """)
+
+    fun `test items from macro invoked with different delimiters are resolved`() = checkByCode("""
+        macro_rules! gen {
+            ($ i:ident) => ( pub fn $ i() {} )
+        }
+        mod a {
+            gen!(foo);
+            gen![bar];
+            gen! { baz }
+                 //X
+        }
+        fn main() {
+            a::foo();
+            a::bar();
+            a::baz();
+        }    //^
+    """)
 }
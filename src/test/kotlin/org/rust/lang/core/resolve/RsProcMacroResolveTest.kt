@@ -100,6 +100,21 @@ class RsProcMacroResolveTest : RsResolveTestBase() {
         struct S;
     """)
 
+    fun `test resolve local variable in function with attr proc macro`() = stubOnlyResolve("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_attribute]
+        pub fn example_proc_macro(attr: TokenStream, item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use dep_proc_macro::example_proc_macro;
+
+        #[example_proc_macro]
+        fn main() {
+            let a = 1;
+            a;
+          //^ lib.rs
+        }
+    """)
+
     // FIXME
     fun `test resolve attr proc macro from macro call with full path`() = expect<IllegalStateException> {
         stubOnlyResolve("""
@@ -236,4 +251,54 @@ class RsProcMacroResolveTest : RsResolveTestBase() {
                  //^ dep-proc-macro/lib.rs
         struct S;
     """)
+
+    fun `test resolve custom derive helper attribute on struct`() = stubOnlyResolve("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use dep_proc_macro::ProcMacroName;
+        #[derive(ProcMacroName)]
+        #[helper(foo)]
+          //^ dep-proc-macro/lib.rs
+        struct S;
+    """)
+
+    fun `test resolve custom derive helper attribute on field`() = stubOnlyResolve("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(helper1, helper2))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use dep_proc_macro::ProcMacroName;
+        #[derive(ProcMacroName)]
+        struct S {
+            #[helper2]
+              //^ dep-proc-macro/lib.rs
+            field: i32
+        }
+    """)
+
+    fun `test resolve custom derive helper attribute on enum variant`() = stubOnlyResolve("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        #[derive(dep_proc_macro::ProcMacroName)]
+        enum E {
+            #[helper]
+              //^ dep-proc-macro/lib.rs
+            A
+        }
+    """)
+
+    fun `test custom derive helper attribute is not resolved without the derive`() = stubOnlyResolve("""
+    //- dep-proc-macro/lib.rs
+        #[proc_macro_derive(ProcMacroName, attributes(helper))]
+        pub fn example_proc_macro(item: TokenStream) -> TokenStream { item }
+    //- lib.rs
+        use dep_proc_macro::ProcMacroName;
+        #[helper]
+          //^ unresolved
+        struct S;
+    """)
 }
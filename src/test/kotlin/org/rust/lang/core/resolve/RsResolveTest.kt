@@ -187,6 +187,50 @@ class RsResolveTest : RsResolveTestBase() {
         }
     """)
 
+    fun `test let chain binding in next operand`() = checkByCode("""
+        fn foo(x: Option<i32>) {
+            if let Some(y) = x && y > 0 {
+                      //X
+                         //^
+            }
+        }
+    """)
+
+    fun `test let chain binding in body`() = checkByCode("""
+        fn foo(x: Option<Option<i32>>) {
+            if let Some(y) = x && let Some(z) = y {
+                                        //X
+                z;
+              //^
+            }
+        }
+    """)
+
+    fun `test let chain binding in else branch`() = checkByCode("""
+        fn foo() {
+            if let Some(z) = None && true {} else {
+                z;
+              //^ unresolved
+            }
+        }
+    """)
+
+    fun `test let else binding`() = checkByCode("""
+        fn foo(x: Option<i32>) {
+            let Some(y) = x else { return };
+                   //X
+            y;
+          //^
+        }
+    """)
+
+    fun `test let else binding is not visible in else block`() = checkByCode("""
+        fn foo(x: Option<i32>) {
+            let Some(y) = x else { y; return };
+                                 //^ unresolved
+        }
+    """)
+
     fun `test while let 1`() = checkByCode("""
         fn main() {
             while let Some(i) = Some(92) {
@@ -531,6 +575,41 @@ class RsResolveTest : RsResolveTestBase() {
         }
     """)
 
+    fun `test asm input operand`() = checkByCode("""
+        fn main() {
+            let x = 92;
+              //X
+            unsafe { asm!("mov {0}, {0}", in(reg) x); }
+                                                //^
+        }
+    """)
+
+    fun `test asm inout operand with output place`() = checkByCode("""
+        fn main() {
+            let x = 92;
+            let mut y = 0;
+                  //X
+            unsafe { asm!("mov {0}, {0}", inout(reg) x => y); }
+                                                        //^
+        }
+    """)
+
+    fun `test asm sym operand function`() = checkByCode("""
+        fn foo() {}
+         //X
+        fn main() {
+            unsafe { asm!("call {}", sym foo); }
+                                       //^
+        }
+    """)
+
+    fun `test asm sym operand static`() = checkByCode("""
+        static FOO: i32 = 0;
+             //X
+        global_asm!("lea {}, %rax", sym FOO);
+                                      //^
+    """)
+
     fun `test await argument`() = checkByCode("""
         fn main() {
             let x = 42;
@@ -258,6 +258,66 @@ class RsExpressionTypeInferenceTest : RsTypificationTestBase() {
         }
     """)
 
+    fun `test array size with const parameter`() = testExpr("""
+        fn foo<const N: usize>(a: [u8; N * 2]) {
+            a;
+          //^ [u8; N * 2]
+        }
+    """)
+
+    fun `test commutative array size with const parameter`() = testExpr("""
+        fn foo<const N: usize>(a: [u8; N + 1]) {
+            let b: [u8; 1 + N] = a;
+            b;
+          //^ [u8; N + 1]
+        }
+    """)
+
+    fun `test array size with const parameter substituted at call site`() = testExpr("""
+        fn double<const N: usize>() -> [u8; N * 2] { loop {} }
+        fn main() {
+            let x = double::<3>();
+            x;
+          //^ [u8; 6]
+        }
+    """)
+
+    fun `test const argument with const parameter`() = testExpr("""
+        fn foo<const N: usize>() -> [u8; N] { loop {} }
+        fn bar<const M: usize>() {
+            let x = foo::<{ M * 2 }>();
+            x;
+          //^ [u8; M * 2]
+        }
+    """)
+
+    fun `test const parameter default`() = testExpr("""
+        struct Buf<const N: usize = 1024> { data: [u8; N] }
+        fn foo(b: Buf) {
+            let x = b.data;
+            x;
+          //^ [u8; 1024]
+        }
+    """)
+
+    fun `test const parameter default with previous parameter`() = testExpr("""
+        struct S<const N: usize, const M: usize = N> { data: [u8; M] }
+        fn foo(s: S<{ 3 }>) {
+            let x = s.data;
+            x;
+          //^ [u8; 3]
+        }
+    """)
+
+    fun `test explicit const argument with default`() = testExpr("""
+        struct Buf<const N: usize = 1024> { data: [u8; N * 2] }
+        fn foo(b: Buf<{ 1 }>) {
+            let x = b.data;
+            x;
+          //^ [u8; 2]
+        }
+    """)
+
     fun `test if condition`() = testExpr("""
         fn main() {
             if true {};
@@ -134,4 +134,52 @@ class RsMacroTypeInferenceTest : RsTypificationTestBase() {
             a;
         } //^ <unknown>
     """, TypeInferenceMarks.macroExprDepthLimitReached)
+
+    fun `test matches macro`() = testExpr("""
+        fn main() {
+            let x = Some(1);
+            let a = matches!(x, Some(_));
+            a;
+        } //^ bool
+    """)
+
+    fun `test assert_matches macro`() = testExpr("""
+        fn main() {
+            let x = Some(1);
+            let a = assert_matches!(x, Some(_));
+            a;
+        } //^ ()
+    """)
+
+    fun `test todo macro`() = testExpr("""
+        fn main() {
+            let a = todo!();
+            a;
+        } //^ !
+    """)
+
+    fun `test todo macro in match arm`() = testExpr("""
+        fn main() {
+            let a = match 0 {
+                0 => 1u8,
+                _ => todo!("later"),
+            };
+            a;
+        } //^ u8
+    """)
+
+    fun `test dbg macro preserves argument type`() = testExpr("""
+        struct S;
+        fn main() {
+            let a = dbg!(S);
+            a;
+        } //^ S
+    """)
+
+    fun `test dbg macro without arguments`() = testExpr("""
+        fn main() {
+            let a = dbg!();
+            a;
+        } //^ ()
+    """)
 }
@@ -28,6 +28,25 @@ class RsPatternMatchingTest : RsTypificationTestBase() {
         }
     """)
 
+    fun `test let chain pattern`() = testExpr("""
+        enum E { L(i32), R(bool) }
+        fn foo(a: E, b: E) {
+            if let E::L(x) = a && let E::R(y) = b {
+                (x, y);
+              //^ (i32, bool)
+            }
+        }
+    """)
+
+    fun `test let else pattern`() = testExpr("""
+        enum E { L(i32), R(bool) }
+        fn foo(e: E) {
+            let E::L(x) = e else { return };
+            x;
+          //^ i32
+        }
+    """)
+
     fun `test while let pattern`() = testExpr("""
         enum E { L(i32), R(bool) }
         fn main() {
@@ -782,4 +782,20 @@ class RsStdlibExpressionTypeInferenceTest : RsTypificationTestBase() {
             b;
         } //^ &S<X>
     """)
+
+    fun `test dbg macro preserves Vec type`() = testExpr("""
+        fn main() {
+            let v = vec![1u8];
+            let a = dbg!(v);
+            a;
+        } //^ Vec<u8>
+    """)
+
+    fun `test matches macro`() = testExpr("""
+        fn main() {
+            let x = Some(1);
+            let a = matches!(x, Some(_));
+            a;
+        } //^ bool
+    """)
 }
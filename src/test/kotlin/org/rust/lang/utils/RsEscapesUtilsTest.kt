@@ -42,6 +42,7 @@ class UnescapeRsTest(private val input: String,
             arrayOf("foo\\r\\nbar", "foo\r\nbar", true, true, false),
             arrayOf("\\u{0119}dw\\u{0105}rd", "\u0119dw\u0105rd", true, true, false),
             arrayOf("\\u{0119}dw\\u{0105}rd", "\\u{0119}dw\\u{0105}rd", false, true, false),
+            arrayOf("\\u{1F600}", "\uD83D\uDE00", true, true, false),
             arrayOf("\\u{0}", "\u0000", true, true, false),
             arrayOf("\\u{00}", "\u0000", true, true, false),
             arrayOf("\\u{000}", "\u0000", true, true, false),
@@ -107,3 +108,43 @@ class UnescapeRsWithOffsetsTest(
         )
     }
 }
+
+@RunWith(Parameterized::class)
+class EscapeRsTest(private val input: String, private val expected: String, private val isByte: Boolean) {
+    @Test
+    fun test() = assertEquals(expected, input.escapeRust(isByte))
+
+    companion object {
+        @Parameters(name = "{index}: \"{0}\" → \"{1}\" B:{2}")
+        @JvmStatic fun data(): Collection<Array<Any>> = listOf(
+            arrayOf("aaa", "aaa", false),
+            arrayOf("a\"a", "a\\\"a", false),
+            arrayOf("a\\a", "a\\\\a", false),
+            arrayOf("a'a", "a'a", false),
+            arrayOf("a\na\r\ta", "a\\na\\r\\ta", false),
+            arrayOf("a\u0000a", "a\\0a", false),
+            arrayOf("a\u0007a", "a\\u{7}a", false),
+            arrayOf("a\u0007a", "a\\x07a", true),
+            arrayOf("\u0119\uD83D\uDE00", "\u0119\uD83D\uDE00", false),
+            arrayOf("\u0119", "\\xC4\\x99", true)
+        )
+    }
+}
+
+@RunWith(Parameterized::class)
+class RawStringHashesCountTest(private val value: String, private val expected: Int) {
+    @Test
+    fun test() = assertEquals(expected, rawStringHashesCount(value))
+
+    companion object {
+        @Parameters(name = "{index}: \"{0}\" → {1}")
+        @JvmStatic fun data(): Collection<Array<Any>> = listOf(
+            arrayOf("foo", 0),
+            arrayOf("#foo#", 0),
+            arrayOf("\"foo\"", 1),
+            arrayOf("\"#foo\"", 2),
+            arrayOf("\"foo\"##", 3),
+            arrayOf("\"", 1)
+        )
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rustPerformanceTests
+
+import com.intellij.openapiext.TestmarkPred
+import com.intellij.openapiext.not
+import com.intellij.psi.PsiDocumentManager
+import org.rust.ExpandMacros
+import org.rust.lang.core.macros.MacroExpansionManagerImpl.Testmarks
+import org.rust.lang.core.macros.RsMacroExpansionTestBase
+import org.rust.stdext.Timings
+
+@ExpandMacros
+class RsMacroExpansionPerformanceTest : RsMacroExpansionTestBase() {
+    // It is a performance test, but we don't want to waste time
+    // measuring CPU performance
+    override fun isPerformanceTest(): Boolean = false
+
+    /** Typing inside a function body must not lead to validation of sibling macro calls */
+    fun `test typing inside function body`() = doTest(generateFixture(), Testmarks.validationScheduled.not())
+
+    /** Typing inside a struct must lead to validation of macro calls of the current file only */
+    fun `test typing inside struct`() = doTest(
+        generateFixture().replace("/*caret*/", "").replace("struct S0 { f", "struct S0 { f/*caret*/"),
+        Testmarks.changedFilesValidation
+    )
+
+    private fun doTest(code: String, typingMark: TestmarkPred) {
+        InlineFile(code).withCaret()
+        myFixture.doHighlighting()
+
+        val timings = Timings()
+        repeat(TYPING_REPEATS) {
+            typingMark.checkHit {
+                timings.measureAverage("typing") {
+                    myFixture.type("a")
+                    PsiDocumentManager.getInstance(project).commitAllDocuments()
+                }
+            }
+            timings.measureAverage("highlighting") {
+                myFixture.doHighlighting()
+            }
+        }
+        println("${name.substring("test ".length)}: ${timings.values()}")
+
+        val highlighting = timings.values().getValue("highlighting")
+        check(highlighting <= HIGHLIGHTING_BUDGET_MS) {
+            "Highlighting took $highlighting ms, but the budget is $HIGHLIGHTING_BUDGET_MS ms"
+        }
+    }
+
+    /** Emulates a crate with a lot of items with macro-generated impls */
+    private fun generateFixture(): String = buildString {
+        append("macro_rules! gen_impl { (\$ name:ident) => { impl \$ name { fn get(&self) -> i32 { self.f } } } }\n")
+        for (i in 0 until ITEMS) {
+            append("struct S$i { f: i32 }\n")
+            append("gen_impl!(S$i);\n")
+        }
+        append("fn main() {\n")
+        append("    let s = S0 { f: 0 };\n")
+        append("    s.get();/*caret*/\n")
+        append("}\n")
+    }
+
+    companion object {
+        private const val ITEMS: Int = 800
+        private const val TYPING_REPEATS: Int = 10
+        private const val HIGHLIGHTING_BUDGET_MS: Long = 1000
+    }
+}
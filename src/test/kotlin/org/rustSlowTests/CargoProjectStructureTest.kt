@@ -87,6 +87,46 @@ class CargoProjectStructureTest : RsWithToolchainTestBase() {
         }
     }
 
+    fun `test dependencies`() = doTest("""
+        Root
+         Project
+          Targets
+           Target(foo[bin])
+          Dependencies
+           Dependency(bar v0.2.0)
+          WorkspaceMember(bar)
+           Targets
+            Target(bar[lib])
+    """) {
+        toml("Cargo.toml", """
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            authors = []
+
+            [dependencies]
+            bar = { path = "bar" }
+
+            [workspace]
+            members = ["bar"]
+        """)
+
+        dir("src") {
+            rust("main.rs", "fn main() {}")
+        }
+        dir("bar") {
+            toml("Cargo.toml", """
+                [package]
+                name = "bar"
+                version = "0.2.0"
+                authors = []
+            """)
+            dir("src") {
+                rust("lib.rs", "")
+            }
+        }
+    }
+
     private fun doTest(expectedTreeStructure: String, builder: FileTreeBuilder.() -> Unit) {
         fileTree(builder).create()
         val structure = CargoProjectTreeStructure(
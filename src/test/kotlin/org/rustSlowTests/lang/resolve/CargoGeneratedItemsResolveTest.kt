@@ -9,6 +9,7 @@ import com.intellij.openapi.util.SystemInfo
 import com.intellij.openapi.util.io.FileUtil
 import com.intellij.testFramework.fixtures.impl.TempDirTestFixtureImpl
 import org.rust.MinRustcVersion
+import org.rust.cargo.project.settings.rustSettings
 import org.rust.cargo.toolchain.Cargo
 import org.rust.fileTree
 import org.rust.ide.experiments.RsExperiments
@@ -818,6 +819,12 @@ class CargoGeneratedItemsResolveTest : RunConfigurationTestBase() {
     private fun withEnabledFetchOutDirFeature(action: () -> Unit) =
         runWithEnabledFeature(RsExperiments.FETCH_OUT_DIR, action)
 
-    private fun withEnabledEvaluateBuildScriptsFeature(action: () -> Unit) =
-        runWithEnabledFeature(RsExperiments.EVALUATE_BUILD_SCRIPTS, action)
+    private fun withEnabledEvaluateBuildScriptsFeature(action: () -> Unit) {
+        project.rustSettings.modify { it.evaluateBuildScripts = true }
+        try {
+            action()
+        } finally {
+            project.rustSettings.modify { it.evaluateBuildScripts = false }
+        }
+    }
 }
@@ -10,6 +10,7 @@ import com.intellij.openapi.util.io.FileUtil
 import com.intellij.testFramework.fixtures.impl.TempDirTestFixtureImpl
 import org.rust.MinRustcVersion
 import org.rust.cargo.RsWithToolchainTestBase
+import org.rust.cargo.project.settings.rustSettings
 import org.rust.fileTree
 import org.rust.lang.core.psi.RsPath
 import org.rust.lang.core.resolve.NameResolutionTestmarks
@@ -545,4 +546,215 @@ class CargoProjectResolveTest : RsWithToolchainTestBase() {
     }.run {
         checkReferenceIsResolved<RsPath>("src/main.rs")
     }
+
+    fun `test target-specific dependency of another platform is not resolved`() {
+        val testProject = buildProject {
+            toml("Cargo.toml", """
+                [package]
+                name = "intellij-rust-test"
+                version = "0.1.0"
+                authors = []
+
+                [target.'cfg(unix)'.dependencies]
+                unix_only = { path = "./unix_only" }
+
+                [target.'cfg(windows)'.dependencies]
+                win_only = { path = "./win_only" }
+
+                [target.'cfg(target_arch = "wasm32")'.dependencies]
+                wasm_only = { path = "./wasm_only" }
+            """)
+
+            dir("src") {
+                rust("main.rs", """
+                    fn main() {
+                        win_only::func();
+                        //^
+                    }
+                """)
+            }
+
+            dir("unix_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "unix_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+            dir("win_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "win_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+            dir("wasm_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "wasm_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+        }
+        testProject.checkReferenceIsResolved<RsPath>("src/main.rs", shouldNotResolve = SystemInfo.isUnix)
+    }
+
+    fun `test target-specific dependency of the host platform is resolved`() {
+        val testProject = buildProject {
+            toml("Cargo.toml", """
+                [package]
+                name = "intellij-rust-test"
+                version = "0.1.0"
+                authors = []
+
+                [target.'cfg(unix)'.dependencies]
+                unix_only = { path = "./unix_only" }
+
+                [target.'cfg(windows)'.dependencies]
+                win_only = { path = "./win_only" }
+
+                [target.'cfg(target_arch = "wasm32")'.dependencies]
+                wasm_only = { path = "./wasm_only" }
+            """)
+
+            dir("src") {
+                rust("main.rs", """
+                    fn main() {
+                        unix_only::func();
+                        //^
+                    }
+                """)
+            }
+
+            dir("unix_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "unix_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+            dir("win_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "win_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+            dir("wasm_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "wasm_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+        }
+        testProject.checkReferenceIsResolved<RsPath>("src/main.rs", shouldNotResolve = !SystemInfo.isUnix)
+    }
+
+    fun `test target-specific dependency of the build target is resolved`() {
+        project.rustSettings.modify { it.buildTarget = "wasm32-unknown-unknown" }
+        val testProject = buildProject {
+            toml("Cargo.toml", """
+                [package]
+                name = "intellij-rust-test"
+                version = "0.1.0"
+                authors = []
+
+                [target.'cfg(unix)'.dependencies]
+                unix_only = { path = "./unix_only" }
+
+                [target.'cfg(windows)'.dependencies]
+                win_only = { path = "./win_only" }
+
+                [target.'cfg(target_arch = "wasm32")'.dependencies]
+                wasm_only = { path = "./wasm_only" }
+            """)
+
+            dir("src") {
+                rust("main.rs", """
+                    fn main() {
+                        wasm_only::func();
+                        //^
+                    }
+                """)
+            }
+
+            dir("unix_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "unix_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+            dir("win_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "win_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+            dir("wasm_only") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "wasm_only"
+                    version = "0.1.0"
+                    authors = []
+                """)
+                dir("src") {
+                    rust("lib.rs", """
+                        pub fn func() {}
+                    """)
+                }
+            }
+        }
+        testProject.checkReferenceIsResolved<RsPath>("src/main.rs")
+    }
 }
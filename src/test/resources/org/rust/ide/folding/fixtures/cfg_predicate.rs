@@ -0,0 +1,14 @@
+#[cfg<fold text='(...)'>(any<fold text='(...)'>(
+    target_os = "linux",
+    all<fold text='(...)'>(
+        unix,
+        not(target_os = "macos")
+    )</fold>
+))</fold>]
+fn foo() <fold text='{...}'>{
+}</fold>
+
+#[derive(
+    Debug
+)]
+struct S;
@@ -0,0 +1,56 @@
+fn main() {
+    let short = items.iter().map(|item| item.value).sum::<i32>();
+
+    let values = items
+        .iter()
+        .filter(|item| item.is_valid())
+        .map(|item| item.value * 2)
+        .collect::<Vec<_>>();
+
+    let names = x
+        .iter()
+        .filter(|item| item.is_visible())
+        .map(|item| item.name.to_uppercase())
+        .collect::<Vec<_>>();
+
+    let total = self
+        .items
+        .iter()
+        .filter(|item| item.is_valid())
+        .map(|item| item.price * item.count)
+        .sum::<u64>();
+
+    x.iter()
+        .filter(|item| item.is_visible())
+        .map(|item| item.name.to_uppercase())
+        .for_each(drop);
+
+    self.items
+        .iter()
+        .filter(|item| item.is_valid())
+        .map(|item| item.price * item.count)
+        .for_each(drop);
+
+    let request = RequestBuilder::new("https://example.com")
+        .header("Accept", "text/html")
+        .timeout(30)
+        .build();
+
+    let config = ConfigBuilder::default()
+        .name("intellij-rust")?
+        .version(VERSION)?
+        .verbose(true)
+        .build()?;
+
+    items.iter().filter(|item| item.is_valid()).for_each(|item| {
+        println!("{}", item.name);
+    });
+
+    let ids = items
+        .iter()
+        .filter(|item| item.is_valid())
+        .map(|item| item.id)
+        .count();
+
+    let field = self.context.settings.value;
+}
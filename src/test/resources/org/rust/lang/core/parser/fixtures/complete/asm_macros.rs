@@ -0,0 +1,7 @@
+fn main() {
+    asm!("mov {0}, {1}", out(reg) x, in(reg) y);
+    asm!("nop", options(nostack, nomem), clobber_abi("C"));
+    asm!("{a}", a = inout(reg) x => _, in("eax") 1, sym foo, const N);
+}
+
+global_asm!("call {}", sym bar);
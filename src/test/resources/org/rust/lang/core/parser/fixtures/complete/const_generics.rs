@@ -1,3 +1,4 @@
 struct S<T, const N: i32, const M: &'static str>;
+struct D<const N: usize = 1024, const M: i32 = -1, const B: bool = { true }, const P: usize = N>;
 fn foo<T, const N: i32, const M: &'static str>() {}
 fn main() { foo::<S<i32, 0, { x }>, -0, "">() }
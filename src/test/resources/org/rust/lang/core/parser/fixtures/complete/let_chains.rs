@@ -0,0 +1,4 @@
+fn main() {
+    if let Some(x) = o && x > 0 {}
+    while a && let Some(b) = c {}
+}
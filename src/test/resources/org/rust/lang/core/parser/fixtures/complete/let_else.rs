@@ -0,0 +1,6 @@
+fn main() {
+    let Some(x) = o else { return };
+    let Foo(y) = foo() else {
+        return;
+    };
+}
@@ -25,6 +25,8 @@ default!(String);
 
 thread_local!(static HANDLE: Handle = Handle(0));
 
+thread_local! { static X: u32 = 0; }
+
 #[cfg(foo)]
 foo!();
 
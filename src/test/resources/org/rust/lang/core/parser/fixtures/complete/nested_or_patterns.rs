@@ -0,0 +1,4 @@
+fn nested_or_patterns() {
+    let Some(A | B) = x;
+    let [0.. | 1] = x;
+}
@@ -14,8 +14,6 @@ import org.rust.cargo.toolchain.RustToolchain.Companion.CARGO_TOML
 import org.rust.ide.icons.RsIcons
 import org.rust.lang.core.psi.ext.elementType
 import org.toml.lang.psi.*
-import org.toml.lang.psi.ext.TomlLiteralKind
-import org.toml.lang.psi.ext.kind
 
 class CargoCrateDocLineMarkerProvider : LineMarkerProvider {
     override fun getLineMarkerInfo(element: PsiElement): LineMarkerInfo<*>? = null
@@ -23,9 +21,14 @@ class CargoCrateDocLineMarkerProvider : LineMarkerProvider {
     override fun collectSlowLineMarkers(elements: MutableList<PsiElement>, result: MutableCollection<LineMarkerInfo<PsiElement>>) {
         if (!tomlPluginIsAbiCompatible()) return
         val firstElement = elements.firstOrNull() ?: return
-        val file = firstElement.containingFile
+        val file = firstElement.containingFile as? TomlFile ?: return
         if (!file.name.equals(CARGO_TOML, ignoreCase = true)) return
 
+        // Versions of dependencies inherited from the workspace are taken from the root manifest
+        val dependencies by lazy(LazyThreadSafetyMode.NONE) { file.dependencies.associateBy { it.key } }
+        fun findDependency(key: TomlKey): CargoTomlDependency? =
+            dependencies[key] ?: file.findWorkspaceDependency(key.text)?.takeIf { it.key == key }
+
         loop@ for (element in elements) {
             val parent = element.parent
             if (parent is TomlKey) {
@@ -34,16 +37,16 @@ class CargoCrateDocLineMarkerProvider : LineMarkerProvider {
                 val table = keyValue.parent as? TomlTable ?: continue@loop
                 if (!table.header.isDependencyListHeader) continue@loop
                 if (key.firstChild?.nextSibling != null) continue@loop
-                val pkgName = keyValue.crateName
-                val pkgVersion = keyValue.version ?: continue@loop
-                result += genLineMarkerInfo(element, pkgName, pkgVersion)
+                val dependency = findDependency(key) ?: continue@loop
+                val version = dependency.version ?: continue@loop
+                result += genLineMarkerInfo(element, dependency.packageName, version)
             } else if (element.elementType == TomlElementTypes.L_BRACKET) {
                 val header = parent as? TomlTableHeader ?: continue@loop
                 val names = header.names
                 if (names.getOrNull(names.size - 2)?.isDependencyKey != true) continue@loop
-                val table = parent.parent as? TomlTable ?: continue@loop
-                val version = table.entries.find { it.name == "version" }?.value?.stringValue ?: continue@loop
-                result += genLineMarkerInfo(element, names.last().text, version)
+                val dependency = findDependency(names.last()) ?: continue@loop
+                val version = dependency.version ?: continue@loop
+                result += genLineMarkerInfo(element, dependency.packageName, version)
             }
         }
     }
@@ -59,26 +62,3 @@ class CargoCrateDocLineMarkerProvider : LineMarkerProvider {
 
     }
 }
-
-private val TomlKeyValue.name get() = key.text
-private val TomlKeyValue.crateName: String
-    get() {
-        return when (val rootValue = value) {
-            is TomlInlineTable -> (rootValue.entries.find { it.name == "package" }?.value?.stringValue)
-                ?: key.text
-            else -> key.text
-        }
-    }
-private val TomlKeyValue.version: String?
-    get() {
-        return when (val value = value) {
-            is TomlLiteral -> value.stringValue
-            is TomlInlineTable -> value.entries.find { it.name == "version" }?.value?.stringValue
-            else -> null
-        }
-    }
-
-private val TomlValue.stringValue: String? get() {
-    val kind = (this as? TomlLiteral)?.kind
-    return (kind as? TomlLiteralKind.String)?.value
-}
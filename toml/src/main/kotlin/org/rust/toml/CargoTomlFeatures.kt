@@ -0,0 +1,158 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml
+
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiManager
+import org.rust.cargo.project.model.cargoProjects
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.toolchain.RustToolchain
+import org.toml.lang.psi.*
+
+/**
+ * An element of a feature array:
+ * ```
+ * [features]
+ * foo = ["bar", "dep:baz", "serde/derive", "serde?/std"]
+ * ```
+ */
+sealed class FeatureDependency {
+    /** `bar`. A name of another feature of the package (or of an optional dependency) */
+    data class Feature(val name: String) : FeatureDependency()
+
+    /** `dep:baz`. Enables optional dependency `baz` without enabling an implicit feature */
+    data class OptionalDependency(val dependencyName: String) : FeatureDependency() {
+        val dependencyNameRange: TextRange get() = TextRange.from(PREFIX.length, dependencyName.length)
+
+        companion object {
+            const val PREFIX: String = "dep:"
+        }
+    }
+
+    /** `serde/derive` or `serde?/std` if [isWeak] */
+    data class DependencyFeature(
+        val dependencyName: String,
+        val featureName: String,
+        val isWeak: Boolean
+    ) : FeatureDependency() {
+        val dependencyNameRange: TextRange get() = TextRange.from(0, dependencyName.length)
+        val featureNameRange: TextRange
+            get() = TextRange.from(dependencyName.length + (if (isWeak) 2 else 1), featureName.length)
+    }
+
+    companion object {
+        fun parse(text: String): FeatureDependency {
+            if (text.startsWith(OptionalDependency.PREFIX)) {
+                return OptionalDependency(text.removePrefix(OptionalDependency.PREFIX))
+            }
+            val slash = text.indexOf('/')
+            if (slash == -1) return Feature(text)
+            val isWeak = slash > 0 && text[slash - 1] == '?'
+            val dependencyName = text.substring(0, if (isWeak) slash - 1 else slash)
+            return DependencyFeature(dependencyName, text.substring(slash + 1), isWeak)
+        }
+    }
+}
+
+/**
+ * A dependency declared in Cargo.toml, e.g. `serde = { version = "1", optional = true }`
+ * in `[dependencies]` table or `[dependencies.serde]` table.
+ *
+ * If the dependency is inherited from the workspace (`serde = { workspace = true }`), [packageName] and [version]
+ * are taken from `[workspace.dependencies]` table of the root manifest
+ */
+class CargoTomlDependency(
+    /** The key that declares the dependency, `serde` in the examples above */
+    val key: TomlKey,
+    /** A name of the package. Differs from [name] if the dependency is renamed via `package = "..."` */
+    val packageName: String,
+    val version: String?,
+    val isOptional: Boolean
+) {
+    val name: String get() = key.text
+}
+
+/** `[features]` table of Cargo.toml */
+val TomlFile.featuresTable: TomlTable?
+    get() = childrenOfType<TomlTable>().find { it.header.names.singleOrNull()?.text == "features" }
+
+val TomlFile.featureKeys: List<TomlKey>
+    get() = featuresTable?.entries?.map { it.key }.orEmpty()
+
+/** Dependencies of all kinds (including target-specific ones) declared in this Cargo.toml */
+val TomlFile.dependencies: List<CargoTomlDependency>
+    get() {
+        val result = mutableListOf<CargoTomlDependency>()
+        val workspaceManifest = lazy { findWorkspaceManifest() }
+        for (table in childrenOfType<TomlTable>()) {
+            val names = table.header.names
+            // `[workspace.dependencies]` are not dependencies of the package
+            if (names.firstOrNull()?.text == "workspace") continue
+            if (table.header.isDependencyListHeader) {
+                for (entry in table.entries) {
+                    result += createDependency(entry.key, entry.value, workspaceManifest)
+                }
+            } else if (names.getOrNull(names.size - 2)?.isDependencyKey == true) {
+                result += createDependency(names.last(), table, workspaceManifest)
+            }
+        }
+        return result
+    }
+
+/** Finds the declaration of dependency [name] in `[workspace.dependencies]` table of this (root) manifest */
+fun TomlFile.findWorkspaceDependency(name: String): CargoTomlDependency? {
+    val key = findWorkspaceDeclaration("dependencies", name) ?: return null
+    val value = when (val parent = key.parent) {
+        is TomlKeyValue -> parent.value
+        is TomlTableHeader -> parent.parent as? TomlTable
+        else -> null
+    }
+    return createDependency(key, value, workspaceManifest = null)
+}
+
+/**
+ * @param value either a version string (`serde = "1"`), an inline table (`serde = { version = "1" }`)
+ * or a table (`[dependencies.serde]`)
+ * @param workspaceManifest the root manifest used to resolve `workspace = true`. `null` for workspace dependencies
+ */
+private fun createDependency(key: TomlKey, value: TomlElement?, workspaceManifest: Lazy<TomlFile?>?): CargoTomlDependency {
+    val properties = value as? TomlKeyValueOwner
+    val entries = properties?.entries.orEmpty()
+    fun property(name: String): TomlValue? = entries.find { it.key.text == name }?.value
+
+    val isInherited = workspaceManifest != null && properties?.isInheritedFromWorkspace == true
+    val inherited = if (isInherited) workspaceManifest?.value?.findWorkspaceDependency(key.text) else null
+
+    val packageName = property("package")?.stringValue ?: inherited?.packageName ?: key.text
+    val version = (value as? TomlValue)?.stringValue ?: property("version")?.stringValue ?: inherited?.version
+    val isOptional = property("optional")?.text == "true"
+    return CargoTomlDependency(key, packageName, version, isOptional)
+}
+
+/** Finds the package of [dependency] among packages of the cargo project that owns this Cargo.toml */
+fun TomlFile.findDependencyPackage(dependency: CargoTomlDependency): CargoWorkspace.Package? =
+    cargoWorkspace?.findPackage(dependency.packageName)
+
+/** Finds the package declared by this Cargo.toml */
+fun TomlFile.findPackage(): CargoWorkspace.Package? {
+    val dir = originalFile.virtualFile?.parent ?: return null
+    return cargoWorkspace?.packages?.find { it.contentRoot == dir }
+}
+
+private val TomlFile.cargoWorkspace: CargoWorkspace?
+    get() {
+        val file = originalFile.virtualFile ?: return null
+        return project.cargoProjects.findProjectForFile(file)?.workspace
+    }
+
+/** Finds the declaration of feature [name] in `[features]` table of the package manifest */
+fun CargoWorkspace.Package.findFeatureKey(manager: PsiManager, name: String): TomlKey? {
+    val manifest = contentRoot?.findChild(RustToolchain.CARGO_TOML) ?: return null
+    val tomlFile = manager.findFile(manifest) as? TomlFile ?: return null
+    return tomlFile.featureKeys.find { it.text == name }
+}
+
+private inline fun <reified T : TomlElement> TomlFile.childrenOfType(): List<T> = children.filterIsInstance<T>()
@@ -201,6 +201,41 @@ object CargoTomlPsiPattern {
         tomlKeyValue("build").withParent(tomlTable("package"))
     )
 
+    /**
+     * ```
+     * [features]
+     * foo = [ "bar", "dep:baz", "serde/derive" ]
+     *          #^
+     * ```
+     */
+    val onFeatureDependencyLiteral: PsiElementPattern.Capture<TomlLiteral> = cargoTomlPsiElement<TomlLiteral>().withParent(
+        psiElement<TomlArray>().withParent(
+            psiElement<TomlKeyValue>().withParent(
+                tomlTable("features")
+            )
+        )
+    )
+
+    /** Any element inside [onFeatureDependencyLiteral] */
+    val inFeatureDependencyLiteral: PsiElementPattern.Capture<PsiElement> =
+        cargoTomlPsiElement<PsiElement>()
+            .withParent(onFeatureDependencyLiteral)
+
+    /**
+     * ```
+     * [package]
+     * version.workspace = true
+     *                    #^
+     * [dependencies]
+     * serde = { workspace = true }
+     *                      #^
+     * ```
+     *
+     * @see CargoTomlWorkspaceInheritance
+     */
+    val onWorkspaceInheritanceValue: PsiElementPattern.Capture<TomlLiteral> = cargoTomlPsiElement<TomlLiteral>()
+        .with("workspaceInheritanceCondition") { literal -> literal.workspaceInheritance != null }
+
     private fun tomlKeyValue(key: String): PsiElementPattern.Capture<TomlKeyValue> =
         psiElement<TomlKeyValue>().withChild(
             psiElement<TomlKey>().withText(key)
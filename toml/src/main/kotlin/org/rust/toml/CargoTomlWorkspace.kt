@@ -0,0 +1,171 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml
+
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.vfs.VirtualFile
+import com.intellij.psi.PsiParserFacade
+import com.intellij.psi.PsiWhiteSpace
+import org.rust.cargo.toolchain.RustToolchain
+import org.rust.openapiext.toPsiFile
+import org.toml.lang.psi.*
+
+/**
+ * A `workspace = true` entry that inherits a value from the root manifest of the workspace:
+ * ```
+ * [package]
+ * version.workspace = true                     # from `[workspace.package]`
+ *
+ * [dependencies]
+ * serde = { workspace = true, features = [] }  # from `[workspace.dependencies]`
+ *
+ * [lints]
+ * workspace = true                             # the whole `[workspace.lints]` table
+ * ```
+ */
+class CargoTomlWorkspaceInheritance(
+    /** The `true` literal of the entry */
+    val value: TomlLiteral,
+    /** A name of the table in `[workspace]`, e.g. `package` for `[workspace.package]` */
+    val tableName: String,
+    /** The inherited key, `version` or `serde` in the examples above. `null` if the whole table is inherited */
+    val keyName: String?
+) {
+    /** `[workspace.dependencies]` for dependencies */
+    val workspaceTableText: String get() = "[workspace.$tableName]"
+
+    fun findWorkspaceManifest(): TomlFile? = (value.containingFile as? TomlFile)?.findWorkspaceManifest()
+
+    /** Finds the inherited declaration: a key of the workspace table or the header of the table itself */
+    fun resolve(): TomlKey? = findWorkspaceManifest()?.findWorkspaceDeclaration(tableName, keyName)
+}
+
+/** Returns a [CargoTomlWorkspaceInheritance] if this literal is the `true` value of a `workspace = true` entry */
+val TomlLiteral.workspaceInheritance: CargoTomlWorkspaceInheritance?
+    get() {
+        if (text != "true") return null
+        val keyValue = parent as? TomlKeyValue ?: return null
+        val segments = keyValue.key.segments
+        if (segments.lastOrNull() != WORKSPACE_KEY) return null
+
+        val owner = keyValue.parent
+        val (table, keyName) = when {
+            // `version.workspace = true` or `serde.workspace = true`
+            owner is TomlTable && segments.size == 2 -> owner to segments.first()
+            // `[dependencies.serde]` or `[lints]` table with `workspace = true` entry
+            owner is TomlTable && segments.size == 1 -> {
+                val names = owner.header.names
+                if (names.singleOrNull()?.text == "lints") return CargoTomlWorkspaceInheritance(this, "lints", null)
+                if (names.firstOrNull()?.text == WORKSPACE_KEY || names.getOrNull(names.size - 2)?.isDependencyKey != true) {
+                    return null
+                }
+                return CargoTomlWorkspaceInheritance(this, "dependencies", names.last().text)
+            }
+            // `serde = { workspace = true }` or `version = { workspace = true }`
+            owner is TomlInlineTable && segments.size == 1 -> {
+                val outerKeyValue = owner.parent as? TomlKeyValue ?: return null
+                val table = outerKeyValue.parent as? TomlTable ?: return null
+                table to (outerKeyValue.key.segments.singleOrNull() ?: return null)
+            }
+            else -> return null
+        }
+
+        val header = table.header
+        val tableName = when {
+            header.names.firstOrNull()?.text == WORKSPACE_KEY -> return null
+            header.isDependencyListHeader -> "dependencies"
+            header.names.singleOrNull()?.text == "package" -> "package"
+            else -> return null
+        }
+        return CargoTomlWorkspaceInheritance(this, tableName, keyName)
+    }
+
+/** Returns `true` if the dependency or the package field declared by these entries is inherited from the workspace */
+val TomlKeyValueOwner.isInheritedFromWorkspace: Boolean
+    get() = entries.any { it.key.text == WORKSPACE_KEY && it.value?.text == "true" }
+
+/**
+ * Finds the root manifest of the workspace in the same way as Cargo does: it's either the manifest
+ * specified by `package.workspace` key or the closest manifest with `[workspace]` table
+ * (starting with this manifest itself)
+ */
+fun TomlFile.findWorkspaceManifest(): TomlFile? {
+    val dir = originalFile.virtualFile?.parent ?: return null
+    val explicitPath = tables.find { it.header.names.singleOrNull()?.text == "package" }
+        ?.entries?.find { it.key.text == WORKSPACE_KEY }
+        ?.value?.stringValue
+    if (explicitPath != null) {
+        return dir.findFileByRelativePath(explicitPath)?.findManifest(project)
+    }
+
+    var current: VirtualFile? = dir
+    while (current != null) {
+        val manifest = current.findManifest(project)
+        if (manifest != null && manifest.tables.any { it.header.names.firstOrNull()?.text == WORKSPACE_KEY }) {
+            return manifest
+        }
+        current = current.parent
+    }
+    return null
+}
+
+/**
+ * Finds the key `[keyName]` in `[workspace.<tableName>]` table or in the header of
+ * `[workspace.<tableName>.<keyName>]` table. If [keyName] is `null`, finds the table itself
+ */
+fun TomlFile.findWorkspaceDeclaration(tableName: String, keyName: String?): TomlKey? {
+    for (table in tables) {
+        val names = table.header.names
+        if (names.size < 2 || names[0].text != WORKSPACE_KEY || names[1].text != tableName) continue
+        when {
+            keyName == null -> return names[1]
+            names.size == 2 -> table.entries.find { it.key.text == keyName }?.let { return it.key }
+            names.size == 3 && names[2].text == keyName -> return names[2]
+        }
+    }
+    return null
+}
+
+/** Finds the table by its header text without brackets, e.g. `workspace.dependencies` */
+fun TomlFile.findTable(name: String): TomlTable? =
+    tables.find { table -> table.header.names.joinToString(".") { it.text } == name }
+
+/** Finds the table by its header text without brackets or adds a new table to the end of the file */
+fun TomlFile.findOrCreateTable(name: String): TomlTable {
+    findTable(name)?.let { return it }
+    val parserFacade = PsiParserFacade.SERVICE.getInstance(project)
+    val trailingWhitespace = lastChild as? PsiWhiteSpace
+    trailingWhitespace?.delete()
+    val newTable = add(TomlPsiFactory(project).createTable(name)) as TomlTable
+    if (newTable.prevSibling != null) {
+        addBefore(parserFacade.createWhiteSpaceFromText("\n\n"), newTable)
+    }
+    if (trailingWhitespace != null) {
+        add(parserFacade.createWhiteSpaceFromText("\n"))
+    }
+    return newTable
+}
+
+/** Adds `key = value` entry after the last entry of the table */
+fun TomlTable.addEntry(key: String, value: String): TomlKeyValue {
+    val keyValue = TomlPsiFactory(project).createKeyValue(key, value)
+    val anchor = entries.lastOrNull() ?: header
+    val added = addAfter(keyValue, anchor) as TomlKeyValue
+    addBefore(PsiParserFacade.SERVICE.getInstance(project).createWhiteSpaceFromText("\n"), added)
+    return added
+}
+
+private val TomlFile.tables: List<TomlTable>
+    get() = children.filterIsInstance<TomlTable>()
+
+/** `["version", "workspace"]` for `version.workspace` */
+private val TomlKey.segments: List<String>
+    get() = text.split('.').map { it.trim() }
+
+private fun VirtualFile.findManifest(project: Project): TomlFile? =
+    findChild(RustToolchain.CARGO_TOML)?.toPsiFile(project) as? TomlFile
+
+private const val WORKSPACE_KEY: String = "workspace"
@@ -51,6 +51,12 @@ val TomlKey.isDependencyKey: Boolean
 val TomlTableHeader.isDependencyListHeader: Boolean
     get() = names.lastOrNull()?.isDependencyKey == true
 
+val TomlValue.stringValue: String?
+    get() {
+        val kind = (this as? TomlLiteral)?.kind
+        return (kind as? TomlLiteralKind.String)?.value
+    }
+
 /** Inserts `=` between key and value if missed and wraps inserted string with quotes if needed */
 class StringValueInsertionHandler(private val keyValue: TomlKeyValue) : InsertHandler<LookupElement> {
     override fun handleInsert(context: InsertionContext, item: LookupElement) {
@@ -8,6 +8,7 @@ package org.rust.toml.completion
 import com.intellij.codeInsight.completion.CompletionContributor
 import com.intellij.codeInsight.completion.CompletionType.BASIC
 import org.rust.toml.CargoTomlPsiPattern.inDependencyKeyValue
+import org.rust.toml.CargoTomlPsiPattern.inFeatureDependencyLiteral
 import org.rust.toml.CargoTomlPsiPattern.inValueWithKey
 import org.rust.toml.CargoTomlPsiPattern.inKey
 import org.rust.toml.CargoTomlPsiPattern.inSpecificDependencyHeaderKey
@@ -22,6 +23,7 @@ class CargoTomlCompletionContributor : CompletionContributor() {
             extend(BASIC, inDependencyKeyValue, CargoTomlDependencyCompletionProvider())
             extend(BASIC, inSpecificDependencyHeaderKey, CargoTomlSpecificDependencyHeaderCompletionProvider())
             extend(BASIC, inSpecificDependencyKeyValue, CargoTomlSpecificDependencyVersionCompletionProvider())
+            extend(BASIC, inFeatureDependencyLiteral, CargoTomlFeatureCompletionProvider())
         }
     }
 }
@@ -13,6 +13,7 @@ import com.intellij.util.ProcessingContext
 import org.rust.lang.core.psi.ext.ancestorStrict
 import org.rust.toml.StringValueInsertionHandler
 import org.rust.toml.getClosestKeyValueAncestor
+import org.rust.toml.isInheritedFromWorkspace
 import org.toml.lang.psi.TomlKey
 import org.toml.lang.psi.TomlKeyValue
 import org.toml.lang.psi.TomlTable
@@ -79,6 +80,8 @@ class CargoTomlSpecificDependencyHeaderCompletionProvider : CompletionProvider<C
 /** @see CargoTomlPsiPattern.inSpecificDependencyKeyValue */
 class CargoTomlSpecificDependencyVersionCompletionProvider : TomlKeyValueCompletionProviderBase() {
     override fun completeKey(keyValue: TomlKeyValue, result: CompletionResultSet) {
+        // The version is inherited from `[workspace.dependencies]`
+        if ((keyValue.parent as? TomlTable)?.isInheritedFromWorkspace == true) return
         val dependencyNameKey = getDependencyKeyFromTableHeader(keyValue)
 
         val version = getCrateLastVersion(dependencyNameKey) ?: return
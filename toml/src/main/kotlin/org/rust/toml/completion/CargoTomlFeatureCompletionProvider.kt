@@ -0,0 +1,70 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.completion
+
+import com.intellij.codeInsight.completion.CompletionParameters
+import com.intellij.codeInsight.completion.CompletionProvider
+import com.intellij.codeInsight.completion.CompletionResultSet
+import com.intellij.codeInsight.lookup.LookupElementBuilder
+import com.intellij.util.ProcessingContext
+import org.rust.lang.core.psi.ext.startOffset
+import org.rust.toml.FeatureDependency
+import org.rust.toml.dependencies
+import org.rust.toml.featureKeys
+import org.rust.toml.findDependencyPackage
+import org.toml.lang.psi.TomlFile
+import org.toml.lang.psi.TomlKeyValue
+import org.toml.lang.psi.TomlLiteral
+import org.toml.lang.psi.ext.TomlLiteralKind
+import org.toml.lang.psi.ext.kind
+
+/**
+ * Completes values of feature arrays:
+ * ```
+ * [features]
+ * foo = ["bar", "dep:baz", "serde/derive", "serde?/std"]
+ * ```
+ *
+ * @see CargoTomlPsiPattern.inFeatureDependencyLiteral
+ */
+class CargoTomlFeatureCompletionProvider : CompletionProvider<CompletionParameters>() {
+    override fun addCompletions(parameters: CompletionParameters, context: ProcessingContext, result: CompletionResultSet) {
+        val literal = parameters.position.parent as? TomlLiteral ?: return
+        val file = parameters.originalFile as? TomlFile ?: return
+        val valueRange = (literal.kind as? TomlLiteralKind.String)?.offsets?.value ?: return
+        val caretOffset = parameters.offset - literal.startOffset
+        if (caretOffset < valueRange.startOffset) return
+
+        // `dep:` and `/` are not identifier parts, so the default prefix can't be used
+        val prefix = literal.text.substring(valueRange.startOffset, caretOffset)
+        val resultSet = result.withPrefixMatcher(prefix)
+        val currentFeature = (literal.parent?.parent as? TomlKeyValue)?.key?.text
+
+        for (key in file.featureKeys) {
+            val name = key.text
+            if (name == currentFeature) continue
+            resultSet.addElement(LookupElementBuilder.create(name))
+        }
+
+        for (dependency in file.dependencies) {
+            val name = dependency.name
+            if (dependency.isOptional) {
+                // Optional dependencies are implicit features
+                resultSet.addElement(LookupElementBuilder.create(name))
+                resultSet.addElement(LookupElementBuilder.create(FeatureDependency.OptionalDependency.PREFIX + name))
+            }
+            // Only the features known from the workspace metadata are completed, so dependencies
+            // that are not fetched yet don't have feature completion
+            val pkg = file.findDependencyPackage(dependency) ?: continue
+            for (feature in pkg.features.map { it.name }) {
+                resultSet.addElement(LookupElementBuilder.create("$name/$feature"))
+                if (dependency.isOptional) {
+                    resultSet.addElement(LookupElementBuilder.create("$name?/$feature"))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.inspections
+
+import com.intellij.codeInspection.LocalInspectionTool
+import com.intellij.codeInspection.ProblemsHolder
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiElementVisitor
+import org.rust.toml.*
+import org.toml.lang.psi.TomlFile
+import org.toml.lang.psi.TomlLiteral
+import org.toml.lang.psi.ext.TomlLiteralKind
+import org.toml.lang.psi.ext.kind
+
+/**
+ * Checks entries of feature arrays:
+ * ```
+ * [features]
+ * foo = ["bar", "dep:baz", "serde/derive"]
+ * ```
+ */
+class CargoTomlFeatureDependencyInspection : LocalInspectionTool() {
+    override fun buildVisitor(holder: ProblemsHolder, isOnTheFly: Boolean): PsiElementVisitor {
+        if (!tomlPluginIsAbiCompatible()) return PsiElementVisitor.EMPTY_VISITOR
+        return object : PsiElementVisitor() {
+            override fun visitElement(element: PsiElement) {
+                if (CargoTomlPsiPattern.onFeatureDependencyLiteral.accepts(element)) {
+                    checkFeatureDependency(holder, element as TomlLiteral)
+                }
+            }
+        }
+    }
+
+    private fun checkFeatureDependency(holder: ProblemsHolder, literal: TomlLiteral) {
+        val file = literal.containingFile as? TomlFile ?: return
+        val kind = literal.kind as? TomlLiteralKind.String ?: return
+        val value = kind.value ?: return
+        val valueOffset = kind.offsets.value?.startOffset ?: return
+
+        fun registerProblem(range: TextRange, message: String) =
+            holder.registerProblem(literal, range.shiftRight(valueOffset), message)
+
+        val dependencies = file.dependencies
+        when (val dependency = FeatureDependency.parse(value)) {
+            is FeatureDependency.Feature -> {
+                val name = dependency.name
+                val isKnown = file.featureKeys.any { it.text == name } ||
+                    dependencies.any { it.name == name && it.isOptional }
+                if (!isKnown) {
+                    registerProblem(TextRange.from(0, value.length), "Unknown feature `$name`")
+                }
+            }
+            is FeatureDependency.OptionalDependency -> {
+                val name = dependency.dependencyName
+                val range = dependency.dependencyNameRange
+                val declaration = dependencies.find { it.name == name }
+                when {
+                    declaration == null -> registerProblem(range, "Unknown dependency `$name`")
+                    !declaration.isOptional -> registerProblem(range, "Dependency `$name` is not optional")
+                }
+            }
+            is FeatureDependency.DependencyFeature -> {
+                val name = dependency.dependencyName
+                val declaration = dependencies.find { it.name == name }
+                if (declaration == null) {
+                    registerProblem(dependency.dependencyNameRange, "Unknown dependency `$name`")
+                    return
+                }
+                if (dependency.isWeak && !declaration.isOptional) {
+                    registerProblem(dependency.dependencyNameRange, "Dependency `$name` is not optional")
+                }
+                // Features of dependencies that are not fetched yet are unknown
+                val pkg = file.findDependencyPackage(declaration) ?: return
+                val feature = dependency.featureName
+                if (pkg.features.none { it.name == feature }) {
+                    registerProblem(dependency.featureNameRange, "Unknown feature `$feature` of `$name`")
+                }
+            }
+        }
+    }
+}
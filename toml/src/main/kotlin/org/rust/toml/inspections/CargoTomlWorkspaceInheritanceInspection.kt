@@ -0,0 +1,98 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.inspections
+
+import com.intellij.codeInspection.LocalInspectionTool
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.codeInspection.ProblemHighlightType
+import com.intellij.codeInspection.ProblemsHolder
+import com.intellij.openapi.project.Project
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiElementVisitor
+import com.intellij.psi.PsiFile
+import org.rust.toml.*
+import org.toml.lang.psi.TomlFile
+import org.toml.lang.psi.TomlLiteral
+
+/**
+ * Checks that `workspace = true` entries refer to existing keys of the root manifest:
+ * ```
+ * [package]
+ * version.workspace = true            # `[workspace.package]` must contain `version`
+ *
+ * [dependencies]
+ * serde = { workspace = true }        # `[workspace.dependencies]` must contain `serde`
+ * ```
+ */
+class CargoTomlWorkspaceInheritanceInspection : LocalInspectionTool() {
+    override fun buildVisitor(holder: ProblemsHolder, isOnTheFly: Boolean): PsiElementVisitor {
+        if (!tomlPluginIsAbiCompatible()) return PsiElementVisitor.EMPTY_VISITOR
+        return object : PsiElementVisitor() {
+            override fun visitElement(element: PsiElement) {
+                if (CargoTomlPsiPattern.onWorkspaceInheritanceValue.accepts(element)) {
+                    checkInheritance(holder, element as TomlLiteral)
+                }
+            }
+        }
+    }
+
+    private fun checkInheritance(holder: ProblemsHolder, literal: TomlLiteral) {
+        val inheritance = literal.workspaceInheritance ?: return
+        // The workspace may be not loaded yet, so nothing is reported without the root manifest
+        val manifest = inheritance.findWorkspaceManifest() ?: return
+        if (manifest.findWorkspaceDeclaration(inheritance.tableName, inheritance.keyName) != null) return
+
+        val keyName = inheritance.keyName
+        val message = if (keyName == null) {
+            "`${inheritance.workspaceTableText}` table is not declared in the workspace manifest"
+        } else {
+            "`$keyName` is not declared in `${inheritance.workspaceTableText}` of the workspace manifest"
+        }
+        holder.registerProblem(
+            literal,
+            message,
+            ProblemHighlightType.GENERIC_ERROR,
+            AddToWorkspaceFix(literal, inheritance.workspaceTableText, keyName)
+        )
+    }
+
+    private class AddToWorkspaceFix(
+        literal: TomlLiteral,
+        private val workspaceTableText: String,
+        private val keyName: String?
+    ) : LocalQuickFixOnPsiElement(literal) {
+        override fun getText(): String =
+            if (keyName == null) "Add `$workspaceTableText` to the workspace manifest" else "Add `$keyName` to `$workspaceTableText`"
+
+        override fun getFamilyName(): String = "Add to the workspace manifest"
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val inheritance = (startElement as? TomlLiteral)?.workspaceInheritance ?: return
+            val manifest = inheritance.findWorkspaceManifest() ?: return
+            val table = manifest.findOrCreateTable("workspace.${inheritance.tableName}")
+            val keyName = inheritance.keyName ?: return
+            table.addEntry(keyName, "\"${defaultValue(inheritance, keyName)}\"")
+        }
+
+        /**
+         * The version of the dependency or the value of the package field known from the cargo project,
+         * otherwise a placeholder value that should be filled by the user
+         */
+        private fun defaultValue(inheritance: CargoTomlWorkspaceInheritance, keyName: String): String {
+            val file = inheritance.value.containingFile as? TomlFile ?: return "*"
+            if (inheritance.tableName == "dependencies") {
+                val dependency = file.dependencies.find { it.name == keyName } ?: return "*"
+                return file.findDependencyPackage(dependency)?.version ?: "*"
+            }
+            val pkg = file.findPackage() ?: return ""
+            return when (keyName) {
+                "version" -> pkg.version
+                "edition" -> pkg.edition.presentation
+                else -> ""
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.inspections
+
+import com.intellij.codeInspection.LocalQuickFixOnPsiElement
+import com.intellij.openapi.project.Project
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiElementVisitor
+import com.intellij.psi.PsiFile
+import org.rust.cargo.project.workspace.CargoWorkspace
+import org.rust.cargo.project.workspace.PackageOrigin
+import org.rust.cargo.toolchain.RustToolchain
+import org.rust.ide.inspections.RsLocalInspectionTool
+import org.rust.ide.inspections.RsProblemsHolder
+import org.rust.lang.core.psi.RsLitExpr
+import org.rust.lang.core.psi.RsMetaItem
+import org.rust.lang.core.psi.RsVisitor
+import org.rust.lang.core.psi.ext.containingCargoPackage
+import org.rust.lang.core.psi.ext.isCfgPredicate
+import org.rust.lang.core.psi.ext.name
+import org.rust.lang.core.psi.ext.value
+import org.rust.openapiext.toPsiFile
+import org.rust.toml.*
+import org.toml.lang.psi.TomlFile
+
+/**
+ * Reports features in `#[cfg(feature = "foo")]` predicates that are declared neither in `[features]` table
+ * nor as an optional dependency in Cargo.toml of the package that owns the file
+ */
+class CargoUnknownFeatureInspection : RsLocalInspectionTool() {
+    override fun getDisplayName() = "Unknown Cargo feature"
+
+    override fun buildVisitor(holder: RsProblemsHolder, isOnTheFly: Boolean): PsiElementVisitor? {
+        if (!tomlPluginIsAbiCompatible()) return null
+        return object : RsVisitor() {
+            override fun visitMetaItem(o: RsMetaItem) {
+                if (o.name != "feature" || !o.isCfgPredicate) return
+                val literal = o.litExpr ?: return
+                val feature = o.value ?: return
+                val pkg = o.containingCargoPackage ?: return
+                if (pkg.origin == PackageOrigin.STDLIB) return
+                val manifest = pkg.findManifest(o.project) ?: return
+                if (manifest.isFeatureDeclared(feature)) return
+
+                holder.registerProblem(
+                    literal,
+                    TextRange(1, literal.textLength - 1),
+                    "Feature `$feature` is not declared in Cargo.toml of package `${pkg.name}`",
+                    AddFeatureToManifestFix(literal, feature)
+                )
+            }
+        }
+    }
+
+    private class AddFeatureToManifestFix(
+        literal: RsLitExpr,
+        private val feature: String
+    ) : LocalQuickFixOnPsiElement(literal) {
+        override fun getText(): String = "Add feature `$feature` to Cargo.toml"
+
+        override fun getFamilyName(): String = "Add feature to Cargo.toml"
+
+        override fun invoke(project: Project, file: PsiFile, startElement: PsiElement, endElement: PsiElement) {
+            val manifest = (startElement as? RsLitExpr)?.containingCargoPackage?.findManifest(project) ?: return
+            if (manifest.isFeatureDeclared(feature)) return
+            manifest.findOrCreateTable("features").addEntry(feature, "[]")
+        }
+    }
+}
+
+private fun CargoWorkspace.Package.findManifest(project: Project): TomlFile? =
+    contentRoot?.findChild(RustToolchain.CARGO_TOML)?.toPsiFile(project) as? TomlFile
+
+/** Optional dependencies define implicit features with the same names */
+private fun TomlFile.isFeatureDeclared(name: String): Boolean =
+    featureKeys.any { it.text == name } || dependencies.any { it.isOptional && it.name == name }
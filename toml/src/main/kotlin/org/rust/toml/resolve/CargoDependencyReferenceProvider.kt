@@ -11,7 +11,10 @@ import com.intellij.psi.*
 import com.intellij.util.ProcessingContext
 import org.rust.cargo.project.model.cargoProjects
 import org.rust.lang.core.psi.RsFile
-import org.toml.lang.psi.TomlKey
+import org.rust.toml.findWorkspaceDeclaration
+import org.rust.toml.findWorkspaceManifest
+import org.rust.toml.isInheritedFromWorkspace
+import org.toml.lang.psi.*
 
 class CargoDependencyReferenceProvider : PsiReferenceProvider() {
 
@@ -24,6 +27,11 @@ class CargoDependencyReferenceProvider : PsiReferenceProvider() {
 private class CargoDependencyReferenceImpl(key: TomlKey) : PsiReferenceBase<TomlKey>(key) {
 
     override fun resolve(): PsiElement? {
+        // `serde = { workspace = true }` refers to the declaration in the root manifest
+        if (element.dependencyProperties?.isInheritedFromWorkspace == true) {
+            val manifest = (element.containingFile as? TomlFile)?.findWorkspaceManifest() ?: return null
+            return manifest.findWorkspaceDeclaration("dependencies", element.text)
+        }
         val project = element.project
         val file = element.containingFile?.virtualFile ?: return null
         val cargoProject = project.cargoProjects.findProjectForFile(file) ?: return null
@@ -35,3 +43,11 @@ private class CargoDependencyReferenceImpl(key: TomlKey) : PsiReferenceBase<Toml
 
     override fun calculateDefaultRangeInElement(): TextRange = TextRange.from(0, element.textLength)
 }
+
+/** `{ ... }` in `serde = { ... }` or `[dependencies.serde]` table */
+private val TomlKey.dependencyProperties: TomlKeyValueOwner?
+    get() = when (val parent = parent) {
+        is TomlKeyValue -> parent.value as? TomlInlineTable
+        is TomlTableHeader -> parent.parent as? TomlTable
+        else -> null
+    }
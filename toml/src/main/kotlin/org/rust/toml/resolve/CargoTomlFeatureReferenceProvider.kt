@@ -0,0 +1,102 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.resolve
+
+import com.intellij.codeInsight.lookup.LookupElement
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.*
+import com.intellij.util.ProcessingContext
+import org.rust.toml.*
+import org.toml.lang.psi.TomlFile
+import org.toml.lang.psi.TomlLiteral
+import org.toml.lang.psi.ext.TomlLiteralKind
+import org.toml.lang.psi.ext.kind
+
+/** @see CargoTomlPsiPattern.onFeatureDependencyLiteral */
+class CargoTomlFeatureReferenceProvider : PsiReferenceProvider() {
+
+    override fun getReferencesByElement(element: PsiElement, context: ProcessingContext): Array<PsiReference> {
+        if (element !is TomlLiteral) return emptyArray()
+        val kind = element.kind as? TomlLiteralKind.String ?: return emptyArray()
+        val value = kind.value ?: return emptyArray()
+        val valueOffset = kind.offsets.value?.startOffset ?: return emptyArray()
+
+        return when (val dependency = FeatureDependency.parse(value)) {
+            is FeatureDependency.Feature -> arrayOf(
+                CargoFeatureReferenceImpl(element, TextRange.from(valueOffset, value.length), dependency.name)
+            )
+            is FeatureDependency.OptionalDependency -> arrayOf(
+                CargoDependencyKeyReferenceImpl(
+                    element,
+                    dependency.dependencyNameRange.shiftRight(valueOffset),
+                    dependency.dependencyName
+                )
+            )
+            is FeatureDependency.DependencyFeature -> arrayOf(
+                CargoDependencyKeyReferenceImpl(
+                    element,
+                    dependency.dependencyNameRange.shiftRight(valueOffset),
+                    dependency.dependencyName
+                ),
+                CargoDependencyFeatureReferenceImpl(
+                    element,
+                    dependency.featureNameRange.shiftRight(valueOffset),
+                    dependency.dependencyName,
+                    dependency.featureName
+                )
+            )
+        }
+    }
+}
+
+/** `bar` in `foo = ["bar"]`. Refers to another feature or to an optional dependency of the same package */
+private class CargoFeatureReferenceImpl(
+    literal: TomlLiteral,
+    range: TextRange,
+    private val name: String
+) : PsiReferenceBase<TomlLiteral>(literal, range) {
+
+    override fun resolve(): PsiElement? {
+        val file = element.containingFile as? TomlFile ?: return null
+        return file.featureKeys.find { it.text == name }
+            ?: file.dependencies.find { it.name == name && it.isOptional }?.key
+    }
+
+    override fun getVariants(): Array<out LookupElement> = LookupElement.EMPTY_ARRAY
+}
+
+/** `baz` in `foo = ["dep:baz"]` or `serde` in `foo = ["serde/derive"]` */
+private class CargoDependencyKeyReferenceImpl(
+    literal: TomlLiteral,
+    range: TextRange,
+    private val dependencyName: String
+) : PsiReferenceBase<TomlLiteral>(literal, range) {
+
+    override fun resolve(): PsiElement? {
+        val file = element.containingFile as? TomlFile ?: return null
+        return file.dependencies.find { it.name == dependencyName }?.key
+    }
+
+    override fun getVariants(): Array<out LookupElement> = LookupElement.EMPTY_ARRAY
+}
+
+/** `derive` in `foo = ["serde/derive"]`. Refers to a feature declared in Cargo.toml of the dependency */
+private class CargoDependencyFeatureReferenceImpl(
+    literal: TomlLiteral,
+    range: TextRange,
+    private val dependencyName: String,
+    private val featureName: String
+) : PsiReferenceBase<TomlLiteral>(literal, range) {
+
+    override fun resolve(): PsiElement? {
+        val file = element.containingFile as? TomlFile ?: return null
+        val dependency = file.dependencies.find { it.name == dependencyName } ?: return null
+        val pkg = file.findDependencyPackage(dependency) ?: return null
+        return pkg.findFeatureKey(element.manager, featureName)
+    }
+
+    override fun getVariants(): Array<out LookupElement> = LookupElement.EMPTY_ARRAY
+}
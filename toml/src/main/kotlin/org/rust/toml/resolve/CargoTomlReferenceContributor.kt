@@ -9,7 +9,9 @@ import com.intellij.psi.PsiReferenceContributor
 import com.intellij.psi.PsiReferenceRegistrar
 import org.rust.lang.core.or
 import org.rust.toml.CargoTomlPsiPattern.onDependencyKey
+import org.rust.toml.CargoTomlPsiPattern.onFeatureDependencyLiteral
 import org.rust.toml.CargoTomlPsiPattern.onSpecificDependencyHeaderKey
+import org.rust.toml.CargoTomlPsiPattern.onWorkspaceInheritanceValue
 import org.rust.toml.tomlPluginIsAbiCompatible
 
 class CargoTomlReferenceContributor : PsiReferenceContributor() {
@@ -20,6 +22,8 @@ class CargoTomlReferenceContributor : PsiReferenceContributor() {
                 onDependencyKey or onSpecificDependencyHeaderKey,
                 CargoDependencyReferenceProvider()
             )
+            registrar.registerReferenceProvider(onFeatureDependencyLiteral, CargoTomlFeatureReferenceProvider())
+            registrar.registerReferenceProvider(onWorkspaceInheritanceValue, CargoTomlWorkspaceReferenceProvider())
             for (type in PathPatternType.values()) {
                 registrar.registerReferenceProvider(type.pattern, CargoTomlFileReferenceProvider(type))
             }
@@ -0,0 +1,35 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.resolve
+
+import com.intellij.codeInsight.lookup.LookupElement
+import com.intellij.openapi.util.TextRange
+import com.intellij.psi.PsiElement
+import com.intellij.psi.PsiReference
+import com.intellij.psi.PsiReferenceBase
+import com.intellij.psi.PsiReferenceProvider
+import com.intellij.util.ProcessingContext
+import org.rust.toml.workspaceInheritance
+import org.toml.lang.psi.TomlLiteral
+
+/** @see org.rust.toml.CargoTomlPsiPattern.onWorkspaceInheritanceValue */
+class CargoTomlWorkspaceReferenceProvider : PsiReferenceProvider() {
+
+    override fun getReferencesByElement(element: PsiElement, context: ProcessingContext): Array<PsiReference> {
+        if (element !is TomlLiteral) return emptyArray()
+        return arrayOf(CargoTomlWorkspaceReferenceImpl(element))
+    }
+}
+
+/** `true` in `version.workspace = true`. Refers to the inherited key in the root manifest */
+private class CargoTomlWorkspaceReferenceImpl(literal: TomlLiteral) : PsiReferenceBase<TomlLiteral>(literal) {
+
+    override fun resolve(): PsiElement? = element.workspaceInheritance?.resolve()
+
+    override fun getVariants(): Array<out LookupElement> = LookupElement.EMPTY_ARRAY
+
+    override fun calculateDefaultRangeInElement(): TextRange = TextRange.from(0, element.textLength)
+}
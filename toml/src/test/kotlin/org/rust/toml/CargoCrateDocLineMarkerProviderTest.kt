@@ -15,8 +15,8 @@ import org.rust.WithStdlibAndDependencyRustProjectDescriptor
 
 @ProjectDescriptor(WithStdlibAndDependencyRustProjectDescriptor::class)
 class CargoCrateDocLineMarkerProviderTest : RsTestBase() {
-    protected fun doTestByText(@Language("toml") source: String) {
-        myFixture.configureByText("cargo.toml", source)
+    protected fun doTestByText(@Language("toml") source: String, fileName: String = "cargo.toml") {
+        myFixture.configureByText(fileName, source)
         myFixture.doHighlighting()
         val expected = markersFrom(source)
         val actual = markersFrom(myFixture.editor, myFixture.project)
@@ -91,4 +91,12 @@ class CargoCrateDocLineMarkerProviderTest : RsTestBase() {
         serde_yaml_rs = { package = 'serde_yaml', version = '''0.8.11''' }  # - Open documentation for `serde_yaml@0.8.11`
         serde_derive_rs = { package = "serde\u005Fderive", version ="1\u002E0\u002E104" }  # - Open documentation for `serde_derive@1.0.104`
     """)
+
+    fun `test dependency inherited from workspace`() = doTestByText("""
+        [workspace.dependencies]
+        serde = "1.0.104"  # - Open documentation for `serde@1.0.104`
+
+        [dependencies]
+        serde = { workspace = true }  # - Open documentation for `serde@1.0.104`
+    """, fileName = "Cargo.toml")
 }
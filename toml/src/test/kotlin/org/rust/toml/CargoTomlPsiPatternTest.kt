@@ -16,6 +16,7 @@ import org.rust.toml.CargoTomlPsiPattern.inSpecificDependencyHeaderKey
 import org.rust.toml.CargoTomlPsiPattern.inSpecificDependencyKeyValue
 import org.rust.toml.CargoTomlPsiPattern.onDependencyKey
 import org.rust.toml.CargoTomlPsiPattern.onSpecificDependencyHeaderKey
+import org.rust.toml.CargoTomlPsiPattern.onWorkspaceInheritanceValue
 import org.rust.toml.CargoTomlPsiPattern.packageWorkspacePath
 import org.rust.toml.CargoTomlPsiPattern.path
 import org.rust.toml.CargoTomlPsiPattern.workspacePath
@@ -164,6 +165,48 @@ class CargoTomlPsiPatternTest : RsTestBase() {
                   #^
     """)
 
+    fun `test workspace inheritance of package field`() = testPattern(onWorkspaceInheritanceValue, """
+        [package]
+        version.workspace = true
+                           #^
+    """)
+
+    fun `test workspace inheritance of package field in inline table`() = testPattern(onWorkspaceInheritanceValue, """
+        [package]
+        edition = { workspace = true }
+                               #^
+    """)
+
+    fun `test workspace inheritance of dependency`() = testPattern(onWorkspaceInheritanceValue, """
+        [dependencies]
+        serde = { workspace = true, features = ["derive"] }
+                             #^
+    """)
+
+    fun `test workspace inheritance of specific dependency`() = testPattern(onWorkspaceInheritanceValue, """
+        [dev-dependencies.serde]
+        workspace = true
+                    #^
+    """)
+
+    fun `test workspace inheritance of lints`() = testPattern(onWorkspaceInheritanceValue, """
+        [lints]
+        workspace = true
+                    #^
+    """)
+
+    fun `test workspace inheritance in workspace dependencies`() = testPatternNegative(onWorkspaceInheritanceValue, """
+        [workspace.dependencies]
+        serde = { workspace = true }
+                             #^
+    """)
+
+    fun `test workspace inheritance with false value`() = testPatternNegative(onWorkspaceInheritanceValue, """
+        [dependencies]
+        serde = { workspace = false }
+                              #^
+    """)
+
     private inline fun <reified T : PsiElement> testPattern(
         pattern: ElementPattern<T>,
         @Language("Toml") code: String,
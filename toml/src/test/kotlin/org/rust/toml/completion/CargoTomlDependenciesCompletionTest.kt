@@ -155,6 +155,12 @@ class CargoTomlDependenciesCompletionTest : CargoTomlCompletionTestBase() {
         version = "1.0<caret>"
     """, "dep" to "1.0")
 
+    fun `test no version completion for specific dependency inherited from workspace`() = checkNoCompletion("""
+        [dependencies.dep]
+        workspace = true
+        <caret>
+    """, "dep" to "1.0")
+
     private fun doTest(
         @Language("TOML") before: String,
         @Language("TOML") after: String,
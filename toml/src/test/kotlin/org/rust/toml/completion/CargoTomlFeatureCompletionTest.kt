@@ -0,0 +1,98 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.completion
+
+import org.intellij.lang.annotations.Language
+import org.rust.ProjectDescriptor
+import org.rust.RsTestBase
+import org.rust.WithDependencyRustProjectDescriptor
+import org.rust.lang.core.completion.RsCompletionTestFixture
+
+@ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+class CargoTomlFeatureCompletionTest : RsTestBase() {
+
+    private lateinit var completionFixture: RsCompletionTestFixture
+
+    override fun setUp() {
+        super.setUp()
+        completionFixture = RsCompletionTestFixture(myFixture, "Cargo.toml")
+        completionFixture.setUp()
+    }
+
+    override fun tearDown() {
+        completionFixture.tearDown()
+        super.tearDown()
+    }
+
+    fun `test features of the same package`() = checkContainsCompletion(listOf("std", "dep-lib-2"), """
+        [features]
+        default = ["<caret>"]
+        std = []
+    """)
+
+    fun `test current feature is not completed`() = checkNotContainsCompletion("default", """
+        [features]
+        default = ["<caret>"]
+        std = []
+    """)
+
+    fun `test optional dependency`() = doSingleCompletion("""
+        [features]
+        default = ["dep:<caret>"]
+    """, """
+        [features]
+        default = ["dep:dep-lib-2<caret>"]
+    """)
+
+    fun `test non-optional dependency is not completed after dep`() = checkNotContainsCompletion("dep:renamed", """
+        [features]
+        default = ["<caret>"]
+    """)
+
+    fun `test dependency features`() = checkContainsCompletion(
+        listOf("dep-lib-2/foo", "dep-lib-2/bar", "dep-lib-2?/foo", "dep-lib-2?/bar"),
+        """
+            [features]
+            default = ["dep-lib-2<caret>"]
+        """
+    )
+
+    fun `test renamed dependency features`() = doSingleCompletion("""
+        [features]
+        default = ["renamed/f<caret>"]
+    """, """
+        [features]
+        default = ["renamed/foo<caret>"]
+    """)
+
+    fun `test weak features of non-optional dependency are not completed`() =
+        checkNotContainsCompletion("renamed?/foo", """
+            [features]
+            default = ["<caret>"]
+        """)
+
+    private fun doSingleCompletion(@Language("TOML") before: String, @Language("TOML") after: String) =
+        completionFixture.doSingleCompletion(withManifestHeader(before), withManifestHeader(after))
+
+    private fun checkContainsCompletion(variants: List<String>, @Language("TOML") code: String) =
+        completionFixture.checkContainsCompletion(withManifestHeader(code), variants)
+
+    private fun checkNotContainsCompletion(variant: String, @Language("TOML") code: String) =
+        completionFixture.checkNotContainsCompletion(withManifestHeader(code), variant)
+
+    /** Adds `[package]` and `[dependencies]` tables with optional `dep-lib-2` and non-optional `renamed` deps */
+    private fun withManifestHeader(code: String): String = """
+        [package]
+        name = "intellij-rust-test"
+        version = "0.1.0"
+        authors = []
+
+        [dependencies]
+        dep-lib-2 = { version = "0.0.1", optional = true }
+        renamed = { package = "dep-lib-2", version = "0.0.1" }
+
+    """.trimIndent() + code.trimIndent()
+}
@@ -0,0 +1,67 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.inspections
+
+import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
+import org.rust.ide.annotator.RsAnnotationTestBase
+import org.rust.ide.annotator.RsAnnotationTestFixture
+
+@ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+class CargoTomlFeatureDependencyInspectionTest : RsAnnotationTestBase() {
+
+    override fun createAnnotationFixture(): RsAnnotationTestFixture = RsAnnotationTestFixture(
+        myFixture,
+        inspectionClasses = listOf(CargoTomlFeatureDependencyInspection::class),
+        baseFileName = "Cargo.toml"
+    )
+
+    fun `test valid feature dependencies`() = checkByText("""
+        [dependencies]
+        dep-lib-2 = { version = "0.0.1", optional = true }
+        renamed = { package = "dep-lib-2", version = "0.0.1" }
+
+        [features]
+        default = ["std", "dep-lib-2", "dep:dep-lib-2", "dep-lib-2?/foo", "renamed/bar"]
+        std = []
+    """)
+
+    fun `test unknown feature`() = checkByText("""
+        [features]
+        default = ["<warning descr="Unknown feature `std`">std</warning>"]
+    """)
+
+    fun `test unknown dependency`() = checkByText("""
+        [features]
+        default = ["dep:<warning descr="Unknown dependency `foo`">foo</warning>", "<warning descr="Unknown dependency `bar`">bar</warning>/baz"]
+    """)
+
+    fun `test non-optional dependency`() = checkByText("""
+        [dependencies]
+        dep-lib-2 = "0.0.1"
+
+        [features]
+        default = ["dep:<warning descr="Dependency `dep-lib-2` is not optional">dep-lib-2</warning>"]
+        std = ["<warning descr="Dependency `dep-lib-2` is not optional">dep-lib-2</warning>?/foo"]
+    """)
+
+    fun `test unknown feature of dependency`() = checkByText("""
+        [dependencies.renamed]
+        package = "dep-lib-2"
+        version = "0.0.1"
+
+        [features]
+        default = ["renamed/<warning descr="Unknown feature `baz` of `renamed`">baz</warning>"]
+    """)
+
+    fun `test features of not fetched dependency are not checked`() = checkByText("""
+        [dependencies]
+        unknown-lib = "1.0"
+
+        [features]
+        default = ["unknown-lib/baz"]
+    """)
+}
@@ -0,0 +1,137 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.inspections
+
+import org.rust.ide.annotator.RsAnnotationTestBase
+import org.rust.ide.annotator.RsAnnotationTestFixture
+
+class CargoTomlWorkspaceInheritanceInspectionTest : RsAnnotationTestBase() {
+
+    override fun createAnnotationFixture(): RsAnnotationTestFixture = RsAnnotationTestFixture(
+        myFixture,
+        inspectionClasses = listOf(CargoTomlWorkspaceInheritanceInspection::class),
+        baseFileName = "Cargo.toml"
+    )
+
+    fun `test inherited keys`() = checkByFileTree("""
+    //- Cargo.toml
+        [workspace]
+        members = ["member"]
+
+        [workspace.package]
+        version = "1.0.0"
+
+        [workspace.dependencies]
+        serde = "1.0"
+
+        [workspace.dependencies.rand]
+        version = "0.7"
+
+        [workspace.lints.rust]
+        unsafe_code = "forbid"
+    //- member/Cargo.toml
+        [package]
+        name = "member"
+        version.workspace = true/*caret*/
+
+        [dependencies]
+        serde = { workspace = true, features = ["derive"] }
+        rand.workspace = true
+
+        [lints]
+        workspace = true
+    """, stubOnly = false)
+
+    fun `test unknown keys`() = checkByFileTree("""
+    //- Cargo.toml
+        [workspace]
+        members = ["member"]
+
+        [workspace.package]
+        version = "1.0.0"
+    //- member/Cargo.toml
+        [package]
+        name = "member"
+        version.workspace = true
+        edition = { workspace = <error descr="`edition` is not declared in `[workspace.package]` of the workspace manifest">true</error> }/*caret*/
+
+        [dev-dependencies]
+        serde = { workspace = <error descr="`serde` is not declared in `[workspace.dependencies]` of the workspace manifest">true</error> }
+
+        [lints]
+        workspace = <error descr="`[workspace.lints]` table is not declared in the workspace manifest">true</error>
+    """, stubOnly = false)
+
+    fun `test no errors without workspace manifest`() = checkByText("""
+        [package]
+        name = "foo"
+        version.workspace = true
+
+        [dependencies]
+        serde = { workspace = true }
+    """)
+
+    fun `test add dependency to workspace`() = checkFixByFileTree("Add `regex` to `[workspace.dependencies]`", """
+    //- Cargo.toml
+        [workspace]
+        members = ["member"]
+
+        [workspace.dependencies]
+        serde = "1.0"
+    //- member/Cargo.toml
+        [dependencies]
+        regex = { workspace = <error>true/*caret*/</error> }
+    """, """
+    //- Cargo.toml
+        [workspace]
+        members = ["member"]
+
+        [workspace.dependencies]
+        serde = "1.0"
+        regex = "*"
+    //- member/Cargo.toml
+        [dependencies]
+        regex = { workspace = true }
+    """, stubOnly = false)
+
+    fun `test add workspace table with package field`() = checkFixByFileTree("Add `version` to `[workspace.package]`", """
+    //- Cargo.toml
+        [workspace]
+        members = ["member"]
+    //- member/Cargo.toml
+        [package]
+        version.workspace = <error>true/*caret*/</error>
+    """, """
+    //- Cargo.toml
+        [workspace]
+        members = ["member"]
+
+        [workspace.package]
+        version = ""
+    //- member/Cargo.toml
+        [package]
+        version.workspace = true
+    """, stubOnly = false)
+
+    fun `test add package field with value from cargo project`() = checkFixByFileTree("Add `version` to `[workspace.package]`", """
+    //- Cargo.toml
+        [workspace]
+
+        [package]
+        name = "test-package"
+        version.workspace = <error>true/*caret*/</error>
+    """, """
+    //- Cargo.toml
+        [workspace]
+
+        [package]
+        name = "test-package"
+        version.workspace = true
+
+        [workspace.package]
+        version = "0.0.1"
+    """, stubOnly = false)
+}
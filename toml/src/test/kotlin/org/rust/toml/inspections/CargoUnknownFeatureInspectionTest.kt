@@ -0,0 +1,91 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.inspections
+
+import org.rust.ide.inspections.RsInspectionsTestBase
+
+class CargoUnknownFeatureInspectionTest : RsInspectionsTestBase(CargoUnknownFeatureInspection::class) {
+    fun `test declared features`() = checkByFileTree("""
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+
+        [features]
+        foo = []
+
+        [dependencies]
+        serde = { version = "1", optional = true }
+    //- main.rs
+        #[cfg(any(feature = "foo", feature = "serde"))]/*caret*/
+        fn foo() {}
+    """, stubOnly = false)
+
+    fun `test unknown feature`() = checkByFileTree("""
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+
+        [features]
+        foo = []
+    //- main.rs
+        #[cfg(feature = "<warning descr="Feature `bar` is not declared in Cargo.toml of package `test-package`">bar</warning>")]/*caret*/
+        fn foo() {}
+
+        #[cfg_attr(not(feature = "<warning descr="Feature `baz` is not declared in Cargo.toml of package `test-package`">baz</warning>"), derive(Debug))]
+        struct S;
+    """, stubOnly = false)
+
+    fun `test not a cfg predicate`() = checkByFileTree("""
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+    //- main.rs
+        #[cfg_attr(unix, doc(feature = "bar"))]/*caret*/
+        fn foo() {}
+    """, stubOnly = false)
+
+    fun `test add feature`() = checkFixByFileTree("Add feature `bar` to Cargo.toml", """
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+
+        [features]
+        foo = []
+    //- main.rs
+        #[cfg(feature = "<warning>bar/*caret*/</warning>")]
+        fn foo() {}
+    """, """
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+
+        [features]
+        foo = []
+        bar = []
+    //- main.rs
+        #[cfg(feature = "bar")]
+        fn foo() {}
+    """, stubOnly = false)
+
+    fun `test add features table`() = checkFixByFileTree("Add feature `bar` to Cargo.toml", """
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+    //- main.rs
+        #[cfg(feature = "<warning>bar/*caret*/</warning>")]
+        fn foo() {}
+    """, """
+    //- Cargo.toml
+        [package]
+        name = "test-package"
+
+        [features]
+        bar = []
+    //- main.rs
+        #[cfg(feature = "bar")]
+        fn foo() {}
+    """, stubOnly = false)
+}
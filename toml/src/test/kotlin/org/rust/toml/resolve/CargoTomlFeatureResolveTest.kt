@@ -0,0 +1,98 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.resolve
+
+import org.rust.ProjectDescriptor
+import org.rust.WithDependencyRustProjectDescriptor
+import org.rust.fileTree
+import org.rust.lang.core.resolve.checkedResolve
+import org.toml.lang.psi.TomlKey
+import org.toml.lang.psi.TomlLiteral
+
+@ProjectDescriptor(WithDependencyRustProjectDescriptor::class)
+class CargoTomlFeatureResolveTest : CargoTomlResolveTestBase() {
+    fun `test feature of the same package`() = doResolveTest<TomlLiteral> {
+        toml("Cargo.toml", """
+            [features]
+            default = ["std"]
+                       #^ Cargo.toml
+            std = []
+        """)
+    }
+
+    fun `test optional dependency`() = doResolveTest<TomlLiteral> {
+        toml("Cargo.toml", """
+            [dependencies]
+            dep-lib-2 = { version = "0.0.1", optional = true }
+
+            [features]
+            default = ["dep:dep-lib-2"]
+                           #^ Cargo.toml
+        """)
+    }
+
+    fun `test dependency name in dependency feature`() = doResolveTest<TomlLiteral> {
+        toml("Cargo.toml", """
+            [dependencies.dep-lib-2]
+            version = "0.0.1"
+
+            [features]
+            default = ["dep-lib-2/foo"]
+                       #^ Cargo.toml
+        """)
+    }
+
+    fun `test unknown feature`() = doResolveTest<TomlLiteral> {
+        toml("Cargo.toml", """
+            [features]
+            default = ["std"]
+                       #^ unresolved
+        """)
+    }
+
+    fun `test feature of dependency`() = checkDependencyFeature("foo", """
+        [dependencies]
+        dep-lib-2 = "0.0.1"
+
+        [features]
+        default = ["dep-lib-2/foo"]
+                             #^
+    """)
+
+    fun `test weak feature of renamed dependency`() = checkDependencyFeature("bar", """
+        [dependencies]
+        renamed = { package = "dep-lib-2", version = "0.0.1", optional = true }
+
+        [features]
+        default = ["renamed?/bar"]
+                            #^
+    """)
+
+    /** Features of dependencies are declared in other files, so [doResolveTest] can't be used */
+    private fun checkDependencyFeature(expectedFeature: String, code: String) {
+        fileTree {
+            toml("Cargo.toml", code)
+            dir("dep-lib-2") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "dep-lib-2"
+                    version = "0.0.1"
+
+                    [features]
+                    foo = []
+                    bar = ["foo"]
+                """)
+            }
+        }.createAndOpenFileWithCaretMarker()
+
+        val (literal, _, offset) = findElementWithDataAndOffsetInEditor<TomlLiteral>()
+        val resolved = literal.checkedResolve(offset) as? TomlKey ?: error("Should resolve to a feature key")
+        assertEquals(expectedFeature, resolved.text)
+        check(resolved.containingFile.virtualFile.path.endsWith("dep-lib-2/Cargo.toml")) {
+            "Should resolve to `dep-lib-2/Cargo.toml`, was ${resolved.containingFile.virtualFile.path} instead"
+        }
+    }
+}
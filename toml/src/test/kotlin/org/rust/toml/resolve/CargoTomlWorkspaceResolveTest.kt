@@ -0,0 +1,187 @@
+/*
+ * Use of this source code is governed by the MIT license that can be
+ * found in the LICENSE file.
+ */
+
+package org.rust.toml.resolve
+
+import com.intellij.psi.PsiElement
+import org.intellij.lang.annotations.Language
+import org.rust.FileTreeBuilder
+import org.rust.RsTestBase
+import org.rust.fileTree
+import org.rust.lang.core.resolve.checkedResolve
+import org.rust.lang.core.resolve.findReference
+import org.rust.toml.dependencies
+import org.toml.lang.psi.TomlFile
+import org.toml.lang.psi.TomlKey
+import org.toml.lang.psi.TomlLiteral
+
+/** Inherited keys are declared in the root manifest, so [CargoTomlResolveTestBase.doResolveTest] can't be used */
+class CargoTomlWorkspaceResolveTest : RsTestBase() {
+    fun `test inherited dependency`() = checkResolve("serde", """
+        [dependencies]
+        serde = { workspace = true }
+                              #^
+    """)
+
+    fun `test inherited specific dependency`() = checkResolve("serde", """
+        [dev-dependencies.serde]
+        workspace = true
+                    #^
+    """)
+
+    fun `test dependency name of inherited dependency`() = checkResolve("serde", """
+        [dependencies]
+        serde = { workspace = true, features = ["rc"] }
+        #^
+    """, TomlKey::class.java)
+
+    fun `test dependency declared as workspace table`() = checkResolve("rand", """
+        [dependencies]
+        rand.workspace = true
+                        #^
+    """)
+
+    fun `test inherited package field`() = checkResolve("version", """
+        [package]
+        name = "member"
+        version.workspace = true
+                           #^
+    """)
+
+    fun `test inherited package field in inline table`() = checkResolve("edition", """
+        [package]
+        name = "member"
+        edition = { workspace = true }
+                               #^
+    """)
+
+    fun `test inherited lints`() = checkResolve("lints", """
+        [lints]
+        workspace = true
+                    #^
+    """)
+
+    fun `test unknown dependency`() = checkUnresolved("""
+        [dependencies]
+        regex = { workspace = true }
+                              #^
+    """)
+
+    fun `test unknown package field`() = checkUnresolved("""
+        [package]
+        description.workspace = true
+                               #^
+    """)
+
+    fun `test explicit workspace path`() {
+        fileTree {
+            dir("root") {
+                toml("Cargo.toml", """
+                    [workspace]
+                    members = ["../member"]
+
+                    [workspace.package]
+                    version = "1.0.0"
+                """)
+            }
+            dir("member") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "member"
+                    workspace = "../root"
+                    version.workspace = true
+                                       #^
+                """)
+            }
+        }.createAndOpenFileWithCaretMarker()
+        checkResolvedKey("version", "root/Cargo.toml")
+    }
+
+    fun `test dependency inherited from workspace`() {
+        fileTree {
+            workspaceManifest()
+            dir("member") {
+                toml("Cargo.toml", """
+                    [package]
+                    name = "member"
+
+                    [dependencies]
+                    my-serde = { workspace = true, features = ["rc", "derive"], optional = true }
+                    #^
+                """)
+            }
+        }.createAndOpenFileWithCaretMarker()
+        val dependency = (myFixture.file as TomlFile).dependencies.single()
+        assertEquals("serde", dependency.packageName)
+        assertEquals("1.0", dependency.version)
+        assertTrue(dependency.isOptional)
+    }
+
+    private fun checkResolve(
+        expectedKey: String,
+        @Language("TOML") memberManifest: String,
+        elementClass: Class<out PsiElement> = TomlLiteral::class.java
+    ) {
+        configureMemberManifest(memberManifest)
+        val (element, _, offset) = findElementWithDataAndOffsetInEditor(elementClass, "^")
+        checkResolvedKey(expectedKey, "Cargo.toml", element, offset)
+    }
+
+    private fun checkUnresolved(@Language("TOML") memberManifest: String) {
+        configureMemberManifest(memberManifest)
+        val (literal, _, offset) = findElementWithDataAndOffsetInEditor<TomlLiteral>()
+        val reference = literal.findReference(offset) ?: error("Should have a reference")
+        assertNull(reference.resolve())
+    }
+
+    private fun checkResolvedKey(expectedKey: String, expectedManifestPath: String) {
+        val (literal, _, offset) = findElementWithDataAndOffsetInEditor<TomlLiteral>()
+        checkResolvedKey(expectedKey, expectedManifestPath, literal, offset)
+    }
+
+    private fun checkResolvedKey(
+        expectedKey: String,
+        expectedManifestPath: String,
+        element: PsiElement,
+        offset: Int
+    ) {
+        val resolved = element.checkedResolve(offset) as? TomlKey ?: error("Should resolve to a key")
+        assertEquals(expectedKey, resolved.text)
+        val path = resolved.containingFile.virtualFile.path
+        check(path.endsWith("/$expectedManifestPath") && !path.endsWith("member/Cargo.toml")) {
+            "Should resolve to `$expectedManifestPath`, was $path instead"
+        }
+    }
+
+    private fun configureMemberManifest(@Language("TOML") memberManifest: String) {
+        fileTree {
+            workspaceManifest()
+            dir("member") {
+                toml("Cargo.toml", memberManifest)
+            }
+        }.createAndOpenFileWithCaretMarker()
+    }
+
+    private fun FileTreeBuilder.workspaceManifest() {
+        toml("Cargo.toml", """
+            [workspace]
+            members = ["member"]
+
+            [workspace.package]
+            version = "1.0.0"
+            edition = "2018"
+
+            [workspace.dependencies]
+            serde = { version = "1.0", features = ["derive", "std"] }
+            my-serde = { package = "serde", version = "1.0", features = ["derive", "std"] }
+
+            [workspace.dependencies.rand]
+            version = "0.7"
+
+            [workspace.lints.rust]
+            unsafe_code = "forbid"
+        """)
+    }
+}